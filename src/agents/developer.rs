@@ -1,34 +1,103 @@
-use super::{Agent, AgentStatus};
+use super::{
+    orchestrator::{AgentCheckpoint, ProgressReporter},
+    quality_assurance::WORKSPACE_PATH_CONTEXT_KEY,
+    Agent, AgentStatus, OrchestratorHandle,
+};
 use crate::{
-    claude_code::{ClaudeCodeClient, CodeGenerationRequest, TaskAnalysis},
-    models::{AgentType, Task, TaskResult},
+    claude_code::{
+        ClaudeCodeClient, CodeGenerationRequest, CodeGenerationResult, GenerationEvent,
+        TaskAnalysis,
+    },
+    llm::CodeGenerationBackend,
+    models::{AgentType, FileChange, FileChangeKind, Task, TaskResult},
     Result, SpiralError,
 };
 // 🔧 UTILITY IMPORTS: Using extracted modules via 3-strikes abstraction rule
 use super::language_detection::{detect_language_from_context, extract_requirements_from_content};
-use super::task_utils::{build_enriched_context, create_failure_result, create_success_result};
+use super::task_utils::{
+    build_enriched_context, create_failure_result, create_success_result, session_key,
+};
 use async_trait::async_trait;
+use futures::StreamExt;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+/// Context flag asking the developer to delegate a QA run on its workspace when done
+pub const RUN_QA_CONTEXT_KEY: &str = "run_qa";
+
+/// Context flag asking for a plan of changes instead of an implementation
+pub const PLAN_ONLY_CONTEXT_KEY: &str = "plan_only";
+
 #[derive(Debug, Clone)]
 pub struct SoftwareDeveloperAgent {
     claude_client: ClaudeCodeClient,
+    /// Generates the code; Claude Code itself unless another backend is configured
+    backend: Arc<dyn CodeGenerationBackend>,
     status: AgentStatus,
 }
 
 impl SoftwareDeveloperAgent {
     pub fn new(claude_client: ClaudeCodeClient) -> Self {
         Self {
+            backend: Arc::new(claude_client.clone()),
             claude_client,
             status: AgentStatus::new(AgentType::SoftwareDeveloper),
         }
     }
 
+    /// Generate code with another backend; language detection and task analysis stay on
+    /// Claude Code
+    pub fn with_backend(mut self, backend: Arc<dyn CodeGenerationBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
     pub fn status(&self) -> &AgentStatus {
         &self.status
     }
 
+    /// 🌊 LIVE PROGRESS: Stream the generation and report what Claude is doing as it does it
+    /// Each tool call nudges the percentage between 20 and 85; the message carries the
+    /// latest text or tool call, which Discord and the task stream show as-is
+    async fn generate_with_progress(
+        &self,
+        request: CodeGenerationRequest,
+        progress: &ProgressReporter,
+    ) -> Result<CodeGenerationResult> {
+        let mut events = self.backend.generate_code_stream(request).await?;
+        let mut percent: u8 = 20;
+
+        while let Some(event) = events.next().await {
+            match event {
+                GenerationEvent::Completed(result) => return Ok(result),
+                GenerationEvent::Failed(reason) => {
+                    return Err(SpiralError::Agent { message: reason })
+                }
+                GenerationEvent::QuotaExceeded(breach) => {
+                    return Err(SpiralError::WorkspaceQuotaExceeded(breach))
+                }
+                GenerationEvent::Started { session_id } => {
+                    debug!("Claude Code session started: {}", session_id);
+                }
+                event => {
+                    if matches!(event, GenerationEvent::ToolUse { .. }) {
+                        percent = (percent + 5).min(85);
+                    }
+                    if let Some(summary) = event.summary() {
+                        progress
+                            .report_with_message(percent, "generating code", summary)
+                            .await;
+                    }
+                }
+            }
+        }
+
+        Err(SpiralError::Agent {
+            message: "Claude Code stream ended without a result".to_string(),
+        })
+    }
+
     /// 🎯 LANGUAGE DETECTION: Enhanced with local analysis + Claude Code intelligence
     /// DECISION: Hybrid approach - fast local detection with Claude Code fallback
     /// Why: Reduces API calls for obvious cases while maintaining accuracy for complex scenarios
@@ -97,7 +166,19 @@ impl SoftwareDeveloperAgent {
         let language = self.detect_language_from_task(task).await?;
 
         // 📝 REQUIREMENT EXTRACTION: Using centralized utility function
-        let requirements = extract_requirements_from_content(&task.content, &task.context);
+        let mut requirements = extract_requirements_from_content(&task.content, &task.context);
+
+        // ♻️ RESUME: An interrupted attempt left files in the same session workspace
+        let resumed = AgentCheckpoint::from_context(task);
+        if resumed.is_some() {
+            requirements.push(
+                "Resume the interrupted attempt: build on the files already in the workspace instead of starting over"
+                    .to_string(),
+            );
+        }
+        let session_id = resumed
+            .and_then(|state| state.session_id)
+            .unwrap_or_else(|| session_key(task));
 
         let existing_code = task.context.get("existing_code").cloned();
 
@@ -122,10 +203,53 @@ impl SoftwareDeveloperAgent {
             context,
             existing_code,
             requirements,
-            session_id: Some(task.id.clone()), // Use task ID as session ID for continuity
+            session_id: Some(session_id), // Shared, resumed or the task's own session
+            dry_run: task.context.get(PLAN_ONLY_CONTEXT_KEY).map(String::as_str) == Some("true"),
         })
     }
 
+    /// Hand the generated workspace to QA when the task asks for it
+    /// Delegation failures are logged rather than failing the development task
+    async fn delegate_qa(
+        &self,
+        task: &Task,
+        workspace_path: &str,
+        orchestrator: &OrchestratorHandle,
+    ) -> Option<String> {
+        if task.context.get(RUN_QA_CONTEXT_KEY).map(String::as_str) != Some("true") {
+            return None;
+        }
+        // Text-only backends leave no workspace to test
+        if workspace_path.is_empty() {
+            info!(
+                "Skipping QA for task {}: no workspace was produced",
+                task.id
+            );
+            return None;
+        }
+
+        let qa_task = Task::new(
+            AgentType::QualityAssurance,
+            format!(
+                "Run the test suite for the code generated by task {}",
+                task.id
+            ),
+            task.priority.clone(),
+        )
+        .with_context(
+            WORKSPACE_PATH_CONTEXT_KEY.to_string(),
+            workspace_path.to_string(),
+        );
+
+        match orchestrator.delegate(qa_task).await {
+            Ok(qa_task_id) => Some(qa_task_id),
+            Err(e) => {
+                warn!("Could not delegate QA for task {}: {}", task.id, e);
+                None
+            }
+        }
+    }
+
     /// ✅ SUCCESS RESULT CREATOR: Formats Claude Code generation results
     /// REFACTORING: Now uses standardized result builder from task_utils
     fn create_success_result(
@@ -146,7 +270,20 @@ impl SoftwareDeveloperAgent {
             .collect();
 
         // Format output based on the type of request
-        let output = if task.content.starts_with("INFORMATION QUERY:")
+        let output = if code_result.dry_run {
+            // 📋 DRY RUN: The explanation is the plan; nothing was written
+            if !code_result.changes.is_empty() {
+                warn!(
+                    "Dry run for task {} still changed {} file(s)",
+                    task.id,
+                    code_result.changes.len()
+                );
+            }
+            format!(
+                "Plan (dry run, no files changed):\n\n{}",
+                code_result.explanation
+            )
+        } else if task.content.starts_with("INFORMATION QUERY:")
             || task.content.starts_with("GREETING:")
             || task.content.starts_with("HELP REQUEST:")
         {
@@ -180,6 +317,10 @@ impl SoftwareDeveloperAgent {
 
         // 📈 AGENT-SPECIFIC METADATA: Development-focused metrics
         let mut metadata = HashMap::new();
+        metadata.insert(
+            WORKSPACE_PATH_CONTEXT_KEY.to_string(),
+            code_result.workspace_path,
+        );
         metadata.insert("language".to_string(), code_result.language);
         metadata.insert(
             "code_length".to_string(),
@@ -193,16 +334,19 @@ impl SoftwareDeveloperAgent {
             "has_tests".to_string(),
             code_result.code.to_lowercase().contains("test").to_string(),
         );
+        metadata.insert("dry_run".to_string(), code_result.dry_run.to_string());
 
         // 🔧 STANDARDIZED RESULT: Using utility function for consistency
-        create_success_result(
+        let mut result = create_success_result(
             task,
             AgentType::SoftwareDeveloper,
             output,
             files_created,
             files_modified,
             Some(metadata),
-        )
+        );
+        result.changes = code_result.changes;
+        result
     }
 
     /// ❌ FAILURE RESULT CREATOR: Standardized error result formatting
@@ -216,17 +360,38 @@ impl SoftwareDeveloperAgent {
         );
         metadata.insert("claude_code_integration".to_string(), "true".to_string());
 
+        // 📦 PARTIAL RESULT: A run stopped by the workspace quota keeps what it wrote
+        let partial_output = match error {
+            SpiralError::WorkspaceQuotaExceeded(breach) => {
+                metadata.insert("workspace_path".to_string(), breach.workspace.clone());
+                Some(breach.partial_output())
+            }
+            _ => None,
+        };
+
         // 🔧 STANDARDIZED RESULT: Using utility function for consistency
         create_failure_result(
             task,
             AgentType::SoftwareDeveloper,
             error,
-            None, // No partial output for developer agent failures
+            partial_output,
             Some(metadata),
         )
     }
 }
 
+/// One changed file for Discord, e.g. "`src/lib.rs` modified (+3 −1)"
+fn describe_change(change: &FileChange) -> String {
+    match change.kind {
+        FileChangeKind::Created => format!("`{}` created (+{})", change.path, change.lines_added),
+        FileChangeKind::Modified => format!(
+            "`{}` modified (+{} −{})",
+            change.path, change.lines_added, change.lines_removed
+        ),
+        FileChangeKind::Deleted => format!("`{}` deleted", change.path),
+    }
+}
+
 #[async_trait]
 impl Agent for SoftwareDeveloperAgent {
     fn agent_type(&self) -> AgentType {
@@ -245,7 +410,7 @@ impl Agent for SoftwareDeveloperAgent {
         matches!(task.agent_type, AgentType::SoftwareDeveloper)
     }
 
-    async fn execute(&self, task: Task) -> Result<TaskResult> {
+    async fn execute(&self, task: Task, orchestrator: OrchestratorHandle) -> Result<TaskResult> {
         info!("SoftwareDeveloperAgent executing task: {}", task.id);
 
         if self.status.is_busy {
@@ -259,7 +424,9 @@ impl Agent for SoftwareDeveloperAgent {
         }
 
         let start_time = std::time::Instant::now();
+        let progress = orchestrator.progress();
 
+        progress.report(5, "analyzing request").await;
         let code_request = match self.build_code_generation_request(&task).await {
             Ok(request) => request,
             Err(e) => {
@@ -273,15 +440,44 @@ impl Agent for SoftwareDeveloperAgent {
             code_request.language
         );
 
-        match self.claude_client.generate_code(code_request).await {
+        let mut notes = AgentCheckpoint::from_context(&task)
+            .map(|state| state.notes)
+            .unwrap_or_default();
+        notes.push(format!("generating {} code", code_request.language));
+        orchestrator
+            .checkpoint(AgentCheckpoint {
+                workspace_path: None,
+                session_id: code_request.session_id.clone(),
+                notes,
+            })
+            .await;
+
+        progress
+            .report_with_message(20, "generating code", &code_request.language)
+            .await;
+        match self.generate_with_progress(code_request, progress).await {
             Ok(code_result) => {
+                progress.report(90, "finalizing").await;
                 let execution_time = start_time.elapsed().as_secs_f64();
                 info!(
                     "Successfully generated code for task: {} in {:.2}s",
                     task.id, execution_time
                 );
 
-                Ok(self.create_success_result(&task, code_result))
+                let workspace_path = code_result.workspace_path.clone();
+                let dry_run = code_result.dry_run;
+                let mut result = self.create_success_result(&task, code_result);
+                // A plan leaves nothing for QA to test
+                if dry_run {
+                    return Ok(result);
+                }
+                if let Some(qa_task_id) = self
+                    .delegate_qa(&task, &workspace_path, &orchestrator)
+                    .await
+                {
+                    result.metadata.insert("qa_task_id".to_string(), qa_task_id);
+                }
+                Ok(result)
             }
             Err(e) => {
                 warn!("Code generation failed for task {}: {}", task.id, e);
@@ -335,6 +531,14 @@ impl Agent for SoftwareDeveloperAgent {
                 "c".to_string(),
             ],
             required_tools: vec!["claude_code_client".to_string()],
+            task_categories: vec![
+                "implementation".to_string(),
+                "bugfix".to_string(),
+                "refactoring".to_string(),
+                "testing".to_string(),
+                "code".to_string(),
+            ],
+            max_complexity: crate::models::TaskComplexity::High,
         }
     }
 
@@ -342,7 +546,10 @@ impl Agent for SoftwareDeveloperAgent {
     /// Why: Developer agent knows best how to present code output
     /// Alternative: Generic formatting (rejected: loses context-specific presentation)
     fn format_response(&self, result: &TaskResult) -> String {
-        const MAX_OUTPUT_RESPONSE: usize = 1500;
+        // Results are shown in an embed, whose 4096 character description leaves room
+        // for this and the lines added below
+        const MAX_OUTPUT_RESPONSE: usize = 3500;
+        const MAX_LISTED_CHANGES: usize = 10;
 
         match &result.result {
             crate::models::TaskExecutionResult::Success {
@@ -357,7 +564,12 @@ impl Agent for SoftwareDeveloperAgent {
                 if output.starts_with("Generated") && output.contains("code:") {
                     // Show full code output
                     if output.len() > MAX_OUTPUT_RESPONSE {
-                        summary.push_str(&output[..MAX_OUTPUT_RESPONSE]);
+                        // Back off to a character boundary, so multi-byte text can't panic
+                        let mut end = MAX_OUTPUT_RESPONSE;
+                        while !output.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        summary.push_str(&output[..end]);
                         summary.push_str("\n\n... (output truncated for Discord limits)");
                     } else {
                         summary.push_str(output);
@@ -367,8 +579,20 @@ impl Agent for SoftwareDeveloperAgent {
                     summary.push_str(output);
                 }
 
-                // Add file information if any
-                if !files_created.is_empty() || !files_modified.is_empty() {
+                // 📁 CHANGED FILES: Name each file with its line counts when the run
+                // recorded its changes; the diffs themselves are in the API result
+                if !result.changes.is_empty() {
+                    summary.push_str("\n\n**📁 Files:**\n");
+                    for change in result.changes.iter().take(MAX_LISTED_CHANGES) {
+                        summary.push_str(&format!("• {}\n", describe_change(change)));
+                    }
+                    if result.changes.len() > MAX_LISTED_CHANGES {
+                        summary.push_str(&format!(
+                            "• ...and {} more\n",
+                            result.changes.len() - MAX_LISTED_CHANGES
+                        ));
+                    }
+                } else if !files_created.is_empty() || !files_modified.is_empty() {
                     summary.push_str("\n\n**📁 Files:**\n");
                     if !files_created.is_empty() {
                         summary.push_str(&format!("• Created: {} files\n", files_created.len()));
@@ -386,3 +610,35 @@ impl Agent for SoftwareDeveloperAgent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::TaskExecutionResult;
+
+    #[tokio::test]
+    async fn test_format_response_truncates_on_a_char_boundary() {
+        let client = ClaudeCodeClient::new(Config::test_config().claude_code)
+            .await
+            .unwrap();
+        let agent = SoftwareDeveloperAgent::new(client);
+        // "é" is two bytes, so the cut would land inside one
+        let output = format!("Generated rust code:\n{}", "é".repeat(2000));
+        let result = TaskResult {
+            task_id: "task-1".to_string(),
+            agent_type: AgentType::SoftwareDeveloper,
+            result: TaskExecutionResult::Success {
+                output,
+                files_created: vec![],
+                files_modified: vec![],
+            },
+            metadata: HashMap::new(),
+            completed_at: chrono::Utc::now(),
+            changes: Vec::new(),
+        };
+
+        let response = agent.format_response(&result);
+        assert!(response.contains("... (output truncated for Discord limits)"));
+    }
+}
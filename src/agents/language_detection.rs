@@ -7,6 +7,7 @@
 //! AUDIT: Verify all language mappings are comprehensive and consistent
 
 use std::collections::HashMap;
+use std::path::Path;
 
 /// 🗺️ LANGUAGE MAPPING: Centralized file extension to language mapping
 /// Inline reasoning: Single source of truth prevents inconsistent language detection
@@ -47,6 +48,28 @@ pub fn language_from_project_type(project_type: &str) -> String {
     }
 }
 
+/// 📦 PROJECT MARKER DETECTION: Detect project type from build files in a directory
+/// Inline reasoning: Build manifests are the most reliable signal for how to build and test
+/// Returns descriptors understood by language_from_project_type
+pub fn project_type_from_directory(dir: &Path) -> Option<&'static str> {
+    // 🎯 PRIORITY ORDER: Spiral Core ecosystem first, then common toolchains
+    const MARKERS: &[(&str, &str)] = &[
+        ("Cargo.toml", "cargo"),
+        ("package.json", "npm"),
+        ("go.mod", "go"),
+        ("pyproject.toml", "pip"),
+        ("setup.py", "pip"),
+        ("pom.xml", "maven"),
+        ("build.gradle", "gradle"),
+        ("build.gradle.kts", "gradle"),
+    ];
+
+    MARKERS
+        .iter()
+        .find(|(marker, _)| dir.join(marker).is_file())
+        .map(|(_, project_type)| *project_type)
+}
+
 /// 📝 CONTENT ANALYSIS: Detect language from textual content patterns
 /// Inline reasoning: Heuristic detection when file/project context unavailable
 /// Audit: These patterns may need refinement based on detection accuracy
@@ -173,6 +196,19 @@ mod tests {
         assert_eq!(language_from_project_type("unknown"), "rust"); // fallback
     }
 
+    #[test]
+    fn test_project_type_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(project_type_from_directory(dir.path()), None);
+
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        assert_eq!(project_type_from_directory(dir.path()), Some("npm"));
+
+        // Cargo wins when a crate also ships JavaScript tooling
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(project_type_from_directory(dir.path()), Some("cargo"));
+    }
+
     #[test]
     fn test_language_from_content() {
         assert_eq!(language_from_content("use tokio in rust"), "rust");
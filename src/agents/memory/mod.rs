@@ -0,0 +1,400 @@
+//! 🧠 AGENT MEMORY: What agents know from earlier tasks
+//!
+//! Agents are stateless between tasks. Memories (summaries of prior tasks, project
+//! conventions, user preferences) are stored per agent type and scope, then rendered
+//! into the context of later tasks in the same scope.
+
+pub mod sqlite;
+pub use sqlite::SqliteAgentMemory;
+
+use crate::{
+    models::{AgentType, Task},
+    validation::TaskContentValidator,
+    Result,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Context key carrying rendered memories into the task
+pub const AGENT_MEMORY_CONTEXT_KEY: &str = "agent_memory";
+
+/// Context keys naming a task's memory scopes; the Discord bot sets them from the
+/// message, the API from the caller's key
+pub const MEMORY_SCOPE_CONTEXT_KEYS: &[&str] = &[
+    "user_id",
+    "discord_author_id",
+    "guild_id",
+    "discord_guild_id",
+    "workspace",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryKind {
+    /// What an earlier task asked for and what came of it
+    TaskSummary,
+    /// How the project does things, e.g. naming or error handling rules
+    Convention,
+    /// How the user wants things done
+    Preference,
+}
+
+impl MemoryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaskSummary => "task_summary",
+            Self::Convention => "convention",
+            Self::Preference => "preference",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "task_summary" => Some(Self::TaskSummary),
+            "convention" => Some(Self::Convention),
+            "preference" => Some(Self::Preference),
+            _ => None,
+        }
+    }
+}
+
+/// Who or what a memory belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum MemoryScope {
+    User(String),
+    Guild(String),
+    Workspace(String),
+}
+
+impl MemoryScope {
+    pub fn type_label(&self) -> &'static str {
+        match self {
+            Self::User(_) => "user",
+            Self::Guild(_) => "guild",
+            Self::Workspace(_) => "workspace",
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Self::User(id) | Self::Guild(id) | Self::Workspace(id) => id,
+        }
+    }
+
+    pub fn from_parts(type_label: &str, id: &str) -> Option<Self> {
+        match type_label {
+            "user" => Some(Self::User(id.to_string())),
+            "guild" => Some(Self::Guild(id.to_string())),
+            "workspace" => Some(Self::Workspace(id.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Scopes a task belongs to, from API context keys or the Discord ones
+    pub fn for_task(task: &Task) -> Vec<Self> {
+        let first = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| task.context.get(*key))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+
+        [
+            first(&["user_id", "discord_author_id"]).map(Self::User),
+            first(&["guild_id", "discord_guild_id"]).map(Self::Guild),
+            first(&["workspace"]).map(Self::Workspace),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MemoryEntry {
+    pub id: String,
+    pub agent_type: AgentType,
+    pub scope: MemoryScope,
+    pub kind: MemoryKind,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MemoryEntry {
+    pub fn new(
+        agent_type: AgentType,
+        scope: MemoryScope,
+        kind: MemoryKind,
+        content: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            agent_type,
+            scope,
+            kind,
+            content,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Storage trait with in-memory and SQLite implementations
+/// Why: Tests and single-run setups need no files, while long-lived deployments keep
+/// what agents learned across restarts
+/// Alternative: JSON file like the schedule store (rejected: memories grow without bound
+/// and are queried by scope, which a flat file rewrites in full on every task)
+#[async_trait]
+pub trait AgentMemory: Send + Sync {
+    async fn remember(&self, entry: MemoryEntry) -> Result<()>;
+
+    /// The agent's memories in any of the scopes, newest first
+    async fn recall(
+        &self,
+        agent_type: &AgentType,
+        scopes: &[MemoryScope],
+        limit: usize,
+    ) -> Result<Vec<MemoryEntry>>;
+
+    /// Keep only the newest `keep` memories of a kind in one scope, returning how many were removed
+    async fn prune(
+        &self,
+        agent_type: &AgentType,
+        scope: &MemoryScope,
+        kind: MemoryKind,
+        keep: usize,
+    ) -> Result<usize>;
+
+    /// A single memory by ID
+    async fn entry(&self, id: &str) -> Result<Option<MemoryEntry>>;
+
+    /// Remove a single memory, returning whether it existed
+    async fn forget(&self, id: &str) -> Result<bool>;
+}
+
+/// Memory that lives as long as the process
+#[derive(Default)]
+pub struct InMemoryAgentMemory {
+    entries: RwLock<HashMap<String, MemoryEntry>>,
+}
+
+impl InMemoryAgentMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AgentMemory for InMemoryAgentMemory {
+    async fn remember(&self, entry: MemoryEntry) -> Result<()> {
+        self.entries.write().await.insert(entry.id.clone(), entry);
+        Ok(())
+    }
+
+    async fn recall(
+        &self,
+        agent_type: &AgentType,
+        scopes: &[MemoryScope],
+        limit: usize,
+    ) -> Result<Vec<MemoryEntry>> {
+        let entries = self.entries.read().await;
+        let mut matching: Vec<MemoryEntry> = entries
+            .values()
+            .filter(|entry| &entry.agent_type == agent_type && scopes.contains(&entry.scope))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+        matching.truncate(limit);
+        Ok(matching)
+    }
+
+    async fn prune(
+        &self,
+        agent_type: &AgentType,
+        scope: &MemoryScope,
+        kind: MemoryKind,
+        keep: usize,
+    ) -> Result<usize> {
+        let mut entries = self.entries.write().await;
+        let mut matching: Vec<(DateTime<Utc>, String)> = entries
+            .values()
+            .filter(|entry| {
+                &entry.agent_type == agent_type && &entry.scope == scope && entry.kind == kind
+            })
+            .map(|entry| (entry.created_at, entry.id.clone()))
+            .collect();
+        matching.sort_by_key(|(created_at, _)| std::cmp::Reverse(*created_at));
+
+        let stale: Vec<String> = matching.into_iter().skip(keep).map(|(_, id)| id).collect();
+        for id in &stale {
+            entries.remove(id);
+        }
+        Ok(stale.len())
+    }
+
+    async fn entry(&self, id: &str) -> Result<Option<MemoryEntry>> {
+        Ok(self.entries.read().await.get(id).cloned())
+    }
+
+    async fn forget(&self, id: &str) -> Result<bool> {
+        Ok(self.entries.write().await.remove(id).is_some())
+    }
+}
+
+/// Render memories for the task context, within the byte budget
+/// Conventions and preferences go first since they apply to every task in the scope.
+/// Entries the Claude request validator would reject are skipped, so one bad memory
+/// cannot block every later task in its scope.
+pub fn render_for_context(entries: &[MemoryEntry], max_bytes: usize) -> Option<String> {
+    let validator = TaskContentValidator::default();
+    let mut ordered: Vec<&MemoryEntry> = entries.iter().collect();
+    ordered.sort_by_key(|entry| entry.kind == MemoryKind::TaskSummary);
+
+    let mut rendered = String::from("Remembered from earlier tasks:");
+    let mut included = 0;
+    for entry in ordered {
+        let line = format!("\n- [{}] {}", entry.kind.as_str(), entry.content);
+        if rendered.len() + line.len() > max_bytes {
+            continue;
+        }
+        if validator
+            .validate_and_sanitize_context_value(&line)
+            .is_err()
+        {
+            continue;
+        }
+        rendered.push_str(&line);
+        included += 1;
+    }
+
+    (included > 0).then_some(rendered)
+}
+
+/// The task's recalled memories as a Claude request context entry, for agents that
+/// build their request context by hand instead of copying the task's
+pub fn memory_context(task: &Task) -> Option<(String, String)> {
+    task.context
+        .get(AGENT_MEMORY_CONTEXT_KEY)
+        .map(|rendered| (AGENT_MEMORY_CONTEXT_KEY.to_string(), rendered.clone()))
+}
+
+/// Summary of a finished task, short enough that several fit in one context value
+pub fn summarize_task(task: &Task, output: &str, max_chars: usize) -> String {
+    let half = max_chars / 2;
+    let request: String = task.content.chars().take(half).collect();
+    let outcome: String = output
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .chars()
+        .take(half)
+        .collect();
+    format!("Asked: {request} | Result: {outcome}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    fn entry(kind: MemoryKind, content: &str) -> MemoryEntry {
+        MemoryEntry::new(
+            AgentType::SoftwareDeveloper,
+            MemoryScope::User("42".to_string()),
+            kind,
+            content.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_recall_and_prune() {
+        let memory = InMemoryAgentMemory::new();
+        let user = MemoryScope::User("42".to_string());
+        let mut older = entry(MemoryKind::TaskSummary, "first");
+        older.created_at = Utc::now() - chrono::Duration::minutes(5);
+        memory.remember(older).await.unwrap();
+        memory
+            .remember(entry(MemoryKind::TaskSummary, "second"))
+            .await
+            .unwrap();
+        memory
+            .remember(MemoryEntry::new(
+                AgentType::ProjectManager,
+                user.clone(),
+                MemoryKind::Preference,
+                "other agent".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let recalled = memory
+            .recall(
+                &AgentType::SoftwareDeveloper,
+                std::slice::from_ref(&user),
+                10,
+            )
+            .await
+            .unwrap();
+        let contents: Vec<_> = recalled.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["second", "first"]);
+
+        let removed = memory
+            .prune(
+                &AgentType::SoftwareDeveloper,
+                &user,
+                MemoryKind::TaskSummary,
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        let recalled = memory
+            .recall(&AgentType::SoftwareDeveloper, &[user], 10)
+            .await
+            .unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].content, "second");
+    }
+
+    #[test]
+    fn test_scopes_from_discord_context() {
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Add logging".to_string(),
+            Priority::Medium,
+        )
+        .with_context("discord_author_id".to_string(), "42".to_string())
+        .with_context("discord_guild_id".to_string(), "7".to_string());
+
+        assert_eq!(
+            MemoryScope::for_task(&task),
+            vec![
+                MemoryScope::User("42".to_string()),
+                MemoryScope::Guild("7".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_puts_conventions_first_and_fits_budget() {
+        let entries = vec![
+            entry(MemoryKind::TaskSummary, "Asked: add a parser"),
+            entry(MemoryKind::Convention, "Errors use thiserror"),
+            entry(MemoryKind::TaskSummary, &"x".repeat(2000)),
+        ];
+
+        let rendered = render_for_context(&entries, 1000).unwrap();
+        assert!(rendered.len() <= 1000);
+        let convention = rendered.find("[convention]").unwrap();
+        let summary = rendered.find("[task_summary]").unwrap();
+        assert!(convention < summary);
+        assert!(!rendered.contains("xxxx"));
+
+        assert!(render_for_context(&[], 1000).is_none());
+    }
+}
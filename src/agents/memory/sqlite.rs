@@ -0,0 +1,334 @@
+use super::{AgentMemory, MemoryEntry, MemoryKind, MemoryScope};
+use crate::{models::AgentType, Result, SpiralError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value, Connection};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS agent_memory (
+        id TEXT PRIMARY KEY,
+        agent_type TEXT NOT NULL,
+        scope_type TEXT NOT NULL,
+        scope_id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at_us INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS agent_memory_scope
+        ON agent_memory (agent_type, scope_type, scope_id, created_at_us);
+";
+
+/// Memory persisted to a SQLite database file
+/// 🏗️ ARCHITECTURE DECISION: One connection behind a std Mutex, used from spawn_blocking
+/// Why: rusqlite is synchronous and memory writes happen once per task, so a pool buys nothing
+/// Alternative: tokio Mutex held across queries (rejected: blocks a runtime worker on disk I/O)
+#[derive(Clone)]
+pub struct SqliteAgentMemory {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteAgentMemory {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let connection = Connection::open(path).map_err(|e| {
+            SpiralError::SystemError(format!(
+                "Failed to open agent memory at {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::with_connection(connection)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        let connection = Connection::open_in_memory().map_err(storage_error)?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    async fn with_db<T, F>(&self, operation: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = connection
+                .lock()
+                .map_err(|_| SpiralError::SystemError("Agent memory lock poisoned".to_string()))?;
+            operation(&connection).map_err(storage_error)
+        })
+        .await
+        .map_err(|e| SpiralError::SystemError(format!("Agent memory task failed: {e}")))?
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> SpiralError {
+    SpiralError::SystemError(format!("Agent memory storage error: {e}"))
+}
+
+type MemoryRow = (String, String, String, String, String, String, i64);
+
+/// Rows written by a newer build (unknown agent or kind) are skipped rather than failing recall
+fn entry_from_row(row: MemoryRow) -> Option<MemoryEntry> {
+    let (id, agent_type, scope_type, scope_id, kind, content, created_at_us) = row;
+    let parsed = (|| {
+        Some(MemoryEntry {
+            agent_type: agent_type.parse::<AgentType>().ok()?,
+            scope: MemoryScope::from_parts(&scope_type, &scope_id)?,
+            kind: MemoryKind::from_label(&kind)?,
+            created_at: DateTime::<Utc>::from_timestamp_micros(created_at_us)?,
+            id: id.clone(),
+            content,
+        })
+    })();
+    if parsed.is_none() {
+        warn!("Skipping unreadable agent memory {}", id);
+    }
+    parsed
+}
+
+#[async_trait]
+impl AgentMemory for SqliteAgentMemory {
+    async fn remember(&self, entry: MemoryEntry) -> Result<()> {
+        self.with_db(move |db| {
+            db.execute(
+                "INSERT OR REPLACE INTO agent_memory
+                    (id, agent_type, scope_type, scope_id, kind, content, created_at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    entry.id,
+                    format!("{:?}", entry.agent_type),
+                    entry.scope.type_label(),
+                    entry.scope.id(),
+                    entry.kind.as_str(),
+                    entry.content,
+                    entry.created_at.timestamp_micros(),
+                ],
+            )
+            .map(|_| ())
+        })
+        .await
+    }
+
+    async fn recall(
+        &self,
+        agent_type: &AgentType,
+        scopes: &[MemoryScope],
+        limit: usize,
+    ) -> Result<Vec<MemoryEntry>> {
+        if scopes.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let scope_clause = vec!["(scope_type = ? AND scope_id = ?)"; scopes.len()].join(" OR ");
+        let sql = format!(
+            "SELECT id, agent_type, scope_type, scope_id, kind, content, created_at_us
+             FROM agent_memory
+             WHERE agent_type = ? AND ({scope_clause})
+             ORDER BY created_at_us DESC
+             LIMIT ?"
+        );
+        let mut values = vec![Value::Text(format!("{agent_type:?}"))];
+        for scope in scopes {
+            values.push(Value::Text(scope.type_label().to_string()));
+            values.push(Value::Text(scope.id().to_string()));
+        }
+        values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+
+        let rows = self
+            .with_db(move |db| {
+                let mut statement = db.prepare(&sql)?;
+                let rows = statement
+                    .query_map(params_from_iter(values), |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                            row.get(5)?,
+                            row.get(6)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<MemoryRow>>>()?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(rows.into_iter().filter_map(entry_from_row).collect())
+    }
+
+    async fn prune(
+        &self,
+        agent_type: &AgentType,
+        scope: &MemoryScope,
+        kind: MemoryKind,
+        keep: usize,
+    ) -> Result<usize> {
+        let agent_type = format!("{agent_type:?}");
+        let scope_type = scope.type_label();
+        let scope_id = scope.id().to_string();
+        let keep = i64::try_from(keep).unwrap_or(i64::MAX);
+
+        self.with_db(move |db| {
+            db.execute(
+                "DELETE FROM agent_memory
+                 WHERE agent_type = ?1 AND scope_type = ?2 AND scope_id = ?3 AND kind = ?4
+                   AND id NOT IN (
+                       SELECT id FROM agent_memory
+                       WHERE agent_type = ?1 AND scope_type = ?2 AND scope_id = ?3 AND kind = ?4
+                       ORDER BY created_at_us DESC
+                       LIMIT ?5
+                   )",
+                params![agent_type, scope_type, scope_id, kind.as_str(), keep],
+            )
+        })
+        .await
+    }
+
+    async fn entry(&self, id: &str) -> Result<Option<MemoryEntry>> {
+        let id = id.to_string();
+        let rows = self
+            .with_db(move |db| {
+                let mut statement = db.prepare(
+                    "SELECT id, agent_type, scope_type, scope_id, kind, content, created_at_us
+                     FROM agent_memory
+                     WHERE id = ?1",
+                )?;
+                let rows = statement
+                    .query_map([id], |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                            row.get(5)?,
+                            row.get(6)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<MemoryRow>>>()?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(rows.into_iter().find_map(entry_from_row))
+    }
+
+    async fn forget(&self, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        self.with_db(move |db| db.execute("DELETE FROM agent_memory WHERE id = ?1", [id]))
+            .await
+            .map(|removed| removed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_memory_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.db");
+        let workspace = MemoryScope::Workspace("/projects/api".to_string());
+        let guild = MemoryScope::Guild("7".to_string());
+
+        {
+            let memory = SqliteAgentMemory::open(&path).unwrap();
+            let mut older = MemoryEntry::new(
+                AgentType::SoftwareDeveloper,
+                workspace.clone(),
+                MemoryKind::TaskSummary,
+                "Asked: add auth".to_string(),
+            );
+            older.created_at = Utc::now() - chrono::Duration::minutes(5);
+            memory.remember(older).await.unwrap();
+            memory
+                .remember(MemoryEntry::new(
+                    AgentType::SoftwareDeveloper,
+                    guild.clone(),
+                    MemoryKind::Convention,
+                    "Use tabs".to_string(),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let memory = SqliteAgentMemory::open(&path).unwrap();
+        let recalled = memory
+            .recall(
+                &AgentType::SoftwareDeveloper,
+                &[workspace.clone(), guild],
+                10,
+            )
+            .await
+            .unwrap();
+        let contents: Vec<_> = recalled.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["Use tabs", "Asked: add auth"]);
+        assert_eq!(recalled[1].scope, workspace);
+
+        assert!(memory
+            .recall(&AgentType::ProjectManager, &[workspace], 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_prune_and_forget() {
+        let memory = SqliteAgentMemory::open_in_memory().unwrap();
+        let user = MemoryScope::User("42".to_string());
+        let mut ids = Vec::new();
+        for minutes_ago in [3, 2, 1] {
+            let mut entry = MemoryEntry::new(
+                AgentType::SoftwareDeveloper,
+                user.clone(),
+                MemoryKind::TaskSummary,
+                format!("{minutes_ago} minutes ago"),
+            );
+            entry.created_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+            ids.push(entry.id.clone());
+            memory.remember(entry).await.unwrap();
+        }
+
+        let removed = memory
+            .prune(
+                &AgentType::SoftwareDeveloper,
+                &user,
+                MemoryKind::TaskSummary,
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        assert_eq!(
+            memory
+                .entry(&ids[2])
+                .await
+                .unwrap()
+                .map(|entry| entry.scope),
+            Some(user.clone())
+        );
+        assert!(memory.forget(&ids[2]).await.unwrap());
+        assert!(!memory.forget(&ids[2]).await.unwrap());
+        assert!(memory.entry(&ids[2]).await.unwrap().is_none());
+
+        let recalled = memory
+            .recall(&AgentType::SoftwareDeveloper, &[user], 10)
+            .await
+            .unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].content, "2 minutes ago");
+    }
+}
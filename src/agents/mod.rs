@@ -1,13 +1,18 @@
 pub mod developer;
 pub mod orchestrator;
 pub mod project_manager;
+pub mod quality_assurance;
 // 🔧 UTILITY MODULES: Extracted via 3-strikes abstraction rule
 pub mod language_detection;
+pub mod memory;
 pub mod task_utils;
 
 pub use developer::SoftwareDeveloperAgent;
 pub use orchestrator::AgentOrchestrator;
 pub use project_manager::ProjectManagerAgent;
+pub use quality_assurance::QualityAssuranceAgent;
+
+pub use orchestrator::OrchestratorHandle;
 
 use crate::{
     claude_code::TaskAnalysis,
@@ -15,6 +20,7 @@ use crate::{
     Result,
 };
 use async_trait::async_trait;
+use std::collections::BTreeSet;
 
 #[async_trait]
 pub trait Agent: Send + Sync {
@@ -23,7 +29,8 @@ pub trait Agent: Send + Sync {
     fn description(&self) -> String;
 
     async fn can_handle(&self, task: &Task) -> bool;
-    async fn execute(&self, task: Task) -> Result<TaskResult>;
+    /// Execute the task; the handle lets the agent delegate follow-up tasks
+    async fn execute(&self, task: Task, orchestrator: OrchestratorHandle) -> Result<TaskResult>;
     async fn analyze_task(&self, task: &Task) -> Result<TaskAnalysis>;
 
     /// 🏗️ ARCHITECTURE DECISION: Agent-specific capabilities
@@ -36,6 +43,8 @@ pub trait Agent: Send + Sync {
             description: self.description(),
             supported_languages: vec![],
             required_tools: vec![],
+            task_categories: vec![],
+            max_complexity: crate::models::TaskComplexity::default(),
         }
     }
 
@@ -57,7 +66,10 @@ pub trait Agent: Send + Sync {
 pub struct AgentStatus {
     pub agent_type: AgentType,
     pub is_busy: bool,
-    pub current_task_id: Option<String>,
+    /// Tasks of this agent type currently executing across all workers
+    pub current_task_ids: BTreeSet<String>,
+    /// Number of current_task_ids
+    pub active_tasks: u32,
     pub tasks_completed: u64,
     pub tasks_failed: u64,
     pub average_execution_time: f64,
@@ -68,7 +80,8 @@ impl AgentStatus {
         Self {
             agent_type,
             is_busy: false,
-            current_task_id: None,
+            current_task_ids: BTreeSet::new(),
+            active_tasks: 0,
             tasks_completed: 0,
             tasks_failed: 0,
             average_execution_time: 0.0,
@@ -76,13 +89,23 @@ impl AgentStatus {
     }
 
     pub fn start_task(&mut self, task_id: String) {
-        self.is_busy = true;
-        self.current_task_id = Some(task_id);
+        self.current_task_ids.insert(task_id);
+        self.refresh_activity();
+    }
+
+    /// Release the task's worker slot without counting the attempt as completed or failed
+    pub fn release_task(&mut self, task_id: &str) {
+        self.current_task_ids.remove(task_id);
+        self.refresh_activity();
+    }
+
+    fn refresh_activity(&mut self) {
+        self.active_tasks = self.current_task_ids.len() as u32;
+        self.is_busy = self.active_tasks > 0;
     }
 
-    pub fn complete_task(&mut self, execution_time: f64) {
-        self.is_busy = false;
-        self.current_task_id = None;
+    pub fn complete_task(&mut self, task_id: &str, execution_time: f64) {
+        self.release_task(task_id);
         self.tasks_completed += 1;
 
         self.average_execution_time =
@@ -90,9 +113,8 @@ impl AgentStatus {
                 / self.tasks_completed as f64;
     }
 
-    pub fn fail_task(&mut self) {
-        self.is_busy = false;
-        self.current_task_id = None;
+    pub fn fail_task(&mut self, task_id: &str) {
+        self.release_task(task_id);
         self.tasks_failed += 1;
     }
 }
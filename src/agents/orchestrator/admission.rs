@@ -0,0 +1,164 @@
+//! Who gets the last slots of a nearly full task queue
+//!
+//! The top QUEUE_RESERVED_PERCENT of the queue is kept for High and Critical priority
+//! tasks and for tasks submitted by admins, so a flood of routine work cannot lock them
+//! out. With QUEUE_EVICT_LOW_PRIORITY set, such a task arriving at a full queue takes the
+//! place of the oldest Low priority task instead of being refused.
+
+use crate::config::OrchestratorConfig;
+use crate::models::{Priority, Task};
+
+/// Who is submitting a task, as far as queue admission is concerned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Submitter {
+    /// Anyone; limited to the unreserved part of the queue below High priority
+    #[default]
+    Standard,
+    /// An admin, whose tasks may use the reserved slots at any priority
+    Privileged,
+}
+
+/// What to do with a submitted task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    Admit,
+    /// Admit after evicting this queued Low priority task
+    Evict(String),
+    Reject,
+}
+
+/// 🚦 QUEUE ADMISSION: Capacity limits by priority and submitter
+/// 🏗️ ARCHITECTURE DECISION: Reserve the top of one queue rather than keep a second queue
+/// Why: Dispatch already orders by priority; only admission needed to tell tasks apart
+/// Alternative: Separate queue for privileged tasks (rejected: two queues to drain, reorder
+/// and checkpoint, for a difference that only matters when the queue is nearly full)
+/// Trade-off: Reserved slots sit idle while the queue is busy with routine work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueAdmission {
+    capacity: usize,
+    reserved: usize,
+    evict_low_priority: bool,
+}
+
+impl QueueAdmission {
+    pub fn new(capacity: usize, reserved_percent: u32, evict_low_priority: bool) -> Self {
+        let reserved = capacity * reserved_percent.min(100) as usize / 100;
+        Self {
+            capacity,
+            reserved,
+            evict_low_priority,
+        }
+    }
+
+    pub fn from_config(config: &OrchestratorConfig) -> Self {
+        Self::new(
+            crate::constants::MAX_QUEUE_SIZE,
+            config.queue_reserved_percent,
+            config.queue_evict_low_priority,
+        )
+    }
+
+    /// Queued tasks a submission may find before it is turned away
+    pub fn limit(&self, task: &Task, submitter: Submitter) -> usize {
+        if Self::uses_reserve(task, submitter) {
+            self.capacity
+        } else {
+            self.capacity - self.reserved
+        }
+    }
+
+    /// Queued tasks a batch may find and still fit; a batch is held to the limit of its
+    /// least entitled task
+    pub fn batch_limit(&self, tasks: &[Task], submitter: Submitter) -> usize {
+        tasks
+            .iter()
+            .map(|task| self.limit(task, submitter))
+            .min()
+            .unwrap_or(self.capacity)
+    }
+
+    fn uses_reserve(task: &Task, submitter: Submitter) -> bool {
+        submitter == Submitter::Privileged || task.priority >= Priority::High
+    }
+
+    /// Decide on `task` against the pending `queue`
+    pub fn decide(&self, queue: &[Task], task: &Task, submitter: Submitter) -> Admission {
+        if queue.len() < self.limit(task, submitter) {
+            return Admission::Admit;
+        }
+        // Only tasks entitled to the reserve may push others out, and never a peer
+        if !self.evict_low_priority
+            || !Self::uses_reserve(task, submitter)
+            || task.priority == Priority::Low
+        {
+            return Admission::Reject;
+        }
+        queue
+            .iter()
+            .filter(|queued| queued.priority == Priority::Low)
+            .min_by_key(|queued| queued.created_at)
+            .map(|oldest| Admission::Evict(oldest.id.clone()))
+            .unwrap_or(Admission::Reject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AgentType;
+
+    fn task(priority: Priority) -> Task {
+        Task::new(AgentType::SoftwareDeveloper, "work".to_string(), priority)
+    }
+
+    #[test]
+    fn test_reserve_is_kept_for_high_priority_and_admins() {
+        let admission = QueueAdmission::new(10, 20, false);
+        let queue: Vec<Task> = (0..8).map(|_| task(Priority::Low)).collect();
+
+        assert_eq!(
+            admission.decide(&queue, &task(Priority::Medium), Submitter::Standard),
+            Admission::Reject
+        );
+        assert_eq!(
+            admission.decide(&queue, &task(Priority::High), Submitter::Standard),
+            Admission::Admit
+        );
+        assert_eq!(
+            admission.decide(&queue, &task(Priority::Low), Submitter::Privileged),
+            Admission::Admit
+        );
+    }
+
+    #[test]
+    fn test_full_queue_evicts_oldest_low_priority_task() {
+        let mut queue: Vec<Task> = (0..10).map(|_| task(Priority::Medium)).collect();
+        let mut oldest = task(Priority::Low);
+        oldest.created_at -= chrono::Duration::minutes(5);
+        queue[3] = task(Priority::Low);
+        queue[7] = oldest.clone();
+
+        let evicting = QueueAdmission::new(10, 20, true);
+        assert_eq!(
+            evicting.decide(&queue, &task(Priority::Critical), Submitter::Standard),
+            Admission::Evict(oldest.id)
+        );
+        // Low priority admin tasks and standard tasks never evict
+        assert_eq!(
+            evicting.decide(&queue, &task(Priority::Low), Submitter::Privileged),
+            Admission::Reject
+        );
+        assert_eq!(
+            evicting.decide(&queue, &task(Priority::Medium), Submitter::Standard),
+            Admission::Reject
+        );
+        assert_eq!(
+            QueueAdmission::new(10, 20, false).decide(
+                &queue,
+                &task(Priority::Critical),
+                Submitter::Standard
+            ),
+            Admission::Reject
+        );
+    }
+}
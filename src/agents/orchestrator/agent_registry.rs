@@ -17,6 +17,12 @@ pub struct AgentRegistry {
     statuses: Arc<RwLock<HashMap<AgentType, AgentStatus>>>,
 }
 
+impl Default for AgentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AgentRegistry {
     pub fn new() -> Self {
         Self {
@@ -129,6 +135,10 @@ impl AgentFactory {
                 let agent = crate::agents::ProjectManagerAgent::new(Some(claude_client));
                 Ok(Arc::new(agent) as Arc<dyn Agent>)
             }
+            AgentType::QualityAssurance => {
+                let agent = crate::agents::QualityAssuranceAgent::new(Some(claude_client));
+                Ok(Arc::new(agent) as Arc<dyn Agent>)
+            }
         }
     }
 }
@@ -153,6 +163,15 @@ mod tests {
             allowed_tools: vec![],
             workspace_cleanup_after_hours: 24,
             max_workspace_size_mb: 100,
+            max_concurrent_invocations: 4,
+            daily_budget_usd: None,
+            response_cache: Default::default(),
+            circuit_breaker: Default::default(),
+            sandbox: Default::default(),
+            mcp_servers: Default::default(),
+            verify_cli_version: false,
+            workspace_templates: Default::default(),
+            transcripts: Default::default(),
         };
         let claude_client = ClaudeCodeClient::new(config).await.unwrap();
         let agent = Arc::new(SoftwareDeveloperAgent::new(claude_client));
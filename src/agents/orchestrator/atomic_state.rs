@@ -120,7 +120,7 @@ impl AtomicTaskStateManager {
 
         // Update agent status
         if let Some(status) = statuses.get_mut(&agent_type) {
-            status.complete_task(execution_time);
+            status.complete_task(task_id, execution_time);
         }
 
         debug!("Task {} atomically completed", task_id);
@@ -161,13 +161,88 @@ impl AtomicTaskStateManager {
 
         // Update agent status
         if let Some(status) = statuses.get_mut(&agent_type) {
-            status.complete_task(execution_time);
+            status.complete_task(task_id, execution_time);
         }
 
         debug!("Task {} atomically marked as failed: {}", task_id, error);
         Ok(())
     }
 
+    /// Atomically return a failed in-progress task to Pending for another attempt
+    /// Returns the updated task so the caller can re-queue it
+    pub async fn retry_task_atomic(&self, task_id: &str) -> Result<Task> {
+        // Acquire all locks in consistent order
+        let mut storage = self.task_storage.lock().await;
+        let mut statuses = self.agent_statuses.write().await;
+
+        let task = storage.get_mut(task_id).ok_or_else(|| SpiralError::Agent {
+            message: format!("Task {task_id} not found in storage"),
+        })?;
+
+        if task.status != TaskStatus::InProgress {
+            return Err(SpiralError::Agent {
+                message: format!(
+                    "Task {} is not in progress (status: {:?})",
+                    task_id, task.status
+                ),
+            });
+        }
+
+        task.status = TaskStatus::Pending;
+        task.retry_count += 1;
+        task.updated_at = chrono::Utc::now();
+
+        // The attempt neither completed nor finally failed, so only release the agent
+        if let Some(status) = statuses.get_mut(&task.agent_type) {
+            status.release_task(task_id);
+        }
+
+        debug!(
+            "Task {} atomically reset to Pending for retry {}",
+            task_id, task.retry_count
+        );
+        Ok(task.clone())
+    }
+
+    /// Atomically cancel a pending or in-progress task and store its final result
+    /// Returns the status the task had, so the caller knows whether an agent was running it
+    pub async fn cancel_task_atomic(
+        &self,
+        task_id: &str,
+        task_result: TaskResult,
+    ) -> Result<TaskStatus> {
+        // Acquire all locks in consistent order
+        let mut storage = self.task_storage.lock().await;
+        let mut results = self.task_results.lock().await;
+        let mut statuses = self.agent_statuses.write().await;
+
+        let task = storage
+            .get_mut(task_id)
+            .ok_or_else(|| SpiralError::NotFound(format!("Task {task_id} not found")))?;
+
+        let previous = task.status.clone();
+        match previous {
+            TaskStatus::Pending => {}
+            TaskStatus::InProgress => {
+                if let Some(status) = statuses.get_mut(&task.agent_type) {
+                    status.release_task(task_id);
+                }
+            }
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled => {
+                return Err(SpiralError::Validation(format!(
+                    "Task {task_id} has already finished ({previous:?})"
+                )));
+            }
+        }
+
+        task.status = TaskStatus::Cancelled;
+        task.updated_at = chrono::Utc::now();
+        results.insert(task_id.to_string(), task_result);
+
+        debug!("Task {} atomically cancelled from {:?}", task_id, previous);
+        Ok(previous)
+    }
+
     /// Cleanup task state if execution fails before completion
     pub async fn cleanup_task_state(&self, task_id: &str) {
         let mut storage = self.task_storage.lock().await;
@@ -181,10 +256,7 @@ impl AtomicTaskStateManager {
 
                 // Remove from agent's active tasks
                 if let Some(status) = statuses.get_mut(&task.agent_type) {
-                    // We need to remove this task from the agent's active set
-                    // This requires exposing a method in AgentStatus
-                    status.is_busy = false;
-                    status.current_task_id = None;
+                    status.release_task(task_id);
                 }
 
                 warn!("Cleaned up incomplete task {}", task_id);
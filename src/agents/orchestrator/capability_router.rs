@@ -0,0 +1,170 @@
+use crate::{
+    claude_code::TaskAnalysis,
+    models::{AgentCapability, AgentType, TaskComplexity},
+};
+use std::collections::HashSet;
+
+/// Score bonus when an analysed required skill names a capability
+const SKILL_MATCH_SCORE: u32 = 3;
+/// Score bonus when the task content mentions a capability
+const CONTENT_MATCH_SCORE: u32 = 2;
+
+/// 🏗️ ARCHITECTURE DECISION: Stateless keyword scoring over advertised capabilities
+/// Why: Agents describe themselves, so adding an agent needs no routing table changes
+/// Alternative: Ask Claude to pick the agent (rejected: second model call per task and
+/// non-deterministic results that are hard to debug)
+/// Alternative: Hard-coded keyword table per AgentType (rejected: duplicates capabilities())
+pub struct CapabilityRouter;
+
+impl CapabilityRouter {
+    /// Score how well an agent fits the task, or None if the task is too complex for it
+    /// Every eligible agent scores at least 1 so a task with no keyword matches still routes
+    pub fn score(
+        capability: &AgentCapability,
+        content: &str,
+        analysis: Option<&TaskAnalysis>,
+    ) -> Option<u32> {
+        if let Some(analysis) = analysis {
+            if TaskComplexity::from_label(&analysis.complexity) > capability.max_complexity {
+                return None;
+            }
+        }
+
+        let words: HashSet<String> = content
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let skills: HashSet<String> = analysis
+            .map(|analysis| {
+                analysis
+                    .required_skills
+                    .iter()
+                    .map(|skill| skill.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let score = capability
+            .supported_languages
+            .iter()
+            .chain(capability.task_categories.iter())
+            .map(|term| {
+                let term = term.to_lowercase();
+                let mut term_score = 0;
+                if skills.contains(&term) {
+                    term_score += SKILL_MATCH_SCORE;
+                }
+                if words.contains(&term) {
+                    term_score += CONTENT_MATCH_SCORE;
+                }
+                term_score
+            })
+            .sum::<u32>();
+
+        Some(score + 1)
+    }
+
+    /// Pick the best-scoring agent among the candidates
+    /// Ties go to the agent with the lower complexity ceiling, so broad agents are kept
+    /// for the work only they can do
+    pub fn select(
+        candidates: impl IntoIterator<Item = (AgentType, AgentCapability)>,
+        content: &str,
+        analysis: Option<&TaskAnalysis>,
+    ) -> Option<AgentType> {
+        candidates
+            .into_iter()
+            .filter_map(|(agent_type, capability)| {
+                Self::score(&capability, content, analysis)
+                    .map(|score| (score, capability.max_complexity, agent_type))
+            })
+            .max_by(|(a_score, a_max, a_type), (b_score, b_max, b_type)| {
+                a_score
+                    .cmp(b_score)
+                    .then_with(|| b_max.cmp(a_max))
+                    .then_with(|| format!("{b_type:?}").cmp(&format!("{a_type:?}")))
+            })
+            .map(|(_, _, agent_type)| agent_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(
+        languages: &[&str],
+        categories: &[&str],
+        max_complexity: TaskComplexity,
+    ) -> AgentCapability {
+        AgentCapability {
+            name: "test".to_string(),
+            description: "test".to_string(),
+            supported_languages: languages.iter().map(|s| s.to_string()).collect(),
+            required_tools: vec![],
+            task_categories: categories.iter().map(|s| s.to_string()).collect(),
+            max_complexity,
+        }
+    }
+
+    fn analysis(complexity: &str, skills: &[&str]) -> TaskAnalysis {
+        TaskAnalysis {
+            complexity: complexity.to_string(),
+            estimated_minutes: 30,
+            required_skills: skills.iter().map(|s| s.to_string()).collect(),
+            challenges: vec![],
+            approach: String::new(),
+            raw_analysis: String::new(),
+        }
+    }
+
+    fn candidates() -> Vec<(AgentType, AgentCapability)> {
+        vec![
+            (
+                AgentType::SoftwareDeveloper,
+                capability(&["rust"], &["implementation"], TaskComplexity::High),
+            ),
+            (
+                AgentType::ProjectManager,
+                capability(&[], &["planning", "roadmap"], TaskComplexity::Critical),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_routes_by_content_and_skills() {
+        let dev_analysis = analysis("Medium", &["rust"]);
+        assert_eq!(
+            CapabilityRouter::select(candidates(), "Fix the parser", Some(&dev_analysis)),
+            Some(AgentType::SoftwareDeveloper)
+        );
+        assert_eq!(
+            CapabilityRouter::select(candidates(), "Draft a roadmap for Q3 planning", None),
+            Some(AgentType::ProjectManager)
+        );
+    }
+
+    #[test]
+    fn test_complexity_ceiling_excludes_agents() {
+        let critical = analysis("Critical", &["rust"]);
+        assert_eq!(
+            CapabilityRouter::select(candidates(), "Rewrite everything in rust", Some(&critical)),
+            Some(AgentType::ProjectManager)
+        );
+
+        let dev_only = vec![candidates().remove(0)];
+        assert_eq!(
+            CapabilityRouter::select(dev_only, "Rewrite everything", Some(&critical)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ties_prefer_lower_complexity_ceiling() {
+        assert_eq!(
+            CapabilityRouter::select(candidates(), "Do something", None),
+            Some(AgentType::SoftwareDeveloper)
+        );
+    }
+}
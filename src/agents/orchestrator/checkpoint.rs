@@ -0,0 +1,234 @@
+use super::TaskProgress;
+use crate::{models::Task, validation::MAX_CONTEXT_VALUE_LENGTH};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Context key set on tasks re-queued from a checkpoint, holding the checkpoint time
+pub const RESUMED_FROM_CHECKPOINT_CONTEXT_KEY: &str = "resumed_from_checkpoint";
+/// Context key with the workspace the interrupted attempt was using
+pub const CHECKPOINT_WORKSPACE_CONTEXT_KEY: &str = "checkpoint_workspace_path";
+/// Context key with the Claude session the interrupted attempt was using
+pub const CHECKPOINT_SESSION_CONTEXT_KEY: &str = "checkpoint_session_id";
+/// Context key with the interrupted attempt's progress notes, one per line
+pub const CHECKPOINT_NOTES_CONTEXT_KEY: &str = "checkpoint_notes";
+
+/// Partial state an agent saves so an interrupted task can pick up where it left off
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentCheckpoint {
+    pub workspace_path: Option<String>,
+    pub session_id: Option<String>,
+    /// What has been done so far, oldest first
+    pub notes: Vec<String>,
+}
+
+impl AgentCheckpoint {
+    /// State saved by an earlier attempt of this task, if it was resumed from a checkpoint
+    pub fn from_context(task: &Task) -> Option<Self> {
+        task.context.get(RESUMED_FROM_CHECKPOINT_CONTEXT_KEY)?;
+        Some(Self {
+            workspace_path: task.context.get(CHECKPOINT_WORKSPACE_CONTEXT_KEY).cloned(),
+            session_id: task.context.get(CHECKPOINT_SESSION_CONTEXT_KEY).cloned(),
+            notes: task
+                .context
+                .get(CHECKPOINT_NOTES_CONTEXT_KEY)
+                .map(|notes| notes.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// A task that was queued or running when the orchestrator stopped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCheckpoint {
+    pub task: Task,
+    pub agent_state: Option<AgentCheckpoint>,
+    pub progress: Option<TaskProgress>,
+    pub checkpointed_at: DateTime<Utc>,
+}
+
+impl TaskCheckpoint {
+    /// The task ready to queue again, with the saved state in its context
+    /// Notes keep the newest lines that fit, since context values are length-limited
+    pub fn into_resumed_task(self) -> Task {
+        let mut task = self.task.with_context(
+            RESUMED_FROM_CHECKPOINT_CONTEXT_KEY.to_string(),
+            self.checkpointed_at.to_rfc3339(),
+        );
+        let Some(state) = self.agent_state else {
+            return task;
+        };
+
+        if let Some(workspace_path) = state.workspace_path {
+            task = task.with_context(CHECKPOINT_WORKSPACE_CONTEXT_KEY.to_string(), workspace_path);
+        }
+        if let Some(session_id) = state.session_id {
+            task = task.with_context(CHECKPOINT_SESSION_CONTEXT_KEY.to_string(), session_id);
+        }
+
+        let mut notes: Vec<&str> = Vec::new();
+        let mut length = 0;
+        for note in state.notes.iter().rev() {
+            if length + note.len() + 1 > MAX_CONTEXT_VALUE_LENGTH {
+                break;
+            }
+            length += note.len() + 1;
+            notes.push(note);
+        }
+        if !notes.is_empty() {
+            notes.reverse();
+            task = task.with_context(CHECKPOINT_NOTES_CONTEXT_KEY.to_string(), notes.join("\n"));
+        }
+        task
+    }
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Agent state held in memory, written to disk only at shutdown
+/// Why: Checkpoints only matter across a restart, and agents update them far more often
+/// than the orchestrator stops
+/// Alternative: Persist on every agent update (rejected: disk writes on the hot path for
+/// state that is almost always discarded when the task completes)
+/// Alternative: SQLite like agent memory (rejected: the file is written once and read once,
+/// so a JSON file like the schedule store is simpler to inspect and repair)
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointStore {
+    path: Option<PathBuf>,
+    agent_state: Arc<Mutex<HashMap<String, AgentCheckpoint>>>,
+}
+
+impl CheckpointStore {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            agent_state: Arc::default(),
+        }
+    }
+
+    /// Replace the saved state of a running task
+    pub async fn record(&self, task_id: &str, state: AgentCheckpoint) {
+        self.agent_state
+            .lock()
+            .await
+            .insert(task_id.to_string(), state);
+    }
+
+    pub async fn agent_state(&self, task_id: &str) -> Option<AgentCheckpoint> {
+        self.agent_state.lock().await.get(task_id).cloned()
+    }
+
+    /// Forget a task's state once its attempt has finished
+    pub async fn clear(&self, task_id: &str) {
+        self.agent_state.lock().await.remove(task_id);
+    }
+
+    /// Write the checkpoints, replacing any earlier file; a no-op without a store path
+    pub async fn save(&self, checkpoints: &[TaskCheckpoint]) -> crate::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let serialized = serde_json::to_string_pretty(checkpoints).map_err(|e| {
+            crate::SpiralError::SystemError(format!("Failed to serialize checkpoints: {e}"))
+        })?;
+        tokio::fs::write(path, serialized).await.map_err(|e| {
+            crate::SpiralError::SystemError(format!(
+                "Failed to write checkpoints to {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Read and delete the checkpoint file so each checkpoint is resumed once
+    pub async fn take_saved(&self) -> Vec<TaskCheckpoint> {
+        let Some(path) = &self.path else {
+            return Vec::new();
+        };
+
+        let checkpoints = load_checkpoints(path).await;
+        if let Err(e) = tokio::fs::remove_file(path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove checkpoint file {:?}: {}", path, e);
+            }
+        }
+        checkpoints
+    }
+}
+
+async fn load_checkpoints(path: &Path) -> Vec<TaskCheckpoint> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            warn!("Failed to read checkpoint file {:?}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    match serde_json::from_str(&content) {
+        Ok(checkpoints) => checkpoints,
+        Err(e) => {
+            warn!("Ignoring corrupt checkpoint file {:?}: {}", path, e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, Priority};
+
+    fn checkpoint(notes: Vec<String>) -> TaskCheckpoint {
+        TaskCheckpoint {
+            task: Task::new(
+                AgentType::SoftwareDeveloper,
+                "Build a parser".to_string(),
+                Priority::High,
+            ),
+            agent_state: Some(AgentCheckpoint {
+                workspace_path: Some("claude-workspaces/session-1".to_string()),
+                session_id: Some("session-1".to_string()),
+                notes,
+            }),
+            progress: None,
+            checkpointed_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_checkpoints_are_taken_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(Some(dir.path().join("checkpoints.json")));
+        let saved = checkpoint(vec!["wrote lexer".to_string()]);
+
+        store.save(std::slice::from_ref(&saved)).await.unwrap();
+        let taken = store.take_saved().await;
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].task.id, saved.task.id);
+        assert_eq!(taken[0].agent_state, saved.agent_state);
+
+        assert!(store.take_saved().await.is_empty());
+    }
+
+    #[test]
+    fn test_resumed_task_carries_state_within_context_limit() {
+        let notes: Vec<String> = (0..100).map(|i| format!("step {i} done")).collect();
+        let task = checkpoint(notes).into_resumed_task();
+
+        let state = AgentCheckpoint::from_context(&task).unwrap();
+        assert_eq!(state.session_id.as_deref(), Some("session-1"));
+        assert_eq!(state.notes.last().map(String::as_str), Some("step 99 done"));
+        assert!(task.context[CHECKPOINT_NOTES_CONTEXT_KEY].len() <= MAX_CONTEXT_VALUE_LENGTH);
+
+        let fresh = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Fresh".to_string(),
+            Priority::Low,
+        );
+        assert!(AgentCheckpoint::from_context(&fresh).is_none());
+    }
+}
@@ -0,0 +1,213 @@
+use super::{
+    AgentCheckpoint, AgentOrchestrator, ProgressReporter, TaskEventKind, TaskProgressTracker,
+};
+use crate::{
+    constants::{MAX_DELEGATIONS_PER_TASK, MAX_DELEGATION_DEPTH},
+    models::Task,
+    Result, SpiralError,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::info;
+
+/// Context key naming the task that delegated this one
+pub const DELEGATED_BY_CONTEXT_KEY: &str = "delegated_by";
+/// Context key with the task's generation in its delegation chain
+pub const DELEGATION_DEPTH_CONTEXT_KEY: &str = "delegation_depth";
+/// Context keys only OrchestratorHandle::delegate may set; submitters' values are dropped
+pub const DELEGATION_CONTEXT_KEYS: &[&str] =
+    &[DELEGATED_BY_CONTEXT_KEY, DELEGATION_DEPTH_CONTEXT_KEY];
+
+/// 🏗️ ARCHITECTURE DECISION: Per-task handle passed into Agent::execute
+/// Why: Agents can submit follow-up work without holding the whole orchestrator, and the
+/// handle knows which task is delegating so limits and lineage are enforced in one place
+/// Alternative: Give agents an Arc<AgentOrchestrator> (rejected: exposes pause, drain and
+/// cleanup to agents, and nothing would stop runaway delegation chains)
+/// Alternative: Let delegating agents await the child's result (rejected: the parent holds a
+/// worker slot, so a child queued behind it in the same pool would deadlock)
+#[derive(Clone)]
+pub struct OrchestratorHandle {
+    orchestrator: Option<AgentOrchestrator>,
+    parent_task_id: String,
+    depth: usize,
+    delegated: Arc<AtomicUsize>,
+    progress: ProgressReporter,
+}
+
+impl OrchestratorHandle {
+    /// Handle for a task executing under the orchestrator
+    /// Delegations from earlier attempts count toward the limit, so retries cannot fan out again
+    pub(crate) async fn for_task(orchestrator: AgentOrchestrator, task: &Task) -> Self {
+        let already_delegated = orchestrator
+            .event_log
+            .events_for(&task.id)
+            .await
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|event| event.kind == TaskEventKind::Delegated)
+                    .count()
+            })
+            .unwrap_or(0);
+
+        Self {
+            progress: ProgressReporter::new(orchestrator.task_progress.clone(), &task.id),
+            orchestrator: Some(orchestrator),
+            parent_task_id: task.id.clone(),
+            depth: delegation_depth(task),
+            delegated: Arc::new(AtomicUsize::new(already_delegated)),
+        }
+    }
+
+    /// Handle for agents running outside the orchestrator; every delegation is rejected
+    /// Progress goes to a private tracker unless a reporter is attached with `with_progress`
+    pub fn detached() -> Self {
+        Self {
+            orchestrator: None,
+            parent_task_id: String::new(),
+            depth: 0,
+            delegated: Arc::new(AtomicUsize::new(0)),
+            progress: ProgressReporter::new(TaskProgressTracker::new(), ""),
+        }
+    }
+
+    /// Send the agent's progress to the caller's tracker, for direct execution
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Reporter for the executing task's progress
+    pub fn progress(&self) -> &ProgressReporter {
+        &self.progress
+    }
+
+    /// Save partial state so the task can resume if the orchestrator shuts down mid-run
+    /// Each call replaces the previous state; detached handles discard it
+    pub async fn checkpoint(&self, state: AgentCheckpoint) {
+        if let Some(orchestrator) = &self.orchestrator {
+            orchestrator
+                .checkpoints
+                .record(&self.parent_task_id, state)
+                .await;
+        }
+    }
+
+    /// Whether a delegation would currently be accepted
+    pub fn can_delegate(&self) -> bool {
+        self.orchestrator.is_some()
+            && self.depth < MAX_DELEGATION_DEPTH
+            && self.delegated.load(Ordering::SeqCst) < MAX_DELEGATIONS_PER_TASK
+    }
+
+    /// Submit a follow-up task and return its ID without waiting for it to run
+    pub async fn delegate(&self, task: Task) -> Result<String> {
+        let Some(orchestrator) = &self.orchestrator else {
+            return Err(SpiralError::Validation(
+                "Delegation requires the orchestrator".to_string(),
+            ));
+        };
+        if self.depth >= MAX_DELEGATION_DEPTH {
+            return Err(SpiralError::Validation(format!(
+                "Task {} is at the delegation depth limit of {MAX_DELEGATION_DEPTH}",
+                self.parent_task_id
+            )));
+        }
+        // Reserve the slot before submitting so concurrent delegations cannot overshoot
+        if self.delegated.fetch_add(1, Ordering::SeqCst) >= MAX_DELEGATIONS_PER_TASK {
+            self.delegated.fetch_sub(1, Ordering::SeqCst);
+            return Err(SpiralError::Validation(format!(
+                "Task {} already delegated {MAX_DELEGATIONS_PER_TASK} tasks",
+                self.parent_task_id
+            )));
+        }
+
+        let agent_type = task.agent_type.clone();
+        let task = task
+            .with_context(
+                DELEGATED_BY_CONTEXT_KEY.to_string(),
+                self.parent_task_id.clone(),
+            )
+            .with_context(
+                DELEGATION_DEPTH_CONTEXT_KEY.to_string(),
+                (self.depth + 1).to_string(),
+            );
+
+        match orchestrator.submit_task(task).await {
+            Ok(child_id) => {
+                info!(
+                    "Task {} delegated {} to {:?}",
+                    self.parent_task_id, child_id, agent_type
+                );
+                orchestrator
+                    .event_log
+                    .record(
+                        &self.parent_task_id,
+                        TaskEventKind::Delegated,
+                        Some(format!("{child_id} to {agent_type:?}")),
+                    )
+                    .await;
+                Ok(child_id)
+            }
+            Err(e) => {
+                self.delegated.fetch_sub(1, Ordering::SeqCst);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Generation of a task in its delegation chain, 0 for tasks submitted directly
+pub fn delegation_depth(task: &Task) -> usize {
+    task.context
+        .get(DELEGATION_DEPTH_CONTEXT_KEY)
+        .and_then(|depth| depth.parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, Priority};
+
+    #[tokio::test]
+    async fn test_detached_handle_rejects_delegation() {
+        let handle = OrchestratorHandle::detached();
+        assert!(!handle.can_delegate());
+
+        let task = Task::new(
+            AgentType::QualityAssurance,
+            "Write tests".to_string(),
+            Priority::Medium,
+        );
+        assert!(matches!(
+            handle.delegate(task).await,
+            Err(SpiralError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_detached_handle_reports_to_attached_tracker() {
+        let tracker = TaskProgressTracker::new();
+        let handle = OrchestratorHandle::detached()
+            .with_progress(ProgressReporter::new(tracker.clone(), "direct-task"));
+
+        handle.progress().report(40, "generating code").await;
+        let progress = tracker.latest("direct-task").await.unwrap();
+        assert_eq!(progress.percent, 40);
+        assert_eq!(progress.phase, "generating code");
+    }
+
+    #[test]
+    fn test_delegation_depth_defaults_to_zero() {
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Build it".to_string(),
+            Priority::Medium,
+        );
+        assert_eq!(delegation_depth(&task), 0);
+
+        let task = task.with_context(DELEGATION_DEPTH_CONTEXT_KEY.to_string(), "2".to_string());
+        assert_eq!(delegation_depth(&task), 2);
+    }
+}
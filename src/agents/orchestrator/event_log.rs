@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex};
+use utoipa::ToSchema;
+
+/// Lifecycle transitions recorded for every task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskEventKind {
+    /// Accepted into the queue
+    Submitted,
+    /// Moved within the queue by an operator
+    Reordered,
+    /// Taken off the queue by the dispatcher with a worker slot
+    Dequeued,
+    /// Transitioned to InProgress and handed to the agent
+    Started,
+    /// Failed transiently and returned to the queue after a backoff
+    Retried,
+    /// Submitted a follow-up task to another agent during execution
+    Delegated,
+    /// Finished with a result
+    Completed,
+    /// Finished with an error or a failure result
+    Failed,
+    /// Stopped by a client request before finishing
+    Cancelled,
+    /// Reset by the orchestrator after execution stopped without a final state
+    CleanedUp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskEvent {
+    pub kind: TaskEventKind,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// An event as it is recorded, for live subscribers
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskEventUpdate {
+    pub task_id: String,
+    #[serde(flatten)]
+    pub event: TaskEvent,
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Append-only in-memory log keyed by task ID
+/// Why: Debugging a stuck task needs the full timeline, not only the current status
+/// Alternative: Tracing logs only (rejected: not queryable per task through the API)
+/// Alternative: Persisting to disk (rejected: task storage itself is in memory, so history
+/// would outlive the tasks it describes)
+/// Retention: Entries are pruned alongside task storage in perform_cleanup
+/// Live: Every recorded event is also broadcast to subscribers, e.g. API streams
+#[derive(Debug, Clone)]
+pub struct TaskEventLog {
+    events: Arc<Mutex<HashMap<String, Vec<TaskEvent>>>>,
+    updates: broadcast::Sender<TaskEventUpdate>,
+}
+
+impl Default for TaskEventLog {
+    fn default() -> Self {
+        Self {
+            events: Arc::default(),
+            updates: broadcast::channel(crate::constants::TASK_UPDATE_BROADCAST_CAPACITY).0,
+        }
+    }
+}
+
+impl TaskEventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive events as they are recorded, for every task
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskEventUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Append an event to the task's history
+    pub async fn record(&self, task_id: &str, kind: TaskEventKind, detail: Option<String>) {
+        let event = TaskEvent {
+            kind,
+            timestamp: Utc::now(),
+            detail,
+        };
+        let mut events = self.events.lock().await;
+        events
+            .entry(task_id.to_string())
+            .or_default()
+            .push(event.clone());
+        drop(events);
+
+        // A send error only means nobody is subscribed right now
+        let _ = self.updates.send(TaskEventUpdate {
+            task_id: task_id.to_string(),
+            event,
+        });
+    }
+
+    /// Events for the task in the order they were recorded, or None if unknown
+    pub async fn events_for(&self, task_id: &str) -> Option<Vec<TaskEvent>> {
+        let events = self.events.lock().await;
+        events.get(task_id).cloned()
+    }
+
+    /// Drop history for tasks the predicate no longer keeps, returning how many were removed
+    pub async fn retain(&self, mut keep: impl FnMut(&str) -> bool) -> usize {
+        let mut events = self.events.lock().await;
+        let initial_count = events.len();
+        events.retain(|task_id, _| keep(task_id));
+        initial_count - events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_events_are_kept_in_order() {
+        let log = TaskEventLog::new();
+        log.record("task-1", TaskEventKind::Submitted, None).await;
+        log.record("task-1", TaskEventKind::Dequeued, None).await;
+        log.record("task-1", TaskEventKind::Failed, Some("boom".to_string()))
+            .await;
+
+        let events = log.events_for("task-1").await.unwrap();
+        let kinds: Vec<_> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TaskEventKind::Submitted,
+                TaskEventKind::Dequeued,
+                TaskEventKind::Failed
+            ]
+        );
+        assert!(events[0].timestamp <= events[2].timestamp);
+        assert_eq!(events[2].detail.as_deref(), Some("boom"));
+        assert!(log.events_for("task-2").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_recorded_events() {
+        let log = TaskEventLog::new();
+        let mut updates = log.subscribe();
+        log.record(
+            "task-1",
+            TaskEventKind::Started,
+            Some("attempt 1".to_string()),
+        )
+        .await;
+
+        let update = updates.recv().await.unwrap();
+        assert_eq!(update.task_id, "task-1");
+        assert_eq!(update.event.kind, TaskEventKind::Started);
+        assert_eq!(update.event.detail.as_deref(), Some("attempt 1"));
+    }
+
+    #[tokio::test]
+    async fn test_retain_prunes_removed_tasks() {
+        let log = TaskEventLog::new();
+        log.record("keep", TaskEventKind::Submitted, None).await;
+        log.record("drop", TaskEventKind::Submitted, None).await;
+
+        assert_eq!(log.retain(|task_id| task_id == "keep").await, 1);
+        assert!(log.events_for("keep").await.is_some());
+        assert!(log.events_for("drop").await.is_none());
+    }
+}
@@ -1,14 +1,28 @@
-use super::{Agent, AgentStatus, SoftwareDeveloperAgent};
+use super::{
+    memory::{
+        self, AgentMemory, InMemoryAgentMemory, MemoryEntry, MemoryKind, MemoryScope,
+        SqliteAgentMemory, AGENT_MEMORY_CONTEXT_KEY,
+    },
+    Agent, AgentStatus, ProjectManagerAgent, QualityAssuranceAgent, SoftwareDeveloperAgent,
+};
 use crate::{
-    claude_code::ClaudeCodeClient,
+    claude_code::{sessions::SESSION_CONTEXT_KEY, ClaudeCodeClient},
     config::Config,
-    models::{AgentType, Task, TaskResult, TaskStatus},
+    llm::BackendSet,
+    models::{
+        AgentType, Task, TaskBatch, TaskBatchStatus, TaskExecutionResult, TaskResult, TaskStatus,
+    },
+    monitoring::{SloReport, TaskOutcomes},
+    session::{
+        self, SessionEvent, SessionRegistry, SharedSessionManager, SESSION_HISTORY_CONTEXT_KEY,
+        USER_SESSION_CONTEXT_KEY,
+    },
     Result, SpiralError,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::sync::{broadcast, mpsc, Mutex, OwnedSemaphorePermit, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
@@ -16,6 +30,60 @@ use tracing::{debug, error, info, warn};
 mod atomic_state;
 use atomic_state::AtomicTaskStateManager;
 
+pub mod retry_policy;
+pub use retry_policy::{FailureClass, RetryPolicy};
+
+pub mod worker_pool;
+pub use worker_pool::WorkerPools;
+
+pub mod scheduler;
+pub use scheduler::{ScheduledTask, TaskSchedule, TaskScheduler};
+
+pub mod event_log;
+pub use event_log::{TaskEvent, TaskEventKind, TaskEventLog, TaskEventUpdate};
+
+pub mod capability_router;
+pub use capability_router::CapabilityRouter;
+
+pub mod workflow;
+pub use workflow::{WorkflowRun, WorkflowRunStatus, WorkflowStepStatus};
+
+pub mod delegation;
+pub use delegation::OrchestratorHandle;
+
+pub mod progress;
+pub use progress::{ProgressReporter, TaskProgress, TaskProgressTracker, TaskProgressUpdate};
+
+pub mod queue_view;
+pub use queue_view::{QueueMove, QueuedTask};
+
+pub mod checkpoint;
+pub use checkpoint::{AgentCheckpoint, CheckpointStore, TaskCheckpoint};
+
+pub mod task_history;
+pub use task_history::AgentTaskRecord;
+
+pub mod admission;
+pub use admission::{Admission, QueueAdmission, Submitter};
+
+pub mod webhook;
+pub use webhook::{WebhookDelivery, WebhookDeliveryStatus, WebhookNotifier, WebhookPayload};
+
+/// ⏸️ DISPATCH CONTROL: Whether the task processor may start new tasks
+/// Queued tasks are kept in every state; only dispatch of new work is affected
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DispatchState {
+    /// Normal operation, queued tasks are dispatched as worker slots free up
+    Running,
+    /// No new tasks are dispatched, in-flight tasks keep running
+    Paused,
+    /// Paused while waiting for in-flight tasks to finish
+    Draining,
+}
+
 // 🏗️ ARCHITECTURE DECISION: Modular service architecture
 // Why: Break up god object into focused, single-responsibility services
 // Alternative: Keep monolithic orchestrator (rejected: violates SOLID principles)
@@ -29,12 +97,37 @@ pub struct AgentOrchestrator {
     agents: Arc<RwLock<HashMap<AgentType, Box<dyn Agent>>>>,
     agent_statuses: Arc<RwLock<HashMap<AgentType, AgentStatus>>>,
     task_queue: Arc<Mutex<Vec<Task>>>,
-    result_sender: Arc<Mutex<Option<mpsc::UnboundedSender<TaskResult>>>>,
+    result_broadcaster: broadcast::Sender<TaskResult>,
     task_storage: Arc<Mutex<HashMap<String, Task>>>,
     task_results: Arc<Mutex<HashMap<String, TaskResult>>>,
+    batches: Arc<Mutex<HashMap<String, TaskBatch>>>,
+    event_log: TaskEventLog,
+    task_progress: TaskProgressTracker,
+    workflows: Arc<HashMap<String, Vec<AgentType>>>,
+    workflow_runs: Arc<Mutex<HashMap<String, WorkflowRun>>>,
     start_time: Arc<std::time::Instant>,
     claude_client: Arc<ClaudeCodeClient>,
     atomic_state: Arc<AtomicTaskStateManager>,
+    retry_policy: RetryPolicy,
+    worker_pools: Arc<WorkerPools>,
+    scheduler: Arc<TaskScheduler>,
+    memory: Arc<dyn AgentMemory>,
+    checkpoints: CheckpointStore,
+    webhooks: WebhookNotifier,
+    dispatch_state: Arc<RwLock<DispatchState>>,
+    /// Which submissions may take the queue's reserved slots or evict Low priority tasks
+    admission: QueueAdmission,
+    /// Submissions refused because the queue was full, since startup
+    queue_rejections: Arc<std::sync::atomic::AtomicU64>,
+    /// Execution times and results of finished tasks, for the SLO report
+    task_outcomes: TaskOutcomes,
+    /// Executions in flight by task ID, so a cancel can stop the agent and shutdown can
+    /// wait for them
+    running_tasks: Arc<std::sync::Mutex<HashMap<String, JoinHandle<()>>>>,
+    /// Session managers whose sessions own workspaces, consulted before archiving one
+    session_managers: SessionRegistry,
+    /// Listeners following the registered managers' session events, stopped on shutdown
+    session_listeners: Arc<std::sync::Mutex<Vec<JoinHandle<()>>>>,
     // 🔧 RESOURCE LEAK FIX: Add task lifecycle management for orchestrator
     task_handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     shutdown_signal_sender: Arc<Mutex<Option<mpsc::Sender<()>>>>,
@@ -49,6 +142,9 @@ impl AgentOrchestrator {
         // Alternative: Individual clients per agent (rejected: increases complexity, API overhead)
         // Audit: Check claude_code.rs:45-60 for client initialization patterns
         let claude_client = ClaudeCodeClient::new(config.claude_code.clone()).await?;
+        // 🔀 Each agent generates through its configured backend, with fallbacks while down
+        let backends = BackendSet::new(&config.llm, claude_client.clone())?;
+        let retry_policy = RetryPolicy::from(&config.orchestrator);
 
         // 🔧 ARCHITECTURE DECISION: HashMap for agent registry with AgentType enum keys
         // Why: Type-safe agent lookup, prevents duplicate registrations, O(1) access
@@ -61,20 +157,73 @@ impl AgentOrchestrator {
         // Why: Explicit control over which agents are available, easier to debug capability issues
         // Alternative: Auto-discovery/reflection (rejected: runtime errors, unclear dependencies)
         // Future: Consider agent plugin architecture when we have >5 agent types
-        let developer_agent = SoftwareDeveloperAgent::new(claude_client.clone());
+        let developer_agent = SoftwareDeveloperAgent::new(claude_client.clone())
+            .with_backend(backends.for_agent(&AgentType::SoftwareDeveloper));
         statuses.insert(
             AgentType::SoftwareDeveloper,
             developer_agent.status().clone(),
         );
         agents.insert(AgentType::SoftwareDeveloper, Box::new(developer_agent));
 
+        let project_manager_agent = ProjectManagerAgent::new(Some(claude_client.clone()))
+            .with_backend(backends.for_agent(&AgentType::ProjectManager));
+        statuses.insert(
+            AgentType::ProjectManager,
+            AgentStatus::new(AgentType::ProjectManager),
+        );
+        agents.insert(AgentType::ProjectManager, Box::new(project_manager_agent));
+
+        let quality_assurance_agent = QualityAssuranceAgent::new(Some(claude_client.clone()))
+            .with_backend(backends.for_agent(&AgentType::QualityAssurance));
+        statuses.insert(
+            AgentType::QualityAssurance,
+            AgentStatus::new(AgentType::QualityAssurance),
+        );
+        agents.insert(
+            AgentType::QualityAssurance,
+            Box::new(quality_assurance_agent),
+        );
+
         info!("Registered {} agents", agents.len());
 
+        let worker_pools = Arc::new(WorkerPools::new(agents.keys(), &config.orchestrator));
+        for agent_type in agents.keys() {
+            info!(
+                "Worker pool for {:?}: {} concurrent task(s)",
+                agent_type,
+                worker_pools.limit(agent_type)
+            );
+        }
+
         // 🔒 CONCURRENCY DESIGN: Arc<RwLock> for shared read access, Arc<Mutex> for exclusive writes
         // Why: Multiple tasks can read agent registry simultaneously, but task queue needs serialization
         // Alternative: Single global mutex (rejected: unnecessary blocking of concurrent reads)
         // Audit: Verify no deadlock potential in execute_task method around lines 245-270
 
+        let scheduler = Arc::new(
+            TaskScheduler::new(
+                config
+                    .orchestrator
+                    .schedule_store_path
+                    .as_ref()
+                    .map(std::path::PathBuf::from),
+            )
+            .with_max_schedules(config.orchestrator.max_schedules),
+        );
+
+        // 🧠 AGENT MEMORY: An unreadable database degrades to process memory rather than
+        // blocking startup, agents just forget again on restart
+        let memory: Arc<dyn AgentMemory> = match &config.orchestrator.memory_store_path {
+            Some(path) => match SqliteAgentMemory::open(path) {
+                Ok(store) => Arc::new(store),
+                Err(e) => {
+                    warn!("{}, keeping agent memory in process only", e);
+                    Arc::new(InMemoryAgentMemory::new())
+                }
+            },
+            None => Arc::new(InMemoryAgentMemory::new()),
+        };
+
         let task_storage = Arc::new(Mutex::new(HashMap::new()));
         let task_results = Arc::new(Mutex::new(HashMap::new()));
         let agent_statuses_arc = Arc::new(RwLock::new(statuses));
@@ -85,20 +234,142 @@ impl AgentOrchestrator {
             agent_statuses_arc.clone(),
         ));
 
-        Ok(Self {
+        let checkpoints = CheckpointStore::new(
+            config
+                .orchestrator
+                .checkpoint_store_path
+                .as_ref()
+                .map(std::path::PathBuf::from),
+        );
+
+        let orchestrator = Self {
             agents: Arc::new(RwLock::new(agents)),
             agent_statuses: agent_statuses_arc,
             task_queue: Arc::new(Mutex::new(Vec::new())),
-            result_sender: Arc::new(Mutex::new(None)),
+            result_broadcaster: broadcast::channel(crate::constants::RESULT_BROADCAST_CAPACITY).0,
             task_storage,
             task_results,
+            batches: Arc::new(Mutex::new(HashMap::new())),
+            event_log: TaskEventLog::new(),
+            task_progress: TaskProgressTracker::new(),
+            workflows: Arc::new(config.orchestrator.workflows.clone()),
+            workflow_runs: Arc::new(Mutex::new(HashMap::new())),
             start_time: Arc::new(std::time::Instant::now()),
             claude_client: Arc::new(claude_client),
             atomic_state,
+            retry_policy,
+            worker_pools,
+            scheduler,
+            memory,
+            checkpoints,
+            webhooks: WebhookNotifier::from_config(&config),
+            dispatch_state: Arc::new(RwLock::new(DispatchState::Running)),
+            admission: QueueAdmission::from_config(&config.orchestrator),
+            queue_rejections: Arc::default(),
+            task_outcomes: TaskOutcomes::new(
+                Duration::from_secs(config.monitoring.slo_window_secs),
+                config.monitoring.slo_targets.clone(),
+            ),
+            running_tasks: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            session_managers: SessionRegistry::new(),
+            session_listeners: Arc::new(std::sync::Mutex::new(Vec::new())),
             // 🔧 RESOURCE LEAK FIX: Initialize task management
             task_handles: Arc::new(Mutex::new(Vec::new())),
             shutdown_signal_sender: Arc::new(Mutex::new(None)),
-        })
+        };
+
+        orchestrator.resume_checkpoints().await;
+        Ok(orchestrator)
+    }
+
+    /// ♻️ CHECKPOINT RESUME: Re-queue tasks interrupted by the last shutdown
+    /// Tasks keep their IDs, so session workspaces keyed by task ID are picked up again
+    async fn resume_checkpoints(&self) {
+        let checkpoints = self.checkpoints.take_saved().await;
+        if checkpoints.is_empty() {
+            return;
+        }
+
+        info!(
+            "Resuming {} task(s) from the last shutdown",
+            checkpoints.len()
+        );
+        for checkpoint in checkpoints {
+            let task_id = checkpoint.task.id.clone();
+            if let Err(e) = self.submit_task(checkpoint.into_resumed_task()).await {
+                warn!("Failed to resume task {} from checkpoint: {}", task_id, e);
+            }
+        }
+    }
+
+    /// 💾 SHUTDOWN CHECKPOINT: Stop dispatching, give in-flight tasks a grace period, then
+    /// save every unfinished task with its agent's partial state for the next start
+    /// Returns how many tasks were checkpointed
+    /// Tasks still running after the grace period are aborted before anything is saved,
+    /// so a task that finished meanwhile is not run again; the rest restart from the
+    /// checkpoint
+    pub async fn checkpoint_and_stop(&self, grace: Duration) -> Result<usize> {
+        if let Err(e) = self.drain(grace).await {
+            warn!("{}, checkpointing unfinished tasks", e);
+        }
+        // The grace period was spent draining; nothing may keep writing to workspaces
+        // while shutdown cleans them up
+        self.stop_executions(Duration::ZERO).await;
+
+        let unfinished: Vec<Task> = {
+            let storage = self.task_storage.lock().await;
+            storage
+                .values()
+                .filter(|task| matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress))
+                .cloned()
+                .collect()
+        };
+
+        let mut checkpoints = Vec::with_capacity(unfinished.len());
+        for task in unfinished {
+            checkpoints.push(TaskCheckpoint {
+                agent_state: self.checkpoints.agent_state(&task.id).await,
+                progress: self.task_progress.latest(&task.id).await,
+                checkpointed_at: chrono::Utc::now(),
+                task,
+            });
+        }
+
+        self.checkpoints.save(&checkpoints).await?;
+        info!("Checkpointed {} unfinished task(s)", checkpoints.len());
+        Ok(checkpoints.len())
+    }
+
+    /// ⚡ EXECUTIONS: Wait up to `grace` for running executions, then abort the rest and
+    /// wait for them to unwind, so none changes its task's status after this returns
+    /// Dispatch must be stopped first, or new executions start behind this
+    async fn stop_executions(&self, grace: Duration) {
+        let executions: Vec<(String, JoinHandle<()>)> = self
+            .running_tasks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .drain()
+            .collect();
+        let deadline = tokio::time::Instant::now() + grace;
+        for (task_id, mut execution) in executions {
+            match tokio::time::timeout_at(deadline, &mut execution).await {
+                Ok(Ok(())) => debug!("Task {} finished before shutdown", task_id),
+                Ok(Err(e)) if e.is_cancelled() => {}
+                Ok(Err(e)) => warn!("Execution of task {} panicked: {}", task_id, e),
+                Err(_) => {
+                    execution.abort();
+                    if let Err(e) = execution.await {
+                        if e.is_panic() {
+                            warn!("Execution of task {} panicked: {}", task_id, e);
+                        }
+                    }
+                    warn!(
+                        "Task {} was still running at shutdown and was aborted",
+                        task_id
+                    );
+                }
+            }
+        }
     }
 
     /// Start orchestrator with proper task lifecycle management
@@ -113,11 +384,7 @@ impl AgentOrchestrator {
             *sender_guard = Some(shutdown_signal_sender);
         }
 
-        let (result_sender, result_receiver) = mpsc::unbounded_channel();
-        {
-            let mut sender = self.result_sender.lock().await;
-            *sender = Some(result_sender);
-        }
+        let result_receiver = self.subscribe_results();
 
         // Start managed tasks with shutdown capability
         let handles = self
@@ -150,7 +417,7 @@ impl AgentOrchestrator {
     /// Start all orchestrator tasks with shutdown management
     async fn start_managed_tasks(
         &self,
-        mut result_receiver: mpsc::UnboundedReceiver<TaskResult>,
+        mut result_receiver: broadcast::Receiver<TaskResult>,
         mut shutdown_signal_receiver: mpsc::Receiver<()>,
     ) -> Vec<JoinHandle<()>> {
         let mut handles = Vec::new();
@@ -168,10 +435,13 @@ impl AgentOrchestrator {
                 tokio::select! {
                     result = result_receiver.recv() => {
                         match result {
-                            Some(result) => {
+                            Ok(result) => {
                                 info!("Received task result: {} - {:?}", result.task_id, result.result);
                             }
-                            None => break, // Channel closed
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                warn!("Result processor lagged, skipped {} result(s)", skipped);
+                            }
+                            Err(broadcast::error::RecvError::Closed) => break,
                         }
                     }
                     _ = shutdown_signal_receiver.recv() => {
@@ -190,6 +460,13 @@ impl AgentOrchestrator {
         });
         handles.push(handle);
 
+        // Scheduler with shutdown
+        let scheduler_orchestrator = self.clone();
+        let handle = tokio::spawn(async move {
+            scheduler_orchestrator.scheduler_loop_managed().await;
+        });
+        handles.push(handle);
+
         handles
     }
 
@@ -203,6 +480,16 @@ impl AgentOrchestrator {
             let _ = sender.send(()).await; // Ignore send errors if receiver is dropped
         }
 
+        // Session listeners only react to events, so there is nothing to wait for
+        for listener in self
+            .session_listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain(..)
+        {
+            listener.abort();
+        }
+
         // Wait for all tasks to complete with timeout
         let handles = {
             let mut handles_guard = self.task_handles.lock().await;
@@ -233,13 +520,22 @@ impl AgentOrchestrator {
             }
         }
 
+        // The processor has stopped, so no execution starts from here
+        self.stop_executions(shutdown_timeout).await;
+
         info!("Agent Orchestrator shutdown complete");
     }
 
     /// 📝 USER REQUEST ENTRY POINT: Where human intentions become agent tasks
     /// This is the primary interface between Discord/API and the agent system
     /// AUDIT CHECKPOINT: Verify task validation, queue management, and error handling
-    pub async fn submit_task(&self, mut task: Task) -> Result<String> {
+    pub async fn submit_task(&self, task: Task) -> Result<String> {
+        self.submit_task_as(task, Submitter::Standard).await
+    }
+
+    /// Submit a task on behalf of `submitter`, whose tasks may be entitled to the queue's
+    /// reserved slots
+    pub async fn submit_task_as(&self, mut task: Task, submitter: Submitter) -> Result<String> {
         debug!(
             "Submitting task: {} for agent: {:?}",
             task.id, task.agent_type
@@ -254,41 +550,146 @@ impl AgentOrchestrator {
             });
         }
 
+        // 💸 BUDGET CHECK: Refuse new work once today's Claude Code spend hits the budget
+        // Why: Tasks already queued or running still finish; only new spend is stopped
+        self.claude_client.costs().check_budget()?;
+
+        // 💸 SESSION BUDGET: A task created in a session counts against that session's budget
+        // Charged before the queue lock is taken, since it may wait on the session store
+        self.charge_session(&task).await?;
+
+        // 📊 STATE TRACKING: Mark task as pending and update timestamp for lifecycle management
+        // Why: Enables status queries, cleanup processes, and execution time tracking
+        task.status = TaskStatus::Pending;
+        task.updated_at = chrono::Utc::now();
+
+        let task_id = task.id.clone();
+
         // 🚦 BACKPRESSURE MECHANISM: Prevent system overload by limiting queue size
         // Why: Protects against memory exhaustion and provides responsive error feedback
-        // Current limit: Check constants.rs for MAX_QUEUE_SIZE value
+        // Current limit: Check constants.rs for MAX_QUEUE_SIZE value, less the slots
+        // reserved for urgent and admin tasks unless this is one
         // Alternative: Unlimited queue (rejected: potential OOM), Dynamic scaling (future enhancement)
-        {
-            let queue = self.task_queue.lock().await;
-            if queue.len() >= crate::constants::MAX_QUEUE_SIZE {
+        // The decision, eviction and insert share one queue lock hold, as in submit_batch,
+        // so concurrent submissions can neither all pass the check nor evict the same task
+        let evicted = {
+            let mut queue = self.task_queue.lock().await;
+            let evicted = match self.admission.decide(&queue, &task, submitter) {
+                Admission::Admit => None,
+                Admission::Evict(evicted_id) => {
+                    queue.retain(|queued| queued.id != evicted_id);
+                    Some(evicted_id)
+                }
+                Admission::Reject => {
+                    drop(queue);
+                    self.record_queue_rejection();
+                    self.refund_session(&task).await;
+                    return Err(SpiralError::Agent {
+                        message: "Task queue is full. Please try again later.".to_string(),
+                    });
+                }
+            };
+
+            // 💾 PERSISTENCE STRATEGY: Dual storage for queue processing and status queries
+            // Why: Queue for processing order, storage for external status API access
+            // Alternative: Single storage with status flags (rejected: complicates priority queue logic)
+            // Audit: Verify cleanup process removes from both locations (cleanup_loop method)
+            self.task_storage
+                .lock()
+                .await
+                .insert(task_id.clone(), task.clone());
+            // Under the queue lock, so no worker can record Started before Submitted
+            self.event_log
+                .record(&task_id, TaskEventKind::Submitted, None)
+                .await;
+            queue.push(task);
+            queue.sort_by(|a, b| {
+                b.priority
+                    .partial_cmp(&a.priority)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            evicted
+        };
+
+        if let Some(evicted_id) = evicted {
+            self.finish_evicted_task(&evicted_id, &task_id).await;
+        }
+
+        info!("Task {} submitted and queued", task_id);
+        Ok(task_id)
+    }
+
+    /// 📦 BATCH SUBMISSION: Enqueue a group of tasks all or nothing
+    /// 🏗️ ARCHITECTURE DECISION: Hold the queue lock across the capacity check and insert
+    /// Why: Checking and inserting separately would let concurrent submissions split a batch
+    /// Alternative: Submit one by one and roll back on failure (rejected: tasks could start
+    /// executing before the rollback)
+    pub async fn submit_batch(&self, tasks: Vec<Task>) -> Result<TaskBatch> {
+        if tasks.is_empty() {
+            return Err(SpiralError::Validation(
+                "Batch must contain at least one task".to_string(),
+            ));
+        }
+        if tasks.len() > crate::constants::MAX_BATCH_SIZE {
+            return Err(SpiralError::Validation(format!(
+                "Batch exceeds maximum size of {} tasks",
+                crate::constants::MAX_BATCH_SIZE
+            )));
+        }
+
+        // 🛡️ SAFETY CHECK: Reject the whole batch if any task targets a missing agent
+        for task in &tasks {
+            if !self.can_handle_agent_type(&task.agent_type).await {
                 return Err(SpiralError::Agent {
-                    message: "Task queue is full. Please try again later.".to_string(),
+                    message: format!("No agent available for type: {:?}", task.agent_type),
                 });
             }
         }
 
-        // 📊 STATE TRACKING: Mark task as pending and update timestamp for lifecycle management
-        // Why: Enables status queries, cleanup processes, and execution time tracking
-        task.status = TaskStatus::Pending;
-        task.updated_at = chrono::Utc::now();
+        self.claude_client.costs().check_budget()?;
 
-        let task_id = task.id.clone();
-
-        // 💾 PERSISTENCE STRATEGY: Dual storage for queue processing and status queries
-        // Why: Queue for processing order, storage for external status API access
-        // Alternative: Single storage with status flags (rejected: complicates priority queue logic)
-        // Audit: Verify cleanup process removes from both locations (cleanup_loop method)
-        {
-            let mut storage = self.task_storage.lock().await;
-            storage.insert(task_id.clone(), task.clone());
+        // 💸 SESSION BUDGET: Every task created in a session counts, as in submit_task_as;
+        // a batch the session can't afford in full is refused whole
+        for (charged, task) in tasks.iter().enumerate() {
+            if let Err(e) = self.charge_session(task).await {
+                for task in &tasks[..charged] {
+                    self.refund_session(task).await;
+                }
+                return Err(e);
+            }
         }
 
-        // 🎯 PRIORITY QUEUE MANAGEMENT: Higher priority tasks execute first
-        // Why: Ensures urgent tasks don't wait behind large batches of low-priority work
-        // Implementation: Rust sorts in ascending order, so we reverse compare (b vs a)
+        let batch = TaskBatch::new(tasks.iter().map(|task| task.id.clone()).collect());
+
         {
             let mut queue = self.task_queue.lock().await;
-            queue.push(task);
+            let limit = self.admission.batch_limit(&tasks, Submitter::Standard);
+            if queue.len() + tasks.len() > limit {
+                drop(queue);
+                self.record_queue_rejection();
+                for task in &tasks {
+                    self.refund_session(task).await;
+                }
+                return Err(SpiralError::QueueFull);
+            }
+
+            let mut storage = self.task_storage.lock().await;
+            for mut task in tasks {
+                task.status = TaskStatus::Pending;
+                task.updated_at = chrono::Utc::now();
+                task.context
+                    .insert("batch_id".to_string(), batch.id.clone());
+                storage.insert(task.id.clone(), task.clone());
+                // Under the queue lock, so no worker can record Started before Submitted
+                self.event_log
+                    .record(
+                        &task.id,
+                        TaskEventKind::Submitted,
+                        Some(format!("batch {}", batch.id)),
+                    )
+                    .await;
+                queue.push(task);
+            }
             queue.sort_by(|a, b| {
                 b.priority
                     .partial_cmp(&a.priority)
@@ -296,8 +697,213 @@ impl AgentOrchestrator {
             });
         }
 
-        info!("Task {} submitted and queued", task_id);
-        Ok(task_id)
+        self.batches
+            .lock()
+            .await
+            .insert(batch.id.clone(), batch.clone());
+
+        info!(
+            "Batch {} submitted with {} task(s)",
+            batch.id,
+            batch.task_ids.len()
+        );
+        Ok(batch)
+    }
+
+    pub async fn get_batch(&self, batch_id: &str) -> Option<TaskBatch> {
+        let batches = self.batches.lock().await;
+        batches.get(batch_id).cloned()
+    }
+
+    /// Aggregate status of a batch's tasks; tasks already removed by cleanup are not counted
+    pub async fn get_batch_status(&self, batch_id: &str) -> Option<TaskBatchStatus> {
+        let batch = self.get_batch(batch_id).await?;
+        let storage = self.task_storage.lock().await;
+        Some(TaskBatchStatus::from_statuses(
+            batch
+                .task_ids
+                .iter()
+                .filter_map(|task_id| storage.get(task_id).map(|task| &task.status)),
+        ))
+    }
+
+    /// 🎯 PRIORITY QUEUE MANAGEMENT: Higher priority tasks execute first
+    /// Why: Ensures urgent tasks don't wait behind large batches of low-priority work
+    /// Implementation: Rust sorts in ascending order, so we reverse compare (b vs a)
+    /// The sort is stable, so tasks of equal priority keep their submission order
+    async fn enqueue_task(&self, task: Task) {
+        let mut queue = self.task_queue.lock().await;
+        queue.push(task);
+        queue.sort_by(|a, b| {
+            b.priority
+                .partial_cmp(&a.priority)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    /// 🔁 RETRY SCHEDULING: Return a transiently failed task to the queue after backoff
+    /// Why: Claude Code timeouts and rate limits usually clear on their own
+    /// Alternative: Immediate re-queue (rejected: retries would hit the same outage)
+    /// Note: Re-queued tasks bypass MAX_QUEUE_SIZE because they were already admitted
+    async fn schedule_retry(&self, task_id: &str, reason: &str) -> Result<()> {
+        let task = self.atomic_state.retry_task_atomic(task_id).await?;
+        let delay = self.retry_policy.backoff_for(task.retry_count);
+        self.event_log
+            .record(
+                task_id,
+                TaskEventKind::Retried,
+                Some(format!("retry {} in {delay:?}: {reason}", task.retry_count)),
+            )
+            .await;
+
+        warn!(
+            "Task {} failed transiently ({}), retry {}/{} in {:?}",
+            task_id,
+            reason,
+            task.retry_count,
+            self.retry_policy.max_retries_for(&task),
+            delay
+        );
+
+        let orchestrator = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            // A cancel during the backoff leaves nothing to retry
+            let still_pending = orchestrator
+                .get_task_status(&task.id)
+                .await
+                .is_some_and(|stored| stored.status == TaskStatus::Pending);
+            if still_pending {
+                orchestrator.enqueue_task(task).await;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// 🛑 CANCELLATION: Stop a queued or running task and finish it as Cancelled
+    /// Running agents are aborted at their next await point, which also kills any Claude
+    /// Code process they started; partial workspace output is left in place
+    /// Subscribers receive a failure result so waiters such as workflows and streams finish
+    /// Returns the status the task had before it was cancelled
+    pub async fn cancel_task(&self, task_id: &str) -> Result<TaskStatus> {
+        self.finish_cancelled(task_id, "Task cancelled", None).await
+    }
+
+    /// 🎟️ QUEUE EVICTION: Finish a Low priority task taken off the queue to make room for
+    /// `admitted_id` as Cancelled with the reason, so its submitter hears about it
+    async fn finish_evicted_task(&self, task_id: &str, admitted_id: &str) {
+        let reason = format!("evicted from the full queue for higher priority task {admitted_id}");
+        match self
+            .finish_cancelled(task_id, "Task evicted from a full queue", Some(reason))
+            .await
+        {
+            Ok(_) => warn!("Task {} evicted to admit task {}", task_id, admitted_id),
+            Err(e) => warn!("Failed to evict task {}: {}", task_id, e),
+        }
+    }
+
+    /// Finish a task as Cancelled with `error` as its result, noting `reason` in its events
+    async fn finish_cancelled(
+        &self,
+        task_id: &str,
+        error: &str,
+        reason: Option<String>,
+    ) -> Result<TaskStatus> {
+        let task = self
+            .get_task_status(task_id)
+            .await
+            .ok_or_else(|| SpiralError::NotFound(format!("Task {task_id} not found")))?;
+        let task_result = TaskResult {
+            task_id: task_id.to_string(),
+            agent_type: task.agent_type,
+            result: TaskExecutionResult::Failure {
+                error: error.to_string(),
+                partial_output: None,
+            },
+            metadata: HashMap::new(),
+            completed_at: chrono::Utc::now(),
+            changes: Vec::new(),
+        };
+
+        // Mark it first so an execution finishing meanwhile cannot overwrite the cancel
+        let previous = self
+            .atomic_state
+            .cancel_task_atomic(task_id, task_result.clone())
+            .await?;
+        self.task_queue
+            .lock()
+            .await
+            .retain(|queued| queued.id != task_id);
+        let execution = self
+            .running_tasks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(task_id);
+        if let Some(execution) = execution {
+            execution.abort();
+        }
+        self.checkpoints.clear(task_id).await;
+
+        self.event_log
+            .record(
+                task_id,
+                TaskEventKind::Cancelled,
+                Some(reason.unwrap_or_else(|| format!("was {previous:?}"))),
+            )
+            .await;
+        self.publish_result(task_result);
+        info!("Task {} cancelled ({:?})", task_id, previous);
+        Ok(previous)
+    }
+
+    /// 🔁 MANUAL RETRY: Submit a failed or cancelled task again as a new task
+    /// The original keeps its outcome and history; the copy starts with a fresh ID, a
+    /// reset retry count and a `retry_of` context entry pointing back at it
+    /// Returns the new task's ID
+    pub async fn resubmit_task(&self, task_id: &str) -> Result<String> {
+        let original = self
+            .get_task_status(task_id)
+            .await
+            .ok_or_else(|| SpiralError::NotFound(format!("Task {task_id} not found")))?;
+        if !matches!(original.status, TaskStatus::Failed | TaskStatus::Cancelled) {
+            return Err(SpiralError::Validation(format!(
+                "Task {task_id} is {:?}; only failed or cancelled tasks can be retried",
+                original.status
+            )));
+        }
+
+        let mut retry = Task::new(original.agent_type, original.content, original.priority)
+            .with_context("retry_of".to_string(), task_id.to_string());
+        for (key, value) in original.context {
+            retry.context.entry(key).or_insert(value);
+        }
+        retry.max_retries = original.max_retries;
+
+        let retry_id = self.submit_task(retry).await?;
+        info!("Task {} resubmitted as {}", task_id, retry_id);
+        Ok(retry_id)
+    }
+
+    /// 📅 DEFERRED SUBMISSION: Register a task to be submitted later or repeatedly
+    /// Validation mirrors submit_task so bad schedules fail at creation, not at run time
+    pub async fn schedule_task(&self, task: Task, schedule: TaskSchedule) -> Result<ScheduledTask> {
+        if !self.can_handle_agent_type(&task.agent_type).await {
+            return Err(SpiralError::Agent {
+                message: format!("No agent available for type: {:?}", task.agent_type),
+            });
+        }
+
+        self.scheduler.schedule(task, schedule).await
+    }
+
+    pub async fn get_schedules(&self) -> Vec<ScheduledTask> {
+        self.scheduler.list().await
+    }
+
+    /// Stop a schedule; None when there is none with this ID
+    pub async fn remove_schedule(&self, schedule_id: &str) -> Option<ScheduledTask> {
+        self.scheduler.remove(schedule_id).await
     }
 
     pub async fn get_task_status(&self, task_id: &str) -> Option<Task> {
@@ -305,6 +911,16 @@ impl AgentOrchestrator {
         storage.get(task_id).cloned()
     }
 
+    /// Lifecycle history of the task, oldest first
+    pub async fn get_task_events(&self, task_id: &str) -> Option<Vec<TaskEvent>> {
+        self.event_log.events_for(task_id).await
+    }
+
+    /// Latest progress the executing agent reported, or None if it has reported none
+    pub async fn get_task_progress(&self, task_id: &str) -> Option<TaskProgress> {
+        self.task_progress.latest(task_id).await
+    }
+
     pub async fn get_task_result(&self, task_id: &str) -> Option<TaskResult> {
         let results = self.task_results.lock().await;
         results.get(task_id).cloned()
@@ -339,6 +955,57 @@ impl AgentOrchestrator {
         }
     }
 
+    /// Managed scheduler loop with graceful shutdown
+    /// Due tasks go through submit_task so queue limits and validation still apply
+    async fn scheduler_loop_managed(&self) {
+        info!("Starting managed scheduler loop");
+
+        loop {
+            if let Some(sender) = &*self.shutdown_signal_sender.lock().await {
+                if sender.is_closed() {
+                    info!("Scheduler loop shutting down gracefully");
+                    break;
+                }
+            }
+
+            for due in self.scheduler.take_due(chrono::Utc::now()).await {
+                let task_id = due.task.id.clone();
+                let Err(e) = self.submit_task(due.task).await else {
+                    continue;
+                };
+                // A one-shot schedule has no next run, so its task would be lost
+                let Some(one_shot) = due.one_shot else {
+                    warn!("Failed to submit scheduled task {}: {}", task_id, e);
+                    continue;
+                };
+                if self
+                    .scheduler
+                    .retry_later(one_shot, chrono::Utc::now())
+                    .await
+                {
+                    warn!(
+                        "Failed to submit scheduled task {}, retrying in {}s: {}",
+                        task_id,
+                        crate::constants::SCHEDULE_RETRY_DELAY_SECS,
+                        e
+                    );
+                } else {
+                    error!(
+                        "Dropped scheduled task {} after {} failed submissions: {}",
+                        task_id,
+                        crate::constants::SCHEDULE_MAX_SUBMIT_ATTEMPTS,
+                        e
+                    );
+                }
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(
+                crate::constants::SCHEDULER_POLL_INTERVAL_SECS,
+            ))
+            .await;
+        }
+    }
+
     /// 🧹 MEMORY MANAGEMENT: Automatic cleanup of historical data to prevent memory growth
     /// AUDIT CHECKPOINT: Verify retention policy doesn't remove active tasks or needed results
     async fn perform_cleanup(&self) -> Result<()> {
@@ -370,6 +1037,47 @@ impl AgentOrchestrator {
             }
         }
 
+        // 📦 BATCH CLEANUP: Drop old batches once none of their tasks are stored
+        {
+            let storage = self.task_storage.lock().await;
+            let mut batches = self.batches.lock().await;
+            batches.retain(|_, batch| {
+                batch.created_at > cutoff_time
+                    || batch.task_ids.iter().any(|id| storage.contains_key(id))
+            });
+        }
+
+        // 📜 EVENT LOG CLEANUP: History lives exactly as long as its task
+        {
+            let storage = self.task_storage.lock().await;
+            let removed = self
+                .event_log
+                .retain(|task_id| storage.contains_key(task_id))
+                .await;
+            if removed > 0 {
+                info!("Cleaned up event history for {} tasks", removed);
+            }
+            self.task_progress
+                .retain(|task_id| storage.contains_key(task_id))
+                .await;
+            self.webhooks
+                .retain(|task_id| storage.contains_key(task_id));
+        }
+
+        // 🔗 WORKFLOW CLEANUP: Drop finished runs past the retention window
+        {
+            let mut runs = self.workflow_runs.lock().await;
+            runs.retain(|_, run| {
+                !run.is_finished() || run.finished_at.is_some_and(|at| at > cutoff_time)
+            });
+        }
+
+        // 🗂️ SESSION WORKSPACE CLEANUP: Archive the workspaces of sessions that have ended
+        let released = self.release_ended_session_workspaces().await;
+        if released > 0 {
+            info!("Archived {} workspaces of ended sessions", released);
+        }
+
         // 📊 RESULT STORAGE CLEANUP: Remove old task results to free memory
         // Why: Results consume significant memory and become less relevant over time
         {
@@ -384,9 +1092,88 @@ impl AgentOrchestrator {
             }
         }
 
+        // 📁 LOG FILE CLEANUP: Delete log files past their retention, off the runtime since
+        // it walks the log directory
+        if let Err(e) = tokio::task::spawn_blocking(crate::logging::apply_retention).await {
+            warn!("Log retention sweep failed: {}", e);
+        }
+
         Ok(())
     }
 
+    /// 🗂️ SESSION WORKSPACE RELEASE: Archive each session-owned workspace whose session no
+    /// registered manager still holds live and no queued or running task works in
+    /// 🏗️ ARCHITECTURE DECISION: Scan the workspaces on disk and ask the session stores
+    /// Why: Sessions expire silently in their store, and the workspace directory is what
+    /// outlives a restart, so the scan also finds workspaces of sessions lost with it
+    /// Alternative: Expiry callbacks from the session stores (rejected: Redis expires keys
+    /// on its own and a memory store forgets everything on restart)
+    /// Trade-off: A workspace is archived up to one cleanup interval after its session ends
+    async fn release_ended_session_workspaces(&self) -> usize {
+        let managers = self.session_managers.managers();
+        // Until a manager registers, as Discord's may do late, every session looks ended
+        if managers.is_empty() {
+            return 0;
+        }
+        let keys = match self.claude_client.managed_session_keys().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                warn!("Failed to list session workspaces: {}", e);
+                return 0;
+            }
+        };
+        // A queued or running task still works in its workspace, whatever its session's state
+        let in_use: std::collections::HashSet<String> = {
+            let storage = self.task_storage.lock().await;
+            storage
+                .values()
+                .filter(|task| matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress))
+                .filter_map(|task| task.context.get(SESSION_CONTEXT_KEY).cloned())
+                .collect()
+        };
+
+        let mut released = 0;
+        for key in keys {
+            let Some(session_id) = session::session_of_workspace_key(&key) else {
+                continue;
+            };
+            if in_use.contains(&key) || Self::session_is_live(&managers, &session_id).await {
+                continue;
+            }
+            match self.claude_client.archive_session_workspace(&key).await {
+                Ok(Some(archive)) => {
+                    info!(
+                        "Archived workspace of ended session {} to {:?}",
+                        session_id, archive
+                    );
+                    released += 1;
+                }
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Failed to archive workspace of session {}: {}",
+                    session_id, e
+                ),
+            }
+        }
+        released
+    }
+
+    /// Whether any manager holds the session live; a store that can't be reached counts as
+    /// holding it, so its workspace is kept
+    async fn session_is_live(managers: &[SharedSessionManager], session_id: &uuid::Uuid) -> bool {
+        for manager in managers {
+            match manager.is_live(session_id).await {
+                Ok(false) => {}
+                Ok(true) => return true,
+                Err(e) => {
+                    warn!("Failed to check session {}: {}", session_id, e);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub async fn get_agent_status(&self, agent_type: &AgentType) -> Option<AgentStatus> {
         let statuses = self.agent_statuses.read().await;
         statuses.get(agent_type).cloned()
@@ -397,11 +1184,482 @@ impl AgentOrchestrator {
         statuses.clone()
     }
 
+    /// 📜 AGENT HISTORY: Tasks handled by one agent type, newest first
+    /// Covers the tasks still held in memory, so older ones age out with task cleanup
+    pub async fn get_agent_task_history(
+        &self,
+        agent_type: &AgentType,
+        status: Option<&TaskStatus>,
+        limit: usize,
+    ) -> Vec<AgentTaskRecord> {
+        self.task_history(
+            |task| {
+                &task.agent_type == agent_type && status.is_none_or(|status| &task.status == status)
+            },
+            limit,
+        )
+        .await
+        .into_iter()
+        .map(|(_, record)| record)
+        .collect()
+    }
+
+    /// 📜 REQUESTER HISTORY: Tasks whose context has `key` set to `value`, such as the
+    /// Discord user who asked for them, newest first and with the tasks themselves
+    pub async fn get_task_history_by_context(
+        &self,
+        key: &str,
+        value: &str,
+        limit: usize,
+    ) -> Vec<(Task, AgentTaskRecord)> {
+        self.task_history(
+            |task| task.context.get(key).is_some_and(|found| found == value),
+            limit,
+        )
+        .await
+    }
+
+    /// ⚙️ RUNNING TASKS: Tasks an agent is executing now, newest first
+    pub async fn get_running_tasks(&self) -> Vec<AgentTaskRecord> {
+        self.task_history(|task| task.status == TaskStatus::InProgress, usize::MAX)
+            .await
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    /// The record of one task, with its timing and outcome
+    pub async fn get_task_record(&self, task_id: &str) -> Option<AgentTaskRecord> {
+        let task = self.get_task_status(task_id).await?;
+        let events = self.event_log.events_for(task_id).await;
+        let result = self.get_task_result(task_id).await;
+        Some(AgentTaskRecord::new(
+            &task,
+            events.as_deref(),
+            result.as_ref(),
+        ))
+    }
+
+    async fn task_history(
+        &self,
+        filter: impl Fn(&Task) -> bool,
+        limit: usize,
+    ) -> Vec<(Task, AgentTaskRecord)> {
+        let mut tasks: Vec<Task> = {
+            let storage = self.task_storage.lock().await;
+            storage
+                .values()
+                .filter(|task| filter(task))
+                .cloned()
+                .collect()
+        };
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.created_at));
+        tasks.truncate(limit);
+
+        let mut history = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let events = self.event_log.events_for(&task.id).await;
+            let result = self.get_task_result(&task.id).await;
+            let record = AgentTaskRecord::new(&task, events.as_deref(), result.as_ref());
+            history.push((task, record));
+        }
+        history
+    }
+
+    /// Submissions refused because the queue was full, a whole batch counting once
+    pub fn queue_rejected_count(&self) -> u64 {
+        self.queue_rejections
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// 🎯 SLO REPORT: Each agent type's execution time percentiles and success rate over
+    /// the SLO window, and the objectives it is missing
+    pub fn slo_report(&self) -> SloReport {
+        self.task_outcomes.report()
+    }
+
+    fn record_queue_rejection(&self) {
+        self.queue_rejections
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
     pub async fn get_queue_length(&self) -> usize {
         let queue = self.task_queue.lock().await;
         queue.len()
     }
 
+    /// 📋 QUEUE SNAPSHOT: Pending tasks in dispatch order, next task first
+    pub async fn get_queue(&self) -> Vec<QueuedTask> {
+        let now = chrono::Utc::now();
+        let queue = self.task_queue.lock().await;
+        queue
+            .iter()
+            .enumerate()
+            .map(|(index, task)| QueuedTask::from_queue(index, task, now))
+            .collect()
+    }
+
+    /// ↕️ QUEUE REORDER: Move a pending task one place without resubmitting it
+    /// Moving past a task of another priority gives the moved task that priority
+    pub async fn move_queued_task(
+        &self,
+        task_id: &str,
+        direction: QueueMove,
+    ) -> Result<QueuedTask> {
+        let entry = {
+            let mut queue = self.task_queue.lock().await;
+            let index = queue
+                .iter()
+                .position(|task| task.id == task_id)
+                .ok_or_else(|| SpiralError::NotFound(format!("Queued task {task_id}")))?;
+
+            let new_index = queue_view::move_task(&mut queue, index, direction);
+            if new_index == index {
+                return Ok(QueuedTask::from_queue(
+                    index,
+                    &queue[index],
+                    chrono::Utc::now(),
+                ));
+            }
+
+            // Keep status queries in step with the priority the queue now uses
+            let priority = queue[new_index].priority.clone();
+            if let Some(stored) = self.task_storage.lock().await.get_mut(task_id) {
+                stored.priority = priority;
+            }
+            QueuedTask::from_queue(new_index, &queue[new_index], chrono::Utc::now())
+        };
+
+        let verb = match direction {
+            QueueMove::Promote => "promoted",
+            QueueMove::Demote => "demoted",
+        };
+        self.event_log
+            .record(
+                task_id,
+                TaskEventKind::Reordered,
+                Some(format!(
+                    "{verb} to position {} at {:?} priority",
+                    entry.position, entry.priority
+                )),
+            )
+            .await;
+        info!(
+            "Task {} {} to queue position {}",
+            task_id, verb, entry.position
+        );
+        Ok(entry)
+    }
+
+    /// 🔁 The retry policy, for callers capping a requested retry budget
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Number of tasks currently executing across all worker pools
+    pub fn get_in_flight_count(&self) -> usize {
+        self.worker_pools.total_in_flight()
+    }
+
+    /// 📡 RESULT SUBSCRIPTION: Receive every final TaskResult as it is produced
+    /// 🏗️ ARCHITECTURE DECISION: tokio broadcast channel instead of a single mpsc consumer
+    /// Why: The API server, Discord bot and future integrations each need their own stream
+    /// Alternative: Polling get_task_result (rejected: latency and lock contention per poll)
+    /// Slow subscribers receive RecvError::Lagged and should fall back to get_task_result
+    pub fn subscribe_results(&self) -> broadcast::Receiver<TaskResult> {
+        self.result_broadcaster.subscribe()
+    }
+
+    /// 📡 LIVE UPDATES: Lifecycle events for every task as they are recorded
+    pub fn subscribe_task_events(&self) -> broadcast::Receiver<TaskEventUpdate> {
+        self.event_log.subscribe()
+    }
+
+    /// 📡 LIVE UPDATES: Progress reports for every task as agents make them
+    pub fn subscribe_task_progress(&self) -> broadcast::Receiver<TaskProgressUpdate> {
+        self.task_progress.subscribe()
+    }
+
+    fn publish_result(&self, task_result: TaskResult) {
+        self.webhooks.notify(&task_result);
+        self.settle_session(&task_result);
+        // A send error only means nobody is subscribed right now
+        if self.result_broadcaster.send(task_result).is_err() {
+            debug!("No result subscribers, result not broadcast");
+        }
+    }
+
+    /// 🔔 TASK CALLBACK: POST the task's result to `callback_url` once it finishes
+    /// Register before submitting the task so an instant result still finds it
+    pub fn register_webhook(&self, task_id: &str, callback_url: &str) -> Result<()> {
+        self.webhooks.register(task_id, callback_url)
+    }
+
+    /// Drop the callback of a task whose submission failed
+    pub fn unregister_webhook(&self, task_id: &str) {
+        self.webhooks.unregister(task_id);
+    }
+
+    /// 🗂️ SESSION WORKSPACES: Let the cleanup loop archive the workspaces of `sessions`
+    /// once they end, and cancel a session's unfinished tasks when it is terminated; each
+    /// front end that opens sessions registers its manager
+    pub fn register_session_manager(&self, sessions: SharedSessionManager) {
+        let listener = tokio::spawn(self.clone().follow_session_events(sessions.subscribe()));
+        self.session_listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(listener);
+        self.session_managers.register(sessions);
+    }
+
+    /// The session managers registered so far and any registered later, for subsystems
+    /// that look after every front end's sessions
+    pub fn session_registry(&self) -> SessionRegistry {
+        self.session_managers.clone()
+    }
+
+    /// The session a task was created in and the manager holding it, if it still exists
+    async fn session_of_task(
+        &self,
+        task: &Task,
+    ) -> Option<(SharedSessionManager, session::Session)> {
+        let session_id = task
+            .context
+            .get(USER_SESSION_CONTEXT_KEY)
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())?;
+        for manager in self.session_managers.managers() {
+            match manager.get_session(&session_id).await {
+                Ok(Some(session)) => return Some((manager, session)),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to look up session {}: {}", session_id, e),
+            }
+        }
+        None
+    }
+
+    /// 💸 SESSION BUDGET: Count a task against its session's budget, refusing it once the
+    /// budget is used up; a store that can't be reached lets the task through
+    async fn charge_session(&self, task: &Task) -> Result<()> {
+        let Some((manager, session)) = self.session_of_task(task).await else {
+            return Ok(());
+        };
+        let workspace_mb = self
+            .claude_client
+            .session_workspace_mb(&session.workspace_key())
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to measure workspace of session {}: {}",
+                    session.id, e
+                );
+                0
+            });
+        match manager.charge_task(&session.id, workspace_mb).await {
+            Ok(_) => Ok(()),
+            Err(e @ SpiralError::RateLimit { .. }) => Err(e),
+            Err(e) => {
+                warn!("Failed to charge task to session {}: {}", session.id, e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Give back the session budget charged for a task the queue then refused
+    async fn refund_session(&self, task: &Task) {
+        let Some((manager, session)) = self.session_of_task(task).await else {
+            return;
+        };
+        if let Err(e) = manager.refund_task(&session.id).await {
+            warn!("Failed to refund task to session {}: {}", session.id, e);
+        }
+    }
+
+    /// Add a finished task's Claude Code spend and its request and answer to its session,
+    /// in the background since the result is published from synchronous code
+    fn settle_session(&self, task_result: &TaskResult) {
+        let answer = match &task_result.result {
+            TaskExecutionResult::Success { output, .. } => {
+                session::history::summarize_outcome(output).to_string()
+            }
+            TaskExecutionResult::Failure { error, .. } => {
+                format!("Failed: {}", session::history::summarize_outcome(error))
+            }
+        };
+        let task_id = task_result.task_id.clone();
+        let orchestrator = self.clone();
+        tokio::spawn(async move {
+            let Some(task) = orchestrator.get_task_status(&task_id).await else {
+                return;
+            };
+            let Some((manager, session)) = orchestrator.session_of_task(&task).await else {
+                return;
+            };
+            if let Err(e) = manager
+                .record_exchange(&session.id, &task.content, &task.agent_type, &answer)
+                .await
+            {
+                warn!("Failed to record history of session {}: {}", session.id, e);
+            }
+            let cost_usd = orchestrator
+                .claude_client
+                .costs()
+                .task_totals(&task_id)
+                .cost_usd;
+            if cost_usd <= 0.0 {
+                return;
+            }
+            if let Err(e) = manager.record_cost(&session.id, cost_usd).await {
+                warn!("Failed to record spend of session {}: {}", session.id, e);
+            }
+        });
+    }
+
+    /// 💬 SESSION HISTORY: Copy of the task with its session's recent conversation in the
+    /// context, read at each attempt so a follow-up sees the task it follows up on
+    async fn with_session_history(&self, mut task: Task) -> Task {
+        task.context.remove(SESSION_HISTORY_CONTEXT_KEY);
+        let Some((manager, session)) = self.session_of_task(&task).await else {
+            return task;
+        };
+        match manager.history(&session.id).await {
+            Ok(history) => {
+                if let Some(rendered) =
+                    history.render_for_context(crate::validation::MAX_CONTEXT_VALUE_LENGTH)
+                {
+                    task.context
+                        .insert(SESSION_HISTORY_CONTEXT_KEY.to_string(), rendered);
+                }
+            }
+            // Like memory, history is an aid; a store failure should not fail the task
+            Err(e) => warn!("Failed to read history of session {}: {}", session.id, e),
+        }
+        task
+    }
+
+    /// 📣 SESSION EVENTS: Ending a session on purpose abandons the work queued in it, while
+    /// an expired session's tasks run to completion, since the user never asked to stop them
+    async fn follow_session_events(self, mut events: broadcast::Receiver<SessionEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(SessionEvent::Terminated(session)) => {
+                    let cancelled = self.cancel_session_tasks(&session.id).await;
+                    if cancelled > 0 {
+                        info!(
+                            "Cancelled {} task(s) of terminated session {}",
+                            cancelled, session.id
+                        );
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Missed {} session event(s)", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// 🛑 SESSION CANCELLATION: Cancel the queued and running tasks created in a session
+    /// Returns how many were cancelled
+    pub async fn cancel_session_tasks(&self, session_id: &uuid::Uuid) -> usize {
+        let session_id = session_id.to_string();
+        let task_ids: Vec<String> = self
+            .task_storage
+            .lock()
+            .await
+            .values()
+            .filter(|task| {
+                matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress)
+                    && task.context.get(USER_SESSION_CONTEXT_KEY) == Some(&session_id)
+            })
+            .map(|task| task.id.clone())
+            .collect();
+
+        let mut cancelled = 0;
+        for task_id in task_ids {
+            match self.cancel_task(&task_id).await {
+                Ok(_) => cancelled += 1,
+                // It finished between the scan and the cancel
+                Err(SpiralError::Validation(_) | SpiralError::NotFound(_)) => {}
+                Err(e) => warn!("Failed to cancel task {}: {}", task_id, e),
+            }
+        }
+        cancelled
+    }
+
+    /// Delivery status of a task's callback, if it has one
+    pub fn get_webhook_delivery(&self, task_id: &str) -> Option<WebhookDelivery> {
+        self.webhooks.get(task_id)
+    }
+
+    /// Current dispatch state of the task processor
+    pub async fn get_dispatch_state(&self) -> DispatchState {
+        *self.dispatch_state.read().await
+    }
+
+    /// ⏸️ PAUSE DISPATCH: Stop starting new tasks while letting in-flight work continue
+    /// Submissions are still accepted and wait in the queue until resume()
+    pub async fn pause(&self) {
+        let mut state = self.dispatch_state.write().await;
+        if *state == DispatchState::Running {
+            info!("Task dispatch paused");
+            *state = DispatchState::Paused;
+        }
+    }
+
+    /// ▶️ RESUME DISPATCH: Return to normal operation from paused or draining
+    pub async fn resume(&self) {
+        let mut state = self.dispatch_state.write().await;
+        if *state != DispatchState::Running {
+            info!("Task dispatch resumed");
+            *state = DispatchState::Running;
+        }
+    }
+
+    /// 🚰 DRAIN: Pause dispatch and wait until every in-flight task has finished
+    /// 🏗️ ARCHITECTURE DECISION: Poll the worker pools instead of tracking completions
+    /// Why: Worker permits are already the source of truth for in-flight work
+    /// Alternative: Completion notifications (rejected: retries re-enter the queue, so
+    /// a notification per task would not mean the system is idle)
+    /// Dispatch stays paused after draining; call resume() to continue
+    pub async fn drain(&self, max_wait: Duration) -> Result<()> {
+        *self.dispatch_state.write().await = DispatchState::Draining;
+        info!(
+            "Draining orchestrator: waiting for {} in-flight task(s)",
+            self.get_in_flight_count()
+        );
+
+        let drained = timeout(max_wait, async {
+            while self.get_in_flight_count() > 0 {
+                tokio::time::sleep(Duration::from_millis(
+                    crate::constants::TASK_POLL_INTERVAL_MS,
+                ))
+                .await;
+            }
+        })
+        .await;
+
+        let mut state = self.dispatch_state.write().await;
+        // 🛡️ SAFETY CHECK: A resume() during the drain wins over the drain completing
+        if *state == DispatchState::Draining {
+            *state = DispatchState::Paused;
+        }
+        drop(state);
+
+        match drained {
+            Ok(()) => {
+                info!("Orchestrator drained, dispatch paused");
+                Ok(())
+            }
+            Err(_) => Err(SpiralError::Timeout {
+                message: format!(
+                    "Drain timed out after {max_wait:?} with {} task(s) still in flight",
+                    self.get_in_flight_count()
+                ),
+            }),
+        }
+    }
+
     async fn can_handle_agent_type(&self, agent_type: &AgentType) -> bool {
         let agents = self.agents.read().await;
         agents.contains_key(agent_type)
@@ -421,15 +1679,48 @@ impl AgentOrchestrator {
                 }
             }
 
-            let task = {
-                let mut queue = self.task_queue.lock().await;
-                queue.pop()
-            };
+            if self.get_dispatch_state().await != DispatchState::Running {
+                tokio::time::sleep(tokio::time::Duration::from_millis(
+                    crate::constants::TASK_POLL_INTERVAL_MS,
+                ))
+                .await;
+                continue;
+            }
 
-            if let Some(task) = task {
-                if let Err(e) = self.execute_task(task).await {
-                    error!("Failed to execute task: {}", e);
-                }
+            if let Some((task, permit)) = self.next_dispatchable_task().await {
+                let waited = chrono::Utc::now() - task.updated_at;
+                self.event_log
+                    .record(
+                        &task.id,
+                        TaskEventKind::Dequeued,
+                        Some(format!(
+                            "waited {:.1}s in queue",
+                            waited.num_milliseconds() as f64 / 1000.0
+                        )),
+                    )
+                    .await;
+                // ⚡ PARALLEL DISPATCH: Each task runs on its own tokio task while holding
+                // a worker slot; dropping the permit frees the slot for the next task
+                let orchestrator = self.clone();
+                let task_id = task.id.clone();
+                // Held across the spawn so the task cannot deregister before it registers
+                let mut running = self
+                    .running_tasks
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                let execution = tokio::spawn(async move {
+                    let _permit = permit;
+                    let task_id = task.id.clone();
+                    if let Err(e) = orchestrator.execute_task(task).await {
+                        error!("Failed to execute task: {}", e);
+                    }
+                    orchestrator
+                        .running_tasks
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .remove(&task_id);
+                });
+                running.insert(task_id, execution);
             } else {
                 tokio::time::sleep(tokio::time::Duration::from_millis(
                     crate::constants::TASK_POLL_INTERVAL_MS,
@@ -439,6 +1730,19 @@ impl AgentOrchestrator {
         }
     }
 
+    /// 🎯 WORKER POOL DISPATCH: Take the highest-priority task whose agent has a free slot
+    /// Why: A saturated agent type must not block queued work for other agent types
+    /// Implementation: The queue is sorted with the next task at the front, so scan forward
+    async fn next_dispatchable_task(&self) -> Option<(Task, OwnedSemaphorePermit)> {
+        let mut queue = self.task_queue.lock().await;
+        for index in 0..queue.len() {
+            if let Some(permit) = self.worker_pools.try_acquire(&queue[index].agent_type) {
+                return Some((queue.remove(index), permit));
+            }
+        }
+        None
+    }
+
     /// 🎬 TASK EXECUTION ORCHESTRATION: Where agent capabilities meet user requests
     /// This is the core coordination logic that manages agent execution and state tracking
     /// AUDIT CHECKPOINT: Critical path for all task processing - verify error handling and state consistency
@@ -469,16 +1773,41 @@ impl AgentOrchestrator {
                         warn!("Failed to start task atomically: {}", e);
                         return Err(e);
                     }
+                    self.event_log
+                        .record(
+                            &task.id,
+                            TaskEventKind::Started,
+                            Some(format!("attempt {}", task.retry_count + 1)),
+                        )
+                        .await;
 
                     // ⏱️ EXECUTION TIMING: Critical for performance analysis and SLA monitoring
                     // Why: Enables identification of slow operations and capacity planning
                     let start_time = std::time::Instant::now();
-                    let result = agent.execute(task.clone()).await;
+                    let handle = OrchestratorHandle::for_task(self.clone(), &task).await;
+                    let result = agent
+                        .execute(
+                            self.with_session_history(self.with_recalled_memory(&task).await)
+                                .await,
+                            handle,
+                        )
+                        .await;
+                    // The attempt is over; a retry saves fresh state if it gets interrupted
+                    self.checkpoints.clear(&task.id).await;
                     let execution_time = start_time.elapsed().as_secs_f64();
 
                     // 🎯 RESULT PROCESSING: Success and failure paths with atomic state management
                     match result {
                         Ok(task_result) => {
+                            // 🔁 RETRY CHECK: Agents report most failures as Failure results
+                            if let TaskExecutionResult::Failure { error, .. } = &task_result.result
+                            {
+                                let class = RetryPolicy::classify_message(error);
+                                if self.retry_policy.should_retry(&task, class) {
+                                    return self.schedule_retry(&task.id, error).await;
+                                }
+                            }
+
                             // ✅ SUCCESS PATH: Atomically update all tracking systems
                             // Why: Ensures consistent state even if process crashes mid-update
                             if let Err(e) = self
@@ -489,17 +1818,35 @@ impl AgentOrchestrator {
                                 error!("Failed to complete task atomically: {}", e);
                                 // Attempt cleanup to prevent zombie task
                                 self.atomic_state.cleanup_task_state(&task.id).await;
+                                self.event_log
+                                    .record(&task.id, TaskEventKind::CleanedUp, Some(e.to_string()))
+                                    .await;
                                 return Err(e);
                             }
 
+                            let (kind, detail) = match &task_result.result {
+                                TaskExecutionResult::Success { .. } => {
+                                    ProgressReporter::new(self.task_progress.clone(), &task.id)
+                                        .report(100, "completed")
+                                        .await;
+                                    (TaskEventKind::Completed, None)
+                                }
+                                TaskExecutionResult::Failure { error, .. } => {
+                                    (TaskEventKind::Failed, Some(error.clone()))
+                                }
+                            };
+                            self.event_log.record(&task.id, kind, detail).await;
+                            self.task_outcomes.record(
+                                &task.agent_type,
+                                Duration::from_secs_f64(execution_time),
+                                kind == TaskEventKind::Completed,
+                            );
+                            self.record_task_memory(&task, &task_result).await;
+
                             // 📢 RESULT BROADCASTING: Notify interested subscribers
                             // Why: Enables real-time notifications and downstream processing
                             // Alternative: Polling (rejected: higher latency, resource waste)
-                            if let Some(sender) = &*self.result_sender.lock().await {
-                                if let Err(e) = sender.send(task_result) {
-                                    error!("Failed to send task result: {}", e);
-                                }
-                            }
+                            self.publish_result(task_result);
 
                             info!(
                                 "Task {} completed successfully in {:.2}s",
@@ -508,6 +1855,13 @@ impl AgentOrchestrator {
                             Ok(())
                         }
                         Err(e) => {
+                            if self
+                                .retry_policy
+                                .should_retry(&task, RetryPolicy::classify_error(&e))
+                            {
+                                return self.schedule_retry(&task.id, &e.to_string()).await;
+                            }
+
                             // ❌ FAILURE PATH: Atomic error state management
                             // Why: Prevents partial state updates on failure
                             if let Err(atomic_err) = self
@@ -521,8 +1875,38 @@ impl AgentOrchestrator {
                                 );
                                 // Attempt cleanup to prevent zombie task
                                 self.atomic_state.cleanup_task_state(&task.id).await;
+                                self.event_log
+                                    .record(
+                                        &task.id,
+                                        TaskEventKind::CleanedUp,
+                                        Some(atomic_err.to_string()),
+                                    )
+                                    .await;
+                            } else {
+                                self.event_log
+                                    .record(&task.id, TaskEventKind::Failed, Some(e.to_string()))
+                                    .await;
                             }
 
+                            self.task_outcomes.record(
+                                &task.agent_type,
+                                Duration::from_secs_f64(execution_time),
+                                false,
+                            );
+
+                            // Subscribers still hear about tasks that errored before producing a result
+                            self.publish_result(TaskResult {
+                                task_id: task.id.clone(),
+                                agent_type: task.agent_type.clone(),
+                                result: TaskExecutionResult::Failure {
+                                    error: e.to_string(),
+                                    partial_output: None,
+                                },
+                                metadata: HashMap::new(),
+                                completed_at: chrono::Utc::now(),
+                                changes: Vec::new(),
+                            });
+
                             error!("Task {} failed: {}", task.id, e);
                             Err(e)
                         }
@@ -540,6 +1924,332 @@ impl AgentOrchestrator {
         }
     }
 
+    /// 🧠 MEMORY STORE: Save a convention, preference or summary for later tasks in its scope
+    pub async fn remember(&self, entry: MemoryEntry) -> Result<()> {
+        self.memory.remember(entry).await
+    }
+
+    /// Memories the agent would see for a task in these scopes, newest first
+    pub async fn recall_memories(
+        &self,
+        agent_type: &AgentType,
+        scopes: &[MemoryScope],
+        limit: usize,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.memory.recall(agent_type, scopes, limit).await
+    }
+
+    pub async fn memory_entry(&self, id: &str) -> Result<Option<MemoryEntry>> {
+        self.memory.entry(id).await
+    }
+
+    pub async fn forget_memory(&self, id: &str) -> Result<bool> {
+        self.memory.forget(id).await
+    }
+
+    /// Copy of the task with the agent's memories for its scopes in the context
+    /// The stored task is left untouched so each attempt recalls afresh
+    async fn with_recalled_memory(&self, task: &Task) -> Task {
+        let mut task = task.clone();
+        task.context.remove(AGENT_MEMORY_CONTEXT_KEY);
+
+        let scopes = MemoryScope::for_task(&task);
+        if scopes.is_empty() {
+            return task;
+        }
+
+        match self
+            .memory
+            .recall(
+                &task.agent_type,
+                &scopes,
+                crate::constants::AGENT_MEMORY_RECALL_LIMIT,
+            )
+            .await
+        {
+            Ok(entries) => {
+                if let Some(rendered) = memory::render_for_context(
+                    &entries,
+                    crate::validation::MAX_CONTEXT_VALUE_LENGTH,
+                ) {
+                    task.context
+                        .insert(AGENT_MEMORY_CONTEXT_KEY.to_string(), rendered);
+                }
+            }
+            // Memory is an aid, a storage failure should not fail the task
+            Err(e) => warn!("Failed to recall memory for task {}: {}", task.id, e),
+        }
+        task
+    }
+
+    /// Summarize a successful task into each of its scopes, keeping only the newest summaries
+    async fn record_task_memory(&self, task: &Task, task_result: &TaskResult) {
+        let TaskExecutionResult::Success { output, .. } = &task_result.result else {
+            return;
+        };
+        let summary =
+            memory::summarize_task(task, output, crate::constants::AGENT_MEMORY_SUMMARY_CHARS);
+
+        for scope in MemoryScope::for_task(task) {
+            let entry = MemoryEntry::new(
+                task.agent_type.clone(),
+                scope.clone(),
+                MemoryKind::TaskSummary,
+                summary.clone(),
+            );
+            let stored = match self.memory.remember(entry).await {
+                Ok(()) => {
+                    self.memory
+                        .prune(
+                            &task.agent_type,
+                            &scope,
+                            MemoryKind::TaskSummary,
+                            crate::constants::AGENT_MEMORY_SUMMARIES_PER_SCOPE,
+                        )
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = stored {
+                warn!("Failed to remember task {} for {:?}: {}", task.id, scope, e);
+            }
+        }
+    }
+
+    /// Configured workflow definitions by name
+    pub fn get_workflows(&self) -> HashMap<String, Vec<AgentType>> {
+        self.workflows.as_ref().clone()
+    }
+
+    pub async fn get_workflow_run(&self, run_id: &str) -> Option<WorkflowRun> {
+        let runs = self.workflow_runs.lock().await;
+        runs.get(run_id).cloned()
+    }
+
+    /// 🔗 WORKFLOW START: Run a named agent pipeline on one request in the background
+    /// The request task supplies content, priority, context and retry budget; its
+    /// agent_type is ignored in favour of each step's agent
+    pub async fn run_workflow(&self, name: &str, request: Task) -> Result<WorkflowRun> {
+        let steps = self
+            .workflows
+            .get(name)
+            .ok_or_else(|| SpiralError::NotFound(format!("Workflow {name}")))?;
+
+        // 🛡️ SAFETY CHECK: Fail before the first step rather than midway through the pipeline
+        for agent_type in steps {
+            if !self.can_handle_agent_type(agent_type).await {
+                return Err(SpiralError::Validation(format!(
+                    "Workflow {name} uses unavailable agent {agent_type:?}"
+                )));
+            }
+        }
+
+        let run = WorkflowRun::new(name, steps);
+        self.workflow_runs
+            .lock()
+            .await
+            .insert(run.id.clone(), run.clone());
+        info!(
+            "Workflow {} started as run {} with {} step(s)",
+            name,
+            run.id,
+            steps.len()
+        );
+
+        let orchestrator = self.clone();
+        let run_id = run.id.clone();
+        tokio::spawn(async move {
+            orchestrator.execute_workflow(&run_id, request).await;
+        });
+
+        Ok(run)
+    }
+
+    /// Execute workflow steps in order, stopping at the first failed step
+    async fn execute_workflow(&self, run_id: &str, request: Task) {
+        let Some(run) = self.get_workflow_run(run_id).await else {
+            return;
+        };
+        let mut previous: Option<(AgentType, String)> = None;
+        let parent_session = self.session_of_task(&request).await;
+
+        for index in 0..run.steps.len() {
+            let mut task = run.step_task(
+                index,
+                &request,
+                previous
+                    .as_ref()
+                    .map(|(agent_type, output)| (agent_type, output.as_str())),
+            );
+            let mut step_session = None;
+            if let Some((sessions, parent)) = &parent_session {
+                match Self::open_step_session(sessions, parent, &task.agent_type).await {
+                    Ok(child) => {
+                        task = child.apply_to_task(task);
+                        step_session = Some(child.id.to_string());
+                    }
+                    // The step still runs, counted against the parent session
+                    Err(e) => warn!(
+                        "Failed to open a session for step {} of run {}: {}",
+                        index + 1,
+                        run_id,
+                        e
+                    ),
+                }
+            }
+            let task_id = task.id.clone();
+            let agent_type = task.agent_type.clone();
+
+            // Subscribe before submitting so a fast result cannot be missed
+            let results = self.subscribe_results();
+            if let Err(e) = self.submit_task(task).await {
+                self.finish_workflow_step(run_id, index, Err(format!("Submit failed: {e}")))
+                    .await;
+                return;
+            }
+            self.update_workflow_run(run_id, |run| {
+                run.steps[index].status = WorkflowStepStatus::Running;
+                run.steps[index].task_id = Some(task_id.clone());
+                run.steps[index].session_id = step_session;
+            })
+            .await;
+
+            let step_timeout = Duration::from_secs(crate::constants::WORKFLOW_STEP_TIMEOUT_SECS);
+            let outcome = match timeout(step_timeout, self.wait_for_result(results, &task_id)).await
+            {
+                Ok(Ok(result)) => match result.result {
+                    TaskExecutionResult::Success { output, .. } => Ok(output),
+                    TaskExecutionResult::Failure { error, .. } => Err(error),
+                },
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => Err(format!(
+                    "Step task {task_id} produced no result within {step_timeout:?}"
+                )),
+            };
+
+            match outcome {
+                Ok(output) => {
+                    self.finish_workflow_step(run_id, index, Ok(output.clone()))
+                        .await;
+                    previous = Some((agent_type, output));
+                }
+                Err(error) => {
+                    self.finish_workflow_step(run_id, index, Err(error)).await;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 🌳 STEP SESSION: A child of the run's session for one agent's step, so the session's
+    /// collaboration tree shows which agent did what and ending it stops every step
+    /// The step keeps working in the parent's workspace, where earlier steps left files
+    async fn open_step_session(
+        sessions: &SharedSessionManager,
+        parent: &session::Session,
+        agent_type: &AgentType,
+    ) -> Result<session::Session> {
+        let child = sessions.create_child_session(&parent.id).await?;
+        sessions
+            .set_metadata(
+                &child.id,
+                session::AGENT_METADATA_KEY,
+                Some(format!("{agent_type:?}")),
+            )
+            .await
+    }
+
+    /// Record a step's outcome, finishing the run on failure or after the last step
+    async fn finish_workflow_step(
+        &self,
+        run_id: &str,
+        index: usize,
+        outcome: std::result::Result<String, String>,
+    ) {
+        self.update_workflow_run(run_id, |run| {
+            let is_last = index + 1 == run.steps.len();
+            match outcome {
+                Ok(output) => {
+                    run.steps[index].status = WorkflowStepStatus::Completed;
+                    if is_last {
+                        run.status = WorkflowRunStatus::Completed;
+                        run.final_output = Some(output);
+                        run.finished_at = Some(chrono::Utc::now());
+                    }
+                }
+                Err(error) => {
+                    warn!(
+                        "Workflow run {} failed at step {}: {}",
+                        run.id,
+                        index + 1,
+                        error
+                    );
+                    run.steps[index].status = WorkflowStepStatus::Failed;
+                    run.status = WorkflowRunStatus::Failed;
+                    run.error = Some(error);
+                    run.finished_at = Some(chrono::Utc::now());
+                }
+            }
+        })
+        .await;
+    }
+
+    async fn update_workflow_run(&self, run_id: &str, update: impl FnOnce(&mut WorkflowRun)) {
+        if let Some(run) = self.workflow_runs.lock().await.get_mut(run_id) {
+            update(run);
+        }
+    }
+
+    /// Wait on a result subscription for one task's final result
+    async fn wait_for_result(
+        &self,
+        mut results: broadcast::Receiver<TaskResult>,
+        task_id: &str,
+    ) -> Result<TaskResult> {
+        loop {
+            match results.recv().await {
+                Ok(result) if result.task_id == task_id => return Ok(result),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // Our result may have been among the skipped ones
+                    if let Some(result) = self.get_task_result(task_id).await {
+                        return Ok(result);
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    return Err(SpiralError::SystemState {
+                        message: "Result channel closed before the task finished".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// 🧭 CAPABILITY ROUTING: Assign the best-suited registered agent to a task, on its
+    /// content alone; the task's current agent_type is ignored
+    /// 🏗️ ARCHITECTURE DECISION: Route without task analysis
+    /// Why: Analysis is a Claude Code run; in the request path it holds the connection for
+    /// seconds per task, and a batch would start one run per entry
+    /// Trade-off: No complexity ceiling is checked, so a broad task may land on an agent
+    /// meant for smaller ones; callers who care name the agent
+    pub async fn route_task_by_content(&self, task: &mut Task) -> Result<AgentType> {
+        let agent_type =
+            CapabilityRouter::select(self.agent_capabilities().await, &task.content, None)
+                .ok_or_else(|| SpiralError::Validation("No agent is registered".to_string()))?;
+
+        info!("Task {} routed to {:?} by content", task.id, agent_type);
+        task.agent_type = agent_type.clone();
+        Ok(agent_type)
+    }
+
+    async fn agent_capabilities(&self) -> Vec<(AgentType, crate::models::AgentCapability)> {
+        let agents = self.agents.read().await;
+        agents
+            .iter()
+            .map(|(agent_type, agent)| (agent_type.clone(), agent.capabilities()))
+            .collect()
+    }
+
     pub async fn analyze_task(&self, task: &Task) -> Result<crate::claude_code::TaskAnalysis> {
         let agents = self.agents.read().await;
         let agent = agents
@@ -551,6 +2261,14 @@ impl AgentOrchestrator {
         agent.analyze_task(task).await
     }
 
+    /// Whether a queued or running task works in the session's workspace
+    pub async fn session_in_use(&self, session_key: &str) -> bool {
+        self.task_storage.lock().await.values().any(|task| {
+            matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress)
+                && super::task_utils::session_key(task) == session_key
+        })
+    }
+
     /// 🔧 CLAUDE CLIENT ACCESS: Provide access to Claude Code client for shutdown cleanup
     /// DECISION: Return Result to handle case where client might be unavailable
     /// Why: Defensive programming for graceful degradation during shutdown
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex};
+use utoipa::ToSchema;
+
+/// Latest progress an agent reported for a running task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskProgress {
+    /// 0-100, agents' own estimate of how far along the task is
+    pub percent: u8,
+    /// Short name of the current step, e.g. "generating code"
+    pub phase: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A progress report as it is made, for live subscribers
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskProgressUpdate {
+    pub task_id: String,
+    #[serde(flatten)]
+    pub progress: TaskProgress,
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Latest-value store keyed by task ID, beside the event log
+/// Why: Callers polling progress want the current phase, not every update; the event log
+/// keeps the lifecycle transitions that matter for debugging
+/// Alternative: Progress entries in TaskEventLog (rejected: chatty agents would bury the
+/// lifecycle timeline)
+/// Retention: Entries are pruned alongside task storage in perform_cleanup
+/// Live: Every update is also broadcast to subscribers, e.g. API streams
+#[derive(Debug, Clone)]
+pub struct TaskProgressTracker {
+    progress: Arc<Mutex<HashMap<String, TaskProgress>>>,
+    updates: broadcast::Sender<TaskProgressUpdate>,
+}
+
+impl Default for TaskProgressTracker {
+    fn default() -> Self {
+        Self {
+            progress: Arc::default(),
+            updates: broadcast::channel(crate::constants::TASK_UPDATE_BROADCAST_CAPACITY).0,
+        }
+    }
+}
+
+impl TaskProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive progress as it is reported, for every task
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskProgressUpdate> {
+        self.updates.subscribe()
+    }
+
+    pub async fn update(&self, task_id: &str, progress: TaskProgress) {
+        let mut entries = self.progress.lock().await;
+        entries.insert(task_id.to_string(), progress.clone());
+        drop(entries);
+
+        // A send error only means nobody is subscribed right now
+        let _ = self.updates.send(TaskProgressUpdate {
+            task_id: task_id.to_string(),
+            progress,
+        });
+    }
+
+    pub async fn latest(&self, task_id: &str) -> Option<TaskProgress> {
+        let entries = self.progress.lock().await;
+        entries.get(task_id).cloned()
+    }
+
+    /// Drop progress for tasks the predicate no longer keeps, returning how many were removed
+    pub async fn retain(&self, mut keep: impl FnMut(&str) -> bool) -> usize {
+        let mut entries = self.progress.lock().await;
+        let initial_count = entries.len();
+        entries.retain(|task_id, _| keep(task_id));
+        initial_count - entries.len()
+    }
+}
+
+/// Lets an agent publish progress for the task it is executing
+/// Obtained from OrchestratorHandle::progress; reporting never fails the task
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    tracker: TaskProgressTracker,
+    task_id: String,
+}
+
+impl ProgressReporter {
+    pub fn new(tracker: TaskProgressTracker, task_id: impl Into<String>) -> Self {
+        Self {
+            tracker,
+            task_id: task_id.into(),
+        }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Report reaching a phase; percentages above 100 are clamped
+    pub async fn report(&self, percent: u8, phase: &str) {
+        self.publish(percent, phase, None).await;
+    }
+
+    /// Report a phase with a human-readable detail, e.g. which file is being written
+    pub async fn report_with_message(&self, percent: u8, phase: &str, message: impl Into<String>) {
+        self.publish(percent, phase, Some(message.into())).await;
+    }
+
+    async fn publish(&self, percent: u8, phase: &str, message: Option<String>) {
+        self.tracker
+            .update(
+                &self.task_id,
+                TaskProgress {
+                    percent: percent.min(100),
+                    phase: phase.to_string(),
+                    message,
+                    updated_at: Utc::now(),
+                },
+            )
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reporter_keeps_latest_progress() {
+        let tracker = TaskProgressTracker::new();
+        let mut updates = tracker.subscribe();
+        let reporter = ProgressReporter::new(tracker.clone(), "task-1");
+
+        reporter.report(10, "analyzing").await;
+        reporter
+            .report_with_message(250, "writing files", "src/main.rs")
+            .await;
+
+        let progress = tracker.latest("task-1").await.unwrap();
+        assert_eq!(progress.percent, 100);
+        assert_eq!(progress.phase, "writing files");
+        assert_eq!(progress.message.as_deref(), Some("src/main.rs"));
+        assert!(tracker.latest("task-2").await.is_none());
+        assert_eq!(updates.recv().await.unwrap().progress.phase, "analyzing");
+        assert_eq!(updates.recv().await.unwrap().task_id, "task-1");
+
+        assert_eq!(tracker.retain(|_| false).await, 1);
+        assert!(tracker.latest("task-1").await.is_none());
+    }
+}
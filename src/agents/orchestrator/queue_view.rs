@@ -0,0 +1,105 @@
+use crate::models::{AgentType, Priority, Task};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A pending task as seen from the queue, front of the queue first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QueuedTask {
+    /// 1-based; tasks whose worker pool is full may be overtaken by later ones
+    pub position: usize,
+    pub task_id: String,
+    pub agent_type: AgentType,
+    pub priority: Priority,
+    pub queued_at: DateTime<Utc>,
+    pub wait_secs: i64,
+    pub retry_count: u32,
+}
+
+impl QueuedTask {
+    pub fn from_queue(index: usize, task: &Task, now: DateTime<Utc>) -> Self {
+        Self {
+            position: index + 1,
+            task_id: task.id.clone(),
+            agent_type: task.agent_type.clone(),
+            priority: task.priority.clone(),
+            queued_at: task.updated_at,
+            wait_secs: (now - task.updated_at).num_seconds().max(0),
+            retry_count: task.retry_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueMove {
+    /// One place closer to the front
+    Promote,
+    /// One place further from the front
+    Demote,
+}
+
+/// Move the task at `index` one place and return its new index
+/// 🏗️ ARCHITECTURE DECISION: Crossing into another priority band adopts that band's priority
+/// Why: The queue is re-sorted by priority on every insert, so a move that left priorities
+/// out of order would be silently undone by the next submission
+/// Alternative: Separate manual ordering key (rejected: two orderings to reason about, and
+/// the API already shows priority as the reason a task runs first)
+pub fn move_task(queue: &mut [Task], index: usize, direction: QueueMove) -> usize {
+    let neighbour = match direction {
+        QueueMove::Promote if index > 0 => index - 1,
+        QueueMove::Demote if index + 1 < queue.len() => index + 1,
+        _ => return index,
+    };
+
+    if queue[neighbour].priority != queue[index].priority {
+        queue[index].priority = queue[neighbour].priority.clone();
+    }
+    queue.swap(index, neighbour);
+    neighbour
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> Vec<Task> {
+        [Priority::High, Priority::Medium, Priority::Medium]
+            .into_iter()
+            .enumerate()
+            .map(|(i, priority)| {
+                Task::new(AgentType::SoftwareDeveloper, format!("task {i}"), priority)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_move_within_priority_band_keeps_priority() {
+        let mut queue = queue();
+        let moved = queue[2].id.clone();
+
+        assert_eq!(move_task(&mut queue, 2, QueueMove::Promote), 1);
+        assert_eq!(queue[1].id, moved);
+        assert_eq!(queue[1].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_move_across_priority_band_adopts_priority() {
+        let mut queue = queue();
+
+        assert_eq!(move_task(&mut queue, 1, QueueMove::Promote), 0);
+        assert_eq!(queue[0].priority, Priority::High);
+        assert_eq!(queue[1].priority, Priority::High);
+
+        assert_eq!(move_task(&mut queue, 0, QueueMove::Demote), 1);
+        assert_eq!(queue[1].priority, Priority::High);
+        assert_eq!(move_task(&mut queue, 1, QueueMove::Demote), 2);
+        assert_eq!(queue[2].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_move_at_queue_ends_is_a_no_op() {
+        let mut queue = queue();
+        assert_eq!(move_task(&mut queue, 0, QueueMove::Promote), 0);
+        assert_eq!(move_task(&mut queue, 2, QueueMove::Demote), 2);
+    }
+}
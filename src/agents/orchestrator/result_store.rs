@@ -178,6 +178,7 @@ mod tests {
             },
             metadata: HashMap::new(),
             completed_at: chrono::Utc::now(),
+            changes: vec![],
         };
 
         store
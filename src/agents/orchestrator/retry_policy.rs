@@ -0,0 +1,185 @@
+use crate::{config::OrchestratorConfig, models::Task, SpiralError};
+use std::time::Duration;
+
+/// 🔁 TRANSIENT ERROR MARKERS: Substrings that identify failures worth retrying
+/// Why: Agents report failures as strings inside TaskExecutionResult::Failure, so
+/// classification has to work on messages as well as on SpiralError variants
+/// Alternative: Retry everything (rejected: validation/security failures never self-heal)
+const TRANSIENT_ERROR_MARKERS: &[&str] = &[
+    "temporarily unavailable",
+    "timed out",
+    "timeout",
+    "rate limit",
+    "overloaded",
+    "connection reset",
+    "connection refused",
+];
+
+/// ⚡ BACKOFF GROWTH: Each retry waits twice as long as the previous one
+/// Why: Standard exponential backoff gives Claude Code room to recover
+/// Alternative: Linear backoff (rejected: hammers a struggling service)
+const BACKOFF_MULTIPLIER: u32 = 2;
+
+/// Whether a failed task attempt should be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Likely to succeed if attempted again (timeouts, rate limits, outages)
+    Transient,
+    /// Will fail the same way every time (validation, security, config)
+    Permanent,
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Orchestrator-owned retry policy
+/// Why: Agents report failures, the orchestrator decides what happens next
+/// Alternative: Retry inside each agent (rejected: duplicated logic, invisible to status API)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl From<&OrchestratorConfig> for RetryPolicy {
+    fn from(config: &OrchestratorConfig) -> Self {
+        Self {
+            max_retries: config.max_task_retries,
+            initial_backoff: Duration::from_millis(config.retry_initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.retry_max_backoff_ms),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry budget for a task: a per-task override wins over the global policy, up to it
+    pub fn max_retries_for(&self, task: &Task) -> u32 {
+        task.max_retries
+            .map_or(self.max_retries, |requested| self.capped(requested))
+    }
+
+    /// 🛡️ A requested retry budget, lowered to the configured maximum
+    /// Why: Callers may ask for fewer retries, but never for more Claude Code runs than
+    /// TASK_MAX_RETRIES allows
+    pub fn capped(&self, requested: u32) -> u32 {
+        requested.min(self.max_retries)
+    }
+
+    /// Decide whether a task that just failed should be attempted again
+    pub fn should_retry(&self, task: &Task, class: FailureClass) -> bool {
+        class == FailureClass::Transient && task.retry_count < self.max_retries_for(task)
+    }
+
+    /// Delay before the given retry attempt (1-based), capped at max_backoff
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1);
+        let factor = BACKOFF_MULTIPLIER.saturating_pow(exponent);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Classify an error returned directly by an agent
+    pub fn classify_error(error: &SpiralError) -> FailureClass {
+        match error {
+            SpiralError::Timeout { .. }
+            | SpiralError::RateLimit { .. }
+            | SpiralError::SystemResource { .. }
+            | SpiralError::ClaudeCodeApi(_) => FailureClass::Transient,
+            SpiralError::Validation(_)
+            | SpiralError::Security(_)
+            | SpiralError::Config(_)
+            | SpiralError::ConfigurationError(_)
+            | SpiralError::WorkspaceQuotaExceeded(_)
+            | SpiralError::Unauthorized => FailureClass::Permanent,
+            other => Self::classify_message(&other.to_string()),
+        }
+    }
+
+    /// Classify a failure message reported inside a TaskExecutionResult
+    pub fn classify_message(message: &str) -> FailureClass {
+        let lowered = message.to_lowercase();
+        if TRANSIENT_ERROR_MARKERS
+            .iter()
+            .any(|marker| lowered.contains(marker))
+        {
+            FailureClass::Transient
+        } else {
+            FailureClass::Permanent
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, Priority};
+
+    fn test_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_and_caps() {
+        let policy = test_policy();
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff_for(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_classification() {
+        assert_eq!(
+            RetryPolicy::classify_message("Claude Code service is temporarily unavailable"),
+            FailureClass::Transient
+        );
+        assert_eq!(
+            RetryPolicy::classify_message("Failed to spawn Claude Code process"),
+            FailureClass::Permanent
+        );
+        assert_eq!(
+            RetryPolicy::classify_error(&SpiralError::Validation("bad".to_string())),
+            FailureClass::Permanent
+        );
+        assert_eq!(
+            RetryPolicy::classify_error(&SpiralError::Timeout {
+                message: "slow".to_string()
+            }),
+            FailureClass::Transient
+        );
+    }
+
+    #[test]
+    fn test_per_task_override_and_budget() {
+        let policy = test_policy();
+        let mut task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "retry me".to_string(),
+            Priority::Medium,
+        )
+        .with_max_retries(1);
+
+        assert!(policy.should_retry(&task, FailureClass::Transient));
+        assert!(!policy.should_retry(&task, FailureClass::Permanent));
+
+        task.retry_count = 1;
+        assert!(!policy.should_retry(&task, FailureClass::Transient));
+
+        // Overrides cannot raise the budget past the policy
+        let task = task.with_max_retries(u32::MAX);
+        assert_eq!(policy.max_retries_for(&task), 3);
+    }
+}
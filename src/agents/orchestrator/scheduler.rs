@@ -0,0 +1,563 @@
+use crate::{
+    constants::{DEFAULT_MAX_SCHEDULES, SCHEDULE_MAX_SUBMIT_ATTEMPTS, SCHEDULE_RETRY_DELAY_SECS},
+    models::Task,
+    Result, SpiralError,
+};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// 📅 CRON SEARCH HORIZON: Give up looking for the next run after this many days
+/// Why: Expressions like "0 0 31 2 *" never match; without a bound the search loops forever
+/// Calculation: Covers every weekday/leap-year combination (Feb 29 on a given weekday)
+const CRON_SEARCH_HORIZON_DAYS: i64 = 366 * 8;
+
+/// Context key attached to every task instance created by the scheduler
+pub const SCHEDULE_ID_CONTEXT_KEY: &str = "schedule_id";
+
+/// When a scheduled task should run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSchedule {
+    /// Run once at the given time (past times run on the next scheduler tick)
+    RunAt(DateTime<Utc>),
+    /// Run repeatedly on a 5-field cron expression evaluated in UTC
+    Cron(String),
+}
+
+/// A task template plus the schedule that produces task instances from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub id: String,
+    pub template: Task,
+    pub schedule: TaskSchedule,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub run_count: u64,
+    pub created_at: DateTime<Utc>,
+    /// Submissions of the current run that failed; only one-shot schedules retry
+    #[serde(default)]
+    pub failed_submissions: u32,
+}
+
+/// A task instance a schedule produced
+#[derive(Debug)]
+pub struct DueTask {
+    pub task: Task,
+    /// A one-shot schedule as it was before this run, to put back if the task cannot be
+    /// submitted; None for cron schedules, which run again at their next match anyway
+    pub one_shot: Option<ScheduledTask>,
+}
+
+impl ScheduledTask {
+    /// Build the task instance for the current run
+    /// The first run reuses the template ID so callers can poll the ID they were given
+    fn instantiate(&self) -> Task {
+        let mut task = if self.run_count == 0 {
+            self.template.clone()
+        } else {
+            let mut task = Task::new(
+                self.template.agent_type.clone(),
+                self.template.content.clone(),
+                self.template.priority.clone(),
+            );
+            task.context = self.template.context.clone();
+            task.max_retries = self.template.max_retries;
+            task
+        };
+
+        let now = Utc::now();
+        task.created_at = now;
+        task.updated_at = now;
+        task.context
+            .insert(SCHEDULE_ID_CONTEXT_KEY.to_string(), self.id.clone());
+        task
+    }
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Scheduler holds templates, orchestrator owns the queue
+/// Why: Scheduled work goes through submit_task like everything else, so validation,
+/// backpressure and retries apply uniformly
+/// Alternative: Insert future tasks into the queue with a not-before timestamp
+/// (rejected: every dispatcher scan would have to skip them)
+pub struct TaskScheduler {
+    entries: Arc<Mutex<HashMap<String, ScheduledTask>>>,
+    store_path: Option<PathBuf>,
+    /// Most schedules kept at once; restored ones count but are never dropped
+    max_schedules: usize,
+}
+
+impl TaskScheduler {
+    /// Create a scheduler, restoring any schedules persisted at `store_path`
+    pub fn new(store_path: Option<PathBuf>) -> Self {
+        let entries = store_path
+            .as_deref()
+            .map(Self::load_entries)
+            .unwrap_or_default();
+
+        if !entries.is_empty() {
+            info!("Restored {} scheduled task(s)", entries.len());
+        }
+
+        Self {
+            entries: Arc::new(Mutex::new(entries)),
+            store_path,
+            max_schedules: DEFAULT_MAX_SCHEDULES,
+        }
+    }
+
+    /// Refuse new schedules once `max_schedules` exist
+    pub fn with_max_schedules(mut self, max_schedules: usize) -> Self {
+        self.max_schedules = max_schedules;
+        self
+    }
+
+    fn load_entries(path: &Path) -> HashMap<String, ScheduledTask> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+            Err(e) => {
+                warn!("Failed to read schedule store {:?}: {}", path, e);
+                return HashMap::new();
+            }
+        };
+
+        match serde_json::from_str::<Vec<ScheduledTask>>(&content) {
+            Ok(list) => list.into_iter().map(|s| (s.id.clone(), s)).collect(),
+            Err(e) => {
+                warn!("Ignoring corrupt schedule store {:?}: {}", path, e);
+                HashMap::new()
+            }
+        }
+    }
+
+    async fn persist(&self, entries: &HashMap<String, ScheduledTask>) {
+        let Some(path) = &self.store_path else {
+            return;
+        };
+
+        let list: Vec<&ScheduledTask> = entries.values().collect();
+        let serialized = match serde_json::to_string_pretty(&list) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                warn!("Failed to serialize schedules: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = tokio::fs::write(path, serialized).await {
+            warn!("Failed to persist schedules to {:?}: {}", path, e);
+        }
+    }
+
+    /// Register a task template to run on the given schedule
+    pub async fn schedule(&self, template: Task, schedule: TaskSchedule) -> Result<ScheduledTask> {
+        let now = Utc::now();
+        let next_run_at = match &schedule {
+            TaskSchedule::RunAt(run_at) => *run_at,
+            TaskSchedule::Cron(expression) => CronExpression::parse(expression)?
+                .next_after(now)
+                .ok_or_else(|| {
+                    SpiralError::Validation(format!(
+                        "Cron expression '{expression}' never matches a future time"
+                    ))
+                })?,
+        };
+
+        let scheduled = ScheduledTask {
+            id: Uuid::new_v4().to_string(),
+            template,
+            schedule,
+            next_run_at,
+            last_run_at: None,
+            run_count: 0,
+            created_at: now,
+            failed_submissions: 0,
+        };
+
+        let mut entries = self.entries.lock().await;
+        // 🛡️ Each schedule is kept in memory and rewritten to the store on every change
+        if entries.len() >= self.max_schedules {
+            return Err(SpiralError::Validation(format!(
+                "At most {} schedules may exist at once; remove one first",
+                self.max_schedules
+            )));
+        }
+        entries.insert(scheduled.id.clone(), scheduled.clone());
+        self.persist(&entries).await;
+
+        info!(
+            "Scheduled task {} (next run: {})",
+            scheduled.id,
+            scheduled.next_run_at.to_rfc3339()
+        );
+        Ok(scheduled)
+    }
+
+    /// All registered schedules, soonest first
+    pub async fn list(&self) -> Vec<ScheduledTask> {
+        let entries = self.entries.lock().await;
+        let mut list: Vec<ScheduledTask> = entries.values().cloned().collect();
+        list.sort_by_key(|s| s.next_run_at);
+        list
+    }
+
+    /// Stop a schedule; tasks it already submitted keep running
+    /// Returns the removed schedule, None when there was none with this ID
+    pub async fn remove(&self, id: &str) -> Option<ScheduledTask> {
+        let mut entries = self.entries.lock().await;
+        let removed = entries.remove(id)?;
+        self.persist(&entries).await;
+        info!("Removed scheduled task {}", id);
+        Some(removed)
+    }
+
+    /// Put back a one-shot schedule whose task could not be submitted, to run again after
+    /// SCHEDULE_RETRY_DELAY_SECS. Returns false, dropping it, once it has used up
+    /// SCHEDULE_MAX_SUBMIT_ATTEMPTS
+    pub async fn retry_later(&self, mut scheduled: ScheduledTask, now: DateTime<Utc>) -> bool {
+        scheduled.failed_submissions += 1;
+        if scheduled.failed_submissions >= SCHEDULE_MAX_SUBMIT_ATTEMPTS {
+            return false;
+        }
+        scheduled.next_run_at = now + Duration::seconds(SCHEDULE_RETRY_DELAY_SECS);
+
+        let mut entries = self.entries.lock().await;
+        entries.insert(scheduled.id.clone(), scheduled);
+        self.persist(&entries).await;
+        true
+    }
+
+    /// Produce task instances for every schedule due at `now`
+    /// One-shot schedules are removed, and returned for retry_later; cron schedules
+    /// advance to their next match
+    pub async fn take_due(&self, now: DateTime<Utc>) -> Vec<DueTask> {
+        let mut entries = self.entries.lock().await;
+        let due_ids: Vec<String> = entries
+            .values()
+            .filter(|s| s.next_run_at <= now)
+            .map(|s| s.id.clone())
+            .collect();
+
+        if due_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let mut due_tasks = Vec::with_capacity(due_ids.len());
+        for id in due_ids {
+            let Some(scheduled) = entries.get_mut(&id) else {
+                continue;
+            };
+
+            let one_shot =
+                matches!(scheduled.schedule, TaskSchedule::RunAt(_)).then(|| scheduled.clone());
+            due_tasks.push(DueTask {
+                task: scheduled.instantiate(),
+                one_shot,
+            });
+            scheduled.last_run_at = Some(now);
+            scheduled.run_count += 1;
+
+            let next = match &scheduled.schedule {
+                TaskSchedule::RunAt(_) => None,
+                TaskSchedule::Cron(expression) => CronExpression::parse(expression)
+                    .ok()
+                    .and_then(|cron| cron.next_after(now)),
+            };
+
+            match next {
+                Some(next_run_at) => scheduled.next_run_at = next_run_at,
+                None => {
+                    debug!("Schedule {} has no further runs, removing", id);
+                    entries.remove(&id);
+                }
+            }
+        }
+
+        self.persist(&entries).await;
+        due_tasks
+    }
+}
+
+/// A parsed 5-field cron expression: minute hour day-of-month month day-of-week
+/// Supports `*`, single values, ranges (`1-5`), lists (`1,15`) and steps (`*/10`, `0-30/5`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpression {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronExpression {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(SpiralError::Validation(format!(
+                "Cron expression must have 5 fields, got {}",
+                fields.len()
+            )));
+        }
+
+        // Day-of-week 7 is an alias for Sunday (0)
+        let mut days_of_week = Self::parse_field(fields[4], 0, 7)?;
+        if days_of_week.contains(&7) {
+            days_of_week.retain(|d| *d != 7);
+            if !days_of_week.contains(&0) {
+                days_of_week.insert(0, 0);
+            }
+        }
+
+        Ok(Self {
+            minutes: Self::parse_field(fields[0], 0, 59)?,
+            hours: Self::parse_field(fields[1], 0, 23)?,
+            days_of_month: Self::parse_field(fields[2], 1, 31)?,
+            months: Self::parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: fields[2] != "*",
+            day_of_week_restricted: fields[4] != "*",
+        })
+    }
+
+    fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>> {
+        let invalid = || SpiralError::Validation(format!("Invalid cron field: '{field}'"));
+        let mut values = Vec::new();
+
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+                None => (part, 1),
+            };
+            if step == 0 {
+                return Err(invalid());
+            }
+
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (
+                    start.parse::<u32>().map_err(|_| invalid())?,
+                    end.parse::<u32>().map_err(|_| invalid())?,
+                )
+            } else {
+                let value = range.parse::<u32>().map_err(|_| invalid())?;
+                // "5/15" means starting at 5, every 15 up to the field maximum
+                if part.contains('/') {
+                    (value, max)
+                } else {
+                    (value, value)
+                }
+            };
+
+            if start < min || end > max || start > end {
+                return Err(invalid());
+            }
+            values.extend((start..=end).step_by(step as usize));
+        }
+
+        values.sort_unstable();
+        values.dedup();
+        Ok(values)
+    }
+
+    fn day_matches(&self, time: &DateTime<Utc>) -> bool {
+        let dom = self.days_of_month.contains(&time.day());
+        let dow = self
+            .days_of_week
+            .contains(&time.weekday().num_days_from_sunday());
+
+        // Standard cron semantics: when both day fields are restricted, either may match
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// The first matching minute strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let horizon = after + Duration::days(CRON_SEARCH_HORIZON_DAYS);
+        let mut time = start;
+
+        // ⚡ PERFORMANCE DECISION: Skip whole months/days/hours that cannot match
+        // Why: Minute-by-minute scanning would take ~500K iterations for yearly schedules
+        while time <= horizon {
+            if !self.months.contains(&time.month()) {
+                let (year, month) = if time.month() == 12 {
+                    (time.year() + 1, 1)
+                } else {
+                    (time.year(), time.month() + 1)
+                };
+                time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+                continue;
+            }
+
+            if !self.day_matches(&time) {
+                time = (time + Duration::days(1)).with_hour(0)?.with_minute(0)?;
+                continue;
+            }
+
+            if !self.hours.contains(&time.hour()) {
+                time = (time + Duration::hours(1)).with_minute(0)?;
+                continue;
+            }
+
+            if !self.minutes.contains(&time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+
+            return Some(time);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, Priority};
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn test_task() -> Task {
+        Task::new(
+            AgentType::SoftwareDeveloper,
+            "scheduled work".to_string(),
+            Priority::Medium,
+        )
+    }
+
+    #[test]
+    fn test_cron_parse_rejects_invalid_expressions() {
+        assert!(CronExpression::parse("* * * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("*/0 * * * *").is_err());
+        assert!(CronExpression::parse("5-1 * * * *").is_err());
+        assert!(CronExpression::parse("*/15 9-17 * * 1-5").is_ok());
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let every_15 = CronExpression::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_15.next_after(at(2025, 1, 1, 10, 7)),
+            Some(at(2025, 1, 1, 10, 15))
+        );
+
+        let daily = CronExpression::parse("30 2 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(at(2025, 1, 1, 3, 0)),
+            Some(at(2025, 1, 2, 2, 30))
+        );
+
+        // 2025-01-04 is a Saturday; next weekday run is Monday the 6th
+        let weekdays = CronExpression::parse("0 9 * * 1-5").unwrap();
+        assert_eq!(
+            weekdays.next_after(at(2025, 1, 4, 12, 0)),
+            Some(at(2025, 1, 6, 9, 0))
+        );
+
+        let new_year = CronExpression::parse("0 0 1 1 *").unwrap();
+        assert_eq!(
+            new_year.next_after(at(2025, 6, 15, 0, 0)),
+            Some(at(2026, 1, 1, 0, 0))
+        );
+
+        let impossible = CronExpression::parse("0 0 31 2 *").unwrap();
+        assert_eq!(impossible.next_after(at(2025, 1, 1, 0, 0)), None);
+    }
+
+    #[tokio::test]
+    async fn test_one_shot_schedule_runs_once_with_template_id() {
+        let scheduler = TaskScheduler::new(None);
+        let template = test_task();
+        let template_id = template.id.clone();
+        let run_at = Utc::now() - Duration::seconds(1);
+
+        scheduler
+            .schedule(template, TaskSchedule::RunAt(run_at))
+            .await
+            .unwrap();
+
+        let mut due = scheduler.take_due(Utc::now()).await;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].task.id, template_id);
+        assert!(scheduler.list().await.is_empty());
+        assert!(scheduler.take_due(Utc::now()).await.is_empty());
+
+        // A run that could not be submitted comes back with the same task ID
+        let one_shot = due.remove(0).one_shot.unwrap();
+        assert!(scheduler.retry_later(one_shot, Utc::now()).await);
+        let retry_at = Utc::now() + Duration::seconds(SCHEDULE_RETRY_DELAY_SECS);
+        let mut due = scheduler.take_due(retry_at).await;
+        assert_eq!(due[0].task.id, template_id);
+
+        let mut one_shot = due.remove(0).one_shot.unwrap();
+        one_shot.failed_submissions = SCHEDULE_MAX_SUBMIT_ATTEMPTS - 1;
+        assert!(!scheduler.retry_later(one_shot, Utc::now()).await);
+        assert!(scheduler.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_count_is_capped() {
+        let scheduler = TaskScheduler::new(None).with_max_schedules(1);
+        let later = TaskSchedule::RunAt(Utc::now() + Duration::hours(1));
+        scheduler
+            .schedule(test_task(), later.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            scheduler.schedule(test_task(), later).await,
+            Err(SpiralError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cron_schedule_advances_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path().join("schedules.json");
+
+        let scheduler = TaskScheduler::new(Some(store.clone()));
+        let scheduled = scheduler
+            .schedule(test_task(), TaskSchedule::Cron("* * * * *".to_string()))
+            .await
+            .unwrap();
+
+        let due = scheduler.take_due(scheduled.next_run_at).await;
+        assert_eq!(due.len(), 1);
+        assert_eq!(
+            due[0].task.context.get(SCHEDULE_ID_CONTEXT_KEY),
+            Some(&scheduled.id)
+        );
+        assert!(due[0].one_shot.is_none());
+
+        let remaining = scheduler.list().await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].run_count, 1);
+        assert!(remaining[0].next_run_at > scheduled.next_run_at);
+
+        // A fresh scheduler restores the schedule from disk
+        let restored = TaskScheduler::new(Some(store.clone()));
+        assert_eq!(restored.list().await.len(), 1);
+
+        // Removal is persisted too
+        assert!(restored.remove(&scheduled.id).await.is_some());
+        assert!(restored.remove(&scheduled.id).await.is_none());
+        assert!(TaskScheduler::new(Some(store)).list().await.is_empty());
+    }
+}
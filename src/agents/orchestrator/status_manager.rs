@@ -18,6 +18,12 @@ pub struct StatusManager {
     task_statuses: Arc<RwLock<HashMap<String, TaskStatus>>>,
 }
 
+impl Default for StatusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StatusManager {
     pub fn new() -> Self {
         Self {
@@ -65,11 +71,11 @@ impl StatusManager {
         }
     }
 
-    /// Mark agent as idle
-    pub async fn mark_agent_idle(&self, agent_type: &AgentType) -> Result<()> {
+    /// Mark the agent's task as no longer running
+    pub async fn mark_agent_idle(&self, agent_type: &AgentType, task_id: &str) -> Result<()> {
         let mut statuses = self.agent_statuses.write().await;
         if let Some(status) = statuses.get_mut(agent_type) {
-            status.is_busy = false;
+            status.release_task(task_id);
             Ok(())
         } else {
             Err(SpiralError::Agent {
@@ -82,11 +88,12 @@ impl StatusManager {
     pub async fn increment_agent_completed(
         &self,
         agent_type: &AgentType,
+        task_id: &str,
         execution_time: f64,
     ) -> Result<()> {
         let mut statuses = self.agent_statuses.write().await;
         if let Some(status) = statuses.get_mut(agent_type) {
-            status.complete_task(execution_time);
+            status.complete_task(task_id, execution_time);
             Ok(())
         } else {
             Err(SpiralError::Agent {
@@ -96,10 +103,14 @@ impl StatusManager {
     }
 
     /// Increment agent task failure count
-    pub async fn increment_agent_failed(&self, agent_type: &AgentType) -> Result<()> {
+    pub async fn increment_agent_failed(
+        &self,
+        agent_type: &AgentType,
+        task_id: &str,
+    ) -> Result<()> {
         let mut statuses = self.agent_statuses.write().await;
         if let Some(status) = statuses.get_mut(agent_type) {
-            status.fail_task();
+            status.fail_task(task_id);
             Ok(())
         } else {
             Err(SpiralError::Agent {
@@ -193,9 +204,23 @@ mod tests {
             .initialize_agent_status(AgentType::SoftwareDeveloper)
             .await;
 
-        // Mark as busy
+        // Mark as busy with two parallel tasks
+        for task_id in ["test-task-1", "test-task-2"] {
+            manager
+                .mark_agent_busy(&AgentType::SoftwareDeveloper, task_id.to_string())
+                .await
+                .unwrap();
+        }
+        let status = manager
+            .get_agent_status(&AgentType::SoftwareDeveloper)
+            .await
+            .unwrap();
+        assert!(status.is_busy);
+        assert_eq!(status.active_tasks, 2);
+
+        // Completing one leaves the other listed
         manager
-            .mark_agent_busy(&AgentType::SoftwareDeveloper, "test-task-1".to_string())
+            .increment_agent_completed(&AgentType::SoftwareDeveloper, "test-task-1", 1.5)
             .await
             .unwrap();
         let status = manager
@@ -203,10 +228,13 @@ mod tests {
             .await
             .unwrap();
         assert!(status.is_busy);
+        assert_eq!(
+            status.current_task_ids.iter().collect::<Vec<_>>(),
+            ["test-task-2"]
+        );
 
-        // Complete task
         manager
-            .increment_agent_completed(&AgentType::SoftwareDeveloper, 1.5)
+            .increment_agent_completed(&AgentType::SoftwareDeveloper, "test-task-2", 1.5)
             .await
             .unwrap();
         let status = manager
@@ -214,7 +242,7 @@ mod tests {
             .await
             .unwrap();
         assert!(!status.is_busy);
-        assert_eq!(status.tasks_completed, 1);
+        assert_eq!(status.tasks_completed, 2);
     }
 
     #[tokio::test]
@@ -0,0 +1,61 @@
+use super::{TaskEvent, TaskEventKind};
+use crate::models::{Priority, Task, TaskExecutionResult, TaskResult, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// One task an agent handled, as listed in its history
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AgentTaskRecord {
+    pub task_id: String,
+    pub status: TaskStatus,
+    pub priority: Priority,
+    pub created_at: DateTime<Utc>,
+    /// First time an agent picked the task up; None while it is still queued
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// From first start to finish, including retry backoff; None until it finishes
+    pub duration_ms: Option<i64>,
+    pub retry_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AgentTaskRecord {
+    pub fn new(task: &Task, events: Option<&[TaskEvent]>, result: Option<&TaskResult>) -> Self {
+        let events = events.unwrap_or_default();
+        let started_at = events
+            .iter()
+            .find(|event| event.kind == TaskEventKind::Started)
+            .map(|event| event.timestamp);
+        let finished_at = result.map(|result| result.completed_at).or_else(|| {
+            events
+                .iter()
+                .rev()
+                .find(|event| {
+                    matches!(
+                        event.kind,
+                        TaskEventKind::Completed | TaskEventKind::Failed | TaskEventKind::Cancelled
+                    )
+                })
+                .map(|event| event.timestamp)
+        });
+
+        Self {
+            task_id: task.id.clone(),
+            status: task.status.clone(),
+            priority: task.priority.clone(),
+            created_at: task.created_at,
+            started_at,
+            finished_at,
+            duration_ms: started_at
+                .zip(finished_at)
+                .map(|(started, finished)| (finished - started).num_milliseconds().max(0)),
+            retry_count: task.retry_count,
+            error: result.and_then(|result| match &result.result {
+                TaskExecutionResult::Failure { error, .. } => Some(error.clone()),
+                TaskExecutionResult::Success { .. } => None,
+            }),
+        }
+    }
+}
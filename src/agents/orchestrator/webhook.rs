@@ -0,0 +1,525 @@
+use super::RetryPolicy;
+use crate::{
+    config::Config,
+    models::{AgentType, TaskExecutionResult, TaskResult},
+    Result, SpiralError,
+};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// Header carrying `sha256=<hex>` of HMAC-SHA256(api_key, "{timestamp}.{body}")
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-spiral-signature";
+/// Unix seconds the payload was signed at; receivers should reject stale timestamps
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-spiral-timestamp";
+/// `task.completed` or `task.failed`
+pub const WEBHOOK_EVENT_HEADER: &str = "x-spiral-event";
+
+/// Where a task's callback delivery stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for the task to finish
+    Pending,
+    /// Task finished, delivery attempts are in progress
+    Delivering,
+    Delivered,
+    /// Every attempt failed, or the receiver rejected the payload outright
+    Failed,
+}
+
+/// Callback registered for a task and the outcome of delivering it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    pub task_id: String,
+    pub callback_url: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last response; None when the request itself failed
+    pub last_response_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// JSON body POSTed to the callback URL
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookPayload {
+    pub event: String,
+    pub task_id: String,
+    pub agent_type: AgentType,
+    pub success: bool,
+    /// Start of the agent's output, or of what it produced before failing
+    pub summary: Option<String>,
+    pub error: Option<String>,
+    pub files_created: Vec<String>,
+    pub files_modified: Vec<String>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl WebhookPayload {
+    pub fn from_result(result: &TaskResult) -> Self {
+        let summarize = |output: &str| {
+            output
+                .chars()
+                .take(crate::constants::WEBHOOK_SUMMARY_CHARS)
+                .collect::<String>()
+        };
+        let (success, summary, error, files_created, files_modified) = match &result.result {
+            TaskExecutionResult::Success {
+                output,
+                files_created,
+                files_modified,
+            } => (
+                true,
+                Some(summarize(output)),
+                None,
+                files_created.clone(),
+                files_modified.clone(),
+            ),
+            TaskExecutionResult::Failure {
+                error,
+                partial_output,
+            } => (
+                false,
+                partial_output.as_deref().map(summarize),
+                Some(error.clone()),
+                Vec::new(),
+                Vec::new(),
+            ),
+        };
+
+        Self {
+            event: if success {
+                "task.completed"
+            } else {
+                "task.failed"
+            }
+            .to_string(),
+            task_id: result.task_id.clone(),
+            agent_type: result.agent_type.clone(),
+            success,
+            summary,
+            error,
+            files_created,
+            files_modified,
+            completed_at: result.completed_at,
+        }
+    }
+}
+
+/// `sha256=<hex>` signature of a payload sent at `timestamp`
+pub fn sign_payload(key: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// 🔔 TASK CALLBACKS: POST a signed result summary to a client URL when a task finishes
+/// 🏗️ ARCHITECTURE DECISION: Registered per task ID before submission, fired from
+/// publish_result
+/// Why: Registering first means even a task that finishes instantly finds its callback,
+/// and publish_result is the one place every completion, failure and cancel passes
+/// Alternative: Store the URL in task context (rejected: context is handed to agents
+/// and Claude, and the URL is delivery metadata, not task input)
+/// 🛡️ SECURITY: Only allowlisted hosts can be called back, so a caller cannot point the
+/// server at internal services; payloads are signed with the API key so receivers can
+/// verify they came from this server
+/// Retention: Deliveries are pruned alongside task storage in perform_cleanup
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    signing_key: Option<String>,
+    allowed_hosts: Arc<Vec<String>>,
+    retry: RetryPolicy,
+    deliveries: Arc<Mutex<HashMap<String, WebhookDelivery>>>,
+}
+
+impl WebhookNotifier {
+    /// `retry.max_retries` is the number of attempts after the first
+    pub fn new(
+        signing_key: Option<String>,
+        allowed_hosts: Vec<String>,
+        retry: RetryPolicy,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                crate::constants::WEBHOOK_REQUEST_TIMEOUT_SECS,
+            ))
+            // A redirect would leave the allowlisted host
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            signing_key,
+            allowed_hosts: Arc::new(
+                allowed_hosts
+                    .into_iter()
+                    .map(|host| host.to_ascii_lowercase())
+                    .collect(),
+            ),
+            retry,
+            deliveries: Arc::default(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.api.api_key.clone(),
+            config.orchestrator.webhook_allowed_hosts.clone(),
+            RetryPolicy {
+                max_retries: config.orchestrator.webhook_max_attempts.saturating_sub(1),
+                initial_backoff: Duration::from_millis(
+                    crate::constants::WEBHOOK_INITIAL_BACKOFF_MS,
+                ),
+                max_backoff: Duration::from_secs(crate::constants::WEBHOOK_MAX_BACKOFF_SECS),
+            },
+        )
+    }
+
+    /// Check a callback URL can be registered: http(s), no credentials, allowlisted host
+    pub fn validate_url(&self, callback_url: &str) -> Result<url::Url> {
+        if self.allowed_hosts.is_empty() {
+            return Err(SpiralError::Validation(
+                "Webhook callbacks are disabled (WEBHOOK_ALLOWED_HOSTS is empty)".to_string(),
+            ));
+        }
+        if self.signing_key.is_none() {
+            return Err(SpiralError::Validation(
+                "Webhook callbacks need an API key to sign payloads".to_string(),
+            ));
+        }
+
+        let url = url::Url::parse(callback_url)
+            .map_err(|e| SpiralError::Validation(format!("Invalid callback_url: {e}")))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(SpiralError::Validation(
+                "callback_url must use http or https".to_string(),
+            ));
+        }
+        if !url.username().is_empty() || url.password().is_some() {
+            return Err(SpiralError::Validation(
+                "callback_url must not contain credentials".to_string(),
+            ));
+        }
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        if !self.allowed_hosts.contains(&host) {
+            return Err(SpiralError::Validation(format!(
+                "callback_url host '{host}' is not in the webhook allowlist"
+            )));
+        }
+        Ok(url)
+    }
+
+    /// Call `callback_url` back when the task finishes
+    pub fn register(&self, task_id: &str, callback_url: &str) -> Result<()> {
+        let url = self.validate_url(callback_url)?;
+        self.lock().insert(
+            task_id.to_string(),
+            WebhookDelivery {
+                task_id: task_id.to_string(),
+                callback_url: url.to_string(),
+                status: WebhookDeliveryStatus::Pending,
+                attempts: 0,
+                last_attempt_at: None,
+                last_response_status: None,
+                last_error: None,
+                delivered_at: None,
+                registered_at: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Forget a callback whose task was never submitted
+    pub fn unregister(&self, task_id: &str) {
+        self.lock().remove(task_id);
+    }
+
+    pub fn get(&self, task_id: &str) -> Option<WebhookDelivery> {
+        self.lock().get(task_id).cloned()
+    }
+
+    /// Start delivering the result if its task has a pending callback
+    pub fn notify(&self, result: &TaskResult) {
+        let url = {
+            let mut deliveries = self.lock();
+            let Some(delivery) = deliveries.get_mut(&result.task_id) else {
+                return;
+            };
+            // A cancel racing the task's own failure result only calls back once
+            if delivery.status != WebhookDeliveryStatus::Pending {
+                return;
+            }
+            delivery.status = WebhookDeliveryStatus::Delivering;
+            delivery.callback_url.clone()
+        };
+
+        let notifier = self.clone();
+        let payload = WebhookPayload::from_result(result);
+        tokio::spawn(async move { notifier.deliver(url, payload).await });
+    }
+
+    /// Drop deliveries for tasks the predicate no longer keeps
+    pub fn retain(&self, mut keep: impl FnMut(&str) -> bool) {
+        self.lock().retain(|task_id, _| keep(task_id));
+    }
+
+    async fn deliver(&self, url: String, payload: WebhookPayload) {
+        let task_id = payload.task_id.clone();
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                self.finish_attempt(&task_id, None, Some(e.to_string()), true);
+                return;
+            }
+        };
+        let key = self.signing_key.clone().unwrap_or_default();
+        let max_attempts = self.retry.max_retries + 1;
+
+        for attempt in 1..=max_attempts {
+            // Re-signed each attempt so the timestamp stays fresh for replay checks
+            let timestamp = Utc::now().timestamp();
+            let response = self
+                .client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(WEBHOOK_EVENT_HEADER, &payload.event)
+                .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+                .header(
+                    WEBHOOK_SIGNATURE_HEADER,
+                    sign_payload(&key, timestamp, &body),
+                )
+                .body(body.clone())
+                .send()
+                .await;
+
+            let (status, error, retryable) = match response {
+                Ok(response) if response.status().is_success() => {
+                    self.finish_attempt(&task_id, Some(response.status().as_u16()), None, true);
+                    info!(
+                        "Delivered webhook for task {} on attempt {}",
+                        task_id, attempt
+                    );
+                    return;
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    // Other 4xx responses mean the receiver rejects this payload for good
+                    let retryable = status.is_server_error()
+                        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                        || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                    (Some(status.as_u16()), format!("HTTP {status}"), retryable)
+                }
+                Err(e) => (None, e.to_string(), true),
+            };
+
+            let last = !retryable || attempt == max_attempts;
+            self.finish_attempt(&task_id, status, Some(error.clone()), last);
+            if last {
+                warn!(
+                    "Webhook for task {} failed after {} attempt(s): {}",
+                    task_id, attempt, error
+                );
+                return;
+            }
+            tokio::time::sleep(self.retry.backoff_for(attempt)).await;
+        }
+    }
+
+    fn finish_attempt(
+        &self,
+        task_id: &str,
+        response_status: Option<u16>,
+        error: Option<String>,
+        last: bool,
+    ) {
+        let mut deliveries = self.lock();
+        let Some(delivery) = deliveries.get_mut(task_id) else {
+            return;
+        };
+        let now = Utc::now();
+        delivery.attempts += 1;
+        delivery.last_attempt_at = Some(now);
+        delivery.last_response_status = response_status;
+        delivery.status = match (&error, last) {
+            (None, _) => {
+                delivery.delivered_at = Some(now);
+                WebhookDeliveryStatus::Delivered
+            }
+            (Some(_), true) => WebhookDeliveryStatus::Failed,
+            (Some(_), false) => WebhookDeliveryStatus::Delivering,
+        };
+        delivery.last_error = error;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, WebhookDelivery>> {
+        self.deliveries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const KEY: &str = "test-api-key-32-characters-long-for-security";
+
+    fn notifier(hosts: &[&str]) -> WebhookNotifier {
+        WebhookNotifier::new(
+            Some(KEY.to_string()),
+            hosts.iter().map(|host| host.to_string()).collect(),
+            RetryPolicy {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(20),
+            },
+        )
+    }
+
+    fn result(task_id: &str) -> TaskResult {
+        TaskResult {
+            task_id: task_id.to_string(),
+            agent_type: AgentType::SoftwareDeveloper,
+            result: TaskExecutionResult::Success {
+                output: "done".to_string(),
+                files_created: vec!["main.rs".to_string()],
+                files_modified: Vec::new(),
+            },
+            metadata: HashMap::new(),
+            completed_at: Utc::now(),
+            changes: Vec::new(),
+        }
+    }
+
+    async fn wait_for(notifier: &WebhookNotifier, task_id: &str) -> WebhookDelivery {
+        for _ in 0..200 {
+            let delivery = notifier.get(task_id).unwrap();
+            if matches!(
+                delivery.status,
+                WebhookDeliveryStatus::Delivered | WebhookDeliveryStatus::Failed
+            ) {
+                return delivery;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Webhook delivery for {task_id} did not finish");
+    }
+
+    #[test]
+    fn test_callback_urls_must_be_allowlisted() {
+        let notifier = notifier(&["hooks.example.com"]);
+        assert!(notifier
+            .validate_url("https://hooks.example.com/spiral")
+            .is_ok());
+        assert!(notifier
+            .validate_url("https://HOOKS.example.com/spiral")
+            .is_ok());
+        assert!(notifier.validate_url("https://evil.example.com/").is_err());
+        assert!(notifier
+            .validate_url("https://user:pw@hooks.example.com/")
+            .is_err());
+        assert!(notifier.validate_url("ftp://hooks.example.com/").is_err());
+        assert!(notifier.validate_url("not a url").is_err());
+
+        let disabled =
+            WebhookNotifier::new(Some(KEY.to_string()), Vec::new(), RetryPolicy::default());
+        assert!(disabled.validate_url("https://hooks.example.com/").is_err());
+    }
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let signature = sign_payload(KEY, 1_700_000_000, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign_payload(KEY, 1_700_000_000, b"{}"));
+        assert_ne!(signature, sign_payload(KEY, 1_700_000_001, b"{}"));
+        assert_ne!(signature, sign_payload(KEY, 1_700_000_000, b"{ }"));
+        assert_ne!(signature, sign_payload("other-key", 1_700_000_000, b"{}"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_until_success() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: axum::http::HeaderMap, body: String| {
+                let counter = counter.clone();
+                async move {
+                    let timestamp: i64 = headers[WEBHOOK_TIMESTAMP_HEADER]
+                        .to_str()
+                        .unwrap()
+                        .parse()
+                        .unwrap();
+                    assert_eq!(
+                        headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap(),
+                        sign_payload(KEY, timestamp, body.as_bytes())
+                    );
+                    // Fail the first attempt to exercise the retry
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let notifier = notifier(&["127.0.0.1"]);
+        notifier
+            .register("task_1", &format!("http://{addr}/hook"))
+            .unwrap();
+        notifier.notify(&result("task_1"));
+        // A second result for the same task does not call back again
+        notifier.notify(&result("task_1"));
+
+        let delivery = wait_for(&notifier, "task_1").await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(delivery.last_response_status, Some(200));
+        assert!(delivery.delivered_at.is_some());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // Nothing registered, nothing delivered
+        notifier.notify(&result("task_2"));
+        assert!(notifier.get("task_2").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let app = Router::new().route("/hook", post(|| async { StatusCode::GONE }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let notifier = notifier(&["127.0.0.1"]);
+        notifier
+            .register("task_1", &format!("http://{addr}/hook"))
+            .unwrap();
+        notifier.notify(&result("task_1"));
+
+        let delivery = wait_for(&notifier, "task_1").await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.last_response_status, Some(410));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{config::OrchestratorConfig, models::AgentType};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 🏗️ ARCHITECTURE DECISION: Semaphore-backed worker pool per agent type
+/// Why: One long SpiralDev job must not block unrelated agents, and a single agent
+/// type may safely run several tasks at once because agents are stateless wrappers
+/// around the shared Claude Code client
+/// Alternative: Dedicated worker tasks per slot (rejected: idle tasks polling the queue)
+/// Alternative: Global concurrency limit (rejected: one agent type could starve the rest)
+#[derive(Debug, Clone)]
+pub struct WorkerPools {
+    pools: HashMap<AgentType, Arc<Semaphore>>,
+    limits: HashMap<AgentType, usize>,
+}
+
+impl WorkerPools {
+    /// Build one pool per registered agent type using configured limits
+    pub fn new<'a>(
+        agent_types: impl IntoIterator<Item = &'a AgentType>,
+        config: &OrchestratorConfig,
+    ) -> Self {
+        let mut pools = HashMap::new();
+        let mut limits = HashMap::new();
+
+        for agent_type in agent_types {
+            // 🛡️ SAFETY CHECK: A zero limit would silently stall every task for this agent
+            let limit = config
+                .agent_concurrency
+                .get(agent_type)
+                .copied()
+                .unwrap_or(config.default_agent_concurrency)
+                .max(1);
+            pools.insert(agent_type.clone(), Arc::new(Semaphore::new(limit)));
+            limits.insert(agent_type.clone(), limit);
+        }
+
+        Self { pools, limits }
+    }
+
+    /// Claim a worker slot for the agent type, or None if the pool is saturated
+    /// The slot is released when the returned permit is dropped
+    pub fn try_acquire(&self, agent_type: &AgentType) -> Option<OwnedSemaphorePermit> {
+        self.pools
+            .get(agent_type)
+            .and_then(|pool| pool.clone().try_acquire_owned().ok())
+    }
+
+    /// Maximum concurrent tasks for the agent type
+    pub fn limit(&self, agent_type: &AgentType) -> usize {
+        self.limits.get(agent_type).copied().unwrap_or(0)
+    }
+
+    /// Tasks currently executing for the agent type
+    pub fn in_flight(&self, agent_type: &AgentType) -> usize {
+        self.pools
+            .get(agent_type)
+            .map(|pool| self.limit(agent_type) - pool.available_permits())
+            .unwrap_or(0)
+    }
+
+    /// Tasks currently executing across all agent types
+    pub fn total_in_flight(&self) -> usize {
+        self.pools
+            .keys()
+            .map(|agent_type| self.in_flight(agent_type))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_limit(limit: usize) -> OrchestratorConfig {
+        let mut config = OrchestratorConfig::default();
+        config
+            .agent_concurrency
+            .insert(AgentType::SoftwareDeveloper, limit);
+        config
+    }
+
+    #[test]
+    fn test_pool_enforces_per_agent_limit() {
+        let config = config_with_limit(2);
+        let pools = WorkerPools::new([AgentType::SoftwareDeveloper].iter(), &config);
+
+        let first = pools.try_acquire(&AgentType::SoftwareDeveloper);
+        let second = pools.try_acquire(&AgentType::SoftwareDeveloper);
+        assert!(first.is_some());
+        assert!(second.is_some());
+        assert!(pools.try_acquire(&AgentType::SoftwareDeveloper).is_none());
+        assert_eq!(pools.in_flight(&AgentType::SoftwareDeveloper), 2);
+
+        drop(first);
+        assert_eq!(pools.total_in_flight(), 1);
+        assert!(pools.try_acquire(&AgentType::SoftwareDeveloper).is_some());
+    }
+
+    #[test]
+    fn test_unregistered_agent_has_no_pool() {
+        let pools = WorkerPools::new(
+            [AgentType::SoftwareDeveloper].iter(),
+            &OrchestratorConfig::default(),
+        );
+
+        assert!(pools.try_acquire(&AgentType::ProjectManager).is_none());
+        assert_eq!(pools.limit(&AgentType::ProjectManager), 0);
+    }
+
+    #[test]
+    fn test_zero_limit_is_clamped() {
+        let config = config_with_limit(0);
+        let pools = WorkerPools::new([AgentType::SoftwareDeveloper].iter(), &config);
+
+        assert_eq!(pools.limit(&AgentType::SoftwareDeveloper), 1);
+    }
+}
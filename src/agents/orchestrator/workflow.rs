@@ -0,0 +1,201 @@
+use crate::models::{AgentType, Task};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Context key linking each step task back to its workflow run
+pub const WORKFLOW_RUN_CONTEXT_KEY: &str = "workflow_run_id";
+/// Context key with the 1-based step position, e.g. `2/3`
+pub const WORKFLOW_STEP_CONTEXT_KEY: &str = "workflow_step";
+/// Context key naming the agent that produced the previous step's output
+pub const PREVIOUS_AGENT_CONTEXT_KEY: &str = "previous_agent";
+/// Context key carrying the previous step's output
+pub const PREVIOUS_OUTPUT_CONTEXT_KEY: &str = "previous_output";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowRunStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowStepStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowStep {
+    pub agent_type: AgentType,
+    pub status: WorkflowStepStatus,
+    /// Set once the step's task has been submitted
+    pub task_id: Option<String>,
+    /// Child session the step ran in, when the run was started in a session
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// One execution of a named workflow
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowRun {
+    pub id: String,
+    pub workflow: String,
+    pub status: WorkflowRunStatus,
+    pub steps: Vec<WorkflowStep>,
+    pub error: Option<String>,
+    /// Output of the last step once the run completes
+    pub final_output: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl WorkflowRun {
+    pub fn new(workflow: &str, steps: &[AgentType]) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            workflow: workflow.to_string(),
+            status: WorkflowRunStatus::Running,
+            steps: steps
+                .iter()
+                .map(|agent_type| WorkflowStep {
+                    agent_type: agent_type.clone(),
+                    status: WorkflowStepStatus::Pending,
+                    task_id: None,
+                    session_id: None,
+                })
+                .collect(),
+            error: None,
+            final_output: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+
+    /// Build the task for a step from the user's request
+    /// 🏗️ ARCHITECTURE DECISION: Pass intermediate results through task context
+    /// Why: Agents already read context, so no agent needs to know it runs in a workflow
+    /// Alternative: Concatenate outputs into the content (rejected: the original request
+    /// would be buried under earlier agents' output)
+    pub fn step_task(
+        &self,
+        index: usize,
+        request: &Task,
+        previous: Option<(&AgentType, &str)>,
+    ) -> Task {
+        let mut task = Task::new(
+            self.steps[index].agent_type.clone(),
+            request.content.clone(),
+            request.priority.clone(),
+        );
+        // Request context first so workflow keys always win
+        task.context = request.context.clone();
+        task.max_retries = request.max_retries;
+        task = task
+            .with_context(WORKFLOW_RUN_CONTEXT_KEY.to_string(), self.id.clone())
+            .with_context(
+                WORKFLOW_STEP_CONTEXT_KEY.to_string(),
+                format!("{}/{}", index + 1, self.steps.len()),
+            );
+
+        if let Some((agent_type, output)) = previous {
+            task = task
+                .with_context(
+                    PREVIOUS_AGENT_CONTEXT_KEY.to_string(),
+                    format!("{agent_type:?}"),
+                )
+                .with_context(
+                    PREVIOUS_OUTPUT_CONTEXT_KEY.to_string(),
+                    truncate_output(output),
+                );
+        }
+
+        task
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != WorkflowRunStatus::Running
+    }
+}
+
+/// Marks a handoff that lost the start of the previous output
+const TRUNCATION_MARKER: &str = "[truncated]\n";
+
+/// Keep the tail of long outputs, where agents put their summaries
+fn truncate_output(output: &str) -> String {
+    let max_bytes = crate::constants::WORKFLOW_CONTEXT_MAX_BYTES;
+    if output.len() <= max_bytes {
+        return output.to_string();
+    }
+    let mut start = output.len() - (max_bytes - TRUNCATION_MARKER.len());
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("{TRUNCATION_MARKER}{}", &output[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    #[test]
+    fn test_step_task_carries_previous_output() {
+        let run = WorkflowRun::new(
+            "review",
+            &[AgentType::SoftwareDeveloper, AgentType::ProjectManager],
+        );
+        let request = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Add a health endpoint".to_string(),
+            Priority::High,
+        )
+        .with_context("repo".to_string(), "spiral-core".to_string());
+
+        let first = run.step_task(0, &request, None);
+        assert_eq!(first.agent_type, AgentType::SoftwareDeveloper);
+        assert_eq!(first.context.get(WORKFLOW_STEP_CONTEXT_KEY).unwrap(), "1/2");
+        assert_eq!(first.context.get("repo").unwrap(), "spiral-core");
+        assert!(!first.context.contains_key(PREVIOUS_OUTPUT_CONTEXT_KEY));
+
+        let second = run.step_task(
+            1,
+            &request,
+            Some((&AgentType::SoftwareDeveloper, "Added /health")),
+        );
+        assert_eq!(second.agent_type, AgentType::ProjectManager);
+        assert_eq!(second.content, "Add a health endpoint");
+        assert_eq!(second.priority, Priority::High);
+        assert_eq!(
+            second.context.get(PREVIOUS_AGENT_CONTEXT_KEY).unwrap(),
+            "SoftwareDeveloper"
+        );
+        assert_eq!(
+            second.context.get(PREVIOUS_OUTPUT_CONTEXT_KEY).unwrap(),
+            "Added /health"
+        );
+        assert_eq!(second.context.get(WORKFLOW_RUN_CONTEXT_KEY), Some(&run.id));
+    }
+
+    #[test]
+    fn test_long_output_keeps_tail() {
+        let max_bytes = crate::constants::WORKFLOW_CONTEXT_MAX_BYTES;
+        let output = format!("{}summary", "x".repeat(max_bytes));
+        let truncated = truncate_output(&output);
+
+        assert!(truncated.starts_with(TRUNCATION_MARKER));
+        assert!(truncated.ends_with("summary"));
+        assert_eq!(truncated.len(), max_bytes);
+
+        // Multi-byte output is cut on a character boundary and still fits the limit
+        let output = "é".repeat(max_bytes);
+        let truncated = truncate_output(&output);
+        assert!(truncated.len() <= max_bytes);
+        assert!(truncated.ends_with('é'));
+    }
+}
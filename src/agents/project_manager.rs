@@ -1,23 +1,91 @@
 //! Project Manager Agent - Strategic analysis and task coordination
 //!
 //! This agent provides high-level strategic thinking, breaking down complex problems
-//! into manageable phases while coordinating between different specialists.
+//! into manageable phases while coordinating between different specialists. It also
+//! produces status summaries and prioritized work lists from the items in a request.
 
-use super::{Agent, AgentStatus};
+use super::{
+    memory::memory_context, task_utils::session_key, Agent, AgentStatus, OrchestratorHandle,
+};
 use crate::{
-    claude_code::{ClaudeCodeClient, TaskAnalysis},
+    claude_code::{costs::requester_context, ClaudeCodeClient, TaskAnalysis},
+    llm::CodeGenerationBackend,
     models::{AgentType, Task, TaskExecutionResult, TaskResult},
+    session::history::history_context,
     Result,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+/// Context key that selects the kind of management output explicitly
+pub const MANAGEMENT_REQUEST_CONTEXT_KEY: &str = "pm_request";
+
+/// Kinds of management output the agent produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagementRequest {
+    /// Summarize where the listed work stands
+    StatusSummary,
+    /// Break the request down into a phased project plan
+    TaskBreakdown,
+    /// Order the listed work items by urgency
+    Prioritization,
+}
+
+impl ManagementRequest {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StatusSummary => "status_summary",
+            Self::TaskBreakdown => "task_breakdown",
+            Self::Prioritization => "prioritization",
+        }
+    }
+
+    /// Progress phase shown while the request is being worked on
+    pub fn phase(&self) -> &'static str {
+        match self {
+            Self::StatusSummary => "summarizing status",
+            Self::TaskBreakdown => "planning",
+            Self::Prioritization => "prioritizing work",
+        }
+    }
+
+    /// 🏗️ ARCHITECTURE DECISION: Explicit context key first, keywords second
+    /// Why: API callers can be precise while Discord users just describe what they want
+    /// Alternative: Separate agent types per request kind (rejected: one persona, one agent)
+    pub fn from_task(task: &Task) -> Self {
+        let requested = task.context.get(MANAGEMENT_REQUEST_CONTEXT_KEY);
+        match requested.map(String::as_str) {
+            Some("status_summary") => return Self::StatusSummary,
+            Some("task_breakdown") => return Self::TaskBreakdown,
+            Some("prioritization") => return Self::Prioritization,
+            _ => {}
+        }
+
+        let content = task.content.to_lowercase();
+        if ["priorit", "rank", "triage"]
+            .iter()
+            .any(|keyword| content.contains(keyword))
+        {
+            Self::Prioritization
+        } else if ["status", "progress", "summar", "standup"]
+            .iter()
+            .any(|keyword| content.contains(keyword))
+        {
+            Self::StatusSummary
+        } else {
+            Self::TaskBreakdown
+        }
+    }
+}
+
 /// Project Manager Agent for strategic analysis and coordination
 pub struct ProjectManagerAgent {
-    claude_client: Option<ClaudeCodeClient>,
+    backend: Option<Arc<dyn CodeGenerationBackend>>,
     status: Arc<RwLock<AgentStatus>>,
 }
 
@@ -25,11 +93,17 @@ impl ProjectManagerAgent {
     /// Create a new Project Manager Agent
     pub fn new(claude_client: Option<ClaudeCodeClient>) -> Self {
         Self {
-            claude_client,
+            backend: claude_client.map(|client| Arc::new(client) as Arc<dyn CodeGenerationBackend>),
             status: Arc::new(RwLock::new(AgentStatus::new(AgentType::ProjectManager))),
         }
     }
 
+    /// Generate with another backend instead of Claude Code
+    pub fn with_backend(mut self, backend: Arc<dyn CodeGenerationBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
     /// Analyze a project request and create an execution plan
     pub async fn create_project_plan(&self, task: &Task) -> Result<ProjectPlan> {
         info!(
@@ -38,8 +112,8 @@ impl ProjectManagerAgent {
         );
 
         // If Claude client is available, use it for comprehensive planning
-        if let Some(ref claude_client) = self.claude_client {
-            self.create_plan_with_claude(task, claude_client).await
+        if let Some(ref backend) = self.backend {
+            self.create_plan_with_claude(task, backend.as_ref()).await
         } else {
             // Fallback to heuristic-based planning
             self.create_plan_with_heuristics(task).await
@@ -50,17 +124,22 @@ impl ProjectManagerAgent {
     async fn create_plan_with_claude(
         &self,
         task: &Task,
-        claude_client: &ClaudeCodeClient,
+        backend: &dyn CodeGenerationBackend,
     ) -> Result<ProjectPlan> {
         let prompt = self.build_planning_prompt(task);
 
         let code_request = crate::claude_code::CodeGenerationRequest {
             language: "json".to_string(),
             description: prompt,
-            context: std::collections::HashMap::from([
+            context: HashMap::from([
                 ("task_type".to_string(), "project_planning".to_string()),
                 ("task_id".to_string(), task.id.clone()),
-            ]),
+            ])
+            .into_iter()
+            .chain(memory_context(task))
+            .chain(history_context(task))
+            .chain(requester_context(task))
+            .collect(),
             existing_code: None,
             requirements: vec![
                 "Create a comprehensive project plan".to_string(),
@@ -68,10 +147,11 @@ impl ProjectManagerAgent {
                 "Identify dependencies and risks".to_string(),
                 "Define clear success criteria".to_string(),
             ],
-            session_id: Some(format!("pm-{}", task.id)),
+            session_id: Some(format!("pm-{}", session_key(task))),
+            dry_run: false,
         };
 
-        match claude_client.generate_code(code_request).await {
+        match backend.generate_code(code_request).await {
             Ok(result) => {
                 // Parse the JSON response into a ProjectPlan
                 match serde_json::from_str::<ProjectPlan>(&result.code) {
@@ -176,6 +256,183 @@ Provide a thorough analysis that ensures project success."#,
         })
     }
 
+    /// Summarize the status of the work items listed in the task
+    pub async fn summarize_status(&self, task: &Task) -> String {
+        info!("[ProjectManager] Summarizing status for task: {}", task.id);
+
+        let requirements = vec![
+            "Group items into done, in progress, and blocked".to_string(),
+            "Call out blockers and who or what they wait on".to_string(),
+            "Finish with the next steps".to_string(),
+        ];
+        match self
+            .create_report_with_claude(task, ManagementRequest::StatusSummary, requirements)
+            .await
+        {
+            Some(report) => report,
+            None => self.summarize_status_with_heuristics(&task.content),
+        }
+    }
+
+    /// Order the work items listed in the task by urgency
+    pub async fn prioritize(&self, task: &Task) -> String {
+        info!("[ProjectManager] Prioritizing items for task: {}", task.id);
+
+        let requirements = vec![
+            "Rank every item, most urgent first".to_string(),
+            "Give a one-line justification per item".to_string(),
+            "Flag items that block others".to_string(),
+        ];
+        match self
+            .create_report_with_claude(task, ManagementRequest::Prioritization, requirements)
+            .await
+        {
+            Some(report) => report,
+            None => self.prioritize_with_heuristics(&task.content),
+        }
+    }
+
+    /// Ask Claude for a markdown report, or None so the caller falls back to heuristics
+    async fn create_report_with_claude(
+        &self,
+        task: &Task,
+        request: ManagementRequest,
+        requirements: Vec<String>,
+    ) -> Option<String> {
+        let backend = self.backend.as_ref()?;
+
+        let code_request = crate::claude_code::CodeGenerationRequest {
+            language: "markdown".to_string(),
+            description: self.build_report_prompt(task, request),
+            context: HashMap::from([
+                ("task_type".to_string(), request.as_str().to_string()),
+                ("task_id".to_string(), task.id.clone()),
+            ])
+            .into_iter()
+            .chain(requester_context(task))
+            .collect(),
+            existing_code: None,
+            requirements,
+            session_id: Some(format!("pm-{}", session_key(task))),
+            dry_run: false,
+        };
+
+        match backend.generate_code(code_request).await {
+            Ok(result) if !result.explanation.trim().is_empty() => Some(result.explanation),
+            Ok(_) => {
+                warn!(
+                    "[ProjectManager] Claude returned an empty {}",
+                    request.as_str()
+                );
+                None
+            }
+            Err(e) => {
+                warn!("[ProjectManager] Claude {} failed: {}", request.as_str(), e);
+                None
+            }
+        }
+    }
+
+    /// Build the prompt for status summaries and prioritization
+    fn build_report_prompt(&self, task: &Task, request: ManagementRequest) -> String {
+        let objective = match request {
+            ManagementRequest::StatusSummary => {
+                "Summarize the status of the work described below. Group items into Done, \
+                 In Progress, and Blocked, then list the next steps."
+            }
+            ManagementRequest::Prioritization => {
+                "Prioritize the work items described below. Return a numbered list, most \
+                 urgent first, with a P0-P3 label and a one-line justification per item."
+            }
+            ManagementRequest::TaskBreakdown => "Break the work described below into phased tasks.",
+        };
+
+        let mut context_keys: Vec<_> = task.context.keys().collect();
+        context_keys.sort();
+        let context = context_keys
+            .into_iter()
+            .map(|key| format!("- {key}: {}", task.context[key]))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            r#"You are a Project Manager AI agent specializing in strategic analysis and coordination.
+
+{objective}
+
+## Request:
+{}
+
+## Context:
+{}
+
+Respond in concise markdown suitable for a chat message."#,
+            task.content,
+            if context.is_empty() { "None" } else { &context }
+        )
+    }
+
+    /// Group listed items by status keywords when Claude is unavailable
+    fn summarize_status_with_heuristics(&self, content: &str) -> String {
+        let mut done = Vec::new();
+        let mut in_progress = Vec::new();
+        let mut blocked = Vec::new();
+
+        for item in parse_work_items(content) {
+            let lower = item.to_lowercase();
+            if ["blocked", "waiting", "stuck"]
+                .iter()
+                .any(|keyword| lower.contains(keyword))
+            {
+                blocked.push(item);
+            } else if ["done", "completed", "finished", "merged", "shipped"]
+                .iter()
+                .any(|keyword| lower.contains(keyword))
+            {
+                done.push(item);
+            } else {
+                in_progress.push(item);
+            }
+        }
+
+        let mut summary = format!(
+            "## Status Summary\n{} item(s): {} done, {} in progress, {} blocked\n",
+            done.len() + in_progress.len() + blocked.len(),
+            done.len(),
+            in_progress.len(),
+            blocked.len()
+        );
+        for (heading, items) in [
+            ("Blocked", &blocked),
+            ("In Progress", &in_progress),
+            ("Done", &done),
+        ] {
+            if !items.is_empty() {
+                summary.push_str(&format!("\n### {heading}\n"));
+                for item in items {
+                    summary.push_str(&format!("- {item}\n"));
+                }
+            }
+        }
+        summary
+    }
+
+    /// Rank listed items by urgency keywords when Claude is unavailable
+    fn prioritize_with_heuristics(&self, content: &str) -> String {
+        let mut items: Vec<(u8, String)> = parse_work_items(content)
+            .into_iter()
+            .map(|item| (urgency_rank(&item), item))
+            .collect();
+        // Stable sort keeps the caller's order within a priority level
+        items.sort_by_key(|(rank, _)| *rank);
+
+        let mut report = String::from("## Prioritized Items\n");
+        for (position, (rank, item)) in items.iter().enumerate() {
+            report.push_str(&format!("{}. [P{rank}] {item}\n", position + 1));
+        }
+        report
+    }
+
     /// Assess task complexity based on content
     fn assess_complexity(&self, content: &str) -> ProjectComplexity {
         let desc_lower = content.to_lowercase();
@@ -259,7 +516,7 @@ impl Agent for ProjectManagerAgent {
     }
 
     fn description(&self) -> String {
-        "Strategic analysis, project planning, and multi-agent coordination".to_string()
+        "Strategic analysis, project planning, status summaries, and prioritization".to_string()
     }
 
     async fn can_handle(&self, task: &Task) -> bool {
@@ -269,9 +526,10 @@ impl Agent for ProjectManagerAgent {
             || task.content.to_lowercase().contains("coordinate")
             || task.content.to_lowercase().contains("strategy")
             || task.content.to_lowercase().contains("analyze")
+            || task.content.to_lowercase().contains("priorit")
     }
 
-    async fn execute(&self, task: Task) -> Result<TaskResult> {
+    async fn execute(&self, task: Task, orchestrator: OrchestratorHandle) -> Result<TaskResult> {
         let start_time = std::time::Instant::now();
 
         // Update status
@@ -280,57 +538,76 @@ impl Agent for ProjectManagerAgent {
             status.start_task(task.id.clone());
         }
 
-        info!("[ProjectManager] Executing task: {}", task.id);
+        let request = ManagementRequest::from_task(&task);
+        info!(
+            "[ProjectManager] Executing {} task: {}",
+            request.as_str(),
+            task.id
+        );
+
+        orchestrator.progress().report(10, request.phase()).await;
 
-        // Create project plan
-        let plan = match self.create_project_plan(&task).await {
-            Ok(p) => p,
-            Err(e) => {
-                let mut status = self.status.write().await;
-                status.fail_task();
-                return Ok(TaskResult {
-                    task_id: task.id,
-                    agent_type: self.agent_type(),
-                    result: TaskExecutionResult::Failure {
-                        error: format!("Failed to create project plan: {e}"),
-                        partial_output: None,
-                    },
-                    metadata: std::collections::HashMap::from([(
-                        "error".to_string(),
-                        "Planning failed".to_string(),
-                    )]),
-                    completed_at: chrono::Utc::now(),
-                });
+        let (output, mut metadata) = match request {
+            ManagementRequest::TaskBreakdown => {
+                let plan = match self.create_project_plan(&task).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        let mut status = self.status.write().await;
+                        status.fail_task(&task.id);
+                        return Ok(TaskResult {
+                            task_id: task.id,
+                            agent_type: self.agent_type(),
+                            result: TaskExecutionResult::Failure {
+                                error: format!("Failed to create project plan: {e}"),
+                                partial_output: None,
+                            },
+                            metadata: HashMap::from([(
+                                "error".to_string(),
+                                "Planning failed".to_string(),
+                            )]),
+                            completed_at: chrono::Utc::now(),
+                            changes: Vec::new(),
+                        });
+                    }
+                };
+
+                // Convert plan to JSON output
+                let output = serde_json::to_string_pretty(&plan)
+                    .unwrap_or_else(|e| format!("Failed to serialize plan: {e}"));
+                let metadata = HashMap::from([
+                    ("phases".to_string(), plan.phases.len().to_string()),
+                    ("complexity".to_string(), format!("{:?}", plan.complexity)),
+                    (
+                        "agents".to_string(),
+                        plan.resource_requirements.agents.join(", "),
+                    ),
+                ]);
+                (output, metadata)
+            }
+            ManagementRequest::StatusSummary => {
+                (self.summarize_status(&task).await, HashMap::new())
             }
+            ManagementRequest::Prioritization => (self.prioritize(&task).await, HashMap::new()),
         };
-
-        // Convert plan to JSON output
-        let output = serde_json::to_string_pretty(&plan)
-            .unwrap_or_else(|e| format!("Failed to serialize plan: {e}"));
+        metadata.insert("request".to_string(), request.as_str().to_string());
 
         // Update status
         {
             let mut status = self.status.write().await;
-            status.complete_task(start_time.elapsed().as_secs_f64());
+            status.complete_task(&task.id, start_time.elapsed().as_secs_f64());
         }
 
         Ok(TaskResult {
             task_id: task.id,
             agent_type: self.agent_type(),
             result: TaskExecutionResult::Success {
-                output: output.clone(),
+                output,
                 files_created: vec![],
                 files_modified: vec![],
             },
-            metadata: std::collections::HashMap::from([
-                ("phases".to_string(), plan.phases.len().to_string()),
-                ("complexity".to_string(), format!("{:?}", plan.complexity)),
-                (
-                    "agents".to_string(),
-                    plan.resource_requirements.agents.join(", "),
-                ),
-            ]),
+            metadata,
             completed_at: chrono::Utc::now(),
+            changes: Vec::new(),
         })
     }
 
@@ -379,6 +656,17 @@ impl Agent for ProjectManagerAgent {
                 "claude_code_client".to_string(),
                 "github_client".to_string(),
             ],
+            task_categories: vec![
+                "planning".to_string(),
+                "architecture".to_string(),
+                "coordination".to_string(),
+                "roadmap".to_string(),
+                "strategy".to_string(),
+                "status".to_string(),
+                "prioritize".to_string(),
+                "breakdown".to_string(),
+            ],
+            max_complexity: crate::models::TaskComplexity::Critical,
         }
     }
 
@@ -386,7 +674,9 @@ impl Agent for ProjectManagerAgent {
     /// Why: Project Manager formats output for strategic clarity
     /// Alternative: Generic formatting (rejected: loses strategic context)
     fn format_response(&self, result: &TaskResult) -> String {
-        const MAX_OUTPUT_RESPONSE: usize = 1500;
+        // Results are shown in an embed, whose 4096 character description leaves room
+        // for this and the lines added below
+        const MAX_OUTPUT_RESPONSE: usize = 3500;
 
         match &result.result {
             crate::models::TaskExecutionResult::Success { output, .. } => {
@@ -409,6 +699,54 @@ impl Agent for ProjectManagerAgent {
     }
 }
 
+/// Split request content into work items
+/// List entries win when present, so an introductory line is not ranked as an item
+fn parse_work_items(content: &str) -> Vec<String> {
+    let lines: Vec<(bool, String)> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let item = line
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .trim_start_matches(['-', '*', '•', '.', ')'])
+                .trim();
+            (item.len() < line.len(), item.to_string())
+        })
+        .filter(|(_, item)| !item.is_empty())
+        .collect();
+
+    let has_list = lines.iter().any(|(is_entry, _)| *is_entry);
+    lines
+        .into_iter()
+        .filter(|(is_entry, _)| !has_list || *is_entry)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// P0 (most urgent) to P3 based on keywords in the item
+fn urgency_rank(item: &str) -> u8 {
+    let lower = item.to_lowercase();
+    let contains_any = |keywords: &[&str]| keywords.iter().any(|keyword| lower.contains(keyword));
+
+    if contains_any(&[
+        "critical",
+        "urgent",
+        "security",
+        "outage",
+        "production",
+        "blocker",
+    ]) {
+        0
+    } else if contains_any(&["bug", "fix", "broken", "regression", "error", "fail"]) {
+        1
+    } else if contains_any(&["feature", "implement", "add", "build"]) {
+        2
+    } else {
+        3
+    }
+}
+
 /// Project plan structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectPlan {
@@ -475,6 +813,8 @@ mod tests {
             status: crate::models::TaskStatus::Pending,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            retry_count: 0,
+            max_retries: None,
         };
 
         assert!(agent.can_handle(&task).await);
@@ -513,6 +853,8 @@ mod tests {
             status: crate::models::TaskStatus::Pending,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            retry_count: 0,
+            max_retries: None,
         };
 
         let phases = agent.generate_phases(&task);
@@ -526,4 +868,84 @@ mod tests {
         // Phases should have dependencies
         assert!(phases[1].dependencies.contains(&"phase-1".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_request_kind_detection() {
+        let task = |content: &str| {
+            Task::new(
+                AgentType::ProjectManager,
+                content.to_string(),
+                crate::models::Priority::Medium,
+            )
+        };
+
+        assert_eq!(
+            ManagementRequest::from_task(&task("Prioritize the open bugs")),
+            ManagementRequest::Prioritization
+        );
+        assert_eq!(
+            ManagementRequest::from_task(&task("Give me a status update on the release")),
+            ManagementRequest::StatusSummary
+        );
+        assert_eq!(
+            ManagementRequest::from_task(&task("Plan the auth rewrite")),
+            ManagementRequest::TaskBreakdown
+        );
+
+        // The context key overrides keyword detection
+        let explicit = task("Prioritize the open bugs").with_context(
+            MANAGEMENT_REQUEST_CONTEXT_KEY.to_string(),
+            "status_summary".to_string(),
+        );
+        assert_eq!(
+            ManagementRequest::from_task(&explicit),
+            ManagementRequest::StatusSummary
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prioritization_without_claude() {
+        let agent = ProjectManagerAgent::new(None);
+        let task = Task::new(
+            AgentType::ProjectManager,
+            "Prioritize these:\n- Update the README\n- Fix broken login\n- Security patch for the API"
+                .to_string(),
+            crate::models::Priority::Medium,
+        );
+
+        let result = agent
+            .execute(task, OrchestratorHandle::detached())
+            .await
+            .unwrap();
+        assert_eq!(result.metadata.get("request").unwrap(), "prioritization");
+        let TaskExecutionResult::Success { output, .. } = result.result else {
+            panic!("Prioritization should succeed without Claude");
+        };
+
+        let lines: Vec<&str> = output.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "1. [P0] Security patch for the API",
+                "2. [P1] Fix broken login",
+                "3. [P3] Update the README",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_summary_without_claude() {
+        let agent = ProjectManagerAgent::new(None);
+        let task = Task::new(
+            AgentType::ProjectManager,
+            "Status summary please:\n1. Login page done\n2. API client in review\n3. Deploy blocked on credentials"
+                .to_string(),
+            crate::models::Priority::Medium,
+        );
+
+        let output = agent.summarize_status(&task).await;
+        assert!(output.contains("3 item(s): 1 done, 1 in progress, 1 blocked"));
+        assert!(output.contains("### Blocked\n- Deploy blocked on credentials"));
+        assert!(!output.contains("Status summary please"));
+    }
 }
@@ -0,0 +1,734 @@
+//! Quality Assurance Agent - Test execution and quality reporting
+//!
+//! This agent runs a project's own test suite inside a workspace, asks Claude to
+//! explain any failures, and returns the outcome as a structured quality report.
+
+use super::{
+    language_detection::{language_from_project_type, project_type_from_directory},
+    memory::memory_context,
+    orchestrator::ProgressReporter,
+    task_utils::session_key,
+    Agent, AgentStatus, OrchestratorHandle,
+};
+use crate::{
+    claude_code::{
+        costs::requester_context, sandbox::Sandbox, sessions::shared_session_key, ClaudeCodeClient,
+        TaskAnalysis, SESSION_DIR_PREFIX, WORKSPACE_DIR_PREFIX,
+    },
+    config::SandboxConfig,
+    constants::{QA_OUTPUT_EXCERPT_CHARS, QA_TEST_TIMEOUT_SECS},
+    llm::CodeGenerationBackend,
+    models::{AgentType, Task, TaskExecutionResult, TaskResult},
+    session::history::history_context,
+    Result, SpiralError,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Context key naming the workspace or repository directory to test
+pub const WORKSPACE_PATH_CONTEXT_KEY: &str = "workspace_path";
+
+/// Quality Assurance Agent for test execution and failure analysis
+pub struct QualityAssuranceAgent {
+    backend: Option<Arc<dyn CodeGenerationBackend>>,
+    /// Claude Code workspace root; only the workspaces directly inside it are tested
+    workspaces: PathBuf,
+    sandbox: Sandbox,
+    status: Arc<RwLock<AgentStatus>>,
+}
+
+impl QualityAssuranceAgent {
+    /// Create a new Quality Assurance Agent
+    pub fn new(claude_client: Option<ClaudeCodeClient>) -> Self {
+        let workspaces = claude_client
+            .as_ref()
+            .and_then(|client| client.workspaces_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("claude-workspaces"));
+        let sandbox = claude_client
+            .as_ref()
+            .map(|client| client.sandbox().clone())
+            .unwrap_or_else(|| Sandbox::new(SandboxConfig::default()));
+        Self {
+            workspaces,
+            sandbox,
+            backend: claude_client.map(|client| Arc::new(client) as Arc<dyn CodeGenerationBackend>),
+            status: Arc::new(RwLock::new(AgentStatus::new(AgentType::QualityAssurance))),
+        }
+    }
+
+    /// Generate with another backend instead of Claude Code
+    pub fn with_backend(mut self, backend: Arc<dyn CodeGenerationBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Run the workspace's test suite and build a quality report
+    pub async fn assess_workspace(
+        &self,
+        task: &Task,
+        progress: &ProgressReporter,
+    ) -> Result<QualityReport> {
+        progress.report(5, "locating workspace").await;
+        let workspace = self.resolve_workspace(task)?;
+        let project_type = project_type_from_directory(&workspace).ok_or_else(|| {
+            SpiralError::Validation(format!(
+                "No supported test suite found in {}",
+                workspace.display()
+            ))
+        })?;
+
+        progress
+            .report_with_message(10, "running tests", project_type)
+            .await;
+        let run = self.run_test_suite(&workspace, project_type).await?;
+        let summary = summarize_output(project_type, &run.output);
+
+        let mut report = QualityReport {
+            workspace: workspace.display().to_string(),
+            project_type: project_type.to_string(),
+            language: language_from_project_type(project_type),
+            command: run.command,
+            passed: run.success,
+            tests_passed: summary.passed,
+            tests_failed: summary.failed,
+            failed_tests: summary.failed_tests,
+            duration_secs: run.duration_secs,
+            analysis: None,
+            output_excerpt: output_tail(&run.output),
+        };
+
+        if !report.passed {
+            progress.report(80, "analyzing failures").await;
+            report.analysis = self.analyze_failures(task, &report).await;
+        }
+
+        info!(
+            "[QualityAssurance] {} in {}: passed={}, failing tests={}",
+            report.command,
+            report.workspace,
+            report.passed,
+            report.failed_tests.len()
+        );
+        Ok(report)
+    }
+
+    /// Resolve the workspace from task context, else the task's session workspace
+    fn resolve_workspace(&self, task: &Task) -> Result<PathBuf> {
+        let root = self.workspaces.canonicalize().map_err(|_| {
+            SpiralError::Validation(format!(
+                "No Claude Code workspaces exist in {}",
+                self.workspaces.display()
+            ))
+        })?;
+        resolve_workspace_in(task, &root)
+    }
+
+    /// Execute the project's test command inside the workspace
+    /// 🛡️ SECURITY AUDIT CHECKPOINT: With a sandbox configured the suite runs in a
+    /// container like Claude Code's, seeing only the workspace
+    async fn run_test_suite(&self, workspace: &Path, project_type: &str) -> Result<TestRun> {
+        let (program, args) = test_command(project_type).ok_or_else(|| {
+            SpiralError::Validation(format!("No test runner known for {project_type} projects"))
+        })?;
+        let command = format!("{program} {}", args.join(" "));
+        info!(
+            "[QualityAssurance] Running `{}` in {}",
+            command,
+            workspace.display()
+        );
+
+        let start_time = Instant::now();
+        let output = tokio::time::timeout(
+            Duration::from_secs(QA_TEST_TIMEOUT_SECS),
+            self.sandbox
+                .program_command(workspace, program)
+                .args(args)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .map_err(|_| SpiralError::Timeout {
+            message: format!("`{command}` did not finish within {QA_TEST_TIMEOUT_SECS}s"),
+        })?
+        .map_err(|e| SpiralError::Agent {
+            message: format!("Failed to run `{command}`: {e}"),
+        })?;
+
+        Ok(TestRun {
+            command,
+            success: output.status.success(),
+            output: format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            duration_secs: start_time.elapsed().as_secs_f64(),
+        })
+    }
+
+    /// Ask Claude to explain the failures, or None when Claude is unavailable
+    async fn analyze_failures(&self, task: &Task, report: &QualityReport) -> Option<String> {
+        let backend = self.backend.as_ref()?;
+
+        let failed_tests = if report.failed_tests.is_empty() {
+            "Not reported by the test runner".to_string()
+        } else {
+            report
+                .failed_tests
+                .iter()
+                .map(|name| format!("- {name}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let prompt = format!(
+            r#"You are a Quality Assurance AI agent reviewing a failed test run.
+
+## Command:
+{}
+
+## Failing tests:
+{}
+
+## Test output (tail):
+{}
+
+Explain the root cause of each failure, group failures that share a cause, and
+suggest the smallest fix for each. Respond in concise markdown."#,
+            report.command, failed_tests, report.output_excerpt
+        );
+
+        let code_request = crate::claude_code::CodeGenerationRequest {
+            language: report.language.clone(),
+            description: prompt,
+            context: HashMap::from([
+                ("task_type".to_string(), "quality_analysis".to_string()),
+                ("task_id".to_string(), task.id.clone()),
+            ])
+            .into_iter()
+            .chain(memory_context(task))
+            .chain(history_context(task))
+            .chain(requester_context(task))
+            .collect(),
+            existing_code: None,
+            requirements: vec![
+                "Identify the root cause of each failure".to_string(),
+                "Suggest the smallest fix".to_string(),
+            ],
+            session_id: Some(format!("qa-{}", session_key(task))),
+            dry_run: false,
+        };
+
+        match backend.generate_code(code_request).await {
+            Ok(result) if !result.explanation.trim().is_empty() => Some(result.explanation),
+            Ok(_) => {
+                warn!("[QualityAssurance] Claude returned an empty failure analysis");
+                None
+            }
+            Err(e) => {
+                warn!("[QualityAssurance] Claude failure analysis failed: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Agent for QualityAssuranceAgent {
+    fn agent_type(&self) -> AgentType {
+        AgentType::QualityAssurance
+    }
+
+    fn name(&self) -> String {
+        "Quality Assurance Agent".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Test suite execution, failure analysis, and quality reporting".to_string()
+    }
+
+    async fn can_handle(&self, task: &Task) -> bool {
+        let content = task.content.to_lowercase();
+        task.agent_type == AgentType::QualityAssurance
+            || task.context.contains_key(WORKSPACE_PATH_CONTEXT_KEY)
+            || content.contains("run tests")
+            || content.contains("test suite")
+            || content.contains("quality")
+    }
+
+    async fn execute(&self, task: Task, orchestrator: OrchestratorHandle) -> Result<TaskResult> {
+        let start_time = Instant::now();
+
+        // Update status
+        {
+            let mut status = self.status.write().await;
+            status.start_task(task.id.clone());
+        }
+
+        info!("[QualityAssurance] Executing task: {}", task.id);
+
+        let report = match self.assess_workspace(&task, orchestrator.progress()).await {
+            Ok(report) => report,
+            Err(e) => {
+                let mut status = self.status.write().await;
+                status.fail_task(&task.id);
+                return Ok(TaskResult {
+                    task_id: task.id,
+                    agent_type: self.agent_type(),
+                    result: TaskExecutionResult::Failure {
+                        error: format!("Quality check could not run: {e}"),
+                        partial_output: None,
+                    },
+                    metadata: HashMap::from([(
+                        "error".to_string(),
+                        "Test execution failed".to_string(),
+                    )]),
+                    completed_at: chrono::Utc::now(),
+                    changes: Vec::new(),
+                });
+            }
+        };
+
+        let output = serde_json::to_string_pretty(&report)
+            .unwrap_or_else(|e| format!("Failed to serialize quality report: {e}"));
+
+        // Update status
+        {
+            let mut status = self.status.write().await;
+            status.complete_task(&task.id, start_time.elapsed().as_secs_f64());
+        }
+
+        let mut metadata = HashMap::from([
+            ("quality_passed".to_string(), report.passed.to_string()),
+            ("project_type".to_string(), report.project_type.clone()),
+            (
+                "failed_tests".to_string(),
+                report.failed_tests.len().to_string(),
+            ),
+        ]);
+        if let Some(tests_passed) = report.tests_passed {
+            metadata.insert("tests_passed".to_string(), tests_passed.to_string());
+        }
+
+        // A run with failing tests is still a successful QA task: the report is the deliverable
+        Ok(TaskResult {
+            task_id: task.id,
+            agent_type: self.agent_type(),
+            result: TaskExecutionResult::Success {
+                output,
+                files_created: vec![],
+                files_modified: vec![],
+            },
+            metadata,
+            completed_at: chrono::Utc::now(),
+            changes: Vec::new(),
+        })
+    }
+
+    async fn analyze_task(&self, task: &Task) -> Result<TaskAnalysis> {
+        debug!("[QualityAssurance] Analyzing task: {}", task.id);
+
+        Ok(TaskAnalysis {
+            complexity: "Medium".to_string(),
+            estimated_minutes: 15,
+            required_skills: vec!["Test execution".to_string(), "Failure analysis".to_string()],
+            challenges: vec![
+                "Slow or flaky test suites".to_string(),
+                "Failures caused by the environment rather than the code".to_string(),
+            ],
+            approach: "Detect the project's test runner, run the suite, analyse failures"
+                .to_string(),
+            raw_analysis: format!("Quality Assurance analysis for task: {}", task.id),
+        })
+    }
+
+    /// 🏗️ ARCHITECTURE DECISION: QA-specific capabilities without languages
+    /// Why: QA runs whatever suite the workspace declares, and listing languages would
+    /// route implementation work in those languages away from the developer
+    /// Alternative: Advertise every supported toolchain (rejected: skews capability routing)
+    fn capabilities(&self) -> crate::models::AgentCapability {
+        crate::models::AgentCapability {
+            name: "Quality Assurance".to_string(),
+            description: "Test suite execution and failure analysis".to_string(),
+            supported_languages: vec![],
+            required_tools: vec!["claude_code_client".to_string(), "test_runner".to_string()],
+            task_categories: vec![
+                "qa".to_string(),
+                "quality".to_string(),
+                "tests".to_string(),
+                "coverage".to_string(),
+                "regression".to_string(),
+            ],
+            max_complexity: crate::models::TaskComplexity::High,
+        }
+    }
+
+    /// 🏗️ ARCHITECTURE DECISION: QA-specific report formatting
+    /// Why: A verdict and the failing tests matter more than the raw JSON report
+    /// Alternative: Generic formatting (rejected: buries the verdict in JSON)
+    fn format_response(&self, result: &TaskResult) -> String {
+        const MAX_FAILED_TESTS_SHOWN: usize = 10;
+        const MAX_ANALYSIS_RESPONSE: usize = 1000;
+
+        match &result.result {
+            TaskExecutionResult::Success { output, .. } => {
+                let Ok(report) = serde_json::from_str::<QualityReport>(output) else {
+                    return output.clone();
+                };
+
+                let mut response = String::from("**🧪 Quality Report:** ");
+                if report.passed {
+                    response.push_str("✅ All tests passed");
+                } else {
+                    response.push_str(&format!(
+                        "❌ {} failing test(s)",
+                        report
+                            .tests_failed
+                            .unwrap_or(report.failed_tests.len() as u32)
+                    ));
+                }
+                response.push_str(&format!(
+                    "\n`{}` in `{}` ({:.0}s)\n",
+                    report.command, report.workspace, report.duration_secs
+                ));
+
+                if !report.failed_tests.is_empty() {
+                    response.push_str("\n**Failing tests:**\n");
+                    for name in report.failed_tests.iter().take(MAX_FAILED_TESTS_SHOWN) {
+                        response.push_str(&format!("• {name}\n"));
+                    }
+                    if report.failed_tests.len() > MAX_FAILED_TESTS_SHOWN {
+                        response.push_str(&format!(
+                            "... and {} more\n",
+                            report.failed_tests.len() - MAX_FAILED_TESTS_SHOWN
+                        ));
+                    }
+                }
+
+                if let Some(analysis) = &report.analysis {
+                    response.push_str("\n**Analysis:**\n");
+                    let truncated: String = analysis.chars().take(MAX_ANALYSIS_RESPONSE).collect();
+                    response.push_str(&truncated);
+                    if truncated.len() < analysis.len() {
+                        response.push_str("\n\n... (analysis truncated for Discord limits)");
+                    }
+                }
+
+                response
+            }
+            TaskExecutionResult::Failure { error, .. } => {
+                format!("❌ Quality check failed: {error}")
+            }
+        }
+    }
+}
+
+/// Structured outcome of a QA run, returned as the task output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityReport {
+    pub workspace: String,
+    pub project_type: String,
+    pub language: String,
+    pub command: String,
+    /// Whether the test command exited successfully
+    pub passed: bool,
+    /// Counts are only present for runners whose summary line is understood
+    pub tests_passed: Option<u32>,
+    pub tests_failed: Option<u32>,
+    pub failed_tests: Vec<String>,
+    pub duration_secs: f64,
+    /// Claude's explanation of the failures, if any failed and Claude was reachable
+    pub analysis: Option<String>,
+    /// Tail of the combined stdout and stderr
+    pub output_excerpt: String,
+}
+
+/// Raw result of running a test command
+struct TestRun {
+    command: String,
+    success: bool,
+    output: String,
+    duration_secs: f64,
+}
+
+#[derive(Debug, Default)]
+struct TestSummary {
+    passed: Option<u32>,
+    failed: Option<u32>,
+    failed_tests: Vec<String>,
+}
+
+/// 🛡️ SECURITY BOUNDARY: Only Claude Code workspaces directly inside the root may be tested
+/// Why: Test suites execute arbitrary build scripts, so callers must not point the agent
+/// at any directory on the host, the server's own checkout included
+/// Alternative: Allow any directory under the working directory (rejected: that includes
+/// the server's source and configuration)
+fn resolve_workspace_in(task: &Task, root: &Path) -> Result<PathBuf> {
+    let requested = task
+        .context
+        .get(WORKSPACE_PATH_CONTEXT_KEY)
+        .cloned()
+        .or_else(|| {
+            shared_session_key(&task.context).map(|key| format!("{SESSION_DIR_PREFIX}{key}"))
+        })
+        .ok_or_else(|| {
+            SpiralError::Validation(format!(
+                "No workspace to test: set the {WORKSPACE_PATH_CONTEXT_KEY} or session context"
+            ))
+        })?;
+
+    // Absolute paths replace the root in the join; symlinks resolve before the check
+    let workspace = root
+        .join(&requested)
+        .canonicalize()
+        .map_err(|_| SpiralError::Validation(format!("Workspace {requested} does not exist")))?;
+    let is_claude_workspace = workspace.parent() == Some(root)
+        && workspace
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with(SESSION_DIR_PREFIX) || name.starts_with(WORKSPACE_DIR_PREFIX)
+            });
+    if !is_claude_workspace {
+        return Err(SpiralError::Validation(format!(
+            "Workspace {requested} is not a Claude Code workspace"
+        )));
+    }
+    if !workspace.is_dir() {
+        return Err(SpiralError::Validation(format!(
+            "Workspace {requested} is not a directory"
+        )));
+    }
+    Ok(workspace)
+}
+
+/// Test command for a project type from language_detection::project_type_from_directory
+fn test_command(project_type: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let command = match project_type {
+        "cargo" => ("cargo", &["test"][..]),
+        "npm" => ("npm", &["test"][..]),
+        "go" => ("go", &["test", "./..."][..]),
+        "pip" => ("python", &["-m", "pytest"][..]),
+        "maven" => ("mvn", &["test"][..]),
+        "gradle" => ("gradle", &["test"][..]),
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Pull pass/fail counts and failing test names out of the runner output
+fn summarize_output(project_type: &str, output: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+
+    for line in output.lines().map(str::trim) {
+        let failed_test = match project_type {
+            "cargo" => {
+                if let Some(result) = line.strip_prefix("test result: ") {
+                    *summary.passed.get_or_insert(0) += count_of(result, "passed");
+                    *summary.failed.get_or_insert(0) += count_of(result, "failed");
+                    None
+                } else {
+                    line.strip_prefix("test ")
+                        .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+                }
+            }
+            "go" => line
+                .strip_prefix("--- FAIL: ")
+                .and_then(|rest| rest.split_whitespace().next()),
+            "pip" => line
+                .strip_prefix("FAILED ")
+                .and_then(|rest| rest.split(" - ").next()),
+            "npm" => line.strip_prefix("FAIL "),
+            _ => None,
+        };
+
+        if let Some(name) = failed_test {
+            if !summary.failed_tests.iter().any(|known| known == name) {
+                summary.failed_tests.push(name.to_string());
+            }
+        }
+    }
+
+    summary
+}
+
+/// Count preceding a label in a `12 passed; 1 failed; ...` summary
+fn count_of(summary: &str, label: &str) -> u32 {
+    summary
+        .split(';')
+        .filter_map(|part| {
+            let mut words = part.split_whitespace().rev();
+            if words.next() != Some(label) {
+                return None;
+            }
+            words.next()?.parse::<u32>().ok()
+        })
+        .sum()
+}
+
+/// Keep the tail of the output, where runners print their failure summaries
+fn output_tail(output: &str) -> String {
+    let char_count = output.chars().count();
+    if char_count <= QA_OUTPUT_EXCERPT_CHARS {
+        return output.to_string();
+    }
+    let tail: String = output
+        .chars()
+        .skip(char_count - QA_OUTPUT_EXCERPT_CHARS)
+        .collect();
+    format!("[truncated]\n{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    fn qa_task(content: &str) -> Task {
+        Task::new(
+            AgentType::QualityAssurance,
+            content.to_string(),
+            Priority::Medium,
+        )
+    }
+
+    #[test]
+    fn test_cargo_output_summary() {
+        let output = "\
+running 3 tests
+test parser::tests::test_ok ... ok
+test parser::tests::test_empty ... FAILED
+test parser::tests::test_nested ... FAILED
+
+failures:
+    parser::tests::test_empty
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out
+   Doc-tests parser
+test result: ok. 4 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out
+";
+        let summary = summarize_output("cargo", output);
+
+        assert_eq!(summary.passed, Some(5));
+        assert_eq!(summary.failed, Some(2));
+        assert_eq!(
+            summary.failed_tests,
+            vec!["parser::tests::test_empty", "parser::tests::test_nested"]
+        );
+    }
+
+    #[test]
+    fn test_other_runner_failures() {
+        let go = summarize_output("go", "--- FAIL: TestParse (0.00s)\nFAIL\n");
+        assert_eq!(go.failed_tests, vec!["TestParse"]);
+        assert_eq!(go.passed, None);
+
+        let pytest = summarize_output(
+            "pip",
+            "FAILED tests/test_api.py::test_login - AssertionError\n",
+        );
+        assert_eq!(pytest.failed_tests, vec!["tests/test_api.py::test_login"]);
+    }
+
+    #[test]
+    fn test_workspace_must_be_a_claude_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        std::fs::create_dir(root_path.join("session-build")).unwrap();
+        std::fs::create_dir(root_path.join("project")).unwrap();
+
+        let session = root_path.join("session-build");
+        for requested in ["session-build", session.to_str().unwrap()] {
+            let task = qa_task("Run the tests").with_context(
+                WORKSPACE_PATH_CONTEXT_KEY.to_string(),
+                requested.to_string(),
+            );
+            assert_eq!(resolve_workspace_in(&task, &root_path).unwrap(), session);
+        }
+
+        // Without a path, the task's own session workspace
+        let task = qa_task("Run the tests").with_context(
+            crate::claude_code::sessions::SESSION_CONTEXT_KEY.to_string(),
+            "build".to_string(),
+        );
+        assert_eq!(resolve_workspace_in(&task, &root_path).unwrap(), session);
+
+        for requested in ["..", "/", ".", "project", "session-build/..", "missing"] {
+            let task = qa_task("Run the tests").with_context(
+                WORKSPACE_PATH_CONTEXT_KEY.to_string(),
+                requested.to_string(),
+            );
+            assert!(matches!(
+                resolve_workspace_in(&task, &root_path),
+                Err(SpiralError::Validation(_))
+            ));
+        }
+
+        // Paths in the request text are not followed
+        assert!(matches!(
+            resolve_workspace_in(&qa_task("Run the tests in ./session-build"), &root_path),
+            Err(SpiralError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_missing_test_suite() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("session-docs")).unwrap();
+        let mut agent = QualityAssuranceAgent::new(None);
+        agent.workspaces = root.path().to_path_buf();
+        let task = qa_task("Run the tests").with_context(
+            WORKSPACE_PATH_CONTEXT_KEY.to_string(),
+            "session-docs".to_string(),
+        );
+
+        let result = agent
+            .execute(task, OrchestratorHandle::detached())
+            .await
+            .unwrap();
+        let TaskExecutionResult::Failure { error, .. } = result.result else {
+            panic!("A workspace without a test suite cannot pass QA");
+        };
+        assert!(error.contains("No supported test suite"));
+    }
+
+    #[test]
+    fn test_format_response_shows_failures() {
+        let agent = QualityAssuranceAgent::new(None);
+        let report = QualityReport {
+            workspace: "/work/parser".to_string(),
+            project_type: "cargo".to_string(),
+            language: "rust".to_string(),
+            command: "cargo test".to_string(),
+            passed: false,
+            tests_passed: Some(1),
+            tests_failed: Some(1),
+            failed_tests: vec!["parser::tests::test_empty".to_string()],
+            duration_secs: 12.0,
+            analysis: Some("Empty input is not handled".to_string()),
+            output_excerpt: String::new(),
+        };
+        let result = TaskResult {
+            task_id: "task-1".to_string(),
+            agent_type: AgentType::QualityAssurance,
+            result: TaskExecutionResult::Success {
+                output: serde_json::to_string(&report).unwrap(),
+                files_created: vec![],
+                files_modified: vec![],
+            },
+            metadata: HashMap::new(),
+            completed_at: chrono::Utc::now(),
+            changes: Vec::new(),
+        };
+
+        let response = agent.format_response(&result);
+        assert!(response.contains("❌ 1 failing test(s)"));
+        assert!(response.contains("• parser::tests::test_empty"));
+        assert!(response.contains("Empty input is not handled"));
+    }
+}
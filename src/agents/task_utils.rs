@@ -1,3 +1,4 @@
+use crate::claude_code::sessions::shared_session_key;
 use crate::models::{AgentType, Priority, Task, TaskExecutionResult, TaskResult};
 use crate::SpiralError;
 /// 🛠️ TASK UTILITIES: Extracted via 3-strikes abstraction rule  
@@ -42,6 +43,23 @@ pub fn build_enriched_context(
     context
 }
 
+/// ⏱️ DURATION METADATA: Milliseconds from the task's creation to its result
+pub const DURATION_MS_METADATA_KEY: &str = "duration_ms";
+
+fn duration_ms(task: &Task) -> String {
+    (chrono::Utc::now() - task.created_at)
+        .num_milliseconds()
+        .max(0)
+        .to_string()
+}
+
+/// 🔄 SESSION KEY: The Claude session a task runs in
+/// Tasks sharing a Discord thread or an explicit session_id continue one session, so a
+/// follow-up builds on the earlier work; any other task gets a session of its own
+pub fn session_key(task: &Task) -> String {
+    shared_session_key(&task.context).unwrap_or_else(|| task.id.clone())
+}
+
 /// ✅ SUCCESS RESULT BUILDER: Standardized successful task result creation
 /// INLINE REASONING: Reduces boilerplate and ensures consistent result format
 /// Audit: Verify all success paths use this builder for consistency
@@ -65,6 +83,7 @@ pub fn create_success_result(
         "files_modified_count".to_string(),
         files_modified.len().to_string(),
     );
+    metadata.insert(DURATION_MS_METADATA_KEY.to_string(), duration_ms(task));
 
     // 🔧 CUSTOM METADATA: Merge agent-specific metadata
     if let Some(custom) = custom_metadata {
@@ -83,6 +102,7 @@ pub fn create_success_result(
         },
         metadata,
         completed_at: chrono::Utc::now(),
+        changes: Vec::new(),
     }
 }
 
@@ -104,6 +124,7 @@ pub fn create_failure_result(
         "has_partial_output".to_string(),
         partial_output.is_some().to_string(),
     );
+    metadata.insert(DURATION_MS_METADATA_KEY.to_string(), duration_ms(task));
 
     // 🔧 CUSTOM METADATA: Agent-specific error context
     if let Some(custom) = custom_metadata {
@@ -121,6 +142,7 @@ pub fn create_failure_result(
         },
         metadata,
         completed_at: chrono::Utc::now(),
+        changes: Vec::new(),
     }
 }
 
@@ -230,6 +252,8 @@ mod tests {
             context: HashMap::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            retry_count: 0,
+            max_retries: None,
         }
     }
 
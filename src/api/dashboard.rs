@@ -0,0 +1,65 @@
+use axum::response::Html;
+
+/// 📊 DASHBOARD: Static page that polls the status, queue, agent and metrics endpoints
+/// 🏗️ ARCHITECTURE DECISION: Embedded at compile time, no frontend build step
+/// Why: The page only reads existing JSON endpoints, so a single file ships with the binary
+/// Alternative: Separate SPA served from disk (rejected: extra deployment artifact)
+pub(super) async fn dashboard_page() -> Html<&'static str> {
+    Html(DASHBOARD_PAGE)
+}
+
+const DASHBOARD_PAGE: &str = include_str!("dashboard.html");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::AgentOrchestrator, api::ApiServer, config::Config};
+    use axum::{
+        body::Body,
+        extract::{ConnectInfo, Request},
+        http::{header, StatusCode},
+    };
+    use std::{net::SocketAddr, sync::Arc};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_dashboard_is_served_without_api_key() {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config, orchestrator).unwrap().build_router();
+
+        let mut request = Request::builder()
+            .uri("/dashboard")
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page = std::str::from_utf8(&body).unwrap();
+        // Every panel reads an existing versioned endpoint
+        for path in [
+            "/system/status",
+            "/queue",
+            "/agents",
+            "/system/metrics/history",
+        ] {
+            assert!(page.contains(&format!("getJson(\"{path}\")")), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_dashboard_page_loads_nothing_external() {
+        assert!(!DASHBOARD_PAGE.contains("<script src"));
+        assert!(!DASHBOARD_PAGE.contains("<link"));
+    }
+}
@@ -0,0 +1,287 @@
+use super::{CreateTaskRequest, CreateTaskResponse};
+use crate::{
+    auth::ApiKeyIdentity,
+    constants::{IDEMPOTENCY_KEY_MAX_LEN, IDEMPOTENCY_MAX_KEYS},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Request header clients set to make POST /tasks safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Response header marking a replayed response
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// What to do with a request carrying an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reservation {
+    /// First use of the key; the caller now owns it until it completes or releases it
+    New,
+    /// The key already created a task; return the same response again
+    Replay(CreateTaskResponse),
+    /// The first request with this key has not finished yet
+    InProgress,
+    /// The key was used for a different request
+    Mismatch,
+}
+
+struct Entry {
+    fingerprint: u64,
+    created: Instant,
+    /// None while the first request is still being handled
+    response: Option<CreateTaskResponse>,
+}
+
+/// 🔁 IDEMPOTENCY CACHE: Task IDs created per Idempotency-Key, kept for a fixed window
+/// 🏗️ ARCHITECTURE DECISION: In-memory, bounded, and reserved before submission
+/// Why: Retries arrive within seconds to minutes of the original, and reserving the key
+/// first makes concurrent duplicates wait for the original instead of racing it
+/// Alternative: Persist keys (rejected: a restart already drops queued tasks' context,
+/// so surviving keys would replay task IDs that no longer exist)
+pub struct IdempotencyCache {
+    window: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl IdempotencyCache {
+    /// A zero window disables idempotency keys
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    pub fn reserve(&self, key: &str, fingerprint: u64) -> Reservation {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        entries.retain(|_, entry| now.duration_since(entry.created) < self.window);
+
+        if let Some(entry) = entries.get(key) {
+            return match &entry.response {
+                _ if entry.fingerprint != fingerprint => Reservation::Mismatch,
+                Some(response) => Reservation::Replay(response.clone()),
+                None => Reservation::InProgress,
+            };
+        }
+
+        if entries.len() >= IDEMPOTENCY_MAX_KEYS {
+            // Drop the oldest finished key; in-flight ones are still needed by their request
+            let oldest = entries
+                .iter()
+                .filter(|(_, entry)| entry.response.is_some())
+                .min_by_key(|(_, entry)| entry.created)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key.to_string(),
+            Entry {
+                fingerprint,
+                created: now,
+                response: None,
+            },
+        );
+        Reservation::New
+    }
+
+    /// Record the response for a reserved key so retries replay it
+    pub fn complete(&self, key: &str, response: &CreateTaskResponse) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get_mut(key) {
+            entry.response = Some(response.clone());
+        }
+    }
+
+    /// Give up a reserved key after a failed request, so a retry can succeed
+    pub fn release(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries
+            .get(key)
+            .is_some_and(|entry| entry.response.is_none())
+        {
+            entries.remove(key);
+        }
+    }
+}
+
+/// Releases a reservation unless the request completed, including when the client
+/// disconnects and the handler is dropped mid-flight
+pub struct ReservationGuard<'a> {
+    cache: &'a IdempotencyCache,
+    key: String,
+    completed: bool,
+}
+
+impl<'a> ReservationGuard<'a> {
+    pub fn new(cache: &'a IdempotencyCache, key: String) -> Self {
+        Self {
+            cache,
+            key,
+            completed: false,
+        }
+    }
+
+    pub fn complete(mut self, response: &CreateTaskResponse) {
+        self.cache.complete(&self.key, response);
+        self.completed = true;
+    }
+}
+
+impl Drop for ReservationGuard<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.cache.release(&self.key);
+        }
+    }
+}
+
+/// Keys are opaque to the server, but must be short printable ASCII
+pub fn validate_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= IDEMPOTENCY_KEY_MAX_LEN
+        && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// The cache key for `key` sent by `identity`; each API key has its own keys, so two
+/// clients picking the same key neither replay each other's tasks nor learn it was used
+pub fn scoped_key(identity: Option<&ApiKeyIdentity>, key: &str) -> String {
+    let owner = identity
+        .map(|identity| identity.key_id.as_deref().unwrap_or("master"))
+        .unwrap_or("anonymous");
+    format!("{owner}/{key}")
+}
+
+/// Identifies the request a key was first used with, independent of context key order
+/// The session is part of the request, since it changes the task that is created
+pub fn fingerprint(request: &CreateTaskRequest, session_id: Option<&uuid::Uuid>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    session_id.hash(&mut hasher);
+    request.content.hash(&mut hasher);
+    request.max_retries.hash(&mut hasher);
+    request.callback_url.hash(&mut hasher);
+    for part in [
+        serde_json::to_string(&request.agent_type),
+        serde_json::to_string(&request.priority),
+        serde_json::to_string(&request.schedule),
+    ] {
+        part.unwrap_or_default().hash(&mut hasher);
+    }
+    request
+        .context
+        .as_ref()
+        .map(|context| context.iter().collect::<BTreeMap<_, _>>())
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(task_id: &str) -> CreateTaskResponse {
+        CreateTaskResponse {
+            task_id: task_id.to_string(),
+            status: "submitted".to_string(),
+            schedule_id: None,
+            next_run_at: None,
+        }
+    }
+
+    #[test]
+    fn test_replays_completed_requests() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        assert_eq!(cache.reserve("key-1", 7), Reservation::New);
+        assert_eq!(cache.reserve("key-1", 7), Reservation::InProgress);
+
+        ReservationGuard::new(&cache, "key-1".to_string()).complete(&response("task_1"));
+        assert_eq!(
+            cache.reserve("key-1", 7),
+            Reservation::Replay(response("task_1"))
+        );
+        assert_eq!(cache.reserve("key-1", 8), Reservation::Mismatch);
+        assert_eq!(cache.reserve("key-2", 7), Reservation::New);
+    }
+
+    #[test]
+    fn test_keys_are_scoped_per_api_key() {
+        let key = |key_id: Option<&str>| ApiKeyIdentity {
+            key_id: key_id.map(str::to_string),
+            name: "client".to_string(),
+            scopes: Vec::new(),
+        };
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let first = scoped_key(Some(&key(Some("key_a"))), "retry-1");
+        cache.reserve(&first, 7);
+        cache.complete(&first, &response("task_1"));
+
+        for other in [
+            scoped_key(Some(&key(Some("key_b"))), "retry-1"),
+            scoped_key(Some(&ApiKeyIdentity::master()), "retry-1"),
+            scoped_key(None, "retry-1"),
+        ] {
+            assert_eq!(cache.reserve(&other, 7), Reservation::New);
+        }
+        assert_eq!(
+            cache.reserve(&first, 7),
+            Reservation::Replay(response("task_1"))
+        );
+    }
+
+    #[test]
+    fn test_failed_requests_free_their_key() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        assert_eq!(cache.reserve("key-1", 7), Reservation::New);
+        drop(ReservationGuard::new(&cache, "key-1".to_string()));
+        assert_eq!(cache.reserve("key-1", 7), Reservation::New);
+    }
+
+    #[test]
+    fn test_keys_expire_after_window() {
+        let cache = IdempotencyCache::new(Duration::from_millis(20));
+        assert_eq!(cache.reserve("key-1", 7), Reservation::New);
+        cache.complete("key-1", &response("task_1"));
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(cache.reserve("key-1", 7), Reservation::New);
+        assert!(!IdempotencyCache::new(Duration::ZERO).is_enabled());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_context_order() {
+        let request = |pairs: &[(&str, &str)]| CreateTaskRequest {
+            agent_type: None,
+            content: "Write a parser".to_string(),
+            priority: None,
+            context: Some(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            max_retries: None,
+            schedule: None,
+            callback_url: None,
+        };
+        let a = request(&[("user_id", "1"), ("guild_id", "2"), ("channel_id", "3")]);
+        let b = request(&[("channel_id", "3"), ("user_id", "1"), ("guild_id", "2")]);
+        let c = request(&[("user_id", "9")]);
+        assert_eq!(fingerprint(&a, None), fingerprint(&b, None));
+        assert_ne!(fingerprint(&a, None), fingerprint(&c, None));
+        let session = uuid::Uuid::new_v4();
+        assert_ne!(fingerprint(&a, None), fingerprint(&a, Some(&session)));
+
+        assert!(validate_key("3f1c2b9e-retry"));
+        assert!(!validate_key(""));
+        assert!(!validate_key("has space"));
+        assert!(!validate_key(&"k".repeat(IDEMPOTENCY_KEY_MAX_LEN + 1)));
+    }
+}
@@ -0,0 +1,181 @@
+use super::ErrorResponse;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tracing::debug;
+use utoipa::ToSchema;
+
+/// Why a connection was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpRejection {
+    Denylisted,
+    NotAllowlisted,
+}
+
+/// Connections refused by the IP filter since startup
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IpFilterStats {
+    pub allowlist_size: usize,
+    pub denylist_size: usize,
+    /// Requests from an address in the denylist
+    pub denylisted_rejections: u64,
+    /// Requests from an address outside a non-empty allowlist
+    pub not_allowlisted_rejections: u64,
+}
+
+/// 🛡️ IP FILTER: Network-level allow/deny lists in front of every API route
+/// 🛡️ SECURITY DECISION: Matched against the TCP peer address only
+/// Why: X-Forwarded-For is set by the client unless a trusted proxy rewrites it, so
+/// trusting it would let anyone step around the lists
+/// Alternative: Honour proxy headers (rejected: no trusted-proxy configuration yet)
+/// Trade-off: Behind a reverse proxy the lists see the proxy's address
+pub struct IpFilter {
+    allowlist: Vec<IpNet>,
+    denylist: Vec<IpNet>,
+    denylisted: AtomicU64,
+    not_allowlisted: AtomicU64,
+}
+
+impl IpFilter {
+    pub fn new(allowlist: Vec<IpNet>, denylist: Vec<IpNet>) -> Self {
+        Self {
+            allowlist,
+            denylist,
+            denylisted: AtomicU64::new(0),
+            not_allowlisted: AtomicU64::new(0),
+        }
+    }
+
+    /// The denylist wins over the allowlist; an empty allowlist allows every address
+    pub fn check(&self, ip: IpAddr) -> Result<(), IpRejection> {
+        // IPv4 clients on a dual-stack socket arrive as ::ffff:a.b.c.d
+        let ip = ip.to_canonical();
+        if self.denylist.iter().any(|net| net.contains(&ip)) {
+            self.denylisted.fetch_add(1, Ordering::Relaxed);
+            return Err(IpRejection::Denylisted);
+        }
+        if !self.allowlist.is_empty() && !self.allowlist.iter().any(|net| net.contains(&ip)) {
+            self.not_allowlisted.fetch_add(1, Ordering::Relaxed);
+            return Err(IpRejection::NotAllowlisted);
+        }
+        Ok(())
+    }
+
+    pub fn stats(&self) -> IpFilterStats {
+        IpFilterStats {
+            allowlist_size: self.allowlist.len(),
+            denylist_size: self.denylist.len(),
+            denylisted_rejections: self.denylisted.load(Ordering::Relaxed),
+            not_allowlisted_rejections: self.not_allowlisted.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Refuse requests from filtered addresses before rate limiting or auth see them
+/// Rejections are only logged at debug level and counted; a blocked scanner would
+/// otherwise fill the logs
+pub async fn ip_filter_middleware(
+    State(filter): State<Arc<IpFilter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match filter.check(addr.ip()) {
+        Ok(()) => next.run(request).await,
+        Err(reason) => {
+            debug!(
+                "IP filter refused {} {} from {}: {:?}",
+                request.method(),
+                request.uri().path(),
+                addr.ip(),
+                reason
+            );
+            (
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse {
+                    error: "Forbidden".to_string(),
+                    details: Some("Client address is not allowed".to_string()),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::AgentOrchestrator, api::ApiServer, config::Config};
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    fn nets(list: &[&str]) -> Vec<IpNet> {
+        list.iter().map(|net| net.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_denylist_wins_over_allowlist() {
+        let filter = IpFilter::new(nets(&["10.0.0.0/8"]), nets(&["10.0.0.66/32"]));
+
+        assert_eq!(filter.check("10.1.2.3".parse().unwrap()), Ok(()));
+        assert_eq!(
+            filter.check("10.0.0.66".parse().unwrap()),
+            Err(IpRejection::Denylisted)
+        );
+        assert_eq!(
+            filter.check("192.168.1.1".parse().unwrap()),
+            Err(IpRejection::NotAllowlisted)
+        );
+        // IPv4-mapped IPv6 matches the IPv4 networks
+        assert_eq!(filter.check("::ffff:10.1.2.3".parse().unwrap()), Ok(()));
+
+        let stats = filter.stats();
+        assert_eq!(stats.denylisted_rejections, 1);
+        assert_eq!(stats.not_allowlisted_rejections, 1);
+    }
+
+    #[test]
+    fn test_empty_lists_allow_everyone() {
+        let filter = IpFilter::new(Vec::new(), Vec::new());
+        assert_eq!(filter.check("203.0.113.9".parse().unwrap()), Ok(()));
+        assert_eq!(filter.check("2001:db8::1".parse().unwrap()), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_clients_are_refused_before_auth() {
+        let mut config = Config::test_config();
+        config.api.ip_allowlist = nets(&["127.0.0.0/8"]);
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config, orchestrator).unwrap().build_router();
+
+        async fn status(router: &axum::Router, peer: [u8; 4]) -> StatusCode {
+            let mut request = Request::get("/health").body(Body::empty()).unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from((peer, 4000))));
+            router.clone().oneshot(request).await.unwrap().status()
+        }
+
+        // No API key either way: the allowed client reaches auth, the other does not
+        assert_eq!(
+            status(&router, [127, 0, 0, 1]).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&router, [198, 51, 100, 7]).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use super::{ApiServer, ErrorResponse};
+use axum::{
+    extract::DefaultBodyLimit,
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Json, Response},
+    Router,
+};
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// 📏 BODY LIMIT: Cap how much of a request body the routes will read
+/// 🛡️ SECURITY DECISION: Enforced as a layer on the body itself, not only in extractors
+/// Why: A request announcing a too-large Content-Length is refused before any of it is
+/// read, and a chunked body is cut off as soon as it passes the limit, so an oversized
+/// submission never sits in memory
+/// Alternative: axum's DefaultBodyLimit alone (rejected: fixed 2MB, and only applies to
+/// extractors that opt in); it is disabled so the configured limit is the only one
+pub(super) fn with_body_limit(router: Router<ApiServer>, max_bytes: usize) -> Router<ApiServer> {
+    router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(max_bytes))
+        .layer(middleware::map_response(payload_too_large_json))
+}
+
+/// The limit layer answers in plain text; clients get the usual JSON error instead
+async fn payload_too_large_json(response: Response) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(ErrorResponse {
+            error: "Request body too large".to_string(),
+            details: None,
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::AgentOrchestrator, config::Config};
+    use axum::{
+        body::Body,
+        extract::{ConnectInfo, Request},
+        http::header,
+    };
+    use futures::stream;
+    use std::{net::SocketAddr, sync::Arc};
+    use tower::ServiceExt;
+
+    async fn router(config: Config) -> Router {
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        ApiServer::new(config, orchestrator).unwrap().build_router()
+    }
+
+    fn request(config: &Config, method: &str, uri: &str, body: Body) -> Request {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-api-key", config.api.api_key.clone().unwrap())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        request
+    }
+
+    fn task_body(content_len: usize) -> String {
+        serde_json::json!({
+            "agent_type": "SoftwareDeveloper",
+            "content": "x".repeat(content_len),
+        })
+        .to_string()
+    }
+
+    async fn error_message(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice::<ErrorResponse>(&body)
+            .unwrap()
+            .error
+    }
+
+    #[tokio::test]
+    async fn test_oversized_task_submissions_are_rejected() {
+        let mut config = Config::test_config();
+        config.api.max_body_bytes = 1024;
+        let router = router(config.clone()).await;
+
+        // Declared length over the limit: refused without reading the body
+        let response = router
+            .clone()
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/tasks",
+                Body::from(task_body(4096)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_message(response).await, "Request body too large");
+
+        // Chunked body with no length: cut off once it passes the limit
+        let chunks = (0..64).map(|_| Ok::<_, std::io::Error>(task_body(4096)));
+        let response = router
+            .clone()
+            .oneshot(request(
+                &config,
+                "POST",
+                "/tasks",
+                Body::from_stream(stream::iter(chunks)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Within the limit, the request reaches the handler
+        let response = router
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/tasks",
+                Body::from(task_body(16)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_batches_use_their_own_limit() {
+        let mut config = Config::test_config();
+        config.api.max_body_bytes = 1024;
+        config.api.max_batch_body_bytes = 1024 * 1024;
+        let router = router(config.clone()).await;
+
+        // Larger than a single task may be, but within the batch limit
+        let batch = format!(r#"{{"tasks": [{}, {}]}}"#, task_body(1000), task_body(1000));
+        let response = router
+            .clone()
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/tasks/batch",
+                Body::from(batch),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let batch = format!(r#"{{"tasks": [{}]}}"#, task_body(2 * 1024 * 1024));
+        let response = router
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/tasks/batch",
+                Body::from(batch),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_responses_are_compressed_on_request() {
+        let config = Config::test_config();
+        let router = router(config.clone()).await;
+
+        let mut compressed = request(&config, "GET", "/v1/queue", Body::empty());
+        compressed
+            .headers_mut()
+            .insert(header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+        let response = router.clone().oneshot(compressed).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let response = router
+            .oneshot(request(&config, "GET", "/v1/queue", Body::empty()))
+            .await
+            .unwrap();
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    }
+}
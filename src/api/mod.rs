@@ -1,27 +1,88 @@
 use crate::{
-    agents::AgentOrchestrator,
-    auth::{auth_middleware, create_auth_state},
-    config::{ApiConfig, Config},
-    models::{AgentType, Priority, Task, TaskStatus},
-    monitoring::SystemMonitor,
-    rate_limit::rate_limit_middleware, // RateLimitConfig},
+    agents::{
+        memory::{MemoryEntry, MemoryKind, MemoryScope, MEMORY_SCOPE_CONTEXT_KEYS},
+        orchestrator::{
+            delegation::DELEGATION_CONTEXT_KEYS, AgentTaskRecord, DispatchState, QueueMove,
+            QueuedTask, Submitter, TaskEvent, TaskProgress, TaskSchedule, WebhookDelivery,
+            WorkflowRun,
+        },
+        quality_assurance::WORKSPACE_PATH_CONTEXT_KEY,
+        AgentOrchestrator,
+    },
+    audit::{self, AuditEvent, AuditEventKind, AuditQuery, AuditSource},
+    auth::{
+        auth_middleware, create_auth_state, ApiKeyIdentity, ApiKeyInfo, ApiKeyScope, ApiKeyStore,
+        Role,
+    },
+    claude_code::{
+        circuit_breaker::{CircuitBreaker, CircuitBreakerMetrics},
+        sessions::{MANAGED_SESSION_KEY_PREFIX, SESSION_CONTEXT_KEY},
+        transcripts::TranscriptEntry,
+        workspace_archive, ClaudeCliStatus, CostReport,
+    },
+    config::{ApiConfig, Config, ConfigHandle},
+    models::{
+        AgentType, FileChange, Priority, Task, TaskBatchStatus, TaskExecutionResult, TaskResult,
+        TaskStatus,
+    },
+    monitoring::{AvailabilityReport, HistoryResolution, RequestMetrics, SystemMonitor},
+    rate_limit::{
+        adaptive::{AdaptiveMode, AdaptiveRateLimitStatus},
+        auth_failure_limit_middleware, rate_limit_middleware,
+        tiers::{RateLimitTier, RateLimitTiers},
+        ApiRateLimiter,
+    },
+    session::{
+        Session, SessionConfig, SessionManager, SharedSessionManager, USER_SESSION_CONTEXT_KEY,
+    },
     validation::TaskContentValidator,
-    Result,
-    SpiralError,
+    Result, SpiralError,
 };
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     middleware,
-    response::Json,
-    routing::{get, post},
-    Router,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
+    routing::{delete, get, post, put},
+    Extension, Router,
 };
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
+use tokio::sync::OwnedMutexGuard;
 use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
 use tracing::{error, info, warn};
+use utoipa::{IntoParams, ToSchema};
+
+#[cfg(feature = "dashboard")]
+mod dashboard;
+mod idempotency;
+mod ip_filter;
+mod limits;
+mod openapi;
+mod request_metrics;
+mod result_files;
+mod sessions;
+mod task_stream;
+mod versioning;
+mod websocket;
+mod workspace_admin;
+
+use idempotency::{IdempotencyCache, Reservation, ReservationGuard};
+pub use ip_filter::IpFilterStats;
+use ip_filter::{ip_filter_middleware, IpFilter};
+pub use openapi::ApiDoc;
+use result_files::read_result_files;
+pub use result_files::ResultFile;
+use task_stream::task_update_stream;
+pub use task_stream::TaskStreamItem;
+pub use versioning::{unversioned_path, API_V1_PREFIX};
+pub use websocket::{WsCommand, WsMessage, WsTopic};
+pub use workspace_admin::WorkspaceFile;
 
 // 🏗️ ARCHITECTURE DECISION: Service metadata constants
 // Why: Centralized version and service info for consistency
@@ -36,16 +97,62 @@ const SERVICE_VERSION: &str = "0.1.0";
 // Audit: All routes must be defined here
 const ROUTE_HEALTH: &str = "/health";
 const ROUTE_TASKS: &str = "/tasks";
+const ROUTE_TASK_BATCH: &str = "/tasks/batch";
+const ROUTE_TASK_BATCH_BY_ID: &str = "/tasks/batch/{batch_id}";
 const ROUTE_TASK_BY_ID: &str = "/tasks/{task_id}";
 const ROUTE_TASK_ANALYZE: &str = "/tasks/{task_id}/analyze";
+const ROUTE_ANALYZE: &str = "/analyze";
+const ROUTE_TASK_EVENTS: &str = "/tasks/{task_id}/events";
+const ROUTE_TASK_PROGRESS: &str = "/tasks/{task_id}/progress";
+const ROUTE_TASK_RESULT: &str = "/tasks/{task_id}/result";
+const ROUTE_TASK_STREAM: &str = "/tasks/{task_id}/stream";
+const ROUTE_TASK_WEBHOOK: &str = "/tasks/{task_id}/webhook";
+const ROUTE_TASK_TRANSCRIPT: &str = "/tasks/{task_id}/transcript";
 const ROUTE_AGENTS: &str = "/agents";
 const ROUTE_AGENT_BY_TYPE: &str = "/agents/{agent_type}";
+const ROUTE_AGENT_MEMORY: &str = "/agents/{agent_type}/memory";
+const ROUTE_AGENT_TASKS: &str = "/agents/{agent_type}/tasks";
+const ROUTE_MEMORY_BY_ID: &str = "/memory/{memory_id}";
 const ROUTE_SYSTEM_STATUS: &str = "/system/status";
 const ROUTE_SYSTEM_METRICS: &str = "/system/metrics";
+const ROUTE_PROMETHEUS_METRICS: &str = "/metrics";
 const ROUTE_SYSTEM_METRICS_HISTORY: &str = "/system/metrics/history";
 const ROUTE_SYSTEM_HEALTH: &str = "/system/health";
+const ROUTE_SYSTEM_AVAILABILITY: &str = "/system/availability";
+const ROUTE_SYSTEM_PAUSE: &str = "/system/pause";
+const ROUTE_SYSTEM_RESUME: &str = "/system/resume";
+const ROUTE_SYSTEM_DRAIN: &str = "/system/drain";
+const ROUTE_SYSTEM_COSTS: &str = "/system/costs";
 const ROUTE_CIRCUIT_BREAKERS: &str = "/circuit-breakers";
+const ROUTE_CIRCUIT_BREAKER_RESET: &str = "/circuit-breakers/{name}/reset";
+const ROUTE_CIRCUIT_BREAKER_TRIP: &str = "/circuit-breakers/{name}/trip";
 const ROUTE_WORKSPACES: &str = "/workspaces";
+const ROUTE_WORKSPACE_BY_ID: &str = "/workspaces/{workspace_id}";
+const ROUTE_WORKSPACE_ARCHIVE: &str = "/workspaces/{workspace_id}/archive";
+const ROUTE_WORKSPACE_FILES: &str = "/workspaces/{workspace_id}/files";
+const ROUTE_SCHEDULES: &str = "/schedules";
+const ROUTE_SESSIONS: &str = "/sessions";
+const ROUTE_SESSION_BY_ID: &str = "/sessions/{session_id}";
+const ROUTE_SESSION_TREE: &str = "/sessions/{session_id}/tree";
+const ROUTE_SESSION_HISTORY: &str = "/sessions/{session_id}/history";
+const ROUTE_QUEUE: &str = "/queue";
+const ROUTE_QUEUE_PROMOTE: &str = "/queue/{task_id}/promote";
+const ROUTE_QUEUE_DEMOTE: &str = "/queue/{task_id}/demote";
+const ROUTE_WEBSOCKET: &str = "/ws";
+const ROUTE_WORKFLOWS: &str = "/workflows";
+const ROUTE_WORKFLOW_RUN: &str = "/workflows/{name}/run";
+const ROUTE_WORKFLOW_RUN_BY_ID: &str = "/workflows/runs/{run_id}";
+const ROUTE_OPENAPI: &str = "/openapi.json";
+const ROUTE_DOCS: &str = "/docs";
+const ROUTE_ADMIN_KEYS: &str = "/admin/keys";
+const ROUTE_ADMIN_KEY_BY_ID: &str = "/admin/keys/{key_id}";
+const ROUTE_ADMIN_AUDIT: &str = "/admin/audit";
+const ROUTE_ADMIN_LOG_LEVEL: &str = "/admin/log-level";
+const ROUTE_ADMIN_ADAPTIVE_RATE_LIMITS: &str = "/admin/rate-limits/adaptive";
+const ROUTE_ADMIN_RATE_LIMIT_TIERS: &str = "/admin/rate-limits/tiers";
+const ROUTE_ADMIN_RATE_LIMIT_TIER: &str = "/admin/rate-limits/tiers/{subject}";
+#[cfg(feature = "dashboard")]
+const ROUTE_DASHBOARD: &str = "/dashboard";
 
 // 🏗️ ARCHITECTURE DECISION: Error message constants
 // Why: Consistent error messages across API responses
@@ -55,6 +162,10 @@ const ERROR_AGENT_NOT_FOUND: &str = "Agent not found";
 const ERROR_INVALID_CONTENT: &str = "Invalid task content";
 const ERROR_INVALID_CONTEXT_KEY: &str = "Invalid context key";
 const ERROR_INVALID_CONTEXT_VALUE: &str = "Invalid context value";
+const ERROR_BATCH_NOT_FOUND: &str = "Batch not found";
+const ERROR_INVALID_MEMORY: &str = "Invalid memory";
+const ERROR_WORKSPACE_NOT_FOUND: &str = "Workspace not found";
+const ERROR_INVALID_CALLBACK: &str = "Invalid callback_url";
 
 // ⚡ PERFORMANCE DECISION: Workspace status thresholds
 // Why: Time-based categorization for workspace activity
@@ -68,6 +179,7 @@ const WORKSPACE_IDLE_THRESHOLD_SECS: u64 = 86400; // 24 hours
 // Why: Consistent fallback values for missing data
 const UNKNOWN_VALUE: &str = "unknown";
 const WORKSPACES_DIR: &str = "claude-workspaces";
+const WORKSPACE_ARCHIVE_DIR: &str = "claude-workspaces-archive";
 const SESSION_PREFIX: &str = "session-";
 
 #[derive(Clone)]
@@ -76,37 +188,232 @@ pub struct ApiServer {
     orchestrator: Arc<AgentOrchestrator>,
     validator: TaskContentValidator,
     system_monitor: Option<Arc<SystemMonitor>>,
-    // rate_limiter: RateLimitConfig,
+    idempotency: Arc<IdempotencyCache>,
+    api_keys: Arc<ApiKeyStore>,
+    ip_filter: Arc<IpFilter>,
+    sessions: SharedSessionManager,
+    request_metrics: RequestMetrics,
+    rate_limiter: ApiRateLimiter,
+    /// Source of reloaded rate limits
+    config_handle: Option<ConfigHandle>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateTaskRequest {
-    pub agent_type: AgentType,
+    /// Omit to let the orchestrator route the task to the best-capable agent
+    #[serde(default)]
+    pub agent_type: Option<AgentType>,
     pub content: String,
     pub priority: Option<Priority>,
     pub context: Option<HashMap<String, String>>,
+    /// Overrides the orchestrator's default retry budget for this task, up to that default
+    pub max_retries: Option<u32>,
+    /// Defer the task (`{"run_at": "<rfc3339>"}`) or repeat it (`{"cron": "*/5 * * * *"}`)
+    pub schedule: Option<TaskSchedule>,
+    /// Allowlisted URL that receives a signed POST when the task completes or fails
+    #[serde(default)]
+    pub callback_url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct CreateTaskResponse {
     pub task_id: String,
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateTaskBatchRequest {
+    pub tasks: Vec<CreateTaskRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateTaskBatchResponse {
+    pub batch_id: String,
+    pub task_ids: Vec<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskBatchStatusResponse {
+    pub batch_id: String,
+    pub task_ids: Vec<String>,
+    pub created_at: String,
+    pub finished: bool,
+    #[serde(flatten)]
+    pub counts: TaskBatchStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RunWorkflowRequest {
+    pub content: String,
+    pub priority: Option<Priority>,
+    pub context: Option<HashMap<String, String>>,
+    /// Retry budget applied to every step's task
+    pub max_retries: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoreMemoryRequest {
+    pub scope: MemoryScope,
+    pub kind: MemoryKind,
+    pub content: String,
+}
+
+/// Scopes to recall from; at least one is required, matching the task context keys
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MemoryQueryParams {
+    pub user: Option<String>,
+    pub guild: Option<String>,
+    pub workspace: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueueResponse {
+    pub length: usize,
+    pub in_flight: usize,
+    pub tasks: Vec<QueuedTask>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowResponse {
+    pub name: String,
+    pub steps: Vec<AgentType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleResponse {
+    pub schedule_id: String,
+    pub task_id: String,
+    pub agent_type: AgentType,
+    pub schedule: TaskSchedule,
+    pub next_run_at: String,
+    pub last_run_at: Option<String>,
+    pub run_count: u64,
+    pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl From<crate::agents::orchestrator::ScheduledTask> for ScheduleResponse {
+    fn from(scheduled: crate::agents::orchestrator::ScheduledTask) -> Self {
+        Self {
+            schedule_id: scheduled.id,
+            task_id: scheduled.template.id,
+            agent_type: scheduled.template.agent_type,
+            schedule: scheduled.schedule,
+            next_run_at: scheduled.next_run_at.to_rfc3339(),
+            last_run_at: scheduled.last_run_at.map(|t| t.to_rfc3339()),
+            run_count: scheduled.run_count,
+            created_at: scheduled.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TaskStatusResponse {
     pub task_id: String,
     pub agent_type: AgentType,
     pub status: TaskStatus,
     pub created_at: String,
     pub updated_at: String,
+    pub retry_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskResultResponse {
+    pub task_id: String,
+    pub agent_type: AgentType,
+    pub success: bool,
+    /// The agent's output, or whatever it produced before failing
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub files_created: Vec<String>,
+    pub files_modified: Vec<String>,
+    /// Only with `include_content=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<ResultFile>>,
+    /// What the task changed in its workspace: new files' content and unified diffs
+    pub changes: Vec<FileChange>,
+    pub metadata: HashMap<String, String>,
+    pub completed_at: String,
+}
+
+/// Every Claude Code invocation a task made, with secrets redacted
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskTranscriptResponse {
+    pub task_id: String,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl From<TaskResult> for TaskResultResponse {
+    fn from(result: TaskResult) -> Self {
+        let (success, output, error, files_created, files_modified) = match result.result {
+            TaskExecutionResult::Success {
+                output,
+                files_created,
+                files_modified,
+            } => (true, Some(output), None, files_created, files_modified),
+            TaskExecutionResult::Failure {
+                error,
+                partial_output,
+            } => (false, partial_output, Some(error), Vec::new(), Vec::new()),
+        };
+
+        Self {
+            task_id: result.task_id,
+            agent_type: result.agent_type,
+            success,
+            output,
+            error,
+            files_created,
+            files_modified,
+            files: None,
+            changes: result.changes,
+            metadata: result.metadata,
+            completed_at: result.completed_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskResultQueryParams {
+    #[serde(default)]
+    pub include_content: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CancelTaskResponse {
+    pub task_id: String,
+    pub status: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskEventsResponse {
+    pub task_id: String,
+    pub events: Vec<TaskEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskProgressResponse {
+    pub task_id: String,
+    pub status: TaskStatus,
+    /// None until the agent reports progress
+    pub progress: Option<TaskProgress>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AgentStatusResponse {
     pub agent_type: AgentType,
     pub is_busy: bool,
-    pub current_task_id: Option<String>,
+    /// Tasks of this agent type executing right now
+    pub current_task_ids: Vec<String>,
+    pub active_tasks: u32,
     pub tasks_completed: u64,
     pub tasks_failed: u64,
     pub average_execution_time: f64,
@@ -121,7 +428,8 @@ impl From<crate::agents::AgentStatus> for AgentStatusResponse {
         Self {
             agent_type: status.agent_type,
             is_busy: status.is_busy,
-            current_task_id: status.current_task_id,
+            current_task_ids: status.current_task_ids.into_iter().collect(),
+            active_tasks: status.active_tasks,
             tasks_completed: status.tasks_completed,
             tasks_failed: status.tasks_failed,
             average_execution_time: status.average_execution_time,
@@ -129,14 +437,59 @@ impl From<crate::agents::AgentStatus> for AgentStatusResponse {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AgentTaskHistoryResponse {
+    pub agent_type: AgentType,
+    /// Newest first
+    pub tasks: Vec<AgentTaskRecord>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AgentTaskHistoryQueryParams {
+    /// Maximum tasks to return, capped at the server limit
+    pub limit: Option<usize>,
+    /// Only tasks currently in this status
+    pub status: Option<TaskStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SystemStatusResponse {
     pub agents: HashMap<AgentType, AgentStatusResponse>,
     pub queue_length: usize,
     pub system_uptime: f64,
+    pub dispatch_state: DispatchState,
+    pub ip_filter: IpFilterStats,
+    /// The Claude Code CLI version and the features it is too old for
+    pub claude_cli: Option<ClaudeCliStatus>,
+    /// How far task submission limits are cut for system health
+    pub adaptive_rate_limits: AdaptiveRateLimitStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DispatchStateResponse {
+    pub dispatch_state: DispatchState,
+    pub in_flight: usize,
+    pub queue_length: usize,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DrainQueryParams {
+    pub timeout_secs: Option<u64>,
+}
+
+/// Content to analyse without creating a task
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnalyzeTaskRequest {
+    /// Omit to analyse with the agent the content would be routed to
+    #[serde(default)]
+    pub agent_type: Option<AgentType>,
+    pub content: String,
+    pub context: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TaskAnalysisResponse {
     pub complexity: String,
     pub estimated_minutes: u32,
@@ -145,14 +498,14 @@ pub struct TaskAnalysisResponse {
     pub approach: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct WorkspaceStatusResponse {
     pub workspace_id: String,
     pub session_id: Option<String>,
@@ -164,7 +517,7 @@ pub struct WorkspaceStatusResponse {
     pub status: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AllWorkspacesStatusResponse {
     pub workspaces: Vec<WorkspaceStatusResponse>,
     pub total_count: usize,
@@ -172,6 +525,128 @@ pub struct AllWorkspacesStatusResponse {
     pub total_size_human: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeleteWorkspaceResponse {
+    pub workspace_id: String,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Unique among active keys, e.g. "ci-deploy"
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+    /// Shown only in this response; only its hash is stored
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeysResponse {
+    pub keys: Vec<ApiKeyInfo>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQueryParams {
+    pub kind: Option<AuditEventKind>,
+    pub source: Option<AuditSource>,
+    /// API key name or Discord user, as recorded
+    pub actor: Option<String>,
+    /// RFC 3339; only events at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// RFC 3339; only events at or before this time
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Defaults to 100, capped at 1000
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MetricsHistoryParams {
+    /// RFC 3339; defaults to 24 hours before `until`
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// RFC 3339; defaults to now
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// raw, 5m or auto (default): raw while the range is within the last 24 hours
+    pub resolution: Option<HistoryResolution>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogResponse {
+    /// False when no audit directory is configured; events are then only traced
+    pub enabled: bool,
+    /// Newest first
+    pub events: Vec<AuditEvent>,
+}
+
+/// Log filter directives, e.g. `info,spiral_core::discord=debug`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogLevel {
+    pub filter: String,
+}
+
+/// `auto` to follow system health, or a level to hold the task submission limits at
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AdaptiveModeRequest {
+    pub mode: AdaptiveMode,
+}
+
+/// A tier above the default granted to a Discord user or API key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitTierGrant {
+    /// Discord user ID, or `api-key:<key_id>`
+    pub subject: String,
+    pub tier: RateLimitTier,
+    pub granted_by: String,
+    pub granted_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SetRateLimitTierRequest {
+    /// `default` removes the subject's grant
+    pub tier: RateLimitTier,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitTierChange {
+    pub subject: String,
+    pub previous_tier: RateLimitTier,
+    pub tier: RateLimitTier,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveWorkspaceResponse {
+    pub workspace_id: String,
+    /// Archive location relative to the server's working directory
+    pub archive_path: String,
+    pub archive_size_bytes: u64,
+    pub freed_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkspaceFilesResponse {
+    pub workspace_id: String,
+    pub files: Vec<WorkspaceFile>,
+    pub total_files: usize,
+    pub total_size_bytes: u64,
+    /// More files exist than were returned
+    pub truncated: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WorkspaceFilesQueryParams {
+    /// Maximum files to return, capped at the server limit
+    pub limit: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TaskQueryParams {
     pub limit: Option<usize>,
@@ -182,25 +657,86 @@ pub struct TaskQueryParams {
 impl ApiServer {
     pub fn new(config: Config, orchestrator: Arc<AgentOrchestrator>) -> Result<Self> {
         let validator = TaskContentValidator::new()?;
-        // let rate_limiter = RateLimitConfig::new();
+        let rate_limiter = ApiRateLimiter::new(
+            config.rate_limit.store.open_or_memory("API rate limits"),
+            config.api.rate_limits.clone(),
+        );
+        let idempotency = Arc::new(IdempotencyCache::new(std::time::Duration::from_secs(
+            config.api.idempotency_window_secs,
+        )));
+        let api_keys = Arc::new(match &config.api.keys_file {
+            Some(path) => ApiKeyStore::load(path)?,
+            None => ApiKeyStore::in_memory(),
+        });
+        let ip_filter = Arc::new(IpFilter::new(
+            config.api.ip_allowlist.clone(),
+            config.api.ip_denylist.clone(),
+        ));
+        let sessions = Arc::new(SessionManager::from_config_or_memory(
+            SessionConfig {
+                store: config.api.session_store.clone(),
+                budget: config.api.session_budget.clone(),
+                policies: config.api.session_policies.clone(),
+                ..SessionConfig::default()
+            },
+            "API sessions",
+        ));
+        orchestrator.register_session_manager(sessions.clone());
         Ok(Self {
             config: config.api,
             orchestrator,
             validator,
             system_monitor: None,
-            // rate_limiter,
+            idempotency,
+            api_keys,
+            ip_filter,
+            sessions,
+            request_metrics: RequestMetrics::default(),
+            rate_limiter,
+            config_handle: None,
         })
     }
 
+    /// Apply the rate limit tiers in `tiers`, shared with the Discord bot
+    pub fn with_rate_limit_tiers(mut self, tiers: Arc<RateLimitTiers>) -> Self {
+        self.rate_limiter = self.rate_limiter.with_tiers(tiers);
+        self
+    }
+
     /// Set the system monitor for monitoring endpoints
     pub fn with_system_monitor(mut self, monitor: Arc<SystemMonitor>) -> Self {
+        monitor.register_session_manager(self.sessions.clone());
+        self.request_metrics = monitor.request_metrics();
         self.system_monitor = Some(monitor);
         self
     }
 
+    /// Adopt rate limits reloaded through `handle` while running
+    pub fn with_config_handle(mut self, handle: ConfigHandle) -> Self {
+        self.config_handle = Some(handle);
+        self
+    }
+
     pub async fn run(&self) -> Result<()> {
         let app = self.build_router();
 
+        if let Some(handle) = &self.config_handle {
+            handle.follow(Arc::new(self.rate_limiter.clone()));
+        }
+
+        tokio::spawn(crate::rate_limit::adaptive::follow_health(
+            self.rate_limiter.adaptive().clone(),
+            self.system_monitor.clone(),
+            self.orchestrator
+                .get_claude_client()
+                .ok()
+                .and_then(|client| {
+                    client
+                        .circuit_breakers()
+                        .get(crate::claude_code::circuit_breaker::CLAUDE_CIRCUIT_BREAKER)
+                }),
+        ));
+
         let listener =
             tokio::net::TcpListener::bind(format!("{}:{}", self.config.host, self.config.port))
                 .await
@@ -210,6 +746,19 @@ impl ApiServer {
             "API server listening on {}:{}",
             self.config.host, self.config.port
         );
+        if !self.config.ip_allowlist.is_empty() || !self.config.ip_denylist.is_empty() {
+            info!(
+                "API IP filter active: {} allowed network(s), {} denied network(s)",
+                self.config.ip_allowlist.len(),
+                self.config.ip_denylist.len()
+            );
+        }
+        if !self.config.signed_route_groups.is_empty() {
+            info!(
+                "Signed requests required for route groups: {:?}",
+                self.config.signed_route_groups
+            );
+        }
 
         axum::serve(
             listener,
@@ -224,12 +773,12 @@ impl ApiServer {
     /// 🏗️ ARCHITECTURE DECISION: Layered middleware approach
     /// Why: Clear separation of concerns for security and observability
     /// Alternative: Monolithic handler (rejected: poor separation)
-    /// Order matters: Rate limit -> Auth -> Trace -> CORS -> Routes
+    /// Order matters: IP filter -> Rate limit -> Auth -> Trace -> CORS -> Routes
     pub fn build_router(&self) -> Router {
         // 🛡️ SECURITY CHECKPOINT: Auth state initialization
         // Critical: API keys and auth config loaded here
         // Audit: Verify auth_state contains valid configuration
-        let auth_state = create_auth_state(self.config.clone());
+        let auth_state = create_auth_state(self.config.clone(), self.api_keys.clone());
 
         // 🛡️ SECURITY DECISION: Restrictive CORS policy
         // Why: Prevent unauthorized cross-origin requests
@@ -248,37 +797,190 @@ impl ApiServer {
                 axum::http::header::CONTENT_TYPE,
                 axum::http::header::AUTHORIZATION,
                 axum::http::HeaderName::from_static("x-api-key"),
+                axum::http::HeaderName::from_static(idempotency::IDEMPOTENCY_KEY_HEADER),
+                axum::http::HeaderName::from_static(sessions::SESSION_ID_HEADER),
+                axum::http::HeaderName::from_static(crate::auth::signing::SIGNATURE_HEADER),
+                axum::http::HeaderName::from_static(crate::auth::signing::TIMESTAMP_HEADER),
+            ])
+            // Browser clients may read their remaining quota and when to retry
+            .expose_headers([
+                axum::http::HeaderName::from_static(crate::rate_limit::RATE_LIMIT_LIMIT_HEADER),
+                axum::http::HeaderName::from_static(crate::rate_limit::RATE_LIMIT_REMAINING_HEADER),
+                axum::http::header::RETRY_AFTER,
             ])
             .max_age(std::time::Duration::from_secs(3600)); // 1 hour cache
 
-        Router::new()
+        // 🏗️ ARCHITECTURE DECISION: One route table served under /v1 and at the legacy paths
+        // Why: Existing clients keep working while the Deprecation header points them at /v1
+        // Alternative: Redirect legacy paths (rejected: clients drop the API key and body
+        // on some redirects)
+        // Health stays unversioned so load balancer probes never see a deprecation
+        let api = Router::new()
             .route(ROUTE_HEALTH, get(health_check))
-            .route(ROUTE_TASKS, post(create_task))
-            .route(ROUTE_TASK_BY_ID, get(get_task_status))
-            .route(ROUTE_TASK_ANALYZE, post(analyze_task))
-            .route(ROUTE_AGENTS, get(get_all_agent_statuses))
-            .route(ROUTE_AGENT_BY_TYPE, get(get_agent_status))
-            .route(ROUTE_SYSTEM_STATUS, get(get_system_status))
-            .route(ROUTE_SYSTEM_METRICS, get(get_system_metrics))
-            .route(ROUTE_SYSTEM_METRICS_HISTORY, get(get_metrics_history))
-            .route(ROUTE_SYSTEM_HEALTH, get(get_system_health))
-            .route(ROUTE_CIRCUIT_BREAKERS, get(get_circuit_breaker_status))
-            .route(ROUTE_WORKSPACES, get(get_all_workspaces_status))
+            .nest(
+                versioning::API_V1_PREFIX,
+                api_routes(&self.config)
+                    .route(ROUTE_HEALTH, get(health_check))
+                    .layer(middleware::from_fn(versioning::current_version)),
+            )
+            .merge(
+                api_routes(&self.config).layer(middleware::from_fn(versioning::deprecated_alias)),
+            )
+            .fallback(versioning::unknown_route)
             .layer(
                 ServiceBuilder::new()
-                    .layer(middleware::from_fn(rate_limit_middleware)) // SECURITY: Rate limiting
+                    .layer(middleware::from_fn_with_state(
+                        self.request_metrics.clone(),
+                        request_metrics::record_request,
+                    ))
+                    // SECURITY: Filtered networks never reach the rate limiter's quota
+                    .layer(middleware::from_fn_with_state(
+                        self.ip_filter.clone(),
+                        ip_filter_middleware,
+                    ))
+                    // SECURITY: Failed authentications are limited per address before auth
+                    // refuses them, so key guessing is bounded
+                    .layer(middleware::from_fn_with_state(
+                        self.rate_limiter.clone(),
+                        auth_failure_limit_middleware,
+                    ))
                     .layer(middleware::from_fn_with_state(auth_state, auth_middleware))
+                    // SECURITY: Rate limiting, after auth so each API key has its own quota
+                    .layer(middleware::from_fn_with_state(
+                        self.rate_limiter.clone(),
+                        rate_limit_middleware,
+                    ))
                     .layer(TraceLayer::new_for_http())
-                    .layer(cors_layer), // SECURITY: Restrictive CORS policy
+                    .layer(cors_layer) // SECURITY: Restrictive CORS policy
+                    // ⚡ PERFORMANCE: Metrics history and workspace listings shrink 5-10x;
+                    // the default predicate skips tiny bodies, images and SSE streams
+                    .layer(CompressionLayer::new()),
             )
-            .with_state(self.clone())
+            .with_state(self.clone());
+
+        // 🛡️ SECURITY DECISION: Static browser pages are the only routes outside auth
+        // Why: Browsers cannot attach the API key to a page load, and the pages are static;
+        // they make every call with the key the user enters
+        // Alternative: Serve them behind auth (rejected: unreachable from a browser)
+        // Trade-off: Swagger UI is off by default and the dashboard is a compile-time
+        // feature, so the public surface only grows when asked for
+        let mut pages = Router::new();
+        if self.config.swagger_ui_enabled {
+            pages = pages.route(ROUTE_DOCS, get(openapi::swagger_ui));
+        }
+        #[cfg(feature = "dashboard")]
+        {
+            pages = pages.route(ROUTE_DASHBOARD, get(dashboard::dashboard_page));
+        }
+        api.merge(
+            pages.layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(
+                        self.ip_filter.clone(),
+                        ip_filter_middleware,
+                    ))
+                    .layer(middleware::from_fn_with_state(
+                        self.rate_limiter.clone(),
+                        rate_limit_middleware,
+                    ))
+                    .layer(TraceLayer::new_for_http()),
+            ),
+        )
     }
 }
 
+/// Every versioned route; mounted under /v1 and, deprecated, at the root
+fn api_routes(config: &ApiConfig) -> Router<ApiServer> {
+    // Batches carry up to MAX_BATCH_SIZE tasks, so they get their own, larger limit
+    let batch = limits::with_body_limit(
+        Router::new().route(ROUTE_TASK_BATCH, post(create_task_batch)),
+        config.max_batch_body_bytes,
+    );
+
+    let routes = Router::new()
+        .route(ROUTE_TASKS, post(create_task))
+        .route(ROUTE_TASK_BATCH_BY_ID, get(get_task_batch_status))
+        .route(ROUTE_TASK_BY_ID, get(get_task_status).delete(cancel_task))
+        .route(ROUTE_TASK_ANALYZE, post(analyze_task))
+        .route(ROUTE_ANALYZE, post(analyze_content))
+        .route(ROUTE_TASK_EVENTS, get(get_task_events))
+        .route(ROUTE_TASK_PROGRESS, get(get_task_progress))
+        .route(ROUTE_TASK_RESULT, get(get_task_result))
+        .route(ROUTE_TASK_STREAM, get(stream_task_updates))
+        .route(ROUTE_TASK_WEBHOOK, get(get_task_webhook))
+        .route(ROUTE_TASK_TRANSCRIPT, get(get_task_transcript))
+        .route(ROUTE_AGENTS, get(get_all_agent_statuses))
+        .route(ROUTE_AGENT_BY_TYPE, get(get_agent_status))
+        .route(
+            ROUTE_AGENT_MEMORY,
+            get(get_agent_memory).post(store_agent_memory),
+        )
+        .route(ROUTE_AGENT_TASKS, get(get_agent_task_history))
+        .route(ROUTE_MEMORY_BY_ID, delete(forget_memory))
+        .route(ROUTE_SYSTEM_STATUS, get(get_system_status))
+        .route(ROUTE_SYSTEM_METRICS, get(get_system_metrics))
+        .route(
+            ROUTE_PROMETHEUS_METRICS,
+            get(request_metrics::prometheus_metrics),
+        )
+        .route(ROUTE_SYSTEM_METRICS_HISTORY, get(get_metrics_history))
+        .route(ROUTE_SYSTEM_HEALTH, get(get_system_health))
+        .route(ROUTE_SYSTEM_AVAILABILITY, get(get_system_availability))
+        .route(ROUTE_SYSTEM_PAUSE, post(pause_dispatch))
+        .route(ROUTE_SYSTEM_RESUME, post(resume_dispatch))
+        .route(ROUTE_SYSTEM_DRAIN, post(drain_dispatch))
+        .route(ROUTE_SYSTEM_COSTS, get(get_system_costs))
+        .route(ROUTE_CIRCUIT_BREAKERS, get(get_circuit_breaker_status))
+        .route(ROUTE_CIRCUIT_BREAKER_RESET, post(reset_circuit_breaker))
+        .route(ROUTE_CIRCUIT_BREAKER_TRIP, post(trip_circuit_breaker))
+        .route(ROUTE_WORKSPACES, get(get_all_workspaces_status))
+        .route(ROUTE_WORKSPACE_BY_ID, delete(delete_workspace))
+        .route(ROUTE_WORKSPACE_ARCHIVE, post(archive_workspace))
+        .route(ROUTE_WORKSPACE_FILES, get(get_workspace_files))
+        .route(ROUTE_SCHEDULES, get(get_schedules))
+        .route(ROUTE_SESSIONS, post(sessions::create_session))
+        .route(
+            ROUTE_SESSION_BY_ID,
+            get(sessions::get_session).delete(sessions::end_session),
+        )
+        .route(ROUTE_SESSION_TREE, get(sessions::get_session_tree))
+        .route(
+            ROUTE_SESSION_HISTORY,
+            delete(sessions::clear_session_history),
+        )
+        .route(ROUTE_QUEUE, get(get_queue))
+        .route(ROUTE_QUEUE_PROMOTE, post(promote_queued_task))
+        .route(ROUTE_QUEUE_DEMOTE, post(demote_queued_task))
+        .route(ROUTE_WEBSOCKET, get(websocket::websocket_handler))
+        .route(ROUTE_WORKFLOWS, get(get_workflows))
+        .route(ROUTE_WORKFLOW_RUN, post(run_workflow))
+        .route(ROUTE_WORKFLOW_RUN_BY_ID, get(get_workflow_run))
+        .route(ROUTE_ADMIN_KEYS, get(list_api_keys).post(create_api_key))
+        .route(ROUTE_ADMIN_KEY_BY_ID, delete(revoke_api_key))
+        .route(ROUTE_ADMIN_AUDIT, get(get_audit_log))
+        .route(ROUTE_ADMIN_LOG_LEVEL, get(get_log_level).put(set_log_level))
+        .route(
+            ROUTE_ADMIN_ADAPTIVE_RATE_LIMITS,
+            get(get_adaptive_rate_limits).put(set_adaptive_rate_limits),
+        )
+        .route(ROUTE_ADMIN_RATE_LIMIT_TIERS, get(list_rate_limit_tiers))
+        .route(ROUTE_ADMIN_RATE_LIMIT_TIER, put(set_rate_limit_tier))
+        .route(ROUTE_OPENAPI, get(openapi::openapi_spec));
+    limits::with_body_limit(routes, config.max_body_bytes).merge(batch)
+}
+
 /// 🏗️ ARCHITECTURE DECISION: Static health response
 /// Why: Simple health check for load balancers and monitoring
 /// Alternative: Include system metrics (rejected: separate /metrics endpoint)
 /// Trade-off: Less info but faster response and lower overhead
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "system",
+    responses(
+        (status = 200, description = "Service is up", body = serde_json::Value),
+    )
+)]
 async fn health_check() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "status": "healthy",
@@ -287,14 +989,43 @@ async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
-/// 📝 CREATE TASK ENDPOINT: Primary user request entry point
-/// AUDIT CHECKPOINT: Critical security and validation path
-/// Verify: Authentication, rate limiting, content validation, orchestrator submission
-async fn create_task(
-    State(api_server): State<ApiServer>,
-    Json(request): Json<CreateTaskRequest>,
-) -> std::result::Result<(StatusCode, Json<CreateTaskResponse>), (StatusCode, Json<ErrorResponse>)>
-{
+/// 🧭 CAPABILITY ROUTING: Pick an agent for a task submitted without agent_type
+/// Keywords only, so a request never waits on a Claude call to be accepted
+async fn route_task(
+    api_server: &ApiServer,
+    task: &mut Task,
+) -> std::result::Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match api_server.orchestrator.route_task_by_content(task).await {
+        Ok(_) => Ok(()),
+        Err(SpiralError::Validation(message)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "No capable agent".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            warn!("Failed to route task: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal orchestrator errors
+                }),
+            ))
+        }
+    }
+}
+
+/// 🛡️ TASK CONSTRUCTION: Validate and sanitize a task request into a Task
+/// Shared by single and batch submission so both apply identical security checks
+async fn build_task(
+    api_server: &ApiServer,
+    request: CreateTaskRequest,
+    identity: Option<&ApiKeyIdentity>,
+) -> std::result::Result<Task, (StatusCode, Json<ErrorResponse>)> {
+    let requested_agent = request.agent_type.clone();
+
     // 🛡️ SECURITY AUDIT CHECKPOINT: Content validation and sanitization
     // CRITICAL: This is the primary defense against malicious task content
     // Verify: XSS prevention, injection attack mitigation, content length limits
@@ -323,7 +1054,18 @@ async fn create_task(
     // 📊 PRIORITY ASSIGNMENT: Default to medium priority for balanced processing
     // AUDIT: Verify priority escalation policies and user privilege alignment
     let priority = request.priority.unwrap_or(Priority::Medium);
-    let mut task = Task::new(request.agent_type, sanitized_content, priority);
+    // Placeholder agent for routed tasks; capability routing below replaces it
+    let mut task = Task::new(
+        requested_agent
+            .clone()
+            .unwrap_or(AgentType::SoftwareDeveloper),
+        sanitized_content,
+        priority,
+    );
+    // 🛡️ A caller may lower the retry budget, never raise it past TASK_MAX_RETRIES
+    if let Some(max_retries) = request.max_retries {
+        task = task.with_max_retries(api_server.orchestrator.retry_policy().capped(max_retries));
+    }
 
     // 🔍 CONTEXT VALIDATION AUDIT CHECKPOINT: Secondary security validation
     // CRITICAL: Context can contain sensitive data or injection vectors
@@ -368,49 +1110,630 @@ async fn create_task(
         }
     }
 
-    // 🎯 ORCHESTRATOR SUBMISSION AUDIT CHECKPOINT: Hand-off to agent system
-    // CRITICAL: Last point of API control before agent processing
-    // Verify: Task queue health, agent availability, resource limits
-    match api_server.orchestrator.submit_task(task).await {
-        Ok(task_id) => {
-            // ✅ SUCCESSFUL SUBMISSION: Task accepted by orchestrator
-            // AUDIT: Verify task ID generation security and uniqueness
-            info!("Task {} successfully submitted to orchestrator", task_id);
-            Ok((
-                StatusCode::CREATED,
-                Json(CreateTaskResponse {
-                    task_id,
-                    status: "submitted".to_string(),
+    // 🛡️ DELEGATION AUDIT CHECKPOINT: Lineage and depth are set by the orchestrator when an
+    // agent delegates; a submitted depth or parent would forge the chain MAX_DELEGATION_DEPTH
+    // is counted on, so they are dropped for every caller, admins included
+    for key in DELEGATION_CONTEXT_KEYS {
+        task.context.remove(*key);
+    }
+
+    // 🛡️ SESSION AUDIT CHECKPOINT: A task joins a user session, its budget, history and
+    // workspace only through an X-Session-Id the caller owns, applied after this; naming
+    // the session or its workspace in the context would skip that ownership check
+    task.context.remove(USER_SESSION_CONTEXT_KEY);
+    if task
+        .context
+        .get(SESSION_CONTEXT_KEY)
+        .is_some_and(|key| key.starts_with(MANAGED_SESSION_KEY_PREFIX))
+    {
+        warn!("Rejected a session-owned workspace named in task context");
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: ERROR_INVALID_CONTEXT_VALUE.to_string(),
+                details: Some(format!(
+                    "{SESSION_CONTEXT_KEY} cannot name a session's workspace; send X-Session-Id instead"
+                )),
+            }),
+        ));
+    }
+
+    // 🛡️ MEMORY SCOPE AUDIT CHECKPOINT: A task's memory scopes come from the caller's key,
+    // not its context, so one key cannot read or write another user's memories
+    // Admin keys may name any scope, as they can through the memory endpoints
+    if !is_admin(identity) {
+        for key in MEMORY_SCOPE_CONTEXT_KEYS {
+            task.context.remove(*key);
+        }
+    }
+    if !MemoryScope::for_task(&task)
+        .iter()
+        .any(|scope| matches!(scope, MemoryScope::User(_)))
+    {
+        task = task.with_context("user_id".to_string(), sessions::owner(identity));
+    }
+
+    if requested_agent.is_none() {
+        route_task(api_server, &mut task).await?;
+    }
+
+    Ok(task)
+}
+
+/// 📝 CREATE TASK ENDPOINT: Primary user request entry point
+/// AUDIT CHECKPOINT: Critical security and validation path
+/// Verify: Authentication, rate limiting, content validation, orchestrator submission
+#[utoipa::path(
+    post,
+    path = "/tasks",
+    tag = "tasks",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retrying with the same key returns the original response instead of creating another task"),
+        ("X-Session-Id" = Option<String>, Header, description = "Session from POST /sessions whose workspace and agent the task uses")
+    ),
+    request_body = CreateTaskRequest,
+    responses(
+        (status = 201, description = "Task queued or scheduled", body = CreateTaskResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "X-Session-Id names no open session of this API key", body = ErrorResponse),
+        (status = 409, description = "A request with this Idempotency-Key is still in progress", body = ErrorResponse),
+        (status = 422, description = "Idempotency-Key was used for a different request", body = ErrorResponse),
+        (status = 429, description = "Daily Claude Code budget is used up", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn create_task(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateTaskRequest>,
+) -> std::result::Result<
+    (StatusCode, HeaderMap, Json<CreateTaskResponse>),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let key = idempotency_key(&api_server, &headers)?;
+    let session =
+        sessions::session_from_headers(&api_server, &headers, identity.as_deref()).await?;
+    let Some(key) = key else {
+        let response =
+            submit_task_request(&api_server, request, session.as_ref(), identity.as_deref())
+                .await?;
+        return Ok((StatusCode::CREATED, HeaderMap::new(), Json(response)));
+    };
+
+    // 🔁 IDEMPOTENT REPLAY: A retried request gets the original task instead of a duplicate
+    let scoped_key = idempotency::scoped_key(identity.as_deref(), &key);
+    let idempotency_error = |status: StatusCode, error: &str| {
+        Err((
+            status,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: Some(format!("Idempotency-Key: {key}")),
+            }),
+        ))
+    };
+    let fingerprint =
+        idempotency::fingerprint(&request, session.as_ref().map(|session| &session.id));
+    match api_server.idempotency.reserve(&scoped_key, fingerprint) {
+        Reservation::New => {}
+        Reservation::Replay(response) => {
+            info!("Replaying task {} for idempotency key", response.task_id);
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                idempotency::IDEMPOTENT_REPLAYED_HEADER,
+                axum::http::HeaderValue::from_static("true"),
+            );
+            return Ok((StatusCode::CREATED, headers, Json(response)));
+        }
+        Reservation::InProgress => {
+            return idempotency_error(
+                StatusCode::CONFLICT,
+                "Request with this Idempotency-Key is still in progress",
+            )
+        }
+        Reservation::Mismatch => {
+            return idempotency_error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used for a different request",
+            )
+        }
+    }
+
+    let guard = ReservationGuard::new(&api_server.idempotency, scoped_key);
+    let response =
+        submit_task_request(&api_server, request, session.as_ref(), identity.as_deref()).await?;
+    guard.complete(&response);
+    Ok((StatusCode::CREATED, HeaderMap::new(), Json(response)))
+}
+
+/// The request's Idempotency-Key, or None when absent or the feature is disabled
+fn idempotency_key(
+    api_server: &ApiServer,
+    headers: &HeaderMap,
+) -> std::result::Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(value) = headers.get(idempotency::IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    if !api_server.idempotency.is_enabled() {
+        return Ok(None);
+    }
+    match value.to_str() {
+        Ok(key) if idempotency::validate_key(key) => Ok(Some(key.to_string())),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid Idempotency-Key".to_string(),
+                details: Some(format!(
+                    "Keys must be 1-{} printable ASCII characters",
+                    crate::constants::IDEMPOTENCY_KEY_MAX_LEN
+                )),
+            }),
+        )),
+    }
+}
+
+/// 🎟️ QUEUE ADMISSION: Admin keys may use the queue's reserved slots at any priority
+fn queue_submitter(identity: Option<&ApiKeyIdentity>) -> Submitter {
+    if is_admin(identity) {
+        Submitter::Privileged
+    } else {
+        Submitter::Standard
+    }
+}
+
+/// Whether the caller's key grants the admin role
+fn is_admin(identity: Option<&ApiKeyIdentity>) -> bool {
+    identity.is_some_and(|identity| identity.role().permits(Role::Admin))
+}
+
+/// Schedule or submit a validated task request, in the caller's session if it named one
+async fn submit_task_request(
+    api_server: &ApiServer,
+    mut request: CreateTaskRequest,
+    session: Option<&Session>,
+    identity: Option<&ApiKeyIdentity>,
+) -> std::result::Result<CreateTaskResponse, (StatusCode, Json<ErrorResponse>)> {
+    let schedule = request.schedule.take();
+    let callback_url = request.callback_url.take();
+    // Only the first run of a schedule keeps the returned task_id, so a repeating
+    // schedule would call back once and then silently stop
+    if callback_url.is_some() && matches!(schedule, Some(TaskSchedule::Cron(_))) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: ERROR_INVALID_CALLBACK.to_string(),
+                details: Some("callback_url cannot be used with cron schedules".to_string()),
+            }),
+        ));
+    }
+    // 🗂️ SESSION DEFAULTS: A task naming no agent goes to the session's agent
+    if request.agent_type.is_none() {
+        request.agent_type = session.and_then(Session::agent);
+    }
+    let mut task = build_task(api_server, request, identity).await?;
+    if let Some(session) = session {
+        task = session.apply_to_task(task);
+    }
+    let task_id = task.id.clone();
+    let agent_type = task.agent_type.clone();
+    register_callback(api_server, &task_id, callback_url.as_deref())?;
+    let result = submit_built_task(api_server, task, schedule, queue_submitter(identity)).await;
+    match (&result, session) {
+        (Ok(_), Some(session)) => sessions::record_agent(api_server, session, &agent_type).await,
+        (Err(_), _) => api_server.orchestrator.unregister_webhook(&task_id),
+        _ => {}
+    }
+    result
+}
+
+/// 🔔 CALLBACK REGISTRATION: Attach a callback before the task is submitted, so even a
+/// task that finishes immediately is called back
+fn register_callback(
+    api_server: &ApiServer,
+    task_id: &str,
+    callback_url: Option<&str>,
+) -> std::result::Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let Some(callback_url) = callback_url else {
+        return Ok(());
+    };
+    match api_server
+        .orchestrator
+        .register_webhook(task_id, callback_url)
+    {
+        Ok(()) => Ok(()),
+        Err(SpiralError::Validation(message)) => {
+            warn!("Rejected callback_url for task {}: {}", task_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: ERROR_INVALID_CALLBACK.to_string(),
+                    details: Some(message),
                 }),
             ))
         }
         Err(e) => {
-            // 🚨 SUBMISSION FAILURE AUDIT CHECKPOINT: System capacity or validation issue
-            // CRITICAL: Could indicate system overload, agent unavailability, or attack
-            warn!("Failed to submit task to orchestrator: {}", e);
+            warn!("Failed to register callback for task {}: {}", task_id, e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
                     error: ERROR_INTERNAL_SERVER.to_string(),
-                    details: None, // SECURITY: Never expose internal orchestrator errors
+                    details: None,
                 }),
             ))
         }
     }
 }
 
-async fn get_task_status(
-    State(api_server): State<ApiServer>,
-    Path(task_id): Path<String>,
-) -> std::result::Result<Json<TaskStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match api_server.orchestrator.get_task_status(&task_id).await {
+async fn submit_built_task(
+    api_server: &ApiServer,
+    task: Task,
+    schedule: Option<TaskSchedule>,
+    submitter: Submitter,
+) -> std::result::Result<CreateTaskResponse, (StatusCode, Json<ErrorResponse>)> {
+    // 📅 DEFERRED SUBMISSION: Scheduled tasks are held by the orchestrator's scheduler
+    // and submitted when due; the first run reuses the returned task_id
+    if let Some(schedule) = schedule {
+        return match api_server.orchestrator.schedule_task(task, schedule).await {
+            Ok(scheduled) => {
+                info!(
+                    "Task {} scheduled as {}",
+                    scheduled.template.id, scheduled.id
+                );
+                Ok(CreateTaskResponse {
+                    task_id: scheduled.template.id,
+                    status: "scheduled".to_string(),
+                    schedule_id: Some(scheduled.id),
+                    next_run_at: Some(scheduled.next_run_at.to_rfc3339()),
+                })
+            }
+            Err(SpiralError::Validation(message)) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid schedule".to_string(),
+                    details: Some(message),
+                }),
+            )),
+            Err(e) => {
+                warn!("Failed to schedule task: {}", e);
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: ERROR_INTERNAL_SERVER.to_string(),
+                        details: None, // SECURITY: Never expose internal orchestrator errors
+                    }),
+                ))
+            }
+        };
+    }
+
+    // 🎯 ORCHESTRATOR SUBMISSION AUDIT CHECKPOINT: Hand-off to agent system
+    // CRITICAL: Last point of API control before agent processing
+    // Verify: Task queue health, agent availability, resource limits
+    match api_server
+        .orchestrator
+        .submit_task_as(task, submitter)
+        .await
+    {
+        Ok(task_id) => {
+            // ✅ SUCCESSFUL SUBMISSION: Task accepted by orchestrator
+            // AUDIT: Verify task ID generation security and uniqueness
+            info!("Task {} successfully submitted to orchestrator", task_id);
+            Ok(CreateTaskResponse {
+                task_id,
+                status: "submitted".to_string(),
+                schedule_id: None,
+                next_run_at: None,
+            })
+        }
+        // 💸 BUDGET: Safe to explain; the message only names the budget and the spend
+        Err(SpiralError::RateLimit { message })
+            if message.starts_with(crate::session::SESSION_BUDGET_EXHAUSTED) =>
+        {
+            Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(ErrorResponse {
+                    error: "Session budget exhausted".to_string(),
+                    details: Some(message),
+                }),
+            ))
+        }
+        Err(SpiralError::RateLimit { message }) => Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error: "Daily budget exhausted".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            // 🚨 SUBMISSION FAILURE AUDIT CHECKPOINT: System capacity or validation issue
+            // CRITICAL: Could indicate system overload, agent unavailability, or attack
+            warn!("Failed to submit task to orchestrator: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal orchestrator errors
+                }),
+            ))
+        }
+    }
+}
+
+/// 📦 BATCH SUBMISSION: Validate every task first, then enqueue all or nothing
+#[utoipa::path(
+    post,
+    path = "/tasks/batch",
+    tag = "tasks",
+    params(
+        ("X-Session-Id" = Option<String>, Header, description = "Session from POST /sessions whose workspace and agent every task in the batch uses")
+    ),
+    request_body = CreateTaskBatchRequest,
+    responses(
+        (status = 201, description = "Batch queued", body = CreateTaskBatchResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "X-Session-Id names no open session of this API key", body = ErrorResponse),
+        (status = 429, description = "Daily Claude Code budget or the session's budget is used up", body = ErrorResponse),
+        (status = 503, description = "Queue is full", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn create_task_batch(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateTaskBatchRequest>,
+) -> std::result::Result<
+    (StatusCode, Json<CreateTaskBatchResponse>),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let session =
+        sessions::session_from_headers(&api_server, &headers, identity.as_deref()).await?;
+    let mut tasks = Vec::with_capacity(request.tasks.len());
+    let mut callbacks = Vec::with_capacity(request.tasks.len());
+    for mut task_request in request.tasks {
+        // Schedules fire independently, which would break the batch's all-or-nothing admission
+        if task_request.schedule.is_some() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid batch".to_string(),
+                    details: Some("Scheduled tasks cannot be submitted in a batch".to_string()),
+                }),
+            ));
+        }
+        let callback_url = task_request.callback_url.clone();
+        // 🗂️ SESSION DEFAULTS: As for a single task, one naming no agent goes to the session's
+        if task_request.agent_type.is_none() {
+            task_request.agent_type = session.as_ref().and_then(Session::agent);
+        }
+        let mut task = build_task(&api_server, task_request, identity.as_deref()).await?;
+        if let Some(session) = &session {
+            task = session.apply_to_task(task);
+        }
+        callbacks.push((task.id.clone(), callback_url));
+        tasks.push(task);
+    }
+
+    for (index, (task_id, callback_url)) in callbacks.iter().enumerate() {
+        if let Err(e) = register_callback(&api_server, task_id, callback_url.as_deref()) {
+            for (registered, _) in &callbacks[..index] {
+                api_server.orchestrator.unregister_webhook(registered);
+            }
+            return Err(e);
+        }
+    }
+
+    let last_agent = tasks.last().map(|task| task.agent_type.clone());
+    let result = api_server.orchestrator.submit_batch(tasks).await;
+    if result.is_err() {
+        for (task_id, _) in &callbacks {
+            api_server.orchestrator.unregister_webhook(task_id);
+        }
+    }
+    match result {
+        Ok(batch) => {
+            if let (Some(session), Some(agent_type)) = (&session, &last_agent) {
+                sessions::record_agent(&api_server, session, agent_type).await;
+            }
+            info!("Batch {} successfully submitted to orchestrator", batch.id);
+            Ok((
+                StatusCode::CREATED,
+                Json(CreateTaskBatchResponse {
+                    batch_id: batch.id,
+                    task_ids: batch.task_ids,
+                    status: "submitted".to_string(),
+                }),
+            ))
+        }
+        Err(SpiralError::Validation(message)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid batch".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(SpiralError::QueueFull) => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Task queue cannot fit the batch".to_string(),
+                details: None,
+            }),
+        )),
+        Err(SpiralError::RateLimit { message })
+            if message.starts_with(crate::session::SESSION_BUDGET_EXHAUSTED) =>
+        {
+            Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(ErrorResponse {
+                    error: "Session budget exhausted".to_string(),
+                    details: Some(message),
+                }),
+            ))
+        }
+        Err(SpiralError::RateLimit { message }) => Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error: "Daily budget exhausted".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            warn!("Failed to submit batch to orchestrator: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal orchestrator errors
+                }),
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/batch/{batch_id}",
+    tag = "tasks",
+    params(("batch_id" = String, Path, description = "Batch ID")),
+    responses(
+        (status = 200, description = "Batch status", body = TaskBatchStatusResponse),
+        (status = 404, description = "Batch not found", body = ErrorResponse),
+    )
+)]
+async fn get_task_batch_status(
+    State(api_server): State<ApiServer>,
+    Path(batch_id): Path<String>,
+) -> std::result::Result<Json<TaskBatchStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: ERROR_BATCH_NOT_FOUND.to_string(),
+                details: None,
+            }),
+        )
+    };
+
+    let batch = api_server
+        .orchestrator
+        .get_batch(&batch_id)
+        .await
+        .ok_or_else(not_found)?;
+    let counts = api_server
+        .orchestrator
+        .get_batch_status(&batch_id)
+        .await
+        .ok_or_else(not_found)?;
+
+    Ok(Json(TaskBatchStatusResponse {
+        batch_id: batch.id,
+        task_ids: batch.task_ids,
+        created_at: batch.created_at.to_rfc3339(),
+        finished: counts.is_finished(),
+        counts,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task status", body = TaskStatusResponse),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+    )
+)]
+async fn get_task_status(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<TaskStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match api_server.orchestrator.get_task_status(&task_id).await {
         Some(task) => Ok(Json(TaskStatusResponse {
             task_id: task.id,
             agent_type: task.agent_type,
             status: task.status,
             created_at: task.created_at.to_rfc3339(),
             updated_at: task.updated_at.to_rfc3339(),
+            retry_count: task.retry_count,
+        })),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not found".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        )),
+    }
+}
+
+/// 🛑 TASK CANCELLATION: Stop a queued or running task
+#[utoipa::path(
+    delete,
+    path = "/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task cancelled", body = CancelTaskResponse),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 409, description = "Task already finished", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn cancel_task(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<CancelTaskResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match api_server.orchestrator.cancel_task(&task_id).await {
+        Ok(_) => Ok(Json(CancelTaskResponse {
+            task_id,
+            status: "cancelled".to_string(),
+            message: "Task cancelled successfully".to_string(),
         })),
+        Err(SpiralError::NotFound(_)) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not found".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        )),
+        Err(SpiralError::Validation(message)) => Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: "Task cannot be cancelled".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            warn!("Failed to cancel task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal orchestrator errors
+                }),
+            ))
+        }
+    }
+}
+
+/// 📜 TASK HISTORY: Every lifecycle transition with timestamps, oldest first
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/events",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task lifecycle events", body = TaskEventsResponse),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+    )
+)]
+async fn get_task_events(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<TaskEventsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match api_server.orchestrator.get_task_events(&task_id).await {
+        Some(events) => Ok(Json(TaskEventsResponse { task_id, events })),
         None => Err((
             StatusCode::NOT_FOUND,
             Json(ErrorResponse {
@@ -421,100 +1744,1005 @@ async fn get_task_status(
     }
 }
 
-async fn analyze_task(
+/// 📈 TASK PROGRESS: Latest percentage and phase reported by the executing agent
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/progress",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Latest progress report", body = TaskProgressResponse),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+    )
+)]
+async fn get_task_progress(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<TaskProgressResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(task) = api_server.orchestrator.get_task_status(&task_id).await else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not found".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        ));
+    };
+
+    Ok(Json(TaskProgressResponse {
+        progress: api_server.orchestrator.get_task_progress(&task_id).await,
+        task_id,
+        status: task.status,
+    }))
+}
+
+/// 📦 TASK RESULT: Output and work products of a finished task
+/// File contents are read from the task's workspace on request, within size limits
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/result",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID"), TaskResultQueryParams),
+    responses(
+        (status = 200, description = "Task result", body = TaskResultResponse),
+        (status = 404, description = "Task result not available", body = ErrorResponse),
+    )
+)]
+async fn get_task_result(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+    Query(params): Query<TaskResultQueryParams>,
+) -> std::result::Result<Json<TaskResultResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(result) = api_server.orchestrator.get_task_result(&task_id).await else {
+        let details = match api_server.orchestrator.get_task_status(&task_id).await {
+            Some(task) => format!("Task {task_id} is {:?}", task.status),
+            None => format!("Task ID: {task_id}"),
+        };
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task result not available".to_string(),
+                details: Some(details),
+            }),
+        ));
+    };
+
+    let mut response = TaskResultResponse::from(result);
+    if params.include_content {
+        let paths: Vec<String> = response
+            .files_created
+            .iter()
+            .chain(&response.files_modified)
+            .cloned()
+            .collect();
+        let files = match response.metadata.get(WORKSPACE_PATH_CONTEXT_KEY) {
+            Some(workspace) => {
+                read_result_files(
+                    std::path::Path::new(workspace),
+                    &paths,
+                    crate::constants::RESULT_FILE_MAX_BYTES,
+                    crate::constants::RESULT_FILES_TOTAL_MAX_BYTES,
+                )
+                .await
+            }
+            None => paths
+                .iter()
+                .map(|path| ResultFile {
+                    path: path.clone(),
+                    size_bytes: None,
+                    content: None,
+                    truncated: false,
+                    error: Some("No workspace recorded for this result".to_string()),
+                })
+                .collect(),
+        };
+        response.files = Some(files);
+    }
+
+    Ok(Json(response))
+}
+
+/// 🔔 TASK WEBHOOK: Delivery status of the task's callback_url
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/webhook",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Callback delivery status", body = WebhookDelivery),
+        (status = 404, description = "Task has no callback", body = ErrorResponse),
+    )
+)]
+async fn get_task_webhook(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<WebhookDelivery>, (StatusCode, Json<ErrorResponse>)> {
+    api_server
+        .orchestrator
+        .get_webhook_delivery(&task_id)
+        .map(Json)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "No webhook registered for task".to_string(),
+                    details: Some(format!("Task ID: {task_id}")),
+                }),
+            )
+        })
+}
+
+/// 📝 TASK TRANSCRIPT: The prompts sent to Claude Code for a task and its answers
+/// 🛡️ SECURITY AUDIT CHECKPOINT: Admin only; prompts hold task content, context and
+/// workspace paths even after secrets are redacted
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/transcript",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Redacted transcript in invocation order", body = TaskTranscriptResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "No transcript recorded for the task", body = ErrorResponse),
+        (status = 503, description = "Claude Code client not available", body = ErrorResponse),
+    )
+)]
+async fn get_task_transcript(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<TaskTranscriptResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let failure = |status: StatusCode, error: &str, details: Option<String>| {
+        (
+            status,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details,
+            }),
+        )
+    };
+    let client = api_server.orchestrator.get_claude_client().map_err(|_| {
+        failure(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Claude Code client not available",
+            None,
+        )
+    })?;
+    let transcripts = client.transcripts();
+    if !transcripts.is_enabled() {
+        return Err(failure(
+            StatusCode::NOT_FOUND,
+            "Transcripts are disabled",
+            Some("Set CLAUDE_TRANSCRIPT_DIR to record them".to_string()),
+        ));
+    }
+    match transcripts.load(&task_id).await {
+        Ok(Some(entries)) => Ok(Json(TaskTranscriptResponse { task_id, entries })),
+        Ok(None) => Err(failure(
+            StatusCode::NOT_FOUND,
+            "Transcript not available",
+            Some(format!("Task ID: {task_id}")),
+        )),
+        Err(e) => {
+            error!("Failed to load transcript for task {}: {}", task_id, e);
+            Err(failure(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load transcript",
+                None,
+            ))
+        }
+    }
+}
+
+/// 📡 TASK STREAM: Server-sent events for one task until it finishes
+/// Sends `status` on connect, then `lifecycle` and `progress` as they happen and a
+/// final `result`, with a heartbeat comment while the task is quiet
+#[utoipa::path(
+    get,
+    path = "/tasks/{task_id}/stream",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Server-sent events until the task finishes", content_type = "text/event-stream", body = TaskStreamItem),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+    )
+)]
+async fn stream_task_updates(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<
+    Sse<impl Stream<Item = std::result::Result<Event, axum::Error>>>,
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let Some(updates) = task_update_stream(api_server.orchestrator.clone(), task_id.clone()).await
+    else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not found".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        ));
+    };
+
+    let events = updates.map(|item| Event::default().event(item.event_name()).json_data(&item));
+    Ok(Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(std::time::Duration::from_secs(
+                crate::constants::SSE_HEARTBEAT_SECS,
+            ))
+            .text("heartbeat"),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/{task_id}/analyze",
+    tag = "tasks",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Analysis of the stored task", body = TaskAnalysisResponse),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn analyze_task(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<TaskAnalysisResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(task) = api_server.orchestrator.get_task_status(&task_id).await else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not found".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        ));
+    };
+
+    run_analysis(&api_server, &task).await
+}
+
+/// 🔍 DRY-RUN ANALYSIS: Analyse content without creating or touching any stored task
+/// Goes through the same validation and routing as submission, so the analysis
+/// matches what submitting the same request would run
+#[utoipa::path(
+    post,
+    path = "/analyze",
+    tag = "tasks",
+    request_body = AnalyzeTaskRequest,
+    responses(
+        (status = 200, description = "Task analysis", body = TaskAnalysisResponse),
+        (status = 400, description = "Invalid content or no capable agent", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn analyze_content(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Json(request): Json<AnalyzeTaskRequest>,
+) -> std::result::Result<Json<TaskAnalysisResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let task = build_task(
+        &api_server,
+        CreateTaskRequest {
+            agent_type: request.agent_type,
+            content: request.content,
+            priority: None,
+            context: request.context,
+            max_retries: None,
+            schedule: None,
+            callback_url: None,
+        },
+        identity.as_deref(),
+    )
+    .await?;
+
+    run_analysis(&api_server, &task).await
+}
+
+async fn run_analysis(
+    api_server: &ApiServer,
+    task: &Task,
+) -> std::result::Result<Json<TaskAnalysisResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match api_server.orchestrator.analyze_task(task).await {
+        Ok(analysis) => Ok(Json(TaskAnalysisResponse {
+            complexity: analysis.complexity,
+            estimated_minutes: analysis.estimated_minutes,
+            required_skills: analysis.required_skills,
+            challenges: analysis.challenges,
+            approach: analysis.approach,
+        })),
+        Err(e) => {
+            // SECURITY: Log detailed error server-side only
+            warn!("Failed to analyze task {}: {}", task.id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal errors
+                }),
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/agents/{agent_type}",
+    tag = "agents",
+    params(("agent_type" = String, Path, description = "Agent type, e.g. SoftwareDeveloper")),
+    responses(
+        (status = 200, description = "Agent status", body = AgentStatusResponse),
+        (status = 404, description = "Agent not found", body = ErrorResponse),
+    )
+)]
+async fn get_agent_status(
+    State(api_server): State<ApiServer>,
+    Path(agent_type_str): Path<String>,
+) -> std::result::Result<Json<AgentStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    // Try to parse the agent type string into AgentType enum
+    let agent_type = match agent_type_str.parse::<AgentType>() {
+        Ok(agent_type) => agent_type,
+        Err(_) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: ERROR_AGENT_NOT_FOUND.to_string(),
+                    details: Some(format!("Agent type: {agent_type_str}")),
+                }),
+            ));
+        }
+    };
+    match api_server.orchestrator.get_agent_status(&agent_type).await {
+        Some(status) => Ok(Json(status.into())),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: ERROR_AGENT_NOT_FOUND.to_string(),
+                details: Some(format!("Agent type: {agent_type:?}")),
+            }),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/agents",
+    tag = "agents",
+    responses(
+        (status = 200, description = "Status of every agent, keyed by agent type", body = HashMap<String, AgentStatusResponse>),
+    )
+)]
+async fn get_all_agent_statuses(
+    State(api_server): State<ApiServer>,
+) -> Json<HashMap<AgentType, AgentStatusResponse>> {
+    let statuses = api_server.orchestrator.get_all_agent_statuses().await;
+    let response: HashMap<AgentType, AgentStatusResponse> = statuses
+        .into_iter()
+        .map(|(agent_type, status)| (agent_type, status.into()))
+        .collect();
+
+    Json(response)
+}
+
+#[utoipa::path(
+    get,
+    path = "/system/status",
+    tag = "system",
+    responses(
+        (status = 200, description = "System status", body = SystemStatusResponse),
+    )
+)]
+async fn get_system_status(State(api_server): State<ApiServer>) -> Json<SystemStatusResponse> {
+    let agent_statuses = api_server.orchestrator.get_all_agent_statuses().await;
+    let queue_length = api_server.orchestrator.get_queue_length().await;
+    let system_uptime = api_server.orchestrator.get_system_uptime().await;
+    let dispatch_state = api_server.orchestrator.get_dispatch_state().await;
+
+    let agents: HashMap<AgentType, AgentStatusResponse> = agent_statuses
+        .into_iter()
+        .map(|(agent_type, status)| (agent_type, status.into()))
+        .collect();
+
+    Json(SystemStatusResponse {
+        agents,
+        queue_length,
+        system_uptime,
+        dispatch_state,
+        ip_filter: api_server.ip_filter.stats(),
+        claude_cli: api_server
+            .orchestrator
+            .get_claude_client()
+            .ok()
+            .map(|client| client.cli_status()),
+        adaptive_rate_limits: api_server.rate_limiter.adaptive().status(),
+    })
+}
+
+async fn dispatch_state_response(orchestrator: &AgentOrchestrator) -> DispatchStateResponse {
+    DispatchStateResponse {
+        dispatch_state: orchestrator.get_dispatch_state().await,
+        in_flight: orchestrator.get_in_flight_count(),
+        queue_length: orchestrator.get_queue_length().await,
+    }
+}
+
+/// ⏸️ PAUSE ENDPOINT: Stop dispatching new tasks, in-flight tasks keep running
+#[utoipa::path(
+    post,
+    path = "/system/pause",
+    tag = "system",
+    responses(
+        (status = 200, description = "Dispatch paused", body = DispatchStateResponse),
+    )
+)]
+async fn pause_dispatch(State(api_server): State<ApiServer>) -> Json<DispatchStateResponse> {
+    api_server.orchestrator.pause().await;
+    Json(dispatch_state_response(&api_server.orchestrator).await)
+}
+
+/// ▶️ RESUME ENDPOINT: Restart dispatch after a pause or drain
+#[utoipa::path(
+    post,
+    path = "/system/resume",
+    tag = "system",
+    responses(
+        (status = 200, description = "Dispatch resumed", body = DispatchStateResponse),
+    )
+)]
+async fn resume_dispatch(State(api_server): State<ApiServer>) -> Json<DispatchStateResponse> {
+    api_server.orchestrator.resume().await;
+    Json(dispatch_state_response(&api_server.orchestrator).await)
+}
+
+/// 🚰 DRAIN ENDPOINT: Pause dispatch and respond once in-flight tasks have finished
+/// DECISION: Block the request until drained so maintenance scripts can simply await it
+#[utoipa::path(
+    post,
+    path = "/system/drain",
+    tag = "system",
+    params(DrainQueryParams),
+    responses(
+        (status = 200, description = "In-flight tasks finished", body = DispatchStateResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 504, description = "In-flight tasks did not finish in time", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn drain_dispatch(
+    State(api_server): State<ApiServer>,
+    Query(params): Query<DrainQueryParams>,
+) -> std::result::Result<Json<DispatchStateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let max_wait = std::time::Duration::from_secs(
+        params
+            .timeout_secs
+            .unwrap_or(crate::constants::DRAIN_TIMEOUT_SECS),
+    );
+
+    match api_server.orchestrator.drain(max_wait).await {
+        Ok(()) => Ok(Json(
+            dispatch_state_response(&api_server.orchestrator).await,
+        )),
+        Err(SpiralError::Timeout { message }) => Err((
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(ErrorResponse {
+                error: "Drain timed out".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            error!("Failed to drain orchestrator: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to drain orchestrator".to_string(),
+                    details: None,
+                }),
+            ))
+        }
+    }
+}
+
+fn invalid_memory(details: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: ERROR_INVALID_MEMORY.to_string(),
+            details: Some(details.to_string()),
+        }),
+    )
+}
+
+fn memory_storage_error(e: SpiralError) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Agent memory storage failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: ERROR_INTERNAL_SERVER.to_string(),
+            details: None,
+        }),
+    )
+}
+
+/// 📜 AGENT TASK HISTORY: Recent tasks for one agent with their outcomes and durations
+#[utoipa::path(
+    get,
+    path = "/agents/{agent_type}/tasks",
+    tag = "agents",
+    params(
+        ("agent_type" = String, Path, description = "Agent type, e.g. SoftwareDeveloper"),
+        AgentTaskHistoryQueryParams
+    ),
+    responses(
+        (status = 200, description = "Recent tasks, newest first", body = AgentTaskHistoryResponse),
+        (status = 404, description = "Agent not found", body = ErrorResponse),
+    )
+)]
+async fn get_agent_task_history(
+    State(api_server): State<ApiServer>,
+    Path(agent_type_str): Path<String>,
+    Query(params): Query<AgentTaskHistoryQueryParams>,
+) -> std::result::Result<Json<AgentTaskHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let agent_type = registered_agent_type(&api_server, &agent_type_str).await?;
+    let limit = params
+        .limit
+        .unwrap_or(crate::constants::AGENT_TASK_HISTORY_DEFAULT_LIMIT)
+        .min(crate::constants::AGENT_TASK_HISTORY_MAX_LIMIT);
+
+    let tasks = api_server
+        .orchestrator
+        .get_agent_task_history(&agent_type, params.status.as_ref(), limit)
+        .await;
+    Ok(Json(AgentTaskHistoryResponse { agent_type, tasks }))
+}
+
+/// Registered agent named in the path
+async fn registered_agent_type(
+    api_server: &ApiServer,
+    agent_type_str: &str,
+) -> std::result::Result<AgentType, (StatusCode, Json<ErrorResponse>)> {
+    match agent_type_str.parse::<AgentType>() {
+        Ok(agent_type)
+            if api_server
+                .orchestrator
+                .get_agent_status(&agent_type)
+                .await
+                .is_some() =>
+        {
+            Ok(agent_type)
+        }
+        _ => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: ERROR_AGENT_NOT_FOUND.to_string(),
+                details: Some(format!("Agent type: {agent_type_str}")),
+            }),
+        )),
+    }
+}
+
+/// Scope IDs are sanitized like task context values so they match the IDs tasks carry
+fn sanitize_memory_scope(
+    api_server: &ApiServer,
+    scope: MemoryScope,
+) -> std::result::Result<MemoryScope, (StatusCode, Json<ErrorResponse>)> {
+    if scope.id().trim().is_empty() {
+        return Err(invalid_memory("Scope ID cannot be empty"));
+    }
+    let id = api_server
+        .validator
+        .validate_and_sanitize_context_value(scope.id())
+        .map_err(|_| invalid_memory("Scope ID failed validation"))?;
+    Ok(MemoryScope::from_parts(scope.type_label(), &id).unwrap_or(scope))
+}
+
+/// 🛡️ MEMORY OWNERSHIP: A key owns its own user scope, the one its tasks are given;
+/// admin keys own every scope
+fn owns_memory_scope(identity: Option<&ApiKeyIdentity>, scope: &MemoryScope) -> bool {
+    is_admin(identity) || *scope == MemoryScope::User(sessions::owner(identity))
+}
+
+fn unowned_memory_scope(scope: &MemoryScope) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: "Memory scope not owned".to_string(),
+            details: Some(format!(
+                "The {} scope {} belongs to another caller; only admin keys may use it",
+                scope.type_label(),
+                scope.id()
+            )),
+        }),
+    )
+}
+
+/// 🧠 MEMORY RECALL: What the agent remembers for the given user, guild or workspace
+#[utoipa::path(
+    get,
+    path = "/agents/{agent_type}/memory",
+    tag = "memory",
+    params(("agent_type" = String, Path, description = "Agent type, e.g. SoftwareDeveloper"), MemoryQueryParams),
+    responses(
+        (status = 200, description = "Remembered entries", body = Vec<MemoryEntry>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "A scope belongs to another caller", body = ErrorResponse),
+        (status = 404, description = "Agent not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn get_agent_memory(
+    State(api_server): State<ApiServer>,
+    Path(agent_type_str): Path<String>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Query(params): Query<MemoryQueryParams>,
+) -> std::result::Result<Json<Vec<MemoryEntry>>, (StatusCode, Json<ErrorResponse>)> {
+    let agent_type = registered_agent_type(&api_server, &agent_type_str).await?;
+    let identity = identity.as_deref();
+
+    let mut scopes = [
+        params.user.map(MemoryScope::User),
+        params.guild.map(MemoryScope::Guild),
+        params.workspace.map(MemoryScope::Workspace),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|scope| sanitize_memory_scope(&api_server, scope))
+    .collect::<std::result::Result<Vec<_>, _>>()?;
+    if scopes.is_empty() {
+        if is_admin(identity) {
+            return Err(invalid_memory(
+                "At least one of user, guild or workspace is required",
+            ));
+        }
+        scopes.push(MemoryScope::User(sessions::owner(identity)));
+    }
+    if let Some(scope) = scopes
+        .iter()
+        .find(|scope| !owns_memory_scope(identity, scope))
+    {
+        return Err(unowned_memory_scope(scope));
+    }
+
+    let limit = params
+        .limit
+        .unwrap_or(crate::constants::AGENT_MEMORY_RECALL_LIMIT)
+        .min(crate::constants::AGENT_MEMORY_MAX_LIST_LIMIT);
+    api_server
+        .orchestrator
+        .recall_memories(&agent_type, &scopes, limit)
+        .await
+        .map(Json)
+        .map_err(memory_storage_error)
+}
+
+/// 🧠 MEMORY STORE: Teach an agent a convention or preference for later tasks in a scope
+#[utoipa::path(
+    post,
+    path = "/agents/{agent_type}/memory",
+    tag = "memory",
+    params(("agent_type" = String, Path, description = "Agent type, e.g. SoftwareDeveloper")),
+    request_body = StoreMemoryRequest,
+    responses(
+        (status = 201, description = "Entry stored", body = MemoryEntry),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "The scope belongs to another caller", body = ErrorResponse),
+        (status = 404, description = "Agent not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn store_agent_memory(
+    State(api_server): State<ApiServer>,
+    Path(agent_type_str): Path<String>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Json(request): Json<StoreMemoryRequest>,
+) -> std::result::Result<(StatusCode, Json<MemoryEntry>), (StatusCode, Json<ErrorResponse>)> {
+    let agent_type = registered_agent_type(&api_server, &agent_type_str).await?;
+    let scope = sanitize_memory_scope(&api_server, request.scope)?;
+    // Memories reach later tasks' prompts, so only the scope's owner may add to it
+    if !owns_memory_scope(identity.as_deref(), &scope) {
+        return Err(unowned_memory_scope(&scope));
+    }
+
+    if request.content.trim().is_empty() {
+        return Err(invalid_memory("Content cannot be empty"));
+    }
+    // Same checks as a context value, since memories are injected into task context
+    let content = api_server
+        .validator
+        .validate_and_sanitize_context_value(&request.content)
+        .map_err(|_| invalid_memory("Content failed validation"))?;
+
+    let entry = MemoryEntry::new(agent_type, scope, request.kind, content);
+    api_server
+        .orchestrator
+        .remember(entry.clone())
+        .await
+        .map_err(memory_storage_error)?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/memory/{memory_id}",
+    tag = "memory",
+    params(("memory_id" = String, Path, description = "Memory entry ID")),
+    responses(
+        (status = 204, description = "Entry forgotten"),
+        (status = 404, description = "Memory entry not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn forget_memory(
+    State(api_server): State<ApiServer>,
+    Path(memory_id): Path<String>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+) -> std::result::Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Memory not found".to_string(),
+                details: Some(format!("Memory ID: {memory_id}")),
+            }),
+        )
+    };
+    // Another caller's memory is reported as missing, like another key's session
+    if !is_admin(identity.as_deref()) {
+        match api_server.orchestrator.memory_entry(&memory_id).await {
+            Ok(Some(entry)) if owns_memory_scope(identity.as_deref(), &entry.scope) => {}
+            Ok(_) => return Err(not_found()),
+            Err(e) => return Err(memory_storage_error(e)),
+        }
+    }
+    match api_server.orchestrator.forget_memory(&memory_id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(not_found()),
+        Err(e) => Err(memory_storage_error(e)),
+    }
+}
+
+/// 📅 SCHEDULES ENDPOINT: Pending delayed and recurring tasks, soonest first
+#[utoipa::path(
+    get,
+    path = "/schedules",
+    tag = "queue",
+    responses(
+        (status = 200, description = "Scheduled tasks", body = Vec<ScheduleResponse>),
+    )
+)]
+async fn get_schedules(State(api_server): State<ApiServer>) -> Json<Vec<ScheduleResponse>> {
+    let schedules = api_server.orchestrator.get_schedules().await;
+    Json(schedules.into_iter().map(ScheduleResponse::from).collect())
+}
+
+/// 📋 QUEUE ENDPOINT: Pending tasks in the order they will be dispatched
+#[utoipa::path(
+    get,
+    path = "/queue",
+    tag = "queue",
+    responses(
+        (status = 200, description = "Queued tasks in dispatch order", body = QueueResponse),
+    )
+)]
+async fn get_queue(State(api_server): State<ApiServer>) -> Json<QueueResponse> {
+    let tasks = api_server.orchestrator.get_queue().await;
+    Json(QueueResponse {
+        length: tasks.len(),
+        in_flight: api_server.orchestrator.get_in_flight_count(),
+        tasks,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/queue/{task_id}/promote",
+    tag = "queue",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task moved up the queue", body = QueuedTask),
+        (status = 404, description = "Task not queued", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn promote_queued_task(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<QueuedTask>, (StatusCode, Json<ErrorResponse>)> {
+    move_queued_task(&api_server, &task_id, QueueMove::Promote).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/queue/{task_id}/demote",
+    tag = "queue",
+    params(("task_id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task moved down the queue", body = QueuedTask),
+        (status = 404, description = "Task not queued", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn demote_queued_task(
+    State(api_server): State<ApiServer>,
+    Path(task_id): Path<String>,
+) -> std::result::Result<Json<QueuedTask>, (StatusCode, Json<ErrorResponse>)> {
+    move_queued_task(&api_server, &task_id, QueueMove::Demote).await
+}
+
+/// ↕️ QUEUE REORDER: Only pending tasks can move; running or finished tasks are not queued
+async fn move_queued_task(
+    api_server: &ApiServer,
+    task_id: &str,
+    direction: QueueMove,
+) -> std::result::Result<Json<QueuedTask>, (StatusCode, Json<ErrorResponse>)> {
+    match api_server
+        .orchestrator
+        .move_queued_task(task_id, direction)
+        .await
+    {
+        Ok(entry) => Ok(Json(entry)),
+        Err(SpiralError::NotFound(_)) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Task not queued".to_string(),
+                details: Some(format!("Task ID: {task_id}")),
+            }),
+        )),
+        Err(e) => {
+            error!("Failed to reorder task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None,
+                }),
+            ))
+        }
+    }
+}
+
+/// 🔗 WORKFLOWS ENDPOINT: Configured multi-agent pipelines, by name
+#[utoipa::path(
+    get,
+    path = "/workflows",
+    tag = "workflows",
+    responses(
+        (status = 200, description = "Configured workflows", body = Vec<WorkflowResponse>),
+    )
+)]
+async fn get_workflows(State(api_server): State<ApiServer>) -> Json<Vec<WorkflowResponse>> {
+    let mut workflows: Vec<WorkflowResponse> = api_server
+        .orchestrator
+        .get_workflows()
+        .into_iter()
+        .map(|(name, steps)| WorkflowResponse { name, steps })
+        .collect();
+    workflows.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(workflows)
+}
+
+/// 🔗 WORKFLOW RUN: Start a pipeline and return immediately with the run to poll
+#[utoipa::path(
+    post,
+    path = "/workflows/{name}/run",
+    tag = "workflows",
+    params(("name" = String, Path, description = "Workflow name")),
+    request_body = RunWorkflowRequest,
+    responses(
+        (status = 202, description = "Workflow run started", body = WorkflowRun),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Workflow not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn run_workflow(
     State(api_server): State<ApiServer>,
-    Path(task_id): Path<String>,
-    Json(request): Json<CreateTaskRequest>,
-) -> std::result::Result<Json<TaskAnalysisResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let mut task = Task::new(request.agent_type, request.content, Priority::Medium);
-    task.id = task_id;
+    Path(name): Path<String>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    headers: HeaderMap,
+    Json(request): Json<RunWorkflowRequest>,
+) -> std::result::Result<(StatusCode, Json<WorkflowRun>), (StatusCode, Json<ErrorResponse>)> {
+    let workflow_not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Workflow not found".to_string(),
+                details: Some(format!("Workflow: {name}")),
+            }),
+        )
+    };
 
-    if let Some(context) = request.context {
-        for (key, value) in context {
-            task = task.with_context(key, value);
-        }
+    let first_agent = api_server
+        .orchestrator
+        .get_workflows()
+        .get(&name)
+        .and_then(|steps| steps.first().cloned())
+        .ok_or_else(workflow_not_found)?;
+
+    let session =
+        sessions::session_from_headers(&api_server, &headers, identity.as_deref()).await?;
+
+    // Same validation as a single task; each step then takes its own agent
+    let mut task = build_task(
+        &api_server,
+        CreateTaskRequest {
+            agent_type: Some(first_agent),
+            content: request.content,
+            priority: request.priority,
+            context: request.context,
+            max_retries: request.max_retries,
+            schedule: None,
+            callback_url: None,
+        },
+        identity.as_deref(),
+    )
+    .await?;
+    // 🌳 Each step runs in a child of the session, sharing its workspace
+    if let Some(session) = &session {
+        task = session.apply_to_task(task);
     }
 
-    match api_server.orchestrator.analyze_task(&task).await {
-        Ok(analysis) => Ok(Json(TaskAnalysisResponse {
-            complexity: analysis.complexity,
-            estimated_minutes: analysis.estimated_minutes,
-            required_skills: analysis.required_skills,
-            challenges: analysis.challenges,
-            approach: analysis.approach,
-        })),
+    match api_server.orchestrator.run_workflow(&name, task).await {
+        Ok(run) => Ok((StatusCode::ACCEPTED, Json(run))),
+        Err(SpiralError::NotFound(_)) => Err(workflow_not_found()),
+        Err(SpiralError::Validation(message)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Workflow cannot run".to_string(),
+                details: Some(message),
+            }),
+        )),
         Err(e) => {
-            // SECURITY: Log detailed error server-side only
-            warn!("Failed to analyze task: {}", e);
+            warn!("Failed to start workflow {}: {}", name, e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
                     error: ERROR_INTERNAL_SERVER.to_string(),
-                    details: None, // SECURITY: Never expose internal errors
+                    details: None, // SECURITY: Never expose internal orchestrator errors
                 }),
             ))
         }
     }
 }
 
-async fn get_agent_status(
+#[utoipa::path(
+    get,
+    path = "/workflows/runs/{run_id}",
+    tag = "workflows",
+    params(("run_id" = String, Path, description = "Workflow run ID")),
+    responses(
+        (status = 200, description = "Workflow run", body = WorkflowRun),
+        (status = 404, description = "Workflow run not found", body = ErrorResponse),
+    )
+)]
+async fn get_workflow_run(
     State(api_server): State<ApiServer>,
-    Path(agent_type_str): Path<String>,
-) -> std::result::Result<Json<AgentStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Try to parse the agent type string into AgentType enum
-    let agent_type = match agent_type_str.parse::<AgentType>() {
-        Ok(agent_type) => agent_type,
-        Err(_) => {
-            return Err((
+    Path(run_id): Path<String>,
+) -> std::result::Result<Json<WorkflowRun>, (StatusCode, Json<ErrorResponse>)> {
+    api_server
+        .orchestrator
+        .get_workflow_run(&run_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| {
+            (
                 StatusCode::NOT_FOUND,
                 Json(ErrorResponse {
-                    error: ERROR_AGENT_NOT_FOUND.to_string(),
-                    details: Some(format!("Agent type: {agent_type_str}")),
+                    error: "Workflow run not found".to_string(),
+                    details: Some(format!("Run ID: {run_id}")),
                 }),
-            ));
-        }
-    };
-    match api_server.orchestrator.get_agent_status(&agent_type).await {
-        Some(status) => Ok(Json(status.into())),
-        None => Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: ERROR_AGENT_NOT_FOUND.to_string(),
-                details: Some(format!("Agent type: {agent_type:?}")),
-            }),
-        )),
-    }
-}
-
-async fn get_all_agent_statuses(
-    State(api_server): State<ApiServer>,
-) -> Json<HashMap<AgentType, AgentStatusResponse>> {
-    let statuses = api_server.orchestrator.get_all_agent_statuses().await;
-    let response: HashMap<AgentType, AgentStatusResponse> = statuses
-        .into_iter()
-        .map(|(agent_type, status)| (agent_type, status.into()))
-        .collect();
-
-    Json(response)
-}
-
-async fn get_system_status(State(api_server): State<ApiServer>) -> Json<SystemStatusResponse> {
-    let agent_statuses = api_server.orchestrator.get_all_agent_statuses().await;
-    let queue_length = api_server.orchestrator.get_queue_length().await;
-    let system_uptime = api_server.orchestrator.get_system_uptime().await;
-
-    let agents: HashMap<AgentType, AgentStatusResponse> = agent_statuses
-        .into_iter()
-        .map(|(agent_type, status)| (agent_type, status.into()))
-        .collect();
-
-    Json(SystemStatusResponse {
-        agents,
-        queue_length,
-        system_uptime,
-    })
+            )
+        })
 }
 
+#[utoipa::path(
+    get,
+    path = "/workspaces",
+    tag = "workspaces",
+    responses(
+        (status = 200, description = "Workspace usage", body = AllWorkspacesStatusResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
 async fn get_all_workspaces_status(
     State(api_server): State<ApiServer>,
 ) -> std::result::Result<Json<AllWorkspacesStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -543,22 +2771,553 @@ async fn get_all_workspaces_status(
     }
 }
 
+/// Directory under the server's working directory, e.g. the workspaces root
+fn workspaces_base_dir(name: &str) -> Result<std::path::PathBuf> {
+    let current_dir = std::env::current_dir().map_err(|e| SpiralError::Agent {
+        message: format!("Failed to get current directory: {e}"),
+    })?;
+    Ok(current_dir.join(name))
+}
+
+/// Resolve a workspace ID from the path, or the 404 to return
+fn workspace_path(
+    workspace_id: &str,
+) -> std::result::Result<std::path::PathBuf, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: ERROR_WORKSPACE_NOT_FOUND.to_string(),
+                details: Some(format!("Workspace ID: {workspace_id}")),
+            }),
+        )
+    };
+    let base = workspaces_base_dir(WORKSPACES_DIR).map_err(|_| not_found())?;
+    workspace_admin::resolve_workspace(&base, workspace_id).ok_or_else(not_found)
+}
+
+/// 🛡️ SAFETY CHECK: Refuse to remove a session workspace while a task works in it
+/// Session workspaces are named after a session key: a task's own ID, a Discord thread
+/// or an explicit or managed session, so several tasks may share one
+/// Returns the session lock, to be held until the workspace is gone so no run starts in it
+async fn ensure_workspace_idle(
+    api_server: &ApiServer,
+    workspace_id: &str,
+) -> std::result::Result<Option<OwnedMutexGuard<()>>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(session_key) = workspace_id.strip_prefix(SESSION_PREFIX) else {
+        return Ok(None);
+    };
+    let in_use = || {
+        (
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: "Workspace in use".to_string(),
+                details: Some(format!(
+                    "Session {session_key} still has tasks working in it"
+                )),
+            }),
+        )
+    };
+    let client = api_server
+        .orchestrator
+        .get_claude_client()
+        .map_err(|e| workspace_action_failed("lock", workspace_id, e))?;
+    let guard = client.try_lock_session(session_key).ok_or_else(in_use)?;
+    // A task between dispatch and its run, or queued behind it, holds no lock yet
+    if api_server.orchestrator.session_in_use(session_key).await {
+        return Err(in_use());
+    }
+    Ok(Some(guard))
+}
+
+fn workspace_action_failed(
+    action: &str,
+    workspace_id: &str,
+    e: impl std::fmt::Display,
+) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Failed to {} workspace {}: {}", action, workspace_id, e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Failed to {action} workspace"),
+            details: None, // SECURITY: Don't expose filesystem details
+        }),
+    )
+}
+
+/// 🗑️ WORKSPACE DELETE: Remove a workspace and everything in it
+#[utoipa::path(
+    delete,
+    path = "/workspaces/{workspace_id}",
+    tag = "workspaces",
+    params(("workspace_id" = String, Path, description = "Workspace directory name")),
+    responses(
+        (status = 200, description = "Workspace deleted", body = DeleteWorkspaceResponse),
+        (status = 404, description = "Workspace not found", body = ErrorResponse),
+        (status = 409, description = "Workspace in use by a queued or running task", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn delete_workspace(
+    State(api_server): State<ApiServer>,
+    Path(workspace_id): Path<String>,
+) -> std::result::Result<Json<DeleteWorkspaceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let path = workspace_path(&workspace_id)?;
+    let _session_guard = ensure_workspace_idle(&api_server, &workspace_id).await?;
+
+    let result = tokio::task::spawn_blocking(move || {
+        let (freed_bytes, _) = calculate_directory_size(&path)?;
+        std::fs::remove_dir_all(&path).map_err(|e| SpiralError::SystemError(e.to_string()))?;
+        Ok::<_, SpiralError>(freed_bytes)
+    })
+    .await;
+    match result {
+        Ok(Ok(freed_bytes)) => {
+            info!(
+                "Workspace {} deleted, freed {} bytes",
+                workspace_id, freed_bytes
+            );
+            Ok(Json(DeleteWorkspaceResponse {
+                workspace_id,
+                freed_bytes,
+                freed_human: format_bytes_human_readable(freed_bytes),
+            }))
+        }
+        Ok(Err(e)) => Err(workspace_action_failed("delete", &workspace_id, e)),
+        Err(e) => Err(workspace_action_failed("delete", &workspace_id, e)),
+    }
+}
+
+/// 📦 WORKSPACE ARCHIVE: Pack a workspace into a .tar.gz in the archive directory and remove it
+#[utoipa::path(
+    post,
+    path = "/workspaces/{workspace_id}/archive",
+    tag = "workspaces",
+    params(("workspace_id" = String, Path, description = "Workspace directory name")),
+    responses(
+        (status = 200, description = "Workspace archived and removed", body = ArchiveWorkspaceResponse),
+        (status = 404, description = "Workspace not found", body = ErrorResponse),
+        (status = 409, description = "Workspace in use by a queued or running task", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn archive_workspace(
+    State(api_server): State<ApiServer>,
+    Path(workspace_id): Path<String>,
+) -> std::result::Result<Json<ArchiveWorkspaceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let path = workspace_path(&workspace_id)?;
+    let _session_guard = ensure_workspace_idle(&api_server, &workspace_id).await?;
+    let archive_dir = workspaces_base_dir(WORKSPACE_ARCHIVE_DIR)
+        .map_err(|e| workspace_action_failed("archive", &workspace_id, e))?;
+
+    let id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let (freed_bytes, _) = calculate_directory_size(&path)?;
+        let archived = workspace_archive::archive_workspace(&path, &archive_dir, &id)
+            .map_err(|e| SpiralError::SystemError(e.to_string()))?;
+        Ok::<_, SpiralError>((archived, freed_bytes))
+    })
+    .await;
+    match result {
+        Ok(Ok((archived, freed_bytes))) => {
+            let archive_path = std::path::Path::new(WORKSPACE_ARCHIVE_DIR)
+                .join(archived.archive_path.file_name().unwrap_or_default())
+                .to_string_lossy()
+                .to_string();
+            info!("Workspace {} archived to {}", workspace_id, archive_path);
+            Ok(Json(ArchiveWorkspaceResponse {
+                workspace_id,
+                archive_path,
+                archive_size_bytes: archived.archive_size_bytes,
+                freed_bytes,
+            }))
+        }
+        Ok(Err(e)) => Err(workspace_action_failed("archive", &workspace_id, e)),
+        Err(e) => Err(workspace_action_failed("archive", &workspace_id, e)),
+    }
+}
+
+/// 🗂️ WORKSPACE FILES: What a workspace contains, for a look before deleting it
+#[utoipa::path(
+    get,
+    path = "/workspaces/{workspace_id}/files",
+    tag = "workspaces",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace directory name"),
+        WorkspaceFilesQueryParams
+    ),
+    responses(
+        (status = 200, description = "Files in the workspace", body = WorkspaceFilesResponse),
+        (status = 404, description = "Workspace not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn get_workspace_files(
+    Path(workspace_id): Path<String>,
+    Query(params): Query<WorkspaceFilesQueryParams>,
+) -> std::result::Result<Json<WorkspaceFilesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let path = workspace_path(&workspace_id)?;
+    let limit = params
+        .limit
+        .unwrap_or(crate::constants::WORKSPACE_FILES_MAX_ENTRIES)
+        .min(crate::constants::WORKSPACE_FILES_MAX_ENTRIES);
+
+    let listing =
+        tokio::task::spawn_blocking(move || workspace_admin::list_workspace_files(&path, limit))
+            .await;
+    match listing {
+        Ok(Ok(listing)) => Ok(Json(WorkspaceFilesResponse {
+            workspace_id,
+            files: listing.files,
+            total_files: listing.total_files,
+            total_size_bytes: listing.total_size_bytes,
+            truncated: listing.truncated,
+        })),
+        Ok(Err(e)) => Err(workspace_action_failed("list", &workspace_id, e)),
+        Err(e) => Err(workspace_action_failed("list", &workspace_id, e)),
+    }
+}
+
+/// 🔑 KEY ISSUANCE: Create a named key with the given scopes
+#[utoipa::path(
+    post,
+    path = "/admin/keys",
+    tag = "admin",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Key created; the key is only returned here", body = CreateApiKeyResponse),
+        (status = 400, description = "Invalid name or scopes", body = ErrorResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn create_api_key(
+    State(api_server): State<ApiServer>,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> std::result::Result<(StatusCode, Json<CreateApiKeyResponse>), (StatusCode, Json<ErrorResponse>)>
+{
+    match api_server
+        .api_keys
+        .create(&request.name, request.scopes)
+        .await
+    {
+        Ok((info, key)) => Ok((
+            StatusCode::CREATED,
+            Json(CreateApiKeyResponse { info, key }),
+        )),
+        Err(e) => Err(api_key_action_failed(e)),
+    }
+}
+
+/// 🔑 KEY LISTING: Every managed key, revoked ones included, without the keys themselves
+#[utoipa::path(
+    get,
+    path = "/admin/keys",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Managed API keys", body = ApiKeysResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+    )
+)]
+async fn list_api_keys(State(api_server): State<ApiServer>) -> Json<ApiKeysResponse> {
+    Json(ApiKeysResponse {
+        keys: api_server.api_keys.list().await,
+    })
+}
+
+/// 🔑 KEY REVOCATION: The key stops authenticating immediately
+#[utoipa::path(
+    delete,
+    path = "/admin/keys/{key_id}",
+    tag = "admin",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses(
+        (status = 200, description = "Key revoked", body = ApiKeyInfo),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "Key not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn revoke_api_key(
+    State(api_server): State<ApiServer>,
+    Path(key_id): Path<String>,
+) -> std::result::Result<Json<ApiKeyInfo>, (StatusCode, Json<ErrorResponse>)> {
+    api_server
+        .api_keys
+        .revoke(&key_id)
+        .await
+        .map(Json)
+        .map_err(api_key_action_failed)
+}
+
+/// 📜 AUDIT QUERY: Recorded security events, newest first
+#[utoipa::path(
+    get,
+    path = "/admin/audit",
+    tag = "admin",
+    params(AuditQueryParams),
+    responses(
+        (status = 200, description = "Matching audit events", body = AuditLogResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+async fn get_audit_log(
+    Query(params): Query<AuditQueryParams>,
+) -> std::result::Result<Json<AuditLogResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(log) = audit::global() else {
+        return Ok(Json(AuditLogResponse {
+            enabled: false,
+            events: Vec::new(),
+        }));
+    };
+
+    let query = AuditQuery {
+        kind: params.kind,
+        source: params.source,
+        actor: params.actor,
+        since: params.since,
+        until: params.until,
+        limit: params
+            .limit
+            .unwrap_or(crate::constants::AUDIT_QUERY_DEFAULT_LIMIT)
+            .min(crate::constants::AUDIT_QUERY_MAX_LIMIT),
+    };
+    match tokio::task::spawn_blocking(move || log.query(&query)).await {
+        Ok(Ok(events)) => Ok(Json(AuditLogResponse {
+            enabled: true,
+            events,
+        })),
+        Ok(Err(e)) => Err(audit_query_failed(e)),
+        Err(e) => Err(audit_query_failed(e)),
+    }
+}
+
+fn audit_query_failed(error: impl std::fmt::Display) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Failed to read audit log: {}", error);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: ERROR_INTERNAL_SERVER.to_string(),
+            details: None, // SECURITY: Never expose internal errors
+        }),
+    )
+}
+
+/// 🔧 LOG LEVEL: The log filter in effect
+#[utoipa::path(
+    get,
+    path = "/admin/log-level",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Current log filter", body = LogLevel),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 503, description = "Logging was not set up with runtime levels", body = ErrorResponse),
+    )
+)]
+async fn get_log_level() -> std::result::Result<Json<LogLevel>, (StatusCode, Json<ErrorResponse>)> {
+    crate::logging::current_filter()
+        .map(|filter| Json(LogLevel { filter }))
+        .ok_or_else(log_level_unavailable)
+}
+
+/// 🔧 LOG LEVEL: Replace the log filter without restarting; lost again on restart
+#[utoipa::path(
+    put,
+    path = "/admin/log-level",
+    tag = "admin",
+    request_body = LogLevel,
+    responses(
+        (status = 200, description = "Filter applied", body = LogLevel),
+        (status = 400, description = "Invalid filter directives", body = ErrorResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+        (status = 503, description = "Logging was not set up with runtime levels", body = ErrorResponse),
+    )
+)]
+async fn set_log_level(
+    Json(request): Json<LogLevel>,
+) -> std::result::Result<Json<LogLevel>, (StatusCode, Json<ErrorResponse>)> {
+    match crate::logging::set_filter(&request.filter) {
+        Ok(filter) => Ok(Json(LogLevel { filter })),
+        Err(SpiralError::Validation(message)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid log filter".to_string(),
+                details: Some(message),
+            }),
+        )),
+        Err(e) => {
+            warn!("Failed to change log filter: {}", e);
+            Err(log_level_unavailable())
+        }
+    }
+}
+
+/// 🩺 ADAPTIVE RATE LIMITS: The level task submissions are limited at, and why
+#[utoipa::path(
+    get,
+    path = "/admin/rate-limits/adaptive",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Adaptive rate limit state", body = AdaptiveRateLimitStatus),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+    )
+)]
+async fn get_adaptive_rate_limits(
+    State(api_server): State<ApiServer>,
+) -> Json<AdaptiveRateLimitStatus> {
+    Json(api_server.rate_limiter.adaptive().status())
+}
+
+/// 🩺 ADAPTIVE RATE LIMITS: Pin task submission limits at a level, or follow health again
+/// Lost on restart, when the limits follow health again
+#[utoipa::path(
+    put,
+    path = "/admin/rate-limits/adaptive",
+    tag = "admin",
+    request_body = AdaptiveModeRequest,
+    responses(
+        (status = 200, description = "Mode applied", body = AdaptiveRateLimitStatus),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+    )
+)]
+async fn set_adaptive_rate_limits(
+    State(api_server): State<ApiServer>,
+    Json(request): Json<AdaptiveModeRequest>,
+) -> Json<AdaptiveRateLimitStatus> {
+    let adaptive = api_server.rate_limiter.adaptive();
+    if let Some(level) = adaptive.set_mode(request.mode) {
+        info!(
+            "Task submission rate limits set to {}% via API (mode {:?})",
+            level.percent(),
+            request.mode
+        );
+    }
+    Json(adaptive.status())
+}
+
+/// 🎟️ RATE LIMIT TIERS: Users and API keys granted a tier above the default
+#[utoipa::path(
+    get,
+    path = "/admin/rate-limits/tiers",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Tier grants", body = Vec<RateLimitTierGrant>),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+    )
+)]
+async fn list_rate_limit_tiers(
+    State(api_server): State<ApiServer>,
+) -> Json<Vec<RateLimitTierGrant>> {
+    Json(
+        api_server
+            .rate_limiter
+            .tiers()
+            .grants()
+            .into_iter()
+            .map(|(subject, grant)| RateLimitTierGrant {
+                subject,
+                tier: grant.tier,
+                granted_by: grant.granted_by,
+                granted_at: grant.granted_at,
+            })
+            .collect(),
+    )
+}
+
+/// 🎟️ RATE LIMIT TIERS: Put a Discord user or API key on a tier; kept across restarts
+#[utoipa::path(
+    put,
+    path = "/admin/rate-limits/tiers/{subject}",
+    tag = "admin",
+    params(("subject" = String, Path, description = "Discord user ID, or api-key:<key_id>")),
+    request_body = SetRateLimitTierRequest,
+    responses(
+        (status = 200, description = "Tier applied", body = RateLimitTierChange),
+        (status = 400, description = "Invalid subject", body = ErrorResponse),
+        (status = 403, description = "Caller lacks the admin scope", body = ErrorResponse),
+    )
+)]
+async fn set_rate_limit_tier(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Path(subject): Path<String>,
+    Json(request): Json<SetRateLimitTierRequest>,
+) -> std::result::Result<Json<RateLimitTierChange>, (StatusCode, Json<ErrorResponse>)> {
+    let subject = crate::rate_limit::tiers::parse_subject(&subject).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid rate limit tier subject".to_string(),
+                details: Some(message),
+            }),
+        )
+    })?;
+    let granted_by = identity
+        .map(|identity| identity.name.clone())
+        .unwrap_or_else(|| "api".to_string());
+    let previous_tier =
+        api_server
+            .rate_limiter
+            .tiers()
+            .set(&subject, request.tier, AuditSource::Api, &granted_by);
+    Ok(Json(RateLimitTierChange {
+        subject,
+        previous_tier,
+        tier: request.tier,
+    }))
+}
+
+fn log_level_unavailable() -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ErrorResponse {
+            error: "Log levels can't be changed in this process".to_string(),
+            details: None,
+        }),
+    )
+}
+
+fn api_key_action_failed(error: SpiralError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        SpiralError::Validation(message) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid API key request".to_string(),
+                details: Some(message),
+            }),
+        ),
+        SpiralError::NotFound(message) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "API key not found".to_string(),
+                details: Some(message),
+            }),
+        ),
+        e => {
+            warn!("API key management failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: ERROR_INTERNAL_SERVER.to_string(),
+                    details: None, // SECURITY: Never expose internal errors
+                }),
+            )
+        }
+    }
+}
+
 async fn scan_workspaces_directory(
     _api_server: &ApiServer,
 ) -> Result<Vec<WorkspaceStatusResponse>> {
     use std::fs;
 
-    // Get the current working directory and construct the workspaces path
-    let current_dir = std::env::current_dir().map_err(|e| SpiralError::Agent {
-        message: format!("Failed to get current directory: {e}"),
-    })?;
-
     // ⚡ PERFORMANCE DECISION: Workspace directory scanning
     // Why: Simple filesystem-based workspace discovery
     // Alternative: Database tracking (rejected: added complexity)
     // Risk: Unbounded directory traversal for large workspace counts
     // Mitigation: Consider adding depth/count limits
-    let workspace_base_dir = current_dir.join(WORKSPACES_DIR);
+    let workspace_base_dir = workspaces_base_dir(WORKSPACES_DIR)?;
 
     if !workspace_base_dir.exists() {
         return Ok(Vec::new());
@@ -740,6 +3499,15 @@ fn format_bytes_human_readable(bytes: u64) -> String {
 /// DECISION: Real-time metrics for operational visibility
 /// Why: Enables proactive monitoring and troubleshooting
 /// Alternative: Log-only metrics (rejected: not accessible for monitoring tools)
+#[utoipa::path(
+    get,
+    path = "/system/metrics",
+    tag = "system",
+    responses(
+        (status = 200, description = "Current system metrics", body = serde_json::Value),
+        (status = 503, description = "Monitoring is not enabled"),
+    )
+)]
 async fn get_system_metrics(
     State(server): State<ApiServer>,
 ) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
@@ -761,24 +3529,103 @@ async fn get_system_metrics(
 /// DECISION: Provide metrics history for trend analysis
 /// Why: Enables identification of performance patterns and degradation
 /// Alternative: Current metrics only (rejected: no trend visibility)
+/// Without parameters this is the monitor's recent in-memory samples, as the dashboard
+/// polls it; a time range or resolution reads the persisted history
+#[utoipa::path(
+    get,
+    path = "/system/metrics/history",
+    tag = "system",
+    params(MetricsHistoryParams),
+    responses(
+        (status = 200, description = "Metrics snapshots, oldest first", body = serde_json::Value),
+        (status = 400, description = "until is before since"),
+        (status = 500, description = "The history could not be read"),
+        (status = 503, description = "Monitoring is not enabled"),
+    )
+)]
 async fn get_metrics_history(
     State(server): State<ApiServer>,
+    Query(params): Query<MetricsHistoryParams>,
 ) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
-    if let Some(monitor) = &server.system_monitor {
+    let Some(monitor) = &server.system_monitor else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    if params.since.is_none() && params.until.is_none() && params.resolution.is_none() {
         let history = monitor.get_metrics_history().await;
-        Ok(Json(serde_json::json!({
+        return Ok(Json(serde_json::json!({
             "metrics_count": history.len(),
             "metrics": history
-        })))
-    } else {
-        Err(StatusCode::SERVICE_UNAVAILABLE)
+        })));
+    }
+
+    let until = params.until.unwrap_or_else(chrono::Utc::now);
+    let since = params.since.unwrap_or_else(|| {
+        until - chrono::Duration::seconds(crate::constants::METRICS_RAW_RETENTION_SECS as i64)
+    });
+    if until < since {
+        return Err(StatusCode::BAD_REQUEST);
     }
+    let (since, until) = (
+        since.timestamp().max(0) as u64,
+        until.timestamp().max(0) as u64,
+    );
+    let resolution = params
+        .resolution
+        .unwrap_or_default()
+        .resolve(since, chrono::Utc::now().timestamp().max(0) as u64);
+    let history = monitor
+        .query_history(since, until, resolution)
+        .await
+        .map_err(|e| {
+            warn!("Failed to read metrics history: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(serde_json::json!({
+        "resolution": resolution,
+        "since": since,
+        "until": until,
+        "metrics_count": history.len(),
+        "metrics": history
+    })))
+}
+
+/// 📈 AVAILABILITY ENDPOINT: Time healthy, degraded, unhealthy and down over the last
+/// 24 hours, 7 days and 30 days, for SLA reporting
+#[utoipa::path(
+    get,
+    path = "/system/availability",
+    tag = "system",
+    responses(
+        (status = 200, description = "Availability per window", body = AvailabilityReport),
+        (status = 500, description = "The history could not be read"),
+        (status = 503, description = "Monitoring is not enabled"),
+    )
+)]
+async fn get_system_availability(
+    State(server): State<ApiServer>,
+) -> std::result::Result<Json<AvailabilityReport>, StatusCode> {
+    let Some(monitor) = &server.system_monitor else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    monitor.availability().await.map(Json).map_err(|e| {
+        warn!("Failed to compute availability: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
 }
 
 /// 🏥 SYSTEM HEALTH ENDPOINT: Overall health assessment
 /// DECISION: Simple health check with detailed status
 /// Why: Standard health check endpoint for load balalncers and monitoring
 /// Alternative: Binary healthy/unhealthy (rejected: insufficient detail)
+#[utoipa::path(
+    get,
+    path = "/system/health",
+    tag = "system",
+    responses(
+        (status = 200, description = "Component health", body = serde_json::Value),
+        (status = 503, description = "Monitoring is not enabled"),
+    )
+)]
 async fn get_system_health(
     State(server): State<ApiServer>,
 ) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
@@ -804,10 +3651,38 @@ async fn get_system_health(
     }
 }
 
+/// 💸 COST ENDPOINT: Claude Code spend per day, task and user, and the daily budget
+#[utoipa::path(
+    get,
+    path = "/system/costs",
+    tag = "system",
+    responses(
+        (status = 200, description = "Spend over the retained history", body = CostReport),
+        (status = 503, description = "Claude Code client not available"),
+    )
+)]
+async fn get_system_costs(
+    State(server): State<ApiServer>,
+) -> std::result::Result<Json<CostReport>, StatusCode> {
+    let client = server
+        .orchestrator
+        .get_claude_client()
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(client.costs().report()))
+}
+
 /// ⚡ CIRCUIT BREAKER STATUS ENDPOINT: Circuit breaker states and metrics
 /// DECISION: Dedicated endpoint for circuit breaker monitoring
 /// Why: Circuit breaker health is critical for system reliability
 /// Alternative: Include in general metrics (rejected: needs specific visibility)
+#[utoipa::path(
+    get,
+    path = "/circuit-breakers",
+    tag = "system",
+    responses(
+        (status = 200, description = "Circuit breaker states", body = serde_json::Value),
+    )
+)]
 async fn get_circuit_breaker_status(
     State(server): State<ApiServer>,
 ) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
@@ -820,11 +3695,9 @@ async fn get_circuit_breaker_status(
     } else {
         // Fallback: get directly from orchestrator's Claude client if available
         if let Ok(client) = server.orchestrator.get_claude_client() {
-            let cb_metrics = client.get_circuit_breaker_metrics().await;
+            let cb_metrics = client.circuit_breakers().metrics().await;
             Ok(Json(serde_json::json!({
-                "circuit_breakers": {
-                    "claude_code": cb_metrics
-                },
+                "circuit_breakers": cb_metrics,
                 "timestamp": std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
@@ -835,3 +3708,177 @@ async fn get_circuit_breaker_status(
         }
     }
 }
+
+/// The named backend's circuit breaker, or the error response for an unknown name
+fn find_circuit_breaker(
+    server: &ApiServer,
+    name: &str,
+) -> std::result::Result<Arc<CircuitBreaker>, (StatusCode, Json<ErrorResponse>)> {
+    let client = server.orchestrator.get_claude_client().map_err(|_| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Claude Code client not available".to_string(),
+                details: None,
+            }),
+        )
+    })?;
+    client.circuit_breakers().get(name).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Circuit breaker not found".to_string(),
+                details: Some(format!("Circuit breaker: {name}")),
+            }),
+        )
+    })
+}
+
+/// 🔧 CIRCUIT BREAKER RESET: Close a backend's circuit without waiting for recovery probes
+/// DECISION: Manual override for operators who fixed the cause (e.g. renewed a key)
+/// Why: An auth failure trips the circuit, and waiting out the open duration after the
+/// fix only delays tasks
+#[utoipa::path(
+    post,
+    path = "/circuit-breakers/{name}/reset",
+    tag = "system",
+    params(("name" = String, Path, description = "Breaker name: claude_code, openai or ollama")),
+    responses(
+        (status = 200, description = "Circuit closed", body = CircuitBreakerMetrics),
+        (status = 404, description = "No breaker with this name", body = ErrorResponse),
+        (status = 503, description = "Claude Code client not available", body = ErrorResponse),
+    )
+)]
+async fn reset_circuit_breaker(
+    State(server): State<ApiServer>,
+    Path(name): Path<String>,
+) -> std::result::Result<Json<CircuitBreakerMetrics>, (StatusCode, Json<ErrorResponse>)> {
+    let breaker = find_circuit_breaker(&server, &name)?;
+    breaker.reset().await;
+    info!("Circuit breaker '{}' reset via API", name);
+    Ok(Json(breaker.get_metrics().await))
+}
+
+/// 🔧 CIRCUIT BREAKER TRIP: Open a backend's circuit, e.g. ahead of planned maintenance
+/// Requests fail fast (or go to a fallback backend) until the open duration has passed
+#[utoipa::path(
+    post,
+    path = "/circuit-breakers/{name}/trip",
+    tag = "system",
+    params(("name" = String, Path, description = "Breaker name: claude_code, openai or ollama")),
+    responses(
+        (status = 200, description = "Circuit opened", body = CircuitBreakerMetrics),
+        (status = 404, description = "No breaker with this name", body = ErrorResponse),
+        (status = 503, description = "Claude Code client not available", body = ErrorResponse),
+    )
+)]
+async fn trip_circuit_breaker(
+    State(server): State<ApiServer>,
+    Path(name): Path<String>,
+) -> std::result::Result<Json<CircuitBreakerMetrics>, (StatusCode, Json<ErrorResponse>)> {
+    let breaker = find_circuit_breaker(&server, &name)?;
+    breaker.trip().await;
+    warn!("Circuit breaker '{}' tripped via API", name);
+    Ok(Json(breaker.get_metrics().await))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude_code::sessions::THREAD_CONTEXT_KEY;
+    use crate::config::Config;
+
+    #[tokio::test]
+    async fn test_memory_scopes_come_from_the_api_key() {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let server = ApiServer::new(config, orchestrator).unwrap();
+        let operator = ApiKeyIdentity {
+            key_id: Some("ci".to_string()),
+            name: "ci".to_string(),
+            scopes: vec![ApiKeyScope::Submit],
+        };
+        let request = || CreateTaskRequest {
+            agent_type: Some(AgentType::SoftwareDeveloper),
+            content: "Add a health check endpoint".to_string(),
+            priority: None,
+            context: Some(HashMap::from([
+                ("user_id".to_string(), "someone-else".to_string()),
+                ("workspace".to_string(), "/projects/api".to_string()),
+                ("delegation_depth".to_string(), "4".to_string()),
+                ("delegated_by".to_string(), "task-1".to_string()),
+            ])),
+            max_retries: None,
+            schedule: None,
+            callback_url: None,
+        };
+
+        let task = build_task(&server, request(), Some(&operator))
+            .await
+            .unwrap();
+        assert_eq!(
+            MemoryScope::for_task(&task),
+            vec![MemoryScope::User("api-key:ci".to_string())]
+        );
+        let task = build_task(&server, request(), Some(&ApiKeyIdentity::master()))
+            .await
+            .unwrap();
+        assert_eq!(
+            MemoryScope::for_task(&task),
+            vec![
+                MemoryScope::User("someone-else".to_string()),
+                MemoryScope::Workspace("/projects/api".to_string())
+            ]
+        );
+        // Delegation lineage is the orchestrator's, even for admin keys
+        assert!(DELEGATION_CONTEXT_KEYS
+            .iter()
+            .all(|key| !task.context.contains_key(*key)));
+
+        let own = MemoryScope::User("api-key:ci".to_string());
+        let other = MemoryScope::User("api-key:other".to_string());
+        assert!(owns_memory_scope(Some(&operator), &own));
+        assert!(!owns_memory_scope(Some(&operator), &other));
+        assert!(!owns_memory_scope(
+            Some(&operator),
+            &MemoryScope::Guild("7".to_string())
+        ));
+        assert!(owns_memory_scope(Some(&ApiKeyIdentity::master()), &other));
+    }
+
+    #[tokio::test]
+    async fn test_shared_session_workspace_is_kept_while_in_use() {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let server = ApiServer::new(config, orchestrator.clone()).unwrap();
+        let thread_id = uuid::Uuid::new_v4().simple().to_string();
+        let workspace_id = format!("{SESSION_PREFIX}thread-{thread_id}");
+        let path = workspaces_base_dir(WORKSPACES_DIR)
+            .unwrap()
+            .join(&workspace_id);
+        std::fs::create_dir_all(&path).unwrap();
+
+        // A run holds the session lock while Claude works in the workspace
+        let client = orchestrator.get_claude_client().unwrap();
+        let run = client
+            .try_lock_session(&format!("thread-{thread_id}"))
+            .unwrap();
+        let deleted = delete_workspace(State(server.clone()), Path(workspace_id.clone())).await;
+        assert!(matches!(deleted, Err((StatusCode::CONFLICT, _))));
+        drop(run);
+
+        // A task queued in the thread will work in it too
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Follow up on the thread".to_string(),
+            Priority::Medium,
+        )
+        .with_context(THREAD_CONTEXT_KEY.to_string(), thread_id);
+        orchestrator.submit_task(task).await.unwrap();
+        let archived = archive_workspace(State(server.clone()), Path(workspace_id.clone())).await;
+        assert!(matches!(archived, Err((StatusCode::CONFLICT, _))));
+        assert!(path.is_dir());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+use axum::response::{Html, Json};
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+/// 📖 OPENAPI DOCUMENT: Generated from the handler annotations and API types
+/// 🏗️ ARCHITECTURE DECISION: Derive the spec from the code instead of maintaining it by hand
+/// Why: Request and response types change with most features, and a hand-written spec
+/// drifts the first time someone forgets to update it
+/// Alternative: Static openapi.yaml (rejected: nothing checks it against the handlers)
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Spiral Core API",
+        description = "Agent orchestration API. Every endpoint requires an API key."
+    ),
+    servers((url = "/v1", description = "Current API version")),
+    paths(
+        super::health_check,
+        super::create_task,
+        super::create_task_batch,
+        super::get_task_batch_status,
+        super::get_task_status,
+        super::cancel_task,
+        super::get_task_events,
+        super::get_task_progress,
+        super::get_task_result,
+        super::stream_task_updates,
+        super::get_task_webhook,
+        super::get_task_transcript,
+        super::analyze_task,
+        super::analyze_content,
+        super::get_all_agent_statuses,
+        super::get_agent_status,
+        super::get_agent_task_history,
+        super::get_agent_memory,
+        super::store_agent_memory,
+        super::forget_memory,
+        super::get_system_status,
+        super::get_system_metrics,
+        super::request_metrics::prometheus_metrics,
+        super::get_metrics_history,
+        super::get_system_health,
+        super::get_system_availability,
+        super::pause_dispatch,
+        super::resume_dispatch,
+        super::drain_dispatch,
+        super::get_system_costs,
+        super::get_circuit_breaker_status,
+        super::reset_circuit_breaker,
+        super::trip_circuit_breaker,
+        super::get_all_workspaces_status,
+        super::delete_workspace,
+        super::archive_workspace,
+        super::get_workspace_files,
+        super::get_schedules,
+        super::sessions::create_session,
+        super::sessions::get_session,
+        super::sessions::get_session_tree,
+        super::sessions::clear_session_history,
+        super::sessions::end_session,
+        super::get_queue,
+        super::promote_queued_task,
+        super::demote_queued_task,
+        super::get_workflows,
+        super::run_workflow,
+        super::get_workflow_run,
+        super::create_api_key,
+        super::list_api_keys,
+        super::revoke_api_key,
+        super::get_audit_log,
+        super::get_log_level,
+        super::set_log_level,
+        super::get_adaptive_rate_limits,
+        super::set_adaptive_rate_limits,
+        super::list_rate_limit_tiers,
+        super::set_rate_limit_tier,
+    ),
+    modifiers(&SecurityAddon),
+    security(("api_key" = []), ("bearer" = [])),
+    tags(
+        (name = "tasks", description = "Submit, track and cancel tasks"),
+        (name = "agents", description = "Agent status"),
+        (name = "memory", description = "Long-term agent memory"),
+        (name = "queue", description = "Queue order and scheduled tasks"),
+        (name = "sessions", description = "Sessions that share a workspace and agent across tasks"),
+        (name = "workflows", description = "Multi-agent workflows"),
+        (name = "workspaces", description = "Agent workspaces on disk"),
+        (name = "system", description = "Health, metrics and dispatch control"),
+        (name = "admin", description = "Scoped API key management and the audit log"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Both header formats the auth middleware accepts
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("x-api-key"))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// 📖 OPENAPI SPEC: The generated document, authenticated like every other route
+pub(super) async fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// 📖 SWAGGER UI: Static page that loads the spec with the key the user enters
+pub(super) async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_PAGE)
+}
+
+// The key is kept in sessionStorage so it is gone when the tab closes
+const SWAGGER_UI_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Spiral Core API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    let apiKey = sessionStorage.getItem("spiral-api-key");
+    if (!apiKey) {
+      apiKey = window.prompt("API key") || "";
+      sessionStorage.setItem("spiral-api-key", apiKey);
+    }
+    window.ui = SwaggerUIBundle({
+      url: "/v1/openapi.json",
+      dom_id: "#swagger-ui",
+      requestInterceptor: (request) => {
+        request.headers["x-api-key"] = apiKey;
+        return request;
+      },
+    });
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_routes_and_auth() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        for path in [
+            "/tasks",
+            "/tasks/{task_id}",
+            "/tasks/{task_id}/result",
+            "/agents/{agent_type}/memory",
+            "/workflows/{name}/run",
+        ] {
+            assert!(spec["paths"][path].is_object(), "Missing path {path}");
+        }
+        assert!(spec["paths"]["/tasks/{task_id}"]["delete"].is_object());
+        assert_eq!(spec["servers"][0]["url"], "/v1");
+        assert_eq!(
+            spec["components"]["securitySchemes"]["api_key"]["name"],
+            "x-api-key"
+        );
+        for schema in ["CreateTaskRequest", "TaskResultResponse", "ErrorResponse"] {
+            assert!(
+                spec["components"]["schemas"][schema].is_object(),
+                "Missing schema {schema}"
+            );
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::ApiServer;
+use crate::monitoring::requests::UNMATCHED_ROUTE;
+use crate::monitoring::RequestMetrics;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Instant;
+
+/// Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 📈 REQUEST METRICS: Time every request and count it under its route template
+/// Layered outside auth and rate limiting, so rejected requests are counted too
+pub(super) async fn record_request(
+    State(metrics): State<RequestMetrics>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_ROUTE.to_string(), |path| {
+            path.as_str().to_string()
+        });
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics.record(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// 📊 PROMETHEUS ENDPOINT: Request counters and latency histograms, plus the monitor's
+/// latest gauges when monitoring is enabled
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "system",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+    )
+)]
+pub(super) async fn prometheus_metrics(State(server): State<ApiServer>) -> Response {
+    let mut body = String::new();
+    server.request_metrics.render_prometheus(&mut body);
+    if let Some(monitor) = &server.system_monitor {
+        monitor
+            .get_current_metrics()
+            .await
+            .render_prometheus(&mut body);
+    }
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{agents::AgentOrchestrator, api::ApiServer, config::Config};
+    use axum::{body::Body, extract::ConnectInfo, extract::Request, Router};
+    use std::{net::SocketAddr, sync::Arc};
+    use tower::ServiceExt;
+
+    async fn get(router: &Router, api_key: &str, uri: &str) -> (u16, String) {
+        let mut request = Request::get(uri)
+            .header("x-api-key", api_key)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status().as_u16();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_recorded_by_route_template() {
+        let config = Config::test_config();
+        let api_key = config.api.api_key.clone().unwrap();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config, orchestrator).unwrap().build_router();
+
+        get(&router, &api_key, "/v1/tasks/missing-1").await;
+        get(&router, &api_key, "/v1/tasks/missing-2").await;
+        get(&router, "wrong-key", "/v1/agents").await;
+        get(&router, &api_key, "/no/such/path").await;
+
+        let (status, body) = get(&router, &api_key, "/v1/metrics").await;
+        assert_eq!(status, 200);
+        assert!(body.contains(
+            "spiral_core_requests_total{method=\"GET\",route=\"/v1/tasks/{task_id}\",status=\"404\"} 2"
+        ));
+        assert!(body.contains("route=\"/v1/agents\",status=\"401\"} 1"));
+        assert!(body.contains("route=\"unmatched\",status=\"404\"} 1"));
+        assert!(body.contains("spiral_core_request_duration_seconds_count{method=\"GET\",route=\"/v1/tasks/{task_id}\"} 2"));
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+use tokio::io::AsyncReadExt;
+use utoipa::ToSchema;
+
+/// A file an agent created or modified, as returned with a task result
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResultFile {
+    /// Path relative to the task's workspace, as the agent reported it
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Omitted for unreadable or binary files and once the response budget is spent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The file is larger than the content returned
+    pub truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ResultFile {
+    fn unavailable(path: &str, error: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            size_bytes: None,
+            content: None,
+            truncated: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Read the files of a task result from its workspace
+/// 🛡️ SECURITY: Only relative paths that resolve inside the workspace are read, so a
+/// result cannot be used to fetch arbitrary files, including through symlinks
+/// Each file is cut at `max_file_bytes`; files past `max_total_bytes` are listed without content
+pub async fn read_result_files(
+    workspace: &Path,
+    paths: &[String],
+    max_file_bytes: usize,
+    max_total_bytes: usize,
+) -> Vec<ResultFile> {
+    let workspace = match tokio::fs::canonicalize(workspace).await {
+        Ok(workspace) => workspace,
+        Err(_) => {
+            return paths
+                .iter()
+                .map(|path| ResultFile::unavailable(path, "Workspace no longer exists"))
+                .collect()
+        }
+    };
+
+    let mut remaining = max_total_bytes;
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let file = read_result_file(&workspace, path, max_file_bytes.min(remaining)).await;
+        if let Some(content) = &file.content {
+            remaining -= content.len();
+        }
+        files.push(file);
+    }
+    files
+}
+
+async fn read_result_file(workspace: &Path, path: &str, max_bytes: usize) -> ResultFile {
+    let relative = Path::new(path);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return ResultFile::unavailable(path, "Path is outside the workspace");
+    }
+
+    let resolved = match tokio::fs::canonicalize(workspace.join(relative)).await {
+        Ok(resolved) if resolved.starts_with(workspace) => resolved,
+        Ok(_) => return ResultFile::unavailable(path, "Path is outside the workspace"),
+        Err(_) => return ResultFile::unavailable(path, "File not found"),
+    };
+    let file = match tokio::fs::File::open(&resolved).await {
+        Ok(file) => file,
+        Err(e) => return ResultFile::unavailable(path, format!("Failed to read file: {e}")),
+    };
+    let size = match file.metadata().await {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        Ok(_) => return ResultFile::unavailable(path, "Not a file"),
+        Err(e) => return ResultFile::unavailable(path, format!("Failed to read file: {e}")),
+    };
+
+    let size_bytes = Some(size);
+    if max_bytes == 0 {
+        return ResultFile {
+            path: path.to_string(),
+            size_bytes,
+            content: None,
+            truncated: true,
+            error: None,
+        };
+    }
+
+    // ⚡ PERFORMANCE: One byte past the cap is enough to tell the file was cut, so a large
+    // artifact is never read whole
+    let mut bytes = Vec::with_capacity(size.min(max_bytes as u64 + 1) as usize);
+    if let Err(e) = file
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut bytes)
+        .await
+    {
+        return ResultFile::unavailable(path, format!("Failed to read file: {e}"));
+    }
+
+    let truncated = bytes.len() > max_bytes;
+    let kept = &bytes[..bytes.len().min(max_bytes)];
+    // A cut can split a multi-byte character, so only the tail may be invalid UTF-8
+    let content = match std::str::from_utf8(kept) {
+        Ok(text) => Some(text),
+        Err(e) if truncated && e.error_len().is_none() => {
+            std::str::from_utf8(&kept[..e.valid_up_to()]).ok()
+        }
+        Err(_) => None,
+    };
+
+    match content {
+        Some(text) if !text.contains('\0') => ResultFile {
+            path: path.to_string(),
+            size_bytes,
+            content: Some(text.to_string()),
+            truncated,
+            error: None,
+        },
+        _ => ResultFile {
+            path: path.to_string(),
+            size_bytes,
+            content: None,
+            truncated: false,
+            error: Some("Binary file".to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_files_within_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.path().join("big.txt"), "é".repeat(10)).unwrap();
+
+        let paths = vec!["src/main.rs".to_string(), "big.txt".to_string()];
+        let files = read_result_files(dir.path(), &paths, 15, 1024).await;
+        assert_eq!(files[0].content.as_deref(), Some("fn main() {}"));
+        assert!(!files[0].truncated);
+        // 15 bytes would split the eighth two-byte character
+        assert_eq!(files[1].content.as_deref(), Some("é".repeat(7).as_str()));
+        assert_eq!(files[1].size_bytes, Some(20));
+        assert!(files[1].truncated);
+
+        let files = read_result_files(dir.path(), &paths, 1024, 12).await;
+        assert!(files[0].content.is_some());
+        assert!(files[1].content.is_none() && files[1].truncated);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_paths_outside_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir(&workspace).unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "secret").unwrap();
+
+        let secret = dir.path().join("secret.txt").to_string_lossy().to_string();
+        let paths = vec![
+            "../secret.txt".to_string(),
+            secret,
+            "missing.rs".to_string(),
+        ];
+        let files = read_result_files(&workspace, &paths, 1024, 1024).await;
+        assert!(files.iter().all(|file| file.content.is_none()));
+        assert_eq!(
+            files[0].error.as_deref(),
+            Some("Path is outside the workspace")
+        );
+        assert_eq!(
+            files[1].error.as_deref(),
+            Some("Path is outside the workspace")
+        );
+        assert_eq!(files[2].error.as_deref(), Some("File not found"));
+    }
+}
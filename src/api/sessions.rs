@@ -0,0 +1,585 @@
+//! Sessions for API clients
+//!
+//! A client opens a session with `POST /sessions`, optionally naming a workspace and an
+//! agent, and sends its ID in the `X-Session-Id` header of `POST /tasks`. Tasks in a
+//! session share its workspace, go to its agent when they name none, and keep it alive;
+//! the agent each task runs with becomes the session's agent. Each finished task's request
+//! and answer are remembered, redacted, for the session's later tasks until the client
+//! clears them. Sessions are private to the API key that opened them.
+
+use super::{ApiServer, ErrorResponse, ERROR_INTERNAL_SERVER};
+use crate::auth::ApiKeyIdentity;
+use crate::claude_code::sessions::{is_valid_shared_session_key, MANAGED_SESSION_KEY_PREFIX};
+use crate::models::AgentType;
+use crate::session::{
+    agent_sessions::{collaboration_tree, CollaborationTree},
+    RemainingBudget, Session, SessionKind, AGENT_METADATA_KEY, WORKSPACE_METADATA_KEY,
+};
+use crate::SpiralError;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Header naming the session a request belongs to
+pub(super) const SESSION_ID_HEADER: &str = "x-session-id";
+
+type ApiError = (StatusCode, Json<ErrorResponse>);
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CreateSessionRequest {
+    /// Workspace the session's tasks share; omit to use the session's own
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Agent for the session's tasks that name none; omit to route the first by capability
+    #[serde(default)]
+    pub agent_type: Option<AgentType>,
+    /// Open a long-lived session held to the remember-me idle timeout and lifetime
+    #[serde(default)]
+    pub remember_me: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SessionResponse {
+    pub session_id: String,
+    /// Active, Suspended, Expired or Terminated
+    pub state: String,
+    pub created_at: String,
+    /// Each task in the session pushes this back
+    pub expires_at: String,
+    pub workspace: Option<String>,
+    pub agent_type: Option<AgentType>,
+    /// Tasks, spend and workspace MB left before the session's tasks are refused
+    pub remaining_budget: RemainingBudget,
+    /// Requests and answers remembered for the session's later tasks
+    pub history_turns: usize,
+    pub remember_me: bool,
+}
+
+impl From<&Session> for SessionResponse {
+    fn from(session: &Session) -> Self {
+        Self {
+            session_id: session.id.to_string(),
+            state: format!("{:?}", session.state),
+            created_at: session.created_at.to_rfc3339(),
+            expires_at: session.expires_at.to_rfc3339(),
+            workspace: session.workspace().map(str::to_string),
+            agent_type: session.agent(),
+            remaining_budget: session.remaining_budget(),
+            history_turns: session.history.len(),
+            remember_me: session.kind == SessionKind::RememberMe,
+        }
+    }
+}
+
+/// The session owner behind an API key; the master key's sessions are its own
+pub(super) fn owner(identity: Option<&ApiKeyIdentity>) -> String {
+    let key = identity
+        .and_then(|identity| identity.key_id.as_deref())
+        .unwrap_or("master");
+    format!("api-key:{key}")
+}
+
+fn not_found(details: String) -> ApiError {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: "Session not found".to_string(),
+            details: Some(details),
+        }),
+    )
+}
+
+fn storage_error(e: SpiralError) -> ApiError {
+    warn!("Session store failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: ERROR_INTERNAL_SERVER.to_string(),
+            details: None, // SECURITY: Store errors can name hosts and paths
+        }),
+    )
+}
+
+/// The caller's session by ID; another key's session is reported as missing
+async fn owned_session(
+    api_server: &ApiServer,
+    session_id: &str,
+    identity: Option<&ApiKeyIdentity>,
+) -> std::result::Result<Session, ApiError> {
+    let missing = || not_found(format!("Session ID: {session_id}"));
+    let id = Uuid::parse_str(session_id).map_err(|_| missing())?;
+    match api_server.sessions.get_session(&id).await {
+        Ok(Some(session)) if session.user_id == owner(identity) => Ok(session),
+        Ok(_) => Err(missing()),
+        Err(e) => Err(storage_error(e)),
+    }
+}
+
+/// 🗂️ SESSION HEADER: The session named by X-Session-Id, validated and extended; None
+/// when the request carries no session
+pub(super) async fn session_from_headers(
+    api_server: &ApiServer,
+    headers: &HeaderMap,
+    identity: Option<&ApiKeyIdentity>,
+) -> std::result::Result<Option<Session>, ApiError> {
+    let Some(value) = headers.get(SESSION_ID_HEADER) else {
+        return Ok(None);
+    };
+    let session_id = value.to_str().unwrap_or_default();
+    let session = owned_session(api_server, session_id, identity).await?;
+    match api_server.sessions.validate_session(&session.id).await {
+        Ok(session) => Ok(Some(session)),
+        Err(SpiralError::Validation(_) | SpiralError::NotFound(_)) => Err(not_found(format!(
+            "Session {session_id} has expired or ended; open a new one"
+        ))),
+        Err(e) => Err(storage_error(e)),
+    }
+}
+
+/// Make `agent_type` the session's agent, after a task in it was submitted
+pub(super) async fn record_agent(
+    api_server: &ApiServer,
+    session: &Session,
+    agent_type: &AgentType,
+) {
+    if session.agent().as_ref() == Some(agent_type) {
+        return;
+    }
+    if let Err(e) = api_server
+        .sessions
+        .set_metadata(
+            &session.id,
+            AGENT_METADATA_KEY,
+            Some(format!("{agent_type:?}")),
+        )
+        .await
+    {
+        warn!("Failed to save the agent of session {}: {}", session.id, e);
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/sessions",
+    tag = "sessions",
+    request_body = CreateSessionRequest,
+    responses(
+        (status = 201, description = "Session opened", body = SessionResponse),
+        (status = 400, description = "Invalid workspace name", body = ErrorResponse),
+        (status = 409, description = "This API key has too many open sessions", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+pub(super) async fn create_session(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Json(request): Json<CreateSessionRequest>,
+) -> std::result::Result<(StatusCode, Json<SessionResponse>), ApiError> {
+    if let Some(workspace) = &request.workspace {
+        if !is_valid_shared_session_key(workspace) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid workspace".to_string(),
+                    details: Some(
+                        "Workspace names are letters, digits, '-' and '_', at most 64 long"
+                            .to_string(),
+                    ),
+                }),
+            ));
+        }
+        // 🛡️ A session's own workspace is reached only through that session
+        if workspace.starts_with(MANAGED_SESSION_KEY_PREFIX) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid workspace".to_string(),
+                    details: Some(format!(
+                        "Workspace names cannot start with '{MANAGED_SESSION_KEY_PREFIX}'"
+                    )),
+                }),
+            ));
+        }
+    }
+
+    if let Err(e) = api_server.sessions.cleanup().await {
+        warn!("Failed to clean up expired API sessions: {}", e);
+    }
+    let kind = if request.remember_me {
+        SessionKind::RememberMe
+    } else {
+        SessionKind::Standard
+    };
+    let mut session = match api_server
+        .sessions
+        .create_session_of_kind(owner(identity.as_deref()), kind)
+        .await
+    {
+        Ok(session) => session,
+        Err(SpiralError::Validation(message)) => {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(ErrorResponse {
+                    error: "Too many open sessions".to_string(),
+                    details: Some(message),
+                }),
+            ))
+        }
+        Err(e) => return Err(storage_error(e)),
+    };
+
+    let metadata = [
+        (WORKSPACE_METADATA_KEY, request.workspace),
+        (
+            AGENT_METADATA_KEY,
+            request.agent_type.map(|agent| format!("{agent:?}")),
+        ),
+    ];
+    for (key, value) in metadata {
+        if value.is_some() {
+            session = api_server
+                .sessions
+                .set_metadata(&session.id, key, value)
+                .await
+                .map_err(storage_error)?;
+        }
+    }
+    info!("Opened API session {} for {}", session.id, session.user_id);
+    Ok((StatusCode::CREATED, Json(SessionResponse::from(&session))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}",
+    tag = "sessions",
+    params(("session_id" = String, Path, description = "Session ID")),
+    responses(
+        (status = 200, description = "The session", body = SessionResponse),
+        (status = 404, description = "No such session for this API key", body = ErrorResponse),
+    )
+)]
+pub(super) async fn get_session(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Path(session_id): Path<String>,
+) -> std::result::Result<Json<SessionResponse>, ApiError> {
+    let session = owned_session(&api_server, &session_id, identity.as_deref()).await?;
+    Ok(Json(SessionResponse::from(&session)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}/tree",
+    tag = "sessions",
+    params(("session_id" = String, Path, description = "Session ID")),
+    responses(
+        (status = 200, description = "The session and the child sessions of its workflow steps", body = CollaborationTree),
+        (status = 404, description = "No such session for this API key", body = ErrorResponse),
+    )
+)]
+pub(super) async fn get_session_tree(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Path(session_id): Path<String>,
+) -> std::result::Result<Json<CollaborationTree>, ApiError> {
+    let session = owned_session(&api_server, &session_id, identity.as_deref()).await?;
+    match collaboration_tree(&api_server.sessions, &session.id).await {
+        Ok(Some(tree)) => Ok(Json(tree)),
+        Ok(None) => Err(not_found(format!("Session ID: {session_id}"))),
+        Err(e) => Err(storage_error(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}/history",
+    tag = "sessions",
+    params(("session_id" = String, Path, description = "Session ID")),
+    responses(
+        (status = 204, description = "History cleared; later tasks start without it"),
+        (status = 404, description = "No such session for this API key", body = ErrorResponse),
+    )
+)]
+pub(super) async fn clear_session_history(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Path(session_id): Path<String>,
+) -> std::result::Result<StatusCode, ApiError> {
+    let session = owned_session(&api_server, &session_id, identity.as_deref()).await?;
+    let cleared = api_server
+        .sessions
+        .clear_history(&session.id)
+        .await
+        .map_err(storage_error)?;
+    info!("Cleared {} turn(s) of API session {}", cleared, session.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}",
+    tag = "sessions",
+    params(("session_id" = String, Path, description = "Session ID")),
+    responses(
+        (status = 204, description = "Session ended"),
+        (status = 404, description = "No such session for this API key", body = ErrorResponse),
+    )
+)]
+pub(super) async fn end_session(
+    State(api_server): State<ApiServer>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Path(session_id): Path<String>,
+) -> std::result::Result<StatusCode, ApiError> {
+    let session = owned_session(&api_server, &session_id, identity.as_deref()).await?;
+    api_server
+        .sessions
+        .terminate_session(&session.id)
+        .await
+        .map_err(storage_error)?;
+    info!("Ended API session {}", session.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agents::AgentOrchestrator, claude_code::sessions::SESSION_CONTEXT_KEY, config::Config,
+        session::USER_SESSION_CONTEXT_KEY,
+    };
+    use axum::{
+        body::Body,
+        extract::{ConnectInfo, Request},
+        http::header,
+        response::Response,
+        Router,
+    };
+    use std::{net::SocketAddr, sync::Arc};
+    use tower::ServiceExt;
+
+    fn request(
+        config: &Config,
+        method: &str,
+        uri: &str,
+        session: Option<&str>,
+        body: Body,
+    ) -> Request {
+        request_as(
+            &config.api.api_key.clone().unwrap(),
+            method,
+            uri,
+            session,
+            body,
+        )
+    }
+
+    fn request_as(
+        key: &str,
+        method: &str,
+        uri: &str,
+        session: Option<&str>,
+        body: Body,
+    ) -> Request {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-api-key", key)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(session) = session {
+            builder = builder.header(SESSION_ID_HEADER, session);
+        }
+        let mut request = builder.body(body).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        request
+    }
+
+    async fn json<T: serde::de::DeserializeOwned>(response: Response) -> T {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn submit(router: &Router, config: &Config, session: &str) -> Response {
+        let body = serde_json::json!({ "content": "Add a health check endpoint" }).to_string();
+        router
+            .clone()
+            .oneshot(request(
+                config,
+                "POST",
+                "/v1/tasks",
+                Some(session),
+                body.into(),
+            ))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tasks_in_a_session_use_its_workspace_and_agent() {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config.clone(), orchestrator.clone())
+            .unwrap()
+            .build_router();
+
+        let body = serde_json::json!({
+            "workspace": "feature-x",
+            "agent_type": "QualityAssurance",
+        })
+        .to_string();
+        let response = router
+            .clone()
+            .oneshot(request(&config, "POST", "/v1/sessions", None, body.into()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let session: SessionResponse = json(response).await;
+        assert_eq!(session.workspace.as_deref(), Some("feature-x"));
+
+        let response = submit(&router, &config, &session.session_id).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created: super::super::CreateTaskResponse = json(response).await;
+        let task = orchestrator
+            .get_task_status(&created.task_id)
+            .await
+            .unwrap();
+        assert_eq!(task.agent_type, AgentType::QualityAssurance);
+        assert_eq!(
+            task.context.get(SESSION_CONTEXT_KEY).map(String::as_str),
+            Some("feature-x")
+        );
+
+        // A batch's tasks join the session the same way
+        let body = serde_json::json!({ "tasks": [{ "content": "Add a readiness probe" }] });
+        let response = router
+            .clone()
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/tasks/batch",
+                Some(&session.session_id),
+                body.to_string().into(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let batch: super::super::CreateTaskBatchResponse = json(response).await;
+        let task = orchestrator
+            .get_task_status(&batch.task_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(task.agent_type, AgentType::QualityAssurance);
+        assert_eq!(
+            task.context.get(SESSION_CONTEXT_KEY).map(String::as_str),
+            Some("feature-x")
+        );
+
+        let uri = format!("/v1/sessions/{}", session.session_id);
+        let response = router
+            .clone()
+            .oneshot(request(&config, "DELETE", &uri, None, Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = submit(&router, &config, &session.session_id).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = submit(&router, &config, "not-a-session").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_other_keys_cannot_reach_a_session_through_task_context() {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config.clone(), orchestrator.clone())
+            .unwrap()
+            .build_router();
+
+        let response = router
+            .clone()
+            .oneshot(request(&config, "POST", "/v1/sessions", None, "{}".into()))
+            .await
+            .unwrap();
+        let session: SessionResponse = json(response).await;
+        let body = r#"{"name": "other", "scopes": ["submit", "read"]}"#;
+        let response = router
+            .clone()
+            .oneshot(request(
+                &config,
+                "POST",
+                "/v1/admin/keys",
+                None,
+                body.into(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created: serde_json::Value = json(response).await;
+        let other = created["key"].as_str().unwrap().to_string();
+        let submit_as_other = |context: serde_json::Value, session: Option<&str>| {
+            let body = serde_json::json!({
+                "content": "Add a health check endpoint",
+                "context": context,
+            })
+            .to_string();
+            router.clone().oneshot(request_as(
+                &other,
+                "POST",
+                "/v1/tasks",
+                session,
+                body.into(),
+            ))
+        };
+
+        // Naming the session in the context does not join it
+        let response = submit_as_other(
+            serde_json::json!({ USER_SESSION_CONTEXT_KEY: session.session_id }),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created: super::super::CreateTaskResponse = json(response).await;
+        let task = orchestrator
+            .get_task_status(&created.task_id)
+            .await
+            .unwrap();
+        assert!(!task.context.contains_key(USER_SESSION_CONTEXT_KEY));
+        assert!(!task.context.contains_key(SESSION_CONTEXT_KEY));
+
+        // Nor does naming its workspace, in a task or in a session of its own
+        let workspace = format!("{MANAGED_SESSION_KEY_PREFIX}{}", session.session_id);
+        let response = submit_as_other(serde_json::json!({ SESSION_CONTEXT_KEY: workspace }), None)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = serde_json::json!({ "workspace": workspace }).to_string();
+        let response = router
+            .clone()
+            .oneshot(request_as(
+                &other,
+                "POST",
+                "/v1/sessions",
+                None,
+                body.into(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // And the header names no session of this key
+        let response = submit_as_other(serde_json::json!({}), Some(&session.session_id))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,202 @@
+use super::TaskResultResponse;
+use crate::{
+    agents::{
+        orchestrator::{TaskEventUpdate, TaskProgress, TaskProgressUpdate},
+        AgentOrchestrator,
+    },
+    models::{TaskResult, TaskStatus},
+};
+use futures::stream::{self, Stream};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use utoipa::ToSchema;
+
+/// One message on a task's update stream
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum TaskStreamItem {
+    /// Where the task stands when the stream opens
+    Status {
+        task_id: String,
+        status: TaskStatus,
+        progress: Option<TaskProgress>,
+    },
+    Lifecycle(TaskEventUpdate),
+    Progress(TaskProgressUpdate),
+    /// The final result; the stream ends after it
+    Result(TaskResultResponse),
+}
+
+impl TaskStreamItem {
+    /// SSE event name, so clients can listen per kind
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Status { .. } => "status",
+            Self::Lifecycle(_) => "lifecycle",
+            Self::Progress(_) => "progress",
+            Self::Result(_) => "result",
+        }
+    }
+}
+
+/// Updates for one task, starting with its current status and ending with its result
+/// Returns None if the task is unknown
+/// 🏗️ ARCHITECTURE DECISION: Filter the orchestrator-wide broadcasts per stream
+/// Why: Streams are few and short-lived, so per-task channels would be bookkeeping
+/// for every task to serve the handful that are watched
+/// Alternative: Poll task status on an interval (rejected: the latency and lock
+/// contention streaming is meant to remove)
+pub async fn task_update_stream(
+    orchestrator: Arc<AgentOrchestrator>,
+    task_id: String,
+) -> Option<impl Stream<Item = TaskStreamItem>> {
+    // Subscribe before taking the snapshot so nothing between the two is missed
+    let events = orchestrator.subscribe_task_events();
+    let progress = orchestrator.subscribe_task_progress();
+    let results = orchestrator.subscribe_results();
+
+    let task = orchestrator.get_task_status(&task_id).await?;
+    let mut pending = VecDeque::from([TaskStreamItem::Status {
+        task_id: task_id.clone(),
+        status: task.status.clone(),
+        progress: orchestrator.get_task_progress(&task_id).await,
+    }]);
+    let finished = matches!(
+        task.status,
+        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+    );
+    if finished {
+        if let Some(result) = orchestrator.get_task_result(&task_id).await {
+            pending.push_back(TaskStreamItem::Result(result.into()));
+        }
+    }
+
+    let state = TaskStream {
+        orchestrator,
+        task_id,
+        events,
+        progress,
+        results,
+        pending,
+        finished,
+    };
+    Some(stream::unfold(state, |mut state| async move {
+        state.next().await.map(|item| (item, state))
+    }))
+}
+
+struct TaskStream {
+    orchestrator: Arc<AgentOrchestrator>,
+    task_id: String,
+    events: Receiver<TaskEventUpdate>,
+    progress: Receiver<TaskProgressUpdate>,
+    results: Receiver<TaskResult>,
+    pending: VecDeque<TaskStreamItem>,
+    finished: bool,
+}
+
+impl TaskStream {
+    async fn next(&mut self) -> Option<TaskStreamItem> {
+        if let Some(item) = self.pending.pop_front() {
+            return Some(item);
+        }
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            // Biased: events and progress are sent before the result, so draining them
+            // first keeps the final "completed" updates ahead of the result that ends the stream
+            tokio::select! {
+                biased;
+                update = self.events.recv() => match update {
+                    Ok(update) if update.task_id == self.task_id => {
+                        return Some(TaskStreamItem::Lifecycle(update));
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return None,
+                },
+                update = self.progress.recv() => match update {
+                    Ok(update) if update.task_id == self.task_id => {
+                        return Some(TaskStreamItem::Progress(update));
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return None,
+                },
+                result = self.results.recv() => match result {
+                    Ok(result) if result.task_id == self.task_id => {
+                        self.finished = true;
+                        return Some(TaskStreamItem::Result(result.into()));
+                    }
+                    Ok(_) => {}
+                    // The result may have been among the skipped ones
+                    Err(RecvError::Lagged(_)) => {
+                        if let Some(result) = self.orchestrator.get_task_result(&self.task_id).await {
+                            self.finished = true;
+                            return Some(TaskStreamItem::Result(result.into()));
+                        }
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        models::{AgentType, Priority, Task},
+    };
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_stream_follows_task_to_its_result() {
+        let orchestrator = Arc::new(AgentOrchestrator::new(Config::test_config()).await.unwrap());
+        let task_id = orchestrator
+            .submit_task(Task::new(
+                AgentType::ProjectManager,
+                "Plan the release".to_string(),
+                Priority::High,
+            ))
+            .await
+            .unwrap();
+        assert!(
+            task_update_stream(orchestrator.clone(), "missing".to_string())
+                .await
+                .is_none()
+        );
+
+        let updates = task_update_stream(orchestrator.clone(), task_id.clone())
+            .await
+            .unwrap();
+        let runner = orchestrator.clone();
+        tokio::spawn(async move { runner.run().await });
+
+        let items: Vec<TaskStreamItem> =
+            tokio::time::timeout(Duration::from_secs(30), updates.collect())
+                .await
+                .expect("Stream should end once the task finishes");
+        orchestrator.shutdown().await;
+
+        let names: Vec<&str> = items.iter().map(TaskStreamItem::event_name).collect();
+        assert_eq!(names.first(), Some(&"status"));
+        assert_eq!(names.last(), Some(&"result"));
+        assert!(names.contains(&"lifecycle"));
+        assert!(names.contains(&"progress"));
+
+        // A stream opened after the task finished replays the result and ends
+        let replay: Vec<TaskStreamItem> = task_update_stream(orchestrator, task_id)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let names: Vec<&str> = replay.iter().map(TaskStreamItem::event_name).collect();
+        assert_eq!(names, vec!["status", "result"]);
+    }
+}
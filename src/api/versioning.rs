@@ -0,0 +1,184 @@
+use super::ErrorResponse;
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{Json, Response},
+};
+
+/// Prefix of the current route tree
+pub const API_V1_PREFIX: &str = "/v1";
+
+const CURRENT_API_VERSION: &str = "v1";
+const SUPPORTED_API_VERSIONS: &[&str] = &[CURRENT_API_VERSION];
+
+/// 2026-10-16T00:00:00Z, when the unversioned paths became aliases of /v1
+const LEGACY_ROUTES_DEPRECATED_AT: i64 = 1_792_108_800;
+
+const API_VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+
+/// Path with the current version prefix removed, for policies that match on routes
+pub fn unversioned_path(path: &str) -> &str {
+    match path.strip_prefix(API_V1_PREFIX) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
+
+/// Version named by the first path segment, e.g. "v2" for /v2/tasks
+fn requested_version(path: &str) -> Option<&str> {
+    let segment = path.trim_start_matches('/').split('/').next()?;
+    let digits = segment.strip_prefix('v')?;
+    (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())).then_some(segment)
+}
+
+/// Tag responses from the versioned tree with the version that served them
+pub(super) async fn current_version(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(
+        API_VERSION_HEADER,
+        HeaderValue::from_static(CURRENT_API_VERSION),
+    );
+    response
+}
+
+/// 📢 DEPRECATION: Legacy unversioned paths answer as /v1 and say where to move
+/// Headers follow RFC 9745 (Deprecation) and RFC 8594 (successor-version link)
+pub(super) async fn deprecated_alias(request: Request, next: Next) -> Response {
+    let successor = format!(
+        "<{API_V1_PREFIX}{}>; rel=\"successor-version\"",
+        request.uri().path()
+    );
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(
+        API_VERSION_HEADER,
+        HeaderValue::from_static(CURRENT_API_VERSION),
+    );
+    headers.insert(
+        DEPRECATION_HEADER,
+        HeaderValue::from_str(&format!("@{LEGACY_ROUTES_DEPRECATED_AT}"))
+            .expect("numeric header value is valid"),
+    );
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(axum::http::header::LINK, link);
+    }
+    response
+}
+
+/// Unmatched paths, telling callers of unknown versions which ones exist
+pub(super) async fn unknown_route(uri: Uri) -> (StatusCode, Json<ErrorResponse>) {
+    let error = match requested_version(uri.path()) {
+        Some(version) if !SUPPORTED_API_VERSIONS.contains(&version) => ErrorResponse {
+            error: "Unsupported API version".to_string(),
+            details: Some(format!(
+                "Requested {version}; supported versions: {}",
+                SUPPORTED_API_VERSIONS.join(", ")
+            )),
+        },
+        _ => ErrorResponse {
+            error: "Not found".to_string(),
+            details: Some(format!("Path: {}", uri.path())),
+        },
+    };
+    (StatusCode::NOT_FOUND, Json(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::AgentOrchestrator, api::ApiServer, config::Config};
+    use axum::{body::Body, extract::ConnectInfo, Router};
+    use std::{net::SocketAddr, sync::Arc};
+    use tower::ServiceExt;
+
+    async fn router() -> (Router, String) {
+        let config = Config::test_config();
+        let api_key = config.api.api_key.clone().unwrap();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let server = ApiServer::new(config, orchestrator).unwrap();
+        (server.build_router(), api_key)
+    }
+
+    async fn get(router: &Router, api_key: Option<&str>, uri: &str) -> Response {
+        let mut request = Request::get(uri);
+        if let Some(key) = api_key {
+            request = request.header("x-api-key", key);
+        }
+        let mut request = request.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        router.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn error_message(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        error.error
+    }
+
+    #[test]
+    fn test_version_prefix_parsing() {
+        assert_eq!(unversioned_path("/v1/tasks"), "/tasks");
+        assert_eq!(unversioned_path("/v1"), "");
+        assert_eq!(unversioned_path("/v10/tasks"), "/v10/tasks");
+        assert_eq!(unversioned_path("/tasks"), "/tasks");
+
+        assert_eq!(requested_version("/v2/tasks"), Some("v2"));
+        assert_eq!(requested_version("/v1"), Some("v1"));
+        assert_eq!(requested_version("/version"), None);
+        assert_eq!(requested_version("/tasks"), None);
+    }
+
+    #[tokio::test]
+    async fn test_versioned_routes_are_current() {
+        let (router, key) = router().await;
+
+        let response = get(&router, Some(&key), "/v1/queue").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[API_VERSION_HEADER], "v1");
+        assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+
+        // Versioned routes are authenticated like the legacy ones
+        let response = get(&router, None, "/v1/queue").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_routes_are_deprecated_aliases() {
+        let (router, key) = router().await;
+
+        let response = get(&router, Some(&key), "/queue").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[API_VERSION_HEADER], "v1");
+        assert_eq!(response.headers()[DEPRECATION_HEADER], "@1792108800");
+        assert_eq!(
+            response.headers()[axum::http::header::LINK],
+            "</v1/queue>; rel=\"successor-version\""
+        );
+
+        // Load balancer probes stay unversioned and undeprecated
+        let response = get(&router, Some(&key), "/health").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+        let response = get(&router, Some(&key), "/v1/health").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_versions_are_rejected() {
+        let (router, key) = router().await;
+
+        let response = get(&router, Some(&key), "/v2/queue").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error_message(response).await, "Unsupported API version");
+
+        let response = get(&router, Some(&key), "/v1/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error_message(response).await, "Not found");
+    }
+}
@@ -0,0 +1,555 @@
+use super::{
+    build_task, queue_submitter, register_callback, AgentStatusResponse, ApiServer,
+    CreateTaskRequest, ErrorResponse, ERROR_INTERNAL_SERVER,
+};
+use crate::{
+    agents::orchestrator::{TaskEventKind, TaskEventUpdate, TaskProgressUpdate},
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    auth::ApiKeyIdentity,
+    monitoring::HealthStatus,
+    rate_limit::policy::{Caller, RateLimitGroup},
+    SpiralError,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, State,
+    },
+    response::Response,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, net::SocketAddr};
+use tokio::sync::{broadcast::error::RecvError, watch};
+use tracing::{debug, info, warn};
+
+/// Event streams a WebSocket client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WsTopic {
+    /// Task lifecycle events, as in GET /tasks/{task_id}/events
+    Tasks,
+    /// Agent progress reports, as in GET /tasks/{task_id}/progress
+    Progress,
+    /// Agent status after each task starts or finishes
+    Agents,
+    /// Overall system health whenever it changes
+    Health,
+}
+
+/// Messages a client sends; `request_id` is echoed back on the reply
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsCommand {
+    Subscribe {
+        topics: Vec<WsTopic>,
+    },
+    Unsubscribe {
+        topics: Vec<WsTopic>,
+    },
+    Submit {
+        #[serde(default)]
+        request_id: Option<String>,
+        task: CreateTaskRequest,
+    },
+    Cancel {
+        #[serde(default)]
+        request_id: Option<String>,
+        task_id: String,
+    },
+}
+
+/// Messages the server sends: replies to commands and events for subscribed topics
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsMessage {
+    Subscribed {
+        topics: Vec<WsTopic>,
+    },
+    TaskEvent(TaskEventUpdate),
+    TaskProgress(TaskProgressUpdate),
+    AgentStatus(AgentStatusResponse),
+    Health {
+        status: HealthStatus,
+    },
+    Submitted {
+        request_id: Option<String>,
+        task_id: String,
+    },
+    Cancelled {
+        request_id: Option<String>,
+        task_id: String,
+    },
+    Error {
+        request_id: Option<String>,
+        #[serde(flatten)]
+        error: ErrorResponse,
+        /// Seconds until a command refused by a rate limit would be let through
+        #[serde(skip_serializing_if = "Option::is_none")]
+        retry_after: Option<u64>,
+    },
+}
+
+impl WsMessage {
+    fn error(request_id: Option<String>, error: &str, details: Option<String>) -> Self {
+        Self::Error {
+            request_id,
+            error: ErrorResponse {
+                error: error.to_string(),
+                details,
+            },
+            retry_after: None,
+        }
+    }
+}
+
+/// 🔌 WEBSOCKET: One connection for live system events and task commands
+/// Authentication happens on the upgrade request like every other route
+pub(super) async fn websocket_handler(
+    State(api_server): State<ApiServer>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let identity = identity.map(|Extension(identity)| identity);
+    let caller = Caller::new(identity.as_ref(), addr.ip());
+    upgrade.on_upgrade(move |socket| run_session(api_server, socket, identity, caller))
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Each connection subscribes to the orchestrator broadcasts
+/// and filters by its own topics
+/// Why: Topic changes are local to the connection, and idle topics cost one skipped
+/// message rather than a subscription
+/// Alternative: Subscribe per topic on demand (rejected: re-subscribing loses events
+/// between the unsubscribe and the next subscribe)
+async fn run_session(
+    api_server: ApiServer,
+    mut socket: WebSocket,
+    identity: Option<ApiKeyIdentity>,
+    caller: Caller,
+) {
+    info!("WebSocket client connected");
+    let mut topics = BTreeSet::new();
+    let mut events = api_server.orchestrator.subscribe_task_events();
+    let mut progress = api_server.orchestrator.subscribe_task_progress();
+    let mut health = api_server
+        .system_monitor
+        .as_ref()
+        .map(|monitor| monitor.subscribe_health());
+
+    loop {
+        let outgoing = tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    handle_command(
+                        &api_server,
+                        &mut topics,
+                        identity.as_ref(),
+                        &caller,
+                        text.as_str(),
+                    )
+                    .await
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by the protocol layer; binary frames carry no commands
+                Some(Ok(_)) => Vec::new(),
+            },
+            update = events.recv() => match update {
+                Ok(update) => task_event_messages(&api_server, &topics, update).await,
+                Err(RecvError::Lagged(skipped)) => lagged(skipped),
+                Err(RecvError::Closed) => break,
+            },
+            update = progress.recv() => match update {
+                Ok(update) if topics.contains(&WsTopic::Progress) => {
+                    vec![WsMessage::TaskProgress(update)]
+                }
+                Ok(_) => Vec::new(),
+                Err(RecvError::Lagged(skipped)) => lagged(skipped),
+                Err(RecvError::Closed) => break,
+            },
+            status = health_transition(&mut health) => {
+                if topics.contains(&WsTopic::Health) {
+                    vec![WsMessage::Health { status }]
+                } else {
+                    Vec::new()
+                }
+            }
+        };
+
+        for message in outgoing {
+            let text = match serde_json::to_string(&message) {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!("Failed to serialize WebSocket message: {}", e);
+                    continue;
+                }
+            };
+            if socket.send(Message::Text(text.into())).await.is_err() {
+                debug!("WebSocket client went away mid-send");
+                return;
+            }
+        }
+    }
+    info!("WebSocket client disconnected");
+}
+
+/// Apply one client command and return the replies
+async fn handle_command(
+    api_server: &ApiServer,
+    topics: &mut BTreeSet<WsTopic>,
+    identity: Option<&ApiKeyIdentity>,
+    caller: &Caller,
+    text: &str,
+) -> Vec<WsMessage> {
+    let command = match serde_json::from_str::<WsCommand>(text) {
+        Ok(command) => command,
+        Err(e) => {
+            return vec![WsMessage::error(
+                None,
+                "Invalid command",
+                Some(e.to_string()),
+            )]
+        }
+    };
+
+    let reply = match command {
+        WsCommand::Subscribe { topics: added } => {
+            topics.extend(added);
+            WsMessage::Subscribed {
+                topics: topics.iter().copied().collect(),
+            }
+        }
+        WsCommand::Unsubscribe { topics: removed } => {
+            for topic in removed {
+                topics.remove(&topic);
+            }
+            WsMessage::Subscribed {
+                topics: topics.iter().copied().collect(),
+            }
+        }
+        WsCommand::Submit {
+            request_id,
+            mut task,
+        } => {
+            if let Some(refusal) = take_submission_token(api_server, identity, caller).await {
+                return vec![refusal.into_message(request_id)];
+            }
+            if task.schedule.take().is_some() {
+                return vec![WsMessage::error(
+                    request_id,
+                    "Invalid command",
+                    Some("Scheduled tasks must be created with POST /tasks".to_string()),
+                )];
+            }
+            let callback_url = task.callback_url.take();
+            let task = match build_task(api_server, task, identity).await {
+                Ok(task) => task,
+                Err((_, error)) => {
+                    return vec![WsMessage::Error {
+                        request_id,
+                        error: error.0,
+                        retry_after: None,
+                    }]
+                }
+            };
+            let task_id = task.id.clone();
+            if let Err((_, error)) =
+                register_callback(api_server, &task_id, callback_url.as_deref())
+            {
+                return vec![WsMessage::Error {
+                    request_id,
+                    error: error.0,
+                    retry_after: None,
+                }];
+            }
+            let submitted = api_server
+                .orchestrator
+                .submit_task_as(task, queue_submitter(identity))
+                .await;
+            if submitted.is_err() {
+                api_server.orchestrator.unregister_webhook(&task_id);
+            }
+            match submitted {
+                Ok(task_id) => {
+                    info!("Task {} submitted over WebSocket", task_id);
+                    WsMessage::Submitted {
+                        request_id,
+                        task_id,
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to submit task to orchestrator: {}", e);
+                    // SECURITY: Never expose internal orchestrator errors
+                    WsMessage::error(request_id, ERROR_INTERNAL_SERVER, None)
+                }
+            }
+        }
+        WsCommand::Cancel {
+            request_id,
+            task_id,
+        } => match api_server.orchestrator.cancel_task(&task_id).await {
+            Ok(_) => WsMessage::Cancelled {
+                request_id,
+                task_id,
+            },
+            Err(SpiralError::NotFound(_)) => WsMessage::error(
+                request_id,
+                "Task not found",
+                Some(format!("Task ID: {task_id}")),
+            ),
+            Err(SpiralError::Validation(message)) => {
+                WsMessage::error(request_id, "Task cannot be cancelled", Some(message))
+            }
+            Err(e) => {
+                warn!("Failed to cancel task {}: {}", task_id, e);
+                WsMessage::error(request_id, ERROR_INTERNAL_SERVER, None)
+            }
+        },
+    };
+    vec![reply]
+}
+
+/// A task submission refused by the caller's rate limit
+struct RateLimited {
+    limit: u32,
+    retry_after: u64,
+}
+
+impl RateLimited {
+    fn into_message(self, request_id: Option<String>) -> WsMessage {
+        WsMessage::Error {
+            request_id,
+            error: ErrorResponse {
+                error: "Rate limit exceeded".to_string(),
+                details: Some(format!(
+                    "{} {} requests per minute; retry in {} seconds",
+                    self.limit,
+                    RateLimitGroup::TaskSubmission.as_str(),
+                    self.retry_after
+                )),
+            },
+            retry_after: Some(self.retry_after),
+        }
+    }
+}
+
+/// 🚦 SUBMISSION LIMIT: Each submit takes a task submission token, as POST /tasks does
+/// Why: The upgrade request is counted once as a read, so without this one socket could
+/// queue tasks past the caller's limit and the degraded-mode cuts
+async fn take_submission_token(
+    api_server: &ApiServer,
+    identity: Option<&ApiKeyIdentity>,
+    caller: &Caller,
+) -> Option<RateLimited> {
+    let (limit, decision) = api_server
+        .rate_limiter
+        .check(RateLimitGroup::TaskSubmission, caller)
+        .await?;
+    if decision.allowed {
+        return None;
+    }
+    warn!(
+        "Rate limit exceeded for WebSocket submit (task_submission) from {} - command denied",
+        caller.counter()
+    );
+    let mut event = AuditEvent::new(
+        AuditEventKind::RateLimited,
+        AuditSource::Api,
+        "WebSocket submit".to_string(),
+    )
+    .with_client(caller.ip.to_string());
+    if let Some(identity) = identity {
+        event = event.with_actor(identity.name.clone());
+    }
+    audit::record(event);
+    Some(RateLimited {
+        limit,
+        retry_after: decision.retry_after_secs(),
+    })
+}
+
+/// The lifecycle event itself, plus the agent's status when the event changed it
+async fn task_event_messages(
+    api_server: &ApiServer,
+    topics: &BTreeSet<WsTopic>,
+    update: TaskEventUpdate,
+) -> Vec<WsMessage> {
+    let changes_agent = matches!(
+        update.event.kind,
+        TaskEventKind::Started
+            | TaskEventKind::Retried
+            | TaskEventKind::Completed
+            | TaskEventKind::Failed
+            | TaskEventKind::Cancelled
+            | TaskEventKind::CleanedUp
+    );
+    let agent_status = if changes_agent && topics.contains(&WsTopic::Agents) {
+        match api_server
+            .orchestrator
+            .get_task_status(&update.task_id)
+            .await
+        {
+            Some(task) => api_server
+                .orchestrator
+                .get_agent_status(&task.agent_type)
+                .await
+                .map(|status| WsMessage::AgentStatus(status.into())),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let mut messages = Vec::new();
+    if topics.contains(&WsTopic::Tasks) {
+        messages.push(WsMessage::TaskEvent(update));
+    }
+    messages.extend(agent_status);
+    messages
+}
+
+fn lagged(skipped: u64) -> Vec<WsMessage> {
+    vec![WsMessage::error(
+        None,
+        "Events dropped",
+        Some(format!(
+            "{skipped} event(s) skipped because the connection fell behind"
+        )),
+    )]
+}
+
+/// Next health status change; never resolves without a system monitor
+async fn health_transition(health: &mut Option<watch::Receiver<HealthStatus>>) -> HealthStatus {
+    if let Some(receiver) = health {
+        if receiver.changed().await.is_ok() {
+            return *receiver.borrow_and_update();
+        }
+        // The monitor was dropped, so no further transitions will come
+        *health = None;
+    }
+    std::future::pending().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::AgentOrchestrator, config::Config, models::TaskStatus};
+    use std::sync::Arc;
+
+    async fn api_server() -> ApiServer {
+        let config = Config::test_config();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        ApiServer::new(config, orchestrator).unwrap()
+    }
+
+    fn caller() -> Caller {
+        Caller::new(None, "127.0.0.1".parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_and_unsubscribe_topics() {
+        let server = api_server().await;
+        let mut topics = BTreeSet::new();
+
+        let replies = handle_command(
+            &server,
+            &mut topics,
+            None,
+            &caller(),
+            r#"{"type":"subscribe","topics":["health","tasks","agents"]}"#,
+        )
+        .await;
+        assert!(matches!(
+            &replies[..],
+            [WsMessage::Subscribed { topics }]
+                if topics == &[WsTopic::Tasks, WsTopic::Agents, WsTopic::Health]
+        ));
+
+        handle_command(
+            &server,
+            &mut topics,
+            None,
+            &caller(),
+            r#"{"type":"unsubscribe","topics":["agents"]}"#,
+        )
+        .await;
+        assert_eq!(
+            topics.into_iter().collect::<Vec<_>>(),
+            vec![WsTopic::Tasks, WsTopic::Health]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_cancel_commands() {
+        let server = api_server().await;
+        let mut topics = BTreeSet::new();
+
+        let replies = handle_command(
+            &server,
+            &mut topics,
+            None,
+            &caller(),
+            r#"{"type":"submit","request_id":"r1","task":{"agent_type":"SoftwareDeveloper","content":"Write a parser"}}"#,
+        )
+        .await;
+        let [WsMessage::Submitted {
+            request_id,
+            task_id,
+        }] = &replies[..]
+        else {
+            panic!("Expected a submitted reply, got {replies:?}");
+        };
+        assert_eq!(request_id.as_deref(), Some("r1"));
+
+        let cancel = format!(r#"{{"type":"cancel","request_id":"r2","task_id":"{task_id}"}}"#);
+        let replies = handle_command(&server, &mut topics, None, &caller(), &cancel).await;
+        assert!(matches!(&replies[..], [WsMessage::Cancelled { .. }]));
+        let task = server.orchestrator.get_task_status(task_id).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Cancelled);
+
+        // Cancelling twice reports why instead of failing silently
+        let replies = handle_command(&server, &mut topics, None, &caller(), &cancel).await;
+        assert!(matches!(
+            &replies[..],
+            [WsMessage::Error { request_id: Some(id), error, .. }]
+                if id == "r2" && error.error == "Task cannot be cancelled"
+        ));
+
+        let replies = handle_command(&server, &mut topics, None, &caller(), "not json").await;
+        assert!(matches!(
+            &replies[..],
+            [WsMessage::Error {
+                request_id: None,
+                ..
+            }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_submit_burst_is_rate_limited() {
+        let server = api_server().await;
+        let mut topics = BTreeSet::new();
+        let submit = r#"{"type":"submit","request_id":"r1","task":{"agent_type":"SoftwareDeveloper","content":"Write a parser"}}"#;
+
+        for _ in 0..crate::rate_limit::TASK_REQUESTS_PER_MINUTE {
+            let replies = handle_command(&server, &mut topics, None, &caller(), submit).await;
+            assert!(matches!(&replies[..], [WsMessage::Submitted { .. }]));
+        }
+
+        let replies = handle_command(&server, &mut topics, None, &caller(), submit).await;
+        assert!(matches!(
+            &replies[..],
+            [WsMessage::Error { request_id: Some(id), error, retry_after: Some(secs) }]
+                if id == "r1" && error.error == "Rate limit exceeded" && *secs > 0
+        ));
+        assert_eq!(
+            server.orchestrator.get_queue().await.len(),
+            crate::rate_limit::TASK_REQUESTS_PER_MINUTE as usize
+        );
+
+        // Another client keeps its own quota
+        let other = Caller::new(None, "192.0.2.7".parse().unwrap());
+        let replies = handle_command(&server, &mut topics, None, &other, submit).await;
+        assert!(matches!(&replies[..], [WsMessage::Submitted { .. }]));
+    }
+}
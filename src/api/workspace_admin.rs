@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use utoipa::ToSchema;
+
+/// A regular file inside a workspace
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkspaceFile {
+    /// Path relative to the workspace root
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified: Option<String>,
+}
+
+/// Files of one workspace, cut off after the entry limit
+#[derive(Debug, Clone)]
+pub struct WorkspaceFiles {
+    pub files: Vec<WorkspaceFile>,
+    /// Counts every file, including those past the limit
+    pub total_files: usize,
+    pub total_size_bytes: u64,
+    pub truncated: bool,
+}
+
+/// Directory of a workspace, if the ID names one directly under `base`
+/// 🛡️ SECURITY: IDs are single path segments and symlinks are refused, so admin
+/// actions cannot reach outside the workspaces directory
+pub fn resolve_workspace(base: &Path, workspace_id: &str) -> Option<PathBuf> {
+    let is_plain_name = !workspace_id.is_empty()
+        && workspace_id != "."
+        && workspace_id != ".."
+        && !workspace_id.contains(['/', '\\']);
+    if !is_plain_name {
+        return None;
+    }
+    let path = base.join(workspace_id);
+    let metadata = fs::symlink_metadata(&path).ok()?;
+    metadata.is_dir().then_some(path)
+}
+
+/// List regular files, sorted by path, without following symlinks
+pub fn list_workspace_files(workspace: &Path, max_entries: usize) -> io::Result<WorkspaceFiles> {
+    let mut files = Vec::new();
+    collect_files(workspace, workspace, &mut files)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let total_files = files.len();
+    let total_size_bytes = files.iter().map(|file| file.size_bytes).sum();
+    files.truncate(max_entries);
+    Ok(WorkspaceFiles {
+        truncated: files.len() < total_files,
+        files,
+        total_files,
+        total_size_bytes,
+    })
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<WorkspaceFile>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &entry.path(), files)?;
+        } else if file_type.is_file() {
+            let metadata = entry.metadata()?;
+            let path = entry.path();
+            files.push(WorkspaceFile {
+                path: path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string(),
+                size_bytes: metadata.len(),
+                modified: metadata.modified().ok().map(|time| {
+                    let datetime: chrono::DateTime<chrono::Utc> = time.into();
+                    datetime.to_rfc3339()
+                }),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace_with_files(base: &Path) -> PathBuf {
+        let workspace = base.join("session-abc");
+        fs::create_dir_all(workspace.join("src")).unwrap();
+        fs::write(workspace.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(workspace.join("Cargo.toml"), "[package]").unwrap();
+        workspace
+    }
+
+    #[test]
+    fn test_resolve_rejects_paths_outside_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("workspaces");
+        workspace_with_files(&base);
+        fs::write(base.join("notes.txt"), "not a workspace").unwrap();
+
+        assert!(resolve_workspace(&base, "session-abc").is_some());
+        for id in [
+            "",
+            ".",
+            "..",
+            "../workspaces",
+            "session-abc/src",
+            "notes.txt",
+            "missing",
+        ] {
+            assert!(resolve_workspace(&base, id).is_none(), "{id:?} resolved");
+        }
+    }
+
+    #[test]
+    fn test_listing_is_sorted_and_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace_with_files(dir.path());
+
+        let listing = list_workspace_files(&workspace, 10).unwrap();
+        let paths: Vec<&str> = listing.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["Cargo.toml", "src/main.rs"]);
+        assert_eq!(listing.total_size_bytes, 21);
+        assert!(!listing.truncated);
+
+        let listing = list_workspace_files(&workspace, 1).unwrap();
+        assert_eq!(listing.files.len(), 1);
+        assert_eq!(listing.total_files, 2);
+        assert!(listing.truncated);
+    }
+}
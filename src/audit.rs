@@ -0,0 +1,421 @@
+/// 📜 AUDIT LOG: Append-only record of security-relevant actions
+/// Covers auth failures, denied scopes, admin actions, self-update approvals,
+/// rate-limit triggers and blocked Discord messages
+/// 🏗️ ARCHITECTURE DECISION: One JSONL file per UTC day, behind a process-wide instance
+/// Why: Lines are only ever appended, and retention deletes whole days, so no written
+/// record is rewritten; the global instance lets middleware and Discord handlers record
+/// events without threading state through every layer
+/// Alternative: SQLite (rejected: rows can be updated in place and the file is opaque
+/// to grep/jq during an incident)
+use crate::{config::AuditConfig, Result, SpiralError};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+use tracing::warn;
+use utoipa::ToSchema;
+
+const AUDIT_FILE_PREFIX: &str = "audit-";
+const AUDIT_FILE_EXTENSION: &str = "jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// Missing or invalid credentials, or an unauthorized Discord user
+    AuthFailure,
+    /// Valid credentials without the scope the action needs
+    AccessDenied,
+    /// A state-changing admin API call or a privileged Discord command
+    AdminAction,
+    /// A self-update plan was approved, rejected or sent back for changes
+    SelfUpdateApproval,
+    /// A request or message was refused by a rate limiter
+    RateLimited,
+    /// A Discord message was blocked by security validation
+    BlockedMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSource {
+    Api,
+    Discord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: AuditEventKind,
+    pub source: AuditSource,
+    /// What was attempted, e.g. "POST /v1/admin/keys" or "!spiral admin"
+    pub action: String,
+    /// API key name or Discord user, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Client IP or Discord channel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// Result or reason, e.g. "200 OK" or the validation issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(kind: AuditEventKind, source: AuditSource, action: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            kind,
+            source,
+            action: action.into(),
+            actor: None,
+            client: None,
+            details: None,
+        }
+    }
+
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn with_client(mut self, client: impl Into<String>) -> Self {
+        self.client = Some(client.into());
+        self
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// Filters for reading the log back; events come newest first
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub kind: Option<AuditEventKind>,
+    pub source: Option<AuditSource>,
+    pub actor: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: usize,
+}
+
+impl AuditQuery {
+    fn matches(&self, event: &AuditEvent) -> bool {
+        self.kind.is_none_or(|kind| event.kind == kind)
+            && self.source.is_none_or(|source| event.source == source)
+            && self
+                .actor
+                .as_ref()
+                .is_none_or(|actor| event.actor.as_ref() == Some(actor))
+            && self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp <= until)
+    }
+}
+
+struct OpenDay {
+    date: NaiveDate,
+    file: File,
+}
+
+pub struct AuditLog {
+    dir: PathBuf,
+    retention_days: u32,
+    current: Mutex<Option<OpenDay>>,
+    /// Last time a rate-limit event was written per client, see `record`
+    rate_limited_seen: Mutex<HashMap<String, Instant>>,
+}
+
+impl AuditLog {
+    pub fn open(dir: impl AsRef<Path>, retention_days: u32) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| {
+            SpiralError::ConfigurationError(format!(
+                "Failed to create audit log directory {}: {e}",
+                dir.display()
+            ))
+        })?;
+        let log = Self {
+            dir,
+            retention_days,
+            current: Mutex::new(None),
+            rate_limited_seen: Mutex::new(HashMap::new()),
+        };
+        log.apply_retention(Utc::now().date_naive());
+        Ok(log)
+    }
+
+    /// Append an event to today's file
+    /// ⚡ PERFORMANCE: Rate-limit events are written at most once per client per window,
+    /// so a flood of rejected requests cannot turn into a flood of disk writes
+    pub fn record(&self, event: &AuditEvent) {
+        if event.kind == AuditEventKind::RateLimited && self.recently_rate_limited(event) {
+            return;
+        }
+
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to serialize audit event: {}", e);
+                return;
+            }
+        };
+
+        let date = event.timestamp.date_naive();
+        let Ok(mut current) = self.current.lock() else {
+            warn!("Audit log lock poisoned, dropping event: {}", line);
+            return;
+        };
+        if current.as_ref().is_none_or(|open| open.date != date) {
+            match self.open_day(date) {
+                Ok(file) => *current = Some(OpenDay { date, file }),
+                Err(e) => {
+                    warn!("Failed to open audit log for {}: {}", date, e);
+                    return;
+                }
+            }
+            self.apply_retention(date);
+        }
+        if let Some(open) = current.as_mut() {
+            if let Err(e) = writeln!(open.file, "{line}") {
+                warn!("Failed to write audit event: {}", e);
+            }
+        }
+    }
+
+    /// Matching events, newest first, reading only the days the time range covers
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>> {
+        let since_date = query.since.map(|since| since.date_naive());
+        let until_date = query.until.map(|until| until.date_naive());
+        let mut days: Vec<(NaiveDate, PathBuf)> = self
+            .day_files()?
+            .into_iter()
+            .filter(|(date, _)| since_date.is_none_or(|since| *date >= since))
+            .filter(|(date, _)| until_date.is_none_or(|until| *date <= until))
+            .collect();
+        days.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+
+        let mut events = Vec::new();
+        for (_, path) in days {
+            let file = File::open(&path)
+                .map_err(|e| SpiralError::SystemError(format!("Failed to read audit log: {e}")))?;
+            let mut day: Vec<AuditEvent> = BufReader::new(file)
+                .lines()
+                .map_while(|line| line.ok())
+                .filter_map(|line| serde_json::from_str(&line).ok())
+                .filter(|event| query.matches(event))
+                .collect();
+            day.reverse();
+            events.extend(day);
+            if events.len() >= query.limit {
+                break;
+            }
+        }
+        events.truncate(query.limit);
+        Ok(events)
+    }
+
+    fn recently_rate_limited(&self, event: &AuditEvent) -> bool {
+        let key = format!(
+            "{:?}:{}:{}",
+            event.source,
+            event.client.as_deref().unwrap_or_default(),
+            event.actor.as_deref().unwrap_or_default()
+        );
+        let window = Duration::from_secs(crate::constants::AUDIT_RATE_LIMIT_WINDOW_SECS);
+        let now = Instant::now();
+        let Ok(mut seen) = self.rate_limited_seen.lock() else {
+            return false;
+        };
+        if seen.len() > crate::constants::AUDIT_RATE_LIMIT_MAX_CLIENTS {
+            seen.retain(|_, last| now.duration_since(*last) < window);
+        }
+        match seen.get(&key) {
+            Some(last) if now.duration_since(*last) < window => true,
+            _ => {
+                seen.insert(key, now);
+                false
+            }
+        }
+    }
+
+    fn open_day(&self, date: NaiveDate) -> std::io::Result<File> {
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        // 🛡️ SECURITY: Records name keys, users and IPs, so only the owner may read them
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(self.day_path(date))
+    }
+
+    fn day_path(&self, date: NaiveDate) -> PathBuf {
+        self.dir.join(format!(
+            "{AUDIT_FILE_PREFIX}{}.{AUDIT_FILE_EXTENSION}",
+            date.format("%Y-%m-%d")
+        ))
+    }
+
+    fn day_files(&self) -> Result<Vec<(NaiveDate, PathBuf)>> {
+        let entries = fs::read_dir(&self.dir).map_err(|e| {
+            SpiralError::SystemError(format!("Failed to list audit log directory: {e}"))
+        })?;
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let date = name
+                    .strip_prefix(AUDIT_FILE_PREFIX)?
+                    .strip_suffix(&format!(".{AUDIT_FILE_EXTENSION}"))?;
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                Some((date, entry.path()))
+            })
+            .collect())
+    }
+
+    /// Delete day files older than the retention period; 0 keeps everything
+    fn apply_retention(&self, today: NaiveDate) {
+        if self.retention_days == 0 {
+            return;
+        }
+        let cutoff = today - ChronoDuration::days(i64::from(self.retention_days));
+        let Ok(days) = self.day_files() else {
+            return;
+        };
+        for (date, path) in days.into_iter().filter(|(date, _)| *date < cutoff) {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove expired audit log {:?}: {}", path, e);
+            } else {
+                tracing::info!("Removed audit log for {} (past retention)", date);
+            }
+        }
+    }
+}
+
+static AUDIT_LOG: OnceLock<AuditLog> = OnceLock::new();
+
+/// Open the process-wide audit log; without a directory, events only go to tracing
+pub fn init(config: &AuditConfig) -> Result<()> {
+    let Some(dir) = &config.log_dir else {
+        return Ok(());
+    };
+    let log = AuditLog::open(dir, config.retention_days)?;
+    if AUDIT_LOG.set(log).is_err() {
+        warn!("Audit log already initialized");
+    }
+    Ok(())
+}
+
+/// The process-wide audit log, if `init` configured one
+pub fn global() -> Option<&'static AuditLog> {
+    AUDIT_LOG.get()
+}
+
+/// Record a security-relevant event: always traced, and appended when the log is open
+pub fn record(event: AuditEvent) {
+    if let Ok(json) = serde_json::to_string(&event) {
+        tracing::info!(target: "audit", "{}", json);
+    }
+    if let Some(log) = global() {
+        log.record(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: AuditEventKind, actor: &str) -> AuditEvent {
+        AuditEvent::new(kind, AuditSource::Api, "POST /v1/admin/keys")
+            .with_actor(actor)
+            .with_client("127.0.0.1")
+    }
+
+    #[test]
+    fn test_events_are_appended_and_queried_newest_first() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = AuditLog::open(dir.path(), 30).unwrap();
+
+        log.record(&event(AuditEventKind::AuthFailure, "first"));
+        log.record(&event(AuditEventKind::AdminAction, "second"));
+        log.record(&event(AuditEventKind::AuthFailure, "third"));
+
+        let all = log
+            .query(&AuditQuery {
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        let actors: Vec<_> = all.iter().filter_map(|e| e.actor.as_deref()).collect();
+        assert_eq!(actors, ["third", "second", "first"]);
+
+        let failures = log
+            .query(&AuditQuery {
+                kind: Some(AuditEventKind::AuthFailure),
+                limit: 1,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].actor.as_deref(), Some("third"));
+
+        // Reopening appends to the same day instead of replacing it
+        let reopened = AuditLog::open(dir.path(), 30).unwrap();
+        reopened.record(&event(AuditEventKind::AdminAction, "fourth"));
+        let all = reopened
+            .query(&AuditQuery {
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[test]
+    fn test_rate_limit_events_are_coalesced_per_client() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = AuditLog::open(dir.path(), 30).unwrap();
+
+        for _ in 0..50 {
+            log.record(&event(AuditEventKind::RateLimited, "flood"));
+        }
+        log.record(&event(AuditEventKind::RateLimited, "flood").with_client("10.0.0.2"));
+
+        let events = log
+            .query(&AuditQuery {
+                kind: Some(AuditEventKind::RateLimited),
+                limit: 100,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_days_past_retention_are_removed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let today = Utc::now().date_naive();
+        let old = dir.path().join(format!(
+            "audit-{}.jsonl",
+            (today - ChronoDuration::days(10)).format("%Y-%m-%d")
+        ));
+        let recent = dir.path().join(format!(
+            "audit-{}.jsonl",
+            (today - ChronoDuration::days(2)).format("%Y-%m-%d")
+        ));
+        fs::write(&old, "").unwrap();
+        fs::write(&recent, "").unwrap();
+
+        AuditLog::open(dir.path(), 7).unwrap();
+        assert!(!old.exists());
+        assert!(recent.exists());
+    }
+}
@@ -0,0 +1,418 @@
+use super::Role;
+use crate::{security, Result, SpiralError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use subtle::ConstantTimeEq;
+use tokio::sync::RwLock;
+use tracing::info;
+use utoipa::ToSchema;
+
+/// What a managed API key is allowed to do
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Submit, cancel and analyse tasks, run workflows, store memories; grants `operator`
+    Submit,
+    /// Read task, agent, queue and system state; grants `viewer`
+    Read,
+    /// Everything, including key management and dispatch/queue/workspace control; grants `admin`
+    Admin,
+}
+
+impl ApiKeyScope {
+    /// Scopes predate roles and map onto them one-to-one
+    pub fn role(self) -> Role {
+        match self {
+            ApiKeyScope::Read => Role::Viewer,
+            ApiKeyScope::Submit => Role::Operator,
+            ApiKeyScope::Admin => Role::Admin,
+        }
+    }
+}
+
+/// The caller behind an authenticated request, added to the request extensions
+#[derive(Debug, Clone)]
+pub struct ApiKeyIdentity {
+    /// None for the master key from API_KEY or .spiral-api-key
+    pub key_id: Option<String>,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl ApiKeyIdentity {
+    /// The master key keeps full access so an operator can always manage the other keys
+    pub fn master() -> Self {
+        Self {
+            key_id: None,
+            name: "master".to_string(),
+            scopes: vec![ApiKeyScope::Admin],
+        }
+    }
+
+    /// The highest role any of the key's scopes grants
+    pub fn role(&self) -> Role {
+        self.scopes
+            .iter()
+            .map(|scope| scope.role())
+            .max()
+            .unwrap_or(Role::Viewer)
+    }
+}
+
+/// A managed API key as listed by the admin endpoints; the key itself is never stored
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredApiKey {
+    #[serde(flatten)]
+    info: ApiKeyInfo,
+    /// Hex SHA-256 of the key
+    key_hash: String,
+}
+
+/// 🔑 API KEY STORE: Named, scoped keys alongside the single master key
+/// 🛡️ SECURITY DECISION: Only SHA-256 hashes of keys are kept, in memory and on disk
+/// Why: A leaked key file does not leak working keys; generated keys carry ~380 bits of
+/// entropy, so a fast unsalted hash is not open to guessing
+/// Alternative: Store keys in plain text like .spiral-api-key (rejected: every key would
+/// leak with the file), argon2 (rejected: slow on every request for no gain at this entropy)
+pub struct ApiKeyStore {
+    keys: RwLock<Vec<StoredApiKey>>,
+    path: Option<PathBuf>,
+}
+
+impl ApiKeyStore {
+    /// Keys that only live as long as the process
+    pub fn in_memory() -> Self {
+        Self {
+            keys: RwLock::new(Vec::new()),
+            path: None,
+        }
+    }
+
+    /// Load keys from the file, if it exists
+    /// A corrupt file is an error rather than an empty store, so the next key created
+    /// cannot silently overwrite the keys already issued
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let keys = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                SpiralError::ConfigurationError(format!(
+                    "Invalid API key file {}: {e}",
+                    path.display()
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "Failed to read API key file {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        Ok(Self {
+            keys: RwLock::new(keys),
+            path: Some(path),
+        })
+    }
+
+    /// Issue a new key; the returned key is shown once and cannot be recovered
+    pub async fn create(
+        &self,
+        name: &str,
+        scopes: Vec<ApiKeyScope>,
+    ) -> Result<(ApiKeyInfo, String)> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > crate::constants::API_KEY_NAME_MAX_CHARS {
+            return Err(SpiralError::Validation(format!(
+                "Key name must be 1-{} characters",
+                crate::constants::API_KEY_NAME_MAX_CHARS
+            )));
+        }
+        if scopes.is_empty() {
+            return Err(SpiralError::Validation(
+                "At least one scope is required".to_string(),
+            ));
+        }
+        let mut scopes = scopes;
+        scopes.sort();
+        scopes.dedup();
+
+        let mut keys = self.keys.write().await;
+        if keys
+            .iter()
+            .any(|key| key.info.revoked_at.is_none() && key.info.name == name)
+        {
+            return Err(SpiralError::Validation(format!(
+                "An active key named '{name}' already exists"
+            )));
+        }
+
+        let key = security::generate_secure_api_key();
+        let stored = StoredApiKey {
+            info: ApiKeyInfo {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: name.to_string(),
+                scopes,
+                created_at: Utc::now(),
+                revoked_at: None,
+            },
+            key_hash: hash_key(&key),
+        };
+        let mut updated = keys.clone();
+        updated.push(stored.clone());
+        self.persist(&updated).await?;
+        *keys = updated;
+
+        info!(
+            "API key '{}' created ({})",
+            stored.info.name, stored.info.id
+        );
+        Ok((stored.info, key))
+    }
+
+    /// Stop a key from authenticating; the record stays listed with its revocation time
+    pub async fn revoke(&self, key_id: &str) -> Result<ApiKeyInfo> {
+        let mut keys = self.keys.write().await;
+        let index = keys
+            .iter()
+            .position(|key| key.info.id == key_id)
+            .ok_or_else(|| SpiralError::NotFound(format!("API key {key_id}")))?;
+        if keys[index].info.revoked_at.is_some() {
+            return Ok(keys[index].info.clone());
+        }
+
+        let mut updated = keys.clone();
+        updated[index].info.revoked_at = Some(Utc::now());
+        self.persist(&updated).await?;
+        *keys = updated;
+
+        info!("API key '{}' revoked ({})", keys[index].info.name, key_id);
+        Ok(keys[index].info.clone())
+    }
+
+    pub async fn list(&self) -> Vec<ApiKeyInfo> {
+        self.keys
+            .read()
+            .await
+            .iter()
+            .map(|key| key.info.clone())
+            .collect()
+    }
+
+    pub async fn active_count(&self) -> usize {
+        self.keys
+            .read()
+            .await
+            .iter()
+            .filter(|key| key.info.revoked_at.is_none())
+            .count()
+    }
+
+    /// Identity of the active key matching `provided`
+    /// Every active key is compared in constant time, so timing reveals neither
+    /// which key matched nor how much of a hash did
+    pub async fn authenticate(&self, provided: &str) -> Option<ApiKeyIdentity> {
+        let provided_hash = hash_key(provided);
+        let keys = self.keys.read().await;
+        let mut matched = None;
+        for key in keys.iter().filter(|key| key.info.revoked_at.is_none()) {
+            if bool::from(provided_hash.as_bytes().ct_eq(key.key_hash.as_bytes())) {
+                matched = Some(ApiKeyIdentity {
+                    key_id: Some(key.info.id.clone()),
+                    name: key.info.name.clone(),
+                    scopes: key.info.scopes.clone(),
+                });
+            }
+        }
+        matched
+    }
+
+    /// Write the whole key list, readable by the owner only
+    /// Written to a temporary file and renamed so a crash never leaves half a file
+    async fn persist(&self, keys: &[StoredApiKey]) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string_pretty(keys)?;
+        let partial = path.with_extension("partial");
+        let write = async {
+            tokio::fs::write(&partial, serialized).await?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                tokio::fs::set_permissions(&partial, std::fs::Permissions::from_mode(0o600))
+                    .await?;
+            }
+            tokio::fs::rename(&partial, path).await
+        };
+        write.await.map_err(|e| {
+            SpiralError::SystemError(format!(
+                "Failed to write API key file {}: {e}",
+                path.display()
+            ))
+        })
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keys_authenticate_until_revoked() {
+        let store = ApiKeyStore::in_memory();
+        let (info, key) = store
+            .create("ci", vec![ApiKeyScope::Submit, ApiKeyScope::Read])
+            .await
+            .unwrap();
+        assert_eq!(key.len(), security::API_KEY_LENGTH);
+
+        let identity = store.authenticate(&key).await.unwrap();
+        assert_eq!(identity.key_id.as_deref(), Some(info.id.as_str()));
+        assert_eq!(identity.role(), Role::Operator);
+        assert!(store.authenticate("not-a-key").await.is_none());
+
+        store.revoke(&info.id).await.unwrap();
+        assert!(store.authenticate(&key).await.is_none());
+        assert!(store.list().await[0].revoked_at.is_some());
+        assert!(matches!(
+            store.revoke("missing").await,
+            Err(SpiralError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_key_requests_are_validated() {
+        let store = ApiKeyStore::in_memory();
+        assert!(store.create(" ", vec![ApiKeyScope::Read]).await.is_err());
+        assert!(store.create("reader", vec![]).await.is_err());
+
+        store
+            .create("reader", vec![ApiKeyScope::Read])
+            .await
+            .unwrap();
+        assert!(store
+            .create("reader", vec![ApiKeyScope::Read])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keys_persist_as_hashes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("keys.json");
+
+        let store = ApiKeyStore::load(&path).unwrap();
+        let (_, key) = store.create("ops", vec![ApiKeyScope::Admin]).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains(&key));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let reloaded = ApiKeyStore::load(&path).unwrap();
+        assert_eq!(reloaded.authenticate(&key).await.unwrap().name, "ops");
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(ApiKeyStore::load(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scopes_are_enforced_on_routes() {
+        use crate::{agents::AgentOrchestrator, api::ApiServer, config::Config};
+        use axum::{
+            body::Body,
+            extract::{ConnectInfo, Request},
+            http::StatusCode,
+            Router,
+        };
+        use std::{net::SocketAddr, sync::Arc};
+        use tower::ServiceExt;
+
+        async fn send(
+            router: &Router,
+            key: &str,
+            method: &str,
+            uri: &str,
+            body: &str,
+        ) -> StatusCode {
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("x-api-key", key)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+            router.clone().oneshot(request).await.unwrap().status()
+        }
+
+        let config = Config::test_config();
+        let master = config.api.api_key.clone().unwrap();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config, orchestrator).unwrap().build_router();
+
+        let mut request = Request::post("/v1/admin/keys")
+            .header("x-api-key", &master)
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"name": "dashboard", "scopes": ["read"]}"#))
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let reader = created["key"].as_str().unwrap().to_string();
+        let reader_id = created["id"].as_str().unwrap().to_string();
+
+        let task = r#"{"agent_type": "SoftwareDeveloper", "content": "Write a test"}"#;
+        assert_eq!(
+            send(&router, &reader, "GET", "/v1/queue", "").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(&router, &reader, "POST", "/v1/tasks", task).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            send(&router, &reader, "GET", "/v1/admin/keys", "").await,
+            StatusCode::FORBIDDEN
+        );
+
+        let revoke = format!("/v1/admin/keys/{reader_id}");
+        assert_eq!(
+            send(&router, &master, "DELETE", &revoke, "").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(&router, &reader, "GET", "/v1/queue", "").await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    config::ApiConfig,
+};
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tracing::warn;
+
+pub mod keys;
+pub mod rbac;
+pub mod signing;
+
+pub use keys::{ApiKeyIdentity, ApiKeyInfo, ApiKeyScope, ApiKeyStore};
+pub use rbac::Role;
+
+#[derive(Clone)]
+pub struct AuthState {
+    pub config: ApiConfig,
+    pub keys: Arc<ApiKeyStore>,
+    pub signatures: Arc<signing::SignatureVerifier>,
+}
+
+/// 🔐 AUTHENTICATION MIDDLEWARE: Primary security enforcement point
+/// AUDIT CHECKPOINT: Critical security boundary - all requests pass through here
+/// Verify: API key validation, header parsing, authentication logging, timing attack prevention
+pub async fn auth_middleware(
+    State(auth_state): State<Arc<AuthState>>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let client_ip = headers
+        .get("x-forwarded-for")
+        .or_else(|| headers.get("x-real-ip"))
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("unknown");
+
+    tracing::debug!(
+        "Auth middleware processing request to: {} from IP: {}",
+        path,
+        client_ip
+    );
+
+    // 🌐 CORS PREFLIGHT BYPASS: Allow OPTIONS requests to proceed without auth
+    // REASONING: CORS preflight requests need to succeed to enable browser CORS
+    if method == Method::OPTIONS {
+        tracing::debug!("Bypassing auth for CORS preflight request to: {}", path);
+        return Ok(next.run(request).await);
+    }
+
+    // 🛡️ SECURITY POLICY AUDIT CHECKPOINT: No bypass paths allowed
+    // CRITICAL: Every endpoint requires authentication to prevent unauthorized access
+    // Previous vulnerability: Health check bypass removed for security hardening
+
+    // 🔑 API KEY EXTRACTION: Support multiple authentication header formats
+    // AUDIT: Verify both x-api-key and Authorization header handling
+    let provided_key = if let Some(header_value) = headers.get("x-api-key") {
+        // Direct API key header
+        header_value.to_str().map_err(|_| {
+            warn!(
+                "Malformed x-api-key header from IP: {} for path: {}",
+                client_ip, path
+            );
+            unauthorized(&method, &path, client_ip, "Malformed x-api-key header")
+        })?
+    } else if let Some(header_value) = headers.get("authorization") {
+        // Authorization header - must start with "Bearer "
+        let auth_str = header_value.to_str().map_err(|_| {
+            warn!(
+                "Malformed authorization header from IP: {} for path: {}",
+                client_ip, path
+            );
+            unauthorized(&method, &path, client_ip, "Malformed authorization header")
+        })?;
+
+        // 🏷️ BEARER TOKEN SUPPORT: Standard OAuth-style authentication
+        if let Some(token) = auth_str.strip_prefix("Bearer ") {
+            token
+        } else {
+            // 🚨 SECURITY: Reject authorization headers without proper Bearer prefix
+            warn!(
+                "Invalid authorization header format from IP: {} for path: {}",
+                client_ip, path
+            );
+            return Err(unauthorized(
+                &method,
+                &path,
+                client_ip,
+                "Authorization header is not a Bearer token",
+            ));
+        }
+    } else {
+        warn!("Missing API key in request to: {}", path);
+        return Err(unauthorized(&method, &path, client_ip, "Missing API key"));
+    };
+
+    // 🔐 VALIDATE API KEY: The master key first, then the managed keys
+    let identity = match &auth_state.config.api_key {
+        // 🔐 CONSTANT-TIME COMPARISON AUDIT CHECKPOINT: Prevent timing attacks
+        // CRITICAL: Use secure comparison to prevent API key extraction via timing
+        // SECURITY DECISION: Use constant-time comparison to prevent timing attacks
+        // Why: Prevents attackers from determining correct API key characters via timing analysis
+        // Alternative: Regular `==` (rejected: vulnerable to timing attacks)
+        Some(expected_key)
+            if bool::from(provided_key.as_bytes().ct_eq(expected_key.as_bytes())) =>
+        {
+            Some(ApiKeyIdentity::master())
+        }
+        _ => auth_state.keys.authenticate(provided_key).await,
+    };
+
+    let Some(identity) = identity else {
+        if auth_state.config.api_key.is_none() && auth_state.keys.active_count().await == 0 {
+            warn!("API authentication enabled but no API key configured");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": "Internal Server Error"})),
+            )
+                .into_response());
+        }
+        // 🚨 AUTHENTICATION FAILURE AUDIT CHECKPOINT: Invalid credentials
+        // CRITICAL: Log for security monitoring but don't reveal details
+        warn!(
+            "Authentication failed for path: {} from IP: {} (invalid key)",
+            path, client_ip
+        );
+        return Err(unauthorized(&method, &path, client_ip, "Invalid API key"));
+    };
+
+    // 🎟️ ROLE CHECK: A valid key may still not be allowed to do this
+    let required = rbac::required_role(&method, &path);
+    if !identity.role().permits(required) {
+        warn!(
+            "API key '{}' lacks {} role for {} {} from IP: {}",
+            identity.name, required, method, path, client_ip
+        );
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::AccessDenied,
+                AuditSource::Api,
+                format!("{method} {path}"),
+            )
+            .with_actor(identity.name.clone())
+            .with_client(client_ip)
+            .with_details(format!("Requires {required} role")),
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Forbidden", "details": format!("Requires {required} role")})),
+        )
+            .into_response());
+    }
+
+    // ✍️ SIGNATURE CHECK: Configured route groups also need proof of the signing secret
+    // The body is buffered to hash it, then handed on unchanged
+    if auth_state.signatures.requires_signature(&method, &path) {
+        let path_and_query = request
+            .uri()
+            .path_and_query()
+            .map(|pq| pq.as_str().to_string())
+            .unwrap_or_else(|| path.clone());
+        let (parts, body) = request.into_parts();
+        let bytes = axum::body::to_bytes(body, auth_state.config.max_body_bytes)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    Json(json!({"error": "Payload Too Large"})),
+                )
+                    .into_response()
+            })?;
+        let now = chrono::Utc::now().timestamp();
+        if let Err(reason) =
+            auth_state
+                .signatures
+                .verify(&parts.headers, &method, &path_and_query, &bytes, now)
+        {
+            warn!(
+                "Rejected signature from '{}' for {} {} from IP: {}: {}",
+                identity.name, method, path, client_ip, reason
+            );
+            return Err(unauthorized(&method, &path, client_ip, reason));
+        }
+        request = Request::from_parts(parts, axum::body::Body::from(bytes));
+    }
+
+    // ✅ AUTHENTICATION SUCCESS: Proceed to next middleware/handler
+    tracing::debug!(
+        "Authentication successful for path: {} from IP: {} as '{}'",
+        path,
+        client_ip,
+        identity.name
+    );
+    // 📜 Admin reads are not audited; anything an admin changes is, with its outcome
+    let audited_actor =
+        (required == Role::Admin && method != Method::GET && method != Method::HEAD)
+            .then(|| identity.name.clone());
+    request.extensions_mut().insert(identity);
+    let response = next.run(request).await;
+
+    if let Some(actor) = audited_actor {
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::AdminAction,
+                AuditSource::Api,
+                format!("{method} {path}"),
+            )
+            .with_actor(actor)
+            .with_client(client_ip)
+            .with_details(response.status().to_string()),
+        );
+    }
+    Ok(response)
+}
+
+/// Audit a rejected credential and build the uniform 401 response
+fn unauthorized(method: &Method, path: &str, client_ip: &str, reason: &str) -> Response {
+    audit::record(
+        AuditEvent::new(
+            AuditEventKind::AuthFailure,
+            AuditSource::Api,
+            format!("{method} {path}"),
+        )
+        .with_client(client_ip)
+        .with_details(reason),
+    );
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "Unauthorized"})),
+    )
+        .into_response()
+}
+
+pub fn create_auth_state(config: ApiConfig, keys: Arc<ApiKeyStore>) -> Arc<AuthState> {
+    let signatures = Arc::new(signing::SignatureVerifier::new(&config));
+    Arc::new(AuthState {
+        config,
+        keys,
+        signatures,
+    })
+}
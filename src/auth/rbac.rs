@@ -0,0 +1,178 @@
+use crate::{api::unversioned_path, config::DiscordConfig};
+use axum::http::Method;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+
+/// 🏗️ ARCHITECTURE DECISION: One ordered role ladder for API keys and Discord users
+/// Why: "Authorized" used to mean a different thing on each surface; a single ladder lets
+/// a dashboard viewer be granted read access without also being able to trigger updates
+/// Alternative: Per-surface permission lists (rejected: the two drift apart over time)
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Read task, agent, queue and system state; Discord status and help commands
+    Viewer,
+    /// Submit, cancel and analyse tasks; talk to agents on Discord
+    Operator,
+    /// Everything, including key management, dispatch control and self-updates
+    Admin,
+}
+
+impl Role {
+    /// Roles are ordered, so a higher role can do everything a lower one can
+    pub fn permits(self, required: Role) -> bool {
+        self >= required
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "viewer" => Ok(Role::Viewer),
+            "operator" => Ok(Role::Operator),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!(
+                "Unknown role '{other}' (expected viewer, operator or admin)"
+            )),
+        }
+    }
+}
+
+/// Role a request needs, decided by method and route
+/// Reads need `viewer`; anything that changes state needs `operator`, except actions
+/// that affect every caller, which need `admin`
+/// The WebSocket accepts task submissions, so opening it needs `operator`
+/// Task transcripts carry full prompts, so reading them needs `admin`
+pub fn required_role(method: &Method, path: &str) -> Role {
+    let segments: Vec<&str> = unversioned_path(path)
+        .trim_matches('/')
+        .split('/')
+        .collect();
+    let is_read = method == Method::GET || method == Method::HEAD;
+
+    match segments.as_slice() {
+        ["admin", ..] => Role::Admin,
+        ["tasks", _, "transcript"] => Role::Admin,
+        ["system", "pause" | "resume" | "drain"] if !is_read => Role::Admin,
+        ["queue", _, "promote" | "demote"] if !is_read => Role::Admin,
+        ["workspaces", _] if method == Method::DELETE => Role::Admin,
+        ["workspaces", _, "archive"] if !is_read => Role::Admin,
+        ["circuit-breakers", _, "reset" | "trip"] if !is_read => Role::Admin,
+        ["ws"] => Role::Operator,
+        _ if is_read => Role::Viewer,
+        _ => Role::Operator,
+    }
+}
+
+/// Role of a Discord user, or None if they may not use the bot at all
+/// Explicit DISCORD_USER_ROLES entries win; the legacy DISCORD_AUTHORIZED_USERS list
+/// keeps its old meaning of full access
+pub fn discord_user_role(config: &DiscordConfig, user_id: u64) -> Option<Role> {
+    config.user_roles.get(&user_id).copied().or_else(|| {
+        config
+            .authorized_users
+            .contains(&user_id)
+            .then_some(Role::Admin)
+    })
+}
+
+/// Parse `id:role` pairs separated by commas, as used by DISCORD_USER_ROLES
+pub fn parse_user_roles(raw: &str) -> Result<Vec<(u64, Role)>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, role) = entry
+                .split_once(':')
+                .ok_or_else(|| format!("Expected 'user_id:role', got '{entry}'"))?;
+            let id = id
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid Discord user ID '{}'", id.trim()))?;
+            Ok((id, role.parse()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_roles_are_ordered() {
+        assert!(Role::Admin.permits(Role::Viewer));
+        assert!(Role::Operator.permits(Role::Operator));
+        assert!(!Role::Operator.permits(Role::Admin));
+        assert!(!Role::Viewer.permits(Role::Operator));
+        assert_eq!("Operator".parse::<Role>(), Ok(Role::Operator));
+        assert!("root".parse::<Role>().is_err());
+    }
+
+    #[test]
+    fn test_required_roles() {
+        let cases = [
+            (Method::GET, "/v1/tasks/abc", Role::Viewer),
+            (Method::GET, "/queue", Role::Viewer),
+            (Method::POST, "/v1/tasks", Role::Operator),
+            (Method::DELETE, "/v1/tasks/abc", Role::Operator),
+            (Method::POST, "/v1/analyze", Role::Operator),
+            (Method::GET, "/v1/ws", Role::Operator),
+            (Method::GET, "/v1/admin/keys", Role::Admin),
+            (Method::POST, "/v1/system/pause", Role::Admin),
+            (Method::POST, "/queue/abc/promote", Role::Admin),
+            (Method::DELETE, "/v1/workspaces/w1", Role::Admin),
+            (
+                Method::POST,
+                "/v1/circuit-breakers/openai/reset",
+                Role::Admin,
+            ),
+            (Method::GET, "/v1/circuit-breakers", Role::Viewer),
+            (Method::POST, "/v1/workspaces/w1/archive", Role::Admin),
+            (Method::GET, "/v1/workspaces/w1/files", Role::Viewer),
+            (Method::GET, "/v1/tasks/abc/transcript", Role::Admin),
+        ];
+        for (method, path, role) in cases {
+            assert_eq!(required_role(&method, path), role, "{method} {path}");
+        }
+    }
+
+    #[test]
+    fn test_discord_user_roles() {
+        let mut config = Config::test_config().discord;
+        config.authorized_users = vec![1, 2];
+        config.user_roles = parse_user_roles("2:viewer, 3:operator")
+            .unwrap()
+            .into_iter()
+            .collect();
+
+        assert_eq!(discord_user_role(&config, 1), Some(Role::Admin));
+        assert_eq!(discord_user_role(&config, 2), Some(Role::Viewer));
+        assert_eq!(discord_user_role(&config, 3), Some(Role::Operator));
+        assert_eq!(discord_user_role(&config, 4), None);
+
+        assert!(parse_user_roles("").unwrap().is_empty());
+        assert!(parse_user_roles("3").is_err());
+        assert!(parse_user_roles("abc:admin").is_err());
+        assert!(parse_user_roles("3:root").is_err());
+    }
+}
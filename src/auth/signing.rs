@@ -0,0 +1,295 @@
+use crate::{api::unversioned_path, config::ApiConfig};
+use axum::http::{HeaderMap, Method};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, str::FromStr, sync::Mutex};
+
+/// Unix seconds at which the client signed the request
+pub const TIMESTAMP_HEADER: &str = "x-spiral-timestamp";
+/// `sha256=<hex HMAC>` over the timestamp, method, path and body
+pub const SIGNATURE_HEADER: &str = "x-spiral-signature";
+
+/// Groups of high-privilege routes that can be made to require a signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedRouteGroup {
+    /// Everything under /admin: key management and the audit log
+    Admin,
+    /// Pausing, resuming and draining dispatch, and reordering the queue
+    Dispatch,
+    /// Deleting and archiving workspaces
+    Workspaces,
+}
+
+impl FromStr for SignedRouteGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "admin" => Ok(Self::Admin),
+            "dispatch" => Ok(Self::Dispatch),
+            "workspaces" => Ok(Self::Workspaces),
+            other => Err(format!(
+                "Unknown route group '{other}' (expected admin, dispatch or workspaces)"
+            )),
+        }
+    }
+}
+
+/// The signable group a request belongs to, if any
+pub fn route_group(method: &Method, path: &str) -> Option<SignedRouteGroup> {
+    let segments: Vec<&str> = unversioned_path(path)
+        .trim_matches('/')
+        .split('/')
+        .collect();
+    let is_read = method == Method::GET || method == Method::HEAD;
+
+    match segments.as_slice() {
+        ["admin", ..] => Some(SignedRouteGroup::Admin),
+        ["system", "pause" | "resume" | "drain"] if !is_read => Some(SignedRouteGroup::Dispatch),
+        ["queue", _, "promote" | "demote"] if !is_read => Some(SignedRouteGroup::Dispatch),
+        ["workspaces", _] if method == Method::DELETE => Some(SignedRouteGroup::Workspaces),
+        ["workspaces", _, "archive"] if !is_read => Some(SignedRouteGroup::Workspaces),
+        _ => None,
+    }
+}
+
+/// The signature a client sends for a request, as `sha256=<hex>`
+/// Signed message: `{timestamp}\n{METHOD}\n{path and query}\n{hex SHA-256 of body}`
+pub fn sign_request(
+    secret: &str,
+    timestamp: i64,
+    method: &Method,
+    path_and_query: &str,
+    body: &[u8],
+) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(signed_message(timestamp, method, path_and_query, body).as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn signed_message(timestamp: i64, method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    format!(
+        "{timestamp}\n{method}\n{path_and_query}\n{}",
+        hex::encode(Sha256::digest(body))
+    )
+}
+
+/// ✍️ REQUEST SIGNATURES: Optional HMAC proof on top of the API key
+/// 🛡️ SECURITY DECISION: A separate shared secret, not the API key, keys the HMAC
+/// Why: A leaked API key (logs, shell history, a proxy) is then not enough to drive the
+/// admin surface; and managed keys are only stored hashed, so they could not key it anyway
+/// Alternative: Sign with the API key (rejected: one leak defeats both checks)
+/// Replays are refused twice over: the timestamp must be within the window, and a
+/// signature is only accepted once while it is inside it
+pub struct SignatureVerifier {
+    secret: Option<String>,
+    groups: Vec<SignedRouteGroup>,
+    max_age_secs: i64,
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl SignatureVerifier {
+    pub fn new(config: &ApiConfig) -> Self {
+        Self {
+            secret: config.signing_secret.clone(),
+            groups: config.signed_route_groups.clone(),
+            max_age_secs: config.signature_max_age_secs as i64,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether this request has to carry a valid signature
+    pub fn requires_signature(&self, method: &Method, path: &str) -> bool {
+        route_group(method, path).is_some_and(|group| self.groups.contains(&group))
+    }
+
+    /// Check a request's signature; the error is the reason, for the audit log
+    pub fn verify(
+        &self,
+        headers: &HeaderMap,
+        method: &Method,
+        path_and_query: &str,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), &'static str> {
+        let Some(secret) = &self.secret else {
+            return Err("Request signing is not configured");
+        };
+        let timestamp: i64 = headers
+            .get(TIMESTAMP_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .ok_or("Missing or invalid signature timestamp")?;
+        if (now - timestamp).abs() > self.max_age_secs {
+            return Err("Signature timestamp outside the replay window");
+        }
+        let provided = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().strip_prefix("sha256="))
+            .and_then(|v| hex::decode(v).ok())
+            .ok_or("Missing or malformed signature")?;
+
+        // 🔐 Mac::verify_slice compares in constant time
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(signed_message(timestamp, method, path_and_query, body).as_bytes());
+        mac.verify_slice(&provided)
+            .map_err(|_| "Signature does not match")?;
+
+        let mut seen = self
+            .seen
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        seen.retain(|_, signed_at| (now - *signed_at).abs() <= self.max_age_secs);
+        if seen.insert(hex::encode(provided), timestamp).is_some() {
+            return Err("Signature has already been used");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use axum::http::HeaderValue;
+
+    const SECRET: &str = "signing-secret";
+
+    fn verifier() -> SignatureVerifier {
+        let mut config = Config::test_config().api;
+        config.signing_secret = Some(SECRET.to_string());
+        config.signed_route_groups = vec![SignedRouteGroup::Admin];
+        config.signature_max_age_secs = 300;
+        SignatureVerifier::new(&config)
+    }
+
+    fn headers(timestamp: i64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(signature).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_valid_signature_is_accepted_once() {
+        let verifier = verifier();
+        let now = 1_800_000_000;
+        let body = br#"{"name": "ci"}"#;
+        let signature = sign_request(SECRET, now, &Method::POST, "/v1/admin/keys", body);
+        let headers = headers(now, &signature);
+
+        assert_eq!(
+            verifier.verify(&headers, &Method::POST, "/v1/admin/keys", body, now + 5),
+            Ok(())
+        );
+        assert!(verifier
+            .verify(&headers, &Method::POST, "/v1/admin/keys", body, now + 6)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tampered_or_stale_requests_are_rejected() {
+        let verifier = verifier();
+        let now = 1_800_000_000;
+        let body = br#"{"name": "ci"}"#;
+        let signature = sign_request(SECRET, now, &Method::POST, "/v1/admin/keys", body);
+        let signed = headers(now, &signature);
+
+        assert!(verifier
+            .verify(&signed, &Method::POST, "/v1/admin/keys", b"{}", now)
+            .is_err());
+        assert!(verifier
+            .verify(&signed, &Method::DELETE, "/v1/admin/keys", body, now)
+            .is_err());
+        assert!(verifier
+            .verify(&signed, &Method::POST, "/v1/admin/keys", body, now + 301)
+            .is_err());
+        assert!(verifier
+            .verify(
+                &HeaderMap::new(),
+                &Method::POST,
+                "/v1/admin/keys",
+                body,
+                now
+            )
+            .is_err());
+
+        let wrong_secret = sign_request("other", now, &Method::POST, "/v1/admin/keys", body);
+        assert!(verifier
+            .verify(
+                &headers(now, &wrong_secret),
+                &Method::POST,
+                "/v1/admin/keys",
+                body,
+                now
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_route_groups() {
+        let verifier = verifier();
+        assert!(verifier.requires_signature(&Method::GET, "/v1/admin/audit"));
+        assert!(!verifier.requires_signature(&Method::POST, "/v1/system/pause"));
+        assert_eq!(
+            route_group(&Method::POST, "/queue/abc/demote"),
+            Some(SignedRouteGroup::Dispatch)
+        );
+        assert_eq!(
+            route_group(&Method::DELETE, "/v1/workspaces/w1"),
+            Some(SignedRouteGroup::Workspaces)
+        );
+        assert_eq!(route_group(&Method::GET, "/v1/system/pause"), None);
+        assert_eq!(route_group(&Method::POST, "/v1/tasks"), None);
+    }
+
+    #[tokio::test]
+    async fn test_signed_groups_are_enforced_by_middleware() {
+        use crate::{agents::AgentOrchestrator, api::ApiServer};
+        use axum::{
+            body::Body,
+            extract::{ConnectInfo, Request},
+            http::StatusCode,
+        };
+        use std::{net::SocketAddr, sync::Arc};
+        use tower::ServiceExt;
+
+        let mut config = Config::test_config();
+        config.api.signing_secret = Some(SECRET.to_string());
+        config.api.signed_route_groups = vec![SignedRouteGroup::Admin];
+        let master = config.api.api_key.clone().unwrap();
+        let orchestrator = Arc::new(AgentOrchestrator::new(config.clone()).await.unwrap());
+        let router = ApiServer::new(config, orchestrator).unwrap().build_router();
+
+        let send = |signature: Option<(i64, String)>| {
+            let mut request = Request::get("/v1/admin/keys").header("x-api-key", &master);
+            if let Some((timestamp, signature)) = signature {
+                request = request
+                    .header(TIMESTAMP_HEADER, timestamp)
+                    .header(SIGNATURE_HEADER, signature);
+            }
+            let mut request = request.body(Body::empty()).unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+            router.clone().oneshot(request)
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let signature = sign_request(SECRET, now, &Method::GET, "/v1/admin/keys", b"");
+        assert_eq!(send(None).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            send(Some((now, signature.clone()))).await.unwrap().status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(Some((now, signature))).await.unwrap().status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
@@ -4,16 +4,19 @@ use spiral_core::{
     claude_code::ClaudeCodeClient,
     config::Config,
     discord::{SpiralConstellationBot, SpiralConstellationBotRunner},
+    logging, prompts,
 };
-use tracing::{info, Level};
+use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+    let config = Config::load()?;
+    let _log_guard = logging::init(&config.logging)?;
+    spiral_core::monitoring::CrashReporter::new(&config.crash).install_panic_hook();
 
     info!("Starting Spiral Constellation Discord Bot");
 
-    let config = Config::load()?;
+    prompts::init(&config.prompts)?;
 
     // Create Claude Code client
     let claude_client = ClaudeCodeClient::new(config.claude_code.clone()).await?;
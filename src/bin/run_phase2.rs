@@ -176,6 +176,15 @@ async fn create_claude_executor() -> Result<Phase2Executor> {
             .collect(),
         workspace_cleanup_after_hours: 24,
         max_workspace_size_mb: 500,
+        max_concurrent_invocations: 4,
+        daily_budget_usd: None,
+        response_cache: Default::default(),
+        circuit_breaker: Default::default(),
+        sandbox: Default::default(),
+        mcp_servers: Default::default(),
+        verify_cli_version: true,
+        workspace_templates: Default::default(),
+        transcripts: Default::default(),
     };
 
     Phase2Executor::with_claude(config).await
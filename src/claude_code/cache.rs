@@ -0,0 +1,466 @@
+//! Response cache for repeated Claude Code requests
+//!
+//! Identical task analyses and small sessionless generations are answered from memory
+//! (and optionally disk) instead of paying for another CLI call. Entries are keyed by a
+//! SHA-256 of the request content, expire after a TTL, and the least recently used entry
+//! is evicted once the cache is full.
+
+use super::cli_client::{CodeGenerationResult, TaskAnalysis};
+use super::costs::USER_CONTEXT_KEYS;
+use super::sessions::{SESSION_CONTEXT_KEY, THREAD_CONTEXT_KEY};
+use crate::config::ResponseCacheConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::fs;
+use tracing::{debug, warn};
+
+/// Context keys naming who asked or where, which do not change what Claude would answer
+const NON_CONTENT_KEYS: [&str; 3] = ["task_id", SESSION_CONTEXT_KEY, THREAD_CONTEXT_KEY];
+
+/// A response worth serving again for the same request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CachedResponse {
+    Analysis(TaskAnalysis),
+    Generation(CodeGenerationResult),
+}
+
+/// 🔑 CACHE KEY: SHA-256 over the request kind, its content parts and its context
+/// Parts are length-prefixed so ("ab", "c") and ("a", "bc") differ, and context is
+/// sorted so HashMap order cannot split one request into many keys. Requester, task and
+/// session identifiers are left out: two users asking the same thing share the answer.
+pub fn cache_key(kind: &str, parts: &[&str], context: &HashMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    let mut feed = |value: &str| {
+        hasher.update((value.len() as u64).to_le_bytes());
+        hasher.update(value.as_bytes());
+    };
+    feed(kind);
+    for part in parts {
+        feed(part);
+    }
+    let content: BTreeMap<_, _> = context
+        .iter()
+        .filter(|(key, _)| {
+            !NON_CONTENT_KEYS.contains(&key.as_str()) && !USER_CONTEXT_KEYS.contains(&key.as_str())
+        })
+        .collect();
+    for (key, value) in content {
+        feed(key);
+        feed(value);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Cache effectiveness, reported through system monitoring
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseCacheMetrics {
+    pub enabled: bool,
+    pub entries: usize,
+    pub capacity: usize,
+    pub ttl_secs: u64,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room for newer ones
+    pub evictions: u64,
+    /// Entries found but too old to serve
+    pub expirations: u64,
+    /// Share of lookups answered from the cache, 0.0 before any lookup
+    pub hit_rate: f64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    response: CachedResponse,
+    /// Unix seconds, so entries restored from disk keep their age
+    stored_at: u64,
+    /// Recency counter; the smallest value is evicted first
+    last_used: u64,
+}
+
+/// What each cache file holds
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    stored_at: u64,
+    response: CachedResponse,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    expirations: u64,
+}
+
+impl CacheState {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+/// 🗃️ RESPONSE CACHE: Content-addressed LRU with TTL and an optional disk mirror
+/// 🏗️ ARCHITECTURE DECISION: Hand-rolled LRU scanning for the oldest entry on eviction
+/// Why: Capacity is in the hundreds and eviction only happens on insert, next to a CLI
+/// call that takes seconds; a linear scan keeps the structure a plain HashMap
+/// Alternative: An LRU crate with a linked list (rejected: a dependency for no
+/// measurable gain at this size)
+/// The disk mirror holds one JSON file per entry and follows the memory cache, so it never
+/// holds more than `capacity` entries; it only matters across restarts.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    state: Arc<Mutex<CacheState>>,
+    capacity: usize,
+    ttl_secs: u64,
+    dir: Option<PathBuf>,
+}
+
+impl ResponseCache {
+    /// Create the cache, restoring unexpired entries from the disk mirror when configured
+    pub async fn load(config: &ResponseCacheConfig) -> Self {
+        let cache = Self {
+            state: Arc::default(),
+            capacity: config.capacity,
+            ttl_secs: config.ttl_secs,
+            dir: config
+                .dir
+                .as_ref()
+                .filter(|_| config.capacity > 0)
+                .map(PathBuf::from),
+        };
+        if let Some(dir) = &cache.dir {
+            if let Err(e) = fs::create_dir_all(dir).await {
+                // A cache that cannot persist still saves calls until the next restart
+                warn!("Response cache directory {:?} is unusable: {}", dir, e);
+            } else {
+                cache.restore(now_secs()).await;
+            }
+        }
+        cache
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// The cached response for a key, if one was stored within the TTL
+    pub async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.get_at(key, now_secs()).await
+    }
+
+    /// Remember a response, evicting the least recently used entry when full
+    pub async fn insert(&self, key: String, response: CachedResponse) {
+        self.insert_at(key, response, now_secs()).await
+    }
+
+    pub fn metrics(&self) -> ResponseCacheMetrics {
+        let state = self.lock();
+        let lookups = state.hits + state.misses;
+        ResponseCacheMetrics {
+            enabled: self.is_enabled(),
+            entries: state.entries.len(),
+            capacity: self.capacity,
+            ttl_secs: self.ttl_secs,
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            expirations: state.expirations,
+            hit_rate: if lookups == 0 {
+                0.0
+            } else {
+                state.hits as f64 / lookups as f64
+            },
+        }
+    }
+
+    async fn get_at(&self, key: &str, now: u64) -> Option<CachedResponse> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let expired = {
+            let mut state = self.lock();
+            let tick = state.tick();
+            let fresh = match state.entries.get_mut(key) {
+                Some(entry) if !self.is_expired(entry.stored_at, now) => {
+                    entry.last_used = tick;
+                    Some(entry.response.clone())
+                }
+                Some(_) => None,
+                None => {
+                    state.misses += 1;
+                    return None;
+                }
+            };
+            if let Some(response) = fresh {
+                state.hits += 1;
+                return Some(response);
+            }
+            state.entries.remove(key);
+            state.expirations += 1;
+            state.misses += 1;
+            key.to_string()
+        };
+        self.remove_file(&expired).await;
+        None
+    }
+
+    async fn insert_at(&self, key: String, response: CachedResponse, now: u64) {
+        if !self.is_enabled() {
+            return;
+        }
+        let evicted = {
+            let mut state = self.lock();
+            let tick = state.tick();
+            let evicted =
+                if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+                    let oldest = state
+                        .entries
+                        .iter()
+                        .min_by_key(|(_, entry)| entry.last_used)
+                        .map(|(key, _)| key.clone());
+                    if let Some(oldest) = &oldest {
+                        state.entries.remove(oldest);
+                        state.evictions += 1;
+                    }
+                    oldest
+                } else {
+                    None
+                };
+            state.entries.insert(
+                key.clone(),
+                CacheEntry {
+                    response: response.clone(),
+                    stored_at: now,
+                    last_used: tick,
+                },
+            );
+            evicted
+        };
+
+        if let Some(evicted) = evicted {
+            self.remove_file(&evicted).await;
+        }
+        self.write_file(
+            &key,
+            DiskEntry {
+                stored_at: now,
+                response,
+            },
+        )
+        .await;
+    }
+
+    fn is_expired(&self, stored_at: u64, now: u64) -> bool {
+        now.saturating_sub(stored_at) >= self.ttl_secs
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Load unexpired cache files, newest first, up to capacity; the rest are deleted
+    async fn restore(&self, now: u64) {
+        let Some(dir) = &self.dir else { return };
+        let mut entries = match fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Could not read response cache directory {:?}: {}", dir, e);
+                return;
+            }
+        };
+
+        let mut restored = Vec::new();
+        let mut stale = Vec::new();
+        while let Ok(Some(file)) = entries.next_entry().await {
+            let path = file.path();
+            let Some(key) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|_| path.extension().is_some_and(|ext| ext == "json"))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            let parsed = fs::read(&path)
+                .await
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<DiskEntry>(&bytes).ok());
+            match parsed {
+                Some(entry) if !self.is_expired(entry.stored_at, now) => {
+                    restored.push((key, entry))
+                }
+                _ => stale.push(key),
+            }
+        }
+
+        restored.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.stored_at));
+        stale.extend(
+            restored
+                .split_off(restored.len().min(self.capacity))
+                .into_iter()
+                .map(|(key, _)| key),
+        );
+        {
+            let mut state = self.lock();
+            // Oldest gets the lowest recency, so it is the first to be evicted
+            for (key, entry) in restored.into_iter().rev() {
+                let tick = state.tick();
+                state.entries.insert(
+                    key,
+                    CacheEntry {
+                        response: entry.response,
+                        stored_at: entry.stored_at,
+                        last_used: tick,
+                    },
+                );
+            }
+            debug!("Restored {} cached responses", state.entries.len());
+        }
+        for key in stale {
+            self.remove_file(&key).await;
+        }
+    }
+
+    fn file_path(&self, key: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("{key}.json")))
+    }
+
+    async fn write_file(&self, key: &str, entry: DiskEntry) {
+        let Some(path) = self.file_path(key) else {
+            return;
+        };
+        let written = match serde_json::to_vec(&entry) {
+            Ok(bytes) => fs::write(&path, bytes).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = written {
+            // The entry is still served from memory; only a restart loses it
+            warn!("Failed to persist cached response to {:?}: {}", path, e);
+        }
+    }
+
+    async fn remove_file(&self, key: &str) {
+        if let Some(path) = self.file_path(key) {
+            let _ = fs::remove_file(path).await;
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(capacity: usize, dir: Option<&std::path::Path>) -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            capacity,
+            ttl_secs: 60,
+            dir: dir.map(|dir| dir.to_string_lossy().into_owned()),
+        }
+    }
+
+    fn analysis(approach: &str) -> CachedResponse {
+        CachedResponse::Analysis(TaskAnalysis {
+            complexity: "Low".to_string(),
+            estimated_minutes: 5,
+            required_skills: vec!["rust".to_string()],
+            challenges: Vec::new(),
+            approach: approach.to_string(),
+            raw_analysis: approach.to_string(),
+        })
+    }
+
+    fn approach(response: Option<CachedResponse>) -> Option<String> {
+        match response? {
+            CachedResponse::Analysis(analysis) => Some(analysis.approach),
+            CachedResponse::Generation(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_cache_key_ignores_requester_and_context_order() {
+        let context = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let alice = context(&[("language", "rust"), ("user_id", "alice"), ("task_id", "1")]);
+        let bob = context(&[("task_id", "2"), ("user_id", "bob"), ("language", "rust")]);
+        assert_eq!(
+            cache_key("analysis", &["add tests"], &alice),
+            cache_key("analysis", &["add tests"], &bob)
+        );
+
+        let python = context(&[("language", "python")]);
+        assert_ne!(
+            cache_key("analysis", &["add tests"], &alice),
+            cache_key("analysis", &["add tests"], &python)
+        );
+        assert_ne!(
+            cache_key("analysis", &["ab", "c"], &HashMap::new()),
+            cache_key("analysis", &["a", "bc"], &HashMap::new())
+        );
+        assert_ne!(
+            cache_key("analysis", &["x"], &HashMap::new()),
+            cache_key("generation", &["x"], &HashMap::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_expires_and_evicts_least_recently_used() {
+        let cache = ResponseCache::load(&config(2, None)).await;
+        cache.insert_at("a".to_string(), analysis("a"), 100).await;
+        cache.insert_at("b".to_string(), analysis("b"), 100).await;
+
+        // Reading "a" makes "b" the least recently used
+        assert_eq!(approach(cache.get_at("a", 110).await).as_deref(), Some("a"));
+        cache.insert_at("c".to_string(), analysis("c"), 110).await;
+        assert!(cache.get_at("b", 110).await.is_none());
+        assert!(cache.get_at("a", 110).await.is_some());
+
+        // Past the TTL the entry is dropped rather than served
+        assert!(cache.get_at("c", 170).await.is_none());
+
+        let metrics = cache.metrics();
+        assert_eq!((metrics.hits, metrics.misses), (2, 2));
+        assert_eq!((metrics.evictions, metrics.expirations), (1, 1));
+        assert_eq!(metrics.entries, 1);
+        assert_eq!(metrics.hit_rate, 0.5);
+
+        let disabled = ResponseCache::load(&config(0, None)).await;
+        disabled.insert("a".to_string(), analysis("a")).await;
+        assert!(disabled.get("a").await.is_none());
+        assert_eq!(disabled.metrics().misses, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_survives_restart_through_disk_mirror() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::load(&config(2, Some(dir.path()))).await;
+        cache.insert("a".to_string(), analysis("a")).await;
+        cache.insert("b".to_string(), analysis("b")).await;
+        cache.insert("c".to_string(), analysis("c")).await;
+
+        // The evicted entry's file went with it
+        let files = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(files, 2);
+
+        let restarted = ResponseCache::load(&config(2, Some(dir.path()))).await;
+        assert_eq!(restarted.metrics().entries, 2);
+        assert_eq!(approach(restarted.get("c").await).as_deref(), Some("c"));
+        assert!(restarted.get("a").await.is_none());
+    }
+}
@@ -0,0 +1,278 @@
+//! What a Claude Code run changed in its workspace
+//!
+//! The workspace is snapshotted before and after each run; comparing the two gives the
+//! files created, modified and deleted, with full content for new files and unified
+//! diffs for the rest.
+
+use crate::constants::{
+    CHANGES_TOTAL_MAX_BYTES, CHANGE_CONTENT_MAX_BYTES, CHANGE_SNAPSHOT_MAX_FILE_BYTES,
+};
+use crate::models::{FileChange, FileChangeKind};
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::SystemTime;
+use tokio::fs;
+
+/// Build output and dependencies: large, regenerated, and never what a reviewer wants
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "__pycache__"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct FileState {
+    len: u64,
+    modified: Option<SystemTime>,
+    /// Kept for files up to CHANGE_SNAPSHOT_MAX_FILE_BYTES
+    content: Option<Vec<u8>>,
+}
+
+impl FileState {
+    fn differs_from(&self, before: &FileState) -> bool {
+        match (&self.content, &before.content) {
+            (Some(after), Some(before)) => after != before,
+            _ => self.len != before.len || self.modified != before.modified,
+        }
+    }
+
+    fn text(&self) -> Option<&str> {
+        self.content
+            .as_deref()
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+    }
+}
+
+/// 📸 WORKSPACE SNAPSHOT: The files of a workspace at one moment
+/// 🏗️ ARCHITECTURE DECISION: Compare snapshots instead of trusting Claude's account
+/// Why: The CLI reports no structured file operations, and files written by tools Claude
+/// ran (formatters, generators, Bash) would be missed anyway
+/// Alternative: git in each workspace (rejected: workspaces need not be repositories,
+/// and committing on the agent's behalf would change what it sees)
+/// Trade-off: Each run reads the workspace twice; dot-entries and build directories are
+/// skipped to keep that cheap
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceSnapshot {
+    files: BTreeMap<String, FileState>,
+}
+
+impl WorkspaceSnapshot {
+    /// Read a workspace; unreadable entries are left out rather than failing the run
+    pub async fn capture(root: &Path) -> Self {
+        let mut files = BTreeMap::new();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(mut entries) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata().await else {
+                    continue;
+                };
+                let path = entry.path();
+                if metadata.is_dir() {
+                    if !SKIPPED_DIRS.contains(&name.as_str()) {
+                        pending.push(path);
+                    }
+                    continue;
+                }
+                if !metadata.is_file() {
+                    continue;
+                }
+                let content = if metadata.len() <= CHANGE_SNAPSHOT_MAX_FILE_BYTES {
+                    fs::read(&path).await.ok()
+                } else {
+                    None
+                };
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.insert(
+                    relative.to_string_lossy().into_owned(),
+                    FileState {
+                        len: metadata.len(),
+                        modified: metadata.modified().ok(),
+                        content,
+                    },
+                );
+            }
+        }
+        Self { files }
+    }
+
+    /// The changes from `before` to this snapshot, in path order
+    /// Content is capped per file and in total; past the budget only paths and line
+    /// counts are kept
+    pub fn changes_since(&self, before: &WorkspaceSnapshot) -> Vec<FileChange> {
+        let mut budget = CHANGES_TOTAL_MAX_BYTES;
+        let mut changes = Vec::new();
+
+        for (path, after) in &self.files {
+            let change = match before.files.get(path) {
+                None => created(path, after),
+                Some(previous) if after.differs_from(previous) => modified(path, previous, after),
+                Some(_) => continue,
+            };
+            changes.push(change);
+        }
+        for (path, previous) in &before.files {
+            if !self.files.contains_key(path) {
+                changes.push(deleted(path, previous));
+            }
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        for change in &mut changes {
+            let Some(content) = change.content.take() else {
+                continue;
+            };
+            let (content, cut) = cap(content, CHANGE_CONTENT_MAX_BYTES.min(budget));
+            budget -= content.len();
+            change.truncated |= cut;
+            change.content = (!content.is_empty() || !cut).then_some(content);
+        }
+        changes
+    }
+}
+
+fn created(path: &str, after: &FileState) -> FileChange {
+    let text = after.text();
+    FileChange {
+        path: path.to_string(),
+        kind: FileChangeKind::Created,
+        lines_added: text.map_or(0, |text| text.lines().count()),
+        lines_removed: 0,
+        content: text.map(str::to_string),
+        truncated: after.content.is_none(),
+    }
+}
+
+fn modified(path: &str, before: &FileState, after: &FileState) -> FileChange {
+    match (before.text(), after.text()) {
+        (Some(old), Some(new)) => diff(path, FileChangeKind::Modified, old, new),
+        _ => FileChange {
+            path: path.to_string(),
+            kind: FileChangeKind::Modified,
+            lines_added: 0,
+            lines_removed: 0,
+            content: None,
+            truncated: after.content.is_none(),
+        },
+    }
+}
+
+fn deleted(path: &str, before: &FileState) -> FileChange {
+    match before.text() {
+        Some(old) => diff(path, FileChangeKind::Deleted, old, ""),
+        None => FileChange {
+            path: path.to_string(),
+            kind: FileChangeKind::Deleted,
+            lines_added: 0,
+            lines_removed: 0,
+            content: None,
+            truncated: before.content.is_none(),
+        },
+    }
+}
+
+fn diff(path: &str, kind: FileChangeKind, old: &str, new: &str) -> FileChange {
+    let diff = TextDiff::from_lines(old, new);
+    let (mut lines_added, mut lines_removed) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => lines_added += 1,
+            ChangeTag::Delete => lines_removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string();
+    FileChange {
+        path: path.to_string(),
+        kind,
+        lines_added,
+        lines_removed,
+        content: Some(unified),
+        truncated: false,
+    }
+}
+
+/// Cut text to at most `max_bytes`, on a character boundary
+fn cap(mut text: String, max_bytes: usize) -> (String, bool) {
+    if text.len() <= max_bytes {
+        return (text, false);
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    (text, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_changes_since_reports_created_modified_and_deleted_files() {
+        let workspace = tempfile::tempdir().unwrap();
+        let root = workspace.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(root.join("README.md"), "old readme\n").unwrap();
+        std::fs::write(root.join("unchanged.txt"), "same\n").unwrap();
+        let before = WorkspaceSnapshot::capture(root).await;
+
+        std::fs::write(root.join("src/lib.rs"), "fn a() {}\nfn c() {}\n").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::remove_file(root.join("README.md")).unwrap();
+        // Session bookkeeping and build output are not the task's changes
+        std::fs::write(root.join(".claude-session-id"), "abc").unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::write(root.join("target/debug/app"), [0u8, 159, 146]).unwrap();
+        let after = WorkspaceSnapshot::capture(root).await;
+
+        let changes = after.changes_since(&before);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind, c.lines_added, c.lines_removed))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("README.md", FileChangeKind::Deleted, 0, 1),
+                ("src/lib.rs", FileChangeKind::Modified, 1, 1),
+                ("src/main.rs", FileChangeKind::Created, 1, 0),
+            ]
+        );
+
+        let diff = changes[1].content.as_deref().unwrap();
+        assert!(diff.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n"));
+        assert!(diff.contains("-fn b() {}\n+fn c() {}\n"));
+        assert_eq!(changes[2].content.as_deref(), Some("fn main() {}\n"));
+        assert!(!changes[2].truncated);
+    }
+
+    #[tokio::test]
+    async fn test_change_content_is_capped() {
+        let workspace = tempfile::tempdir().unwrap();
+        let before = WorkspaceSnapshot::capture(workspace.path()).await;
+        let large = "é".repeat(CHANGE_CONTENT_MAX_BYTES);
+        std::fs::write(workspace.path().join("large.txt"), &large).unwrap();
+        std::fs::write(workspace.path().join("binary.bin"), [0u8, 159, 146, 150]).unwrap();
+
+        let changes = WorkspaceSnapshot::capture(workspace.path())
+            .await
+            .changes_since(&before);
+        let binary = &changes[0];
+        assert_eq!(binary.path, "binary.bin");
+        assert_eq!(binary.content, None);
+
+        let capped = &changes[1];
+        assert!(capped.truncated);
+        assert!(capped.content.as_ref().unwrap().len() <= CHANGE_CONTENT_MAX_BYTES);
+    }
+}
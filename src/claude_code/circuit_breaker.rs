@@ -1,16 +1,94 @@
+use crate::SpiralError;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+/// Registry name of the Claude Code CLI's breaker; other backends use their kind's name
+pub const CLAUDE_CIRCUIT_BREAKER: &str = "claude_code";
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub enum CircuitState {
     Closed,   // Normal operation
     Open,     // Failing, reject all requests
     HalfOpen, // Testing if service recovered
 }
 
+/// What kind of failure a backend call ended in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// The call took too long or the backend reported a timeout
+    Timeout,
+    /// Credentials were missing, expired or rejected
+    Auth,
+    /// Anything else the backend got wrong: crashes, bad output, server errors
+    Other,
+}
+
+impl FailureClass {
+    /// Classify an error from a backend call; None for errors that are the caller's
+    /// fault (invalid requests), which say nothing about the backend's health
+    pub fn classify(error: &SpiralError) -> Option<Self> {
+        if matches!(error, SpiralError::Validation(_)) {
+            return None;
+        }
+        Some(Self::from_message(&error.to_string()))
+    }
+
+    /// Classify by the wording of an error message, such as a CLI's stderr
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        const AUTH_PATTERNS: [&str; 7] = [
+            "401",
+            "403",
+            "unauthorized",
+            "authentication",
+            "invalid api key",
+            "invalid x-api-key",
+            "not logged in",
+        ];
+        if AUTH_PATTERNS
+            .iter()
+            .any(|pattern| message.contains(pattern))
+        {
+            Self::Auth
+        } else if message.contains("timed out") || message.contains("timeout") {
+            Self::Timeout
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// How a breaker reacts to one class of failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureAction {
+    /// Counts towards the failure threshold
+    Count,
+    /// Opens the circuit at once
+    Trip,
+    /// Leaves the breaker untouched
+    Ignore,
+}
+
+impl std::str::FromStr for FailureAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "count" => Ok(Self::Count),
+            "trip" => Ok(Self::Trip),
+            "ignore" => Ok(Self::Ignore),
+            other => Err(format!(
+                "Unknown failure action '{other}' (expected count, trip or ignore)"
+            )),
+        }
+    }
+}
+
 /// Circuit breaker configuration
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
@@ -18,10 +96,14 @@ pub struct CircuitBreakerConfig {
     pub failure_threshold: u32,
     /// Duration to wait before attempting recovery
     pub timeout_duration: Duration,
-    /// Number of successful requests needed to close circuit from half-open
+    /// Probe requests let through while half-open; all must succeed to close the circuit
     pub success_threshold: u32,
     /// Time window for counting failures
     pub failure_window: Duration,
+    /// Reaction to timeouts, which may only mean a task was large
+    pub on_timeout: FailureAction,
+    /// Reaction to authentication failures, which will not fix themselves
+    pub on_auth_error: FailureAction,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -31,6 +113,21 @@ impl Default for CircuitBreakerConfig {
             timeout_duration: Duration::from_secs(60),
             success_threshold: 3,
             failure_window: Duration::from_secs(300), // 5 minutes
+            on_timeout: FailureAction::Count,
+            on_auth_error: FailureAction::Trip,
+        }
+    }
+}
+
+impl From<&crate::config::CircuitBreakerPolicy> for CircuitBreakerConfig {
+    fn from(policy: &crate::config::CircuitBreakerPolicy) -> Self {
+        Self {
+            failure_threshold: policy.failure_threshold.max(1),
+            timeout_duration: Duration::from_secs(policy.open_secs),
+            success_threshold: policy.half_open_probes.max(1),
+            failure_window: Duration::from_secs(policy.failure_window_secs),
+            on_timeout: policy.on_timeout,
+            on_auth_error: policy.on_auth_error,
         }
     }
 }
@@ -46,6 +143,8 @@ pub struct CircuitBreaker {
     last_state_change: Arc<RwLock<Instant>>,
     total_requests: Arc<AtomicU64>,
     total_failures: Arc<AtomicU64>,
+    /// Probes admitted since the circuit last went half-open
+    half_open_probes: Arc<AtomicU32>,
 }
 
 impl CircuitBreaker {
@@ -59,6 +158,7 @@ impl CircuitBreaker {
             last_state_change: Arc::new(RwLock::new(Instant::now())),
             total_requests: Arc::new(AtomicU64::new(0)),
             total_failures: Arc::new(AtomicU64::new(0)),
+            half_open_probes: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -74,8 +174,9 @@ impl CircuitBreaker {
                 // Check if timeout has elapsed
                 let last_change = *self.last_state_change.read().await;
                 if last_change.elapsed() >= self.config.timeout_duration {
-                    // Transition to half-open
+                    // Transition to half-open; this request is the first probe
                     self.transition_to_half_open().await;
+                    self.half_open_probes.fetch_add(1, Ordering::Relaxed);
                     true
                 } else {
                     false
@@ -83,7 +184,19 @@ impl CircuitBreaker {
             }
             CircuitState::HalfOpen => {
                 // Allow limited requests in half-open state
-                true
+                let admitted = self.half_open_probes.fetch_add(1, Ordering::Relaxed);
+                if admitted < self.config.success_threshold {
+                    return true;
+                }
+                // Probes whose outcome was never recorded must not wedge the breaker
+                let last_change = *self.last_state_change.read().await;
+                if last_change.elapsed() >= self.config.timeout_duration {
+                    self.transition_to_half_open().await;
+                    self.half_open_probes.fetch_add(1, Ordering::Relaxed);
+                    true
+                } else {
+                    false
+                }
             }
         }
     }
@@ -115,6 +228,29 @@ impl CircuitBreaker {
         }
     }
 
+    /// Record a failed request of a known class, applying the configured reaction
+    pub async fn record_classified_failure(&self, class: FailureClass) {
+        let action = match class {
+            FailureClass::Timeout => self.config.on_timeout,
+            FailureClass::Auth => self.config.on_auth_error,
+            FailureClass::Other => FailureAction::Count,
+        };
+        match action {
+            FailureAction::Count => self.record_failure().await,
+            FailureAction::Trip => {
+                self.total_failures.fetch_add(1, Ordering::Relaxed);
+                *self.last_failure_time.write().await = Some(Instant::now());
+                if *self.state.read().await != CircuitState::Open {
+                    warn!("Circuit breaker tripped by {:?} failure", class);
+                    self.transition_to_open().await;
+                }
+            }
+            FailureAction::Ignore => {
+                debug!("Circuit breaker ignoring {:?} failure", class);
+            }
+        }
+    }
+
     /// Record failed request
     pub async fn record_failure(&self) {
         self.total_failures.fetch_add(1, Ordering::Relaxed);
@@ -189,6 +325,7 @@ impl CircuitBreaker {
 
         self.success_count.store(0, Ordering::Relaxed);
         self.failure_count.store(0, Ordering::Relaxed);
+        self.half_open_probes.store(0, Ordering::Relaxed);
 
         info!("Circuit breaker transitioned to half-open");
     }
@@ -211,11 +348,29 @@ impl CircuitBreaker {
         );
     }
 
+    /// 🔧 MANUAL RESET: Close the circuit now, e.g. once an operator fixed the cause
+    pub async fn reset(&self) {
+        *self.last_failure_time.write().await = None;
+        self.transition_to_closed().await;
+    }
+
+    /// 🔧 MANUAL TRIP: Open the circuit now; it half-opens after the usual open duration
+    pub async fn trip(&self) {
+        self.transition_to_open().await;
+    }
+
     /// Get current circuit state
     pub async fn get_state(&self) -> CircuitState {
         *self.state.read().await
     }
 
+    /// Whether a request would be refused right now
+    /// Unlike should_allow_request this neither counts a request nor moves to half-open
+    pub async fn is_rejecting(&self) -> bool {
+        *self.state.read().await == CircuitState::Open
+            && self.last_state_change.read().await.elapsed() < self.config.timeout_duration
+    }
+
     /// Get circuit breaker metrics
     pub async fn get_metrics(&self) -> CircuitBreakerMetrics {
         let last_change = *self.last_state_change.read().await;
@@ -230,7 +385,7 @@ impl CircuitBreaker {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub struct CircuitBreakerMetrics {
     pub state: CircuitState,
     pub failure_count: u32,
@@ -239,3 +394,136 @@ pub struct CircuitBreakerMetrics {
     pub total_failures: u64,
     pub last_state_change_seconds: u64,
 }
+
+/// 🔌 BREAKER REGISTRY: Every backend's breaker by name, for monitoring and manual control
+/// 🏗️ ARCHITECTURE DECISION: Breakers are created through the registry, one per name
+/// Why: Backend sets are built more than once (orchestrator and Discord bot); sharing
+/// the breaker by name means a failure seen by one counts for both, and a reset reaches both
+/// Alternative: A breaker per backend instance (rejected: two views of one backend's
+/// health that disagree, and a reset endpoint that only fixes one of them)
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreakerRegistry {
+    breakers: Arc<std::sync::RwLock<BTreeMap<String, Arc<CircuitBreaker>>>>,
+}
+
+impl CircuitBreakerRegistry {
+    /// The breaker registered under a name, creating it with this config if there is none
+    pub fn get_or_create(&self, name: &str, config: CircuitBreakerConfig) -> Arc<CircuitBreaker> {
+        let mut breakers = self.breakers.write().unwrap_or_else(|e| e.into_inner());
+        breakers
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(config)))
+            .clone()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<CircuitBreaker>> {
+        let breakers = self.breakers.read().unwrap_or_else(|e| e.into_inner());
+        breakers.get(name).cloned()
+    }
+
+    /// Metrics of every registered breaker, by name
+    pub async fn metrics(&self) -> HashMap<String, CircuitBreakerMetrics> {
+        let breakers: Vec<_> = {
+            let breakers = self.breakers.read().unwrap_or_else(|e| e.into_inner());
+            breakers
+                .iter()
+                .map(|(name, breaker)| (name.clone(), breaker.clone()))
+                .collect()
+        };
+        let mut metrics = HashMap::new();
+        for (name, breaker) in breakers {
+            metrics.insert(name, breaker.get_metrics().await);
+        }
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(on_timeout: FailureAction, on_auth_error: FailureAction) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            timeout_duration: Duration::from_millis(50),
+            success_threshold: 2,
+            on_timeout,
+            on_auth_error,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_failures_are_classified_by_message() {
+        assert_eq!(
+            FailureClass::from_message("Invalid API key · Please run /login"),
+            FailureClass::Auth
+        );
+        assert_eq!(
+            FailureClass::from_message("OpenAI returned 401 Unauthorized: bad key"),
+            FailureClass::Auth
+        );
+        assert_eq!(
+            FailureClass::from_message("operation timed out"),
+            FailureClass::Timeout
+        );
+        assert_eq!(
+            FailureClass::from_message("Claude Code execution failed: segfault"),
+            FailureClass::Other
+        );
+        assert_eq!(
+            FailureClass::classify(&SpiralError::Validation("empty".to_string())),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failure_actions_per_class() {
+        let breaker = breaker(FailureAction::Ignore, FailureAction::Trip);
+        for _ in 0..5 {
+            breaker
+                .record_classified_failure(FailureClass::Timeout)
+                .await;
+        }
+        assert_eq!(breaker.get_state().await, CircuitState::Closed);
+
+        breaker.record_classified_failure(FailureClass::Auth).await;
+        assert_eq!(breaker.get_state().await, CircuitState::Open);
+
+        breaker.reset().await;
+        assert_eq!(breaker.get_state().await, CircuitState::Closed);
+        assert!(breaker.should_allow_request().await);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_admits_limited_probes() {
+        let breaker = breaker(FailureAction::Count, FailureAction::Count);
+        breaker.trip().await;
+        assert!(!breaker.should_allow_request().await);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(breaker.should_allow_request().await);
+        assert!(breaker.should_allow_request().await);
+        assert!(
+            !breaker.should_allow_request().await,
+            "Only two probes are let through while half-open"
+        );
+
+        breaker.record_success().await;
+        breaker.record_success().await;
+        assert_eq!(breaker.get_state().await, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_registry_shares_breakers_by_name() {
+        let registry = CircuitBreakerRegistry::default();
+        let first = registry.get_or_create("openai", CircuitBreakerConfig::default());
+        let second = registry.get_or_create("openai", CircuitBreakerConfig::default());
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(registry.get("ollama").is_none());
+
+        first.trip().await;
+        let metrics = registry.metrics().await;
+        assert_eq!(metrics["openai"].state, CircuitState::Open);
+    }
+}
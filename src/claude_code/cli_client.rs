@@ -1,17 +1,40 @@
+use super::cache::{cache_key, CachedResponse, ResponseCache, ResponseCacheMetrics};
+use super::changes::WorkspaceSnapshot;
+use super::command_builder::SessionMode;
+use super::costs::{CostAttribution, CostTracker, Usage};
+use super::mcp::{self, McpInvocation};
+use super::pool::{
+    invocation_class, InvocationPool, InvocationPoolMetrics, UNATTRIBUTED_INVOCATION,
+};
+use super::quota::{self, WorkspaceQuota, BYTES_PER_MB};
+use super::sandbox::Sandbox;
+use super::sessions::{self, remember_claude_session, SessionLocks, MANAGED_SESSION_KEY_PREFIX};
+use super::stream::{parse_stream_line, GenerationEvent, StreamLine};
+use super::transcripts::{TranscriptEntry, TranscriptStore};
+use super::version::{ClaudeCliStatus, CliCompatibility, CliFeature};
+use super::workspace_archive;
+use super::workspace_templates::{WorkspaceTemplates, WORKSPACE_TEMPLATE_CONTEXT_KEY};
 use crate::{
-    claude_code::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+    claude_code::circuit_breaker::{
+        CircuitBreaker, CircuitBreakerRegistry, FailureClass, CLAUDE_CIRCUIT_BREAKER,
+    },
     config::ClaudeCodeConfig,
+    constants::{CLAUDE_CACHE_MAX_GENERATION_BYTES, CLAUDE_STREAM_EVENT_BUFFER},
+    models::{FileChange, FileChangeKind},
+    prompts,
     validation::TaskContentValidator,
     Result, SpiralError,
 };
+use futures::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -26,6 +49,45 @@ pub struct ClaudeCodeCliClient {
     claude_binary: String,
     validator: TaskContentValidator,
     circuit_breaker: Arc<CircuitBreaker>,
+    circuit_breakers: CircuitBreakerRegistry,
+    costs: Arc<CostTracker>,
+    session_locks: SessionLocks,
+    response_cache: ResponseCache,
+    sandbox: Sandbox,
+    quota: WorkspaceQuota,
+    invocations: InvocationPool,
+    cli: CliCompatibility,
+    templates: Arc<WorkspaceTemplates>,
+    transcripts: TranscriptStore,
+}
+
+/// Permission mode of dry runs: Claude may look around but not change anything
+const PLAN_PERMISSION_MODE: &str = "plan";
+
+/// Tools that change the workspace or run commands, withheld from dry runs
+const WRITE_TOOLS: &[&str] = &["Write", "Edit", "MultiEdit", "NotebookEdit", "Bash"];
+
+/// Directory name prefix of shared session workspaces, followed by the session key
+pub(crate) const SESSION_DIR_PREFIX: &str = "session-";
+
+/// Directory name prefix of one-off workspaces, followed by a UUID
+pub(crate) const WORKSPACE_DIR_PREFIX: &str = "workspace-";
+
+/// Where ended sessions' workspaces are archived; the API's archive endpoint uses it too
+const WORKSPACE_ARCHIVE_DIR: &str = "claude-workspaces-archive";
+
+/// The session key of a workspace directory that a user session owns
+fn managed_session_key(dir_name: &str) -> Option<&str> {
+    dir_name
+        .strip_prefix(SESSION_DIR_PREFIX)
+        .filter(|key| key.starts_with(MANAGED_SESSION_KEY_PREFIX))
+}
+
+/// One finished CLI run: its response, the workspace it ran in and what it changed there
+struct CliRun {
+    response: ClaudeCodeCliResponse,
+    workspace: PathBuf,
+    changes: Vec<FileChange>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,6 +117,19 @@ pub struct CliUsage {
     pub service_tier: String,
 }
 
+impl From<&ClaudeCodeCliResponse> for Usage {
+    fn from(response: &ClaudeCodeCliResponse) -> Self {
+        let usage = &response.usage;
+        Self {
+            input_tokens: usage.input_tokens.into(),
+            output_tokens: usage.output_tokens.into(),
+            cache_tokens: u64::from(usage.cache_creation_input_tokens.unwrap_or(0))
+                + u64::from(usage.cache_read_input_tokens.unwrap_or(0)),
+            cost_usd: response.total_cost_usd,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CodeGenerationRequest {
     pub language: String,
@@ -63,9 +138,11 @@ pub struct CodeGenerationRequest {
     pub existing_code: Option<String>,
     pub requirements: Vec<String>,
     pub session_id: Option<String>,
+    /// Plan the changes without making them: Claude runs in plan mode without write tools
+    pub dry_run: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeGenerationResult {
     pub code: String,
     pub language: String,
@@ -74,6 +151,12 @@ pub struct CodeGenerationResult {
     pub files_to_modify: Vec<FileModification>,
     pub session_id: Option<String>,
     pub workspace_path: String,
+    /// Everything the run changed in the workspace, diffs included
+    #[serde(default)]
+    pub changes: Vec<FileChange>,
+    /// A dry run: `explanation` is the plan and nothing was written
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,22 +175,58 @@ pub struct FileModification {
 
 impl ClaudeCodeCliClient {
     pub async fn new(config: ClaudeCodeConfig) -> Result<Self> {
+        let sandbox = Sandbox::new(config.sandbox.clone());
+        sandbox.verify().await?;
+        // Sandboxed runs use the image's CLI, so the host need not have one
         let claude_binary = if let Some(path) = &config.claude_binary_path {
             path.clone()
+        } else if sandbox.is_enabled() {
+            config.sandbox.binary.clone()
         } else {
             Self::find_claude_binary().await?
         };
 
+        // 🔖 VERSION CHECK: Fail now, not mid-task, when the CLI is too old
+        let cli = if config.verify_cli_version {
+            CliCompatibility::detect(sandbox.probe_command(&claude_binary)).await?
+        } else {
+            CliCompatibility::default()
+        };
+        if !config.mcp_servers.is_empty() {
+            cli.require(CliFeature::McpConfig, "CLAUDE_MCP_CONFIG")?;
+        }
+
+        mcp::validate_servers(&config.mcp_servers, sandbox.is_enabled())?;
+        let templates = Arc::new(WorkspaceTemplates::from_config(
+            &config.workspace_templates,
+        )?);
         let validator = TaskContentValidator::new()?;
 
-        // Initialize circuit breaker with default config
-        let circuit_breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig::default()));
+        // Other backends register their breakers alongside this one
+        let circuit_breakers = CircuitBreakerRegistry::default();
+        let circuit_breaker = circuit_breakers
+            .get_or_create(CLAUDE_CIRCUIT_BREAKER, (&config.circuit_breaker).into());
+        let costs = Arc::new(CostTracker::new(config.daily_budget_usd));
+        let response_cache = ResponseCache::load(&config.response_cache).await;
+        let quota = WorkspaceQuota::new(config.max_workspace_size_mb);
+        let invocations = InvocationPool::new(config.max_concurrent_invocations);
+        let transcripts = TranscriptStore::open(&config.transcripts).await?;
 
         Ok(Self {
             config,
             claude_binary,
             validator,
             circuit_breaker,
+            circuit_breakers,
+            costs,
+            session_locks: SessionLocks::default(),
+            response_cache,
+            sandbox,
+            quota,
+            invocations,
+            cli,
+            templates,
+            transcripts,
         })
     }
 
@@ -118,7 +237,7 @@ impl ClaudeCodeCliClient {
     /// PERFORMANCE DECISION: Use tokio::process::Command for non-blocking operation
     /// Why: Prevents blocking the async runtime during binary discovery
     /// Alternative: spawn_blocking (considered: more overhead for simple command)
-    async fn find_claude_binary() -> Result<String> {
+    pub(crate) async fn find_claude_binary() -> Result<String> {
         // Expand home directory path
         let home_dir = std::env::var("HOME").unwrap_or_else(|_| "/Users/hogers".to_string());
 
@@ -160,12 +279,79 @@ impl ClaudeCodeCliClient {
         ))
     }
 
+    /// Claude Code invocation in a session workspace; the prompt goes to its stdin
+    fn session_command(
+        &self,
+        workspace: &Path,
+        session_mode: &SessionMode,
+        permission_mode: &str,
+        output_format: &str,
+        mcp: Option<&McpInvocation>,
+    ) -> Command {
+        // 🛡️ SECURITY AUDIT CHECKPOINT: With a sandbox configured this is a container run
+        // that can only reach the workspace
+        let mut command = self.sandbox.command(workspace, &self.claude_binary);
+        command
+            .args([
+                "--print",
+                "--output-format",
+                output_format,
+                "--model",
+                "sonnet",
+                "--permission-mode",
+                permission_mode,
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true); // A cancelled task must not leave Claude Code running
+
+        // The CLI refuses stream-json in print mode without --verbose
+        if output_format == "stream-json" {
+            command.arg("--verbose");
+        }
+
+        // 🔄 SESSION CONTINUITY STRATEGY: Resume the session recorded in the workspace
+        // DECISION: Resume by the ID Claude Code reported, never by our own session key
+        // Why: The CLI only knows the session IDs it generated; our keys name workspaces
+        // Alternative: Always new sessions (rejected: loses valuable context)
+        debug!("Claude Code session mode: {:?}", session_mode);
+        command.args(session_mode.cli_args());
+
+        // 🔌 MCP SERVERS: The agent's servers, with their tools approved like built-in ones
+        let mut allowed_tools = self.config.allowed_tools.clone();
+        if let Some(mcp) = mcp {
+            command.arg("--mcp-config").arg(&mcp.config_path);
+            allowed_tools.extend(mcp.allowed_tools());
+        }
+
+        // 📋 DRY RUN: Plan mode already refuses edits; the write tools are also withheld
+        // so no pre-approval in allowed_tools can bring them back
+        if permission_mode == PLAN_PERMISSION_MODE {
+            allowed_tools.retain(|tool| !WRITE_TOOLS.contains(&tool.as_str()));
+            command.args(["--disallowedTools", &WRITE_TOOLS.join(",")]);
+        }
+
+        // Add allowed tools if any are specified
+        if !allowed_tools.is_empty() {
+            let tools_str = allowed_tools.join(",");
+            command.args(["--allowedTools", &tools_str]);
+        }
+
+        // Add workspace directory to allowed directories
+        command.arg("--add-dir").arg(workspace);
+        command
+    }
+
     /// Execute Claude Code CLI command with optional session ID for continuity
     async fn execute_claude_command_with_session(
         &self,
         prompt: &str,
+        permission_mode: &str,
         session_id: Option<&str>,
-    ) -> Result<ClaudeCodeCliResponse> {
+        template: Option<&str>,
+        invocation_class: &str,
+    ) -> Result<CliRun> {
         // Check circuit breaker before making request
         if !self.circuit_breaker.should_allow_request().await {
             warn!("Circuit breaker is open - Claude Code service is unavailable");
@@ -178,62 +364,25 @@ impl ClaudeCodeCliClient {
         // Why: Prevents cross-contamination between tasks, enables safe file operations
         // Alternative: Shared workspace (rejected: security risk, concurrent access issues)
         // AUDIT CHECKPOINT: Verify workspace creation doesn't allow directory traversal
-        let (workspace, is_new_session) = self.get_or_create_session_workspace(session_id).await?;
+        let (workspace, is_new_session) = self.prepare_workspace(session_id, template).await?;
+        let session_mode = sessions::session_mode(&workspace, is_new_session).await;
 
         debug!(
             "Executing Claude Code command in session workspace: {:?} (new: {})",
             workspace, is_new_session
         );
 
-        let mut command = Command::new(&self.claude_binary);
-        command
-            .args([
-                "--print",
-                "--output-format",
-                "json",
-                "--model",
-                "sonnet",
-                "--permission-mode",
-                &self.config.permission_mode,
-            ])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(&workspace); // Always use session workspace
-
-        // 🔄 SESSION CONTINUITY STRATEGY: Smart session management for context preservation
-        // DECISION: Three-tier approach - explicit resume, new session, or continue
-        // Why: Balances context preservation with clean slate operations
-        // Alternative: Always new sessions (rejected: loses valuable context)
-        if let Some(sid) = session_id {
-            // Only use --resume if this is NOT a new session
-            // A new session means no existing conversation to resume
-            if !is_new_session {
-                command.args(["--resume", sid]); // Explicit session continuation
-                debug!("Resuming existing session: {}", sid);
-            } else {
-                // New session with ID - start fresh conversation
-                debug!("Starting new session with ID: {}", sid);
-            }
-        } else if is_new_session {
-            // For new sessions, don't add continue/resume flags
-            // 📝 REASONING: Clean slate prevents unexpected context interference
-        } else {
-            // For existing workspace without specific session ID, continue most recent
-            command.args(["--continue"]);
-            // ⚠️ RISK: May inherit unexpected context - monitor for side effects
-        }
-
-        // Add allowed tools if any are specified
-        if !self.config.allowed_tools.is_empty() {
-            let tools_str = self.config.allowed_tools.join(",");
-            command.args(["--allowedTools", &tools_str]);
-        }
-
-        // Add workspace directory to allowed directories
-        let workspace_str = workspace.to_string_lossy();
-        command.args(["--add-dir", &workspace_str]);
+        let mcp = mcp::prepare(&self.config.mcp_servers, &workspace, invocation_class).await?;
+        let mut command = self.session_command(
+            &workspace,
+            &session_mode,
+            permission_mode,
+            "json",
+            mcp.as_ref(),
+        );
 
+        let prompt = self.quota.annotate_prompt(prompt, &workspace).await;
+        let before = WorkspaceSnapshot::capture(&workspace).await;
         let mut child = command.spawn().map_err(|e| SpiralError::Agent {
             message: format!("Failed to spawn Claude Code process: {e}"),
         })?;
@@ -255,20 +404,28 @@ impl ClaudeCodeCliClient {
             })?;
         }
 
-        // Wait for completion and read output
-        let output = child
-            .wait_with_output()
-            .await
-            .map_err(|e| SpiralError::Agent {
-                message: format!("Claude Code process failed: {e}"),
-            })?;
+        // Wait for completion and read output; going over the workspace quota drops the
+        // child, and kill_on_drop stops Claude Code
+        let output = self
+            .quota
+            .enforce(&workspace, async {
+                child
+                    .wait_with_output()
+                    .await
+                    .map_err(|e| SpiralError::Agent {
+                        message: format!("Claude Code process failed: {e}"),
+                    })
+            })
+            .await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             warn!("Claude Code process failed: {stderr}");
 
             // Record failure in circuit breaker
-            self.circuit_breaker.record_failure().await;
+            self.circuit_breaker
+                .record_classified_failure(FailureClass::from_message(&stderr))
+                .await;
 
             return Err(SpiralError::Agent {
                 message: format!("Claude Code execution failed: {stderr}"),
@@ -296,12 +453,15 @@ impl ClaudeCodeCliClient {
         // Check for limitation messages and log them for improvement
         if let Err(e) = self.check_for_limitations(&response) {
             // Record failure if we hit limitations
-            self.circuit_breaker.record_failure().await;
+            self.record_error(&e).await;
             return Err(e);
         }
 
         // Record success in circuit breaker
         self.circuit_breaker.record_success().await;
+        if session_id.is_some() {
+            remember_claude_session(&workspace, &response.session_id).await;
+        }
 
         // Note: We intentionally don't clean up the workspace immediately
         // to allow for inspection of generated files if needed.
@@ -311,28 +471,53 @@ impl ClaudeCodeCliClient {
             workspace
         );
 
-        Ok(response)
+        let changes = WorkspaceSnapshot::capture(&workspace)
+            .await
+            .changes_since(&before);
+        Ok(CliRun {
+            response,
+            workspace,
+            changes,
+        })
     }
 
-    /// Get or create a workspace for a specific session
-    async fn get_or_create_session_workspace(
+    /// The session's workspace, seeded with the requested template when it is new
+    async fn prepare_workspace(
         &self,
         session_id: Option<&str>,
+        template: Option<&str>,
     ) -> Result<(PathBuf, bool)> {
+        let (workspace, is_new) = self.get_or_create_session_workspace(session_id).await?;
+        if let (true, Some(template)) = (is_new, template) {
+            if let Err(e) = self.templates.seed(template, &workspace).await {
+                // A half-seeded workspace would pass for an existing session next time
+                let _ = fs::remove_dir_all(&workspace).await;
+                return Err(e);
+            }
+        }
+        Ok((workspace, is_new))
+    }
+
+    /// Directory holding every Claude Code workspace
+    pub(crate) fn workspaces_dir(&self) -> Result<PathBuf> {
         let current_dir = std::env::current_dir().map_err(|e| SpiralError::Agent {
             message: format!("Failed to get current directory: {e}"),
         })?;
 
-        let base_workspace_dir = if let Some(working_dir) = &self.config.working_directory {
-            let working_path = PathBuf::from(working_dir);
-            if working_path.is_absolute() {
-                current_dir.join("claude-workspaces")
-            } else {
-                current_dir.join(working_path).join("claude-workspaces")
+        Ok(match &self.config.working_directory {
+            Some(working_dir) if !PathBuf::from(working_dir).is_absolute() => {
+                current_dir.join(working_dir).join("claude-workspaces")
             }
-        } else {
-            current_dir.join("claude-workspaces")
-        };
+            _ => current_dir.join("claude-workspaces"),
+        })
+    }
+
+    /// Get or create a workspace for a specific session
+    async fn get_or_create_session_workspace(
+        &self,
+        session_id: Option<&str>,
+    ) -> Result<(PathBuf, bool)> {
+        let base_workspace_dir = self.workspaces_dir()?;
 
         // Create base workspace directory if it doesn't exist
         if !base_workspace_dir.exists() {
@@ -344,8 +529,15 @@ impl ClaudeCodeCliClient {
         }
 
         let (workspace_path, is_new) = if let Some(sid) = session_id {
+            // 🛡️ SECURITY: The key becomes a directory name, so it must not traverse
+            if !sessions::is_valid_session_key(sid) {
+                return Err(SpiralError::Validation(format!(
+                    "Invalid session ID '{}': use letters, digits, '-' and '_' only",
+                    sid.chars().take(64).collect::<String>()
+                )));
+            }
             // Use specific session ID for workspace
-            let session_workspace = base_workspace_dir.join(format!("session-{sid}"));
+            let session_workspace = base_workspace_dir.join(format!("{SESSION_DIR_PREFIX}{sid}"));
             let is_new_session = !session_workspace.exists();
 
             if is_new_session {
@@ -363,7 +555,8 @@ impl ClaudeCodeCliClient {
         } else {
             // Create a new unique workspace if no session ID provided
             let workspace_id = Uuid::new_v4().to_string();
-            let workspace_path = base_workspace_dir.join(format!("workspace-{workspace_id}"));
+            let workspace_path =
+                base_workspace_dir.join(format!("{WORKSPACE_DIR_PREFIX}{workspace_id}"));
 
             fs::create_dir_all(&workspace_path)
                 .await
@@ -380,20 +573,7 @@ impl ClaudeCodeCliClient {
 
     /// Clean up old workspaces based on configuration
     pub async fn cleanup_old_workspaces(&self) -> Result<()> {
-        let current_dir = std::env::current_dir().map_err(|e| SpiralError::Agent {
-            message: format!("Failed to get current directory: {e}"),
-        })?;
-
-        let base_workspace_dir = if let Some(working_dir) = &self.config.working_directory {
-            let working_path = PathBuf::from(working_dir);
-            if working_path.is_absolute() {
-                current_dir.join("claude-workspaces")
-            } else {
-                current_dir.join(working_path).join("claude-workspaces")
-            }
-        } else {
-            current_dir.join("claude-workspaces")
-        };
+        let base_workspace_dir = self.workspaces_dir()?;
 
         if !base_workspace_dir.exists() {
             return Ok(());
@@ -419,6 +599,10 @@ impl ClaudeCodeCliClient {
             if !path.is_dir() {
                 continue;
             }
+            // Workspaces of user sessions are archived when their session ends instead
+            if managed_session_key(&entry.file_name().to_string_lossy()).is_some() {
+                continue;
+            }
 
             // Check if workspace is old enough to clean up
             if let Ok(metadata) = entry.metadata().await {
@@ -444,23 +628,87 @@ impl ClaudeCodeCliClient {
         Ok(())
     }
 
-    /// Get workspace usage statistics
-    pub async fn get_workspace_stats(&self) -> Result<WorkspaceStats> {
-        let current_dir = std::env::current_dir().map_err(|e| SpiralError::Agent {
-            message: format!("Failed to get current directory: {e}"),
-        })?;
+    /// Size in whole MB of a session's workspace; 0 before a task has created it
+    pub async fn session_workspace_mb(&self, session_key: &str) -> Result<u64> {
+        if !sessions::is_valid_session_key(session_key) {
+            return Err(SpiralError::Validation(format!(
+                "Invalid session ID '{session_key}'"
+            )));
+        }
+        let workspace = self
+            .workspaces_dir()?
+            .join(format!("{SESSION_DIR_PREFIX}{session_key}"));
+        Ok(quota::scan_workspace(&workspace).await.bytes / BYTES_PER_MB)
+    }
 
-        let base_workspace_dir = if let Some(working_dir) = &self.config.working_directory {
-            let working_path = PathBuf::from(working_dir);
-            if working_path.is_absolute() {
-                current_dir.join("claude-workspaces")
-            } else {
-                current_dir.join(working_path).join("claude-workspaces")
+    /// 🗂️ MANAGED WORKSPACES: Session keys of the workspaces on disk that user sessions own
+    pub async fn managed_session_keys(&self) -> Result<Vec<String>> {
+        let base_workspace_dir = self.workspaces_dir()?;
+        let mut entries = match fs::read_dir(&base_workspace_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(SpiralError::Agent {
+                    message: format!("Failed to read workspace directory: {e}"),
+                })
             }
-        } else {
-            current_dir.join("claude-workspaces")
         };
 
+        let mut keys = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| SpiralError::Agent {
+            message: format!("Failed to read workspace entry: {e}"),
+        })? {
+            if let Some(key) = managed_session_key(&entry.file_name().to_string_lossy()) {
+                keys.push(key.to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// 🔒 SESSION WORKSPACE LOCK: Exclusive use of a session's workspace, if no run holds it
+    /// A run waiting for the session starts only once the guard is dropped
+    pub fn try_lock_session(&self, session_key: &str) -> Option<tokio::sync::OwnedMutexGuard<()>> {
+        self.session_locks.try_acquire(session_key)
+    }
+
+    /// 📦 SESSION WORKSPACE RELEASE: Archive the workspace of an ended user session next to
+    /// the workspaces directory, then remove it
+    /// Returns None when a run is still using the workspace, or it is already gone
+    pub async fn archive_session_workspace(&self, session_key: &str) -> Result<Option<PathBuf>> {
+        if !sessions::is_valid_session_key(session_key) {
+            return Err(SpiralError::Validation(format!(
+                "Invalid session ID '{session_key}'"
+            )));
+        }
+        // Holding the session lock keeps a queued task from starting in it meanwhile
+        let Some(_session_guard) = self.session_locks.try_acquire(session_key) else {
+            debug!(
+                "Session workspace {} is in use; archiving it later",
+                session_key
+            );
+            return Ok(None);
+        };
+
+        let base_workspace_dir = self.workspaces_dir()?;
+        let workspace_id = format!("{SESSION_DIR_PREFIX}{session_key}");
+        let workspace = base_workspace_dir.join(&workspace_id);
+        if !workspace.is_dir() {
+            return Ok(None);
+        }
+        let archive_dir = base_workspace_dir.with_file_name(WORKSPACE_ARCHIVE_DIR);
+        let archived = tokio::task::spawn_blocking(move || {
+            workspace_archive::archive_workspace(&workspace, &archive_dir, &workspace_id)
+        })
+        .await
+        .map_err(|e| SpiralError::SystemError(format!("Workspace archive task failed: {e}")))?
+        .map_err(|e| SpiralError::SystemError(format!("Failed to archive workspace: {e}")))?;
+        Ok(Some(archived.archive_path))
+    }
+
+    /// Get workspace usage statistics
+    pub async fn get_workspace_stats(&self) -> Result<WorkspaceStats> {
+        let base_workspace_dir = self.workspaces_dir()?;
+
         if !base_workspace_dir.exists() {
             return Ok(WorkspaceStats {
                 total_workspaces: 0,
@@ -637,68 +885,72 @@ impl ClaudeCodeCliClient {
     }
 
     /// Execute command with fallback permission modes if initial attempt fails
-    async fn execute_with_fallback(&self, prompt: &str) -> Result<ClaudeCodeCliResponse> {
-        self.execute_with_fallback_and_session(prompt, None).await
-    }
-
-    /// Execute command with fallback permission modes and session support
-    async fn execute_with_fallback_and_session(
+    /// `invocation_class` is the invocation pool queue to wait in, see pool::invocation_class
+    async fn execute_with_fallback(
         &self,
         prompt: &str,
-        session_id: Option<&str>,
+        invocation_class: &str,
     ) -> Result<ClaudeCodeCliResponse> {
-        let (response, _) = self
-            .execute_with_fallback_and_session_info(prompt, session_id)
+        let run = self
+            .execute_with_fallback_and_session_info(
+                prompt,
+                &self.config.permission_mode,
+                None,
+                None,
+                invocation_class,
+            )
             .await?;
-        Ok(response)
+        Ok(run.response)
     }
 
-    /// Execute command with fallback and return both response and workspace info
+    /// Execute command with fallback and return the response with its workspace and changes
+    /// `template` names the workspace template a new workspace is seeded with
     async fn execute_with_fallback_and_session_info(
         &self,
         prompt: &str,
+        permission_mode: &str,
         session_id: Option<&str>,
-    ) -> Result<(ClaudeCodeCliResponse, PathBuf)> {
+        template: Option<&str>,
+        invocation_class: &str,
+    ) -> Result<CliRun> {
+        // Related tasks share the session; they take turns rather than overlap
+        let _session_guard = self.session_locks.acquire(session_id).await;
+        // Taken after the session lock, so a task queued behind its session holds no slot;
+        // the permission fallback below reuses it
+        let _invocation = self.invocations.acquire(invocation_class).await;
+
         // Try with configured permissions first
         // Note: workspace will be created inside execute_claude_command_with_session
         match self
-            .execute_claude_command_with_session(prompt, session_id)
+            .execute_claude_command_with_session(
+                prompt,
+                permission_mode,
+                session_id,
+                template,
+                invocation_class,
+            )
             .await
         {
-            Ok(response) => {
-                // Get workspace path for return value
-                let (workspace, _) = self.get_or_create_session_workspace(session_id).await?;
-                Ok((response, workspace))
-            }
+            Ok(run) => Ok(run),
             Err(e) => {
                 // If it failed due to permissions, try with more permissive mode
-                if e.to_string().contains("permission") || e.to_string().contains("access") {
+                // A dry run never escalates: that would let it write after all
+                if is_permission_failure(&e) && permission_mode != PLAN_PERMISSION_MODE {
                     // 🔓 PERMISSION ESCALATION DECISION: Auto-retry with elevated permissions
                     // 🛡️ SECURITY AUDIT CHECKPOINT: Permission bypass activation
                     // Why: User experience - avoid manual retry for common permission issues
                     // Risk: May execute with higher privileges than intended
                     // Mitigation: Log security event, monitor for abuse patterns
-                    // 🚨 SECURITY AUDIT LOG: Permission bypass activation
-                    // CRITICAL: This event must be monitored for abuse patterns
-                    // MITIGATION: Log all relevant context for security analysis
-                    warn!(
-                        "SECURITY EVENT: Permission bypass activated - reason: initial_execution_failed, prompt_length: {}, session_id: {:?}, timestamp: {}",
-                        prompt.len(),
-                        session_id.unwrap_or("none"),
-                        chrono::Utc::now().to_rfc3339()
-                    );
-                    warn!("Initial execution failed due to permissions, retrying with bypassPermissions mode");
-
-                    let response = self
-                        .execute_claude_command_with_permissions_and_session(
-                            prompt,
-                            "bypassPermissions",
-                            session_id,
-                        )
-                        .await?;
-                    // Get workspace path for return value
-                    let (workspace, _) = self.get_or_create_session_workspace(session_id).await?;
-                    Ok((response, workspace))
+                    log_permission_bypass(prompt, session_id);
+
+                    self.execute_claude_command_with_permissions_and_session(
+                        prompt,
+                        "bypassPermissions",
+                        session_id,
+                        template,
+                        invocation_class,
+                    )
+                    .await
                 } else {
                     Err(e)
                 }
@@ -712,61 +964,26 @@ impl ClaudeCodeCliClient {
         prompt: &str,
         permission_mode: &str,
         session_id: Option<&str>,
-    ) -> Result<ClaudeCodeCliResponse> {
+        template: Option<&str>,
+        invocation_class: &str,
+    ) -> Result<CliRun> {
         // Create or get workspace for this session (fallback)
-        let (workspace, is_new_session) = self.get_or_create_session_workspace(session_id).await?;
+        let (workspace, is_new_session) = self.prepare_workspace(session_id, template).await?;
+        let session_mode = sessions::session_mode(&workspace, is_new_session).await;
 
         debug!("Executing Claude Code command with permission mode: {} in session workspace: {:?} (new: {})", permission_mode, workspace, is_new_session);
 
-        let mut command = Command::new(&self.claude_binary);
-        command
-            .args([
-                "--print",
-                "--output-format",
-                "json",
-                "--model",
-                "sonnet",
-                "--permission-mode",
-                permission_mode,
-            ])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(&workspace); // Always use session workspace
-
-        // 🔄 SESSION CONTINUITY STRATEGY: Smart session management for context preservation
-        // DECISION: Three-tier approach - explicit resume, new session, or continue
-        // Why: Balances context preservation with clean slate operations
-        // Alternative: Always new sessions (rejected: loses valuable context)
-        if let Some(sid) = session_id {
-            // Only use --resume if this is NOT a new session
-            // A new session means no existing conversation to resume
-            if !is_new_session {
-                command.args(["--resume", sid]); // Explicit session continuation
-                debug!("Resuming existing session: {}", sid);
-            } else {
-                // New session with ID - start fresh conversation
-                debug!("Starting new session with ID: {}", sid);
-            }
-        } else if is_new_session {
-            // For new sessions, don't add continue/resume flags
-            // 📝 REASONING: Clean slate prevents unexpected context interference
-        } else {
-            // For existing workspace without specific session ID, continue most recent
-            command.args(["--continue"]);
-            // ⚠️ RISK: May inherit unexpected context - monitor for side effects
-        }
-
-        // Add allowed tools
-        if !self.config.allowed_tools.is_empty() {
-            let tools_str = self.config.allowed_tools.join(",");
-            command.args(["--allowedTools", &tools_str]);
-        }
-
-        // Add workspace directory to allowed directories
-        let workspace_str = workspace.to_string_lossy();
-        command.args(["--add-dir", &workspace_str]);
+        let mcp = mcp::prepare(&self.config.mcp_servers, &workspace, invocation_class).await?;
+        let mut command = self.session_command(
+            &workspace,
+            &session_mode,
+            permission_mode,
+            "json",
+            mcp.as_ref(),
+        );
 
+        let prompt = self.quota.annotate_prompt(prompt, &workspace).await;
+        let before = WorkspaceSnapshot::capture(&workspace).await;
         let mut child = command.spawn().map_err(|e| SpiralError::Agent {
             message: format!("Failed to spawn Claude Code process: {e}"),
         })?;
@@ -788,13 +1005,19 @@ impl ClaudeCodeCliClient {
             })?;
         }
 
-        // Wait for completion and read output
-        let output = child
-            .wait_with_output()
-            .await
-            .map_err(|e| SpiralError::Agent {
-                message: format!("Claude Code process failed: {e}"),
-            })?;
+        // Wait for completion and read output; going over the workspace quota drops the
+        // child, and kill_on_drop stops Claude Code
+        let output = self
+            .quota
+            .enforce(&workspace, async {
+                child
+                    .wait_with_output()
+                    .await
+                    .map_err(|e| SpiralError::Agent {
+                        message: format!("Claude Code process failed: {e}"),
+                    })
+            })
+            .await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -816,6 +1039,9 @@ impl ClaudeCodeCliClient {
 
         // Check for limitation messages and log them for improvement
         self.check_for_limitations(&response)?;
+        if session_id.is_some() {
+            remember_claude_session(&workspace, &response.session_id).await;
+        }
 
         // Log workspace info for fallback execution
         info!(
@@ -823,7 +1049,14 @@ impl ClaudeCodeCliClient {
             workspace
         );
 
-        Ok(response)
+        let changes = WorkspaceSnapshot::capture(&workspace)
+            .await
+            .changes_since(&before);
+        Ok(CliRun {
+            response,
+            workspace,
+            changes,
+        })
     }
 
     /// Generate code using Claude Code CLI
@@ -843,43 +1076,406 @@ impl ClaudeCodeCliClient {
         session_id: Option<&str>,
     ) -> Result<CodeGenerationResult> {
         info!("Generating code for language: {}", request.language);
-
-        // 🛡️ INPUT VALIDATION: Critical security boundary
-        // Validate description content for safety
-        let _sanitized_description = self
-            .validator
-            .validate_and_sanitize_task_content(&request.description)
-            .map_err(|e| SpiralError::Validation(format!("Invalid request content: {e}")))?;
-
-        // Validate context keys and values
-        for (key, value) in &request.context {
-            self.validator.validate_context_key(key).map_err(|e| {
-                SpiralError::Validation(format!("Invalid context key '{key}': {e}"))
-            })?;
-            let _sanitized_value = self
-                .validator
-                .validate_and_sanitize_context_value(value)
-                .map_err(|e| {
-                    SpiralError::Validation(format!("Invalid context value for '{key}': {e}"))
-                })?;
-        }
+        self.validate_generation_request(&request)?;
 
         // Build comprehensive prompt
         let prompt = self.build_generation_prompt(&request);
 
+        // Only sessionless requests are cached: a session's answer depends on its history
+        let cache_key = session_id
+            .is_none()
+            .then(|| Self::generation_cache_key(&prompt, &request));
+        if let Some(key) = &cache_key {
+            if let Some(CachedResponse::Generation(result)) = self.response_cache.get(key).await {
+                info!("Serving code generation from the response cache");
+                return Ok(result);
+            }
+        }
+
         let request_start = std::time::Instant::now();
-        let (response, workspace_path) = self
-            .execute_with_fallback_and_session_info(&prompt, session_id)
-            .await?;
+        let permission_mode = self.permission_mode_for(&request);
+        let run = self
+            .execute_with_fallback_and_session_info(
+                &prompt,
+                permission_mode,
+                session_id,
+                workspace_template(&request),
+                &invocation_class(&request.context),
+            )
+            .await;
         let duration = request_start.elapsed();
+        self.record_transcript(
+            &request.context,
+            &prompt,
+            permission_mode,
+            run.as_ref().map(|run| &run.response),
+            duration,
+        )
+        .await;
+        let CliRun {
+            response,
+            workspace,
+            changes,
+        } = run?;
 
         info!(
             "Claude Code CLI call completed - Duration: {:?}ms, Cost: ${:.4}",
             duration.as_millis(),
             response.total_cost_usd
         );
+        self.costs.record(
+            Usage::from(&response),
+            &CostAttribution::from_context(&request.context),
+        );
+
+        let mut result = self.parse_code_generation_response(
+            response,
+            request.language,
+            session_id,
+            &workspace,
+            changes,
+        )?;
+        result.dry_run = request.dry_run;
+        if let Some(key) = cache_key {
+            if Self::is_small_generation(&result) {
+                self.response_cache
+                    .insert(key, CachedResponse::Generation(result.clone()))
+                    .await;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Keyed on the rendered prompt, so an edited prompt template is not answered from
+    /// the cache of the old one
+    fn generation_cache_key(prompt: &str, request: &CodeGenerationRequest) -> String {
+        cache_key("generation", &[&request.language, prompt], &request.context)
+    }
+
+    /// Whether a result is small enough to be worth caching
+    fn is_small_generation(result: &CodeGenerationResult) -> bool {
+        let size = result.code.len()
+            + result.explanation.len()
+            + result
+                .files_to_create
+                .iter()
+                .map(|file| file.content.len())
+                .sum::<usize>()
+            + result
+                .files_to_modify
+                .iter()
+                .map(|file| file.changes.len())
+                .sum::<usize>();
+        size <= CLAUDE_CACHE_MAX_GENERATION_BYTES
+    }
+
+    /// The Claude CLI this client runs, as found or configured
+    pub fn claude_binary(&self) -> &str {
+        &self.claude_binary
+    }
+
+    /// The container every command run in a workspace goes through, when configured
+    pub fn sandbox(&self) -> &Sandbox {
+        &self.sandbox
+    }
+
+    /// Running and queued Claude Code processes across this client's clones
+    pub fn invocation_pool_metrics(&self) -> InvocationPoolMetrics {
+        self.invocations.metrics()
+    }
+
+    /// Hits, misses and size of the response cache shared by this client's clones
+    pub fn response_cache_metrics(&self) -> ResponseCacheMetrics {
+        self.response_cache.metrics()
+    }
+
+    fn validate_generation_request(&self, request: &CodeGenerationRequest) -> Result<()> {
+        validate_generation_request(&self.validator, request)?;
+        // An unknown template fails the request now, not after it waited for a slot
+        match workspace_template(request) {
+            Some(template) => self.templates.validate(template),
+            None => Ok(()),
+        }
+    }
+
+    /// Spend of every call made through this client and its clones
+    pub fn costs(&self) -> &Arc<CostTracker> {
+        &self.costs
+    }
+
+    /// The Claude Code CLI in use and what it is too old for
+    pub fn cli_status(&self) -> ClaudeCliStatus {
+        self.cli.status(&self.claude_binary)
+    }
+
+    /// Whether the circuit breaker is currently refusing requests
+    pub async fn circuit_open(&self) -> bool {
+        self.circuit_breaker.is_rejecting().await
+    }
+
+    /// 🌊 STREAMING GENERATION: generate_code, but yielding events while Claude works
+    /// 🏗️ ARCHITECTURE DECISION: The CLI is driven by a spawned task feeding a bounded channel
+    /// Why: stdout and stderr must keep draining even while the consumer is busy, or the
+    /// CLI stalls on a full pipe; dropping the stream closes the channel, which ends the
+    /// task and kills the process via kill_on_drop
+    /// Alternative: Parse stdout lazily inside the stream (rejected: stderr would fill up
+    /// while nobody polls)
+    /// Invalid requests and an open circuit are returned as Err before anything runs;
+    /// later failures arrive as a final GenerationEvent::Failed or QuotaExceeded
+    pub async fn generate_code_stream(
+        &self,
+        request: CodeGenerationRequest,
+    ) -> Result<impl Stream<Item = GenerationEvent> + Send> {
+        info!(
+            "Streaming code generation for language: {}",
+            request.language
+        );
+        self.validate_generation_request(&request)?;
+
+        if !self.circuit_breaker.should_allow_request().await {
+            warn!("Circuit breaker is open - Claude Code service is unavailable");
+            return Err(SpiralError::Agent {
+                message: "Claude Code service is temporarily unavailable due to repeated failures"
+                    .to_string(),
+            });
+        }
+
+        let (events, receiver) = mpsc::channel(CLAUDE_STREAM_EVENT_BUFFER);
+        let client = self.clone();
+        if self.cli.supports(CliFeature::StreamJson) {
+            let prompt = self.build_generation_prompt(&request);
+            tokio::spawn(async move {
+                client
+                    .drive_generation_stream(prompt, request, events)
+                    .await;
+            });
+        } else {
+            // 🔖 OLD CLI: Without stream-json the only event is the finished generation
+            tokio::spawn(async move {
+                let event = match client.generate_code(request).await {
+                    Ok(result) => GenerationEvent::Completed(result),
+                    Err(e) => GenerationEvent::failure(e),
+                };
+                let _ = events.send(event).await;
+            });
+        }
+
+        Ok(stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        }))
+    }
+
+    /// Run a streaming generation to the end, with the same permission fallback as
+    /// generate_code, and finish with Completed, Failed or QuotaExceeded
+    async fn drive_generation_stream(
+        self,
+        prompt: String,
+        request: CodeGenerationRequest,
+        events: mpsc::Sender<GenerationEvent>,
+    ) {
+        let session_id = request.session_id.as_deref();
+        let _session_guard = self.session_locks.acquire(session_id).await;
+        let class = invocation_class(&request.context);
+        let _invocation = self.invocations.acquire(&class).await;
+        let request_start = std::time::Instant::now();
+
+        let mut permission_mode = self.permission_mode_for(&request);
+        let template = workspace_template(&request);
+        let mut outcome = self
+            .stream_claude_command(
+                &prompt,
+                permission_mode,
+                session_id,
+                template,
+                &class,
+                &events,
+            )
+            .await;
+        if permission_mode != PLAN_PERMISSION_MODE
+            && matches!(&outcome, Err(e) if is_permission_failure(e))
+        {
+            log_permission_bypass(&prompt, session_id);
+            permission_mode = "bypassPermissions";
+            outcome = self
+                .stream_claude_command(
+                    &prompt,
+                    permission_mode,
+                    session_id,
+                    template,
+                    &class,
+                    &events,
+                )
+                .await;
+        }
+        self.record_transcript(
+            &request.context,
+            &prompt,
+            permission_mode,
+            outcome.as_ref().map(|run| &run.response),
+            request_start.elapsed(),
+        )
+        .await;
+
+        let final_event = outcome
+            .and_then(|run| {
+                info!(
+                    "Claude Code CLI stream completed - Duration: {:?}ms, Cost: ${:.4}",
+                    request_start.elapsed().as_millis(),
+                    run.response.total_cost_usd
+                );
+                self.costs.record(
+                    Usage::from(&run.response),
+                    &CostAttribution::from_context(&request.context),
+                );
+                self.parse_code_generation_response(
+                    run.response,
+                    request.language.clone(),
+                    session_id,
+                    &run.workspace,
+                    run.changes,
+                )
+            })
+            .map(|mut result| {
+                result.dry_run = request.dry_run;
+                result
+            })
+            .map_or_else(GenerationEvent::failure, GenerationEvent::Completed);
+        // A send error only means the consumer already went away
+        let _ = events.send(final_event).await;
+    }
+
+    /// Run the CLI with stream-json output, forwarding events as lines arrive
+    async fn stream_claude_command(
+        &self,
+        prompt: &str,
+        permission_mode: &str,
+        session_id: Option<&str>,
+        template: Option<&str>,
+        invocation_class: &str,
+        events: &mpsc::Sender<GenerationEvent>,
+    ) -> Result<CliRun> {
+        let (workspace, is_new_session) = self.prepare_workspace(session_id, template).await?;
+        let session_mode = sessions::session_mode(&workspace, is_new_session).await;
+        debug!(
+            "Streaming Claude Code command with permission mode: {} in session workspace: {:?} (new: {})",
+            permission_mode, workspace, is_new_session
+        );
+
+        let prompt = self.quota.annotate_prompt(prompt, &workspace).await;
+        let mcp = mcp::prepare(&self.config.mcp_servers, &workspace, invocation_class).await?;
+        let before = WorkspaceSnapshot::capture(&workspace).await;
+        let mut child = self
+            .session_command(
+                &workspace,
+                &session_mode,
+                permission_mode,
+                "stream-json",
+                mcp.as_ref(),
+            )
+            .spawn()
+            .map_err(|e| SpiralError::Agent {
+                message: format!("Failed to spawn Claude Code process: {e}"),
+            })?;
+
+        // 📝 STDIN COMMUNICATION: Same prompt handling as the blocking path
+        // Stdin is dropped afterwards so the CLI sees end of input and starts working
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(prompt.as_bytes())
+                .await
+                .map_err(|e| SpiralError::Agent {
+                    message: format!("Failed to write to Claude Code stdin: {e}"),
+                })?;
+            stdin.flush().await.map_err(|e| SpiralError::Agent {
+                message: format!("Failed to flush Claude Code stdin: {e}"),
+            })?;
+        }
 
-        self.parse_code_generation_response(response, request.language, session_id, &workspace_path)
+        let stderr = child.stderr.take().map(|mut stderr| {
+            tokio::spawn(async move {
+                let mut output = String::new();
+                let _ = stderr.read_to_string(&mut output).await;
+                output
+            })
+        });
+        let stdout = child.stdout.take().ok_or_else(|| SpiralError::Agent {
+            message: "Claude Code stdout was not captured".to_string(),
+        })?;
+
+        // Returning early from here, including for the workspace quota, drops the child,
+        // which kills the CLI
+        let (response, status) = self
+            .quota
+            .enforce(&workspace, async {
+                let mut lines = BufReader::new(stdout).lines();
+                let mut response = None;
+                while let Some(line) = lines.next_line().await.map_err(|e| SpiralError::Agent {
+                    message: format!("Failed to read Claude Code output: {e}"),
+                })? {
+                    for parsed in parse_stream_line(&line) {
+                        match parsed {
+                            StreamLine::Event(event) => {
+                                if events.send(event).await.is_err() {
+                                    // Nobody is listening any more
+                                    return Err(SpiralError::Agent {
+                                        message: "Generation stream was dropped".to_string(),
+                                    });
+                                }
+                            }
+                            StreamLine::Result(result) => response = Some(*result),
+                        }
+                    }
+                }
+
+                let status = child.wait().await.map_err(|e| SpiralError::Agent {
+                    message: format!("Claude Code process failed: {e}"),
+                })?;
+                Ok((response, status))
+            })
+            .await?;
+        let stderr = match stderr {
+            Some(handle) => handle.await.unwrap_or_default(),
+            None => String::new(),
+        };
+
+        if !status.success() {
+            warn!("Claude Code process failed: {stderr}");
+            self.circuit_breaker
+                .record_classified_failure(FailureClass::from_message(&stderr))
+                .await;
+            return Err(SpiralError::Agent {
+                message: format!("Claude Code execution failed: {stderr}"),
+            });
+        }
+
+        let Some(response) = response else {
+            self.circuit_breaker.record_failure().await;
+            return Err(SpiralError::Agent {
+                message: "Claude Code stream ended without a result".to_string(),
+            });
+        };
+
+        if let Err(e) = self.check_for_limitations(&response) {
+            self.record_error(&e).await;
+            return Err(e);
+        }
+        self.circuit_breaker.record_success().await;
+        if session_id.is_some() {
+            remember_claude_session(&workspace, &response.session_id).await;
+        }
+
+        info!(
+            "Claude Code streaming execution completed in workspace: {:?}",
+            workspace
+        );
+        let changes = WorkspaceSnapshot::capture(&workspace)
+            .await
+            .changes_since(&before);
+        Ok(CliRun {
+            response,
+            workspace,
+            changes,
+        })
     }
 
     /// Detect programming language using Claude Code CLI
@@ -892,7 +1488,11 @@ impl ClaudeCodeCliClient {
              Context: {context}\n\nCode:\n```\n{code_snippet}\n```"
         );
 
-        let response = self.execute_with_fallback(&prompt).await?;
+        let response = self
+            .execute_with_fallback(&prompt, UNATTRIBUTED_INVOCATION)
+            .await?;
+        self.costs
+            .record(Usage::from(&response), &CostAttribution::default());
 
         // Extract just the language name from the response
         let language = response
@@ -918,8 +1518,18 @@ impl ClaudeCodeCliClient {
             task_description.chars().take(100).collect::<String>()
         );
 
+        let cache_key = cache_key("analysis", &[task_description], &context);
+        if let Some(CachedResponse::Analysis(analysis)) = self.response_cache.get(&cache_key).await
+        {
+            info!("Serving task analysis from the response cache");
+            return Ok(analysis);
+        }
+
+        // Sorted so identical requests send identical prompts
         let context_str = context
             .iter()
+            .collect::<std::collections::BTreeMap<_, _>>()
+            .into_iter()
             .map(|(k, v)| format!("{k}: {v}"))
             .collect::<Vec<_>>()
             .join("\n");
@@ -936,71 +1546,81 @@ impl ClaudeCodeCliClient {
              5. Suggested approach"
         );
 
-        let response = self.execute_with_fallback(&prompt).await?;
+        let request_start = std::time::Instant::now();
+        let response = self
+            .execute_with_fallback(&prompt, &invocation_class(&context))
+            .await;
+        self.record_transcript(
+            &context,
+            &prompt,
+            &self.config.permission_mode,
+            response.as_ref(),
+            request_start.elapsed(),
+        )
+        .await;
+        let response = response?;
+        self.costs.record(
+            Usage::from(&response),
+            &CostAttribution::from_context(&context),
+        );
 
-        Ok(TaskAnalysis {
+        let analysis = TaskAnalysis {
             complexity: self.extract_complexity(&response.result),
             estimated_minutes: self.extract_time_estimate(&response.result),
             required_skills: self.extract_required_skills(&response.result),
             challenges: self.extract_challenges(&response.result),
             approach: self.extract_approach(&response.result),
             raw_analysis: response.result,
-        })
+        };
+        self.response_cache
+            .insert(cache_key, CachedResponse::Analysis(analysis.clone()))
+            .await;
+        Ok(analysis)
     }
 
-    fn build_generation_prompt(&self, request: &CodeGenerationRequest) -> String {
-        let mut prompt = format!(
-            "You are a Software Developer Agent in the Spiral Core orchestration system. \
-             Generate high-quality {} code following these principles:\n\n\
-             1. Follow SOLID principles\n\
-             2. Apply DRY principle\n\
-             3. Use SID naming (Short, Intuitive, Descriptive)\n\
-             4. Ensure compile-time safety and error handling\n\
-             5. Include comprehensive documentation\n\
-             6. Follow language-specific best practices\n\
-             7. Implement security best practices\n\n\
-             Task: {}\n\n",
-            request.language, request.description
-        );
-
-        // Add context
-        if !request.context.is_empty() {
-            prompt.push_str("Context:\n");
-            for (key, value) in &request.context {
-                prompt.push_str(&format!("- {key}: {value}\n"));
-            }
-            prompt.push('\n');
-        }
+    /// Add an invocation to the transcript of the task named in `context`
+    async fn record_transcript(
+        &self,
+        context: &HashMap<String, String>,
+        prompt: &str,
+        permission_mode: &str,
+        outcome: std::result::Result<&ClaudeCodeCliResponse, &SpiralError>,
+        duration: std::time::Duration,
+    ) {
+        let (response, error) = match outcome {
+            Ok(response) => (Some(response), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let entry = TranscriptEntry {
+            timestamp: chrono::Utc::now(),
+            agent_type: context.get("agent_type").cloned(),
+            session_id: response.map(|response| response.session_id.clone()),
+            permission_mode: permission_mode.to_string(),
+            prompt: prompt.to_string(),
+            response: response.map(|response| response.result.clone()),
+            error,
+            cost_usd: response.map(|response| response.total_cost_usd),
+            duration_ms: duration.as_millis() as u64,
+        };
+        self.transcripts.record(context, entry).await;
+    }
 
-        // Add requirements
-        if !request.requirements.is_empty() {
-            prompt.push_str("Requirements:\n");
-            for requirement in &request.requirements {
-                prompt.push_str(&format!("- {requirement}\n"));
-            }
-            prompt.push('\n');
-        }
+    /// The redacted Claude Code transcripts of tasks run through this client
+    pub fn transcripts(&self) -> &TranscriptStore {
+        &self.transcripts
+    }
 
-        // Add existing code if provided
-        if let Some(existing) = &request.existing_code {
-            prompt.push_str(&format!(
-                "Existing code to modify:\n```{}\n{}\n```\n\n",
-                request.language, existing
-            ));
+    /// Dry runs plan in plan mode; everything else runs in the configured mode
+    fn permission_mode_for(&self, request: &CodeGenerationRequest) -> &str {
+        if request.dry_run {
+            PLAN_PERMISSION_MODE
+        } else {
+            &self.config.permission_mode
         }
+    }
 
-        prompt.push_str(
-            "IMPORTANT: After implementing the code, you MUST:\n\
-             1. Verify that the code compiles without errors\n\
-             2. Run all tests and ensure they pass\n\
-             3. Fix any compilation errors or test failures\n\
-             4. If creating a package, verify it builds successfully\n\
-             5. Ensure type safety and no unused imports\n\n\
-             Use the available tools to compile, test, and validate your implementation. \
-             Do not consider the task complete until the code compiles cleanly and all tests pass.\n\n\
-             Provide the complete implementation with explanations."
-        );
-        prompt
+    fn build_generation_prompt(&self, request: &CodeGenerationRequest) -> String {
+        prompts::render_request("generation", request)
     }
 
     fn parse_code_generation_response(
@@ -1009,18 +1629,38 @@ impl ClaudeCodeCliClient {
         language: String,
         session_id: Option<&str>,
         workspace_path: &Path,
+        changes: Vec<FileChange>,
     ) -> Result<CodeGenerationResult> {
         let result_text = &response.result;
 
         // Extract code blocks from the response
         let code = self.extract_code_block(result_text, &language);
 
-        // ARCHITECTURE DECISION: Claude Code CLI returns unstructured text responses
-        // Why: Current CLI doesn't provide structured file operation metadata
-        // Alternative: Parse response text for file operations (future enhancement)
-        // Current: Return empty vectors until structured output is available
-        let files_to_create = Vec::new();
-        let files_to_modify = Vec::new();
+        // ARCHITECTURE DECISION: File operations come from the workspace, not the text
+        // Why: The CLI reports no structured file operations; the before/after workspace
+        // snapshots show what was actually written
+        let mut files_to_create = Vec::new();
+        let mut files_to_modify = Vec::new();
+        for change in &changes {
+            let content = change.content.clone().unwrap_or_default();
+            match change.kind {
+                FileChangeKind::Created => files_to_create.push(FileCreation {
+                    path: change.path.clone(),
+                    content,
+                    description: format!("{} lines", change.lines_added),
+                }),
+                FileChangeKind::Modified => files_to_modify.push(FileModification {
+                    path: change.path.clone(),
+                    changes: content,
+                    description: format!("+{} -{}", change.lines_added, change.lines_removed),
+                }),
+                FileChangeKind::Deleted => files_to_modify.push(FileModification {
+                    path: change.path.clone(),
+                    changes: content,
+                    description: "deleted".to_string(),
+                }),
+            }
+        }
 
         Ok(CodeGenerationResult {
             code,
@@ -1033,6 +1673,8 @@ impl ClaudeCodeCliClient {
                 Some(response.session_id.clone())
             }),
             workspace_path: workspace_path.to_string_lossy().to_string(),
+            changes,
+            dry_run: false,
         })
     }
 
@@ -1137,6 +1779,18 @@ impl ClaudeCodeCliClient {
         "Implement using best practices and modular design".to_string()
     }
 
+    /// Count a failed call against the breaker as its error class dictates
+    async fn record_error(&self, error: &SpiralError) {
+        if let Some(class) = FailureClass::classify(error) {
+            self.circuit_breaker.record_classified_failure(class).await;
+        }
+    }
+
+    /// Every backend's circuit breaker, this client's included
+    pub fn circuit_breakers(&self) -> &CircuitBreakerRegistry {
+        &self.circuit_breakers
+    }
+
     /// Get circuit breaker status and metrics
     pub async fn get_circuit_breaker_metrics(
         &self,
@@ -1159,7 +1813,10 @@ impl ClaudeCodeCliClient {
         let test_prompt = "Respond with just 'ok' to confirm connectivity.";
 
         let start = std::time::Instant::now();
-        match self.execute_with_fallback(test_prompt).await {
+        match self
+            .execute_with_fallback(test_prompt, UNATTRIBUTED_INVOCATION)
+            .await
+        {
             Ok(response) => {
                 let elapsed = start.elapsed();
                 debug!("Claude API connectivity test succeeded in {:?}", elapsed);
@@ -1182,7 +1839,61 @@ impl ClaudeCodeCliClient {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 🛡️ INPUT VALIDATION: Critical security boundary
+/// Description and every context entry must pass before anything reaches a model,
+/// whichever backend serves the request
+pub(crate) fn validate_generation_request(
+    validator: &TaskContentValidator,
+    request: &CodeGenerationRequest,
+) -> Result<()> {
+    // Validate description content for safety
+    let _sanitized_description = validator
+        .validate_and_sanitize_task_content(&request.description)
+        .map_err(|e| SpiralError::Validation(format!("Invalid request content: {e}")))?;
+
+    // Validate context keys and values
+    for (key, value) in &request.context {
+        validator
+            .validate_context_key(key)
+            .map_err(|e| SpiralError::Validation(format!("Invalid context key '{key}': {e}")))?;
+        let _sanitized_value = validator
+            .validate_and_sanitize_context_value(value)
+            .map_err(|e| {
+                SpiralError::Validation(format!("Invalid context value for '{key}': {e}"))
+            })?;
+    }
+    Ok(())
+}
+
+/// Failures that the permission fallback retries with bypassPermissions
+/// The workspace template the request's context names, if any
+fn workspace_template(request: &CodeGenerationRequest) -> Option<&str> {
+    request
+        .context
+        .get(WORKSPACE_TEMPLATE_CONTEXT_KEY)
+        .map(String::as_str)
+        .filter(|template| !template.trim().is_empty())
+}
+
+fn is_permission_failure(error: &SpiralError) -> bool {
+    let message = error.to_string();
+    message.contains("permission") || message.contains("access")
+}
+
+/// 🚨 SECURITY AUDIT LOG: Permission bypass activation
+/// CRITICAL: This event must be monitored for abuse patterns
+/// MITIGATION: Log all relevant context for security analysis
+fn log_permission_bypass(prompt: &str, session_id: Option<&str>) {
+    warn!(
+        "SECURITY EVENT: Permission bypass activated - reason: initial_execution_failed, prompt_length: {}, session_id: {:?}, timestamp: {}",
+        prompt.len(),
+        session_id.unwrap_or("none"),
+        chrono::Utc::now().to_rfc3339()
+    );
+    warn!("Initial execution failed due to permissions, retrying with bypassPermissions mode");
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskAnalysis {
     pub complexity: String,
     pub estimated_minutes: u32,
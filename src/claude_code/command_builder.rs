@@ -30,6 +30,8 @@ pub struct ClaudeCommandBuilder {
     additional_dirs: Vec<PathBuf>,
     timeout_seconds: Option<u32>,
     environment_vars: Vec<(String, String)>,
+    mcp_config: Option<PathBuf>,
+    mcp_servers: Vec<String>,
 }
 
 /// 📊 OUTPUT FORMAT: How Claude Code returns results
@@ -58,6 +60,17 @@ pub enum SessionMode {
     Continue,       // Continue most recent session
 }
 
+impl SessionMode {
+    /// CLI flags selecting this session
+    pub fn cli_args(&self) -> Vec<&str> {
+        match self {
+            SessionMode::NewSession => Vec::new(),
+            SessionMode::Resume(session_id) => vec!["--resume", session_id],
+            SessionMode::Continue => vec!["--continue"],
+        }
+    }
+}
+
 impl ClaudeCommandBuilder {
     /// 🚀 BUILDER INITIALIZATION: Start with binary path
     /// DECISION: Require binary path upfront for fail-fast behavior
@@ -73,6 +86,8 @@ impl ClaudeCommandBuilder {
             additional_dirs: Vec::new(),
             timeout_seconds: None,
             environment_vars: Vec::new(),
+            mcp_config: None,
+            mcp_servers: Vec::new(),
         }
     }
 
@@ -152,6 +167,18 @@ impl ClaudeCommandBuilder {
         self
     }
 
+    /// 🔌 MCP SERVERS: Config file declaring the servers (see mcp::prepare), and the
+    /// servers in it whose tools run without prompting
+    pub fn with_mcp_config(
+        mut self,
+        path: impl Into<PathBuf>,
+        servers: Vec<impl Into<String>>,
+    ) -> Self {
+        self.mcp_config = Some(path.into());
+        self.mcp_servers = servers.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
     /// 📁 WORKSPACE AND DIRECTORY CONFIGURATION
     pub fn with_workspace(mut self, path: impl Into<PathBuf>) -> Self {
         self.workspace = Some(path.into());
@@ -206,21 +233,18 @@ impl ClaudeCommandBuilder {
         ]);
 
         // Session handling
-        match self.session_mode {
-            SessionMode::NewSession => {
-                // No session flags needed
-            }
-            SessionMode::Resume(ref session_id) => {
-                command.args(["--resume", session_id]);
-            }
-            SessionMode::Continue => {
-                command.arg("--continue");
-            }
+        command.args(self.session_mode.cli_args());
+
+        // MCP servers, whose tools are named mcp__<server>__<tool>
+        let mut allowed_tools = self.allowed_tools.clone();
+        if let Some(ref mcp_config) = self.mcp_config {
+            command.args(["--mcp-config", &mcp_config.to_string_lossy()]);
+            allowed_tools.extend(self.mcp_servers.iter().map(|name| format!("mcp__{name}")));
         }
 
         // Allowed tools
-        if !self.allowed_tools.is_empty() {
-            command.args(["--allowedTools", &self.allowed_tools.join(",")]);
+        if !allowed_tools.is_empty() {
+            command.args(["--allowedTools", &allowed_tools.join(",")]);
         }
 
         // Workspace directory
@@ -275,6 +299,14 @@ impl ClaudeCommandBuilder {
             }
         }
 
+        // MCP servers are only usable through a config declaring them
+        if !self.mcp_servers.is_empty() && self.mcp_config.is_none() {
+            return Err("MCP servers need an MCP config file".to_string());
+        }
+        if self.mcp_servers.iter().any(|name| name.is_empty()) {
+            return Err("MCP server name cannot be empty".to_string());
+        }
+
         Ok(())
     }
 }
@@ -336,6 +368,24 @@ mod tests {
         assert_eq!(builder.allowed_tools, vec!["Read", "Write", "Edit"]);
     }
 
+    #[test]
+    fn test_mcp_configuration() {
+        let command = ClaudeCommandBuilder::new("/usr/bin/claude")
+            .with_allowed_tools(vec!["Read"])
+            .with_mcp_config("/tmp/workspace/.spiral-mcp.json", vec!["postgres"])
+            .build();
+        let args: Vec<_> = command
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        let config = args.iter().position(|arg| arg == "--mcp-config").unwrap();
+        assert_eq!(args[config + 1], "/tmp/workspace/.spiral-mcp.json");
+        let tools = args.iter().position(|arg| arg == "--allowedTools").unwrap();
+        assert_eq!(args[tools + 1], "Read,mcp__postgres");
+    }
+
     #[test]
     fn test_validation() {
         // Valid configuration
@@ -0,0 +1,376 @@
+//! Token and spend accounting for Claude Code calls
+//!
+//! Every CLI response reports its token usage and cost; the tracker adds them up per day,
+//! per task and per user, and enforces the optional daily budget.
+
+use crate::{
+    constants::{COST_HISTORY_DAYS, COST_REPORT_TOP_N},
+    models::Task,
+    Result, SpiralError,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use utoipa::ToSchema;
+
+/// Context keys that identify who asked for a task, checked in order
+pub(crate) const USER_CONTEXT_KEYS: [&str; 2] = ["user_id", "discord_author_id"];
+
+/// What a single Claude Code call consumed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cache writes and reads, billed at different rates from fresh input
+    pub cache_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Who a call's spend is charged to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostAttribution {
+    pub task_id: Option<String>,
+    pub user: Option<String>,
+}
+
+impl CostAttribution {
+    /// Read the task and requesting user from a generation request's context
+    pub fn from_context(context: &HashMap<String, String>) -> Self {
+        let non_empty = |key: &str| context.get(key).filter(|v| !v.is_empty()).cloned();
+        Self {
+            task_id: non_empty("task_id"),
+            user: USER_CONTEXT_KEYS.iter().find_map(|key| non_empty(key)),
+        }
+    }
+}
+
+/// The task's requesting user as a context entry, for agents that build their own context
+pub fn requester_context(task: &Task) -> Option<(String, String)> {
+    USER_CONTEXT_KEYS.iter().find_map(|key| {
+        task.context
+            .get(*key)
+            .filter(|value| !value.is_empty())
+            .map(|value| (key.to_string(), value.clone()))
+    })
+}
+
+/// Running totals for one day, task or user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CostTotals {
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl CostTotals {
+    fn add(&mut self, usage: &Usage) {
+        self.calls += 1;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.cache_tokens += usage.cache_tokens;
+        self.cost_usd += usage.cost_usd;
+    }
+
+    fn merge(&mut self, other: &CostTotals) {
+        self.calls += other.calls;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_tokens += other.cache_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DailyCost {
+    pub date: NaiveDate,
+    pub totals: CostTotals,
+}
+
+/// Spend charged to one task or user
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AttributedCost {
+    pub id: String,
+    pub totals: CostTotals,
+}
+
+/// Spend over the retained history, with today's budget position
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CostReport {
+    /// The current day in UTC, which is when the daily budget resets
+    pub date: NaiveDate,
+    pub today: CostTotals,
+    pub daily_budget_usd: Option<f64>,
+    pub budget_remaining_usd: Option<f64>,
+    pub budget_exceeded: bool,
+    /// Most recent first
+    pub days: Vec<DailyCost>,
+    /// Most expensive first, limited to the top entries
+    pub tasks: Vec<AttributedCost>,
+    pub users: Vec<AttributedCost>,
+}
+
+#[derive(Debug, Default)]
+struct DayLedger {
+    totals: CostTotals,
+    tasks: HashMap<String, CostTotals>,
+    users: HashMap<String, CostTotals>,
+}
+
+/// 💸 COST TRACKER: Shared by every clone of the Claude Code client
+/// 🏗️ ARCHITECTURE DECISION: Ledger kept per day, in memory
+/// Why: Per-day buckets make the budget check and history pruning trivial, and task and
+/// user totals fall out by summing the retained days
+/// Alternative: Persist every call to disk (rejected: spend is also visible in the
+/// Anthropic console; this ledger exists for the budget and quick answers)
+/// Trade-off: A restart forgets today's spend, so the budget starts over
+#[derive(Debug)]
+pub struct CostTracker {
+    daily_budget_usd: Option<f64>,
+    days: Mutex<BTreeMap<NaiveDate, DayLedger>>,
+}
+
+impl CostTracker {
+    pub fn new(daily_budget_usd: Option<f64>) -> Self {
+        Self {
+            daily_budget_usd,
+            days: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn daily_budget_usd(&self) -> Option<f64> {
+        self.daily_budget_usd
+    }
+
+    pub fn record(&self, usage: Usage, attribution: &CostAttribution) {
+        self.record_on(Utc::now().date_naive(), usage, attribution);
+    }
+
+    fn record_on(&self, date: NaiveDate, usage: Usage, attribution: &CostAttribution) {
+        let mut days = self.days.lock().unwrap_or_else(|e| e.into_inner());
+        let day = days.entry(date).or_default();
+        day.totals.add(&usage);
+        if let Some(task_id) = &attribution.task_id {
+            day.tasks.entry(task_id.clone()).or_default().add(&usage);
+        }
+        if let Some(user) = &attribution.user {
+            day.users.entry(user.clone()).or_default().add(&usage);
+        }
+
+        let oldest_kept = date - Duration::days(COST_HISTORY_DAYS - 1);
+        days.retain(|day, _| *day >= oldest_kept);
+    }
+
+    /// Spend on the given day
+    fn spent_on(&self, date: NaiveDate) -> CostTotals {
+        let days = self.days.lock().unwrap_or_else(|e| e.into_inner());
+        days.get(&date).map(|day| day.totals).unwrap_or_default()
+    }
+
+    /// Refuse new work once today's spend has reached the daily budget
+    pub fn check_budget(&self) -> Result<()> {
+        self.check_budget_on(Utc::now().date_naive())
+    }
+
+    fn check_budget_on(&self, date: NaiveDate) -> Result<()> {
+        let Some(budget) = self.daily_budget_usd else {
+            return Ok(());
+        };
+        let spent = self.spent_on(date).cost_usd;
+        if spent >= budget {
+            return Err(SpiralError::RateLimit {
+                message: format!(
+                    "Daily Claude Code budget of ${budget:.2} is used up (${spent:.2} spent \
+                     today); new tasks are accepted again after midnight UTC"
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Spend charged to one task over the retained history
+    pub fn task_totals(&self, task_id: &str) -> CostTotals {
+        let days = self.days.lock().unwrap_or_else(|e| e.into_inner());
+        let mut totals = CostTotals::default();
+        for day in days.values() {
+            if let Some(spent) = day.tasks.get(task_id) {
+                totals.merge(spent);
+            }
+        }
+        totals
+    }
+
+    /// Spend charged to one user over the retained history
+    pub fn user_totals(&self, user: &str) -> CostTotals {
+        let days = self.days.lock().unwrap_or_else(|e| e.into_inner());
+        let mut totals = CostTotals::default();
+        for day in days.values() {
+            if let Some(spent) = day.users.get(user) {
+                totals.merge(spent);
+            }
+        }
+        totals
+    }
+
+    pub fn report(&self) -> CostReport {
+        self.report_on(Utc::now().date_naive())
+    }
+
+    fn report_on(&self, date: NaiveDate) -> CostReport {
+        let days = self.days.lock().unwrap_or_else(|e| e.into_inner());
+        let today = days.get(&date).map(|day| day.totals).unwrap_or_default();
+
+        let mut tasks: HashMap<&str, CostTotals> = HashMap::new();
+        let mut users: HashMap<&str, CostTotals> = HashMap::new();
+        for day in days.values() {
+            for (id, spent) in &day.tasks {
+                tasks.entry(id).or_default().merge(spent);
+            }
+            for (id, spent) in &day.users {
+                users.entry(id).or_default().merge(spent);
+            }
+        }
+
+        CostReport {
+            date,
+            today,
+            daily_budget_usd: self.daily_budget_usd,
+            budget_remaining_usd: self
+                .daily_budget_usd
+                .map(|budget| (budget - today.cost_usd).max(0.0)),
+            budget_exceeded: self
+                .daily_budget_usd
+                .is_some_and(|budget| today.cost_usd >= budget),
+            days: days
+                .iter()
+                .rev()
+                .map(|(date, day)| DailyCost {
+                    date: *date,
+                    totals: day.totals,
+                })
+                .collect(),
+            tasks: top_spenders(tasks),
+            users: top_spenders(users),
+        }
+    }
+}
+
+fn top_spenders(totals: HashMap<&str, CostTotals>) -> Vec<AttributedCost> {
+    let mut ranked: Vec<AttributedCost> = totals
+        .into_iter()
+        .map(|(id, totals)| AttributedCost {
+            id: id.to_string(),
+            totals,
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.totals
+            .cost_usd
+            .total_cmp(&a.totals.cost_usd)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    ranked.truncate(COST_REPORT_TOP_N);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(cost_usd: f64) -> Usage {
+        Usage {
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_tokens: 10,
+            cost_usd,
+        }
+    }
+
+    fn charged_to(task_id: &str, user: &str) -> CostAttribution {
+        CostAttribution {
+            task_id: Some(task_id.to_string()),
+            user: Some(user.to_string()),
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_spend_is_aggregated_per_day_task_and_user() {
+        let tracker = CostTracker::new(None);
+        tracker.record_on(day(1), usage(0.50), &charged_to("t-1", "alice"));
+        tracker.record_on(day(2), usage(0.25), &charged_to("t-1", "alice"));
+        tracker.record_on(day(2), usage(1.00), &charged_to("t-2", "bob"));
+        tracker.record_on(day(2), usage(0.10), &CostAttribution::default());
+
+        let report = tracker.report_on(day(2));
+        assert_eq!(report.today.calls, 3);
+        assert!((report.today.cost_usd - 1.35).abs() < 1e-9);
+        assert_eq!(report.today.input_tokens, 300);
+        assert_eq!(
+            report.days.iter().map(|d| d.date).collect::<Vec<_>>(),
+            vec![day(2), day(1)]
+        );
+
+        assert_eq!(report.tasks[0].id, "t-2");
+        assert_eq!(report.tasks[1].id, "t-1");
+        assert_eq!(report.tasks[1].totals.calls, 2);
+        assert!((tracker.user_totals("alice").cost_usd - 0.75).abs() < 1e-9);
+        assert_eq!(report.users.len(), 2);
+    }
+
+    #[test]
+    fn test_budget_refuses_work_once_spent_and_resets_next_day() {
+        let tracker = CostTracker::new(Some(1.0));
+        tracker.record_on(day(1), usage(0.60), &CostAttribution::default());
+        assert!(tracker.check_budget_on(day(1)).is_ok());
+
+        tracker.record_on(day(1), usage(0.40), &CostAttribution::default());
+        let error = tracker.check_budget_on(day(1)).unwrap_err();
+        assert!(matches!(error, SpiralError::RateLimit { .. }));
+
+        let report = tracker.report_on(day(1));
+        assert!(report.budget_exceeded);
+        assert_eq!(report.budget_remaining_usd, Some(0.0));
+        assert!(tracker.check_budget_on(day(2)).is_ok());
+        assert!(CostTracker::new(None).check_budget_on(day(1)).is_ok());
+    }
+
+    #[test]
+    fn test_old_days_are_pruned() {
+        let tracker = CostTracker::new(None);
+        let start = day(1);
+        tracker.record_on(start, usage(1.0), &charged_to("old", "alice"));
+        tracker.record_on(
+            start + Duration::days(COST_HISTORY_DAYS),
+            usage(0.5),
+            &charged_to("new", "alice"),
+        );
+
+        let report = tracker.report_on(start + Duration::days(COST_HISTORY_DAYS));
+        assert_eq!(report.days.len(), 1);
+        assert_eq!(report.tasks.len(), 1);
+        assert!((tracker.user_totals("alice").cost_usd - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_attribution_reads_task_and_user_from_context() {
+        let context = HashMap::from([
+            ("task_id".to_string(), "t-9".to_string()),
+            ("discord_author_id".to_string(), "42".to_string()),
+            ("user_id".to_string(), String::new()),
+        ]);
+        assert_eq!(
+            CostAttribution::from_context(&context),
+            charged_to("t-9", "42")
+        );
+        assert_eq!(
+            CostAttribution::from_context(&HashMap::new()),
+            CostAttribution::default()
+        );
+    }
+}
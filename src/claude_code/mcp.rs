@@ -0,0 +1,229 @@
+//! MCP (Model Context Protocol) servers offered to Claude Code
+//!
+//! Servers are declared once in the MCP config file and offered per agent type. Each
+//! run gets a config listing only its agent's servers, passed to the CLI with
+//! `--mcp-config`, and those servers' tools are pre-approved like the built-in ones.
+
+use crate::config::McpServerConfig;
+use crate::models::AgentType;
+use crate::{Result, SpiralError};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+
+/// File in the workspace holding the run's MCP config
+/// Inside the workspace so a sandboxed run sees it at the same path
+const MCP_CONFIG_FILE: &str = ".spiral-mcp.json";
+
+/// Server names become tool prefixes (`mcp__<name>__<tool>`), so only a safe alphabet
+/// is accepted
+fn is_valid_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// 🔌 MCP VALIDATION: Reject server definitions that could only fail mid-task
+/// Unsandboxed, the command must exist on the host; sandboxed, it runs in the image and
+/// is left to the image to provide
+pub fn validate_servers(
+    servers: &BTreeMap<String, McpServerConfig>,
+    sandboxed: bool,
+) -> Result<()> {
+    for (name, server) in servers {
+        let invalid = |reason: String| {
+            SpiralError::ConfigurationError(format!("MCP server '{name}' {reason}"))
+        };
+        if !is_valid_server_name(name) {
+            return Err(invalid(
+                "has an invalid name (use letters, digits, '-' and '_')".to_string(),
+            ));
+        }
+        if server.command.trim().is_empty() {
+            return Err(invalid("has no command".to_string()));
+        }
+        if !sandboxed && !command_exists(&server.command) {
+            return Err(invalid(format!(
+                "command '{}' was not found",
+                server.command
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn command_exists(command: &str) -> bool {
+    let path = Path::new(command);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(command).is_file()))
+        .unwrap_or(false)
+}
+
+/// The servers offered to an invocation of the given class (see pool::invocation_class)
+/// Servers without an agent list go to everyone, including unattributed calls
+fn servers_for<'a>(
+    servers: &'a BTreeMap<String, McpServerConfig>,
+    invocation_class: &str,
+) -> BTreeMap<&'a str, &'a McpServerConfig> {
+    let agent = invocation_class.parse::<AgentType>().ok();
+    servers
+        .iter()
+        .filter(|(_, server)| {
+            server.agents.is_empty()
+                || agent
+                    .as_ref()
+                    .is_some_and(|agent| server.agents.contains(agent))
+        })
+        .map(|(name, server)| (name.as_str(), server))
+        .collect()
+}
+
+/// MCP servers prepared for one Claude Code run
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpInvocation {
+    pub config_path: PathBuf,
+    pub servers: Vec<String>,
+}
+
+impl McpInvocation {
+    /// Allowed-tools entries approving every tool of the run's servers
+    pub fn allowed_tools(&self) -> impl Iterator<Item = String> + '_ {
+        self.servers.iter().map(|name| format!("mcp__{name}"))
+    }
+}
+
+/// Write the run's MCP config into its workspace; None when no server applies
+/// The file carries server environments, which may hold credentials, so it is only
+/// readable by the owner and removed again when a later run has no servers
+pub async fn prepare(
+    servers: &BTreeMap<String, McpServerConfig>,
+    workspace: &Path,
+    invocation_class: &str,
+) -> Result<Option<McpInvocation>> {
+    let config_path = workspace.join(MCP_CONFIG_FILE);
+    let offered = servers_for(servers, invocation_class);
+    if offered.is_empty() {
+        let _ = fs::remove_file(&config_path).await;
+        return Ok(None);
+    }
+
+    let definitions: serde_json::Map<String, serde_json::Value> = offered
+        .iter()
+        .map(|(name, server)| {
+            let definition = json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env,
+            });
+            (name.to_string(), definition)
+        })
+        .collect();
+    let contents = serde_json::to_vec_pretty(&json!({ "mcpServers": definitions }))?;
+    write_private(&config_path, &contents)
+        .await
+        .map_err(|e| SpiralError::Agent {
+            message: format!("Failed to write MCP config {config_path:?}: {e}"),
+        })?;
+
+    let servers: Vec<String> = offered.keys().map(|name| name.to_string()).collect();
+    debug!("MCP servers for {}: {:?}", invocation_class, servers);
+    Ok(Some(McpInvocation {
+        config_path,
+        servers,
+    }))
+}
+
+async fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(contents).await?;
+    // Tokio writes in the background; the CLI must see the whole file
+    file.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn server(command: &str, agents: Vec<AgentType>) -> McpServerConfig {
+        McpServerConfig {
+            command: command.to_string(),
+            args: vec!["--read-only".to_string()],
+            env: HashMap::from([("DATABASE_URL".to_string(), "postgres://db".to_string())]),
+            agents,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare_offers_each_agent_its_servers() {
+        let servers = BTreeMap::from([
+            (
+                "postgres".to_string(),
+                server("mcp-postgres", vec![AgentType::SoftwareDeveloper]),
+            ),
+            ("browser".to_string(), server("mcp-browser", vec![])),
+        ]);
+        let workspace = tempfile::tempdir().unwrap();
+
+        let developer = prepare(&servers, workspace.path(), "SoftwareDeveloper")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(developer.servers, ["browser", "postgres"]);
+        assert_eq!(
+            developer.allowed_tools().collect::<Vec<_>>(),
+            ["mcp__browser", "mcp__postgres"]
+        );
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&developer.config_path).unwrap()).unwrap();
+        assert_eq!(
+            written["mcpServers"]["postgres"]["env"]["DATABASE_URL"],
+            "postgres://db"
+        );
+        // The agent list is ours, not part of Claude's format
+        assert!(written["mcpServers"]["postgres"].get("agents").is_none());
+
+        let qa = prepare(&servers, workspace.path(), "QualityAssurance")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(qa.servers, ["browser"]);
+
+        // No servers left: the previous run's file and its credentials are removed
+        let only_dev = BTreeMap::from([(
+            "postgres".to_string(),
+            server("mcp-postgres", vec![AgentType::SoftwareDeveloper]),
+        )]);
+        assert!(prepare(&only_dev, workspace.path(), "unattributed")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!qa.config_path.exists());
+    }
+
+    #[test]
+    fn test_validate_servers_checks_names_and_commands() {
+        let valid = BTreeMap::from([("shell".to_string(), server("sh", vec![]))]);
+        assert!(validate_servers(&valid, false).is_ok());
+
+        let bad_name = BTreeMap::from([("my server".to_string(), server("sh", vec![]))]);
+        assert!(validate_servers(&bad_name, true).is_err());
+
+        let missing =
+            BTreeMap::from([("db".to_string(), server("/nonexistent/mcp-server", vec![]))]);
+        assert!(validate_servers(&missing, false).is_err());
+        // Sandboxed runs take the command from the image
+        assert!(validate_servers(&missing, true).is_ok());
+    }
+}
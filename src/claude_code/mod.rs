@@ -1,12 +1,33 @@
+pub mod cache;
+pub mod changes;
 pub mod circuit_breaker;
 mod cli_client;
 mod command_builder;
+pub mod costs;
+pub mod mcp;
+pub mod pool;
+pub mod quota;
+pub mod sandbox;
+pub mod sessions;
+mod stream;
+pub mod transcripts;
+pub mod version;
+pub mod workspace_archive;
+pub mod workspace_templates;
 
+pub use cache::{ResponseCache, ResponseCacheMetrics};
+pub(crate) use cli_client::{
+    validate_generation_request, SESSION_DIR_PREFIX, WORKSPACE_DIR_PREFIX,
+};
 pub use cli_client::{
     ClaudeCodeCliClient as ClaudeCodeClient, CodeGenerationRequest, CodeGenerationResult,
     FileCreation, FileModification, TaskAnalysis,
 };
 pub use command_builder::{ClaudeCommandBuilder, OutputFormat, PermissionMode, SessionMode};
+pub use costs::{CostAttribution, CostReport, CostTracker};
+pub use pool::{InvocationPool, InvocationPoolMetrics};
+pub use stream::GenerationEvent;
+pub use version::{ClaudeCliStatus, CliCompatibility, CliFeature};
 
 // 🧪 TEST MODULE: Comprehensive testing for external AI integration
 #[cfg(test)]
@@ -0,0 +1,285 @@
+//! Global limit on simultaneous Claude Code processes
+//!
+//! Every CLI invocation takes a slot first. When all slots are busy, callers queue per
+//! agent type and freed slots go to the agent types in turn, so one busy agent cannot
+//! keep the others waiting behind its backlog.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use utoipa::ToSchema;
+
+/// Queue for invocations whose context names no agent type, e.g. language detection
+pub const UNATTRIBUTED_INVOCATION: &str = "unattributed";
+
+/// The queue an invocation waits in: the requesting agent's type
+pub fn invocation_class(context: &HashMap<String, String>) -> String {
+    context
+        .get("agent_type")
+        .filter(|agent| !agent.is_empty())
+        .cloned()
+        .unwrap_or_else(|| UNATTRIBUTED_INVOCATION.to_string())
+}
+
+/// Slot usage and queueing of the invocation pool
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InvocationPoolMetrics {
+    pub max_concurrent: usize,
+    pub running: usize,
+    pub queued: usize,
+    /// Waiting invocations per agent type
+    pub queued_by_agent: BTreeMap<String, usize>,
+    pub total_invocations: u64,
+    /// Invocations that waited for a slot and then ran
+    pub total_queued: u64,
+    pub average_wait_ms: f64,
+    pub max_wait_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    running: usize,
+    waiters: HashMap<String, VecDeque<oneshot::Sender<()>>>,
+    /// Agent types with waiters, in the order they are served next
+    turns: VecDeque<String>,
+    total_invocations: u64,
+    total_queued: u64,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+impl PoolState {
+    /// Give free slots to waiters, taking the agent types in turn and each type's
+    /// waiters oldest first
+    fn dispatch(&mut self, max_concurrent: usize) {
+        while self.running < max_concurrent {
+            let Some(class) = self.turns.pop_front() else {
+                return;
+            };
+            let Some(queue) = self.waiters.get_mut(&class) else {
+                continue;
+            };
+            // A send error means that caller gave up waiting
+            let mut granted = false;
+            while let Some(waiter) = queue.pop_front() {
+                if waiter.send(()).is_ok() {
+                    granted = true;
+                    break;
+                }
+            }
+            if queue.is_empty() {
+                self.waiters.remove(&class);
+            } else {
+                self.turns.push_back(class);
+            }
+            if granted {
+                self.running += 1;
+            }
+        }
+    }
+}
+
+/// 🚦 INVOCATION POOL: Cap on Claude Code processes across all agents
+/// 🏗️ ARCHITECTURE DECISION: Round-robin over per-agent FIFO queues instead of one
+/// tokio Semaphore
+/// Why: A Semaphore is fair in arrival order, so a burst of twenty developer tasks
+/// would make a single QA run wait for all of them; taking turns bounds its wait to
+/// one invocation per other waiting agent type
+/// Alternative: Rely on the orchestrator's per-agent worker pools (rejected: they bound
+/// tasks, not processes; analyses, retries and API-driven generations bypass them)
+/// Trade-off: Slots are handed over under a std Mutex; every section is a few queue
+/// operations and never awaits
+#[derive(Debug, Clone)]
+pub struct InvocationPool {
+    max_concurrent: usize,
+    state: Arc<Mutex<PoolState>>,
+}
+
+/// A held slot; dropping it passes the slot on
+#[derive(Debug)]
+pub struct InvocationPermit {
+    pool: InvocationPool,
+}
+
+impl Drop for InvocationPermit {
+    fn drop(&mut self) {
+        self.pool.release();
+    }
+}
+
+/// A caller waiting for a slot; if it gives up after being granted one, the slot is
+/// passed on instead of leaking
+struct Waiting {
+    receiver: Option<oneshot::Receiver<()>>,
+    pool: InvocationPool,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.pool.release();
+            }
+        }
+    }
+}
+
+impl InvocationPool {
+    /// A pool of `max_concurrent` slots; 0 is raised to 1 so invocations still run
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            state: Arc::default(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait for a slot in the agent type's queue
+    pub async fn acquire(&self, class: &str) -> InvocationPermit {
+        let started = Instant::now();
+        let mut receiver = {
+            let mut state = self.state();
+            state.total_invocations += 1;
+            let (sender, receiver) = oneshot::channel();
+            let queue = state.waiters.entry(class.to_string()).or_default();
+            queue.push_back(sender);
+            if queue.len() == 1 {
+                state.turns.push_back(class.to_string());
+            }
+            state.dispatch(self.max_concurrent);
+            receiver
+        };
+        if receiver.try_recv().is_ok() {
+            return InvocationPermit { pool: self.clone() };
+        }
+
+        let mut waiting = Waiting {
+            receiver: Some(receiver),
+            pool: self.clone(),
+        };
+        if let Some(receiver) = waiting.receiver.as_mut() {
+            // Senders are only dropped by a send, so this resolves once the slot is ours
+            let _ = receiver.await;
+        }
+        waiting.receiver = None;
+
+        let waited = started.elapsed();
+        let mut state = self.state();
+        state.total_queued += 1;
+        state.total_wait += waited;
+        state.max_wait = state.max_wait.max(waited);
+        InvocationPermit { pool: self.clone() }
+    }
+
+    fn release(&self) {
+        let mut state = self.state();
+        state.running = state.running.saturating_sub(1);
+        state.dispatch(self.max_concurrent);
+    }
+
+    pub fn metrics(&self) -> InvocationPoolMetrics {
+        let state = self.state();
+        let queued_by_agent: BTreeMap<String, usize> = state
+            .waiters
+            .iter()
+            .map(|(class, queue)| {
+                let waiting = queue.iter().filter(|waiter| !waiter.is_closed()).count();
+                (class.clone(), waiting)
+            })
+            .filter(|(_, waiting)| *waiting > 0)
+            .collect();
+        let average_wait_ms = if state.total_queued == 0 {
+            0.0
+        } else {
+            state.total_wait.as_secs_f64() * 1000.0 / state.total_queued as f64
+        };
+        InvocationPoolMetrics {
+            max_concurrent: self.max_concurrent,
+            running: state.running,
+            queued: queued_by_agent.values().sum(),
+            queued_by_agent,
+            total_invocations: state.total_invocations,
+            total_queued: state.total_queued,
+            average_wait_ms,
+            max_wait_ms: state.max_wait.as_millis() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    /// Queue a caller that reports its name once it holds a slot, then keeps it
+    fn spawn_waiter(
+        pool: &InvocationPool,
+        class: &'static str,
+        name: &'static str,
+        served: &mpsc::UnboundedSender<(&'static str, InvocationPermit)>,
+    ) {
+        let pool = pool.clone();
+        let served = served.clone();
+        tokio::spawn(async move {
+            let permit = pool.acquire(class).await;
+            let _ = served.send((name, permit));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_freed_slots_alternate_between_agent_types() {
+        let pool = InvocationPool::new(1);
+        let held = pool.acquire("SoftwareDeveloper").await;
+        let (served, mut order) = mpsc::unbounded_channel();
+
+        for name in ["dev-1", "dev-2", "dev-3"] {
+            spawn_waiter(&pool, "SoftwareDeveloper", name, &served);
+            tokio::task::yield_now().await;
+        }
+        spawn_waiter(&pool, "QualityAssurance", "qa-1", &served);
+        tokio::task::yield_now().await;
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.running, 1);
+        assert_eq!(metrics.queued, 4);
+        assert_eq!(metrics.queued_by_agent["SoftwareDeveloper"], 3);
+
+        // Each released slot goes to the next agent type in turn
+        drop(held);
+        let mut names = Vec::new();
+        for _ in 0..4 {
+            let (name, permit) = order.recv().await.unwrap();
+            names.push(name);
+            drop(permit);
+        }
+        assert_eq!(names, ["dev-1", "qa-1", "dev-2", "dev-3"]);
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.running, 0);
+        assert_eq!(metrics.total_invocations, 5);
+        assert_eq!(metrics.total_queued, 4);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_leak_its_slot() {
+        let pool = InvocationPool::new(1);
+        let held = pool.acquire("SoftwareDeveloper").await;
+
+        let gave_up =
+            tokio::time::timeout(Duration::from_millis(20), pool.acquire("SoftwareDeveloper"))
+                .await;
+        assert!(gave_up.is_err());
+        assert_eq!(pool.metrics().queued, 0);
+
+        drop(held);
+        assert_eq!(pool.metrics().running, 0);
+        let _again = pool.acquire(UNATTRIBUTED_INVOCATION).await;
+        assert_eq!(pool.metrics().running, 1);
+    }
+}
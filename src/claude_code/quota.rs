@@ -0,0 +1,261 @@
+//! Workspace size quota, enforced while Claude Code runs
+//!
+//! A watchdog samples the workspace size during each run and stops the run once it
+//! passes `max_workspace_size_mb`; whatever was written so far stays in the workspace
+//! and is reported as the partial result.
+
+use crate::constants::{
+    WORKSPACE_QUOTA_MAX_LISTED_FILES, WORKSPACE_QUOTA_POLL_MS, WORKSPACE_QUOTA_WARN_PERCENT,
+};
+use crate::{Result, SpiralError};
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use tracing::warn;
+
+pub(crate) const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// What a workspace holds: total size and the files in it, relative to its root
+#[derive(Debug, Default)]
+pub struct WorkspaceScan {
+    pub bytes: u64,
+    pub files: Vec<String>,
+}
+
+/// Walk a workspace; unreadable entries are skipped rather than failing the scan
+/// Dot-entries such as `.claude-session-id` count towards the size but are not listed
+pub async fn scan_workspace(root: &Path) -> WorkspaceScan {
+    let mut scan = WorkspaceScan::default();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(mut entries) = fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            let path = entry.path();
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_file() {
+                scan.bytes += metadata.len();
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                let hidden = relative
+                    .components()
+                    .any(|part| part.as_os_str().to_string_lossy().starts_with('.'));
+                if !hidden {
+                    scan.files.push(relative.to_string_lossy().into_owned());
+                }
+            }
+        }
+    }
+    scan.files.sort();
+    scan
+}
+
+/// A run stopped for outgrowing its workspace quota, with the files it left behind
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaBreach {
+    pub workspace: String,
+    pub size_mb: u64,
+    pub limit_mb: u64,
+    /// Files written before the run was stopped, at most WORKSPACE_QUOTA_MAX_LISTED_FILES
+    pub files: Vec<String>,
+    /// Files left out of `files` to keep the report short
+    pub unlisted_files: usize,
+}
+
+impl QuotaBreach {
+    /// The partial result reported in place of the generation
+    pub fn partial_output(&self) -> String {
+        let mut output = format!(
+            "Generation stopped at {} MB, over the {} MB workspace quota. \
+             Files written so far are kept in {}:\n",
+            self.size_mb, self.limit_mb, self.workspace
+        );
+        for file in &self.files {
+            output.push_str(&format!("- {file}\n"));
+        }
+        if self.unlisted_files > 0 {
+            output.push_str(&format!("- ...and {} more\n", self.unlisted_files));
+        }
+        output
+    }
+}
+
+impl fmt::Display for QuotaBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} grew to {} MB, over its {} MB quota; generation was stopped",
+            self.workspace, self.size_mb, self.limit_mb
+        )
+    }
+}
+
+/// 📦 WORKSPACE QUOTA: Watchdog for one workspace's size during a run
+/// 🏗️ ARCHITECTURE DECISION: Poll the size instead of watching file events
+/// Why: A build writing thousands of files would flood a watcher, while a poll every
+/// couple of seconds costs one directory walk and bounds the overshoot by what can be
+/// written in that time
+/// Alternative: A filesystem quota or container disk limit (rejected: needs root or a
+/// sandbox, and the process would just see write errors instead of a clean stop)
+#[derive(Debug, Clone)]
+pub struct WorkspaceQuota {
+    limit_mb: u64,
+    poll: Duration,
+}
+
+impl WorkspaceQuota {
+    /// A quota of `limit_mb`; 0 leaves workspaces unlimited
+    pub fn new(limit_mb: u64) -> Self {
+        Self {
+            limit_mb,
+            poll: Duration::from_millis(WORKSPACE_QUOTA_POLL_MS),
+        }
+    }
+
+    fn limit_bytes(&self) -> u64 {
+        self.limit_mb.saturating_mul(BYTES_PER_MB)
+    }
+
+    fn warn_bytes(&self) -> u64 {
+        self.limit_bytes() / 100 * WORKSPACE_QUOTA_WARN_PERCENT
+    }
+
+    /// ⚠️ QUOTA NOTICE: The prompt, told about the quota when the workspace is already
+    /// near it, so a resumed session cleans up instead of writing more
+    pub async fn annotate_prompt(&self, prompt: &str, workspace: &Path) -> String {
+        if self.limit_mb == 0 {
+            return prompt.to_string();
+        }
+        let used = scan_workspace(workspace).await.bytes;
+        if used < self.warn_bytes() {
+            return prompt.to_string();
+        }
+        format!(
+            "NOTE: This workspace already uses {} of its {} MB quota and the run is stopped \
+             if it goes over. Remove build artifacts you no longer need (e.g. target/, \
+             node_modules/) and avoid large generated files.\n\n{prompt}",
+            used / BYTES_PER_MB,
+            self.limit_mb
+        )
+    }
+
+    /// Run `work` while watching the workspace; if the quota is exceeded first, `work`
+    /// is dropped (which kills a child process it owns) and the breach is returned
+    pub async fn enforce<T>(
+        &self,
+        workspace: &Path,
+        work: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if self.limit_mb == 0 {
+            return work.await;
+        }
+        tokio::select! {
+            outcome = work => outcome,
+            breach = self.watch(workspace.to_path_buf()) => {
+                Err(SpiralError::WorkspaceQuotaExceeded(breach))
+            }
+        }
+    }
+
+    /// Resolves only once the workspace is over quota
+    async fn watch(&self, workspace: PathBuf) -> QuotaBreach {
+        let mut warned = false;
+        loop {
+            tokio::time::sleep(self.poll).await;
+            let scan = scan_workspace(&workspace).await;
+            if scan.bytes > self.limit_bytes() {
+                warn!(
+                    "Workspace {:?} is over its {} MB quota; stopping Claude Code",
+                    workspace, self.limit_mb
+                );
+                let mut files = scan.files;
+                let unlisted_files = files.len().saturating_sub(WORKSPACE_QUOTA_MAX_LISTED_FILES);
+                files.truncate(WORKSPACE_QUOTA_MAX_LISTED_FILES);
+                return QuotaBreach {
+                    workspace: workspace.display().to_string(),
+                    size_mb: scan.bytes / BYTES_PER_MB,
+                    limit_mb: self.limit_mb,
+                    files,
+                    unlisted_files,
+                };
+            }
+            if !warned && scan.bytes >= self.warn_bytes() {
+                warned = true;
+                warn!(
+                    "Workspace {:?} is at {} MB of its {} MB quota",
+                    workspace,
+                    scan.bytes / BYTES_PER_MB,
+                    self.limit_mb
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(limit_mb: u64) -> WorkspaceQuota {
+        WorkspaceQuota {
+            limit_mb,
+            poll: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_is_stopped_with_partial_files_once_over_quota() {
+        let workspace = tempfile::tempdir().unwrap();
+        let root = workspace.path().to_path_buf();
+        let writer = async move {
+            std::fs::create_dir_all(root.join("src")).unwrap();
+            std::fs::write(root.join("src/lib.rs"), "pub fn small() {}").unwrap();
+            std::fs::write(root.join(".claude-session-id"), "abc").unwrap();
+            std::fs::write(root.join("blob.bin"), vec![0u8; 2 * BYTES_PER_MB as usize]).unwrap();
+            // A run that would otherwise go on for a long time
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok("finished")
+        };
+
+        let outcome = quota(1).enforce(workspace.path(), writer).await;
+        let Err(SpiralError::WorkspaceQuotaExceeded(breach)) = outcome else {
+            panic!("expected a quota breach, got {outcome:?}");
+        };
+        assert_eq!(breach.limit_mb, 1);
+        assert_eq!(breach.size_mb, 2);
+        assert_eq!(breach.files, vec!["blob.bin", "src/lib.rs"]);
+        assert!(breach.partial_output().contains("- src/lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_work_within_quota_completes() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outcome = quota(1)
+            .enforce(workspace.path(), async { Ok::<_, SpiralError>(7) })
+            .await;
+        assert_eq!(outcome.unwrap(), 7);
+
+        // Unlimited workspaces are never scanned or annotated
+        let prompt = quota(0).annotate_prompt("build it", workspace.path()).await;
+        assert_eq!(prompt, "build it");
+    }
+
+    #[tokio::test]
+    async fn test_prompt_warns_when_workspace_is_near_quota() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::write(
+            workspace.path().join("big.bin"),
+            vec![0u8; (BYTES_PER_MB * 9 / 10) as usize],
+        )
+        .unwrap();
+        let prompt = quota(1).annotate_prompt("build it", workspace.path()).await;
+        assert!(prompt.starts_with("NOTE: This workspace already uses"));
+        assert!(prompt.ends_with("build it"));
+    }
+}
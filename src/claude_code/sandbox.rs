@@ -0,0 +1,223 @@
+//! Container sandbox for Claude Code runs
+//!
+//! With a runtime configured, each CLI invocation runs in a fresh Docker or Podman
+//! container that sees only the task workspace, under CPU, memory and process limits
+//! and, by default, without network access.
+
+use crate::config::SandboxConfig;
+use crate::{Result, SpiralError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+use tokio::process::Command;
+use tracing::info;
+
+/// Container runtimes able to host a Claude Code run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxRuntime {
+    Docker,
+    Podman,
+}
+
+impl SandboxRuntime {
+    pub fn binary(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+impl FromStr for SandboxRuntime {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "docker" => Ok(Self::Docker),
+            "podman" => Ok(Self::Podman),
+            other => Err(format!(
+                "Unknown sandbox runtime '{other}' (expected docker or podman)"
+            )),
+        }
+    }
+}
+
+/// 🛡️ SANDBOX: Where the Claude Code process runs
+/// 🏗️ ARCHITECTURE DECISION: Wrap the CLI invocation, not the individual tools
+/// Why: Claude Code decides itself which Bash commands to run; only confining the whole
+/// process bounds what those commands can reach
+/// Alternative: Drop Bash from the allowed tools (rejected: the agent can then no longer
+/// compile or test what it wrote)
+/// Trade-off: Each run pays container start-up, a second or two next to runs of minutes.
+/// Cancelling a task kills the runtime client; the container finishes on its own
+/// (still within its limits) and is then removed by --rm
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    config: SandboxConfig,
+}
+
+impl Sandbox {
+    pub fn new(config: SandboxConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.runtime.is_some()
+    }
+
+    /// Fail at startup, not on the first task, when the runtime is missing
+    pub async fn verify(&self) -> Result<()> {
+        let Some(runtime) = self.config.runtime else {
+            return Ok(());
+        };
+        let available = Command::new(runtime.binary())
+            .arg("--version")
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success());
+        if !available {
+            return Err(SpiralError::ConfigurationError(format!(
+                "CLAUDE_SANDBOX is {} but '{}' is not available",
+                runtime.binary(),
+                runtime.binary()
+            )));
+        }
+        info!(
+            "Claude Code runs sandboxed in {} image {}",
+            runtime.binary(),
+            self.config.image
+        );
+        Ok(())
+    }
+
+    /// The command starting Claude Code for a workspace; the caller adds the CLI arguments
+    /// Unsandboxed, this is the host binary run in the workspace. Sandboxed, it is a
+    /// container run with the workspace mounted at the same path, so paths Claude reports
+    /// and `--add-dir` mean the same inside and out.
+    pub fn command(&self, workspace: &Path, host_binary: &str) -> Command {
+        self.workspace_command(workspace, host_binary, &self.config.binary)
+    }
+
+    /// Any other program run in a workspace, such as its test suite; sandboxed, the
+    /// program must be installed in the image
+    pub fn program_command(&self, workspace: &Path, program: &str) -> Command {
+        self.workspace_command(workspace, program, program)
+    }
+
+    fn workspace_command(&self, workspace: &Path, host_program: &str, program: &str) -> Command {
+        let Some(runtime) = self.config.runtime else {
+            let mut command = Command::new(host_program);
+            command.current_dir(workspace);
+            return command;
+        };
+
+        let mut command = Command::new(runtime.binary());
+        command.args(self.container_args(workspace, program));
+        command
+    }
+
+    /// The command starting Claude Code with no workspace, for checks like `--version`
+    /// Sandboxed, the container gets no mounts and no network
+    pub fn probe_command(&self, host_binary: &str) -> Command {
+        let Some(runtime) = self.config.runtime else {
+            return Command::new(host_binary);
+        };
+        let mut command = Command::new(runtime.binary());
+        command.args([
+            "run",
+            "--rm",
+            "--network=none",
+            &self.config.image,
+            &self.config.binary,
+        ]);
+        command
+    }
+
+    fn container_args(&self, workspace: &Path, program: &str) -> Vec<String> {
+        let workspace = workspace.display();
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            // Keeps stdin open for the prompt
+            "-i".to_string(),
+            "--init".to_string(),
+            format!("--network={}", self.config.network),
+            format!("--cpus={}", self.config.cpus),
+            format!("--memory={}m", self.config.memory_mb),
+            format!("--pids-limit={}", self.config.pids_limit),
+            "--cap-drop=ALL".to_string(),
+            "--security-opt=no-new-privileges".to_string(),
+            format!("--volume={workspace}:{workspace}:rw"),
+            format!("--workdir={workspace}"),
+        ];
+        // Passed by name so values (API keys) never appear in the process list
+        for name in &self.config.pass_env {
+            args.push(format!("--env={name}"));
+        }
+        args.push(self.config.image.clone());
+        args.push(program.to_string());
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_args_confine_the_run() {
+        let sandbox = Sandbox::new(SandboxConfig {
+            runtime: Some(SandboxRuntime::Podman),
+            ..Default::default()
+        });
+        let args = sandbox.container_args(Path::new("/tmp/claude-workspaces/session-a"), "claude");
+
+        assert!(args.contains(&"--network=none".to_string()));
+        assert!(args.contains(&"--cap-drop=ALL".to_string()));
+        assert!(args.contains(&format!(
+            "--memory={}m",
+            crate::constants::CLAUDE_SANDBOX_DEFAULT_MEMORY_MB
+        )));
+        assert!(args.contains(
+            &"--volume=/tmp/claude-workspaces/session-a:/tmp/claude-workspaces/session-a:rw"
+                .to_string()
+        ));
+        assert!(args.contains(&"--env=ANTHROPIC_API_KEY".to_string()));
+        // Image and binary come last, so the CLI arguments follow them
+        assert_eq!(args[args.len() - 1], "claude");
+        assert_eq!(
+            args[args.len() - 2],
+            crate::constants::CLAUDE_SANDBOX_DEFAULT_IMAGE
+        );
+
+        // Only one volume: the workspace
+        let volumes = args.iter().filter(|a| a.starts_with("--volume")).count();
+        assert_eq!(volumes, 1);
+
+        // Other programs, like a test suite, run in the same kind of container
+        let test_run =
+            sandbox.program_command(Path::new("/tmp/claude-workspaces/session-a"), "cargo");
+        assert_eq!(test_run.as_std().get_program(), "podman");
+        assert_eq!(
+            test_run.as_std().get_args().last(),
+            Some(std::ffi::OsStr::new("cargo"))
+        );
+    }
+
+    #[test]
+    fn test_unsandboxed_runs_host_binary() {
+        let sandbox = Sandbox::new(SandboxConfig::default());
+        assert!(!sandbox.is_enabled());
+        let command = sandbox.command(Path::new("/tmp"), "/usr/local/bin/claude");
+        assert_eq!(
+            command.as_std().get_program(),
+            std::ffi::OsStr::new("/usr/local/bin/claude")
+        );
+        assert_eq!(
+            "Podman".parse::<SandboxRuntime>(),
+            Ok(SandboxRuntime::Podman)
+        );
+        assert!("lxc".parse::<SandboxRuntime>().is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Claude session reuse across related tasks
+//!
+//! A session key names a workspace; the Claude Code session that last ran there is
+//! remembered inside it, so the next request with the same key resumes that conversation.
+
+use super::command_builder::SessionMode;
+use crate::constants::{MAX_SESSION_KEY_LENGTH, MAX_SHARED_SESSION_KEY_LENGTH};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::fs;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use tracing::{debug, warn};
+
+/// Task context key naming a session explicitly, e.g. from an API caller
+pub const SESSION_CONTEXT_KEY: &str = "session_id";
+
+/// Task context key set by the Discord bot for messages posted in a thread
+pub const THREAD_CONTEXT_KEY: &str = "discord_thread_id";
+
+/// Prefix of session keys owned by a user session; their workspace is archived once the
+/// session ends instead of aging out
+pub const MANAGED_SESSION_KEY_PREFIX: &str = "managed-";
+
+/// File inside a session workspace holding the Claude Code session ID to resume
+const CLAUDE_SESSION_FILE: &str = ".claude-session-id";
+
+/// Session keys become workspace directory names, so only a safe alphabet is accepted
+pub fn is_valid_session_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_SESSION_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whether a key may name a workspace shared by several tasks; shorter than a task's own
+pub fn is_valid_shared_session_key(key: &str) -> bool {
+    key.len() <= MAX_SHARED_SESSION_KEY_LENGTH && is_valid_session_key(key)
+}
+
+/// 🔄 SHARED SESSION: The session a task continues, if it is related to earlier ones
+/// An explicit session_id wins over the Discord thread; an invalid one is ignored with a
+/// warning rather than failing the task
+pub fn shared_session_key(context: &HashMap<String, String>) -> Option<String> {
+    if let Some(explicit) = context.get(SESSION_CONTEXT_KEY) {
+        if is_valid_shared_session_key(explicit) {
+            return Some(explicit.clone());
+        }
+        warn!("Ignoring invalid session_id context value; starting a fresh session");
+    }
+    context
+        .get(THREAD_CONTEXT_KEY)
+        .filter(|thread_id| is_valid_shared_session_key(thread_id))
+        .map(|thread_id| format!("thread-{thread_id}"))
+}
+
+/// How to start Claude Code in a session workspace
+/// A fresh workspace starts a new session; an existing one resumes the session recorded
+/// in it, or continues the directory's latest conversation for workspaces that predate
+/// the record
+pub async fn session_mode(workspace: &Path, is_new_workspace: bool) -> SessionMode {
+    if is_new_workspace {
+        return SessionMode::NewSession;
+    }
+    match fs::read_to_string(workspace.join(CLAUDE_SESSION_FILE)).await {
+        Ok(id) if !id.trim().is_empty() => SessionMode::Resume(id.trim().to_string()),
+        _ => SessionMode::Continue,
+    }
+}
+
+/// Record the Claude Code session that just ran, for the next request to resume
+pub async fn remember_claude_session(workspace: &Path, claude_session_id: &str) {
+    if claude_session_id.is_empty() {
+        return;
+    }
+    if let Err(e) = fs::write(workspace.join(CLAUDE_SESSION_FILE), claude_session_id).await {
+        // Only costs context on the next request, so it does not fail this one
+        warn!("Failed to record Claude session in {:?}: {}", workspace, e);
+    } else {
+        debug!(
+            "Recorded Claude session {} in {:?}",
+            claude_session_id, workspace
+        );
+    }
+}
+
+/// 🔒 SESSION LOCKS: One Claude Code run per session at a time
+/// 🏗️ ARCHITECTURE DECISION: Related tasks queue behind each other instead of running together
+/// Why: Two runs resuming the same conversation in the same workspace would both branch
+/// from the same point and overwrite each other's files
+/// Alternative: Fork the session for concurrent tasks (rejected: the follow-up would not
+/// see the earlier task's work, which is the point of sharing)
+#[derive(Debug, Clone, Default)]
+pub struct SessionLocks {
+    locks: Arc<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>>,
+}
+
+impl SessionLocks {
+    /// Wait for exclusive use of the session; None needs no lock, as its workspace is fresh
+    pub async fn acquire(&self, session_id: Option<&str>) -> Option<OwnedMutexGuard<()>> {
+        Some(self.lock_for(session_id?).lock_owned().await)
+    }
+
+    /// Exclusive use of the session if no run holds it, without waiting
+    pub fn try_acquire(&self, session_id: &str) -> Option<OwnedMutexGuard<()>> {
+        self.lock_for(session_id).try_lock_owned().ok()
+    }
+
+    fn lock_for(&self, session_id: &str) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        // Forget sessions nobody holds or waits for
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks.entry(session_id.to_string()).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_shared_session_key_prefers_explicit_session() {
+        let mut context = HashMap::from([(THREAD_CONTEXT_KEY.to_string(), "123".to_string())]);
+        assert_eq!(shared_session_key(&context).as_deref(), Some("thread-123"));
+
+        context.insert(SESSION_CONTEXT_KEY.to_string(), "feature-x".to_string());
+        assert_eq!(shared_session_key(&context).as_deref(), Some("feature-x"));
+
+        // A key that could escape the workspace directory falls back to the thread
+        context.insert(SESSION_CONTEXT_KEY.to_string(), "../etc".to_string());
+        assert_eq!(shared_session_key(&context).as_deref(), Some("thread-123"));
+        assert_eq!(shared_session_key(&HashMap::new()), None);
+        assert!(!is_valid_session_key(
+            &"a".repeat(MAX_SESSION_KEY_LENGTH + 1)
+        ));
+
+        let long = "a".repeat(MAX_SHARED_SESSION_KEY_LENGTH + 1);
+        context.insert(SESSION_CONTEXT_KEY.to_string(), long);
+        assert_eq!(shared_session_key(&context).as_deref(), Some("thread-123"));
+    }
+
+    #[tokio::test]
+    async fn test_session_mode_resumes_recorded_session() {
+        let workspace = tempfile::tempdir().unwrap();
+        assert_eq!(
+            session_mode(workspace.path(), true).await,
+            SessionMode::NewSession
+        );
+        assert_eq!(
+            session_mode(workspace.path(), false).await,
+            SessionMode::Continue
+        );
+
+        remember_claude_session(workspace.path(), "3f2a-claude").await;
+        assert_eq!(
+            session_mode(workspace.path(), false).await,
+            SessionMode::Resume("3f2a-claude".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_locks_serialize_runs_in_one_session() {
+        let locks = SessionLocks::default();
+        let held = locks.acquire(Some("thread-1")).await;
+        assert!(held.is_some());
+        assert!(locks.acquire(None).await.is_none());
+
+        // Another session is independent
+        assert!(locks.acquire(Some("thread-2")).await.is_some());
+
+        let waiting =
+            tokio::time::timeout(Duration::from_millis(50), locks.acquire(Some("thread-1")));
+        assert!(waiting.await.is_err());
+
+        assert!(locks.try_acquire("thread-1").is_none());
+
+        drop(held);
+        assert!(locks.acquire(Some("thread-1")).await.is_some());
+        assert!(locks.try_acquire("thread-1").is_some());
+    }
+}
@@ -0,0 +1,192 @@
+use super::cli_client::{ClaudeCodeCliResponse, CodeGenerationResult};
+use super::quota::QuotaBreach;
+use crate::SpiralError;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Longest tool target (file path, shell command) carried in a ToolUse event
+const MAX_TOOL_TARGET_CHARS: usize = 120;
+
+/// One incremental update from a streaming generation
+/// The stream always ends with exactly one Completed, Failed or QuotaExceeded
+#[derive(Debug, Clone)]
+pub enum GenerationEvent {
+    /// The CLI started or resumed its session
+    Started { session_id: String },
+    /// A block of assistant text, as soon as Claude produced it
+    Text(String),
+    /// Claude called a tool, e.g. Write with the file it is writing
+    ToolUse {
+        tool: String,
+        target: Option<String>,
+    },
+    /// The finished generation
+    Completed(CodeGenerationResult),
+    /// The generation failed or was refused; carries the reason
+    Failed(String),
+    /// The generation was stopped for outgrowing its workspace; carries what it left
+    QuotaExceeded(QuotaBreach),
+}
+
+impl GenerationEvent {
+    /// The final event for a generation that ended in an error
+    pub fn failure(error: SpiralError) -> Self {
+        match error {
+            SpiralError::WorkspaceQuotaExceeded(breach) => Self::QuotaExceeded(breach),
+            other => Self::Failed(other.to_string()),
+        }
+    }
+
+    /// Short human-readable description, for progress messages
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            Self::Text(text) => text
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(|line| truncate(line, MAX_TOOL_TARGET_CHARS)),
+            Self::ToolUse {
+                tool,
+                target: Some(target),
+            } => Some(format!("{tool} {target}")),
+            Self::ToolUse { tool, target: None } => Some(tool.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// What one line of `--output-format stream-json` turned into
+#[derive(Debug)]
+pub(super) enum StreamLine {
+    Event(GenerationEvent),
+    /// The final line; same shape as the `--output-format json` response
+    Result(Box<ClaudeCodeCliResponse>),
+}
+
+#[derive(Deserialize)]
+struct RawLine {
+    #[serde(rename = "type")]
+    line_type: String,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    message: Option<Value>,
+}
+
+/// Parse one stream-json line; unknown or uninteresting lines yield nothing
+/// An assistant message can hold several content blocks, so one line may give several events
+pub(super) fn parse_stream_line(line: &str) -> Vec<StreamLine> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Vec::new();
+    }
+    let Ok(raw) = serde_json::from_str::<RawLine>(line) else {
+        return Vec::new();
+    };
+
+    match raw.line_type.as_str() {
+        "system" if raw.subtype.as_deref() == Some("init") => raw
+            .session_id
+            .map(|session_id| vec![StreamLine::Event(GenerationEvent::Started { session_id })])
+            .unwrap_or_default(),
+        "assistant" => raw
+            .message
+            .as_ref()
+            .and_then(|message| message.get("content"))
+            .and_then(Value::as_array)
+            .map(|blocks| blocks.iter().filter_map(content_event).collect())
+            .unwrap_or_default(),
+        "result" => serde_json::from_str::<ClaudeCodeCliResponse>(line)
+            .map(|response| vec![StreamLine::Result(Box::new(response))])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn content_event(block: &Value) -> Option<StreamLine> {
+    let event = match block.get("type")?.as_str()? {
+        "text" => {
+            let text = block.get("text")?.as_str()?.trim();
+            if text.is_empty() {
+                return None;
+            }
+            GenerationEvent::Text(text.to_string())
+        }
+        "tool_use" => {
+            let input = block.get("input");
+            let target = ["file_path", "path", "command", "pattern", "url"]
+                .iter()
+                .find_map(|key| input?.get(key)?.as_str())
+                .map(|target| truncate(target, MAX_TOOL_TARGET_CHARS));
+            GenerationEvent::ToolUse {
+                tool: block.get("name")?.as_str()?.to_string(),
+                target,
+            }
+        }
+        _ => return None,
+    };
+    Some(StreamLine::Event(event))
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        format!("{}...", text.chars().take(max_chars).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_assistant_blocks_into_events() {
+        let line = r#"{"type":"assistant","message":{"content":[
+            {"type":"text","text":"I'll create the module.\nThen test it."},
+            {"type":"tool_use","name":"Write","input":{"file_path":"src/lib.rs","content":"fn main() {}"}},
+            {"type":"tool_use","name":"TodoWrite","input":{"todos":[]}}
+        ]}}"#
+            .replace('\n', "");
+        let events: Vec<GenerationEvent> = parse_stream_line(&line)
+            .into_iter()
+            .map(|parsed| match parsed {
+                StreamLine::Event(event) => event,
+                StreamLine::Result(_) => panic!("not a result line"),
+            })
+            .collect();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0].summary().as_deref(),
+            Some("I'll create the module.")
+        );
+        assert_eq!(events[1].summary().as_deref(), Some("Write src/lib.rs"));
+        assert_eq!(events[2].summary().as_deref(), Some("TodoWrite"));
+    }
+
+    #[test]
+    fn test_parses_init_and_result_lines() {
+        let init = r#"{"type":"system","subtype":"init","session_id":"abc","tools":[]}"#;
+        assert!(matches!(
+            parse_stream_line(init).as_slice(),
+            [StreamLine::Event(GenerationEvent::Started { session_id })] if session_id == "abc"
+        ));
+
+        let result = r#"{"type":"result","subtype":"success","is_error":false,"duration_ms":10,
+            "duration_api_ms":8,"num_turns":2,"result":"Done","session_id":"abc",
+            "total_cost_usd":0.01,"usage":{"input_tokens":5,"output_tokens":7,"service_tier":"standard"}}"#
+            .replace('\n', "");
+        assert!(matches!(
+            parse_stream_line(&result).as_slice(),
+            [StreamLine::Result(response)] if response.result == "Done"
+        ));
+
+        // Tool results, blank lines and non-JSON noise are skipped
+        assert!(parse_stream_line(r#"{"type":"user","message":{"content":[]}}"#).is_empty());
+        assert!(parse_stream_line("").is_empty());
+        assert!(parse_stream_line("warning: something").is_empty());
+    }
+}
@@ -73,6 +73,8 @@ impl MockClaudeClient {
             files_to_modify: vec![],
             session_id: Some("mock-session".to_string()),
             workspace_path: "/tmp/mock-workspace".to_string(),
+            changes: vec![],
+            dry_run: request.dry_run,
         })
     }
 
@@ -93,6 +95,7 @@ impl MockClaudeClient {
             existing_code: None,
             requirements: vec![],
             session_id: None,
+            dry_run: false,
         };
 
         self.generate_code(request).await
@@ -43,6 +43,7 @@ async fn test_workspace_isolation() {
         existing_code: None,
         requirements: vec![],
         session_id: Some(session1_id.to_string()),
+        dry_run: false,
     };
 
     let result1 = client
@@ -58,6 +59,7 @@ async fn test_workspace_isolation() {
         existing_code: None,
         requirements: vec![],
         session_id: Some(session2_id.to_string()),
+        dry_run: false,
     };
 
     let result2 = client
@@ -106,6 +108,7 @@ async fn test_permission_escalation_audit() {
         existing_code: None,
         requirements: vec!["Write system files".to_string()],
         session_id: None,
+        dry_run: false,
     };
 
     // Capture logs to verify security events are logged
@@ -158,6 +161,7 @@ async fn test_directory_traversal_protection() {
             existing_code: None,
             requirements: vec![],
             session_id: None,
+            dry_run: false,
         };
 
         let result = client.generate_code(request).await;
@@ -221,6 +225,7 @@ async fn test_workspace_cleanup() {
             existing_code: None,
             requirements: vec![],
             session_id: Some(session_id.to_string()),
+            dry_run: false,
         };
 
         let _ = client
@@ -273,6 +278,7 @@ async fn test_session_continuity_security() {
         existing_code: None,
         requirements: vec![],
         session_id: Some(session_id.to_string()),
+        dry_run: false,
     };
 
     let _ = client
@@ -288,6 +294,7 @@ async fn test_session_continuity_security() {
         existing_code: None,
         requirements: vec![],
         session_id: Some(different_session.to_string()),
+        dry_run: false,
     };
 
     let result2 = client
@@ -339,6 +346,7 @@ async fn test_concurrent_session_access() {
                 existing_code: None,
                 requirements: vec![],
                 session_id: Some(session_id.clone()),
+                dry_run: false,
             };
 
             client_clone
@@ -384,6 +392,15 @@ fn create_test_config() -> ClaudeCodeConfig {
         workspace_cleanup_after_hours: 1, // Clean up after 1 hour for tests
         timeout_seconds: 60,              // Short timeout for tests
         max_workspace_size_mb: 100,
+        max_concurrent_invocations: 4,
+        daily_budget_usd: None,
+        response_cache: Default::default(),
+        circuit_breaker: Default::default(),
+        sandbox: Default::default(),
+        mcp_servers: Default::default(),
+        verify_cli_version: false,
+        workspace_templates: Default::default(),
+        transcripts: Default::default(),
     }
 }
 
@@ -104,6 +104,7 @@ async fn test_prompt_construction() {
             "Follow SOLID principles".to_string(),
         ],
         session_id: None,
+        dry_run: false,
     };
 
     // 🔍 TEST REQUEST VALIDATION: Ensure request structure is valid
@@ -149,6 +150,15 @@ async fn test_api_url_validation() {
         allowed_tools: vec![],
         workspace_cleanup_after_hours: 0,
         max_workspace_size_mb: 0, // Invalid size
+        max_concurrent_invocations: 4,
+        daily_budget_usd: None,
+        response_cache: Default::default(),
+        circuit_breaker: Default::default(),
+        sandbox: Default::default(),
+        mcp_servers: Default::default(),
+        verify_cli_version: false,
+        workspace_templates: Default::default(),
+        transcripts: Default::default(),
     };
 
     let _result = ClaudeCodeClient::new(invalid_config).await;
@@ -246,6 +256,15 @@ fn create_test_config() -> ClaudeCodeConfig {
         allowed_tools: vec!["Edit".to_string(), "Write".to_string(), "Read".to_string()],
         workspace_cleanup_after_hours: 24,
         max_workspace_size_mb: 100,
+        max_concurrent_invocations: 4,
+        daily_budget_usd: None,
+        response_cache: Default::default(),
+        circuit_breaker: Default::default(),
+        sandbox: Default::default(),
+        mcp_servers: Default::default(),
+        verify_cli_version: false,
+        workspace_templates: Default::default(),
+        transcripts: Default::default(),
     }
 }
 
@@ -266,6 +285,15 @@ async fn test_find_claude_binary() {
         allowed_tools: vec!["Read".to_string()],
         workspace_cleanup_after_hours: 24,
         max_workspace_size_mb: 100,
+        max_concurrent_invocations: 4,
+        daily_budget_usd: None,
+        response_cache: Default::default(),
+        circuit_breaker: Default::default(),
+        sandbox: Default::default(),
+        mcp_servers: Default::default(),
+        verify_cli_version: false,
+        workspace_templates: Default::default(),
+        transcripts: Default::default(),
     };
 
     // This should succeed if Claude is installed
@@ -304,6 +332,15 @@ mod integration_helpers {
             allowed_tools: vec!["Edit".to_string(), "Write".to_string(), "Read".to_string()],
             workspace_cleanup_after_hours: 24,
             max_workspace_size_mb: 100,
+            max_concurrent_invocations: 4,
+            daily_budget_usd: None,
+            response_cache: Default::default(),
+            circuit_breaker: Default::default(),
+            sandbox: Default::default(),
+            mcp_servers: Default::default(),
+            verify_cli_version: false,
+            workspace_templates: Default::default(),
+            transcripts: Default::default(),
         }
     }
 }
@@ -0,0 +1,269 @@
+//! Claude Code CLI version detection
+//!
+//! The CLI's flags and output change between releases. The version is read once at
+//! startup: a CLI older than the supported minimum stops startup with an upgrade hint,
+//! and features newer than the installed CLI are worked around or refused up front
+//! instead of failing mid-task on output that no longer parses.
+
+use crate::constants::{
+    CLAUDE_CLI_MCP_CONFIG_VERSION, CLAUDE_CLI_MIN_VERSION, CLAUDE_CLI_STREAM_JSON_VERSION,
+    CLAUDE_CLI_TESTED_MAJOR, CLAUDE_CLI_VERSION_TIMEOUT_SECS,
+};
+use crate::{Result, SpiralError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// A CLI release, e.g. 1.0.35
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CliVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl CliVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// The first `x.y.z` in `--version` output such as "1.0.35 (Claude Code)"
+    /// Pre-release suffixes ("1.1.0-beta.2") are ignored
+    pub fn parse(output: &str) -> Option<Self> {
+        output.split_whitespace().find_map(|word| {
+            let word = word.trim_start_matches('v');
+            let core = word.split(['-', '+']).next()?;
+            let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+            let version = Self::new(parts.next()??, parts.next()??, parts.next()??);
+            parts.next().is_none().then_some(version)
+        })
+    }
+}
+
+impl fmt::Display for CliVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// CLI features Spiral Core relies on that not every supported release has
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CliFeature {
+    /// `--output-format stream-json`, for live progress; without it generations are
+    /// reported when they finish
+    StreamJson,
+    /// `--mcp-config`, for MCP servers; without it configured servers stop startup
+    McpConfig,
+}
+
+impl CliFeature {
+    pub const ALL: [CliFeature; 2] = [CliFeature::StreamJson, CliFeature::McpConfig];
+
+    /// The oldest CLI whose output for this feature Spiral Core parses
+    pub fn min_version(self) -> CliVersion {
+        match self {
+            CliFeature::StreamJson => CLAUDE_CLI_STREAM_JSON_VERSION,
+            CliFeature::McpConfig => CLAUDE_CLI_MCP_CONFIG_VERSION,
+        }
+    }
+
+    fn flag(self) -> &'static str {
+        match self {
+            CliFeature::StreamJson => "--output-format stream-json",
+            CliFeature::McpConfig => "--mcp-config",
+        }
+    }
+}
+
+/// The CLI in use, as reported by the system status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ClaudeCliStatus {
+    pub binary: String,
+    /// None when the version check is disabled
+    pub version: Option<String>,
+    pub minimum_version: String,
+    /// Features the installed CLI is too old for
+    pub unavailable_features: Vec<CliFeature>,
+}
+
+/// 🔖 CLI COMPATIBILITY: What the installed Claude Code CLI can do
+/// 🏗️ ARCHITECTURE DECISION: Check the version once at startup and gate on it
+/// Why: An unknown flag or a renamed result field only shows up as a parse error in the
+/// middle of someone's task; the version tells us up front
+/// Alternative: Probe each flag with `--help` (rejected: help text is not a stable
+/// interface, and it cannot reveal output format changes)
+/// Trade-off: The version minimums are maintained by hand in constants.rs
+#[derive(Debug, Clone, Default)]
+pub struct CliCompatibility {
+    /// None when the check is disabled; every feature is then assumed available
+    version: Option<CliVersion>,
+}
+
+impl CliCompatibility {
+    /// Compatibility of a known version, refusing one older than the supported minimum
+    pub fn for_version(version: CliVersion) -> Result<Self> {
+        if version < CLAUDE_CLI_MIN_VERSION {
+            return Err(SpiralError::ConfigurationError(format!(
+                "Claude Code CLI {version} is too old; Spiral Core needs {CLAUDE_CLI_MIN_VERSION} \
+                 or newer. Upgrade with `npm install -g @anthropic-ai/claude-code`"
+            )));
+        }
+        if version.major > CLAUDE_CLI_TESTED_MAJOR {
+            warn!(
+                "Claude Code CLI {} is newer than the {}.x releases Spiral Core was tested with",
+                version, CLAUDE_CLI_TESTED_MAJOR
+            );
+        }
+        Ok(Self {
+            version: Some(version),
+        })
+    }
+
+    /// Run `<cli> --version` and check the result; `command` starts the CLI
+    pub async fn detect(mut command: Command) -> Result<Self> {
+        let unusable = |reason: String| {
+            SpiralError::ConfigurationError(format!(
+                "Cannot determine the Claude Code CLI version: {reason}. Check \
+                 CLAUDE_BINARY_PATH, or set CLAUDE_VERIFY_CLI_VERSION=false to skip the check"
+            ))
+        };
+        command.arg("--version").kill_on_drop(true);
+        let output = tokio::time::timeout(
+            Duration::from_secs(CLAUDE_CLI_VERSION_TIMEOUT_SECS),
+            command.output(),
+        )
+        .await
+        .map_err(|_| unusable("`--version` did not answer in time".to_string()))?
+        .map_err(|e| unusable(format!("the CLI could not be started ({e})")))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(unusable(format!("`--version` failed: {}", stderr.trim())));
+        }
+        let version = CliVersion::parse(&stdout)
+            .ok_or_else(|| unusable(format!("unrecognised output {:?}", stdout.trim())))?;
+
+        let compatibility = Self::for_version(version)?;
+        let missing = compatibility.unavailable_features();
+        if missing.is_empty() {
+            info!("Claude Code CLI {} detected", version);
+        } else {
+            warn!(
+                "Claude Code CLI {} detected; too old for {:?}",
+                version, missing
+            );
+        }
+        Ok(compatibility)
+    }
+
+    pub fn version(&self) -> Option<CliVersion> {
+        self.version
+    }
+
+    pub fn supports(&self, feature: CliFeature) -> bool {
+        self.version
+            .is_none_or(|version| version >= feature.min_version())
+    }
+
+    pub fn unavailable_features(&self) -> Vec<CliFeature> {
+        CliFeature::ALL
+            .into_iter()
+            .filter(|feature| !self.supports(*feature))
+            .collect()
+    }
+
+    /// Refuse a configured feature the CLI is too old for; `purpose` says what needs it
+    pub fn require(&self, feature: CliFeature, purpose: &str) -> Result<()> {
+        match self.version {
+            Some(version) if !self.supports(feature) => {
+                Err(SpiralError::ConfigurationError(format!(
+                    "{purpose} needs `{}` from Claude Code CLI {} or newer, but {version} is \
+                     installed",
+                    feature.flag(),
+                    feature.min_version()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn status(&self, binary: &str) -> ClaudeCliStatus {
+        ClaudeCliStatus {
+            binary: binary.to_string(),
+            version: self.version.map(|version| version.to_string()),
+            minimum_version: CLAUDE_CLI_MIN_VERSION.to_string(),
+            unavailable_features: self.unavailable_features(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::CLAUDE_CLI_MCP_CONFIG_VERSION;
+
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(
+            CliVersion::parse("1.0.35 (Claude Code)"),
+            Some(CliVersion::new(1, 0, 35))
+        );
+        assert_eq!(
+            CliVersion::parse("claude v2.1.0-beta.2\n"),
+            Some(CliVersion::new(2, 1, 0))
+        );
+        assert_eq!(CliVersion::parse("Claude Code"), None);
+        assert_eq!(CliVersion::parse("1.0"), None);
+    }
+
+    #[test]
+    fn test_features_are_gated_on_the_detected_version() {
+        let too_old = CliVersion::new(0, 2, 125);
+        let error = CliCompatibility::for_version(too_old).unwrap_err();
+        assert!(error.to_string().contains("too old"));
+
+        let minimum = CliCompatibility::for_version(CLAUDE_CLI_MIN_VERSION).unwrap();
+        assert_eq!(
+            minimum.unavailable_features(),
+            [CliFeature::StreamJson, CliFeature::McpConfig]
+        );
+        let error = minimum
+            .require(CliFeature::McpConfig, "MCP servers")
+            .unwrap_err();
+        assert!(error.to_string().contains("--mcp-config"));
+
+        let current = CliCompatibility::for_version(CLAUDE_CLI_MCP_CONFIG_VERSION).unwrap();
+        assert!(current.unavailable_features().is_empty());
+        assert!(current
+            .require(CliFeature::McpConfig, "MCP servers")
+            .is_ok());
+
+        // Unchecked CLIs are assumed to support everything
+        let unchecked = CliCompatibility::default();
+        assert!(unchecked.supports(CliFeature::StreamJson));
+        assert_eq!(unchecked.status("claude").version, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detect_runs_the_cli() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo '1.0.99 (Claude Code)'", "sh"]);
+        let detected = CliCompatibility::detect(command).await.unwrap();
+        assert_eq!(detected.version(), Some(CliVersion::new(1, 0, 99)));
+
+        let error = CliCompatibility::detect(Command::new("/nonexistent/claude"))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("CLAUDE_VERIFY_CLI_VERSION"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Workspace archives
+//!
+//! A workspace is packed into a `.tar.gz` before it is removed, both when an admin archives
+//! one through the API and when the session that owned it ends.
+
+use flate2::{write::GzEncoder, Compression};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where a workspace ended up after archiving
+#[derive(Debug, Clone)]
+pub struct ArchivedWorkspace {
+    pub archive_path: PathBuf,
+    pub archive_size_bytes: u64,
+}
+
+/// Pack a workspace into `<archive_dir>/<id>-<timestamp>.tar.gz`, then remove it
+/// The archive is written under a temporary name first, so the workspace is only
+/// removed once a complete archive exists
+pub fn archive_workspace(
+    workspace: &Path,
+    archive_dir: &Path,
+    workspace_id: &str,
+) -> io::Result<ArchivedWorkspace> {
+    fs::create_dir_all(archive_dir)?;
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let archive_path = archive_dir.join(format!("{workspace_id}-{stamp}.tar.gz"));
+    let partial_path = archive_path.with_extension("gz.partial");
+
+    let result = write_archive(workspace, &partial_path, workspace_id)
+        .and_then(|()| fs::rename(&partial_path, &archive_path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial_path);
+        return Err(e);
+    }
+
+    fs::remove_dir_all(workspace)?;
+    Ok(ArchivedWorkspace {
+        archive_size_bytes: fs::metadata(&archive_path)?.len(),
+        archive_path,
+    })
+}
+
+fn write_archive(workspace: &Path, destination: &Path, workspace_id: &str) -> io::Result<()> {
+    let encoder = GzEncoder::new(fs::File::create(destination)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    // Store links as links; following them could pull in files from outside the workspace
+    builder.follow_symlinks(false);
+    builder.append_dir_all(workspace_id, workspace)?;
+    builder.into_inner()?.finish()?.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+
+    #[test]
+    fn test_archive_packs_then_removes_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("session-abc");
+        fs::create_dir_all(workspace.join("src")).unwrap();
+        fs::write(workspace.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(workspace.join("Cargo.toml"), "[package]").unwrap();
+        let archive_dir = dir.path().join("archive");
+
+        let archived = archive_workspace(&workspace, &archive_dir, "session-abc").unwrap();
+        assert!(!workspace.exists());
+        assert!(archived.archive_size_bytes > 0);
+        assert_eq!(fs::read_dir(&archive_dir).unwrap().count(), 1);
+
+        let mut archive = tar::Archive::new(GzDecoder::new(
+            fs::File::open(&archived.archive_path).unwrap(),
+        ));
+        let entries: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert!(entries.contains(&"session-abc/src/main.rs".to_string()));
+        assert!(entries.contains(&"session-abc/Cargo.toml".to_string()));
+    }
+}
@@ -0,0 +1,495 @@
+//! Workspace templates: scaffolds a new Claude Code workspace starts from
+//!
+//! A task names a template in its `workspace_template` context key: a built-in one such
+//! as `rust-lib`, one registered in the templates file, or (when allowed) a git URL. The
+//! template's files are copied into the new workspace before Claude Code first runs, so
+//! generation builds on a known layout instead of inventing one every time.
+
+use crate::config::{WorkspaceTemplateConfig, WorkspaceTemplateSource};
+use crate::constants::{WORKSPACE_TEMPLATE_CLONE_TIMEOUT_SECS, WORKSPACE_TEMPLATE_MAX_BYTES};
+use crate::{Result, SpiralError};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use tokio::process::Command;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Context key naming the template a task's workspace is seeded with
+pub const WORKSPACE_TEMPLATE_CONTEXT_KEY: &str = "workspace_template";
+
+const RUST_LIB: &[(&str, &str)] = &[
+    (
+        "Cargo.toml",
+        "[package]\nname = \"scaffold\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+    ),
+    (
+        "src/lib.rs",
+        "//! Library scaffold\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_builds() {}\n}\n",
+    ),
+    (".gitignore", "/target\n"),
+];
+
+const NEXTJS_APP: &[(&str, &str)] = &[
+    (
+        "package.json",
+        r#"{
+  "name": "scaffold",
+  "version": "0.1.0",
+  "private": true,
+  "scripts": {
+    "dev": "next dev",
+    "build": "next build",
+    "start": "next start",
+    "lint": "next lint"
+  },
+  "dependencies": {
+    "next": "^14.2.0",
+    "react": "^18.3.0",
+    "react-dom": "^18.3.0"
+  },
+  "devDependencies": {
+    "@types/node": "^20.0.0",
+    "@types/react": "^18.3.0",
+    "typescript": "^5.4.0"
+  }
+}
+"#,
+    ),
+    (
+        "tsconfig.json",
+        r#"{
+  "compilerOptions": {
+    "target": "ES2017",
+    "lib": ["dom", "dom.iterable", "esnext"],
+    "strict": true,
+    "noEmit": true,
+    "module": "esnext",
+    "moduleResolution": "bundler",
+    "jsx": "preserve",
+    "incremental": true,
+    "plugins": [{ "name": "next" }]
+  },
+  "include": ["next-env.d.ts", "**/*.ts", "**/*.tsx"],
+  "exclude": ["node_modules"]
+}
+"#,
+    ),
+    (
+        "app/layout.tsx",
+        "export default function RootLayout({ children }: { children: React.ReactNode }) {\n  return (\n    <html lang=\"en\">\n      <body>{children}</body>\n    </html>\n  );\n}\n",
+    ),
+    (
+        "app/page.tsx",
+        "export default function Home() {\n  return <main>Hello</main>;\n}\n",
+    ),
+    (".gitignore", "/node_modules\n/.next\n"),
+];
+
+/// Template files by `/`-separated path relative to the template root
+pub type TemplateFiles = BTreeMap<String, Vec<u8>>;
+
+/// Where a template's files come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateSource {
+    /// Files compiled into the binary
+    Builtin(&'static [(&'static str, &'static str)]),
+    Directory(PathBuf),
+    /// Shallow clone of a branch or tag; the default branch when `rev` is None
+    Git {
+        url: String,
+        rev: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceTemplate {
+    pub source: TemplateSource,
+    /// Expected checksum of the files (see `checksum`); seeding refuses any mismatch
+    pub sha256: Option<String>,
+}
+
+impl WorkspaceTemplate {
+    fn from_config(name: &str, config: &WorkspaceTemplateSource) -> Result<Self> {
+        let source = match (&config.path, &config.git) {
+            (Some(path), None) => TemplateSource::Directory(PathBuf::from(path)),
+            (None, Some(url)) => TemplateSource::Git {
+                url: url.clone(),
+                rev: config.rev.clone(),
+            },
+            _ => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "Workspace template '{name}' needs exactly one of `path` and `git`"
+                )))
+            }
+        };
+        Ok(Self {
+            source,
+            sha256: config.sha256.clone(),
+        })
+    }
+}
+
+/// SHA-256 over every file's path, length and content, in path order
+/// The same files always give the same checksum, wherever they were read from
+pub fn checksum(files: &TemplateFiles) -> String {
+    let mut hasher = Sha256::new();
+    for (path, content) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(content);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Names are typed into task context and show up in logs, so only a safe alphabet is accepted
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_git_url(spec: &str) -> bool {
+    spec.contains("://") || spec.starts_with("git@")
+}
+
+/// 🛡️ SECURITY AUDIT CHECKPOINT: Only plain https URLs reach `git clone`
+/// file://, ssh and ext:: transports could read the host or run commands
+fn validate_git_url(url: &str) -> Result<()> {
+    let valid = url.starts_with("https://")
+        && url.len() > "https://".len()
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control());
+    if valid {
+        Ok(())
+    } else {
+        Err(SpiralError::Validation(format!(
+            "Workspace template URL '{url}' is not an https git URL"
+        )))
+    }
+}
+
+/// 📦 WORKSPACE TEMPLATES: The scaffolds tasks can start from
+/// 🏗️ ARCHITECTURE DECISION: Copy the files in before the first run
+/// Why: Claude starts from the layout, build files and conventions the team already uses,
+/// and the template's files never show up as changes the run made
+/// Alternative: Describe the scaffold in the prompt (rejected: Claude re-creates it
+/// differently each time, and the tokens are spent on boilerplate)
+/// Trade-off: Git templates are cloned per workspace; pin them with a checksum so a
+/// changed repository is refused instead of silently seeding something else
+#[derive(Debug, Clone)]
+pub struct WorkspaceTemplates {
+    templates: BTreeMap<String, WorkspaceTemplate>,
+    allow_git_urls: bool,
+}
+
+impl WorkspaceTemplates {
+    /// Only the templates compiled into the binary; git URLs are refused
+    pub fn builtin() -> Self {
+        let builtin = |files| WorkspaceTemplate {
+            source: TemplateSource::Builtin(files),
+            sha256: None,
+        };
+        Self {
+            templates: BTreeMap::from([
+                ("rust-lib".to_string(), builtin(RUST_LIB)),
+                ("nextjs-app".to_string(), builtin(NEXTJS_APP)),
+            ]),
+            allow_git_urls: false,
+        }
+    }
+
+    /// The built-in templates plus the configured ones
+    pub fn from_config(config: &WorkspaceTemplateConfig) -> Result<Self> {
+        let mut templates = Self::builtin();
+        templates.allow_git_urls = config.allow_git_urls;
+        for (name, source) in &config.templates {
+            templates.register(name, WorkspaceTemplate::from_config(name, source)?)?;
+        }
+        Ok(templates)
+    }
+
+    /// Add a template, replacing any of the same name
+    pub fn register(&mut self, name: &str, template: WorkspaceTemplate) -> Result<()> {
+        let invalid = |reason: String| {
+            SpiralError::ConfigurationError(format!("Workspace template '{name}' {reason}"))
+        };
+        if !is_valid_name(name) {
+            return Err(invalid(
+                "has an invalid name (use letters, digits, '-' and '_')".to_string(),
+            ));
+        }
+        if let Some(sha256) = &template.sha256 {
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid(
+                    "has a sha256 that is not 64 hex digits".to_string(),
+                ));
+            }
+        }
+        match &template.source {
+            TemplateSource::Directory(path) if !path.is_dir() => {
+                return Err(invalid(format!("directory {path:?} does not exist")));
+            }
+            TemplateSource::Git { url, .. } => {
+                validate_git_url(url).map_err(|e| invalid(e.to_string()))?
+            }
+            _ => {}
+        }
+
+        if self.templates.insert(name.to_string(), template).is_some() {
+            warn!("Workspace template '{}' replaces an earlier one", name);
+        }
+        Ok(())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// The template a task named: a registered name, or an allowed git URL
+    fn resolve(&self, spec: &str) -> Result<WorkspaceTemplate> {
+        if let Some(template) = self.templates.get(spec) {
+            return Ok(template.clone());
+        }
+        if !is_git_url(spec) {
+            return Err(SpiralError::Validation(format!(
+                "Unknown workspace template '{spec}' (available: {})",
+                self.names().collect::<Vec<_>>().join(", ")
+            )));
+        }
+        if !self.allow_git_urls {
+            return Err(SpiralError::Validation(
+                "Git URLs as workspace templates are disabled; register the repository as a \
+                 template or set CLAUDE_WORKSPACE_TEMPLATE_GIT_URLS=true"
+                    .to_string(),
+            ));
+        }
+        validate_git_url(spec)?;
+        Ok(WorkspaceTemplate {
+            source: TemplateSource::Git {
+                url: spec.to_string(),
+                rev: None,
+            },
+            sha256: None,
+        })
+    }
+
+    /// Check that a task's template exists before any work is queued for it
+    pub fn validate(&self, spec: &str) -> Result<()> {
+        self.resolve(spec).map(|_| ())
+    }
+
+    /// Copy the named template into a workspace and return the files' checksum
+    pub async fn seed(&self, spec: &str, workspace: &Path) -> Result<String> {
+        let template = self.resolve(spec)?;
+        let files = read_source(&template.source).await?;
+        let actual = checksum(&files);
+        if let Some(expected) = &template.sha256 {
+            if !expected.eq_ignore_ascii_case(&actual) {
+                return Err(SpiralError::Security(format!(
+                    "Workspace template '{spec}' does not match its checksum (expected \
+                     {expected}, found {actual})"
+                )));
+            }
+        }
+
+        for (path, content) in &files {
+            write_file(&workspace.join(path), content)
+                .await
+                .map_err(|e| SpiralError::Agent {
+                    message: format!("Failed to seed {path} from template '{spec}': {e}"),
+                })?;
+        }
+        info!(
+            "Seeded workspace {:?} from template '{}' ({} files, sha256 {})",
+            workspace,
+            spec,
+            files.len(),
+            actual
+        );
+        Ok(actual)
+    }
+}
+
+async fn write_file(target: &Path, content: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(target, content).await
+}
+
+async fn read_source(source: &TemplateSource) -> Result<TemplateFiles> {
+    match source {
+        TemplateSource::Builtin(files) => Ok(files
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+            .collect()),
+        TemplateSource::Directory(path) => read_dir_files(path).await,
+        TemplateSource::Git { url, rev } => clone(url, rev.as_deref()).await,
+    }
+}
+
+/// Shallow-clone a repository into a scratch directory and read its files
+async fn clone(url: &str, rev: Option<&str>) -> Result<TemplateFiles> {
+    let scratch = std::env::temp_dir().join(format!("spiral-template-{}", Uuid::new_v4()));
+    let mut command = Command::new("git");
+    command
+        .args(["clone", "--quiet", "--depth", "1"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true);
+    if let Some(rev) = rev {
+        command.args(["--branch", rev]);
+    }
+    command.arg("--").arg(url).arg(&scratch);
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(WORKSPACE_TEMPLATE_CLONE_TIMEOUT_SECS),
+        command.output(),
+    )
+    .await;
+    let files = match output {
+        Ok(Ok(output)) if output.status.success() => read_dir_files(&scratch).await,
+        Ok(Ok(output)) => Err(SpiralError::Git {
+            message: format!(
+                "Cloning workspace template {url} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }),
+        Ok(Err(e)) => Err(SpiralError::Git {
+            message: format!("Cannot run git to clone workspace template {url}: {e}"),
+        }),
+        Err(_) => Err(SpiralError::Timeout {
+            message: format!(
+                "Cloning workspace template {url} took longer than \
+                 {WORKSPACE_TEMPLATE_CLONE_TIMEOUT_SECS}s"
+            ),
+        }),
+    };
+    let _ = fs::remove_dir_all(&scratch).await;
+    files
+}
+
+/// Every regular file under `root`, skipping `.git` and symlinks
+/// Templates larger than WORKSPACE_TEMPLATE_MAX_BYTES are refused
+async fn read_dir_files(root: &Path) -> Result<TemplateFiles> {
+    let unreadable = |e: std::io::Error| SpiralError::Agent {
+        message: format!("Failed to read workspace template {root:?}: {e}"),
+    };
+    let mut files = TemplateFiles::new();
+    let mut total = 0u64;
+    let mut pending = vec![(root.to_path_buf(), String::new())];
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = fs::read_dir(&dir).await.map_err(unreadable)?;
+        while let Some(entry) = entries.next_entry().await.map_err(unreadable)? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = format!("{prefix}{name}");
+            let file_type = entry.file_type().await.map_err(unreadable)?;
+            if file_type.is_dir() && name != ".git" {
+                pending.push((entry.path(), format!("{relative}/")));
+            } else if file_type.is_file() {
+                let content = fs::read(entry.path()).await.map_err(unreadable)?;
+                total += content.len() as u64;
+                if total > WORKSPACE_TEMPLATE_MAX_BYTES {
+                    return Err(SpiralError::Validation(format!(
+                        "Workspace template {root:?} is larger than \
+                         {WORKSPACE_TEMPLATE_MAX_BYTES} bytes"
+                    )));
+                }
+                files.insert(relative, content);
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seed_builtin_template() {
+        let workspace = tempfile::tempdir().unwrap();
+        let templates = WorkspaceTemplates::builtin();
+
+        let sum = templates.seed("rust-lib", workspace.path()).await.unwrap();
+        let manifest = std::fs::read_to_string(workspace.path().join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("[package]"));
+        assert!(workspace.path().join("src/lib.rs").is_file());
+        assert_eq!(
+            sum,
+            checksum(
+                &read_source(&TemplateSource::Builtin(RUST_LIB))
+                    .await
+                    .unwrap()
+            )
+        );
+
+        let error = templates.validate("django-app").unwrap_err();
+        assert!(error.to_string().contains("rust-lib"));
+        let error = templates
+            .validate("https://github.com/example/scaffold.git")
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("CLAUDE_WORKSPACE_TEMPLATE_GIT_URLS"));
+    }
+
+    #[tokio::test]
+    async fn test_directory_template_is_checked_against_its_checksum() {
+        let template = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(template.path().join("src")).unwrap();
+        std::fs::write(template.path().join("src/main.py"), "print('hi')\n").unwrap();
+        std::fs::create_dir_all(template.path().join(".git")).unwrap();
+        std::fs::write(template.path().join(".git/HEAD"), "ref: main\n").unwrap();
+        let files = read_dir_files(template.path()).await.unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), ["src/main.py"]);
+
+        let mut templates = WorkspaceTemplates::builtin();
+        let pinned = |sha256: String| WorkspaceTemplate {
+            source: TemplateSource::Directory(template.path().to_path_buf()),
+            sha256: Some(sha256),
+        };
+        templates
+            .register("py-tool", pinned(checksum(&files)))
+            .unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        templates.seed("py-tool", workspace.path()).await.unwrap();
+        assert!(workspace.path().join("src/main.py").is_file());
+
+        // A changed template no longer matches the pinned checksum
+        std::fs::write(template.path().join("src/main.py"), "print('changed')\n").unwrap();
+        let error = templates
+            .seed("py-tool", tempfile::tempdir().unwrap().path())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SpiralError::Security(_)));
+    }
+
+    #[test]
+    fn test_registration_is_validated() {
+        let mut templates = WorkspaceTemplates::builtin();
+        let git = |url: &str| WorkspaceTemplate {
+            source: TemplateSource::Git {
+                url: url.to_string(),
+                rev: Some("v1".to_string()),
+            },
+            sha256: None,
+        };
+        assert!(templates
+            .register("team-service", git("https://example.com/team/service.git"))
+            .is_ok());
+        assert!(templates.register("local", git("file:///etc")).is_err());
+        assert!(templates
+            .register("../escape", git("https://example.com/x.git"))
+            .is_err());
+        let bad_checksum = WorkspaceTemplate {
+            sha256: Some("abc".to_string()),
+            ..git("https://example.com/x.git")
+        };
+        assert!(templates.register("short-sum", bad_checksum).is_err());
+        assert!(templates.names().any(|name| name == "team-service"));
+    }
+}
@@ -0,0 +1,324 @@
+//! 🧱 CONFIG LAYERS: Where each setting's value comes from
+//!
+//! Every setting is named by its environment variable and resolved from, highest first:
+//! 1. Command-line flags (`--set KEY=VALUE`, `--api-port`, ...)
+//! 2. Environment variables, including those loaded from .env
+//! 3. The configuration file: --config, else SPIRAL_CONFIG, else spiral.toml if present
+//! 4. The profile named by SPIRAL_PROFILE in any layer above, if any
+//! 5. Built-in defaults
+//!
+//! 🏗️ ARCHITECTURE DECISION: The file is keyed by the environment variable names, in
+//! tables that only group them
+//! Why: Config::load already reads every setting by its variable name, so one lookup
+//! serves every layer, and .env.example documents the file's keys too
+//! Alternative: Deserialize the file into Config (rejected: a second schema to keep in
+//! step with the loader, and env overrides would have to be merged field by field)
+//! Trade-off: Keys are SHOUTY_CASE rather than idiomatic TOML
+
+use super::profiles::{Profile, PROFILE_VAR};
+use crate::{Result, SpiralError};
+use std::{
+    collections::HashMap,
+    env::VarError,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+/// Read when neither --config nor SPIRAL_CONFIG names a file, if it exists
+pub const DEFAULT_CONFIG_FILE: &str = "spiral.toml";
+
+/// Environment variable naming the configuration file
+pub const CONFIG_FILE_VAR: &str = "SPIRAL_CONFIG";
+
+/// Which layer a setting's value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    CommandLine,
+    Environment,
+    File,
+    Profile,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    /// The configuration file read, if any
+    file_path: Option<PathBuf>,
+    file: HashMap<String, String>,
+    overrides: HashMap<String, String>,
+}
+
+impl ConfigLayers {
+    /// Read the configuration file: `path`, else SPIRAL_CONFIG, else spiral.toml when it
+    /// exists. A file named explicitly must exist
+    pub fn discover(path: Option<&Path>) -> Result<Self> {
+        let explicit = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_FILE_VAR).map(PathBuf::from));
+        let path = match explicit {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => PathBuf::from(DEFAULT_CONFIG_FILE),
+            None => return Ok(Self::default()),
+        };
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SpiralError::ConfigurationError(format!("Failed to read {}: {e}", path.display()))
+        })?;
+        let file = parse_file(&content)
+            .map_err(|e| SpiralError::ConfigurationError(format!("{}: {e}", path.display())))?;
+        Ok(Self {
+            file_path: Some(path),
+            file,
+            overrides: HashMap::new(),
+        })
+    }
+
+    /// Values from the command line, which win over every other layer
+    pub fn with_overrides(mut self, overrides: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.overrides.extend(overrides);
+        self
+    }
+
+    pub fn file_path(&self) -> Option<&Path> {
+        self.file_path.as_deref()
+    }
+
+    /// The value of `key` and the layer it came from; None leaves the default
+    pub fn lookup(&self, key: &str) -> Option<(String, Layer)> {
+        self.explicit(key).or_else(|| {
+            let value = self.profile()?.default_for(key)?;
+            Some((value.to_string(), Layer::Profile))
+        })
+    }
+
+    /// The profile selected by SPIRAL_PROFILE; None when unset or not a profile, which
+    /// the loader reports
+    pub fn profile(&self) -> Option<Profile> {
+        self.explicit(PROFILE_VAR)?.0.parse().ok()
+    }
+
+    fn explicit(&self, key: &str) -> Option<(String, Layer)> {
+        if let Some(value) = self.overrides.get(key) {
+            return Some((value.clone(), Layer::CommandLine));
+        }
+        if let Ok(value) = std::env::var(key) {
+            return Some((value, Layer::Environment));
+        }
+        self.file.get(key).map(|value| (value.clone(), Layer::File))
+    }
+}
+
+static LAYERS: RwLock<Option<ConfigLayers>> = RwLock::new(None);
+
+/// Resolve settings through `layers` from now on
+pub fn install(layers: ConfigLayers) {
+    *LAYERS.write().unwrap_or_else(|e| e.into_inner()) = Some(layers);
+}
+
+/// Install the discovered configuration file unless layers were installed already, as
+/// main does from its flags
+pub(crate) fn ensure_installed() -> Result<()> {
+    if LAYERS.read().unwrap_or_else(|e| e.into_inner()).is_some() {
+        return Ok(());
+    }
+    install(ConfigLayers::discover(None)?);
+    Ok(())
+}
+
+/// Read the installed configuration file again, keeping the command-line values; returns
+/// the layers it replaced so a rejected reload can restore them
+pub(crate) fn reread_file() -> Result<ConfigLayers> {
+    let previous = LAYERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default();
+    let Some(path) = previous.file_path() else {
+        return Err(SpiralError::ConfigurationError(
+            "No configuration file to reload".to_string(),
+        ));
+    };
+    let reread = ConfigLayers::discover(Some(path))?.with_overrides(previous.overrides.clone());
+    install(reread);
+    Ok(previous)
+}
+
+/// The configuration file settings are read from, if any
+pub fn installed_file() -> Option<PathBuf> {
+    LAYERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|layers| layers.file_path.clone())
+}
+
+/// A setting by its environment variable name, through the layers; answers like
+/// `std::env::var` so the loader reads every setting the same way
+pub fn var(key: &str) -> std::result::Result<String, VarError> {
+    match LAYERS.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(layers) => layers
+            .lookup(key)
+            .map(|(value, _)| value)
+            .ok_or(VarError::NotPresent),
+        None => std::env::var(key),
+    }
+}
+
+/// Settings in a configuration file, by upper-cased key; tables only group keys, and
+/// lists become the comma-separated values the loader expects
+fn parse_file(content: &str) -> std::result::Result<HashMap<String, String>, String> {
+    let table: toml::Table = content
+        .parse()
+        .map_err(|e: toml::de::Error| e.to_string())?;
+    let mut settings = HashMap::new();
+    flatten(&table, &mut settings)?;
+    Ok(settings)
+}
+
+fn flatten(
+    table: &toml::Table,
+    settings: &mut HashMap<String, String>,
+) -> std::result::Result<(), String> {
+    for (key, value) in table {
+        let value = match value {
+            toml::Value::Table(group) => {
+                flatten(group, settings)?;
+                continue;
+            }
+            toml::Value::Array(items) => items
+                .iter()
+                .map(plain_value)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("{key}: lists may only hold plain values"))?
+                .join(","),
+            value => plain_value(value).unwrap_or_default(),
+        };
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "'{key}' is not a setting name; use the environment variable name"
+            ));
+        }
+        let name = key.to_ascii_uppercase();
+        if settings.insert(name.clone(), value).is_some() {
+            return Err(format!("{name} is set more than once"));
+        }
+    }
+    Ok(())
+}
+
+fn plain_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(n) => Some(n.to_string()),
+        toml::Value::Float(n) => Some(n.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Datetime(d) => Some(d.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+/// Command-line flags that set configuration, above the environment and the file
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ConfigArgs {
+    /// Configuration file [default: $SPIRAL_CONFIG, else spiral.toml if present]
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Profile of defaults: dev, staging or prod (SPIRAL_PROFILE)
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Address the API listens on (API_HOST)
+    #[arg(long, value_name = "HOST")]
+    pub api_host: Option<String>,
+    /// Port the API listens on (API_PORT)
+    #[arg(long, value_name = "PORT")]
+    pub api_port: Option<u16>,
+    /// Log level or filter, e.g. debug or spiral_core=trace (LOG_LEVEL)
+    #[arg(long, value_name = "FILTER")]
+    pub log_level: Option<String>,
+    /// Any setting by its environment variable name; repeatable, applied after the
+    /// flags above
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+    pub settings: Vec<(String, String)>,
+}
+
+impl ConfigArgs {
+    /// The layers these flags describe, reading the configuration file they select
+    pub fn layers(&self) -> Result<ConfigLayers> {
+        let named = [
+            ("API_HOST", self.api_host.clone()),
+            ("API_PORT", self.api_port.map(|port| port.to_string())),
+            ("LOG_LEVEL", self.log_level.clone()),
+            (PROFILE_VAR, self.profile.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value?)));
+        Ok(ConfigLayers::discover(self.config.as_deref())?
+            .with_overrides(named.chain(self.settings.iter().cloned())))
+    }
+}
+
+fn parse_assignment(raw: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{raw}'"))?;
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("'{key}' is not a setting name"));
+    }
+    Ok((key.to_ascii_uppercase(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_settings_rank_below_env_and_command_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spiral.toml");
+        std::fs::write(
+            &path,
+            r#"
+                [api]
+                API_PORT = 3001
+                allowed_origins = ["http://a", "http://b"]
+                SPIRAL_LAYERS_TEST_ENV = "file"
+
+                [orchestrator]
+                TASK_MAX_RETRIES = 4
+            "#,
+        )
+        .unwrap();
+        std::env::set_var("SPIRAL_LAYERS_TEST_ENV", "env");
+
+        let layers = ConfigArgs {
+            config: Some(path.clone()),
+            api_port: Some(3002),
+            settings: vec![parse_assignment("task_max_retries=1").unwrap()],
+            ..Default::default()
+        }
+        .layers()
+        .unwrap();
+        assert_eq!(layers.file_path(), Some(path.as_path()));
+        assert_eq!(
+            layers.lookup("ALLOWED_ORIGINS"),
+            Some(("http://a,http://b".to_string(), Layer::File))
+        );
+        assert_eq!(
+            layers.lookup("SPIRAL_LAYERS_TEST_ENV"),
+            Some(("env".to_string(), Layer::Environment))
+        );
+        assert_eq!(
+            layers.lookup("API_PORT"),
+            Some(("3002".to_string(), Layer::CommandLine))
+        );
+        assert_eq!(
+            layers.lookup("TASK_MAX_RETRIES"),
+            Some(("1".to_string(), Layer::CommandLine))
+        );
+        assert_eq!(layers.lookup("SPIRAL_LAYERS_TEST_UNSET"), None);
+        std::env::remove_var("SPIRAL_LAYERS_TEST_ENV");
+
+        assert!(parse_file("[api]\nAPI_PORT = 1\n[other]\napi_port = 2").is_err());
+        assert!(parse_assignment("API_PORT").is_err());
+        assert!(ConfigLayers::discover(Some(&dir.path().join("missing.toml"))).is_err());
+    }
+}
@@ -1,13 +1,48 @@
-use crate::{Result, SpiralError};
+use crate::{models::AgentType, Result, SpiralError};
 use dotenvy::dotenv;
 use serde::{Deserialize, Serialize};
-use std::env;
+use std::collections::{BTreeMap, HashMap};
+
+pub mod layers;
+pub mod profiles;
+pub mod reload;
+pub mod secrets;
+
+// Every setting is read through the layers, so spiral.toml and command-line flags reach
+// all of them the same way environment variables do
+use layers as env;
+
+pub use layers::{ConfigArgs, ConfigLayers};
+pub use profiles::Profile;
+pub use reload::{ConfigHandle, Reloadable};
+
+pub use secrets::{EnvSecretProvider, FileSecretProvider, SecretProvider, Secrets};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Profile whose defaults were applied, from SPIRAL_PROFILE
+    #[serde(default)]
+    pub profile: Option<Profile>,
     pub claude_code: ClaudeCodeConfig,
     pub discord: DiscordConfig,
     pub api: ApiConfig,
+    pub orchestrator: OrchestratorConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub prompts: PromptConfig,
+    #[serde(default)]
+    pub alerting: AlertingConfig,
+    #[serde(default)]
+    pub monitoring: MonitoringSettings,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub crash: CrashConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,7 +53,160 @@ pub struct ClaudeCodeConfig {
     pub permission_mode: String,
     pub allowed_tools: Vec<String>,
     pub workspace_cleanup_after_hours: u64,
+    /// Size at which a running task is stopped, keeping its partial results; 0 for no limit
     pub max_workspace_size_mb: u64,
+    /// Claude Code processes allowed to run at once, across all agents
+    #[serde(default = "default_max_concurrent_invocations")]
+    pub max_concurrent_invocations: usize,
+    /// MCP servers by name, offered to the agents each one lists
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, McpServerConfig>,
+    /// Check `claude --version` at startup and gate features on it
+    #[serde(default = "default_verify_cli_version")]
+    pub verify_cli_version: bool,
+    /// Scaffolds a task's workspace can start from, next to the built-in ones
+    #[serde(default)]
+    pub workspace_templates: WorkspaceTemplateConfig,
+    #[serde(default)]
+    pub transcripts: TranscriptConfig,
+    /// Daily Claude Code spend in USD after which new tasks are refused; None for no limit
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerPolicy,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+}
+
+/// Container that Claude Code runs in, confining its Bash tool to the task workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Container runtime; None runs Claude Code directly on the host
+    pub runtime: Option<crate::claude_code::sandbox::SandboxRuntime>,
+    /// Image with the Claude Code CLI installed
+    pub image: String,
+    /// Claude Code binary inside the image
+    pub binary: String,
+    pub cpus: f64,
+    pub memory_mb: u64,
+    /// Most processes the container may run at once
+    pub pids_limit: u32,
+    /// Container network; "none" cuts it off entirely
+    pub network: String,
+    /// Host environment variables passed into the container, e.g. API credentials
+    pub pass_env: Vec<String>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        use crate::constants::{
+            CLAUDE_SANDBOX_DEFAULT_CPUS, CLAUDE_SANDBOX_DEFAULT_IMAGE,
+            CLAUDE_SANDBOX_DEFAULT_MEMORY_MB, CLAUDE_SANDBOX_DEFAULT_PIDS_LIMIT,
+        };
+        Self {
+            runtime: None,
+            image: CLAUDE_SANDBOX_DEFAULT_IMAGE.to_string(),
+            binary: "claude".to_string(),
+            cpus: CLAUDE_SANDBOX_DEFAULT_CPUS,
+            memory_mb: CLAUDE_SANDBOX_DEFAULT_MEMORY_MB,
+            pids_limit: CLAUDE_SANDBOX_DEFAULT_PIDS_LIMIT,
+            network: "none".to_string(),
+            pass_env: vec!["ANTHROPIC_API_KEY".to_string()],
+        }
+    }
+}
+
+/// An MCP server Claude Code may start for extra tools, e.g. a database or browser
+/// Read from the same `mcpServers` format Claude Code uses, plus an optional agent list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Agent types offered the server; empty offers it to every agent
+    #[serde(default)]
+    pub agents: Vec<AgentType>,
+}
+
+/// Scaffolds tasks can seed their workspace with through the `workspace_template` context key
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceTemplateConfig {
+    /// Registered templates by name
+    pub templates: BTreeMap<String, WorkspaceTemplateSource>,
+    /// Let tasks name any https git URL instead of a registered template
+    pub allow_git_urls: bool,
+}
+
+/// A registered workspace template: a local directory or a git repository
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceTemplateSource {
+    pub path: Option<String>,
+    pub git: Option<String>,
+    /// Branch or tag to clone; the default branch when unset
+    pub rev: Option<String>,
+    /// SHA-256 of the template's files; seeding fails once they no longer match
+    pub sha256: Option<String>,
+}
+
+/// When a backend's circuit breaker stops sending it requests, and how it recovers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerPolicy {
+    /// Failures within the window that open the circuit
+    pub failure_threshold: u32,
+    /// Seconds the circuit stays open before probing the backend again
+    pub open_secs: u64,
+    /// Probe requests let through while half-open; all must succeed to close it
+    pub half_open_probes: u32,
+    /// Seconds within which failures count towards the threshold
+    pub failure_window_secs: u64,
+    /// Reaction to timeouts
+    pub on_timeout: crate::claude_code::circuit_breaker::FailureAction,
+    /// Reaction to rejected or missing credentials
+    pub on_auth_error: crate::claude_code::circuit_breaker::FailureAction,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        let defaults = crate::claude_code::circuit_breaker::CircuitBreakerConfig::default();
+        Self {
+            failure_threshold: defaults.failure_threshold,
+            open_secs: defaults.timeout_duration.as_secs(),
+            half_open_probes: defaults.success_threshold,
+            failure_window_secs: defaults.failure_window.as_secs(),
+            on_timeout: defaults.on_timeout,
+            on_auth_error: defaults.on_auth_error,
+        }
+    }
+}
+
+/// Reuse of Claude Code answers to identical analysis and small generation requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseCacheConfig {
+    /// Responses kept; 0 disables the cache
+    pub capacity: usize,
+    /// Seconds a response is reused before Claude is asked again
+    pub ttl_secs: u64,
+    /// Directory mirroring the cache so it survives restarts; None keeps it in memory
+    pub dir: Option<String>,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: crate::constants::CLAUDE_CACHE_DEFAULT_CAPACITY,
+            ttl_secs: crate::constants::CLAUDE_CACHE_DEFAULT_TTL_SECS,
+            dir: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,7 +214,65 @@ pub struct DiscordConfig {
     pub token: String,
     pub command_prefix: String,
     pub agent_mention_pattern: String,
+    /// Users with full (admin) access, kept for configs that predate roles
     pub authorized_users: Vec<u64>,
+    /// Per-user roles; an entry here overrides membership of authorized_users
+    #[serde(default)]
+    pub user_roles: HashMap<u64, crate::auth::Role>,
+    /// Open a thread for each task started from a channel message and answer there
+    #[serde(default = "default_task_threads")]
+    pub task_threads: bool,
+    /// File where per-guild settings changed with `!spiral config` are kept; None keeps
+    /// them in memory only
+    #[serde(default)]
+    pub guild_settings_path: Option<String>,
+    /// File where each user's `!spiral prefs` are kept; None keeps them in memory only
+    #[serde(default)]
+    pub user_prefs_path: Option<String>,
+    /// Directory of `<locale>.json` files overriding the built-in message catalogs
+    #[serde(default)]
+    pub locale_dir: Option<String>,
+    /// Where private DM sessions are kept; memory loses them on restart
+    #[serde(default)]
+    pub session_store: crate::session::SessionStoreBackend,
+    /// Budget each user session starts with
+    #[serde(default)]
+    pub session_budget: crate::session::SessionBudget,
+    /// Idle timeouts and lifetimes of user sessions
+    #[serde(default)]
+    pub session_policies: crate::session::SessionPolicies,
+    /// Show the running task and queue length as the bot's status instead of a help hint
+    #[serde(default)]
+    pub presence_status: bool,
+    /// Seconds between status updates while presence_status is on
+    #[serde(default = "default_presence_interval_secs")]
+    pub presence_interval_secs: u64,
+    /// Messages each user may send the bot; a guild's `!spiral config` limit replaces it
+    #[serde(default = "default_user_rate_limit")]
+    pub user_rate_limit: crate::rate_limit::TokenBucket,
+    /// Messages to the bot each channel takes, across its users; None leaves channels
+    /// unlimited
+    #[serde(default = "default_channel_rate_limit")]
+    pub channel_rate_limit: Option<crate::rate_limit::TokenBucket>,
+}
+
+fn default_task_threads() -> bool {
+    true
+}
+
+fn default_presence_interval_secs() -> u64 {
+    crate::constants::DISCORD_PRESENCE_INTERVAL_SECS
+}
+
+fn default_user_rate_limit() -> crate::rate_limit::TokenBucket {
+    crate::rate_limit::TokenBucket::per_minute(crate::constants::DISCORD_USER_MESSAGES_PER_MINUTE)
+}
+
+fn default_channel_rate_limit() -> Option<crate::rate_limit::TokenBucket> {
+    Some(crate::rate_limit::TokenBucket {
+        burst: crate::constants::DISCORD_CHANNEL_MESSAGE_BURST,
+        refill_per_minute: crate::constants::DISCORD_CHANNEL_MESSAGES_PER_MINUTE,
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +281,1206 @@ pub struct ApiConfig {
     pub port: u16,
     pub api_key: Option<String>,
     pub allowed_origins: Vec<String>,
+    /// Serve the interactive API documentation page at /docs
+    #[serde(default)]
+    pub swagger_ui_enabled: bool,
+    /// How long POST /tasks replays a request with the same Idempotency-Key; 0 disables it
+    #[serde(default = "default_idempotency_window_secs")]
+    pub idempotency_window_secs: u64,
+    /// Largest request body accepted, in bytes; larger requests get 413
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Largest POST /tasks/batch body accepted, in bytes
+    #[serde(default = "default_max_batch_body_bytes")]
+    pub max_batch_body_bytes: usize,
+    /// Where scoped API keys created via /admin/keys are kept; None keeps them in memory
+    #[serde(default)]
+    pub keys_file: Option<String>,
+    /// Encrypts the generated .spiral-api-key file when set; never serialized
+    #[serde(default, skip_serializing)]
+    pub key_file_passphrase: Option<String>,
+    /// Networks allowed to reach the API; empty allows every address
+    #[serde(default)]
+    pub ip_allowlist: Vec<ipnet::IpNet>,
+    /// Networks refused even when they are in the allowlist
+    #[serde(default)]
+    pub ip_denylist: Vec<ipnet::IpNet>,
+    /// Shared secret for HMAC request signatures; never serialized
+    #[serde(default, skip_serializing)]
+    pub signing_secret: Option<String>,
+    /// Route groups whose requests must carry a valid signature
+    #[serde(default)]
+    pub signed_route_groups: Vec<crate::auth::signing::SignedRouteGroup>,
+    /// Largest accepted difference between a signature's timestamp and server time
+    #[serde(default = "default_signature_max_age_secs")]
+    pub signature_max_age_secs: u64,
+    /// Where sessions opened with POST /sessions are kept
+    #[serde(default)]
+    pub session_store: crate::session::SessionStoreBackend,
+    /// Budget each session opened with POST /sessions starts with
+    #[serde(default)]
+    pub session_budget: crate::session::SessionBudget,
+    /// Idle timeouts and lifetimes of sessions opened with POST /sessions
+    #[serde(default)]
+    pub session_policies: crate::session::SessionPolicies,
+    /// Requests per minute each client may make, by route group and API key or network
+    #[serde(default = "crate::rate_limit::policy::default_policies")]
+    pub rate_limits: Vec<crate::rate_limit::policy::RateLimitPolicy>,
+}
+
+/// Comma-separated CIDR networks from an env var; a bare address means just that host
+fn parse_ip_networks(var: &str) -> Result<Vec<ipnet::IpNet>> {
+    env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .parse::<ipnet::IpNet>()
+                .or_else(|_| entry.parse::<std::net::IpAddr>().map(ipnet::IpNet::from))
+                .map_err(|_| {
+                    SpiralError::ConfigurationError(format!(
+                        "{var}: '{entry}' is not an IP address or CIDR network"
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn default_idempotency_window_secs() -> u64 {
+    crate::constants::IDEMPOTENCY_DEFAULT_WINDOW_SECS
+}
+
+fn default_max_body_bytes() -> usize {
+    crate::constants::API_DEFAULT_MAX_BODY_BYTES
+}
+
+fn default_signature_max_age_secs() -> u64 {
+    crate::constants::SIGNATURE_DEFAULT_MAX_AGE_SECS
+}
+
+fn default_max_batch_body_bytes() -> usize {
+    crate::constants::API_DEFAULT_MAX_BATCH_BODY_BYTES
+}
+
+fn default_verify_cli_version() -> bool {
+    true
+}
+
+fn default_max_concurrent_invocations() -> usize {
+    crate::constants::CLAUDE_DEFAULT_MAX_CONCURRENT_INVOCATIONS
+}
+
+/// Where each task's redacted Claude Code transcript is kept
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptConfig {
+    /// Directory for the per-task transcript files; None keeps no transcripts
+    pub dir: Option<String>,
+    /// Days a transcript is kept after its last write; 0 keeps them forever
+    pub retention_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Directory for the daily audit files; None only traces audit events
+    pub log_dir: Option<String>,
+    /// Days of audit files kept; 0 keeps them forever
+    pub retention_days: u32,
+}
+
+impl LlmConfig {
+    /// Every backend named for an agent or as a fallback must be configured
+    pub fn validate(&self) -> Result<()> {
+        let referenced = self.agent_backends.values().chain(&self.fallback);
+        for backend in referenced {
+            let configured = match backend {
+                crate::llm::BackendKind::Claude => true,
+                crate::llm::BackendKind::OpenAi => self.openai.is_some(),
+                crate::llm::BackendKind::Ollama => self.ollama.is_some(),
+            };
+            if !configured {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "The {backend} backend is selected but not configured (set {})",
+                    match backend {
+                        crate::llm::BackendKind::OpenAi => "OPENAI_API_KEY",
+                        _ => "OLLAMA_URL",
+                    }
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptConfig {
+    /// Directory of templates overriding the built-in prompts; None uses the built-in ones
+    pub templates_dir: Option<String>,
+    /// Seconds between checks of the directory for edits; 0 loads it once
+    pub reload_interval_secs: u64,
+}
+
+impl Default for PromptConfig {
+    fn default() -> Self {
+        Self {
+            templates_dir: None,
+            reload_interval_secs: crate::constants::PROMPT_TEMPLATE_RELOAD_SECS,
+        }
+    }
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable lines, for terminals
+    #[default]
+    Text,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+/// When a log file is closed and a new one started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRotation {
+    /// A file per day
+    #[default]
+    Daily,
+    /// A file per day, and another whenever the current one reaches max_file_mb
+    Size,
+}
+
+/// Log files next to stdout, rotated and removed after their retention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFileConfig {
+    pub dir: String,
+    pub rotation: LogRotation,
+    /// Size a file may reach with size rotation
+    pub max_file_mb: u64,
+    /// Days files are kept; 0 keeps them forever
+    pub retention_days: u32,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            dir: "logs".to_string(),
+            rotation: LogRotation::default(),
+            max_file_mb: crate::constants::LOG_DEFAULT_MAX_FILE_MB,
+            retention_days: crate::constants::LOG_DEFAULT_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Log format, levels and files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    /// EnvFilter directives: a default level and per-module levels, changeable at runtime
+    pub level: String,
+    /// Also write to files; None logs to stdout only
+    pub file: Option<LogFileConfig>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            level: crate::constants::DEFAULT_LOG_FILTER.to_string(),
+            file: None,
+        }
+    }
+}
+
+/// Where panics are reported and whether crashed subsystems are restarted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashConfig {
+    /// Directory crash reports are written to; None only logs them
+    pub report_dir: Option<String>,
+    /// Restart a supervised subsystem, e.g. the Discord handler, after it panics
+    pub restart: bool,
+    /// Restarts allowed per subsystem within an hour before it is left stopped
+    pub max_restarts: u32,
+}
+
+impl Default for CrashConfig {
+    fn default() -> Self {
+        Self {
+            report_dir: Some("logs/crashes".to_string()),
+            restart: true,
+            max_restarts: crate::constants::CRASH_MAX_RESTARTS,
+        }
+    }
+}
+
+/// Where the API and Discord rate limits keep their counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitSettings {
+    /// Memory counts per instance; Redis shares the counts, so several instances grant
+    /// one quota between them
+    pub store: crate::rate_limit::RateLimiterBackend,
+    /// File the tiers granted to users and API keys are kept in; None keeps them in memory
+    pub tiers_path: Option<String>,
+}
+
+/// How the system monitor keeps what it collects and what it holds tasks to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitoringSettings {
+    /// Seconds between metrics collections
+    pub interval_secs: u64,
+    /// Collections kept in memory for the dashboard
+    pub retention_count: usize,
+    pub cpu: ResourceThresholds,
+    pub memory: ResourceThresholds,
+    pub disk: ResourceThresholds,
+    /// SQLite database for metrics history; None keeps only recent samples in memory
+    pub history_path: Option<String>,
+    /// Seconds of finished tasks the latency percentiles and success rates cover
+    pub slo_window_secs: u64,
+    /// Objectives whose misses degrade health
+    pub slo_targets: Vec<crate::monitoring::SloTarget>,
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> Self {
+        let monitor = crate::monitoring::MonitoringConfig::default();
+        Self {
+            interval_secs: monitor.collection_interval.as_secs(),
+            retention_count: monitor.metrics_retention_count,
+            cpu: ResourceThresholds {
+                warning_percent: monitor.cpu_warning_threshold,
+                critical_percent: monitor.cpu_critical_threshold,
+            },
+            memory: ResourceThresholds {
+                warning_percent: monitor.memory_warning_threshold,
+                critical_percent: monitor.memory_critical_threshold,
+            },
+            disk: ResourceThresholds {
+                warning_percent: monitor.disk_warning_threshold,
+                critical_percent: monitor.disk_critical_threshold,
+            },
+            history_path: None,
+            slo_window_secs: crate::constants::SLO_WINDOW_SECS,
+            slo_targets: crate::monitoring::slo::default_targets(),
+        }
+    }
+}
+
+impl MonitoringSettings {
+    /// Reject settings the monitor can't run with: a zero interval or retention, or
+    /// thresholds outside 0-100 or with warning not below critical
+    pub fn validate(&self) -> Result<()> {
+        if self.interval_secs == 0 {
+            return Err(SpiralError::ConfigurationError(
+                "monitoring interval must be at least 1 second".to_string(),
+            ));
+        }
+        if self.retention_count == 0 {
+            return Err(SpiralError::ConfigurationError(
+                "monitoring retention must keep at least 1 collection".to_string(),
+            ));
+        }
+        for (resource, thresholds) in [
+            ("cpu", &self.cpu),
+            ("memory", &self.memory),
+            ("disk", &self.disk),
+        ] {
+            let ResourceThresholds {
+                warning_percent: warning,
+                critical_percent: critical,
+            } = *thresholds;
+            if !(warning > 0.0 && critical <= 100.0) {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "{resource} thresholds must be between 0 and 100 percent, got warning {warning} and critical {critical}"
+                )));
+            }
+            if warning >= critical {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "{resource} warning threshold ({warning}%) must be below its critical threshold ({critical}%)"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Usage at which a resource degrades health, and at which it is critical
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceThresholds {
+    pub warning_percent: f64,
+    pub critical_percent: f64,
+}
+
+/// Alert rules and where their notifications go
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertingConfig {
+    pub rules: Vec<crate::monitoring::AlertRule>,
+    /// Discord channel alerts are posted to, using the bot token; None posts none
+    pub discord_channel_id: Option<u64>,
+    /// URL each alert is POSTed to as JSON; None sends none
+    pub webhook_url: Option<String>,
+    /// Mail server and recipients of alert emails; None sends none
+    pub smtp: Option<SmtpConfig>,
+}
+
+impl Default for AlertingConfig {
+    fn default() -> Self {
+        Self {
+            rules: crate::monitoring::alerts::default_rules(),
+            discord_channel_id: None,
+            webhook_url: None,
+            smtp: None,
+        }
+    }
+}
+
+/// How the connection to the mail server is secured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS, usually port 587
+    #[default]
+    StartTls,
+    /// TLS from the start, usually port 465
+    Tls,
+    /// No encryption, for a relay on the same host or network
+    None,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub tls: SmtpTls,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Never serialized
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            log_dir: None,
+            retention_days: crate::constants::AUDIT_DEFAULT_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Code generation backends besides Claude Code
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmConfig {
+    /// OpenAI-compatible chat completions API; None leaves it unconfigured
+    #[serde(default)]
+    pub openai: Option<OpenAiConfig>,
+    /// Local Ollama server; None leaves it unconfigured
+    #[serde(default)]
+    pub ollama: Option<OllamaConfig>,
+    /// Agent types that generate with another backend instead of Claude Code
+    #[serde(default)]
+    pub agent_backends: HashMap<AgentType, crate::llm::BackendKind>,
+    /// Backends tried in order while an agent's own backend is unavailable
+    #[serde(default)]
+    pub fallback: Vec<crate::llm::BackendKind>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiConfig {
+    /// Never serialized
+    #[serde(default, skip_serializing)]
+    pub api_key: String,
+    pub base_url: String,
+    pub model: String,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    pub base_url: String,
+    pub model: String,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestratorConfig {
+    pub max_task_retries: u32,
+    pub retry_initial_backoff_ms: u64,
+    pub retry_max_backoff_ms: u64,
+    /// Concurrent tasks per agent type when no explicit limit is configured
+    pub default_agent_concurrency: usize,
+    /// Explicit per-agent-type concurrency limits
+    pub agent_concurrency: HashMap<AgentType, usize>,
+    /// Where scheduled tasks are persisted; None keeps schedules in memory only
+    pub schedule_store_path: Option<String>,
+    /// Most delayed and cron schedules kept at once
+    pub max_schedules: usize,
+    /// SQLite database for agent memory; None keeps memories in memory only
+    pub memory_store_path: Option<String>,
+    /// Where unfinished tasks are saved on shutdown; None drops them
+    pub checkpoint_store_path: Option<String>,
+    /// Named multi-agent workflows, each an ordered list of agent steps
+    pub workflows: HashMap<String, Vec<AgentType>>,
+    /// Hosts task callback_url may point at; empty disables webhook callbacks
+    pub webhook_allowed_hosts: Vec<String>,
+    /// Delivery attempts per task callback, including the first
+    pub webhook_max_attempts: u32,
+    /// Percent of the queue only High and Critical priority or admin tasks may fill
+    pub queue_reserved_percent: u32,
+    /// Whether such a task finding the queue full evicts the oldest Low priority task
+    pub queue_evict_low_priority: bool,
+}
+
+impl Default for OrchestratorConfig {
+    fn default() -> Self {
+        Self {
+            max_task_retries: 2,
+            retry_initial_backoff_ms: 5_000,
+            retry_max_backoff_ms: 300_000,
+            default_agent_concurrency: 1,
+            agent_concurrency: HashMap::new(),
+            schedule_store_path: None,
+            max_schedules: crate::constants::DEFAULT_MAX_SCHEDULES,
+            memory_store_path: None,
+            checkpoint_store_path: None,
+            workflows: HashMap::new(),
+            webhook_allowed_hosts: Vec::new(),
+            webhook_max_attempts: crate::constants::WEBHOOK_DEFAULT_MAX_ATTEMPTS,
+            queue_reserved_percent: crate::constants::QUEUE_RESERVED_PERCENT,
+            queue_evict_low_priority: false,
+        }
+    }
+}
+
+/// Parse `AgentType=limit` pairs such as `SoftwareDeveloper=2,ProjectManager=1`
+/// Malformed entries are skipped with a warning rather than failing startup
+fn parse_agent_concurrency(raw: &str) -> HashMap<AgentType, usize> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry.split_once('=').and_then(|(agent, limit)| {
+                let agent_type = agent.trim().parse::<AgentType>().ok()?;
+                let limit = limit.trim().parse::<usize>().ok()?;
+                Some((agent_type, limit))
+            });
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid AGENT_CONCURRENCY entry: {}", entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// Parse `AgentType=backend` pairs such as `ProjectManager=ollama`
+/// Unlike concurrency limits, a bad entry fails startup: silently running an agent on
+/// the wrong model is worse than not starting
+fn parse_agent_backends(raw: &str) -> Result<HashMap<AgentType, crate::llm::BackendKind>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (agent, backend) = entry.split_once('=').ok_or_else(|| {
+                SpiralError::ConfigurationError(format!(
+                    "LLM_AGENT_BACKENDS: expected 'AgentType=backend', got '{entry}'"
+                ))
+            })?;
+            let agent_type = agent.trim().parse::<AgentType>().map_err(|_| {
+                SpiralError::ConfigurationError(format!(
+                    "LLM_AGENT_BACKENDS: unknown agent type '{}'",
+                    agent.trim()
+                ))
+            })?;
+            let backend = backend
+                .parse()
+                .map_err(|e| SpiralError::ConfigurationError(format!("LLM_AGENT_BACKENDS: {e}")))?;
+            Ok((agent_type, backend))
+        })
+        .collect()
+}
+
+/// Parse CLAUDE_DAILY_BUDGET_USD; unset or empty means no budget
+/// A malformed budget fails startup rather than quietly leaving spend unlimited
+fn parse_daily_budget(raw: Option<String>) -> Result<Option<f64>> {
+    let Some(raw) = raw.filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+    match raw.trim().parse::<f64>() {
+        Ok(budget) if budget.is_finite() && budget > 0.0 => Ok(Some(budget)),
+        _ => Err(SpiralError::ConfigurationError(format!(
+            "CLAUDE_DAILY_BUDGET_USD must be a positive amount in USD, got '{raw}'"
+        ))),
+    }
+}
+
+/// 💸 SESSION BUDGET: SESSION_MAX_TASKS, SESSION_MAX_COST_USD and SESSION_MAX_WORKSPACE_MB;
+/// unset or empty leaves that limit off, and a malformed one fails startup like the daily
+/// budget does
+fn parse_session_budget() -> Result<crate::session::SessionBudget> {
+    fn limit<T: std::str::FromStr + PartialOrd + Default>(name: &str) -> Result<Option<T>> {
+        let Some(raw) = env::var(name).ok().filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        match raw.trim().parse::<T>() {
+            Ok(limit) if limit > T::default() => Ok(Some(limit)),
+            _ => Err(SpiralError::ConfigurationError(format!(
+                "{name} must be a positive number, got '{raw}'"
+            ))),
+        }
+    }
+    Ok(crate::session::SessionBudget {
+        max_tasks: limit("SESSION_MAX_TASKS")?,
+        max_cost_usd: limit::<f64>("SESSION_MAX_COST_USD")?.filter(|max| max.is_finite()),
+        max_workspace_mb: limit("SESSION_MAX_WORKSPACE_MB")?,
+    })
+}
+
+/// ⏳ SESSION POLICIES: SESSION_IDLE_TIMEOUT_MINUTES and SESSION_MAX_LIFETIME_HOURS for
+/// every session, SESSION_REMEMBER_ME_* for remember-me sessions, and SESSION_USER_POLICIES
+/// (`user=IDLE_MINUTES/LIFETIME_HOURS,...`, either side may be empty) for particular users
+/// A malformed value fails startup, like the session budget
+fn parse_session_policies() -> Result<crate::session::SessionPolicies> {
+    use crate::session::{SessionPolicies, SessionPolicy};
+    use chrono::Duration;
+
+    fn positive(name: &str, raw: &str) -> Result<Option<i64>> {
+        if raw.trim().is_empty() {
+            return Ok(None);
+        }
+        match raw.trim().parse::<i64>() {
+            Ok(value) if value > 0 => Ok(Some(value)),
+            _ => Err(SpiralError::ConfigurationError(format!(
+                "{name} must be a positive whole number, got '{raw}'"
+            ))),
+        }
+    }
+    let var = |name: &str| positive(name, &env::var(name).unwrap_or_default());
+
+    let defaults = SessionPolicies::default();
+    let mut policies = SessionPolicies {
+        default: SessionPolicy {
+            idle_timeout: var("SESSION_IDLE_TIMEOUT_MINUTES")?.map(Duration::minutes),
+            max_lifetime: var("SESSION_MAX_LIFETIME_HOURS")?.map(Duration::hours),
+        },
+        remember_me: SessionPolicy {
+            idle_timeout: var("SESSION_REMEMBER_ME_IDLE_DAYS")?
+                .map(Duration::days)
+                .or(defaults.remember_me.idle_timeout),
+            max_lifetime: var("SESSION_REMEMBER_ME_LIFETIME_DAYS")?
+                .map(Duration::days)
+                .or(defaults.remember_me.max_lifetime),
+        },
+        users: Default::default(),
+    };
+
+    let raw = env::var("SESSION_USER_POLICIES").unwrap_or_default();
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let parsed = entry.rsplit_once('=').and_then(|(user, limits)| {
+            let (idle, lifetime) = limits.split_once('/')?;
+            Some((user.trim(), idle, lifetime))
+        });
+        let Some((user, idle, lifetime)) = parsed.filter(|(user, ..)| !user.is_empty()) else {
+            return Err(SpiralError::ConfigurationError(format!(
+                "SESSION_USER_POLICIES entries look like user=IDLE_MINUTES/LIFETIME_HOURS, got '{entry}'"
+            )));
+        };
+        let policy = SessionPolicy {
+            idle_timeout: positive("SESSION_USER_POLICIES idle minutes", idle)?
+                .map(Duration::minutes),
+            max_lifetime: positive("SESSION_USER_POLICIES lifetime hours", lifetime)?
+                .map(Duration::hours),
+        };
+        policies.users.insert(user.to_string(), policy);
+    }
+    Ok(policies)
+}
+
+/// 🔌 CIRCUIT BREAKER POLICY: `{prefix}_CIRCUIT_*` variables over the defaults
+/// A malformed value fails startup, as a silently ignored policy would only show up
+/// during an outage
+fn circuit_breaker_policy(prefix: &str) -> Result<CircuitBreakerPolicy> {
+    fn read<T: std::str::FromStr>(name: String, default: T) -> Result<T> {
+        match env::var(&name) {
+            Ok(raw) if !raw.trim().is_empty() => raw.trim().parse().map_err(|_| {
+                SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+            }),
+            _ => Ok(default),
+        }
+    }
+    let defaults = CircuitBreakerPolicy::default();
+    Ok(CircuitBreakerPolicy {
+        failure_threshold: read(
+            format!("{prefix}_CIRCUIT_FAILURE_THRESHOLD"),
+            defaults.failure_threshold,
+        )?,
+        open_secs: read(format!("{prefix}_CIRCUIT_OPEN_SECS"), defaults.open_secs)?,
+        half_open_probes: read(
+            format!("{prefix}_CIRCUIT_HALF_OPEN_PROBES"),
+            defaults.half_open_probes,
+        )?,
+        failure_window_secs: read(
+            format!("{prefix}_CIRCUIT_FAILURE_WINDOW_SECS"),
+            defaults.failure_window_secs,
+        )?,
+        on_timeout: read(format!("{prefix}_CIRCUIT_ON_TIMEOUT"), defaults.on_timeout)?,
+        on_auth_error: read(
+            format!("{prefix}_CIRCUIT_ON_AUTH_ERROR"),
+            defaults.on_auth_error,
+        )?,
+    })
+}
+
+/// 🛡️ SANDBOX: CLAUDE_SANDBOX names the runtime; the other CLAUDE_SANDBOX_* variables
+/// override the limits
+fn sandbox_config() -> Result<SandboxConfig> {
+    let defaults = SandboxConfig::default();
+    let invalid = |name: &str, raw: &str| {
+        SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+    };
+    let runtime = match env::var("CLAUDE_SANDBOX") {
+        Ok(raw) if !raw.trim().is_empty() && raw.trim() != "none" => {
+            Some(raw.parse().map_err(|e: String| {
+                SpiralError::ConfigurationError(format!("CLAUDE_SANDBOX: {e}"))
+            })?)
+        }
+        _ => None,
+    };
+    let number = |name: &str, default: f64| -> Result<f64> {
+        match env::var(name) {
+            Ok(raw) if !raw.trim().is_empty() => raw
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite() && *value > 0.0)
+                .ok_or_else(|| invalid(name, &raw)),
+            _ => Ok(default),
+        }
+    };
+    Ok(SandboxConfig {
+        runtime,
+        image: env::var("CLAUDE_SANDBOX_IMAGE").unwrap_or(defaults.image),
+        binary: env::var("CLAUDE_SANDBOX_BINARY").unwrap_or(defaults.binary),
+        cpus: number("CLAUDE_SANDBOX_CPUS", defaults.cpus)?,
+        memory_mb: number("CLAUDE_SANDBOX_MEMORY_MB", defaults.memory_mb as f64)? as u64,
+        pids_limit: number("CLAUDE_SANDBOX_PIDS_LIMIT", defaults.pids_limit as f64)? as u32,
+        network: env::var("CLAUDE_SANDBOX_NETWORK")
+            .ok()
+            .filter(|network| !network.trim().is_empty())
+            .unwrap_or(defaults.network),
+        pass_env: match env::var("CLAUDE_SANDBOX_ENV") {
+            Ok(raw) => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Err(_) => defaults.pass_env,
+        },
+    })
+}
+
+/// MCP servers from the file named by CLAUDE_MCP_CONFIG, if any
+/// The file is a Claude Code `.mcp.json`: `{"mcpServers": {"name": {"command": ...}}}`
+fn mcp_servers() -> Result<BTreeMap<String, McpServerConfig>> {
+    #[derive(Deserialize)]
+    struct McpConfigFile {
+        #[serde(rename = "mcpServers", default)]
+        mcp_servers: BTreeMap<String, McpServerConfig>,
+    }
+
+    let path = match env::var("CLAUDE_MCP_CONFIG") {
+        Ok(path) if !path.trim().is_empty() => path,
+        _ => return Ok(BTreeMap::new()),
+    };
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        SpiralError::ConfigurationError(format!("CLAUDE_MCP_CONFIG: cannot read {path}: {e}"))
+    })?;
+    let file: McpConfigFile = serde_json::from_str(&contents).map_err(|e| {
+        SpiralError::ConfigurationError(format!("CLAUDE_MCP_CONFIG: invalid {path}: {e}"))
+    })?;
+    Ok(file.mcp_servers)
+}
+
+/// Log format from LOG_FORMAT, levels from LOG_LEVEL and files from LOG_DIR and LOG_*,
+/// checked so a typo fails startup rather than silently logging nothing
+fn logging() -> Result<LoggingConfig> {
+    let defaults = LoggingConfig::default();
+    let format = match env::var("LOG_FORMAT").ok().as_deref().map(str::trim) {
+        None | Some("") => defaults.format,
+        Some(raw) => match raw.to_ascii_lowercase().as_str() {
+            "text" => LogFormat::Text,
+            "json" => LogFormat::Json,
+            _ => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "LOG_FORMAT must be text or json, got '{raw}'"
+                )))
+            }
+        },
+    };
+    // RUST_LOG is what most deployments already set, so it is honoured when LOG_LEVEL isn't
+    let level = ["LOG_LEVEL", "RUST_LOG"]
+        .into_iter()
+        .find_map(|name| env::var(name).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or(defaults.level);
+    crate::logging::parse_filter(&level)
+        .map_err(|e| SpiralError::ConfigurationError(format!("LOG_LEVEL: {e}")))?;
+
+    Ok(LoggingConfig {
+        format,
+        level,
+        file: log_files()?,
+    })
+}
+
+/// 📁 LOG FILES: LOG_DIR turns file logging on; LOG_ROTATION, LOG_MAX_FILE_MB and
+/// LOG_RETENTION_DAYS tune it
+fn log_files() -> Result<Option<LogFileConfig>> {
+    let Some(dir) = env::var("LOG_DIR").ok().filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let defaults = LogFileConfig::default();
+    let rotation = match env::var("LOG_ROTATION").ok().as_deref().map(str::trim) {
+        None | Some("") => defaults.rotation,
+        Some(raw) => match raw.to_ascii_lowercase().as_str() {
+            "daily" => LogRotation::Daily,
+            "size" => LogRotation::Size,
+            _ => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "LOG_ROTATION must be daily or size, got '{raw}'"
+                )))
+            }
+        },
+    };
+    fn read<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+        match env::var(name) {
+            Ok(raw) if !raw.trim().is_empty() => raw.trim().parse().map_err(|_| {
+                SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+            }),
+            _ => Ok(default),
+        }
+    }
+    let max_file_mb = read("LOG_MAX_FILE_MB", defaults.max_file_mb)?;
+    if max_file_mb == 0 {
+        return Err(SpiralError::ConfigurationError(
+            "LOG_MAX_FILE_MB must be at least 1".to_string(),
+        ));
+    }
+
+    Ok(Some(LogFileConfig {
+        dir: dir.trim().to_string(),
+        rotation,
+        max_file_mb,
+        retention_days: read("LOG_RETENTION_DAYS", defaults.retention_days)?,
+    }))
+}
+
+/// 💥 CRASH REPORTS: CRASH_REPORT_DIR ("none" to only log), CRASH_RESTART and
+/// CRASH_MAX_RESTARTS
+fn crash() -> Result<CrashConfig> {
+    let defaults = CrashConfig::default();
+    let report_dir = match env::var("CRASH_REPORT_DIR") {
+        Ok(dir) if dir.trim().eq_ignore_ascii_case("none") => None,
+        Ok(dir) if !dir.trim().is_empty() => Some(dir.trim().to_string()),
+        _ => defaults.report_dir,
+    };
+    let invalid = |name: &str, raw: &str| {
+        SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+    };
+    let restart = match env::var("CRASH_RESTART") {
+        Ok(raw) if !raw.trim().is_empty() => raw
+            .trim()
+            .parse()
+            .map_err(|_| invalid("CRASH_RESTART", &raw))?,
+        _ => defaults.restart,
+    };
+    let max_restarts = match env::var("CRASH_MAX_RESTARTS") {
+        Ok(raw) if !raw.trim().is_empty() => raw
+            .trim()
+            .parse()
+            .map_err(|_| invalid("CRASH_MAX_RESTARTS", &raw))?,
+        _ => defaults.max_restarts,
+    };
+    Ok(CrashConfig {
+        report_dir,
+        restart,
+        max_restarts,
+    })
+}
+
+/// 💬 DISCORD RATE LIMITS: DISCORD_USER_RATE_PER_MINUTE and DISCORD_USER_RATE_BURST for
+/// each user, DISCORD_CHANNEL_RATE_PER_MINUTE (0 for no channel limit) and
+/// DISCORD_CHANNEL_RATE_BURST for each channel; a burst defaults to a minute's worth
+fn discord_rate_limits() -> Result<(
+    crate::rate_limit::TokenBucket,
+    Option<crate::rate_limit::TokenBucket>,
+)> {
+    use crate::rate_limit::TokenBucket;
+    fn read(name: &str) -> Result<Option<u32>> {
+        match env::var(name) {
+            Ok(raw) if !raw.trim().is_empty() => raw.trim().parse().map(Some).map_err(|_| {
+                SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+            }),
+            _ => Ok(None),
+        }
+    }
+    let bucket = |rate: u32, burst: Option<u32>, burst_name: &str| -> Result<TokenBucket> {
+        match burst {
+            Some(0) => Err(SpiralError::ConfigurationError(format!(
+                "{burst_name} must be at least 1"
+            ))),
+            burst => Ok(TokenBucket {
+                burst: burst.unwrap_or(rate),
+                refill_per_minute: rate,
+            }),
+        }
+    };
+
+    let user_defaults = default_user_rate_limit();
+    let user = match read("DISCORD_USER_RATE_PER_MINUTE")? {
+        Some(0) => {
+            return Err(SpiralError::ConfigurationError(
+                "DISCORD_USER_RATE_PER_MINUTE must be at least 1".to_string(),
+            ))
+        }
+        Some(rate) => bucket(
+            rate,
+            read("DISCORD_USER_RATE_BURST")?,
+            "DISCORD_USER_RATE_BURST",
+        )?,
+        None => bucket(
+            user_defaults.refill_per_minute,
+            read("DISCORD_USER_RATE_BURST")?.or(Some(user_defaults.burst)),
+            "DISCORD_USER_RATE_BURST",
+        )?,
+    };
+    let channel_defaults = default_channel_rate_limit();
+    let channel = match read("DISCORD_CHANNEL_RATE_PER_MINUTE")? {
+        Some(0) => None,
+        Some(rate) => Some(bucket(
+            rate,
+            read("DISCORD_CHANNEL_RATE_BURST")?,
+            "DISCORD_CHANNEL_RATE_BURST",
+        )?),
+        None => match channel_defaults {
+            Some(defaults) => Some(bucket(
+                defaults.refill_per_minute,
+                read("DISCORD_CHANNEL_RATE_BURST")?.or(Some(defaults.burst)),
+                "DISCORD_CHANNEL_RATE_BURST",
+            )?),
+            None => None,
+        },
+    };
+    Ok((user, channel))
+}
+
+/// 🚦 RATE LIMIT STORE: RATE_LIMIT_STORE (`memory` or `redis`) and RATE_LIMIT_REDIS_URL,
+/// which defaults to the session store's Redis server, and RATE_LIMIT_TIERS_PATH
+fn rate_limit() -> Result<RateLimitSettings> {
+    use crate::rate_limit::RateLimiterBackend;
+    let store = match env::var("RATE_LIMIT_STORE")
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .as_str()
+    {
+        "" | "memory" => RateLimiterBackend::Memory,
+        "redis" => RateLimiterBackend::Redis {
+            url: ["RATE_LIMIT_REDIS_URL", "SESSION_REDIS_URL"]
+                .into_iter()
+                .find_map(|name| env::var(name).ok().filter(|v| !v.trim().is_empty()))
+                .unwrap_or_else(|| "redis://127.0.0.1:6379".to_string()),
+        },
+        other => {
+            return Err(SpiralError::ConfigurationError(format!(
+                "RATE_LIMIT_STORE: unknown store `{other}`, use memory or redis"
+            )))
+        }
+    };
+    Ok(RateLimitSettings {
+        store,
+        // 🎟️ RATE LIMIT TIERS: Gitignored file in project root, like .spiral-guild-settings.json
+        tiers_path: Some(
+            env::var("RATE_LIMIT_TIERS_PATH")
+                .unwrap_or_else(|_| ".spiral-rate-limit-tiers.json".to_string()),
+        ),
+    })
+}
+
+/// Collection interval, retention and thresholds from MONITOR_*, the metrics history
+/// location, and the task objectives from SLO_TARGETS_FILE
+/// The file is a JSON list of targets replacing the defaults:
+/// `[{"agent_type": "SoftwareDeveloper", "objective": "p95_secs", "target": 300}]`
+fn monitoring() -> Result<MonitoringSettings> {
+    let defaults = MonitoringSettings::default();
+    let slo_targets = match env::var("SLO_TARGETS_FILE")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    {
+        Some(path) => {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                SpiralError::ConfigurationError(format!(
+                    "SLO_TARGETS_FILE: cannot read {path}: {e}"
+                ))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                SpiralError::ConfigurationError(format!("SLO_TARGETS_FILE: invalid {path}: {e}"))
+            })?
+        }
+        None => defaults.slo_targets,
+    };
+
+    fn read<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+        match env::var(name) {
+            Ok(raw) if !raw.trim().is_empty() => raw.trim().parse().map_err(|_| {
+                SpiralError::ConfigurationError(format!("{name} has an invalid value '{raw}'"))
+            }),
+            _ => Ok(default),
+        }
+    }
+    let thresholds = |resource: &str, defaults: ResourceThresholds| -> Result<_> {
+        Ok(ResourceThresholds {
+            warning_percent: read(
+                &format!("MONITOR_{resource}_WARNING_PERCENT"),
+                defaults.warning_percent,
+            )?,
+            critical_percent: read(
+                &format!("MONITOR_{resource}_CRITICAL_PERCENT"),
+                defaults.critical_percent,
+            )?,
+        })
+    };
+
+    let settings = MonitoringSettings {
+        interval_secs: read("MONITOR_INTERVAL_SECS", defaults.interval_secs)?,
+        retention_count: read("MONITOR_RETENTION_COUNT", defaults.retention_count)?,
+        cpu: thresholds("CPU", defaults.cpu)?,
+        memory: thresholds("MEMORY", defaults.memory)?,
+        disk: thresholds("DISK", defaults.disk)?,
+        // 📉 METRICS HISTORY: Gitignored SQLite file, so trends survive restarts
+        history_path: Some(
+            env::var("METRICS_HISTORY_PATH").unwrap_or_else(|_| ".spiral-metrics.db".to_string()),
+        ),
+        slo_window_secs: env::var("SLO_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(defaults.slo_window_secs),
+        slo_targets,
+    };
+    settings.validate()?;
+    Ok(settings)
+}
+
+/// Alert rules from the file named by ALERT_RULES_FILE, and the notification channels
+/// The file is a JSON list of rules replacing the defaults:
+/// `[{"name": "disk", "condition": {"kind": "resource", "resource": "disk", "percent": 90}}]`
+fn alerting(secrets: &Secrets) -> Result<AlertingConfig> {
+    let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+    let rules = match var("ALERT_RULES_FILE") {
+        Some(path) => {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                SpiralError::ConfigurationError(format!(
+                    "ALERT_RULES_FILE: cannot read {path}: {e}"
+                ))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                SpiralError::ConfigurationError(format!("ALERT_RULES_FILE: invalid {path}: {e}"))
+            })?
+        }
+        None => crate::monitoring::alerts::default_rules(),
+    };
+
+    let discord_channel_id = var("ALERT_DISCORD_CHANNEL_ID")
+        .map(|raw| {
+            raw.trim().parse().map_err(|_| {
+                SpiralError::ConfigurationError(format!(
+                    "ALERT_DISCORD_CHANNEL_ID must be a channel ID, got '{raw}'"
+                ))
+            })
+        })
+        .transpose()?;
+
+    let webhook_url = var("ALERT_WEBHOOK_URL");
+    if let Some(url) = &webhook_url {
+        match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            _ => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "ALERT_WEBHOOK_URL must be an http(s) URL, got '{url}'"
+                )))
+            }
+        }
+    }
+
+    let smtp = match var("ALERT_SMTP_HOST") {
+        Some(host) => {
+            let tls = match var("ALERT_SMTP_TLS").as_deref().map(str::to_lowercase) {
+                None => SmtpTls::StartTls,
+                Some(tls) if tls == "starttls" => SmtpTls::StartTls,
+                Some(tls) if tls == "tls" => SmtpTls::Tls,
+                Some(tls) if tls == "none" => SmtpTls::None,
+                Some(other) => {
+                    return Err(SpiralError::ConfigurationError(format!(
+                        "ALERT_SMTP_TLS must be starttls, tls or none, got '{other}'"
+                    )))
+                }
+            };
+            let port = match var("ALERT_SMTP_PORT") {
+                Some(raw) => raw.trim().parse().map_err(|_| {
+                    SpiralError::ConfigurationError(format!(
+                        "ALERT_SMTP_PORT must be a port number, got '{raw}'"
+                    ))
+                })?,
+                None if tls == SmtpTls::Tls => 465,
+                None => 587,
+            };
+            let to: Vec<String> = var("ALERT_EMAIL_TO")
+                .unwrap_or_default()
+                .split(',')
+                .map(|address| address.trim().to_string())
+                .filter(|address| !address.is_empty())
+                .collect();
+            let Some(from) = var("ALERT_EMAIL_FROM").filter(|_| !to.is_empty()) else {
+                return Err(SpiralError::ConfigurationError(
+                    "ALERT_SMTP_HOST needs ALERT_EMAIL_FROM and ALERT_EMAIL_TO".to_string(),
+                ));
+            };
+            Some(SmtpConfig {
+                host,
+                port,
+                tls,
+                username: var("ALERT_SMTP_USERNAME"),
+                password: secrets
+                    .lookup("ALERT_SMTP_PASSWORD")?
+                    .map(|(password, _)| password),
+                from,
+                to,
+            })
+        }
+        None => None,
+    };
+
+    Ok(AlertingConfig {
+        rules,
+        discord_channel_id,
+        webhook_url,
+        smtp,
+    })
+}
+
+/// Workspace templates from the file named by CLAUDE_WORKSPACE_TEMPLATES, if any
+/// The file maps names to sources: `{"service": {"git": "https://...", "sha256": "..."}}`
+fn workspace_templates() -> Result<WorkspaceTemplateConfig> {
+    let allow_git_urls = env::var("CLAUDE_WORKSPACE_TEMPLATE_GIT_URLS")
+        .map(|v| v.to_lowercase() == "true")
+        .unwrap_or(false);
+    let path = match env::var("CLAUDE_WORKSPACE_TEMPLATES") {
+        Ok(path) if !path.trim().is_empty() => path,
+        _ => {
+            return Ok(WorkspaceTemplateConfig {
+                templates: BTreeMap::new(),
+                allow_git_urls,
+            })
+        }
+    };
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        SpiralError::ConfigurationError(format!(
+            "CLAUDE_WORKSPACE_TEMPLATES: cannot read {path}: {e}"
+        ))
+    })?;
+    let templates = serde_json::from_str(&contents).map_err(|e| {
+        SpiralError::ConfigurationError(format!("CLAUDE_WORKSPACE_TEMPLATES: invalid {path}: {e}"))
+    })?;
+    Ok(WorkspaceTemplateConfig {
+        templates,
+        allow_git_urls,
+    })
+}
+
+/// Parse `name=Agent>Agent` workflows separated by `;`, such as
+/// `feature=SoftwareDeveloper>ProjectManager;hotfix=SoftwareDeveloper`
+/// A workflow with any unknown agent is skipped with a warning rather than run partially
+/// Replace tokens, keys, passwords and webhook URLs with "[redacted]", and strip the
+/// password from connection URLs such as Redis ones
+fn redact_secrets(value: &mut serde_json::Value) {
+    const SECRET_FIELDS: [&str; 5] = ["api_key", "secret", "password", "passphrase", "webhook_url"];
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                let secret = name == "token"
+                    || name.ends_with("_token")
+                    || SECRET_FIELDS.iter().any(|secret| name.contains(secret));
+                if secret && !field.is_null() {
+                    *field = serde_json::Value::String("[redacted]".to_string());
+                } else {
+                    redact_secrets(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        serde_json::Value::String(text) => {
+            if let Ok(mut url) = url::Url::parse(text) {
+                if url.password().is_some() && url.set_password(Some("redacted")).is_ok() {
+                    *text = url.to_string();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_workflows(raw: &str) -> HashMap<String, Vec<AgentType>> {
+    raw.split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry.split_once('=').and_then(|(name, steps)| {
+                let name = name.trim();
+                let steps = steps
+                    .split('>')
+                    .map(|step| step.trim().parse::<AgentType>().ok())
+                    .collect::<Option<Vec<_>>>()?;
+                (!name.is_empty() && !steps.is_empty()).then(|| (name.to_string(), steps))
+            });
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid WORKFLOWS entry: {}", entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// The session store named by SESSION_STORE (`memory`, `sqlite` or `redis`), with the
+/// path or URL that backend uses
+fn parse_session_store(
+    kind: &str,
+    path: String,
+    url: String,
+) -> Result<crate::session::SessionStoreBackend> {
+    use crate::session::SessionStoreBackend;
+    match kind.trim().to_lowercase().as_str() {
+        "memory" => Ok(SessionStoreBackend::Memory),
+        "sqlite" => Ok(SessionStoreBackend::Sqlite { path }),
+        "redis" => Ok(SessionStoreBackend::Redis { url }),
+        other => Err(SpiralError::ConfigurationError(format!(
+            "SESSION_STORE: unknown store `{other}`, use memory, sqlite or redis"
+        ))),
+    }
 }
 
 impl Config {
@@ -45,6 +1491,23 @@ impl Config {
             Err(e) => tracing::warn!("Could not load .env file: {}", e),
         }
 
+        // 🧱 CONFIG FILE: After .env, so SPIRAL_CONFIG may be set there
+        layers::ensure_installed()?;
+        if let Some(path) = layers::installed_file() {
+            tracing::info!("Loaded configuration file from: {:?}", path);
+        }
+        let profile = env::var(profiles::PROFILE_VAR)
+            .ok()
+            .map(|raw| raw.parse::<Profile>())
+            .transpose()?;
+        match profile {
+            Some(profile) => tracing::info!("Using the {} configuration profile", profile),
+            None => tracing::info!("No configuration profile; using built-in defaults"),
+        }
+
+        // 🔐 CREDENTIALS: Tokens and keys come from SECRET_PROVIDER, falling back to env
+        let secrets = Secrets::from_env()?;
+
         let claude_code = ClaudeCodeConfig {
             claude_binary_path: env::var("CLAUDE_BINARY_PATH").ok(),
             working_directory: env::var("CLAUDE_WORKING_DIR").ok(),
@@ -52,14 +1515,9 @@ impl Config {
                 .unwrap_or_else(|_| "300".to_string())
                 .parse()
                 .unwrap_or(300),
-            permission_mode: env::var("CLAUDE_PERMISSION_MODE").unwrap_or_else(|_| {
-                // Use more permissive mode in development environments
-                if cfg!(debug_assertions) {
-                    "bypassPermissions".to_string()
-                } else {
-                    "acceptEdits".to_string()
-                }
-            }),
+            // bypassPermissions only when asked for, e.g. by the dev profile
+            permission_mode: env::var("CLAUDE_PERMISSION_MODE")
+                .unwrap_or_else(|_| "acceptEdits".to_string()),
             allowed_tools: env::var("CLAUDE_ALLOWED_TOOLS")
                 .unwrap_or_else(|_| {
                     "Edit,Write,Read,Bash,MultiEdit,Glob,Grep,TodoWrite,NotebookEdit,WebFetch"
@@ -76,10 +1534,50 @@ impl Config {
                 .unwrap_or_else(|_| "100".to_string())
                 .parse()
                 .unwrap_or(100),
+            max_concurrent_invocations: env::var("CLAUDE_MAX_CONCURRENT_INVOCATIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::constants::CLAUDE_DEFAULT_MAX_CONCURRENT_INVOCATIONS),
+            daily_budget_usd: parse_daily_budget(env::var("CLAUDE_DAILY_BUDGET_USD").ok())?,
+            response_cache: ResponseCacheConfig {
+                capacity: env::var("CLAUDE_CACHE_CAPACITY")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::constants::CLAUDE_CACHE_DEFAULT_CAPACITY),
+                ttl_secs: env::var("CLAUDE_CACHE_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::constants::CLAUDE_CACHE_DEFAULT_TTL_SECS),
+                dir: env::var("CLAUDE_CACHE_DIR")
+                    .ok()
+                    .filter(|dir| !dir.trim().is_empty()),
+            },
+            circuit_breaker: circuit_breaker_policy("CLAUDE")?,
+            sandbox: sandbox_config()?,
+            mcp_servers: mcp_servers()?,
+            verify_cli_version: env::var("CLAUDE_VERIFY_CLI_VERSION")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(true),
+            workspace_templates: workspace_templates()?,
+            // 📝 TRANSCRIPTS: Per-task files under logs/, next to the audit log
+            transcripts: TranscriptConfig {
+                dir: Some(
+                    env::var("CLAUDE_TRANSCRIPT_DIR")
+                        .unwrap_or_else(|_| "logs/transcripts".to_string()),
+                )
+                .filter(|dir| !dir.trim().is_empty()),
+                retention_days: env::var("CLAUDE_TRANSCRIPT_RETENTION_DAYS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::constants::TRANSCRIPT_DEFAULT_RETENTION_DAYS),
+            },
         };
 
         // OPTIONAL: Discord integration configuration
-        let discord_token = env::var("DISCORD_TOKEN").unwrap_or_else(|_| "".to_string());
+        let discord_token = secrets
+            .lookup("DISCORD_TOKEN")?
+            .map(|(token, _)| token)
+            .unwrap_or_default();
 
         // Only validate Discord token if provided
         if !discord_token.is_empty() {
@@ -115,27 +1613,76 @@ impl Config {
             })
             .collect();
 
+        // 🎭 Per-user roles, e.g. "123:viewer,456:operator"
+        // A typo must not silently leave someone with the wrong access, so it is an error
+        let user_roles = crate::auth::rbac::parse_user_roles(
+            &env::var("DISCORD_USER_ROLES").unwrap_or_default(),
+        )
+        .map_err(|e| SpiralError::ConfigurationError(format!("DISCORD_USER_ROLES: {e}")))?
+        .into_iter()
+        .collect();
+
+        // 🗄️ SESSION STORE: Gitignored SQLite file by default, like .spiral-memory.db; an
+        // unknown backend is an error rather than a silent fall back to memory
+        let session_store = parse_session_store(
+            &env::var("SESSION_STORE").unwrap_or_else(|_| "sqlite".to_string()),
+            env::var("SESSION_STORE_PATH").unwrap_or_else(|_| ".spiral-sessions.db".to_string()),
+            env::var("SESSION_REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+        )?;
+        let session_budget = parse_session_budget()?;
+        let session_policies = parse_session_policies()?;
+        let (user_rate_limit, channel_rate_limit) = discord_rate_limits()?;
+
         let discord = DiscordConfig {
             token: discord_token,
             command_prefix: env::var("DISCORD_PREFIX").unwrap_or_else(|_| "!spiral".to_string()),
             agent_mention_pattern: env::var("AGENT_MENTION_PATTERN")
                 .unwrap_or_else(|_| r"@Spiral(\w+)".to_string()),
             authorized_users,
+            user_roles,
+            task_threads: env::var("DISCORD_TASK_THREADS")
+                .map(|v| v.to_lowercase() != "false")
+                .unwrap_or(true),
+            // 🏘️ GUILD SETTINGS: Gitignored file in project root, like .spiral-schedules.json
+            guild_settings_path: Some(
+                env::var("DISCORD_GUILD_SETTINGS_PATH")
+                    .unwrap_or_else(|_| ".spiral-guild-settings.json".to_string()),
+            ),
+            user_prefs_path: Some(
+                env::var("DISCORD_USER_PREFS_PATH")
+                    .unwrap_or_else(|_| ".spiral-user-prefs.json".to_string()),
+            ),
+            locale_dir: env::var("DISCORD_LOCALE_DIR")
+                .ok()
+                .filter(|dir| !dir.trim().is_empty()),
+            session_store: session_store.clone(),
+            session_budget: session_budget.clone(),
+            session_policies: session_policies.clone(),
+            presence_status: env::var("DISCORD_PRESENCE_STATUS")
+                .map(|v| v.to_lowercase() == "true")
+                .unwrap_or(false),
+            presence_interval_secs: env::var("DISCORD_PRESENCE_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::constants::DISCORD_PRESENCE_INTERVAL_SECS),
+            user_rate_limit,
+            channel_rate_limit,
         };
 
         // 🔐 SECURE API KEY LOADING: Environment variable or generated secure key
-        // DECISION: Prioritize env var, fall back to secure file-based key
-        let api_key = match env::var("API_KEY").ok() {
-            Some(key) if !key.trim().is_empty() => {
-                tracing::info!("Using API key from environment variable");
+        // DECISION: Prioritize the secret provider, fall back to secure file-based key
+        let key_file_passphrase = secrets
+            .lookup("API_KEY_FILE_PASSPHRASE")?
+            .map(|(passphrase, _)| passphrase);
+        let api_key = match secrets.lookup("API_KEY")? {
+            Some((key, provider)) => {
+                tracing::info!("Using API key from the {} secret provider", provider);
                 Some(key)
             }
-            _ => {
-                tracing::info!(
-                    "No API_KEY environment variable set, checking for generated key file"
-                );
+            None => {
+                tracing::info!("No API_KEY secret set, checking for generated key file");
                 // Try to load from secure file, don't generate here (will be done in startup validation)
-                match crate::security::load_api_key_from_file() {
+                match crate::security::load_api_key_from_file(key_file_passphrase.as_deref()) {
                     Ok(Some(key)) => {
                         tracing::info!("Using existing API key from secure file");
                         Some(key)
@@ -178,6 +1725,31 @@ impl Config {
             .filter(|s| !s.is_empty())
             .collect();
 
+        // 🛡️ NETWORK ACCESS CONTROL: A mistyped network must fail startup, not open the API
+        let ip_allowlist = parse_ip_networks("API_IP_ALLOWLIST")?;
+        let ip_denylist = parse_ip_networks("API_IP_DENYLIST")?;
+
+        // ✍️ REQUEST SIGNING: Groups without a secret would lock out every client
+        let signing_secret = secrets
+            .lookup("API_SIGNING_SECRET")?
+            .map(|(secret, _)| secret);
+        let signed_route_groups = env::var("API_SIGNED_ROUTE_GROUPS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+            .map(|group| {
+                group.parse().map_err(|e| {
+                    SpiralError::ConfigurationError(format!("API_SIGNED_ROUTE_GROUPS: {e}"))
+                })
+            })
+            .collect::<Result<Vec<crate::auth::signing::SignedRouteGroup>>>()?;
+        if !signed_route_groups.is_empty() && signing_secret.is_none() {
+            return Err(SpiralError::ConfigurationError(
+                "API_SIGNED_ROUTE_GROUPS is set but API_SIGNING_SECRET is not".to_string(),
+            ));
+        }
+
         let api = ApiConfig {
             host: env::var("API_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()), // SECURITY: Default to localhost only
             port: env::var("API_PORT")
@@ -186,19 +1758,217 @@ impl Config {
                 .unwrap_or(3000),
             api_key,
             allowed_origins,
+            swagger_ui_enabled: env::var("API_SWAGGER_UI")
+                .map(|value| value.parse().unwrap_or(false))
+                .unwrap_or(false),
+            idempotency_window_secs: env::var("API_IDEMPOTENCY_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_idempotency_window_secs),
+            max_body_bytes: env::var("API_MAX_BODY_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_max_body_bytes),
+            max_batch_body_bytes: env::var("API_MAX_BATCH_BODY_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_max_batch_body_bytes),
+            // 🔑 SCOPED KEYS: Gitignored file in project root, next to .spiral-api-key
+            keys_file: Some(
+                env::var("API_KEYS_FILE").unwrap_or_else(|_| ".spiral-api-keys.json".to_string()),
+            ),
+            ip_allowlist,
+            ip_denylist,
+            key_file_passphrase,
+            signing_secret,
+            signed_route_groups,
+            signature_max_age_secs: env::var("API_SIGNATURE_MAX_AGE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_signature_max_age_secs),
+            // 🗂️ API SESSIONS: Same store and budget as the Discord sessions, so both can share it
+            session_store,
+            session_budget,
+            session_policies,
+            // 🚦 RATE LIMITS: RATE_LIMIT_POLICIES entries replace or add to the defaults
+            rate_limits: crate::rate_limit::policy::merge_policies(
+                crate::rate_limit::policy::default_policies(),
+                crate::rate_limit::policy::parse_policies(
+                    &env::var("RATE_LIMIT_POLICIES").unwrap_or_default(),
+                )
+                .map_err(|e| {
+                    SpiralError::ConfigurationError(format!("RATE_LIMIT_POLICIES: {e}"))
+                })?,
+            ),
+        };
+
+        // 🔁 RETRY POLICY: Transient Claude Code failures are retried before a task fails
+        // Why: Rate limits and timeouts usually clear up within minutes
+        // Alternative: Fail immediately (rejected: pushes retry burden onto users)
+        let orchestrator_defaults = OrchestratorConfig::default();
+        let orchestrator = OrchestratorConfig {
+            max_task_retries: env::var("TASK_MAX_RETRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.max_task_retries),
+            retry_initial_backoff_ms: env::var("TASK_RETRY_BACKOFF_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.retry_initial_backoff_ms),
+            retry_max_backoff_ms: env::var("TASK_RETRY_MAX_BACKOFF_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.retry_max_backoff_ms),
+            // ⚡ WORKER POOLS: Per-agent-type parallelism, defaulting to one task at a time
+            default_agent_concurrency: env::var("DEFAULT_AGENT_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.default_agent_concurrency),
+            agent_concurrency: env::var("AGENT_CONCURRENCY")
+                .map(|raw| parse_agent_concurrency(&raw))
+                .unwrap_or_default(),
+            // 💾 SCHEDULE PERSISTENCE: Gitignored file in project root, like .spiral-api-key
+            schedule_store_path: Some(
+                env::var("SCHEDULE_STORE_PATH")
+                    .unwrap_or_else(|_| ".spiral-schedules.json".to_string()),
+            ),
+            max_schedules: env::var("MAX_SCHEDULES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.max_schedules),
+            // 🧠 AGENT MEMORY: Gitignored SQLite file, so agents remember across restarts
+            memory_store_path: Some(
+                env::var("AGENT_MEMORY_PATH").unwrap_or_else(|_| ".spiral-memory.db".to_string()),
+            ),
+            // ♻️ SHUTDOWN CHECKPOINTS: Gitignored file, removed again once tasks are resumed
+            checkpoint_store_path: Some(
+                env::var("CHECKPOINT_STORE_PATH")
+                    .unwrap_or_else(|_| ".spiral-checkpoints.json".to_string()),
+            ),
+            // 🔗 WORKFLOWS: No built-in pipelines, every workflow is opted into explicitly
+            workflows: env::var("WORKFLOWS")
+                .map(|raw| parse_workflows(&raw))
+                .unwrap_or_default(),
+            // 🔔 WEBHOOKS: Off until hosts are allowlisted, so callers cannot make the
+            // server call arbitrary URLs
+            webhook_allowed_hosts: env::var("WEBHOOK_ALLOWED_HOSTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            webhook_max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(orchestrator_defaults.webhook_max_attempts),
+            // 🎟️ QUEUE ADMISSION: Urgent and admin work keeps a slice of a full queue;
+            // evicting queued work is opt-in because it fails tasks users already submitted
+            queue_reserved_percent: match env::var("QUEUE_RESERVED_PERCENT") {
+                Ok(raw) => raw
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|percent| *percent <= 100)
+                    .ok_or_else(|| {
+                        SpiralError::ConfigurationError(format!(
+                            "QUEUE_RESERVED_PERCENT: expected 0-100, got '{raw}'"
+                        ))
+                    })?,
+                Err(_) => orchestrator_defaults.queue_reserved_percent,
+            },
+            queue_evict_low_priority: env::var("QUEUE_EVICT_LOW_PRIORITY")
+                .map(|v| v.to_lowercase() == "true")
+                .unwrap_or(orchestrator_defaults.queue_evict_low_priority),
+        };
+
+        // 📜 AUDIT LOG: Daily JSONL files under logs/, next to the self-update logs
+        let audit = AuditConfig {
+            log_dir: Some(env::var("AUDIT_LOG_DIR").unwrap_or_else(|_| "logs/audit".to_string())),
+            retention_days: env::var("AUDIT_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::constants::AUDIT_DEFAULT_RETENTION_DAYS),
+        };
+
+        // 🔀 LLM BACKENDS: OpenAI is configured by its key, Ollama by its URL
+        let openai = match secrets.lookup("OPENAI_API_KEY")? {
+            Some((api_key, _)) => Some(OpenAiConfig {
+                api_key,
+                base_url: env::var("OPENAI_BASE_URL")
+                    .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
+                model: env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4o".to_string()),
+                circuit_breaker: circuit_breaker_policy("OPENAI")?,
+            }),
+            None => None,
+        };
+        let ollama = match env::var("OLLAMA_URL") {
+            Ok(base_url) => Some(OllamaConfig {
+                base_url,
+                model: env::var("OLLAMA_MODEL").unwrap_or_else(|_| "qwen2.5-coder".to_string()),
+                circuit_breaker: circuit_breaker_policy("OLLAMA")?,
+            }),
+            Err(_) => None,
+        };
+        let llm = LlmConfig {
+            openai,
+            ollama,
+            agent_backends: parse_agent_backends(
+                &env::var("LLM_AGENT_BACKENDS").unwrap_or_default(),
+            )?,
+            fallback: env::var("LLM_FALLBACK_BACKENDS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|backend| !backend.is_empty())
+                .map(|backend| {
+                    backend.parse().map_err(|e| {
+                        SpiralError::ConfigurationError(format!("LLM_FALLBACK_BACKENDS: {e}"))
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+        llm.validate()?;
+
+        // 📝 PROMPT TEMPLATES: Built-in unless a directory of overrides is configured
+        let prompts = PromptConfig {
+            templates_dir: env::var("PROMPT_TEMPLATES_DIR")
+                .ok()
+                .filter(|dir| !dir.trim().is_empty()),
+            reload_interval_secs: env::var("PROMPT_TEMPLATE_RELOAD_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::constants::PROMPT_TEMPLATE_RELOAD_SECS),
         };
 
         Ok(Config {
+            profile,
             claude_code,
             discord,
             api,
+            orchestrator,
+            audit,
+            llm,
+            prompts,
+            alerting: alerting(&secrets)?,
+            monitoring: monitoring()?,
+            logging: logging()?,
+            crash: crash()?,
+            rate_limit: rate_limit()?,
         })
     }
 
+    /// 🔍 PRINT CONFIG: The resolved configuration as JSON with credentials redacted, for
+    /// --print-config
+    pub fn redacted(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        redact_secrets(&mut value);
+        value
+    }
+
     /// Create a test configuration with sensible defaults
     #[cfg(test)]
     pub fn test_config() -> Self {
         Self {
+            profile: None,
             claude_code: ClaudeCodeConfig {
                 claude_binary_path: Some("mock-claude".to_string()),
                 working_directory: Some("/tmp/test".to_string()),
@@ -207,19 +1977,64 @@ impl Config {
                 allowed_tools: vec!["edit".to_string(), "read".to_string()],
                 workspace_cleanup_after_hours: 1,
                 max_workspace_size_mb: 100,
+                max_concurrent_invocations: 4,
+                daily_budget_usd: None,
+                response_cache: Default::default(),
+                circuit_breaker: Default::default(),
+                sandbox: Default::default(),
+                mcp_servers: Default::default(),
+                verify_cli_version: false,
+                workspace_templates: Default::default(),
+                transcripts: Default::default(),
             },
             discord: DiscordConfig {
                 token: "mock-discord-token-for-testing-only".to_string(),
                 command_prefix: "!test".to_string(),
                 agent_mention_pattern: r"@Test(\w+)".to_string(),
                 authorized_users: vec![123456789],
+                user_roles: HashMap::new(),
+                task_threads: true,
+                guild_settings_path: None,
+                user_prefs_path: None,
+                locale_dir: None,
+                session_store: Default::default(),
+                session_budget: Default::default(),
+                session_policies: Default::default(),
+                presence_status: false,
+                presence_interval_secs: crate::constants::DISCORD_PRESENCE_INTERVAL_SECS,
+                user_rate_limit: default_user_rate_limit(),
+                channel_rate_limit: default_channel_rate_limit(),
             },
             api: ApiConfig {
                 host: "127.0.0.1".to_string(),
                 port: 3000,
                 api_key: Some("test-api-key-32-characters-long-for-security".to_string()),
                 allowed_origins: vec!["http://localhost:3000".to_string()],
+                swagger_ui_enabled: false,
+                idempotency_window_secs: default_idempotency_window_secs(),
+                max_body_bytes: default_max_body_bytes(),
+                max_batch_body_bytes: default_max_batch_body_bytes(),
+                keys_file: None,
+                key_file_passphrase: None,
+                ip_allowlist: Vec::new(),
+                ip_denylist: Vec::new(),
+                signing_secret: None,
+                signed_route_groups: Vec::new(),
+                signature_max_age_secs: default_signature_max_age_secs(),
+                session_store: Default::default(),
+                session_budget: Default::default(),
+                session_policies: Default::default(),
+                rate_limits: crate::rate_limit::policy::default_policies(),
             },
+            orchestrator: OrchestratorConfig::default(),
+            audit: AuditConfig::default(),
+            llm: LlmConfig::default(),
+            prompts: PromptConfig::default(),
+            alerting: AlertingConfig::default(),
+            monitoring: MonitoringSettings::default(),
+            logging: LoggingConfig::default(),
+            crash: CrashConfig::default(),
+            rate_limit: RateLimitSettings::default(),
         }
     }
 }
@@ -0,0 +1,137 @@
+//! 🎚️ CONFIG PROFILES: Named sets of defaults for each kind of deployment
+//!
+//! SPIRAL_PROFILE (or --profile) selects one; its settings rank below the configuration
+//! file, so anything set explicitly still wins. Without a profile the built-in defaults
+//! apply, which are the safe ones: Claude Code never bypasses permissions unless a
+//! profile or setting says so.
+//!
+//! 🏗️ ARCHITECTURE DECISION: Profiles are one more layer of settings by variable name
+//! Why: The loader already resolves every setting through the layers, so a profile needs
+//! no code of its own per setting, and `--print-config` shows exactly what it chose
+//! Alternative: Build-type defaults via cfg!(debug_assertions) (rejected: a debug binary
+//! deployed by mistake silently ran with bypassPermissions, and nothing recorded why)
+//! Trade-off: A profile can only set what an environment variable can
+
+use crate::SpiralError;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Environment variable selecting the profile
+pub const PROFILE_VAR: &str = "SPIRAL_PROFILE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// A developer's machine or sandbox: Claude Code runs unprompted, limits are loose
+    Dev,
+    /// Production settings with more logging
+    Staging,
+    /// Least privilege: edits accepted, no shell or web tools
+    Prod,
+}
+
+impl Profile {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Staging => "staging",
+            Self::Prod => "prod",
+        }
+    }
+
+    /// The profile's settings, by environment variable name
+    pub fn defaults(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Dev => &[
+                ("CLAUDE_PERMISSION_MODE", "bypassPermissions"),
+                (
+                    "CLAUDE_ALLOWED_TOOLS",
+                    "Edit,Write,Read,Bash,MultiEdit,Glob,Grep,TodoWrite,WebFetch",
+                ),
+                ("LOG_LEVEL", "info,spiral_core=debug"),
+                (
+                    "RATE_LIMIT_POLICIES",
+                    "task_submission=100,admin=600,reads=1200,writes=600",
+                ),
+            ],
+            Self::Staging => &[
+                ("CLAUDE_PERMISSION_MODE", "acceptEdits"),
+                (
+                    "CLAUDE_ALLOWED_TOOLS",
+                    "Edit,Write,Read,Bash,MultiEdit,Glob,Grep,TodoWrite",
+                ),
+                ("LOG_LEVEL", "info,spiral_core=debug"),
+            ],
+            Self::Prod => &[
+                ("CLAUDE_PERMISSION_MODE", "acceptEdits"),
+                (
+                    "CLAUDE_ALLOWED_TOOLS",
+                    "Edit,Write,Read,MultiEdit,Glob,Grep",
+                ),
+                ("LOG_LEVEL", "info"),
+            ],
+        }
+    }
+
+    /// The profile's value for `key`, if it sets one
+    pub fn default_for(self, key: &str) -> Option<&'static str> {
+        self.defaults()
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = SpiralError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "dev" | "development" => Ok(Self::Dev),
+            "staging" => Ok(Self::Staging),
+            "prod" | "production" => Ok(Self::Prod),
+            _ => Err(SpiralError::ConfigurationError(format!(
+                "{PROFILE_VAR} must be dev, staging or prod, got '{raw}'"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::layers::{ConfigLayers, Layer};
+
+    #[test]
+    fn test_profile_defaults_rank_below_explicit_settings() {
+        assert_eq!("Production".parse::<Profile>().unwrap(), Profile::Prod);
+        assert!("qa".parse::<Profile>().is_err());
+
+        let layers = ConfigLayers::default().with_overrides([
+            (PROFILE_VAR.to_string(), "dev".to_string()),
+            ("LOG_LEVEL".to_string(), "warn".to_string()),
+        ]);
+        assert_eq!(
+            layers.lookup("CLAUDE_PERMISSION_MODE"),
+            Some(("bypassPermissions".to_string(), Layer::Profile))
+        );
+        assert_eq!(
+            layers.lookup("LOG_LEVEL"),
+            Some(("warn".to_string(), Layer::CommandLine))
+        );
+
+        // A profile only fills in what it lists
+        assert_eq!(
+            ConfigLayers::default()
+                .with_overrides([(PROFILE_VAR.to_string(), "prod".to_string())])
+                .lookup("RATE_LIMIT_POLICIES"),
+            None
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! 🔄 CONFIG RELOAD: Apply edits to the configuration file without a restart
+//!
+//! Only settings the running subsystems can adopt safely are reloaded: API rate limits,
+//! Discord access and message limits, and monitoring thresholds. A reload that changes
+//! anything else (ports, tokens, storage paths, ...) is rejected as a whole and the running
+//! configuration is kept, so the server never runs half on old and half on new settings.
+//!
+//! 🏗️ ARCHITECTURE DECISION: One handle holding the current Config, with subsystems
+//! subscribed to its changes
+//! Why: Each subsystem keeps reading its own copy of the settings it needs, so request
+//! paths don't take a lock on the whole configuration
+//! Alternative: Have every subsystem read through the handle (rejected: most settings are
+//! fixed at startup and threading the handle everywhere buys nothing for them)
+//! Trade-off: A reloadable setting needs its subsystem to implement Reloadable
+
+use super::{layers, Config};
+use crate::constants::CONFIG_RELOAD_DEBOUNCE_MS;
+use crate::{Result, SpiralError};
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use notify::{RecursiveMode, Watcher};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Settings, by their path in the serialized Config, that may change while running
+pub const RELOADABLE_SETTINGS: &[&str] = &[
+    "api.rate_limits",
+    "discord.authorized_users",
+    "discord.user_roles",
+    "discord.user_rate_limit",
+    "discord.channel_rate_limit",
+    "monitoring.cpu",
+    "monitoring.memory",
+    "monitoring.disk",
+];
+
+/// A subsystem that adopts reloaded settings
+#[async_trait]
+pub trait Reloadable: Send + Sync {
+    /// Named in logs
+    fn name(&self) -> &'static str;
+
+    /// Take the reloadable settings from `config`
+    async fn apply_config(&self, config: &Config);
+}
+
+/// 🔄 CONFIG HANDLE: The configuration in effect, shared by everything that reloads it
+#[derive(Clone)]
+pub struct ConfigHandle {
+    current: Arc<ArcSwap<Config>>,
+    changes: Arc<watch::Sender<Arc<Config>>>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: Config) -> Self {
+        let config = Arc::new(config);
+        Self {
+            current: Arc::new(ArcSwap::new(Arc::clone(&config))),
+            changes: Arc::new(watch::Sender::new(config)),
+        }
+    }
+
+    /// The configuration in effect
+    pub fn current(&self) -> Arc<Config> {
+        self.current.load_full()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Arc<Config>> {
+        self.changes.subscribe()
+    }
+
+    /// Make `config` current and notify subscribers, returning the settings that changed.
+    /// Fails, keeping the current configuration, if a setting that needs a restart differs
+    pub fn apply(&self, config: Config) -> Result<Vec<String>> {
+        let changed = changed_settings(&self.current(), &config)?;
+        let fixed: Vec<&str> = changed
+            .iter()
+            .map(String::as_str)
+            .filter(|setting| !RELOADABLE_SETTINGS.contains(setting))
+            .collect();
+        if !fixed.is_empty() {
+            return Err(SpiralError::ConfigurationError(format!(
+                "Changing {} needs a restart",
+                fixed.join(", ")
+            )));
+        }
+        if !changed.is_empty() {
+            let config = Arc::new(config);
+            self.current.store(Arc::clone(&config));
+            self.changes.send_replace(config);
+        }
+        Ok(changed)
+    }
+
+    /// Pass every reloaded configuration to `subsystem`
+    pub fn follow<R: Reloadable + 'static>(&self, subsystem: Arc<R>) -> JoinHandle<()> {
+        let mut changes = self.subscribe();
+        tokio::spawn(async move {
+            while changes.changed().await.is_ok() {
+                let config = Arc::clone(&changes.borrow_and_update());
+                subsystem.apply_config(&config).await;
+                debug!("Reloaded configuration applied to the {}", subsystem.name());
+            }
+        })
+    }
+}
+
+/// Paths of the settings that differ between `old` and `new`; reloadable settings are
+/// compared whole
+fn changed_settings(old: &Config, new: &Config) -> Result<Vec<String>> {
+    let to_value = |config: &Config| {
+        serde_json::to_value(config).map_err(|e| {
+            SpiralError::ConfigurationError(format!("Failed to compare configurations: {e}"))
+        })
+    };
+    let mut changed = Vec::new();
+    diff(&to_value(old)?, &to_value(new)?, "", &mut changed);
+    Ok(changed)
+}
+
+fn diff(old: &Value, new: &Value, path: &str, changed: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) if !RELOADABLE_SETTINGS.contains(&path) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff(
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    &child,
+                    changed,
+                );
+            }
+        }
+        _ => changed.push(path.to_string()),
+    }
+}
+
+/// Load the configuration again with the file's current content and apply it. The file
+/// is only re-read; the environment and command-line flags stay as they were at startup
+pub fn reload(handle: &ConfigHandle) -> Result<Vec<String>> {
+    let previous = layers::reread_file()?;
+    let result = Config::load().and_then(|config| handle.apply(config));
+    if result.is_err() {
+        layers::install(previous);
+    }
+    result
+}
+
+/// 👀 Reload whenever the configuration file at `path` changes
+pub async fn watch_file(handle: ConfigHandle, path: PathBuf) {
+    let (events, mut edits) = mpsc::unbounded_channel();
+    let file_name = path.file_name().map(ToOwned::to_owned);
+    let mut watcher =
+        match notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if !event.kind.is_access()
+                && event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == file_name.as_deref())
+            {
+                let _ = events.send(());
+            }
+        }) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("Configuration file won't be reloaded: {}", e);
+                return;
+            }
+        };
+    // Watch the directory: editors that save by renaming replace the file's inode
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+        warn!("Configuration file won't be reloaded: {}", e);
+        return;
+    }
+    info!("Reloading {} when it changes", path.display());
+
+    while edits.recv().await.is_some() {
+        tokio::time::sleep(Duration::from_millis(CONFIG_RELOAD_DEBOUNCE_MS)).await;
+        while edits.try_recv().is_ok() {}
+
+        // Loading may fetch secrets with blocking requests
+        let reloading = handle.clone();
+        match tokio::task::spawn_blocking(move || reload(&reloading)).await {
+            Ok(Ok(changed)) if changed.is_empty() => {
+                debug!("{} changed without changing any setting", path.display())
+            }
+            Ok(Ok(changed)) => info!("Configuration reloaded: {}", changed.join(", ")),
+            Ok(Err(e)) => error!(
+                "Configuration reload rejected, keeping the running configuration: {}",
+                e
+            ),
+            Err(e) => error!("Configuration reload failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::TokenBucket;
+
+    #[tokio::test]
+    async fn test_reloadable_changes_apply_and_others_are_rejected() {
+        let handle = ConfigHandle::new(Config::test_config());
+        let mut changes = handle.subscribe();
+
+        let mut edited = Config::test_config();
+        edited.discord.authorized_users.push(42);
+        edited.discord.user_rate_limit = TokenBucket::per_minute(3);
+        edited.monitoring.cpu.warning_percent = 50.0;
+        let mut changed = handle.apply(edited).unwrap();
+        changed.sort();
+        assert_eq!(
+            changed,
+            [
+                "discord.authorized_users",
+                "discord.user_rate_limit",
+                "monitoring.cpu"
+            ]
+        );
+        assert!(changes.has_changed().unwrap());
+        assert!(changes
+            .borrow_and_update()
+            .discord
+            .authorized_users
+            .contains(&42));
+
+        let mut restart = (*handle.current()).clone();
+        restart.api.port += 1;
+        restart.discord.token = "another-token".to_string();
+        restart.monitoring.disk.critical_percent = 99.0;
+        let err = handle.apply(restart).unwrap_err().to_string();
+        assert!(err.contains("api.port") && err.contains("discord.token"));
+        assert!(!err.contains("monitoring"));
+        assert!(!changes.has_changed().unwrap());
+        assert_eq!(handle.current().api.port, Config::test_config().api.port);
+    }
+}
@@ -0,0 +1,507 @@
+//! 🔐 SECRET PROVIDERS: Where DISCORD_TOKEN and API_KEY come from
+//!
+//! 🏗️ ARCHITECTURE DECISION: A small synchronous trait resolved once in Config::load
+//! Why: Production deployments keep credentials in a secret store rather than in .env;
+//! fetching them into memory at startup means they are never written to disk
+//! Alternative: Render .env from the store before launch (rejected: the token then sits in
+//! a file on the host)
+//! Trade-off: Rotating a secret needs a restart
+//!
+//! Vault and AWS Secrets Manager are behind the `vault` and `aws-secrets` features so
+//! default builds carry no code that talks to them.
+
+use super::layers as env;
+use crate::{Result, SpiralError};
+use std::path::{Path, PathBuf};
+
+/// A source of named secrets
+pub trait SecretProvider: Send + Sync {
+    /// Short name for logs, e.g. "env" or "vault"
+    fn name(&self) -> &'static str;
+
+    /// The secret's value, or None if this provider does not hold it
+    /// Err means the provider itself failed and startup should stop
+    fn get(&self, key: &str) -> Result<Option<String>>;
+}
+
+/// Secrets from environment variables, including those loaded from .env
+pub struct EnvSecretProvider;
+
+impl SecretProvider for EnvSecretProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(env::var(key).ok().filter(|value| !value.trim().is_empty()))
+    }
+}
+
+/// Secrets as one file per key in a directory, as mounted by Docker and Kubernetes
+/// secrets (e.g. /run/secrets/DISCORD_TOKEN)
+pub struct FileSecretProvider {
+    dir: PathBuf,
+}
+
+impl FileSecretProvider {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl SecretProvider for FileSecretProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        // Keys are env-var style names; anything else could walk out of the directory
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(SpiralError::ConfigurationError(format!(
+                "Invalid secret name '{key}'"
+            )));
+        }
+        let path = self.dir.join(key);
+        match std::fs::read_to_string(&path) {
+            // Secret files are usually written with a trailing newline
+            Ok(content) => Ok(Some(content.trim_end_matches(['\r', '\n']).to_string())
+                .filter(|value| !value.trim().is_empty())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(SpiralError::ConfigurationError(format!(
+                "Failed to read secret file {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Secrets fetched once from a remote store as a flat key/value object
+#[cfg(any(feature = "vault", feature = "aws-secrets"))]
+struct FetchedSecrets(std::collections::HashMap<String, String>);
+
+#[cfg(any(feature = "vault", feature = "aws-secrets"))]
+impl FetchedSecrets {
+    fn from_json(source: &str, value: &serde_json::Value) -> Result<Self> {
+        let object = value.as_object().ok_or_else(|| {
+            SpiralError::ConfigurationError(format!("{source} secret is not a JSON object"))
+        })?;
+        Ok(Self(
+            object
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect(),
+        ))
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+}
+
+/// Run a request to a secret store from synchronous startup code
+/// Config::load runs inside the tokio runtime, which must not be blocked on, so the
+/// request gets a throwaway runtime on its own thread
+#[cfg(any(feature = "vault", feature = "aws-secrets"))]
+fn fetch_blocking<F, T>(fetch: F) -> Result<T>
+where
+    F: std::future::Future<Output = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| SpiralError::Internal(e.into()))?
+            .block_on(fetch)
+    })
+    .join()
+    .map_err(|_| SpiralError::ConfigurationError("Secret fetch thread panicked".to_string()))?
+}
+
+#[cfg(any(feature = "vault", feature = "aws-secrets"))]
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(
+            crate::constants::SECRET_FETCH_TIMEOUT_SECS,
+        ))
+        .build()
+        .map_err(|e| SpiralError::Internal(e.into()))
+}
+
+/// Secrets from one HashiCorp Vault KV v2 secret, e.g. `secret/spiral-core`
+#[cfg(feature = "vault")]
+pub struct VaultSecretProvider {
+    secrets: FetchedSecrets,
+}
+
+#[cfg(feature = "vault")]
+impl VaultSecretProvider {
+    pub fn fetch(addr: &str, token: &str, mount: &str, path: &str) -> Result<Self> {
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            addr.trim_end_matches('/'),
+            mount.trim_matches('/'),
+            path.trim_matches('/')
+        );
+        let token = token.to_string();
+        let body: serde_json::Value = fetch_blocking(async move {
+            let response = http_client()?
+                .get(&url)
+                .header("X-Vault-Token", token)
+                .send()
+                .await
+                .map_err(|e| {
+                    SpiralError::ConfigurationError(format!("Vault request failed: {e}"))
+                })?;
+            if !response.status().is_success() {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "Vault returned {} for {url}",
+                    response.status()
+                )));
+            }
+            response.json().await.map_err(|e| {
+                SpiralError::ConfigurationError(format!("Invalid Vault response: {e}"))
+            })
+        })?;
+        Ok(Self {
+            secrets: FetchedSecrets::from_json("Vault", &body["data"]["data"])?,
+        })
+    }
+}
+
+#[cfg(feature = "vault")]
+impl SecretProvider for VaultSecretProvider {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.secrets.get(key))
+    }
+}
+
+/// Secrets from one AWS Secrets Manager secret holding a JSON object of key/value pairs
+#[cfg(feature = "aws-secrets")]
+pub struct AwsSecretsManagerProvider {
+    secrets: FetchedSecrets,
+}
+
+/// Static AWS credentials, as read from AWS_ACCESS_KEY_ID and friends
+#[cfg(feature = "aws-secrets")]
+#[derive(Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+#[cfg(feature = "aws-secrets")]
+impl AwsSecretsManagerProvider {
+    /// `endpoint` overrides the regional endpoint, e.g. for LocalStack
+    pub fn fetch(
+        region: &str,
+        secret_id: &str,
+        credentials: AwsCredentials,
+        endpoint: Option<&str>,
+    ) -> Result<Self> {
+        let host = format!("secretsmanager.{region}.amazonaws.com");
+        let url = endpoint
+            .map(|endpoint| endpoint.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("https://{host}"));
+        let host = url::Url::parse(&url)
+            .ok()
+            .and_then(|url| {
+                url.host_str()
+                    .map(|h| url.port().map_or(h.to_string(), |p| format!("{h}:{p}")))
+            })
+            .unwrap_or(host);
+        let body = serde_json::json!({ "SecretId": secret_id }).to_string();
+        let headers = aws_sigv4::sign_post(
+            &credentials,
+            region,
+            "secretsmanager",
+            &host,
+            &[
+                ("content-type", "application/x-amz-json-1.1"),
+                ("x-amz-target", "secretsmanager.GetSecretValue"),
+            ],
+            &body,
+            chrono::Utc::now(),
+        );
+
+        let response: serde_json::Value = fetch_blocking(async move {
+            let mut request = http_client()?.post(&url).body(body);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            let response = request.send().await.map_err(|e| {
+                SpiralError::ConfigurationError(format!("Secrets Manager request failed: {e}"))
+            })?;
+            if !response.status().is_success() {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "Secrets Manager returned {}",
+                    response.status()
+                )));
+            }
+            response.json().await.map_err(|e| {
+                SpiralError::ConfigurationError(format!("Invalid Secrets Manager response: {e}"))
+            })
+        })?;
+
+        let secret_string = response["SecretString"].as_str().ok_or_else(|| {
+            SpiralError::ConfigurationError(format!("Secret {secret_id} has no SecretString"))
+        })?;
+        let value: serde_json::Value = serde_json::from_str(secret_string).map_err(|_| {
+            SpiralError::ConfigurationError(format!("Secret {secret_id} is not a JSON object"))
+        })?;
+        Ok(Self {
+            secrets: FetchedSecrets::from_json("Secrets Manager", &value)?,
+        })
+    }
+}
+
+#[cfg(feature = "aws-secrets")]
+impl SecretProvider for AwsSecretsManagerProvider {
+    fn name(&self) -> &'static str {
+        "aws-secrets-manager"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.secrets.get(key))
+    }
+}
+
+/// Just enough AWS Signature Version 4 to call one JSON API with static credentials
+/// 🏗️ ARCHITECTURE DECISION: Sign requests with the hmac/sha2 crates already in the tree
+/// Why: The AWS SDK pulls in a large dependency tree for a single startup call
+/// Alternative: aws-sdk-secretsmanager (rejected: build time and supply-chain surface)
+#[cfg(feature = "aws-secrets")]
+mod aws_sigv4 {
+    use super::AwsCredentials;
+    use chrono::{DateTime, Utc};
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+
+    fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(data.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    pub(super) fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+        let date_key = hmac(format!("AWS4{secret}").as_bytes(), date);
+        let region_key = hmac(&date_key, region);
+        let service_key = hmac(&region_key, service);
+        hmac(&service_key, "aws4_request")
+    }
+
+    /// Headers to send with a POST to `/`, including Authorization
+    pub(super) fn sign_post(
+        credentials: &AwsCredentials,
+        region: &str,
+        service: &str,
+        host: &str,
+        extra_headers: &[(&str, &str)],
+        body: &str,
+        now: DateTime<Utc>,
+    ) -> Vec<(String, String)> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let mut headers: Vec<(String, String)> = extra_headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+            .chain([
+                ("host".to_string(), host.to_string()),
+                ("x-amz-date".to_string(), amz_date.clone()),
+            ])
+            .chain(
+                credentials
+                    .session_token
+                    .iter()
+                    .map(|token| ("x-amz-security-token".to_string(), token.clone())),
+            )
+            .collect();
+        headers.sort();
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/\n\n{canonical_headers}\n{signed_headers}\n{}",
+            hex::encode(Sha256::digest(body.as_bytes()))
+        );
+
+        let scope = format!("{date}/{region}/{service}/aws4_request");
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signature = hex::encode(hmac(
+            &signing_key(&credentials.secret_access_key, &date, region, service),
+            &string_to_sign,
+        ));
+
+        headers.push((
+            "authorization".to_string(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                credentials.access_key_id
+            ),
+        ));
+        // reqwest sets Host itself from the URL
+        headers.retain(|(name, _)| name != "host");
+        headers
+    }
+}
+
+/// The configured provider, backed by plain environment variables
+/// Lookups try the configured store first, so env vars keep working for local overrides
+pub struct Secrets {
+    providers: Vec<Box<dyn SecretProvider>>,
+}
+
+impl Secrets {
+    pub fn new(providers: Vec<Box<dyn SecretProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// Build the providers named by SECRET_PROVIDER: env (default), file, vault or aws
+    pub fn from_env() -> Result<Self> {
+        let selected = env::var("SECRET_PROVIDER").unwrap_or_else(|_| "env".to_string());
+        let primary: Box<dyn SecretProvider> = match selected.trim().to_lowercase().as_str() {
+            "" | "env" => return Ok(Self::new(vec![Box::new(EnvSecretProvider)])),
+            "file" => Box::new(FileSecretProvider::new(
+                env::var("SECRETS_DIR").unwrap_or_else(|_| "/run/secrets".to_string()),
+            )),
+            #[cfg(feature = "vault")]
+            "vault" => Box::new(VaultSecretProvider::fetch(
+                &required_env("VAULT_ADDR")?,
+                &required_env("VAULT_TOKEN")?,
+                &env::var("VAULT_KV_MOUNT").unwrap_or_else(|_| "secret".to_string()),
+                &env::var("VAULT_SECRET_PATH").unwrap_or_else(|_| "spiral-core".to_string()),
+            )?),
+            #[cfg(feature = "aws-secrets")]
+            "aws" => Box::new(AwsSecretsManagerProvider::fetch(
+                &required_env("AWS_REGION")?,
+                &required_env("AWS_SECRET_ID")?,
+                AwsCredentials {
+                    access_key_id: required_env("AWS_ACCESS_KEY_ID")?,
+                    secret_access_key: required_env("AWS_SECRET_ACCESS_KEY")?,
+                    session_token: env::var("AWS_SESSION_TOKEN").ok(),
+                },
+                env::var("AWS_ENDPOINT_URL").ok().as_deref(),
+            )?),
+            other => {
+                return Err(SpiralError::ConfigurationError(format!(
+                    "SECRET_PROVIDER '{other}' is not available (expected env, file{}{})",
+                    if cfg!(feature = "vault") {
+                        ", vault"
+                    } else {
+                        ""
+                    },
+                    if cfg!(feature = "aws-secrets") {
+                        ", aws"
+                    } else {
+                        ""
+                    },
+                )))
+            }
+        };
+        tracing::info!("Loading secrets from the {} provider", primary.name());
+        Ok(Self::new(vec![primary, Box::new(EnvSecretProvider)]))
+    }
+
+    /// The secret and the name of the provider that held it
+    pub fn lookup(&self, key: &str) -> Result<Option<(String, &'static str)>> {
+        for provider in &self.providers {
+            if let Some(value) = provider.get(key)? {
+                return Ok(Some((value, provider.name())));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(any(feature = "vault", feature = "aws-secrets"))]
+fn required_env(key: &str) -> Result<String> {
+    env::var(key)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| SpiralError::ConfigurationError(format!("{key} must be set")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedProvider(&'static str, &'static str);
+
+    impl SecretProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn get(&self, key: &str) -> Result<Option<String>> {
+            Ok((key == self.0).then(|| self.1.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_file_provider_reads_one_file_per_secret() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("DISCORD_TOKEN"), "token-value\n").unwrap();
+        std::fs::write(dir.path().join("API_KEY"), "\n").unwrap();
+        let provider = FileSecretProvider::new(dir.path());
+
+        assert_eq!(
+            provider.get("DISCORD_TOKEN").unwrap().as_deref(),
+            Some("token-value")
+        );
+        assert_eq!(provider.get("API_KEY").unwrap(), None);
+        assert_eq!(provider.get("MISSING").unwrap(), None);
+        assert!(provider.get("../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_lookup_uses_the_first_provider_holding_the_secret() {
+        let secrets = Secrets::new(vec![
+            Box::new(FixedProvider("API_KEY", "from-store")),
+            Box::new(FixedProvider("DISCORD_TOKEN", "from-fallback")),
+        ]);
+
+        assert_eq!(
+            secrets.lookup("API_KEY").unwrap(),
+            Some(("from-store".to_string(), "fixed"))
+        );
+        assert_eq!(
+            secrets.lookup("DISCORD_TOKEN").unwrap().unwrap().0,
+            "from-fallback"
+        );
+        assert_eq!(secrets.lookup("OTHER").unwrap(), None);
+    }
+
+    #[cfg(feature = "aws-secrets")]
+    #[test]
+    fn test_sigv4_signing_key_matches_aws_example() {
+        // Example from the AWS "derive a signing key" documentation
+        let key = aws_sigv4::signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+}
@@ -15,6 +15,56 @@ pub const ANTHROPIC_API_VERSION: &str = "2023-06-01";
 /// Calculation: ~10 polls/second = reasonable for human-perceived responsiveness
 pub const TASK_POLL_INTERVAL_MS: u64 = 100;
 
+/// 📅 SCHEDULER TICK INTERVAL: How often due scheduled tasks are enqueued
+/// Why: Cron has minute granularity, so a 5s tick keeps runs within seconds of their slot
+/// Alternative: 60s (rejected: runs could drift almost a full minute late)
+pub const SCHEDULER_POLL_INTERVAL_SECS: u64 = 5;
+
+/// 📅 SCHEDULE RETRY: Seconds before a one-shot schedule whose task could not be submitted
+/// (queue full, budget spent) is tried again
+/// Why: Long enough for the queue to drain a little, short next to typical delays
+pub const SCHEDULE_RETRY_DELAY_SECS: i64 = 60;
+
+/// 📅 SCHEDULE RETRY: Submissions a one-shot schedule gets before it is dropped
+/// Why: A task that can never be submitted (its agent was removed) must not retry forever;
+/// ten minutes of retries outlast a full queue
+pub const SCHEDULE_MAX_SUBMIT_ATTEMPTS: u32 = 10;
+
+/// 📅 SCHEDULE LIMIT: Schedules kept at once unless MAX_SCHEDULES says otherwise
+/// Why: Every schedule is held in memory and rewritten to the store on each change
+pub const DEFAULT_MAX_SCHEDULES: usize = 1_000;
+
+/// 📡 RESULT BROADCAST CAPACITY: Results buffered per subscriber before it lags
+/// Why: Results are small and infrequent, 256 covers a full burst of parallel completions
+/// Alternative: Unbounded (rejected: a stalled subscriber would grow memory without limit)
+pub const RESULT_BROADCAST_CAPACITY: usize = 256;
+
+/// 📡 TASK UPDATE BROADCAST CAPACITY: Lifecycle and progress updates buffered per subscriber
+/// Why: Agents report progress several times per task, so updates outnumber results ~4:1
+/// Alternative: RESULT_BROADCAST_CAPACITY (rejected: a burst of parallel tasks would lag streams)
+pub const TASK_UPDATE_BROADCAST_CAPACITY: usize = 1024;
+
+/// 💓 SSE HEARTBEAT INTERVAL: Keep-alive comment sent on idle task streams
+/// Why: Proxies commonly drop idle connections after 30-60s; 15s stays well inside that
+/// Alternative: No heartbeat (rejected: long Claude runs emit nothing for minutes)
+pub const SSE_HEARTBEAT_SECS: u64 = 15;
+
+/// 🚰 DRAIN TIMEOUT: Maximum wait for in-flight tasks before maintenance proceeds
+/// Why: Twice the default 300s Claude Code timeout, so a running task can always finish
+/// Alternative: Unbounded wait (rejected: one hung task would block self-updates forever)
+pub const DRAIN_TIMEOUT_SECS: u64 = 600;
+
+/// ♻️ SHUTDOWN GRACE PERIOD: Wait for in-flight tasks before checkpointing them on shutdown
+/// Why: Short tasks finish cleanly, long ones are checkpointed and resumed after restart
+/// Alternative: DRAIN_TIMEOUT_SECS (rejected: process supervisors kill after ~30-90s anyway)
+pub const SHUTDOWN_GRACE_SECS: u64 = 30;
+
+/// ✋ SELF-UPDATE APPROVAL TIMEOUT: How long a self-update plan waits for a decision
+/// Why: Long enough to read a plan between other work; after that its buttons expire so a
+/// stale plan cannot be approved against a codebase that has moved on
+/// Alternative: No expiry (rejected: the update holds the system lock while it waits)
+pub const SELF_UPDATE_APPROVAL_TIMEOUT_SECS: u64 = 600;
+
 /// 📊 DEFAULT TIME ESTIMATE: Conservative baseline for task complexity estimation
 /// Why: 30min baseline balances underestimation risk with user expectations
 /// Research: Most coding tasks fall in 15-60min range, 30min is safe middle ground
@@ -28,6 +78,91 @@ pub const DEFAULT_TIME_ESTIMATE_MINUTES: u32 = 30;
 /// Alternative: 10K (rejected: potential OOM), 100 (rejected: too restrictive)
 pub const MAX_QUEUE_SIZE: usize = 1000;
 
+/// 🎟️ QUEUE RESERVED PERCENT: Share of MAX_QUEUE_SIZE kept for High and Critical priority
+/// tasks and admin submissions
+/// Why: 10% (100 slots) is enough for urgent work to get in while routine submissions
+/// have filled the rest, without idling much capacity
+/// Alternative: 0 (rejected: urgent tasks are refused like any other at a full queue)
+pub const QUEUE_RESERVED_PERCENT: u32 = 10;
+
+/// 📦 MAX BATCH SIZE: Upper bound on tasks in a single batch submission
+/// Why: One request should not be able to claim a tenth of the queue at once
+/// Alternative: Only the queue capacity check (rejected: a single batch could fill the queue)
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// 🔗 WORKFLOW CONTEXT LIMIT: Bytes of a step's output passed to the next step
+/// Why: Claude requests reject context values over MAX_CONTEXT_VALUE_LENGTH, so a larger
+/// handoff would fail every step after a long output
+/// Alternative: Full output (rejected: long code dumps would dominate the next agent's prompt)
+pub const WORKFLOW_CONTEXT_MAX_BYTES: usize = crate::validation::MAX_CONTEXT_VALUE_LENGTH;
+
+/// ⏱️ WORKFLOW STEP TIMEOUT: Longest a workflow waits for one step's result
+/// Why: One hour covers queue wait, the 300s Claude timeout and every retry backoff
+/// Alternative: No timeout (rejected: a task that never reports would leave the run running forever)
+pub const WORKFLOW_STEP_TIMEOUT_SECS: u64 = 3600;
+
+/// 🔀 DELEGATION DEPTH LIMIT: Generations of follow-up tasks one submission can spawn
+/// Why: 3 covers plan → implement → test while stopping agents that delegate in a cycle
+/// Alternative: Cycle detection by agent type (rejected: legitimate chains revisit agents)
+pub const MAX_DELEGATION_DEPTH: usize = 3;
+
+/// 🔀 DELEGATION FAN-OUT LIMIT: Follow-up tasks a single task may submit
+/// Why: With the depth limit this caps one submission at 3 + 9 + 27 = 39 delegated tasks
+/// Alternative: No limit (rejected: one confused agent could fill the queue)
+pub const MAX_DELEGATIONS_PER_TASK: usize = 3;
+
+/// 🧪 QA TEST SUITE TIMEOUT: Longest the QA agent lets a project's test suite run
+/// Why: 15min fits a cold cargo build plus tests for a mid-sized workspace
+/// Alternative: The 300s Claude timeout (rejected: first builds alone can exceed it)
+pub const QA_TEST_TIMEOUT_SECS: u64 = 900;
+
+/// 🔍 QA FAILURE EXCERPT: Characters of test output sent to Claude and kept in the report
+/// Why: The tail of the output holds the failure summaries, 6K chars covers dozens of them
+/// Alternative: Full output (rejected: build logs would crowd out the failures in the prompt)
+pub const QA_OUTPUT_EXCERPT_CHARS: usize = 6000;
+
+/// 🧠 AGENT MEMORY RECALL: Memories considered when building a task's context
+/// Why: 20 short entries roughly fill the context value budget they are rendered into
+/// Alternative: All memories in scope (rejected: the oldest never fit and cost a full scan)
+pub const AGENT_MEMORY_RECALL_LIMIT: usize = 20;
+
+/// 🧠 AGENT MEMORY LISTING: Most memories one GET /agents/{agent_type}/memory returns
+/// Why: Each entry is up to 1000 bytes; an uncapped limit turns one request into a full
+/// table scan serialized into a single response
+pub const AGENT_MEMORY_MAX_LIST_LIMIT: usize = 100;
+
+/// 📝 AGENT MEMORY SUMMARY LENGTH: Characters kept from a finished task
+/// Why: 200 chars names the request and outcome, so several summaries share one context value
+/// Alternative: Full output (rejected: a single code dump would crowd out everything else)
+pub const AGENT_MEMORY_SUMMARY_CHARS: usize = 200;
+
+/// 🗄️ AGENT MEMORY RETENTION: Task summaries kept per agent and scope
+/// Why: Only the newest summaries are recalled, so older ones just grow the database
+/// Alternative: Time-based expiry (rejected: a quiet project would forget everything)
+pub const AGENT_MEMORY_SUMMARIES_PER_SCOPE: usize = 50;
+
+/// 🔐 REMEMBER-ME IDLE TIMEOUT: Days a remember-me session may go unused
+/// Why: A week covers a client used on working days only without keeping abandoned
+/// sessions open for long
+/// Alternative: No idle timeout (rejected: a leaked session ID would stay usable for a month)
+pub const SESSION_REMEMBER_ME_IDLE_DAYS: i64 = 7;
+
+/// 🔐 REMEMBER-ME LIFETIME: Days a remember-me session lives at most, however busy
+/// Why: Forces a fresh session, and a fresh look at the client, once a month
+/// Alternative: Unlimited (rejected: sessions would never rotate)
+pub const SESSION_REMEMBER_ME_LIFETIME_DAYS: i64 = 30;
+
+/// 💬 SESSION HISTORY ENTRY LENGTH: Characters kept of one request or answer
+/// Why: 300 chars keeps what was asked and done, so several turns share one context value
+/// Alternative: Full text (rejected: one pasted file would push every other turn out)
+pub const SESSION_HISTORY_ENTRY_CHARS: usize = 300;
+
+/// 🗄️ SESSION HISTORY SIZE: Bytes of transcript kept per session, oldest turns dropped first
+/// Why: Only the newest few turns fit a task's context, and the session is rewritten on
+/// every task, so a long transcript only slows the store
+/// Alternative: A fixed number of turns (rejected: turn lengths vary tenfold)
+pub const SESSION_HISTORY_MAX_BYTES: usize = 8 * 1024;
+
 /// 📚 MAX STORED TASKS: Historical data retention vs memory usage balance
 /// Why: 10K tasks provides good audit trail without memory pressure
 /// Retention: ~1 week of high activity (10K tasks ÷ 24 hours ÷ 60 minutes = ~7 tasks/min)
@@ -40,6 +175,133 @@ pub const MAX_STORED_TASKS: usize = 10000;
 /// Alternative: Same as tasks (rejected: results less critical), 1K (rejected: too limited)
 pub const MAX_STORED_RESULTS: usize = 5000;
 
+/// 📄 RESULT FILE LIMIT: Bytes of one generated file returned by GET /tasks/{id}/result
+/// Why: 256KB covers any reasonable source file while keeping one response bounded
+/// Alternative: Whole files (rejected: a generated lockfile or bundle could be megabytes)
+pub const RESULT_FILE_MAX_BYTES: usize = 256 * 1024;
+
+/// 📦 RESULT CONTENT BUDGET: Total file bytes returned in one result response
+/// Why: 2MB fits a typical multi-file project; later files are listed without content
+/// Alternative: Per-file limit only (rejected: hundreds of files would still be unbounded)
+pub const RESULT_FILES_TOTAL_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+/// 📝 SNAPSHOT FILE LIMIT: Largest file whose content is kept to diff a task's changes
+/// Why: Source files are far smaller; larger ones (bundles, data) are compared by size
+/// and modification time and reported as changed without a diff
+pub const CHANGE_SNAPSHOT_MAX_FILE_BYTES: u64 = 256 * 1024;
+
+/// 📝 CHANGE CONTENT LIMIT: Bytes of one created file or diff kept in a task result
+/// Why: Task results are stored and sent to webhooks; 32KB shows any reviewable change
+pub const CHANGE_CONTENT_MAX_BYTES: usize = 32 * 1024;
+
+/// 📝 CHANGE BUDGET: Total content bytes of all changes kept in one task result
+/// Why: Bounds stored results; later changes keep their path and line counts only
+pub const CHANGES_TOTAL_MAX_BYTES: usize = 512 * 1024;
+
+/// 📏 REQUEST BODY LIMIT: Largest request body the API reads, in bytes
+/// Why: A maximal task (10K chars of content plus context) is well under 64KB, so
+/// anything bigger is a mistake or abuse and is refused before it is buffered
+/// Alternative: axum's 2MB extractor default (rejected: 30x more memory per request
+/// than any valid task needs)
+pub const API_DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// 📏 BATCH BODY LIMIT: Largest POST /tasks/batch body, in bytes
+/// Why: MAX_BATCH_SIZE tasks of typical size fit in 2MB
+pub const API_DEFAULT_MAX_BATCH_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// 🔁 IDEMPOTENCY WINDOW: How long an Idempotency-Key on POST /tasks replays its task
+/// Why: 24 hours outlasts any client or Discord retry loop while keeping the cache small
+/// Alternative: 1 hour (rejected: a client retrying after an overnight outage would duplicate)
+pub const IDEMPOTENCY_DEFAULT_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// 🔁 IDEMPOTENCY CAPACITY: Keys remembered at once; the oldest are dropped past this
+/// Why: 10K is far above the 10/min task creation limit over a day, so only abuse hits it
+pub const IDEMPOTENCY_MAX_KEYS: usize = 10_000;
+
+/// 🔁 IDEMPOTENCY KEY LENGTH: Longest key accepted, in bytes
+/// Why: Fits UUIDs and prefixed IDs with room to spare; longer keys are rejected as abuse
+pub const IDEMPOTENCY_KEY_MAX_LEN: usize = 255;
+
+/// 🔑 API KEY NAME LENGTH: Longest name accepted for a managed API key, in characters
+/// Why: Names identify keys in listings and logs; 64 fits "ci-deploy-staging-eu" style names
+pub const API_KEY_NAME_MAX_CHARS: usize = 64;
+
+/// 📜 AUDIT RETENTION: Days of audit log files kept before they are deleted
+/// Why: 90 days covers a quarterly access review and most incident investigations
+/// Alternative: Keep forever (rejected: unbounded disk use; set 0 to opt in)
+pub const AUDIT_DEFAULT_RETENTION_DAYS: u32 = 90;
+
+/// 📝 TRANSCRIPT RETENTION: Days a task's Claude transcript is kept after its last write
+/// Why: Transcripts are for debugging recent tasks and are far larger than audit events;
+/// 30 days covers a release cycle without letting full prompts pile up
+pub const TRANSCRIPT_DEFAULT_RETENTION_DAYS: u32 = 30;
+
+/// 📝 PROMPT TEMPLATE RELOAD: Seconds between checks of the templates directory for edits
+/// Why: Short enough that a tuned prompt is live by the next task, and one directory
+/// listing per interval is negligible next to a Claude run
+pub const PROMPT_TEMPLATE_RELOAD_SECS: u64 = 5;
+
+/// 🩺 DOCTOR CHECK TIMEOUT: Seconds `spiral-core doctor` waits for Discord to answer
+/// Why: A hung request should fail the check, not the whole report; Discord normally
+/// answers within a second
+pub const DOCTOR_CHECK_TIMEOUT_SECS: u64 = 10;
+
+/// 🔄 CONFIG RELOAD DEBOUNCE: Milliseconds to let edits to the configuration file settle
+/// Why: Editors save in several writes (truncate, write, rename); reloading after the last
+/// one avoids rejecting a half-written file
+pub const CONFIG_RELOAD_DEBOUNCE_MS: u64 = 500;
+
+/// 📜 AUDIT RATE-LIMIT WINDOW: A throttled client is audited at most once per window
+/// Why: One record per minute shows an attack's shape without one line per rejected request
+pub const AUDIT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+/// 📜 AUDIT RATE-LIMIT TRACKING: Clients remembered before stale entries are swept
+pub const AUDIT_RATE_LIMIT_MAX_CLIENTS: usize = 10_000;
+
+/// 📜 AUDIT QUERY SIZE: Events returned by GET /admin/audit by default and at most
+pub const AUDIT_QUERY_DEFAULT_LIMIT: usize = 100;
+pub const AUDIT_QUERY_MAX_LIMIT: usize = 1000;
+
+/// 🔐 SECRET FETCH TIMEOUT: How long startup waits for Vault or AWS Secrets Manager
+/// Why: Secrets are fetched once before anything else starts; an unreachable store should
+/// fail startup quickly rather than hang it
+pub const SECRET_FETCH_TIMEOUT_SECS: u64 = 10;
+
+/// 🔔 WEBHOOK ATTEMPTS: Deliveries tried per task callback before giving up
+/// Why: 5 attempts with doubling backoff spans about 15 seconds plus request timeouts,
+/// enough to ride out a receiver restart
+/// Alternative: Retry for hours (rejected: GET /tasks/{id}/result covers long outages)
+pub const WEBHOOK_DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// 🔔 WEBHOOK BACKOFF: Delay before the first redelivery, doubling after each failure
+pub const WEBHOOK_INITIAL_BACKOFF_MS: u64 = 1_000;
+
+/// 🔔 WEBHOOK BACKOFF CAP: Longest wait between two delivery attempts
+pub const WEBHOOK_MAX_BACKOFF_SECS: u64 = 60;
+
+/// 🔔 WEBHOOK TIMEOUT: How long one delivery attempt may take
+/// Why: Receivers should acknowledge and process asynchronously; a slow one is retried
+pub const WEBHOOK_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// 🔔 WEBHOOK SUMMARY LENGTH: Characters of task output included in a callback payload
+/// Why: Enough to show what was produced; the full result is one GET away
+pub const WEBHOOK_SUMMARY_CHARS: usize = 2000;
+
+/// 📜 AGENT HISTORY PAGE: Tasks returned by GET /agents/{type}/tasks when no limit is given
+/// Why: 50 fills an agent view without scrolling through the whole retained history
+pub const AGENT_TASK_HISTORY_DEFAULT_LIMIT: usize = 50;
+
+/// 📜 AGENT HISTORY LIMIT: Most tasks one history request may return
+/// Why: Each record looks up its events and result, so 500 bounds the lock traffic
+/// of a single request
+pub const AGENT_TASK_HISTORY_MAX_LIMIT: usize = 500;
+
+/// 🗂️ WORKSPACE LISTING LIMIT: Files returned by GET /workspaces/{id}/files
+/// Why: 1000 entries covers a generated project; node_modules-sized trees are cut
+/// off with `truncated` set instead of producing a multi-megabyte response
+/// Alternative: Paginate (rejected: listings are for spot checks before deleting)
+pub const WORKSPACE_FILES_MAX_ENTRIES: usize = 1000;
+
 /// 🧹 CLEANUP INTERVAL: Memory management frequency vs overhead balance
 /// Why: 5min (300s) provides regular cleanup without constant overhead
 /// Impact: Cleanup runs ~288 times/day (acceptable CPU usage)
@@ -59,6 +321,114 @@ pub const DISCORD_MESSAGE_TRUNCATE_LENGTH: usize = 1000;
 /// Alternative: 4 chars (rejected: collision risk), 16 chars (rejected: too long for display)
 pub const DISCORD_TASK_ID_DISPLAY_LENGTH: usize = 8;
 
+/// 🧵 DISCORD THREAD NAME: Discord refuses thread names longer than 100 characters
+pub const DISCORD_THREAD_NAME_LENGTH: usize = 100;
+
+/// 🖼️ DISCORD EMBED LIMITS: Longest embed description and field value Discord accepts
+pub const DISCORD_EMBED_DESCRIPTION_LENGTH: usize = 4096;
+pub const DISCORD_EMBED_FIELD_LENGTH: usize = 1024;
+
+/// 📖 RESULT PAGE LENGTH: Most characters of a task answer shown on one embed page
+/// Why: Discord caps a whole embed at 6000 characters; this leaves room for the title,
+/// footer and the file list fields beside the page
+/// Alternative: The 4096 description limit (rejected: an embed with long file lists would
+/// be refused)
+pub const DISCORD_RESULT_PAGE_LENGTH: usize = 3000;
+
+/// 📖 RESULT PAGES: Most pages a task answer is split into
+/// Why: 25 pages is more than anyone turns through in Discord; longer output is better read
+/// from the workspace or the attached files
+pub const MAX_DISCORD_RESULT_PAGES: usize = 25;
+
+/// ✂️ BRIEF ANSWER LENGTH: Characters of an answer shown to users who prefer brief answers
+/// Why: About a screenful in Discord, enough for a summary without scrolling
+pub const DISCORD_BRIEF_ANSWER_LENGTH: usize = 800;
+
+/// 📖 PAGE IDLE TIMEOUT: Minutes without a page turn before a paged answer is forgotten
+/// Why: Answers are read soon after they arrive; keeping every page of every answer for
+/// the bot's lifetime would grow without bound
+pub const DISCORD_PAGE_IDLE_MINUTES: u64 = 30;
+
+/// 📜 TASK LIST SIZE: Recent tasks `!spiral tasks` lists for the caller
+/// Why: Ten one-line entries fit a Discord message with room to spare; older tasks can be
+/// looked up by ID
+pub const DISCORD_TASK_LIST_LIMIT: usize = 10;
+
+/// 📎 TASK RESULT ATTACHMENTS: Most files attached to a task result one by one
+/// Why: Discord takes at most 10 files per message; a task that produced more is sent
+/// as one archive of its workspace instead
+pub const MAX_TASK_RESULT_FILES: usize = 10;
+
+/// 📎 TASK RESULT ATTACHMENT SIZE: Most bytes of files attached to one task result
+/// Why: Stays under the 10 MiB upload limit of servers without boosts, so uploads are not
+/// refused; larger output is left in the workspace and the embed says so
+pub const MAX_TASK_RESULT_ATTACHMENT_BYTES: u64 = 8 * 1024 * 1024;
+
+/// 📝 COMPOSE DRAFT SIZE: Most messages `!spiral compose` collects into one request
+/// Why: Enough for a spec written as several paragraphs; longer material belongs in a file
+/// in the workspace
+pub const MAX_COMPOSE_MESSAGES: usize = 10;
+
+/// 📝 COMPOSE REQUEST LENGTH: Most characters of a composed request
+/// Why: Three full Discord messages; keeps the rendered prompt far below the model's
+/// context while the per-message limit still stops single floods
+pub const MAX_COMPOSE_REQUEST_LENGTH: usize = 12_000;
+
+/// 📝 COMPOSE IDLE TIMEOUT: Minutes without a new message before a draft is dropped
+/// Why: A forgotten draft would otherwise swallow the user's chat in that channel hours later
+pub const COMPOSE_IDLE_MINUTES: u64 = 30;
+
+/// 🧭 SETUP IDLE TIMEOUT: Minutes without an answer before a `!spiral setup` wizard is dropped
+/// Why: Answers come within minutes; an abandoned wizard would otherwise treat the admin's
+/// later chat in that channel as setup answers
+pub const SETUP_IDLE_MINUTES: u64 = 15;
+
+/// 🤔 CLARIFICATION CONFIDENCE: Intent confidence below which the bot asks before starting a task
+/// Why: Below this the classifier found no keyword at all; a guessed task wastes a Claude run
+pub const INTENT_CLARIFY_CONFIDENCE: f64 = 0.5;
+
+/// 🤔 CLARIFICATION TIMEOUT: Minutes a clarifying question waits for its buttons to be pressed
+/// Why: An old question answered much later would start a task its asker has moved on from
+pub const CLARIFICATION_IDLE_MINUTES: u64 = 10;
+
+/// 📅 DISCORD SCHEDULES PER USER: Recurring tasks one user may have registered
+/// Why: Each run is a full agent task with Claude Code spend; a handful covers nightly and
+/// weekly chores without letting one user fill the queue
+pub const MAX_DISCORD_SCHEDULES_PER_USER: usize = 5;
+
+/// 📅 DISCORD SCHEDULE INTERVAL: Shortest gap allowed between two runs of a schedule
+/// Why: Hourly is the most frequent recurring chore worth an agent task; anything tighter
+/// is usually a mistyped cron field such as `*` for the minute
+pub const DISCORD_SCHEDULE_MIN_INTERVAL_MINUTES: i64 = 60;
+
+/// 🎛️ TRACKED TASK MESSAGES: Progress messages whose reactions still control their task
+/// Why: Same reach as the task threads; reacting to an older message only needs
+/// `!spiral task <id>` and the API instead
+pub const MAX_TRACKED_TASK_MESSAGES: usize = 1000;
+
+/// 🧵 TRACKED TASK THREADS: Task threads whose follow-ups reach their agent unmentioned
+/// Why: Covers weeks of threads on a busy server; each entry is two integers, and a
+/// forgotten thread only needs its agent mentioned again
+pub const MAX_TRACKED_TASK_THREADS: usize = 1000;
+
+/// 🟢 PRESENCE INTERVAL: Default seconds between updates of the bot's work status
+/// Why: Elapsed time stays roughly current while staying far below Discord's gateway limit
+/// of 120 commands per minute per shard
+pub const DISCORD_PRESENCE_INTERVAL_SECS: u64 = 30;
+
+/// 🟢 PRESENCE MINIMUM INTERVAL: Shortest configurable gap between status updates
+/// Why: Discord clients refresh presences slowly; faster updates are invisible gateway load
+pub const DISCORD_PRESENCE_MIN_INTERVAL_SECS: u64 = 10;
+
+/// 📰 DIGEST CHECK INTERVAL: Seconds between checks for notification digests that are due
+/// Why: Digest intervals are whole minutes, so a digest is posted at most a minute late
+pub const DISCORD_DIGEST_CHECK_SECS: u64 = 60;
+
+/// 📰 DIGEST INTERVAL RANGE: Minutes a guild may batch notifications for
+/// Why: Under 5 minutes a digest barely reduces messages; over a day they are stale news
+pub const DISCORD_DIGEST_MIN_MINUTES: u64 = 5;
+pub const DISCORD_DIGEST_MAX_MINUTES: u64 = 1440;
+
 // 🔧 CODE PROCESSING CONFIGURATION
 /// 📝 CODE SNIPPET TRUNCATION: AI context limit vs processing accuracy balance
 /// Why: 500 chars captures most function signatures and key context
@@ -88,3 +458,297 @@ pub const DEFAULT_IMPLEMENTATION_APPROACH: &str =
 /// Why: "Standard requirements" avoids over/under-stating complexity
 /// Psychology: Neutral framing prevents panic or overconfidence
 pub const DEFAULT_IMPLEMENTATION_CHALLENGE: &str = "Standard implementation requirements";
+
+/// ✍️ SIGNATURE REPLAY WINDOW: How far a signed request's timestamp may be from server time
+/// Why: Five minutes tolerates ordinary clock drift; the seen-signature cache only needs
+/// to remember signatures for this long
+pub const SIGNATURE_DEFAULT_MAX_AGE_SECS: u64 = 300;
+
+/// 🌊 CLAUDE STREAM BUFFER: Events a streaming generation may run ahead of its consumer
+/// Why: Enough to absorb a burst of tool calls; beyond it the CLI's output is left unread
+/// (back-pressure) rather than buffered without bound
+pub const CLAUDE_STREAM_EVENT_BUFFER: usize = 64;
+
+/// 🔀 LLM HTTP TIMEOUT: Longest wait for an OpenAI or Ollama completion
+/// Why: Local models on modest hardware can take minutes for a full implementation; the
+/// task timeout above this still bounds the whole task
+pub const LLM_HTTP_TIMEOUT_SECS: u64 = 300;
+
+/// 💸 COST HISTORY: Days of Claude Code spend kept for the cost report
+/// Why: A month covers billing-period questions; older days are dropped so per-task and
+/// per-user totals cannot grow without bound
+pub const COST_HISTORY_DAYS: i64 = 30;
+
+/// 💸 COST REPORT SIZE: Tasks and users listed in a cost report, most expensive first
+pub const COST_REPORT_TOP_N: usize = 10;
+
+/// 🔄 SESSION KEY LENGTH: Longest session key a workspace may be named after
+/// Why: Keys become directory names; 128 stays far below filesystem name limits
+pub const MAX_SESSION_KEY_LENGTH: usize = 128;
+
+/// 🔄 SHARED SESSION KEY LENGTH: Longest session_id accepted from task context
+/// Why: Agents prefix it (pm-, qa-, thread-), and the result must still fit the limit above
+pub const MAX_SHARED_SESSION_KEY_LENGTH: usize = 64;
+
+/// 🗃️ RESPONSE CACHE SIZE: Claude Code responses kept for identical requests
+/// Why: Analyses are a few KiB each, so a few hundred cost little memory while covering
+/// the repeats of a busy day
+pub const CLAUDE_CACHE_DEFAULT_CAPACITY: usize = 256;
+
+/// 🗃️ RESPONSE CACHE TTL: Seconds a cached response is reused
+/// Why: An hour absorbs retries and duplicate submissions without serving advice that
+/// predates later changes to the codebase for long
+pub const CLAUDE_CACHE_DEFAULT_TTL_SECS: u64 = 3600;
+
+/// 🗃️ CACHEABLE GENERATION SIZE: Largest generation result, in bytes, worth caching
+/// Why: Small answers (snippets, plans) repeat; large implementations rarely do and would
+/// crowd the cache
+pub const CLAUDE_CACHE_MAX_GENERATION_BYTES: usize = 16 * 1024;
+
+/// 🛡️ SANDBOX IMAGE: Container image Claude Code runs in when sandboxing is on
+/// Why: A locally built image (see docs/OPERATIONS.md) so nothing is pulled implicitly
+pub const CLAUDE_SANDBOX_DEFAULT_IMAGE: &str = "spiral-claude-sandbox:latest";
+
+/// 🛡️ SANDBOX CPUS: CPU share of one sandboxed run
+/// Why: Enough for a cargo build; several concurrent runs still leave the host responsive
+pub const CLAUDE_SANDBOX_DEFAULT_CPUS: f64 = 2.0;
+
+/// 🛡️ SANDBOX MEMORY: Memory limit of one sandboxed run, in MiB
+/// Why: Node plus a Rust or TypeScript toolchain fit in 4 GiB; a runaway build is killed
+/// instead of swapping the host
+pub const CLAUDE_SANDBOX_DEFAULT_MEMORY_MB: u64 = 4096;
+
+/// 🛡️ SANDBOX PIDS: Most processes one sandboxed run may have
+/// Why: Far above what a build spawns, far below what a fork bomb needs
+pub const CLAUDE_SANDBOX_DEFAULT_PIDS_LIMIT: u32 = 512;
+
+/// 🚦 CLAUDE INVOCATION LIMIT: Claude Code processes allowed to run at once
+/// Why: Each run is a Node process plus whatever builds it starts; four keep a
+/// small server responsive while agents of different types still run side by side
+pub const CLAUDE_DEFAULT_MAX_CONCURRENT_INVOCATIONS: usize = 4;
+
+/// 🔖 CLAUDE CLI MINIMUM: Oldest Claude Code CLI Spiral Core runs with
+/// Why: 1.0 is the first release whose JSON result carries `total_cost_usd` and which
+/// accepts `--permission-mode`; older ones fail every task with a parse error
+pub const CLAUDE_CLI_MIN_VERSION: crate::claude_code::version::CliVersion =
+    crate::claude_code::version::CliVersion::new(1, 0, 0);
+
+/// 🔖 CLAUDE CLI STREAM-JSON: Oldest CLI whose stream-json events we parse
+/// Why: Earlier releases emit tool calls in a different shape, so progress would be lost
+pub const CLAUDE_CLI_STREAM_JSON_VERSION: crate::claude_code::version::CliVersion =
+    crate::claude_code::version::CliVersion::new(1, 0, 10);
+
+/// 🔖 CLAUDE CLI MCP CONFIG: Oldest CLI taking `--mcp-config` with `mcp__<server>` tool approvals
+pub const CLAUDE_CLI_MCP_CONFIG_VERSION: crate::claude_code::version::CliVersion =
+    crate::claude_code::version::CliVersion::new(1, 0, 24);
+
+/// 🔖 CLAUDE CLI TESTED MAJOR: Newest major release Spiral Core was tested with
+/// Why: A new major version may change flags; it is allowed but logged as untested
+pub const CLAUDE_CLI_TESTED_MAJOR: u32 = 1;
+
+/// 🔖 CLAUDE CLI VERSION TIMEOUT: Seconds `claude --version` may take at startup
+/// Why: A sandboxed check starts a container; anything slower means a hung CLI
+pub const CLAUDE_CLI_VERSION_TIMEOUT_SECS: u64 = 30;
+
+/// 📦 WORKSPACE QUOTA POLL: How often a running task's workspace size is checked against its quota
+/// Why: 2s bounds the overshoot to what a build writes in two seconds while keeping the
+/// directory walk negligible next to the run itself
+pub const WORKSPACE_QUOTA_POLL_MS: u64 = 2000;
+
+/// 📦 WORKSPACE QUOTA WARNING: Share of the workspace quota at which the run is warned about and the next prompt
+/// tells Claude to clean up
+pub const WORKSPACE_QUOTA_WARN_PERCENT: u64 = 80;
+
+/// 📦 PARTIAL RESULT FILES: Most files listed in the partial result of a run stopped by its quota
+/// Why: A runaway build can leave thousands of files; the first ones are enough to see
+/// what was produced and the rest stay on disk
+pub const WORKSPACE_QUOTA_MAX_LISTED_FILES: usize = 50;
+
+/// 🧱 WORKSPACE TEMPLATE CLONE TIMEOUT: Seconds a git workspace template may take to clone
+/// Why: A shallow clone of a scaffold takes seconds; an unreachable host must not hold
+/// the task's invocation slot until the run timeout
+pub const WORKSPACE_TEMPLATE_CLONE_TIMEOUT_SECS: u64 = 120;
+
+/// 🧱 WORKSPACE TEMPLATE SIZE: Most bytes of files a workspace template may seed
+/// Why: Templates are read into memory to be checksummed; a scaffold is far smaller, and
+/// anything bigger is a mistaken path or repository
+pub const WORKSPACE_TEMPLATE_MAX_BYTES: u64 = 20 * 1024 * 1024;
+
+/// 💬 USER RATE LIMIT: Messages a user may send the bot per minute in servers, by default
+/// Why: Enough for a conversation with an agent; a script posting faster is refused
+pub const DISCORD_USER_MESSAGES_PER_MINUTE: u32 = 5;
+
+/// 💬 CHANNEL RATE LIMIT: Messages to the bot a channel takes per minute, by default
+/// Why: Several users together can keep the agents busier than any one of them; a busy
+/// channel can still send a burst of DISCORD_CHANNEL_MESSAGE_BURST
+pub const DISCORD_CHANNEL_MESSAGES_PER_MINUTE: u32 = 30;
+
+/// 💬 CHANNEL BURST: Messages to the bot a quiet channel may send at once
+pub const DISCORD_CHANNEL_MESSAGE_BURST: u32 = 10;
+
+/// ✉️ DM RATE LIMIT: Direct messages a user may send the bot per minute
+/// Why: A DM has no moderators or server settings to rein in abuse, so it gets a tighter
+/// limit than the 5 per minute allowed in servers
+pub const DM_RATE_LIMIT_PER_MINUTE: usize = 3;
+
+/// ✉️ DM SESSION IDLE: Hours a private DM session lasts after its last message
+/// Why: Long enough to come back to a task after a break; after that a DM starts a fresh
+/// Claude conversation rather than resuming a stale one
+pub const DM_SESSION_IDLE_HOURS: i64 = 2;
+
+/// 🗄️ REDIS SESSION GRACE: Seconds a session stays in Redis after it expires
+/// Why: Redis deletes the key itself; the grace lets validation still see the session and
+/// report it expired rather than unknown, and cleanup_expired tidies the rest
+pub const SESSION_REDIS_EXPIRY_GRACE_SECS: i64 = 3600;
+
+/// 🗄️ REDIS TIMEOUT: Seconds to wait for the Redis server to connect or answer
+/// Why: A session lookup or rate limit check sits in front of every DM and API request; a
+/// hung server must fail it, not stall it
+pub const REDIS_TIMEOUT_SECS: u64 = 5;
+
+/// 🚦 RATE LIMIT KEYS: Keys the in-memory rate limiter store holds before sweeping idle ones
+/// Why: Every Discord user is a key; sweeping only past this bound keeps the common case
+/// to a single lookup
+pub const RATE_LIMIT_MEMORY_MAX_KEYS: usize = 10_000;
+
+/// 🎟️ TRUSTED TIER: How many times the normal rate limits a trusted user or key gets
+/// Why: Enough for a CI pipeline or a power user's bursts, while a runaway script is
+/// still stopped; unlimited is there for the clients that need no limit at all
+pub const RATE_LIMIT_TRUSTED_MULTIPLIER: u32 = 5;
+
+/// 🔑 FAILED AUTHENTICATION LIMIT: Requests with a missing or wrong API key an address may
+/// make per minute before all its requests are refused
+/// Why: Key guessing is refused by auth before the per-key limits count it; this bounds it
+/// per address, while a client with a valid key never spends from it
+pub const API_AUTH_FAILURES_PER_MINUTE: u32 = 10;
+
+/// 🩺 ADAPTIVE RATE LIMITS: Percent of the task submission limits kept while degraded
+/// Why: A degraded system still finishes tasks, only slower; halving intake keeps the
+/// queue from growing faster than it drains
+pub const ADAPTIVE_DEGRADED_PERCENT: u32 = 50;
+
+/// 🩺 ADAPTIVE RATE LIMITS: Percent kept while unhealthy, critical, or with Claude's
+/// circuit open
+/// Why: Tasks queued now can't run until recovery; a trickle still lets urgent work in
+pub const ADAPTIVE_CRITICAL_PERCENT: u32 = 20;
+
+/// 🩺 ADAPTIVE RATE LIMITS: Seconds between checks of health and the circuit breaker
+/// Why: Health is collected every 30 seconds by default; checking more often catches a
+/// circuit opening without waiting for the next collection
+pub const ADAPTIVE_RATE_LIMIT_CHECK_SECS: u64 = 10;
+
+/// 📣 SESSION EVENTS: Lifecycle events a session listener may fall behind by
+/// Why: Listeners only cancel tasks and count sessions, so they keep up easily; the room
+/// is for a burst of expiries from one cleanup pass
+pub const SESSION_EVENT_CAPACITY: usize = 256;
+
+/// 🧹 SESSION CLEANUP INTERVAL: Seconds between the session janitor's runs
+/// Why: Matches the orchestrator's cleanup loop, so a session's workspace is archived at
+/// most one more interval after the janitor announces its expiry
+pub const SESSION_CLEANUP_INTERVAL_SECS: u64 = 300;
+
+/// 🚨 ALERT NOTIFY TIMEOUT: Seconds one notifier may take to deliver an alert
+/// Why: Alerts are sent from the monitoring loop; an unreachable mail server or webhook
+/// must not hold up the next metrics collection
+pub const ALERT_NOTIFY_TIMEOUT_SECS: u64 = 10;
+
+/// 🚨 ALERT CIRCUIT OPEN: Seconds a circuit breaker stays open before the default rule fires
+/// Why: A breaker opening briefly and recovering on its own is routine; five minutes open
+/// means the backend is down, not flaky
+pub const ALERT_CIRCUIT_OPEN_SECS: u64 = 300;
+
+/// 🚨 ALERT QUEUE PERCENT: How full the task queue gets before the default rule fires
+/// Why: At 80% there is still room to react before submissions start being refused
+pub const ALERT_QUEUE_FULL_PERCENT: f64 = 80.0;
+
+/// 📉 METRICS RAW RETENTION: Seconds every collected snapshot is kept in the history file
+/// Why: A day of 30-second samples answers "what happened overnight" at full detail
+/// while staying a few thousand rows
+pub const METRICS_RAW_RETENTION_SECS: u64 = 24 * 60 * 60;
+
+/// 📉 METRICS ROLLUP WINDOW: Seconds of history each rolled-up snapshot stands for
+/// Why: Five minutes keeps a month of history to under 9,000 rows and matches the
+/// shortest range dashboards usually zoom out to
+pub const METRICS_ROLLUP_SECS: u64 = 300;
+
+/// 📉 METRICS ROLLUP RETENTION: Seconds rolled-up snapshots are kept
+/// Why: A month covers week-over-week comparisons and the gap since the last release
+pub const METRICS_ROLLUP_RETENTION_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// 🎯 SLO WINDOW: Seconds of finished tasks that latency percentiles and success rates cover
+/// Why: An hour reflects the current state of the backend while holding enough tasks for
+/// a meaningful p95
+pub const SLO_WINDOW_SECS: u64 = 60 * 60;
+
+/// 🎯 SLO MIN SAMPLES: Tasks an agent type must finish in the window before it is judged
+/// Why: With fewer tasks one slow or failed task decides the percentile on its own
+pub const SLO_MIN_SAMPLES: usize = 5;
+
+/// 🎯 SLO MAX SAMPLES: Finished tasks kept per agent type for percentiles
+/// Why: Bounds memory and sorting cost; far more tasks than an agent finishes in an hour
+pub const SLO_MAX_SAMPLES_PER_AGENT: usize = 2000;
+
+/// 🎯 SLO DEFAULT P95: Seconds the default objective allows for 95% of tasks
+/// Why: Most agent tasks finish within minutes; ten minutes at p95 means the backend is
+/// struggling, not that a task was large
+pub const SLO_DEFAULT_P95_SECS: f64 = 600.0;
+
+/// 🎯 SLO DEFAULT SUCCESS RATE: Fraction of tasks the default objective expects to succeed
+/// Why: Occasional failures from bad requests are normal; more than one in ten points at
+/// the agents or their backend
+pub const SLO_DEFAULT_SUCCESS_RATE: f64 = 0.9;
+
+/// 📊 DEFAULT LOG FILTER: Levels used when LOG_LEVEL is unset
+/// Why: Debug detail for this crate, while serenity, hyper and rustls stay at info instead
+/// of logging every frame
+pub const DEFAULT_LOG_FILTER: &str = "info,spiral_core=debug";
+
+/// 📁 LOG FILE PREFIX: Name of the main log files, dated after it
+pub const LOG_FILE_PREFIX: &str = "spiral-core";
+
+/// 📁 SECURITY LOG FILE PREFIX: Name of the files holding only security events
+pub const SECURITY_LOG_FILE_PREFIX: &str = "security-events";
+
+/// 🛡️ SECURITY EVENTS TARGET: Tracing target of security events, routed to their own file
+pub const SECURITY_EVENTS_TARGET: &str = "security_events";
+
+/// 📁 LOG MAX FILE MB: Size at which size-based rotation starts a new log file
+/// Why: Small enough to open in an editor or attach to an issue, large enough that a busy
+/// day is a handful of files
+pub const LOG_DEFAULT_MAX_FILE_MB: u64 = 100;
+
+/// 📁 LOG RETENTION DAYS: Days log files are kept by default
+/// Why: Two weeks covers looking back past a weekend or a release; the audit log, which
+/// matters for longer, has its own retention
+pub const LOG_DEFAULT_RETENTION_DAYS: u32 = 14;
+
+/// 💥 CRASH MAX RESTARTS: Restarts a crashed subsystem gets within CRASH_RESTART_WINDOW_SECS
+/// Why: A one-off panic is worth riding out, but a subsystem that panics on every start
+/// only floods the ops channel; after this many it is left stopped and health is unhealthy
+pub const CRASH_MAX_RESTARTS: u32 = 5;
+
+/// 💥 CRASH RESTART WINDOW: Seconds over which a subsystem's restarts are counted
+pub const CRASH_RESTART_WINDOW_SECS: u64 = 3600;
+
+/// 💥 CRASH RESTART BACKOFF: Seconds to wait before restarting a crashed subsystem
+/// Why: Gives whatever it depends on (Discord gateway, disk) a moment to recover
+pub const CRASH_RESTART_BACKOFF_SECS: u64 = 5;
+
+/// 💥 CRASH HEALTH WINDOW: Seconds a panic keeps health at least degraded
+/// Why: Long enough for a dashboard or uptime check to notice a restarted subsystem
+pub const CRASH_HEALTH_WINDOW_SECS: u64 = 900;
+
+/// 💥 CRASH RECENT REPORTS: Most crash reports kept in memory; older ones are only on disk
+pub const CRASH_RECENT_REPORTS: usize = 50;
+
+/// 📈 AVAILABILITY WINDOWS: Periods availability is reported over, by label
+/// Why: The day, week and month SLA figures are usually quoted for; the month matches
+/// METRICS_ROLLUP_RETENTION_SECS, the oldest history kept
+pub const AVAILABILITY_WINDOWS: &[(&str, u64)] =
+    &[("24h", 86_400), ("7d", 7 * 86_400), ("30d", 30 * 86_400)];
+
+/// 📈 AVAILABILITY MAX GAP: Sampling intervals one snapshot covers before the rest of a
+/// gap counts as down
+/// Why: A slow collection or two is not an outage, but a gap of several means nothing
+/// was running
+pub const AVAILABILITY_MAX_GAP_INTERVALS: u64 = 3;
@@ -0,0 +1,257 @@
+//! Clarifying questions before uncertain tasks
+//!
+//! When the intent classifier is unsure what a message asks for, or rates it high risk, the
+//! bot does not guess: it asks the author which of a few interpretations they meant, with
+//! one button each. The message runs as a task only after a button is pressed; the chosen
+//! interpretation replaces the classified intent.
+
+use super::intent_classifier::{IntentResponse, IntentType};
+use super::message_security::RiskLevel;
+use super::spiral_constellation_bot::UserIntent;
+use crate::constants::{CLARIFICATION_IDLE_MINUTES, INTENT_CLARIFY_CONFIDENCE};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, Message};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Prefix of the custom IDs of the interpretation buttons; the rest names the interpretation
+const CLARIFY_BUTTON_PREFIX: &str = "spiral-clarify:";
+
+/// Whether the bot should ask before turning this classification into a task
+pub fn needs_clarification(intent: &IntentResponse) -> bool {
+    intent.intent_type != IntentType::Malicious
+        && (intent.confidence < INTENT_CLARIFY_CONFIDENCE || intent.risk_level >= RiskLevel::High)
+}
+
+/// 🤔 INTERPRETATION: One reading of an unclear request, offered as a button
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    Build,
+    Inspect,
+    Explain,
+    Cancel,
+}
+
+impl Interpretation {
+    pub const ALL: [Interpretation; 4] = [
+        Interpretation::Build,
+        Interpretation::Inspect,
+        Interpretation::Explain,
+        Interpretation::Cancel,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            Interpretation::Build => "build",
+            Interpretation::Inspect => "inspect",
+            Interpretation::Explain => "explain",
+            Interpretation::Cancel => "cancel",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Interpretation::Build => "🚀 Build or change code",
+            Interpretation::Inspect => "🔍 Look at the workspace",
+            Interpretation::Explain => "❓ Just explain",
+            Interpretation::Cancel => "✖️ Never mind",
+        }
+    }
+
+    /// The intent the task runs with; None for Cancel
+    pub fn intent(self) -> Option<UserIntent> {
+        match self {
+            Interpretation::Build => Some(UserIntent::TaskRequest),
+            Interpretation::Inspect => Some(UserIntent::StatusQuery),
+            Interpretation::Explain => Some(UserIntent::HelpRequest),
+            Interpretation::Cancel => None,
+        }
+    }
+
+    pub fn custom_id(self) -> String {
+        format!("{CLARIFY_BUTTON_PREFIX}{}", self.id())
+    }
+
+    pub fn from_custom_id(custom_id: &str) -> Option<Self> {
+        let id = custom_id.strip_prefix(CLARIFY_BUTTON_PREFIX)?;
+        Self::ALL.into_iter().find(|choice| choice.id() == id)
+    }
+}
+
+/// The question posted in reply to an unclear request
+pub fn question(intent: &IntentResponse) -> String {
+    let reason = if intent.risk_level >= RiskLevel::High {
+        "This looks like it could touch sensitive parts of the system"
+    } else {
+        "I'm not sure what you'd like me to do"
+    };
+    format!(
+        "🤔 {reason}, so I'd rather ask than guess. What did you mean?\n\
+        -# No task starts until you pick one; this question expires in {CLARIFICATION_IDLE_MINUTES} minutes."
+    )
+}
+
+/// One button per interpretation, Cancel last
+pub fn buttons() -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(
+        Interpretation::ALL
+            .into_iter()
+            .map(|choice| {
+                CreateButton::new(choice.custom_id())
+                    .label(choice.label())
+                    .style(match choice {
+                        Interpretation::Build => ButtonStyle::Primary,
+                        Interpretation::Cancel => ButtonStyle::Danger,
+                        _ => ButtonStyle::Secondary,
+                    })
+            })
+            .collect(),
+    )]
+}
+
+/// What pressing an interpretation button did
+#[derive(Debug)]
+pub enum Resolution {
+    /// The request runs with the chosen intent; pass the message through the handler again
+    Confirmed(Box<Message>),
+    Cancelled,
+    /// Someone other than the author pressed the button; the question stays open
+    NotAuthor,
+    /// The question was answered already or has expired
+    Expired,
+}
+
+#[derive(Debug)]
+struct PendingClarification {
+    message: Message,
+    asked_at: Instant,
+}
+
+impl PendingClarification {
+    fn is_expired(&self) -> bool {
+        self.asked_at.elapsed() > Duration::from_secs(CLARIFICATION_IDLE_MINUTES * 60)
+    }
+}
+
+/// 🤔 CLARIFICATIONS: Open questions by the message that asks them, and confirmed answers
+/// 🏗️ ARCHITECTURE DECISION: Keep the original message and run it through the handler again
+/// Why: Security checks, agent detection and task threads all stay in one code path
+/// Alternative: Store the half-processed request (rejected: a second copy of the task pipeline)
+/// Trade-off: A confirmed request counts twice against the author's rate limit
+#[derive(Debug, Default)]
+pub struct Clarifications {
+    pending: Mutex<HashMap<u64, PendingClarification>>,
+    /// Intent chosen for a request message, taken when that message is handled again
+    confirmed: Mutex<HashMap<u64, UserIntent>>,
+}
+
+impl Clarifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember `message` until the question posted as `question_id` is answered
+    pub fn ask(&self, question_id: u64, message: Message) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, open| !open.is_expired());
+        pending.insert(
+            question_id,
+            PendingClarification {
+                message,
+                asked_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Answer the question posted as `question_id` on behalf of `user_id`
+    pub fn resolve(&self, question_id: u64, user_id: u64, choice: Interpretation) -> Resolution {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get(&question_id) {
+            None => return Resolution::Expired,
+            Some(open) if open.is_expired() => {
+                pending.remove(&question_id);
+                return Resolution::Expired;
+            }
+            Some(open) if open.message.author.id.get() != user_id => return Resolution::NotAuthor,
+            Some(_) => {}
+        }
+        let Some(open) = pending.remove(&question_id) else {
+            return Resolution::Expired;
+        };
+        match choice.intent() {
+            Some(intent) => {
+                self.confirmed
+                    .lock()
+                    .unwrap()
+                    .insert(open.message.id.get(), intent);
+                Resolution::Confirmed(Box::new(open.message))
+            }
+            None => Resolution::Cancelled,
+        }
+    }
+
+    /// The intent confirmed for a request message, if its author answered a question about it
+    pub fn take_confirmed(&self, message_id: u64) -> Option<UserIntent> {
+        self.confirmed.lock().unwrap().remove(&message_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classification(confidence: f64, risk_level: RiskLevel) -> IntentResponse {
+        IntentResponse {
+            intent_type: IntentType::Unknown,
+            confidence,
+            parameters: HashMap::new(),
+            risk_level,
+        }
+    }
+
+    #[test]
+    fn test_unclear_or_risky_requests_need_clarification() {
+        assert!(needs_clarification(&classification(0.0, RiskLevel::Low)));
+        assert!(needs_clarification(&classification(0.6, RiskLevel::High)));
+        assert!(!needs_clarification(&classification(0.5, RiskLevel::Low)));
+        assert!(!needs_clarification(&classification(
+            0.7,
+            RiskLevel::Medium
+        )));
+        for choice in Interpretation::ALL {
+            assert_eq!(
+                Interpretation::from_custom_id(&choice.custom_id()),
+                Some(choice)
+            );
+        }
+        assert_eq!(Interpretation::from_custom_id("spiral-page:1"), None);
+    }
+
+    #[test]
+    fn test_only_the_author_answers_and_only_once() {
+        let clarifications = Clarifications::new();
+        let mut message = Message::default();
+        message.id = 7.into();
+        message.author.id = 2.into();
+        clarifications.ask(100, message);
+
+        assert!(matches!(
+            clarifications.resolve(100, 3, Interpretation::Build),
+            Resolution::NotAuthor
+        ));
+        let Resolution::Confirmed(message) =
+            clarifications.resolve(100, 2, Interpretation::Explain)
+        else {
+            panic!("the author's answer confirms the request");
+        };
+        assert!(matches!(
+            clarifications.resolve(100, 2, Interpretation::Build),
+            Resolution::Expired
+        ));
+        assert_eq!(
+            clarifications.take_confirmed(message.id.get()),
+            Some(UserIntent::HelpRequest)
+        );
+        assert_eq!(clarifications.take_confirmed(message.id.get()), None);
+    }
+}
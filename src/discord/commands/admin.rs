@@ -2,6 +2,8 @@ use super::CommandHandler;
 use crate::discord::{
     agent_registry::get_agent_registry, spiral_constellation_bot::SpiralConstellationBot,
 };
+use crate::monitoring::slo::{SloObjective, SloReport};
+use crate::monitoring::AvailabilityReport;
 use serenity::{model::channel::Message, prelude::Context};
 use std::time::Instant;
 
@@ -121,6 +123,16 @@ impl AdminCommand {
             panel.push('\n');
         }
 
+        // Task objectives, when this bot runs tasks through the orchestrator
+        if let Some(report) = bot.task_slo_report() {
+            panel.push_str(&format_slo_section(&report));
+        }
+
+        // Availability for SLA reporting, when the system monitor keeps history
+        if let Some(report) = bot.availability_report().await {
+            panel.push_str(&format_availability_section(&report));
+        }
+
         // Performance stats - HONEST metrics only
         let generation_time = start_time.elapsed();
         panel.push_str("**⚡ Performance**\n");
@@ -156,11 +168,85 @@ impl AdminCommand {
         panel.push_str("• `!spiral security reset` - Reset security metrics\n");
         panel.push_str("• `!spiral debug <message>` - Debug specific issues\n");
         panel.push_str("• `!spiral ratelimit @user` - Check user rate limits\n");
+        panel.push_str("• `!spiral ratelimit tier @user trusted` - Grant a rate limit tier\n");
 
         panel
     }
 }
 
+/// 🎯 TASK SLOS: One line per agent type with tasks in the window, then each missed objective
+fn format_slo_section(report: &SloReport) -> String {
+    let mut section = format!("**🎯 Task SLOs** (last {} min)\n", report.window_secs / 60);
+    if report.agents.values().all(|summary| summary.tasks == 0) {
+        section.push_str("• No tasks finished in this window\n\n");
+        return section;
+    }
+
+    let mut agents: Vec<_> = report
+        .agents
+        .iter()
+        .filter(|(_, summary)| summary.tasks > 0)
+        .collect();
+    agents.sort_by_key(|(agent_type, _)| format!("{agent_type:?}"));
+    for (agent_type, summary) in agents {
+        section.push_str(&format!(
+            "• {agent_type:?}: p50 {:.1}s · p95 {:.1}s · p99 {:.1}s · {:.1}% of {} succeeded\n",
+            summary.p50_secs,
+            summary.p95_secs,
+            summary.p99_secs,
+            summary.success_rate * 100.0,
+            summary.tasks
+        ));
+    }
+    if report.violations.is_empty() {
+        section.push_str("• 🟢 All objectives met\n");
+    }
+    for violation in &report.violations {
+        let (actual, target) = match violation.objective {
+            SloObjective::SuccessRate => (
+                format!("{:.1}%", violation.actual * 100.0),
+                format!("{:.1}%", violation.target * 100.0),
+            ),
+            _ => (
+                format!("{:.1}s", violation.actual),
+                format!("{:.1}s", violation.target),
+            ),
+        };
+        section.push_str(&format!(
+            "• 🟡 {:?} {:?}: {actual} (target {target})\n",
+            violation.agent_type, violation.objective
+        ));
+    }
+    section.push('\n');
+    section
+}
+
+/// 📈 AVAILABILITY: One line per window; downtime in minutes since that is what an
+/// SLA is argued over
+fn format_availability_section(report: &AvailabilityReport) -> String {
+    let mut section = String::from("**📈 Availability**\n");
+    for window in &report.windows {
+        let Some(availability) = window.availability_percent else {
+            section.push_str(&format!("• {}: ❓ No history yet\n", window.window));
+            continue;
+        };
+        let icon = if window.unhealthy_secs + window.down_secs == 0 {
+            "🟢"
+        } else {
+            "🟡"
+        };
+        section.push_str(&format!(
+            "• {icon} {}: {availability:.2}% available · {:.2}% healthy · {} min unhealthy · {} min down\n",
+            window.window,
+            window.healthy_percent.unwrap_or_default(),
+            window.unhealthy_secs / 60,
+            window.down_secs / 60
+        ));
+    }
+    section.push('\n');
+    section
+}
+
 impl CommandHandler for AdminCommand {
     async fn handle(
         &self,
@@ -40,7 +40,7 @@ impl ClaudeAgentsCommand {
     /// Critical: This shows users what validation/utility agents are available
     /// Verify: Agent descriptions match actual agent capabilities in .md files
     /// Security: No sensitive paths or implementation details exposed
-    fn list_claude_agents(&self) -> String {
+    pub fn list_claude_agents(&self) -> String {
         let mut response = "🤖 **Available Claude Agents**\n\n".to_string();
 
         // Utility Agents
@@ -0,0 +1,164 @@
+use super::{parse_agent_name, CommandHandler};
+use crate::constants::{COMPOSE_IDLE_MINUTES, MAX_COMPOSE_MESSAGES};
+use crate::discord::spiral_constellation_bot::{AgentPersona, SpiralConstellationBot};
+use crate::models::AgentType;
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::info;
+
+pub const COMPOSE_COMMAND: &str = "!spiral compose";
+pub const DONE_COMMAND: &str = "!spiral done";
+
+const USAGE: &str = "**📝 Compose**\n\n\
+    • `!spiral compose [messages] [SpiralDev|SpiralPM|SpiralQA]` - Collect your next messages into one request\n\
+    • `!spiral done` - Submit what you have written\n\
+    • `!spiral compose cancel` - Drop the draft";
+
+/// What `!spiral compose` was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposeRequest {
+    Start {
+        max_messages: usize,
+        agent_type: Option<AgentType>,
+    },
+    Cancel,
+}
+
+/// Parse the text after `!spiral compose`; the message count and agent may come in any order
+pub fn parse_request(args: &str) -> Result<ComposeRequest, String> {
+    let mut max_messages = MAX_COMPOSE_MESSAGES;
+    let mut agent_type = None;
+    for word in args.split_whitespace() {
+        if word.eq_ignore_ascii_case("cancel") {
+            return Ok(ComposeRequest::Cancel);
+        }
+        if let Some(named) = parse_agent_name(word) {
+            agent_type = Some(named?);
+            continue;
+        }
+        max_messages = match word.parse::<usize>() {
+            Ok(count @ 1..=MAX_COMPOSE_MESSAGES) => count,
+            Ok(_) => {
+                return Err(format!(
+                    "❌ Compose between 1 and {MAX_COMPOSE_MESSAGES} messages."
+                ))
+            }
+            Err(_) => return Err(format!("❓ Unknown option `{word}`\n\n{USAGE}")),
+        };
+    }
+    Ok(ComposeRequest::Start {
+        max_messages,
+        agent_type,
+    })
+}
+
+pub struct ComposeCommand {
+    // Drafts live on the bot, which collects messages into them
+}
+
+impl Default for ComposeCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ComposeCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CommandHandler for ComposeCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let content = content.trim();
+        let (channel_id, user_id) = (msg.channel_id.get(), msg.author.id.get());
+
+        // The bot submits an open draft before commands are routed, so reaching here means
+        // there is none
+        if content.to_lowercase().starts_with(DONE_COMMAND) {
+            return Some(format!(
+                "📝 You have no request in progress here. Start one with `{COMPOSE_COMMAND}`."
+            ));
+        }
+
+        let args = content.get(COMPOSE_COMMAND.len()..).unwrap_or_default();
+        let response = match parse_request(args) {
+            Ok(ComposeRequest::Cancel) => match bot.compose_drafts().finish(channel_id, user_id) {
+                Some(draft) => format!(
+                    "🗑️ Draft dropped ({} messages).",
+                    draft.combined_messages.len()
+                ),
+                None => "📝 You have no request in progress here.".to_string(),
+            },
+            Ok(ComposeRequest::Start {
+                max_messages,
+                agent_type,
+            }) => {
+                let draft = bot.compose_drafts().start(
+                    channel_id,
+                    user_id,
+                    agent_type.clone(),
+                    max_messages,
+                );
+                info!(
+                    "[ComposeCommand] {} ({}) composing up to {} messages in channel {}",
+                    msg.author.name, msg.author.id, draft.max_messages, channel_id
+                );
+                let agent = agent_type
+                    .map(|agent_type| {
+                        let persona = AgentPersona::for_agent_type(&agent_type);
+                        format!(" for {} **{}**", persona.emoji, persona.name)
+                    })
+                    .unwrap_or_default();
+                format!(
+                    "📝 Composing a request{agent}. Send up to {} messages, then `{DONE_COMMAND}`. \
+                     `{COMPOSE_COMMAND} cancel` drops the draft, and it is dropped after \
+                     {COMPOSE_IDLE_MINUTES} minutes without a message.",
+                    draft.max_messages
+                )
+            }
+            Err(message) => message,
+        };
+        Some(response)
+    }
+
+    fn command_prefix(&self) -> &str {
+        COMPOSE_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Write one request over several messages"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_compose_options() {
+        assert_eq!(
+            parse_request(""),
+            Ok(ComposeRequest::Start {
+                max_messages: MAX_COMPOSE_MESSAGES,
+                agent_type: None,
+            })
+        );
+        assert_eq!(
+            parse_request("SpiralPM 3"),
+            Ok(ComposeRequest::Start {
+                max_messages: 3,
+                agent_type: Some(AgentType::ProjectManager),
+            })
+        );
+        assert_eq!(parse_request("cancel"), Ok(ComposeRequest::Cancel));
+        assert!(parse_request("0").is_err());
+        assert!(parse_request("SpiralBob").is_err());
+        assert!(parse_request("please").is_err());
+    }
+}
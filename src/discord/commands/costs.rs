@@ -0,0 +1,118 @@
+use super::CommandHandler;
+use crate::claude_code::{costs::CostTotals, CostReport};
+use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::info;
+
+/// Days of history and spenders shown; the API has the full report
+const DAYS_SHOWN: usize = 7;
+const SPENDERS_SHOWN: usize = 5;
+
+pub struct CostsCommand {
+    // Costs command reads the bot's cost tracker and keeps no state
+}
+
+impl Default for CostsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CostsCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn format_totals(totals: &CostTotals) -> String {
+        format!(
+            "${:.2} • {} calls • {} in / {} out tokens",
+            totals.cost_usd, totals.calls, totals.input_tokens, totals.output_tokens
+        )
+    }
+
+    /// 💸 COST SUMMARY: Today against the budget, recent days, and who spent most
+    /// User IDs are shown as code rather than mentions so the report pings nobody
+    fn generate_cost_report(report: &CostReport, own: &CostTotals) -> String {
+        let mut response = "💸 **Claude Code Spend**\n\n".to_string();
+
+        response.push_str(&format!(
+            "**Today ({}):** {}\n",
+            report.date,
+            Self::format_totals(&report.today)
+        ));
+        match (report.daily_budget_usd, report.budget_remaining_usd) {
+            (Some(budget), Some(remaining)) => {
+                let status = if report.budget_exceeded {
+                    "🔴 Used up - new tasks are refused until midnight UTC"
+                } else {
+                    "🟢 Accepting tasks"
+                };
+                response.push_str(&format!(
+                    "**Daily Budget:** ${budget:.2} (${remaining:.2} left) {status}\n"
+                ));
+            }
+            _ => response.push_str("**Daily Budget:** none configured\n"),
+        }
+        response.push_str(&format!("**Your Spend:** {}\n\n", Self::format_totals(own)));
+
+        if report.days.len() > 1 {
+            response.push_str("**📅 Recent Days**\n");
+            for day in report.days.iter().take(DAYS_SHOWN) {
+                response.push_str(&format!(
+                    "• {} - ${:.2} ({} calls)\n",
+                    day.date, day.totals.cost_usd, day.totals.calls
+                ));
+            }
+            response.push('\n');
+        }
+
+        if !report.users.is_empty() {
+            response.push_str("**👥 Top Users**\n");
+            for user in report.users.iter().take(SPENDERS_SHOWN) {
+                response.push_str(&format!("• `{}` - ${:.2}\n", user.id, user.totals.cost_usd));
+            }
+            response.push('\n');
+        }
+
+        if !report.tasks.is_empty() {
+            response.push_str("**📋 Top Tasks**\n");
+            for task in report.tasks.iter().take(SPENDERS_SHOWN) {
+                response.push_str(&format!("• `{}` - ${:.2}\n", task.id, task.totals.cost_usd));
+            }
+        }
+
+        response
+    }
+}
+
+impl CommandHandler for CostsCommand {
+    async fn handle(
+        &self,
+        _content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        info!(
+            "[CostsCommand] User {} ({}) requested the cost report",
+            msg.author.name,
+            msg.author.id.get()
+        );
+
+        let Some(costs) = bot.cost_tracker() else {
+            return Some(
+                "💸 Cost tracking is unavailable: this bot has no Claude Code client".to_string(),
+            );
+        };
+        let own = costs.user_totals(&msg.author.id.to_string());
+        Some(Self::generate_cost_report(&costs.report(), &own))
+    }
+
+    fn command_prefix(&self) -> &str {
+        "!spiral costs"
+    }
+
+    fn description(&self) -> &str {
+        "Claude Code spend per day, user and task, and the daily budget"
+    }
+}
@@ -117,11 +117,9 @@ impl DebugCommand {
         report.push_str(&format!("• Request Time: {}\n", msg.timestamp));
 
         // Authorization status
-        let is_authorized = bot.is_authorized_user(msg.author.id.get());
-        let auth_status = if is_authorized {
-            "🟢 Authorized"
-        } else {
-            "🔴 Not Authorized"
+        let auth_status = match bot.message_role(msg) {
+            Some(role) => format!("🟢 Authorized ({role})"),
+            None => "🔴 Not Authorized".to_string(),
         };
         report.push_str(&format!("• Authorization: {auth_status}\n\n"));
 
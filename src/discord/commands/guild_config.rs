@@ -0,0 +1,478 @@
+use super::CommandHandler;
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSource};
+use crate::auth::Role;
+use crate::constants::{DISCORD_DIGEST_MAX_MINUTES, DISCORD_DIGEST_MIN_MINUTES};
+use crate::discord::guild_settings::GuildSettings;
+use crate::discord::locale::Locale;
+use crate::discord::messages;
+use crate::discord::spiral_constellation_bot::{AgentPersona, SpiralConstellationBot};
+use crate::models::AgentType;
+use serenity::{
+    model::{channel::Message, id::GuildId},
+    prelude::Context,
+};
+use tracing::{info, warn};
+
+pub const CONFIG_COMMAND: &str = "!spiral config";
+
+/// A guild may not raise its members above this; admin stays with DISCORD_USER_ROLES
+const MAX_GRANTED_ROLE: Role = Role::Operator;
+
+const USAGE: &str = "**⚙️ Server Settings**\n\n\
+    • `!spiral config` - Show this server's settings\n\
+    • `!spiral config prefix <prefix>|reset` - Extra command prefix, e.g. `!sp`\n\
+    • `!spiral config channels <#channel ...>|all` - Channels Spiral answers in\n\
+    • `!spiral config tasks <#channel ...>|all` - Channels agent tasks may start in\n\
+    • `!spiral config results <#channel>|none` - Channel finished tasks are announced in\n\
+    • `!spiral config ops <#channel>|none` - Channel security alerts go to\n\
+    • `!spiral config role <@role> <viewer|operator|none>` - Spiral role for a server role\n\
+    • `!spiral config agent <dev|pm|qa|none>` - Agent for mentions that name none\n\
+    • `!spiral config ratelimit <messages per minute>|default` - Per-user message limit\n\
+    • `!spiral config locale <en|nl|de>|default` - Language Spiral replies in\n\
+    • `!spiral config digest <minutes>|off` - Batch routine notifications into digests\n\n\
+    *Changing settings needs a Spiral admin, or Manage Server together with the operator role.*";
+
+/// One change to a guild's settings, as parsed from the command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    Prefix(Option<String>),
+    Channels(Vec<u64>),
+    TaskChannels(Vec<u64>),
+    ResultsChannel(Option<u64>),
+    OpsChannel(Option<u64>),
+    RoleGrant { role_id: u64, role: Option<Role> },
+    DefaultAgent(Option<AgentType>),
+    RateLimit(Option<usize>),
+    Locale(Option<Locale>),
+    Digest(Option<u64>),
+}
+
+impl ConfigChange {
+    pub(crate) fn apply(self, settings: &mut GuildSettings) {
+        match self {
+            ConfigChange::Prefix(prefix) => settings.command_prefix = prefix,
+            ConfigChange::Channels(channels) => settings.allowed_channels = channels,
+            ConfigChange::TaskChannels(channels) => settings.task_channels = channels,
+            ConfigChange::ResultsChannel(channel) => settings.results_channel = channel,
+            ConfigChange::OpsChannel(channel) => settings.ops_channel = channel,
+            ConfigChange::RoleGrant { role_id, role } => match role {
+                Some(role) => {
+                    settings.role_grants.insert(role_id, role);
+                }
+                None => {
+                    settings.role_grants.remove(&role_id);
+                }
+            },
+            ConfigChange::DefaultAgent(agent) => settings.default_agent = agent,
+            ConfigChange::RateLimit(limit) => settings.rate_limit_per_minute = limit,
+            ConfigChange::Locale(locale) => settings.locale = locale,
+            ConfigChange::Digest(minutes) => settings.digest_minutes = minutes,
+        }
+    }
+}
+
+/// Parse the text after `!spiral config`; Ok(None) asks for the current settings
+pub fn parse_change(args: &str) -> Result<Option<ConfigChange>, String> {
+    let mut words = args.split_whitespace();
+    let Some(setting) = words.next() else {
+        return Ok(None);
+    };
+    let values: Vec<&str> = words.collect();
+    let single = || match values.as_slice() {
+        [value] => Ok(*value),
+        _ => Err(format!(
+            "❌ `{setting}` takes exactly one value.\n\n{USAGE}"
+        )),
+    };
+
+    match setting.to_lowercase().as_str() {
+        "show" => Ok(None),
+        "prefix" => {
+            let prefix = single()?;
+            if prefix.eq_ignore_ascii_case("reset") {
+                return Ok(Some(ConfigChange::Prefix(None)));
+            }
+            if prefix.chars().count() > 16 || prefix.eq_ignore_ascii_case("!spiral") {
+                return Err(
+                    "❌ The prefix must be at most 16 characters and not `!spiral`.".to_string(),
+                );
+            }
+            Ok(Some(ConfigChange::Prefix(Some(prefix.to_string()))))
+        }
+        "channels" => {
+            parse_channels(&values).map(|channels| Some(ConfigChange::Channels(channels)))
+        }
+        "tasks" => {
+            parse_channels(&values).map(|channels| Some(ConfigChange::TaskChannels(channels)))
+        }
+        "results" => {
+            parse_channel(single()?).map(|channel| Some(ConfigChange::ResultsChannel(channel)))
+        }
+        "ops" => parse_channel(single()?).map(|channel| Some(ConfigChange::OpsChannel(channel))),
+        "role" => {
+            let [role_mention, role_name] = values.as_slice() else {
+                return Err(format!(
+                    "❌ Name a server role and a Spiral role.\n\n{USAGE}"
+                ));
+            };
+            let role_id = parse_id(role_mention, "<@&", "role")?;
+            let role = match role_name.to_lowercase().as_str() {
+                "none" => None,
+                name => {
+                    let role: Role = name.parse().map_err(|e| format!("❌ {e}"))?;
+                    if !MAX_GRANTED_ROLE.permits(role) {
+                        return Err(format!(
+                            "❌ Servers can grant at most `{MAX_GRANTED_ROLE}`; admins are configured with DISCORD_USER_ROLES."
+                        ));
+                    }
+                    Some(role)
+                }
+            };
+            Ok(Some(ConfigChange::RoleGrant { role_id, role }))
+        }
+        "agent" => {
+            let agent = single()?;
+            if agent.eq_ignore_ascii_case("none") {
+                return Ok(Some(ConfigChange::DefaultAgent(None)));
+            }
+            AgentType::from_mention(agent)
+                .map(|agent| Some(ConfigChange::DefaultAgent(Some(agent))))
+                .ok_or_else(|| format!("❌ Unknown agent `{agent}`; use dev, pm or qa."))
+        }
+        "ratelimit" => {
+            let limit = single()?;
+            if limit.eq_ignore_ascii_case("default") {
+                return Ok(Some(ConfigChange::RateLimit(None)));
+            }
+            match limit.parse::<usize>() {
+                Ok(limit) if (1..=60).contains(&limit) => {
+                    Ok(Some(ConfigChange::RateLimit(Some(limit))))
+                }
+                _ => Err(
+                    "❌ The rate limit must be between 1 and 60 messages per minute.".to_string(),
+                ),
+            }
+        }
+        "locale" => {
+            let locale = single()?;
+            if locale.eq_ignore_ascii_case("default") {
+                return Ok(Some(ConfigChange::Locale(None)));
+            }
+            locale
+                .parse()
+                .map(|locale| Some(ConfigChange::Locale(Some(locale))))
+                .map_err(|e| format!("❌ {e}"))
+        }
+        "digest" => {
+            let minutes = single()?;
+            if minutes.eq_ignore_ascii_case("off") {
+                return Ok(Some(ConfigChange::Digest(None)));
+            }
+            match minutes.parse::<u64>() {
+                Ok(minutes)
+                    if (DISCORD_DIGEST_MIN_MINUTES..=DISCORD_DIGEST_MAX_MINUTES)
+                        .contains(&minutes) =>
+                {
+                    Ok(Some(ConfigChange::Digest(Some(minutes))))
+                }
+                _ => Err(format!(
+                    "❌ The digest interval must be between {DISCORD_DIGEST_MIN_MINUTES} and {DISCORD_DIGEST_MAX_MINUTES} minutes."
+                )),
+            }
+        }
+        _ => Err(format!("❌ Unknown setting `{setting}`.\n\n{USAGE}")),
+    }
+}
+
+/// Channels to restrict something to; `all` lifts the restriction
+fn parse_channels(values: &[&str]) -> Result<Vec<u64>, String> {
+    if let [value] = values {
+        if value.eq_ignore_ascii_case("all") {
+            return Ok(Vec::new());
+        }
+    }
+    if values.is_empty() {
+        return Err(format!("❌ Name at least one channel.\n\n{USAGE}"));
+    }
+    values
+        .iter()
+        .map(|value| parse_id(value, "<#", "channel"))
+        .collect()
+}
+
+/// A single channel; `none` unsets it
+fn parse_channel(value: &str) -> Result<Option<u64>, String> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    parse_id(value, "<#", "channel").map(Some)
+}
+
+/// An ID from a mention such as `<#123>` or `<@&123>`, or a bare ID
+fn parse_id(value: &str, mention_start: &str, kind: &str) -> Result<u64, String> {
+    value
+        .strip_prefix(mention_start)
+        .and_then(|rest| rest.strip_suffix('>'))
+        .unwrap_or(value)
+        .parse()
+        .map_err(|_| format!("❌ `{value}` is not a {kind}."))
+}
+
+pub struct GuildConfigCommand {
+    // Settings live in the bot's guild settings store
+}
+
+impl Default for GuildConfigCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GuildConfigCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub(crate) fn format_settings(settings: &GuildSettings) -> String {
+        let prefix = match &settings.command_prefix {
+            Some(prefix) => format!("`{prefix}` and `!spiral`"),
+            None => "`!spiral`".to_string(),
+        };
+        let channel_list = |channels: &[u64]| {
+            if channels.is_empty() {
+                return "all channels".to_string();
+            }
+            channels
+                .iter()
+                .map(|channel| format!("<#{channel}>"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let channel = |channel: Option<u64>| match channel {
+            Some(channel) => format!("<#{channel}>"),
+            None => "none".to_string(),
+        };
+        let channels = channel_list(&settings.allowed_channels);
+        let task_channels = channel_list(&settings.task_channels);
+        let results_channel = channel(settings.results_channel);
+        let ops_channel = channel(settings.ops_channel);
+        let mut grants: Vec<_> = settings.role_grants.iter().collect();
+        grants.sort();
+        let grants = if grants.is_empty() {
+            "none".to_string()
+        } else {
+            grants
+                .iter()
+                .map(|(role_id, role)| format!("<@&{role_id}> → `{role}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let agent = match &settings.default_agent {
+            Some(agent) => AgentPersona::for_agent_type(agent).name.to_string(),
+            None => "none".to_string(),
+        };
+        let rate_limit = match settings.rate_limit_per_minute {
+            Some(limit) => format!("{limit} messages per minute"),
+            None => "default".to_string(),
+        };
+        let locale = settings.locale.unwrap_or_default();
+        let digest = match settings.digest_minutes {
+            Some(minutes) => format!("every {minutes} minutes"),
+            None => "off".to_string(),
+        };
+        format!(
+            "**⚙️ Server Settings**\n\n\
+            **Prefix:** {prefix}\n\
+            **Channels:** {channels}\n\
+            **Task channels:** {task_channels}\n\
+            **Results channel:** {results_channel}\n\
+            **Ops channel:** {ops_channel}\n\
+            **Role grants:** {grants}\n\
+            **Default agent:** {agent}\n\
+            **Rate limit:** {rate_limit}\n\
+            **Locale:** `{locale}`\n\
+            **Digest:** {digest}\n\n\
+            *Use `!spiral config help` to change them.*"
+        )
+    }
+
+    /// 🛡️ SECURITY DECISION: Spiral admins, or operators who may manage the Discord server
+    /// Why: Server managers know their channels and roles, but a role from the bot's own
+    /// config is still required so that inviting the bot grants nothing
+    pub(crate) async fn may_edit(
+        ctx: &Context,
+        msg: &Message,
+        guild_id: GuildId,
+        role: Role,
+    ) -> bool {
+        if role.permits(Role::Admin) {
+            return true;
+        }
+        if !role.permits(Role::Operator) {
+            return false;
+        }
+        let guild = match guild_id.to_partial_guild(&ctx.http).await {
+            Ok(guild) => guild,
+            Err(e) => {
+                warn!(
+                    "[GuildConfigCommand] Failed to fetch guild {}: {}",
+                    guild_id, e
+                );
+                return false;
+            }
+        };
+        match guild_id.member(&ctx.http, msg.author.id).await {
+            Ok(member) => guild.member_permissions(&member).manage_guild(),
+            Err(e) => {
+                warn!("[GuildConfigCommand] Failed to fetch member: {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl CommandHandler for GuildConfigCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let Some(guild_id) = msg.guild_id else {
+            return Some("❌ Server settings only work in servers, not direct messages.".into());
+        };
+        let args = content
+            .get(CONFIG_COMMAND.len()..)
+            .unwrap_or_default()
+            .trim();
+        if args.eq_ignore_ascii_case("help") {
+            return Some(USAGE.to_string());
+        }
+
+        let change = match parse_change(args) {
+            Ok(Some(change)) => change,
+            Ok(None) => {
+                return Some(Self::format_settings(
+                    &bot.guild_settings().get(guild_id.get()),
+                ))
+            }
+            Err(message) => return Some(message),
+        };
+
+        let role = bot.message_role(msg).unwrap_or(Role::Viewer);
+        if !Self::may_edit(ctx, msg, guild_id, role).await {
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::AccessDenied,
+                    AuditSource::Discord,
+                    content.chars().take(200).collect::<String>(),
+                )
+                .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                .with_client(format!("channel {}", msg.channel_id))
+                .with_details("Requires admin, or operator with Manage Server"),
+            );
+            return Some(format!(
+                "{} (requires `{}`, or `{}` with Manage Server)",
+                messages::security::INSUFFICIENT_ROLE,
+                Role::Admin,
+                Role::Operator
+            ));
+        }
+
+        info!(
+            "[GuildConfigCommand] {} ({}) changed guild {}: {:?}",
+            msg.author.name, msg.author.id, guild_id, change
+        );
+        let settings = bot
+            .guild_settings()
+            .update(guild_id.get(), |settings| change.apply(settings));
+        Some(format!("✅ Saved.\n\n{}", Self::format_settings(&settings)))
+    }
+
+    fn command_prefix(&self) -> &str {
+        CONFIG_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Show and change this server's Spiral settings"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_change_reads_each_setting() {
+        assert_eq!(parse_change(""), Ok(None));
+        assert_eq!(
+            parse_change("prefix !sp"),
+            Ok(Some(ConfigChange::Prefix(Some("!sp".to_string()))))
+        );
+        assert_eq!(
+            parse_change("channels <#10> 11"),
+            Ok(Some(ConfigChange::Channels(vec![10, 11])))
+        );
+        assert_eq!(
+            parse_change("channels all"),
+            Ok(Some(ConfigChange::Channels(Vec::new())))
+        );
+        assert_eq!(
+            parse_change("tasks <#12>"),
+            Ok(Some(ConfigChange::TaskChannels(vec![12])))
+        );
+        assert_eq!(
+            parse_change("results <#13>"),
+            Ok(Some(ConfigChange::ResultsChannel(Some(13))))
+        );
+        assert_eq!(
+            parse_change("ops none"),
+            Ok(Some(ConfigChange::OpsChannel(None)))
+        );
+        assert_eq!(
+            parse_change("role <@&5> operator"),
+            Ok(Some(ConfigChange::RoleGrant {
+                role_id: 5,
+                role: Some(Role::Operator)
+            }))
+        );
+        assert_eq!(
+            parse_change("agent qa"),
+            Ok(Some(ConfigChange::DefaultAgent(Some(
+                AgentType::QualityAssurance
+            ))))
+        );
+        assert_eq!(
+            parse_change("ratelimit 10"),
+            Ok(Some(ConfigChange::RateLimit(Some(10))))
+        );
+        assert_eq!(
+            parse_change("locale nl"),
+            Ok(Some(ConfigChange::Locale(Some(Locale::Nl))))
+        );
+        assert_eq!(
+            parse_change("locale default"),
+            Ok(Some(ConfigChange::Locale(None)))
+        );
+        assert_eq!(
+            parse_change("digest 30"),
+            Ok(Some(ConfigChange::Digest(Some(30))))
+        );
+        assert_eq!(
+            parse_change("digest off"),
+            Ok(Some(ConfigChange::Digest(None)))
+        );
+    }
+
+    #[test]
+    fn test_parse_change_refuses_admin_grants_and_bad_values() {
+        assert!(parse_change("role <@&5> admin").is_err());
+        assert!(parse_change("channels <#abc>").is_err());
+        assert!(parse_change("ratelimit 0").is_err());
+        assert!(parse_change("agent king").is_err());
+        assert!(parse_change("locale fr").is_err());
+        assert!(parse_change("digest 1").is_err());
+        assert!(parse_change("colour blue").is_err());
+    }
+}
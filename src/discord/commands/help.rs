@@ -1,4 +1,5 @@
-use super::{get_commands_by_auth, CommandHandler};
+use super::{get_commands_by_role, CommandHandler};
+use crate::auth::Role;
 use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
 use serenity::{model::channel::Message, prelude::Context};
 use tracing::info;
@@ -19,7 +20,7 @@ impl HelpCommand {
     }
 
     /// Generate comprehensive help information (compact version)
-    fn generate_help_content(&self, is_authorized: bool) -> String {
+    fn generate_help_content(&self, role: Role) -> String {
         let mut help_text = String::new();
 
         help_text.push_str("🌌 **Spiral Core Help**\n\n");
@@ -27,22 +28,28 @@ impl HelpCommand {
         // Quick start
         help_text.push_str("**Quick Start**\n");
         help_text.push_str("• Mention agents: `@<AgentName> <your request>`\n");
-        help_text.push_str("• Join roles: `!spiral roles join <AgentName>`\n\n");
+        help_text.push_str("• Join roles: `!spiral roles join <AgentName>`\n");
+        help_text
+            .push_str("• Slash commands: `/spiral task`, `/spiral status`, `/spiral agents`\n\n");
 
         // Core commands
         help_text.push_str("**Commands**\n");
         help_text.push_str("• `!spiral help` - This help\n");
         help_text.push_str("• `!spiral commands` - Command list\n");
         help_text.push_str("• `!spiral roles join <name>` - Join agent role\n");
-        help_text.push_str("• `!spiral roles setup` - Create roles\n");
-        help_text.push_str("• `!spiral ratelimit` - Check limits\n\n");
+        help_text.push_str("• `!spiral roles setup` - Create roles\n\n");
 
-        // Admin commands (only if authorized)
-        if is_authorized {
+        // Operator and admin commands (only for users holding those roles)
+        if role.permits(Role::Operator) {
+            help_text.push_str("**Operator**\n");
+            help_text.push_str("• `!spiral debug` - Debug (reply to msg)\n\n");
+        }
+        if role.permits(Role::Admin) {
             help_text.push_str("**Admin**\n");
             help_text.push_str("• `!spiral admin` - Dashboard\n");
             help_text.push_str("• `!spiral security stats` - Metrics\n");
-            help_text.push_str("• `!spiral debug` - Debug (reply to msg)\n\n");
+            help_text.push_str("• `!spiral ratelimit` - Check limits\n");
+            help_text.push_str("• `!spiral ratelimit tier @user <tier>` - Grant a tier\n\n");
         }
 
         // Agent list - dynamically loaded from registry
@@ -59,15 +66,26 @@ impl HelpCommand {
     }
 
     /// Generate concise command list from static definitions
-    fn generate_commands_list(&self, is_authorized: bool) -> String {
+    fn generate_commands_list(&self, role: Role) -> String {
         let mut commands_text = String::new();
         commands_text.push_str("🎮 **Spiral Commands**\n\n");
 
-        // General commands (always available)
-        let public_commands = get_commands_by_auth(false);
-        if !public_commands.is_empty() {
-            commands_text.push_str("**🌐 General Commands**\n");
-            for command in public_commands {
+        // One section per role the user holds, lowest first
+        let sections = [
+            (Role::Viewer, "**🌐 General Commands**\n"),
+            (Role::Operator, "**🛠️ Operator Commands**\n"),
+            (Role::Admin, "**🔐 Admin Commands**\n"),
+        ];
+        for (section_role, heading) in sections {
+            if !role.permits(section_role) {
+                continue;
+            }
+            let commands = get_commands_by_role(section_role);
+            if commands.is_empty() {
+                continue;
+            }
+            commands_text.push_str(heading);
+            for command in commands {
                 commands_text.push_str(&format!(
                     "• `{}` - {}\n",
                     command.prefix, command.description
@@ -76,21 +94,6 @@ impl HelpCommand {
             commands_text.push('\n');
         }
 
-        // Admin commands (only for authorized users)
-        if is_authorized {
-            let admin_commands = get_commands_by_auth(true);
-            if !admin_commands.is_empty() {
-                commands_text.push_str("**🔐 Admin Commands**\n");
-                for command in admin_commands {
-                    commands_text.push_str(&format!(
-                        "• `{}` - {}\n",
-                        command.prefix, command.description
-                    ));
-                }
-                commands_text.push('\n');
-            }
-        }
-
         commands_text.push_str("*Use `!spiral help` for detailed usage information* 💡");
         commands_text
     }
@@ -108,7 +111,8 @@ impl CommandHandler for HelpCommand {
         const HELP_PREFIX: &str = "!spiral help";
 
         let content_lower = content.to_lowercase();
-        let is_authorized = bot.is_authorized_user(msg.author.id.get());
+        // Unknown users are refused before commands are routed, so this is a fallback
+        let role = bot.message_role(msg).unwrap_or(Role::Viewer);
 
         // Match command type using const patterns
         match content_lower.as_str() {
@@ -118,7 +122,7 @@ impl CommandHandler for HelpCommand {
                     msg.author.name,
                     msg.author.id.get()
                 );
-                Some(self.generate_commands_list(is_authorized))
+                Some(self.generate_commands_list(role))
             }
             cmd if cmd.starts_with(HELP_PREFIX) || cmd == "help" => {
                 info!(
@@ -126,7 +130,7 @@ impl CommandHandler for HelpCommand {
                     msg.author.name,
                     msg.author.id.get()
                 );
-                Some(self.generate_help_content(is_authorized))
+                Some(self.generate_help_content(role))
             }
             _ => None,
         }
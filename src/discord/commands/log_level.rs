@@ -0,0 +1,65 @@
+use super::CommandHandler;
+use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
+use crate::logging;
+use serenity::{model::channel::Message, prelude::Context};
+
+const USAGE: &str = "Usage: `!spiral loglevel` to show the filter, `!spiral loglevel <filter>` to \
+                     change it (e.g. `info,spiral_core::discord=trace`), or `!spiral loglevel reset`";
+
+pub struct LogLevelCommand {
+    // The filter lives in the logging subscriber; nothing to keep here
+}
+
+impl Default for LogLevelCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogLevelCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// 🔧 RUNTIME LOG LEVEL: Show, change or reset the log filter of the running process
+    /// Directives are taken from the original message, since module paths are case-sensitive
+    fn respond(args: &str) -> String {
+        let result = match args.trim() {
+            "" => {
+                return match logging::current_filter() {
+                    Some(filter) => format!("📊 **Log filter:** `{filter}`\n{USAGE}"),
+                    None => "📊 Log levels can't be changed in this process.".to_string(),
+                }
+            }
+            reset if reset.eq_ignore_ascii_case("reset") => logging::reset_filter(),
+            directives => logging::set_filter(directives),
+        };
+        match result {
+            Ok(filter) => format!("✅ Log filter is now `{filter}` until the next restart."),
+            Err(e) => format!("❌ {e}\n{USAGE}"),
+        }
+    }
+}
+
+impl CommandHandler for LogLevelCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        _msg: &Message,
+        _ctx: &Context,
+        _bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let args = content
+            .get(self.command_prefix().len()..)
+            .unwrap_or_default();
+        Some(Self::respond(args))
+    }
+
+    fn command_prefix(&self) -> &str {
+        "!spiral loglevel"
+    }
+
+    fn description(&self) -> &str {
+        "Show or change log levels without restarting"
+    }
+}
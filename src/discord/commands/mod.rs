@@ -1,16 +1,33 @@
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSource};
+use crate::auth::Role;
+use crate::discord::messages;
 use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
-use serenity::{model::channel::Message, prelude::Context};
+use crate::models::AgentType;
+use serenity::{
+    model::{channel::Message, id::ChannelId, user::User},
+    prelude::Context,
+};
 use tracing::debug;
 
 pub mod admin;
 pub mod claude_agents;
+pub mod compose;
+pub mod costs;
 pub mod debug;
 pub mod debug_progress;
+pub mod guild_config;
 pub mod help;
+pub mod log_level;
+pub mod prefs;
 pub mod rate_limit;
 pub mod roles;
+pub mod schedule;
 pub mod security;
 pub mod self_update;
+pub mod session;
+pub mod setup;
+pub mod slash;
+pub mod tasks;
 
 /// Command handler trait for all Discord commands
 #[allow(async_fn_in_trait)]
@@ -34,7 +51,8 @@ pub struct CommandInfo {
     pub prefix: &'static str,
     pub description: &'static str,
     pub category: CommandCategory,
-    pub requires_auth: bool,
+    /// Lowest role allowed to run the command
+    pub required_role: Role,
 }
 
 /// Command categories for organization
@@ -56,11 +74,11 @@ pub fn get_commands_by_category(category: CommandCategory) -> Vec<&'static Comma
         .collect()
 }
 
-/// Get commands by authorization requirement
-pub fn get_commands_by_auth(requires_auth: bool) -> Vec<&'static CommandInfo> {
+/// Get commands whose lowest allowed role is exactly `role`
+pub fn get_commands_by_role(role: Role) -> Vec<&'static CommandInfo> {
     AVAILABLE_COMMANDS
         .iter()
-        .filter(|cmd| cmd.requires_auth == requires_auth)
+        .filter(|cmd| cmd.required_role == role)
         .collect()
 }
 
@@ -71,70 +89,149 @@ pub const AVAILABLE_COMMANDS: &[CommandInfo] = &[
         prefix: "!spiral admin",
         description: "System dashboard with metrics and quick actions",
         category: CommandCategory::Admin,
-        requires_auth: true,
+        required_role: Role::Admin,
+    },
+    CommandInfo {
+        name: "config",
+        prefix: "!spiral config",
+        description: "Show and change this server's prefix, channels, role grants, default agent and rate limit",
+        category: CommandCategory::Admin,
+        required_role: Role::Viewer,
+    },
+    CommandInfo {
+        name: "setup",
+        prefix: "!spiral setup",
+        description: "Guided first-run setup: permissions, agent roles, channels and operators",
+        category: CommandCategory::Admin,
+        required_role: Role::Operator,
+    },
+    CommandInfo {
+        name: "loglevel",
+        prefix: "!spiral loglevel",
+        description: "Show or change log levels without restarting",
+        category: CommandCategory::Admin,
+        required_role: Role::Admin,
+    },
+    CommandInfo {
+        name: "costs",
+        prefix: "!spiral costs",
+        description: "Claude Code spend per day, user and task, and the daily budget",
+        category: CommandCategory::Admin,
+        required_role: Role::Operator,
     },
     CommandInfo {
         name: "debug progress",
         prefix: "!spiral debug progress",
         description: "Demo the progress bar functionality",
         category: CommandCategory::Debug,
-        requires_auth: false,
+        required_role: Role::Viewer,
     },
     CommandInfo {
         name: "debug",
         prefix: "!spiral debug",
         description: "Debug information and security analysis",
         category: CommandCategory::Debug,
-        requires_auth: true,
+        required_role: Role::Operator,
     },
     CommandInfo {
         name: "help",
         prefix: "!spiral help",
         description: "Show available commands and usage information",
         category: CommandCategory::General,
-        requires_auth: false,
+        required_role: Role::Viewer,
     },
     CommandInfo {
         name: "commands",
         prefix: "!spiral commands",
         description: "Show concise command list",
         category: CommandCategory::General,
-        requires_auth: false,
+        required_role: Role::Viewer,
     },
     CommandInfo {
         name: "ratelimit",
         prefix: "!spiral ratelimit",
-        description: "Check and manage user rate limits",
+        description: "Check rate limits and grant rate limit tiers",
         category: CommandCategory::Admin,
-        requires_auth: true,
+        required_role: Role::Admin,
     },
     CommandInfo {
         name: "roles",
         prefix: "!spiral roles",
         description: "Manage Discord agent roles",
         category: CommandCategory::Roles,
-        requires_auth: true,
+        required_role: Role::Operator,
     },
     CommandInfo {
         name: "security",
         prefix: "!spiral security",
         description: "Security metrics and analysis tools",
         category: CommandCategory::Security,
-        requires_auth: true,
+        required_role: Role::Admin,
+    },
+    // 🏗️ ARCHITECTURE DECISION: "tasks" is listed before "task"
+    // Why: Routing takes the first matching prefix, and "!spiral task" prefixes "!spiral tasks"
+    CommandInfo {
+        name: "tasks",
+        prefix: "!spiral tasks",
+        description: "List your recent tasks with their status and duration",
+        category: CommandCategory::General,
+        required_role: Role::Viewer,
+    },
+    CommandInfo {
+        name: "task",
+        prefix: "!spiral task",
+        description: "Show one task's status, progress, timing and result",
+        category: CommandCategory::General,
+        required_role: Role::Viewer,
+    },
+    CommandInfo {
+        name: "compose",
+        prefix: "!spiral compose",
+        description: "Write one request over several messages",
+        category: CommandCategory::General,
+        required_role: Role::Operator,
+    },
+    CommandInfo {
+        name: "done",
+        prefix: "!spiral done",
+        description: "Submit the request you are composing",
+        category: CommandCategory::General,
+        required_role: Role::Operator,
+    },
+    CommandInfo {
+        name: "prefs",
+        prefix: "!spiral prefs",
+        description: "Your own defaults: preferred agent, verbosity, language and task threads",
+        category: CommandCategory::General,
+        required_role: Role::Viewer,
+    },
+    CommandInfo {
+        name: "session",
+        prefix: "!spiral session",
+        description: "Your session: the agent and workspace your tasks continue with",
+        category: CommandCategory::General,
+        required_role: Role::Operator,
+    },
+    CommandInfo {
+        name: "schedule",
+        prefix: "!spiral schedule",
+        description: "Run a task on a cron schedule; list or remove schedules",
+        category: CommandCategory::General,
+        required_role: Role::Operator,
     },
     CommandInfo {
         name: "update",
         prefix: "!spiral update",
         description: "Self-update system information",
         category: CommandCategory::Updates,
-        requires_auth: true,
+        required_role: Role::Admin,
     },
     CommandInfo {
         name: "self-update",
         prefix: "!spiral self-update",
         description: "Self-update system information (alias)",
         category: CommandCategory::Updates,
-        requires_auth: true,
+        required_role: Role::Admin,
     },
     // 🏗️ ARCHITECTURE DECISION: Dual command aliases for discoverability
     // Why: Users might look for "agents" or "claude-agents"
@@ -146,14 +243,14 @@ pub const AVAILABLE_COMMANDS: &[CommandInfo] = &[
         prefix: "!spiral agents",
         description: "List all available Claude validation and utility agents",
         category: CommandCategory::General,
-        requires_auth: false,
+        required_role: Role::Viewer,
     },
     CommandInfo {
         name: "claude-agents",
         prefix: "!spiral claude-agents",
         description: "List all available Claude validation and utility agents",
         category: CommandCategory::General,
-        requires_auth: false,
+        required_role: Role::Viewer,
     },
 ];
 
@@ -163,13 +260,22 @@ pub const AVAILABLE_COMMANDS: &[CommandInfo] = &[
 pub struct CommandRouter {
     pub admin: admin::AdminCommand,
     pub claude_agents: claude_agents::ClaudeAgentsCommand,
+    pub costs: costs::CostsCommand,
+    pub guild_config: guild_config::GuildConfigCommand,
     pub debug: debug::DebugCommand,
     pub debug_progress: debug_progress::DebugProgressCommand,
     pub help: help::HelpCommand,
+    pub log_level: log_level::LogLevelCommand,
     pub rate_limit: rate_limit::RateLimitCommand,
     pub roles: roles::RolesCommand,
     pub security: security::SecurityCommand,
     pub self_update: self_update::SelfUpdateCommand,
+    pub tasks: tasks::TasksCommand,
+    pub schedule: schedule::ScheduleCommand,
+    pub compose: compose::ComposeCommand,
+    pub setup: setup::SetupCommand,
+    pub prefs: prefs::PrefsCommand,
+    pub session: session::SessionCommand,
 }
 
 impl Default for CommandRouter {
@@ -183,13 +289,22 @@ impl CommandRouter {
         Self {
             admin: admin::AdminCommand::new(),
             claude_agents: claude_agents::ClaudeAgentsCommand::new(),
+            costs: costs::CostsCommand::new(),
+            guild_config: guild_config::GuildConfigCommand::new(),
             debug: debug::DebugCommand::new(),
             debug_progress: debug_progress::DebugProgressCommand::new(),
             help: help::HelpCommand::new(),
+            log_level: log_level::LogLevelCommand::new(),
             rate_limit: rate_limit::RateLimitCommand::new(),
             roles: roles::RolesCommand::new(),
             security: security::SecurityCommand::new(),
             self_update: self_update::SelfUpdateCommand::new(),
+            tasks: tasks::TasksCommand::new(),
+            schedule: schedule::ScheduleCommand::new(),
+            compose: compose::ComposeCommand::new(),
+            setup: setup::SetupCommand::new(),
+            prefs: prefs::PrefsCommand::new(),
+            session: session::SessionCommand::new(),
         }
     }
 
@@ -222,10 +337,21 @@ impl CommandRouter {
                     command_info.name, command_info.prefix
                 );
                 debug!(
-                    "[CommandRouter] Command requires auth: {}",
-                    command_info.requires_auth
+                    "[CommandRouter] Command requires role: {}",
+                    command_info.required_role
                 );
 
+                if let Err(denial) = authorize(
+                    bot.message_role(msg),
+                    &msg.author,
+                    msg.channel_id,
+                    &content.chars().take(200).collect::<String>(),
+                    command_info.required_role,
+                    &command_info.category,
+                ) {
+                    return Some(denial);
+                }
+
                 // Route to appropriate handler based on command name
                 // 🔄 DRY PATTERN: Command name to handler mapping
                 // Critical: This mapping must stay synchronized with AVAILABLE_COMMANDS
@@ -235,6 +361,10 @@ impl CommandRouter {
                     // 📐 SOLID: Both commands use same handler (DRY principle)
                     "agents" => self.claude_agents.handle(content, msg, ctx, bot).await,
                     "claude-agents" => self.claude_agents.handle(content, msg, ctx, bot).await,
+                    "config" => self.guild_config.handle(content, msg, ctx, bot).await,
+                    "setup" => self.setup.handle(content, msg, ctx, bot).await,
+                    "costs" => self.costs.handle(content, msg, ctx, bot).await,
+                    "loglevel" => self.log_level.handle(content, msg, ctx, bot).await,
                     "debug" => self.debug.handle(content, msg, ctx, bot).await,
                     "debug progress" => self.debug_progress.handle(content, msg, ctx, bot).await,
                     "help" => self.help.handle(content, msg, ctx, bot).await,
@@ -242,6 +372,12 @@ impl CommandRouter {
                     "ratelimit" => self.rate_limit.handle(content, msg, ctx, bot).await,
                     "roles" => self.roles.handle(content, msg, ctx, bot).await,
                     "security" => self.security.handle(content, msg, ctx, bot).await,
+                    "tasks" => self.tasks.handle(content, msg, ctx, bot).await,
+                    "task" => self.tasks.handle(content, msg, ctx, bot).await,
+                    "schedule" => self.schedule.handle(content, msg, ctx, bot).await,
+                    "compose" | "done" => self.compose.handle(content, msg, ctx, bot).await,
+                    "prefs" => self.prefs.handle(content, msg, ctx, bot).await,
+                    "session" => self.session.handle(content, msg, ctx, bot).await,
                     "update" => self.self_update.handle(content, msg, ctx, bot).await,
                     "self-update" => self.self_update.handle(content, msg, ctx, bot).await,
                     _ => {
@@ -268,4 +404,93 @@ impl CommandRouter {
         debug!("[CommandRouter] No matching command found for: {}", content);
         None
     }
+
+    /// Run a `/spiral` command the caller has already authorized; the options arrive parsed,
+    /// so handlers get typed values instead of the message text
+    /// `/spiral task` is not routed here: it runs through the bot, which owns the agents
+    pub async fn route_slash(
+        &self,
+        command: &slash::SlashCommand,
+        user: &User,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        debug!(
+            "[CommandRouter] Routing {} from user: {} ({})",
+            command.audit_target(),
+            user.name,
+            user.id
+        );
+        match command {
+            slash::SlashCommand::Task { .. } => None,
+            slash::SlashCommand::Status { task_id } => {
+                Some(bot.status_report(task_id.as_deref()).await)
+            }
+            slash::SlashCommand::Update(action) => {
+                Some(self.self_update.respond(action, user.id.get(), bot))
+            }
+            slash::SlashCommand::Agents => Some(self.claude_agents.list_claude_agents()),
+            slash::SlashCommand::Tasks => Some(self.tasks.list_for(user.id.get(), bot).await),
+        }
+    }
+}
+
+/// 🎭 AGENT NAME: An agent named like the bot's roles, e.g. `SpiralQA` or `@SpiralQA`
+/// None when the word names no agent; Err when it looks like one but is unknown. Bare
+/// keywords are not accepted, so a request starting with "project" keeps its first word
+pub(crate) fn parse_agent_name(word: &str) -> Option<Result<AgentType, String>> {
+    let name = word.trim_start_matches('@');
+    let head = name.get(..6)?;
+    if !head.eq_ignore_ascii_case("spiral") {
+        return None;
+    }
+    Some(AgentType::from_mention(&name[6..]).ok_or_else(|| format!("❓ Unknown agent `{word}`")))
+}
+
+/// 🎭 ROLE CHECK: The bot has already refused users without any role; here the user's
+/// role, resolved for the guild they wrote in, must also reach the command's requirement.
+/// Shared by `!spiral` and `/spiral` commands so both are checked and audited the same
+/// way; Err is the denial to send
+pub(crate) fn authorize(
+    role: Option<Role>,
+    user: &User,
+    channel_id: ChannelId,
+    invocation: &str,
+    required_role: Role,
+    category: &CommandCategory,
+) -> Result<(), String> {
+    let permitted = role.is_some_and(|role| role.permits(required_role));
+    if !permitted {
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::AccessDenied,
+                AuditSource::Discord,
+                invocation,
+            )
+            .with_actor(format!("{} ({})", user.name, user.id))
+            .with_client(format!("channel {channel_id}"))
+            .with_details(format!("Requires {required_role} role")),
+        );
+        return Err(format!(
+            "{} (requires `{}`)",
+            messages::security::INSUFFICIENT_ROLE,
+            required_role
+        ));
+    }
+
+    // 📜 AUDIT: Privileged commands are recorded whoever runs them
+    if matches!(
+        category,
+        CommandCategory::Admin | CommandCategory::Security | CommandCategory::Updates
+    ) {
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::AdminAction,
+                AuditSource::Discord,
+                invocation,
+            )
+            .with_actor(format!("{} ({})", user.name, user.id))
+            .with_client(format!("channel {channel_id}")),
+        );
+    }
+    Ok(())
 }
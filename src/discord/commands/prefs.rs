@@ -0,0 +1,196 @@
+use super::CommandHandler;
+use crate::discord::locale::Locale;
+use crate::discord::spiral_constellation_bot::{AgentPersona, SpiralConstellationBot};
+use crate::discord::user_prefs::{UserPrefs, Verbosity};
+use crate::models::AgentType;
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::info;
+
+pub const PREFS_COMMAND: &str = "!spiral prefs";
+
+const USAGE: &str = "**🙋 Your Preferences**\n\n\
+    • `!spiral prefs` - Show your preferences\n\
+    • `!spiral prefs agent <dev|pm|qa>|default` - Agent for your requests that name none\n\
+    • `!spiral prefs verbosity <brief|normal|detailed>|default` - How much of an answer you see\n\
+    • `!spiral prefs language <en|nl|de>|default` - Language Spiral replies to you in\n\
+    • `!spiral prefs threads <on|off>|default` - Whether your tasks get their own thread\n\
+    • `!spiral prefs reset` - Forget all of them\n\n\
+    *`default` uses the server's setting. Preferences apply in every server and in DMs.*";
+
+/// One change to a user's preferences, as parsed from the command
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefChange {
+    Agent(Option<AgentType>),
+    Verbosity(Option<Verbosity>),
+    Locale(Option<Locale>),
+    TaskThreads(Option<bool>),
+    Reset,
+}
+
+impl PrefChange {
+    fn apply(self, prefs: &mut UserPrefs) {
+        match self {
+            PrefChange::Agent(agent) => prefs.preferred_agent = agent,
+            PrefChange::Verbosity(verbosity) => prefs.verbosity = verbosity,
+            PrefChange::Locale(locale) => prefs.locale = locale,
+            PrefChange::TaskThreads(threads) => prefs.task_threads = threads,
+            PrefChange::Reset => *prefs = UserPrefs::default(),
+        }
+    }
+}
+
+/// Parse the text after `!spiral prefs`; Ok(None) asks for the current preferences
+pub fn parse_change(args: &str) -> Result<Option<PrefChange>, String> {
+    let mut words = args.split_whitespace();
+    let Some(pref) = words.next() else {
+        return Ok(None);
+    };
+    let values: Vec<&str> = words.collect();
+    if pref.eq_ignore_ascii_case("reset") && values.is_empty() {
+        return Ok(Some(PrefChange::Reset));
+    }
+    let [value] = values.as_slice() else {
+        return Err(format!("❌ `{pref}` takes exactly one value.\n\n{USAGE}"));
+    };
+    let is_default = value.eq_ignore_ascii_case("default");
+
+    match pref.to_lowercase().as_str() {
+        "agent" if is_default => Ok(Some(PrefChange::Agent(None))),
+        "agent" => AgentType::from_mention(value)
+            .map(|agent| Some(PrefChange::Agent(Some(agent))))
+            .ok_or_else(|| format!("❌ Unknown agent `{value}`; use dev, pm or qa.")),
+        "verbosity" if is_default => Ok(Some(PrefChange::Verbosity(None))),
+        "verbosity" => value
+            .parse()
+            .map(|verbosity| Some(PrefChange::Verbosity(Some(verbosity))))
+            .map_err(|e| format!("❌ {e}")),
+        "language" | "locale" if is_default => Ok(Some(PrefChange::Locale(None))),
+        "language" | "locale" => value
+            .parse()
+            .map(|locale| Some(PrefChange::Locale(Some(locale))))
+            .map_err(|e| format!("❌ {e}")),
+        "threads" => match value.to_lowercase().as_str() {
+            "default" => Ok(Some(PrefChange::TaskThreads(None))),
+            "on" => Ok(Some(PrefChange::TaskThreads(Some(true)))),
+            "off" => Ok(Some(PrefChange::TaskThreads(Some(false)))),
+            _ => Err("❌ Task threads are `on`, `off` or `default`.".to_string()),
+        },
+        _ => Err(format!("❌ Unknown preference `{pref}`.\n\n{USAGE}")),
+    }
+}
+
+pub struct PrefsCommand {
+    // Preferences live in the bot's user preference store
+}
+
+impl Default for PrefsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrefsCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn format_prefs(prefs: &UserPrefs) -> String {
+        let agent = match &prefs.preferred_agent {
+            Some(agent) => AgentPersona::for_agent_type(agent).name.to_string(),
+            None => "server default".to_string(),
+        };
+        let verbosity = prefs.verbosity();
+        let language = match prefs.locale {
+            Some(locale) => format!("`{locale}`"),
+            None => "server default".to_string(),
+        };
+        let threads = match prefs.task_threads {
+            Some(true) => "on",
+            Some(false) => "off",
+            None => "server default",
+        };
+        format!(
+            "**🙋 Your Preferences**\n\n\
+            **Agent:** {agent}\n\
+            **Verbosity:** `{verbosity}`\n\
+            **Language:** {language}\n\
+            **Task threads:** {threads}\n\n\
+            *Use `!spiral prefs help` to change them.*"
+        )
+    }
+}
+
+impl CommandHandler for PrefsCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let args = content
+            .get(PREFS_COMMAND.len()..)
+            .unwrap_or_default()
+            .trim();
+        if args.eq_ignore_ascii_case("help") {
+            return Some(USAGE.to_string());
+        }
+
+        let user_id = msg.author.id.get();
+        let change = match parse_change(args) {
+            Ok(Some(change)) => change,
+            Ok(None) => return Some(Self::format_prefs(&bot.user_prefs().get(user_id))),
+            Err(message) => return Some(message),
+        };
+        info!(
+            "[PrefsCommand] {} ({}) changed their preferences: {:?}",
+            msg.author.name, msg.author.id, change
+        );
+        let prefs = bot
+            .user_prefs()
+            .update(user_id, |prefs| change.apply(prefs));
+        Some(format!("✅ Saved.\n\n{}", Self::format_prefs(&prefs)))
+    }
+
+    fn command_prefix(&self) -> &str {
+        PREFS_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Show and change your own Spiral preferences"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_change_reads_each_preference() {
+        assert_eq!(parse_change(""), Ok(None));
+        assert_eq!(
+            parse_change("agent qa"),
+            Ok(Some(PrefChange::Agent(Some(AgentType::QualityAssurance))))
+        );
+        assert_eq!(
+            parse_change("verbosity brief"),
+            Ok(Some(PrefChange::Verbosity(Some(Verbosity::Brief))))
+        );
+        assert_eq!(
+            parse_change("language nl"),
+            Ok(Some(PrefChange::Locale(Some(Locale::Nl))))
+        );
+        assert_eq!(
+            parse_change("threads off"),
+            Ok(Some(PrefChange::TaskThreads(Some(false))))
+        );
+        assert_eq!(
+            parse_change("threads default"),
+            Ok(Some(PrefChange::TaskThreads(None)))
+        );
+        assert_eq!(parse_change("reset"), Ok(Some(PrefChange::Reset)));
+        assert!(parse_change("agent sales").is_err());
+        assert!(parse_change("verbosity").is_err());
+        assert!(parse_change("colour blue").is_err());
+    }
+}
@@ -1,10 +1,16 @@
 use super::CommandHandler;
+use crate::audit::AuditSource;
 use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
+use crate::rate_limit::tiers::{parse_subject, RateLimitTier};
 use serenity::{model::channel::Message, prelude::Context};
-use tracing::{info, warn};
+use tracing::info;
+
+const USAGE: &str = "Usage: `!spiral ratelimit [@user]` to show limits, \
+                     `!spiral ratelimit tier <@user|user_id|api-key:<key_id>> <default|trusted|unlimited>` \
+                     to grant a tier, or `!spiral ratelimit tiers` to list grants";
 
 pub struct RateLimitCommand {
-    // Rate limit command doesn't need state for now
+    // Tiers live in the bot's shared tier store; nothing to keep here
 }
 
 impl Default for RateLimitCommand {
@@ -18,55 +24,72 @@ impl RateLimitCommand {
         Self {}
     }
 
-    /// Generate rate limit status for a user  
-    fn generate_rate_limit_status(&self, _user_id: u64, username: &str) -> String {
-        // Rate limit data access not yet implemented in secure message handler
-        format!(
-            "⏱️ **Rate Limit Status for {username}**\n\n\
-            **Current Status:** 🟢 Good Standing\n\
-            **Requests Used:** ❓ Data not available\n\
-            **Reset Time:** ❓ Data not available\n\
-            **Daily Limit:** ❓ Data not available\n\n\
-            **Notes:**\n\
-            • Rate limiting is active to prevent abuse\n\
-            • Limits reset daily at midnight UTC\n\
-            • Contact admin if you need higher limits\n\n\
-            *Rate limit data collection not yet implemented* ⚠️"
-        )
+    /// The subject a tier is granted to: a user mention, a user ID or `api-key:<key_id>`
+    fn parse_target(raw: &str) -> Result<String, String> {
+        let mention = raw
+            .strip_prefix("<@")
+            .and_then(|rest| rest.strip_suffix('>'))
+            .map(|id| id.trim_start_matches('!'));
+        parse_subject(mention.unwrap_or(raw))
     }
 
-    /// Generate rate limit reset confirmation
-    fn generate_reset_confirmation(&self, target_user: &str) -> String {
-        format!(
-            "🔄 **Rate Limit Reset**\n\n\
-            **Target User:** {target_user}\n\
-            **Action:** Rate limit reset requested\n\
-            **Status:** ❓ Reset functionality not implemented\n\n\
-            **What would happen:**\n\
-            • User's rate limit counters would be cleared\n\
-            • Fresh daily allowance would be granted\n\
-            • User would be notified of the reset\n\n\
-            *Rate limit reset not yet implemented* ⚠️"
-        )
+    /// A user's tier and what is left of their message limit
+    async fn status(bot: &SpiralConstellationBot, user_id: u64, name: &str) -> String {
+        let (tier, remaining) = bot.rate_limit_status(user_id).await;
+        let limits = match remaining {
+            Some((remaining, bucket)) => format!(
+                "**Messages available now:** {remaining} of {}\n\
+                 **Refill rate:** {} per minute",
+                bucket.burst, bucket.refill_per_minute
+            ),
+            None => "**Limits:** none".to_string(),
+        };
+        format!("⏱️ **Rate Limit Status for {name}**\n\n**Tier:** {tier}\n{limits}")
     }
 
-    /// Parse mentioned user from command
-    fn parse_mentioned_user(&self, content: &str, msg: &Message) -> Option<(u64, String)> {
-        // Check for @user mention
-        if !msg.mentions.is_empty() {
-            let mentioned_user = &msg.mentions[0];
-            return Some((mentioned_user.id.get(), mentioned_user.name.clone()));
-        }
+    /// Put a user or API key on a tier; audited by the tier store
+    fn set_tier(bot: &SpiralConstellationBot, msg: &Message, args: &[&str]) -> String {
+        let [target, tier] = args else {
+            return format!("❌ {USAGE}");
+        };
+        let subject = match Self::parse_target(target) {
+            Ok(subject) => subject,
+            Err(e) => return format!("❌ {e}\n{USAGE}"),
+        };
+        let tier = match tier.parse::<RateLimitTier>() {
+            Ok(tier) => tier,
+            Err(e) => return format!("❌ {e}"),
+        };
+        let granted_by = format!("{} ({})", msg.author.name, msg.author.id);
+        let previous =
+            bot.rate_limit_tiers()
+                .set(&subject, tier, AuditSource::Discord, &granted_by);
+        let shown = match subject.parse::<u64>() {
+            Ok(user_id) => format!("<@{user_id}>"),
+            Err(_) => format!("`{subject}`"),
+        };
+        format!("✅ Rate limit tier of {shown} changed from **{previous}** to **{tier}**.")
+    }
 
-        // Check for raw user ID
-        let parts: Vec<&str> = content.split_whitespace().collect();
-        if parts.len() >= 4 {
-            if let Ok(user_id) = parts[3].parse::<u64>() {
-                return Some((user_id, format!("User {user_id}")));
-            }
+    fn list_tiers(bot: &SpiralConstellationBot) -> String {
+        let grants = bot.rate_limit_tiers().grants();
+        if grants.is_empty() {
+            return "🎟️ Everyone is on the default rate limit tier.".to_string();
         }
-
-        None
+        let mut response = String::from("🎟️ **Rate Limit Tiers**\n\n");
+        for (subject, grant) in grants {
+            let shown = match subject.parse::<u64>() {
+                Ok(user_id) => format!("<@{user_id}>"),
+                Err(_) => format!("`{subject}`"),
+            };
+            response.push_str(&format!(
+                "• {shown}: **{}** (granted by {} on {})\n",
+                grant.tier,
+                grant.granted_by,
+                grant.granted_at.format("%Y-%m-%d")
+            ));
+        }
+        response
     }
 }
 
@@ -76,59 +99,37 @@ impl CommandHandler for RateLimitCommand {
         content: &str,
         msg: &Message,
         _ctx: &Context,
-        _bot: &SpiralConstellationBot,
+        bot: &SpiralConstellationBot,
     ) -> Option<String> {
-        // FLOW: Parse action → Validate target → Execute → Respond
-        // 1. Parse ratelimit action (check, reset, etc.)
-        // 2. Validate target user (self vs mentioned user)
-        // 3. Execute the appropriate action
-        // 4. Return formatted response
-
-        const RATELIMIT_RESET: &str = "!spiral ratelimit reset";
-        const RATELIMIT_CHECK: &str = "!spiral ratelimit";
-
-        let content_lower = content.to_lowercase();
+        let args: Vec<&str> = content
+            .get(self.command_prefix().len()..)
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
 
-        // Match ratelimit command type using const patterns
-        match content_lower.as_str() {
-            cmd if cmd.starts_with(RATELIMIT_RESET) => {
-                if let Some((target_user_id, target_username)) =
-                    self.parse_mentioned_user(content, msg)
-                {
-                    info!(
-                        "[RateLimitCommand] Admin {} resetting rate limit for user {} ({})",
-                        msg.author.name, target_username, target_user_id
-                    );
-                    Some(self.generate_reset_confirmation(&target_username))
-                } else {
-                    warn!(
-                        "[RateLimitCommand] Invalid reset command from {}: missing user target",
-                        msg.author.name
-                    );
-                    Some("❌ Usage: `!spiral ratelimit reset @user` or `!spiral ratelimit reset <user_id>`".to_string())
-                }
+        match args.as_slice() {
+            [] => Some(Self::status(bot, msg.author.id.get(), &msg.author.name).await),
+            [command, rest @ ..] if command.eq_ignore_ascii_case("tier") => {
+                Some(Self::set_tier(bot, msg, rest))
             }
-            cmd if cmd.starts_with(RATELIMIT_CHECK) => {
-                if let Some((target_user_id, target_username)) =
-                    self.parse_mentioned_user(content, msg)
-                {
-                    // Admin checking another user's rate limit
+            [command] if command.eq_ignore_ascii_case("tiers") => Some(Self::list_tiers(bot)),
+            [target] => match Self::parse_target(target).map(|s| s.parse::<u64>()) {
+                Ok(Ok(user_id)) => {
                     info!(
-                        "[RateLimitCommand] Admin {} checking rate limit for user {} ({})",
-                        msg.author.name, target_username, target_user_id
+                        "[RateLimitCommand] {} checking rate limit of user {}",
+                        msg.author.name, user_id
                     );
-                    Some(self.generate_rate_limit_status(target_user_id, &target_username))
-                } else {
-                    // User checking their own rate limit
-                    info!(
-                        "[RateLimitCommand] User {} ({}) checking own rate limit",
-                        msg.author.name,
-                        msg.author.id.get()
-                    );
-                    Some(self.generate_rate_limit_status(msg.author.id.get(), &msg.author.name))
+                    let name = msg
+                        .mentions
+                        .iter()
+                        .find(|user| user.id.get() == user_id)
+                        .map(|user| user.name.clone())
+                        .unwrap_or_else(|| format!("User {user_id}"));
+                    Some(Self::status(bot, user_id, &name).await)
                 }
-            }
-            _ => None,
+                _ => Some(format!("❌ {USAGE}")),
+            },
+            _ => Some(format!("❌ {USAGE}")),
         }
     }
 
@@ -137,6 +138,23 @@ impl CommandHandler for RateLimitCommand {
     }
 
     fn description(&self) -> &str {
-        "Check and manage user rate limits with admin controls"
+        "Show rate limits and grant users or API keys a rate limit tier"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_are_mentions_user_ids_or_api_keys() {
+        assert_eq!(RateLimitCommand::parse_target("<@123>").unwrap(), "123");
+        assert_eq!(RateLimitCommand::parse_target("<@!123>").unwrap(), "123");
+        assert_eq!(RateLimitCommand::parse_target("123").unwrap(), "123");
+        assert_eq!(
+            RateLimitCommand::parse_target("api-key:ci").unwrap(),
+            "api-key:ci"
+        );
+        assert!(RateLimitCommand::parse_target("<#123>").is_err());
     }
 }
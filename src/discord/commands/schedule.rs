@@ -0,0 +1,351 @@
+use super::{parse_agent_name, CommandHandler};
+use crate::agents::orchestrator::scheduler::CronExpression;
+use crate::agents::orchestrator::ScheduledTask;
+use crate::auth::Role;
+use crate::constants::{
+    DISCORD_SCHEDULE_MIN_INTERVAL_MINUTES, DISCORD_TASK_ID_DISPLAY_LENGTH,
+    MAX_DISCORD_SCHEDULES_PER_USER,
+};
+use crate::discord::spiral_constellation_bot::{
+    AgentPersona, SpiralConstellationBot, AUTHOR_CONTEXT_KEY, GUILD_CONTEXT_KEY,
+};
+use crate::models::AgentType;
+use chrono::{DateTime, Duration, Utc};
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::info;
+
+pub const SCHEDULE_COMMAND: &str = "!spiral schedule";
+
+/// Runs checked against the minimum interval; enough to see a whole day of an hourly rule
+const INTERVAL_CHECK_RUNS: usize = 48;
+
+const USAGE: &str = "**📅 Scheduled Tasks**\n\n\
+    • `!spiral schedule <cron> [SpiralDev|SpiralPM|SpiralQA] <request>` - Run a task on a schedule\n\
+    • `!spiral schedule list` - Schedules in this server\n\
+    • `!spiral schedule remove <id>` - Stop a schedule\n\n\
+    The cron expression has 5 fields in UTC: minute hour day-of-month month day-of-week.\n\
+    Example: `!spiral schedule 0 3 * * * SpiralQA Audit the dependencies for advisories`\n\n\
+    *Results are posted in the channel the schedule was created in.*";
+
+/// What `!spiral schedule` was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleRequest {
+    List,
+    Remove(String),
+    Add {
+        cron: String,
+        agent_type: Option<AgentType>,
+        request: String,
+    },
+}
+
+/// Parse the text after `!spiral schedule`
+pub fn parse_request(args: &str, now: DateTime<Utc>) -> Result<ScheduleRequest, String> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words.as_slice() {
+        [] | ["list"] => return Ok(ScheduleRequest::List),
+        ["help"] => return Err(USAGE.to_string()),
+        ["remove", id] => return Ok(ScheduleRequest::Remove(id.to_string())),
+        ["remove", ..] => return Err("❌ Usage: `!spiral schedule remove <id>`".to_string()),
+        _ => {}
+    }
+    if words.len() < 6 {
+        return Err(format!(
+            "❌ Give a 5-field cron expression and the request.\n\n{USAGE}"
+        ));
+    }
+
+    let cron = words[..5].join(" ");
+    let expression = CronExpression::parse(&cron).map_err(|e| format!("❌ {e}"))?;
+    check_interval(&expression, now)?;
+
+    let (agent_type, request_words) = match parse_agent_name(words[5]) {
+        Some(agent_type) => (Some(agent_type?), &words[6..]),
+        None => (None, &words[5..]),
+    };
+    if request_words.is_empty() {
+        return Err("❌ Please describe the task.".to_string());
+    }
+
+    Ok(ScheduleRequest::Add {
+        cron,
+        agent_type,
+        request: request_words.join(" "),
+    })
+}
+
+/// Refuse schedules that would run more often than the minimum interval
+pub fn check_interval(expression: &CronExpression, now: DateTime<Utc>) -> Result<(), String> {
+    let min_interval = Duration::minutes(DISCORD_SCHEDULE_MIN_INTERVAL_MINUTES);
+    let mut previous = expression
+        .next_after(now)
+        .ok_or("❌ That cron expression never matches a future time.")?;
+    for _ in 0..INTERVAL_CHECK_RUNS {
+        let Some(next) = expression.next_after(previous) else {
+            break;
+        };
+        if next - previous < min_interval {
+            return Err(format!(
+                "❌ Schedules may run at most every {DISCORD_SCHEDULE_MIN_INTERVAL_MINUTES} minutes; \
+                 this one runs at {} and again at {}.",
+                previous.format("%H:%M"),
+                next.format("%H:%M")
+            ));
+        }
+        previous = next;
+    }
+    Ok(())
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..DISCORD_TASK_ID_DISPLAY_LENGTH).unwrap_or(id)
+}
+
+/// One line per schedule, soonest first
+pub fn format_schedules(schedules: &[ScheduledTask]) -> String {
+    if schedules.is_empty() {
+        return format!("📅 No scheduled tasks here.\n\n{USAGE}");
+    }
+    let mut list = "📅 **Scheduled Tasks**\n\n".to_string();
+    for schedule in schedules {
+        let persona = AgentPersona::for_agent_type(&schedule.template.agent_type);
+        let cron = match &schedule.schedule {
+            crate::agents::orchestrator::TaskSchedule::Cron(cron) => cron.as_str(),
+            crate::agents::orchestrator::TaskSchedule::RunAt(_) => "once",
+        };
+        let requester = schedule
+            .template
+            .context
+            .get(AUTHOR_CONTEXT_KEY)
+            .map(|id| format!(" · by <@{id}>"))
+            .unwrap_or_default();
+        list.push_str(&format!(
+            "`{}` {} **{}** · `{cron}` · next <t:{}:R> · {} runs{requester}\n",
+            short_id(&schedule.id),
+            persona.emoji,
+            persona.name,
+            schedule.next_run_at.timestamp(),
+            schedule.run_count
+        ));
+    }
+    list.push_str("\n*Remove one with `!spiral schedule remove <id>`.*");
+    list
+}
+
+pub struct ScheduleCommand {
+    // Schedules live in the orchestrator's scheduler; nothing to keep here
+}
+
+impl Default for ScheduleCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScheduleCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Schedules visible from where the message was sent: the server's, or in a direct
+    /// message the sender's own
+    async fn visible_schedules(
+        msg: &Message,
+        bot: &SpiralConstellationBot,
+    ) -> Option<Vec<ScheduledTask>> {
+        let mut schedules = bot.discord_schedules().await?;
+        let author = msg.author.id.get().to_string();
+        let guild = msg.guild_id.map(|id| id.get().to_string());
+        schedules.retain(|schedule| {
+            let context = &schedule.template.context;
+            match &guild {
+                Some(guild) => context.get(GUILD_CONTEXT_KEY) == Some(guild),
+                None => {
+                    !context.contains_key(GUILD_CONTEXT_KEY)
+                        && context.get(AUTHOR_CONTEXT_KEY) == Some(&author)
+                }
+            }
+        });
+        Some(schedules)
+    }
+
+    async fn remove(&self, id: &str, msg: &Message, bot: &SpiralConstellationBot) -> String {
+        let Some(schedules) = Self::visible_schedules(msg, bot).await else {
+            return "📅 Schedules are run by the orchestrator, which this bot is running without."
+                .to_string();
+        };
+        let matches: Vec<&ScheduledTask> = schedules
+            .iter()
+            .filter(|schedule| schedule.id.starts_with(id))
+            .collect();
+        let schedule = match matches.as_slice() {
+            [schedule] => schedule,
+            [] => return format!("❓ No schedule `{id}` here. See `!spiral schedule list`."),
+            _ => return format!("❓ `{id}` matches several schedules; give more of the ID."),
+        };
+
+        let is_owner = schedule.template.context.get(AUTHOR_CONTEXT_KEY)
+            == Some(&msg.author.id.get().to_string());
+        let is_admin = bot
+            .message_role(msg)
+            .is_some_and(|role| role.permits(Role::Admin));
+        if !is_owner && !is_admin {
+            return format!(
+                "🔒 Only whoever created schedule `{}` or a `{}` can remove it.",
+                short_id(&schedule.id),
+                Role::Admin
+            );
+        }
+
+        match bot.remove_schedule(&schedule.id).await {
+            Some(_) => {
+                info!(
+                    "[ScheduleCommand] {} ({}) removed schedule {}",
+                    msg.author.name, msg.author.id, schedule.id
+                );
+                format!("🗑️ Schedule `{}` removed.", short_id(&schedule.id))
+            }
+            None => format!("❓ Schedule `{}` is already gone.", short_id(&schedule.id)),
+        }
+    }
+
+    async fn add(
+        &self,
+        cron: &str,
+        agent_type: Option<AgentType>,
+        request: &str,
+        msg: &Message,
+        bot: &SpiralConstellationBot,
+    ) -> String {
+        let author = msg.author.id.get().to_string();
+        let owned = bot
+            .discord_schedules()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|schedule| schedule.template.context.get(AUTHOR_CONTEXT_KEY) == Some(&author))
+            .count();
+        if owned >= MAX_DISCORD_SCHEDULES_PER_USER {
+            return format!(
+                "❌ You already have {owned} schedules, the most allowed. Remove one first."
+            );
+        }
+
+        let agent_type = agent_type
+            .or_else(|| {
+                msg.guild_id
+                    .and_then(|guild_id| bot.guild_settings().get(guild_id.get()).default_agent)
+            })
+            .unwrap_or(AgentType::SoftwareDeveloper);
+        let persona = AgentPersona::for_agent_type(&agent_type);
+        match bot
+            .schedule_from_message(msg, cron, agent_type.clone(), request)
+            .await
+        {
+            Ok(schedule) => {
+                info!(
+                    "[ScheduleCommand] {} ({}) scheduled {} for {:?} on `{}`",
+                    msg.author.name, msg.author.id, schedule.id, agent_type, cron
+                );
+                format!(
+                    "📅 Scheduled `{}`: {} **{}** runs `{cron}` (UTC), first <t:{}:R>. \
+                     Results are posted here.",
+                    short_id(&schedule.id),
+                    persona.emoji,
+                    persona.name,
+                    schedule.next_run_at.timestamp()
+                )
+            }
+            Err(message) => message,
+        }
+    }
+}
+
+impl CommandHandler for ScheduleCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let args = content
+            .trim()
+            .get(SCHEDULE_COMMAND.len()..)
+            .unwrap_or_default()
+            .trim();
+        let response = match parse_request(args, Utc::now()) {
+            Ok(ScheduleRequest::List) => match Self::visible_schedules(msg, bot).await {
+                Some(schedules) => format_schedules(&schedules),
+                None => {
+                    "📅 Schedules are run by the orchestrator, which this bot is running without."
+                        .to_string()
+                }
+            },
+            Ok(ScheduleRequest::Remove(id)) => self.remove(&id, msg, bot).await,
+            Ok(ScheduleRequest::Add {
+                cron,
+                agent_type,
+                request,
+            }) => self.add(&cron, agent_type, &request, msg, bot).await,
+            Err(message) => message,
+        };
+        Some(response)
+    }
+
+    fn command_prefix(&self) -> &str {
+        SCHEDULE_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Run agent tasks on a recurring schedule"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parses_schedule_requests() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(parse_request("", now), Ok(ScheduleRequest::List));
+        assert_eq!(
+            parse_request("remove 1a2b3c4d", now),
+            Ok(ScheduleRequest::Remove("1a2b3c4d".to_string()))
+        );
+        assert_eq!(
+            parse_request("0 3 * * * @SpiralQA Audit the dependencies", now),
+            Ok(ScheduleRequest::Add {
+                cron: "0 3 * * *".to_string(),
+                agent_type: Some(AgentType::QualityAssurance),
+                request: "Audit the dependencies".to_string(),
+            })
+        );
+        // A request starting with an agent keyword keeps the default agent
+        assert_eq!(
+            parse_request("0 9 * * 1 project status summary", now),
+            Ok(ScheduleRequest::Add {
+                cron: "0 9 * * 1".to_string(),
+                agent_type: None,
+                request: "project status summary".to_string(),
+            })
+        );
+        assert!(parse_request("0 3 * * * SpiralBob do things", now).is_err());
+        assert!(parse_request("0 3 * * * SpiralQA", now).is_err());
+        assert!(parse_request("0 3 * * Audit", now).is_err());
+    }
+
+    #[test]
+    fn test_refuses_schedules_more_frequent_than_hourly() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let hourly = CronExpression::parse("0 * * * *").unwrap();
+        assert!(check_interval(&hourly, now).is_ok());
+        let every_minute = CronExpression::parse("* 3 * * *").unwrap();
+        assert!(check_interval(&every_minute, now).is_err());
+        // A burst later in the day is caught too
+        let burst = CronExpression::parse("0,30 9 * * *").unwrap();
+        assert!(check_interval(&burst, now).is_err());
+    }
+}
@@ -84,9 +84,15 @@ impl SecurityCommand {
             metrics.low_confidence_count
         ));
         report.push_str(&format!(
-            "• Malicious Intent Detected: {}\n\n",
+            "• Malicious Intent Detected: {}\n",
             metrics.intent_malicious
         ));
+        report.push_str(&format!(
+            "• Clarifications Asked: {} ({} confirmed, {} cancelled)\n\n",
+            metrics.clarifications_requested,
+            metrics.clarifications_confirmed,
+            metrics.clarifications_cancelled
+        ));
 
         // Recent activity summary
         report.push_str("**🕐 Recent Activity**\n");
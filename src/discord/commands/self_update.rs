@@ -1,4 +1,5 @@
-use super::CommandHandler;
+use super::{slash::UpdateAction, CommandHandler};
+use crate::auth::Role;
 use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
 use serenity::{model::channel::Message, prelude::Context};
 use tracing::info;
@@ -121,8 +122,8 @@ impl SelfUpdateCommand {
     /// Handle retry of a failed update
     fn handle_retry(&self, codename: &str, user_id: u64, bot: &SpiralConstellationBot) -> String {
         // Check authorization
-        if !bot.is_authorized_user(user_id) {
-            return "❌ You are not authorized to retry updates.".to_string();
+        if !bot.has_role(user_id, Role::Admin) {
+            return "❌ Retrying updates requires the admin role.".to_string();
         }
 
         // For now, return a message about the retry being queued
@@ -162,6 +163,20 @@ impl SelfUpdateCommand {
             *Self-improvement is the highest form of evolution* 🌟"
             .to_string()
     }
+
+    /// Answer an update action, however it was parsed
+    pub fn respond(
+        &self,
+        action: &UpdateAction,
+        user_id: u64,
+        bot: &SpiralConstellationBot,
+    ) -> String {
+        match action {
+            UpdateAction::Status => self.generate_manual_update_status(),
+            UpdateAction::Help => self.generate_update_help(),
+            UpdateAction::Retry(codename) => self.handle_retry(codename, user_id, bot),
+        }
+    }
 }
 
 impl CommandHandler for SelfUpdateCommand {
@@ -191,7 +206,8 @@ impl CommandHandler for SelfUpdateCommand {
                     msg.author.name,
                     msg.author.id.get()
                 );
-                return Some(self.handle_retry(codename, msg.author.id.get(), bot));
+                let action = UpdateAction::Retry(codename.to_string());
+                return Some(self.respond(&action, msg.author.id.get(), bot));
             } else {
                 return Some(
                     "❌ Please specify the codename of the update to retry.\n\
@@ -210,7 +226,7 @@ impl CommandHandler for SelfUpdateCommand {
                     msg.author.name,
                     msg.author.id.get()
                 );
-                Some(self.generate_update_help())
+                Some(self.respond(&UpdateAction::Help, msg.author.id.get(), bot))
             }
             cmd if cmd == UPDATE_MANUAL || cmd == "!spiral self-update" => {
                 info!(
@@ -218,7 +234,7 @@ impl CommandHandler for SelfUpdateCommand {
                     msg.author.name,
                     msg.author.id.get()
                 );
-                Some(self.generate_manual_update_status())
+                Some(self.respond(&UpdateAction::Status, msg.author.id.get(), bot))
             }
             _ => None,
         }
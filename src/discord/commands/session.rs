@@ -0,0 +1,204 @@
+use super::CommandHandler;
+use crate::claude_code::sessions::is_valid_shared_session_key;
+use crate::discord::spiral_constellation_bot::{AgentPersona, SpiralConstellationBot};
+use crate::session::Session;
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::{info, warn};
+
+pub const SESSION_COMMAND: &str = "!spiral session";
+
+const USAGE: &str = "**🗂️ Your Session**\n\n\
+    • `!spiral session` - Show your session\n\
+    • `!spiral session workspace <name>` - Run your tasks in one shared workspace\n\
+    • `!spiral session workspace off` - Go back to your session's own workspace\n\
+    • `!spiral session history clear` - Forget your earlier requests; later tasks start without them\n\
+    • `!spiral session end` - End your session and cancel its unfinished tasks; your next task starts a fresh one\n\n\
+    *Every task you start extends your session, and its request and answer are remembered for your next ones. It ends on its own after a while without one.*";
+
+/// One action on the sender's session, as parsed from the command
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionAction {
+    Show,
+    Workspace(Option<String>),
+    ClearHistory,
+    End,
+    Help,
+}
+
+/// Parse the text after `!spiral session`
+pub fn parse_action(args: &str) -> Result<SessionAction, String> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words.as_slice() {
+        [] => Ok(SessionAction::Show),
+        [help] if help.eq_ignore_ascii_case("help") => Ok(SessionAction::Help),
+        [end] if end.eq_ignore_ascii_case("end") => Ok(SessionAction::End),
+        [history, clear]
+            if history.eq_ignore_ascii_case("history") && clear.eq_ignore_ascii_case("clear") =>
+        {
+            Ok(SessionAction::ClearHistory)
+        }
+        [workspace, name] if workspace.eq_ignore_ascii_case("workspace") => {
+            if name.eq_ignore_ascii_case("off") {
+                Ok(SessionAction::Workspace(None))
+            } else if is_valid_shared_session_key(name) {
+                Ok(SessionAction::Workspace(Some(name.to_string())))
+            } else {
+                Err(format!(
+                    "❌ `{name}` can't name a workspace; use letters, digits, `-` and `_` only."
+                ))
+            }
+        }
+        _ => Err(format!("❌ Unknown session action.\n\n{USAGE}")),
+    }
+}
+
+pub struct SessionCommand {
+    // Sessions live in the bot's user session manager
+}
+
+impl Default for SessionCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn format_session(session: &Session) -> String {
+        let agent = match session.agent() {
+            Some(agent) => AgentPersona::for_agent_type(&agent).name.to_string(),
+            None => "none yet".to_string(),
+        };
+        let workspace = match session.workspace() {
+            Some(workspace) => format!("`{workspace}`"),
+            None => "the session's own".to_string(),
+        };
+        let remaining = session.remaining_budget();
+        let left: Vec<String> = [
+            remaining.tasks.map(|tasks| format!("{tasks} tasks")),
+            remaining.cost_usd.map(|cost| format!("${cost:.2}")),
+            remaining
+                .workspace_mb
+                .map(|mb| format!("{mb} MB of workspace")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let budget = if left.is_empty() {
+            "unlimited".to_string()
+        } else {
+            format!("{} left", left.join(", "))
+        };
+        format!(
+            "**🗂️ Your Session** `{}`\n\n\
+            **Started:** <t:{}:R>\n\
+            **Expires:** <t:{}:R>\n\
+            **Agent:** {agent}\n\
+            **Workspace:** {workspace}\n\
+            **Budget:** {budget}\n\
+            **History:** {} earlier request(s) and answer(s)\n\n\
+            *Use `!spiral session help` to change it.*",
+            &session.id.to_string()[..8],
+            session.created_at.timestamp(),
+            session.expires_at.timestamp(),
+            session.history.len(),
+        )
+    }
+}
+
+impl CommandHandler for SessionCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let args = content
+            .get(SESSION_COMMAND.len()..)
+            .unwrap_or_default()
+            .trim();
+        let action = match parse_action(args) {
+            Ok(action) => action,
+            Err(message) => return Some(message),
+        };
+
+        let user_id = msg.author.id.get();
+        let sessions = bot.user_sessions();
+        let result = match action {
+            SessionAction::Help => return Some(USAGE.to_string()),
+            SessionAction::Show => sessions.current(user_id).await.map(|session| {
+                session.as_ref().map_or_else(
+                    || "🗂️ You have no session right now; your next task starts one.".to_string(),
+                    Self::format_session,
+                )
+            }),
+            SessionAction::Workspace(workspace) => {
+                info!(
+                    "[SessionCommand] {} ({}) set their workspace to {:?}",
+                    msg.author.name, msg.author.id, workspace
+                );
+                sessions
+                    .set_workspace(user_id, workspace)
+                    .await
+                    .map(|session| format!("✅ Saved.\n\n{}", Self::format_session(&session)))
+            }
+            SessionAction::ClearHistory => sessions.clear_history(user_id).await.map(|cleared| {
+                match cleared {
+                    Some(_) => "🧹 History cleared; your next task starts without it.".to_string(),
+                    None => "🗂️ You have no session right now; there is no history to clear."
+                        .to_string(),
+                }
+            }),
+            SessionAction::End => sessions.end(user_id).await.map(|ended| {
+                if ended {
+                    "🗂️ Session ended and its unfinished tasks cancelled; your next task starts a fresh one.".to_string()
+                } else {
+                    "🗂️ You have no session to end.".to_string()
+                }
+            }),
+        };
+        Some(result.unwrap_or_else(|e| {
+            warn!("[SessionCommand] Session store failed: {}", e);
+            "❌ Your session couldn't be reached right now; try again shortly.".to_string()
+        }))
+    }
+
+    fn command_prefix(&self) -> &str {
+        SESSION_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Show and change your session"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_action_reads_each_action() {
+        assert_eq!(parse_action(""), Ok(SessionAction::Show));
+        assert_eq!(parse_action("END"), Ok(SessionAction::End));
+        assert_eq!(
+            parse_action("workspace feature-x"),
+            Ok(SessionAction::Workspace(Some("feature-x".to_string())))
+        );
+        assert_eq!(
+            parse_action("workspace off"),
+            Ok(SessionAction::Workspace(None))
+        );
+        assert!(parse_action("workspace ../etc").is_err());
+        assert!(parse_action("workspace").is_err());
+        assert_eq!(
+            parse_action("history clear"),
+            Ok(SessionAction::ClearHistory)
+        );
+        assert!(parse_action("history").is_err());
+        assert!(parse_action("restart").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use super::guild_config::GuildConfigCommand;
+use super::CommandHandler;
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSource};
+use crate::auth::Role;
+use crate::discord::messages;
+use crate::discord::setup_wizard::SetupProgress;
+use crate::discord::spiral_constellation_bot::SpiralConstellationBot;
+use serenity::{
+    model::{channel::Message, id::GuildId, permissions::Permissions},
+    prelude::Context,
+};
+use tracing::{info, warn};
+
+pub const SETUP_COMMAND: &str = "!spiral setup";
+
+/// What the bot needs each of its server permissions for
+const REQUIRED_PERMISSIONS: [(Permissions, &str); 9] = [
+    (Permissions::VIEW_CHANNEL, "View Channels"),
+    (Permissions::SEND_MESSAGES, "Send Messages"),
+    (Permissions::EMBED_LINKS, "Embed Links (task results)"),
+    (
+        Permissions::ATTACH_FILES,
+        "Attach Files (files tasks produce)",
+    ),
+    (Permissions::ADD_REACTIONS, "Add Reactions (task reactions)"),
+    (Permissions::READ_MESSAGE_HISTORY, "Read Message History"),
+    (Permissions::MANAGE_ROLES, "Manage Roles (agent roles)"),
+    (
+        Permissions::CREATE_PUBLIC_THREADS,
+        "Create Public Threads (task threads)",
+    ),
+    (
+        Permissions::SEND_MESSAGES_IN_THREADS,
+        "Send Messages in Threads (task threads)",
+    ),
+];
+
+/// The permissions the bot lacks, named as Discord's role settings show them
+pub fn missing_permissions(granted: Permissions) -> Vec<&'static str> {
+    if granted.administrator() {
+        return Vec::new();
+    }
+    REQUIRED_PERMISSIONS
+        .iter()
+        .filter(|(permission, _)| !granted.contains(*permission))
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+pub struct SetupCommand {
+    // Running wizards live on the bot, which passes answers to them
+}
+
+impl Default for SetupCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetupCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The bot's own server permissions; None when they could not be looked up
+    async fn bot_permissions(ctx: &Context, guild_id: GuildId) -> Option<Permissions> {
+        let bot_user = ctx.http.get_current_user().await.ok()?;
+        let guild = guild_id.to_partial_guild(&ctx.http).await.ok()?;
+        let member = guild_id.member(&ctx.http, bot_user.id).await.ok()?;
+        Some(guild.member_permissions(&member))
+    }
+
+    /// Permission check and role creation, reported before the first question
+    async fn prepare(ctx: &Context, guild_id: GuildId, bot: &SpiralConstellationBot) -> String {
+        let Some(permissions) = Self::bot_permissions(ctx, guild_id).await else {
+            warn!(
+                "[SetupCommand] Could not look up permissions in guild {}",
+                guild_id
+            );
+            return "⚠️ Couldn't check my permissions; agent roles were not created.".to_string();
+        };
+
+        let missing = missing_permissions(permissions);
+        let mut report = if missing.is_empty() {
+            "✅ I have every permission I need.".to_string()
+        } else {
+            format!(
+                "⚠️ **Missing permissions** - grant them to my role:\n{}",
+                missing
+                    .iter()
+                    .map(|name| format!("• {name}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        let roles = if permissions.administrator() || permissions.manage_roles() {
+            match bot.create_agent_roles(ctx, guild_id).await {
+                Ok(roles) if roles.is_empty() => "🎭 The agent roles already exist.".to_string(),
+                Ok(roles) => format!(
+                    "🎭 Created agent roles: {}",
+                    roles
+                        .iter()
+                        .map(|role| format!("<@&{}>", role.id))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Err(e) => format!("⚠️ Couldn't create the agent roles: {e}"),
+            }
+        } else {
+            "🎭 Agent roles were not created without Manage Roles; run `!spiral roles setup` once it is granted.".to_string()
+        };
+        report.push_str(&format!("\n{roles}"));
+        report
+    }
+
+    /// 🧭 SETUP ANSWER: Pass a message to the author's wizard in that channel; the reply to
+    /// post, None when they run no wizard there
+    pub fn answer(msg: &Message, bot: &SpiralConstellationBot) -> Option<String> {
+        let progress =
+            bot.setup_wizards()
+                .answer(msg.channel_id.get(), msg.author.id.get(), &msg.content)?;
+        Some(match progress {
+            SetupProgress::Ask(question) => question,
+            SetupProgress::Cancelled => "🧭 Setup cancelled; no settings were changed.".to_string(),
+            SetupProgress::Finished { guild_id, changes } => {
+                info!(
+                    "[SetupCommand] {} ({}) finished setup of guild {}: {:?}",
+                    msg.author.name, msg.author.id, guild_id, changes
+                );
+                let settings = bot.guild_settings().update(guild_id, |settings| {
+                    for change in changes {
+                        change.apply(settings);
+                    }
+                });
+                format!(
+                    "🎉 **Setup complete!**\n\n{}\n\n\
+                    Spiral admins are configured with `DISCORD_USER_ROLES`. \
+                    Mention an agent such as @SpiralDev to start a task.",
+                    GuildConfigCommand::format_settings(&settings)
+                )
+            }
+        })
+    }
+}
+
+impl CommandHandler for SetupCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let Some(guild_id) = msg.guild_id else {
+            return Some("❌ Setup only works in servers, not direct messages.".into());
+        };
+        let (channel_id, user_id) = (msg.channel_id.get(), msg.author.id.get());
+        let args = content
+            .get(SETUP_COMMAND.len()..)
+            .unwrap_or_default()
+            .trim();
+        if args.eq_ignore_ascii_case("cancel") {
+            return Some(if bot.setup_wizards().cancel(channel_id, user_id) {
+                "🧭 Setup cancelled; no settings were changed.".to_string()
+            } else {
+                "🧭 You are not running a setup here.".to_string()
+            });
+        }
+
+        let role = bot.message_role(msg).unwrap_or(Role::Viewer);
+        if !GuildConfigCommand::may_edit(ctx, msg, guild_id, role).await {
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::AccessDenied,
+                    AuditSource::Discord,
+                    SETUP_COMMAND,
+                )
+                .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                .with_client(format!("channel {}", msg.channel_id))
+                .with_details("Requires admin, or operator with Manage Server"),
+            );
+            return Some(format!(
+                "{} (requires `{}`, or `{}` with Manage Server)",
+                messages::security::INSUFFICIENT_ROLE,
+                Role::Admin,
+                Role::Operator
+            ));
+        }
+
+        info!(
+            "[SetupCommand] {} ({}) started setup of guild {}",
+            msg.author.name, msg.author.id, guild_id
+        );
+        let report = Self::prepare(ctx, guild_id, bot).await;
+        let question = bot
+            .setup_wizards()
+            .start(guild_id.get(), channel_id, user_id);
+        Some(format!(
+            "🧭 **Spiral Setup**\n\n{report}\n\n\
+            A few questions follow; answer each in this channel. \
+            Nothing is saved until the last one.\n\n{question}"
+        ))
+    }
+
+    fn command_prefix(&self) -> &str {
+        SETUP_COMMAND
+    }
+
+    fn description(&self) -> &str {
+        "Guided first-run setup of this server"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_permissions_lists_what_the_bot_lacks() {
+        assert!(missing_permissions(Permissions::ADMINISTRATOR).is_empty());
+        let all = REQUIRED_PERMISSIONS
+            .iter()
+            .fold(Permissions::empty(), |all, (permission, _)| {
+                all | *permission
+            });
+        assert!(missing_permissions(all).is_empty());
+        assert_eq!(
+            missing_permissions(all - Permissions::MANAGE_ROLES),
+            vec!["Manage Roles (agent roles)"]
+        );
+        assert_eq!(
+            missing_permissions(Permissions::empty()).len(),
+            REQUIRED_PERMISSIONS.len()
+        );
+    }
+}
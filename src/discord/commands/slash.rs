@@ -0,0 +1,325 @@
+//! `/spiral` application command
+//!
+//! The slash form of the core `!spiral` commands. Discord validates the options and hands
+//! them over typed, so handlers match on [`SlashCommand`] instead of re-splitting text.
+
+use super::{CommandCategory, AVAILABLE_COMMANDS};
+use crate::auth::Role;
+use crate::discord::message_security::MAX_MESSAGE_LENGTH;
+use crate::models::{AgentType, Priority};
+use serenity::all::{
+    CommandData, CommandOptionType, CreateCommand, CreateCommandOption, ResolvedOption,
+    ResolvedValue,
+};
+
+pub const SPIRAL_COMMAND: &str = "spiral";
+
+/// What `/spiral update` was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateAction {
+    Status,
+    Help,
+    Retry(String),
+}
+
+/// A parsed `/spiral` invocation
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlashCommand {
+    Task {
+        agent_type: AgentType,
+        description: String,
+        priority: Priority,
+    },
+    Status {
+        task_id: Option<String>,
+    },
+    Update(UpdateAction),
+    Agents,
+    Tasks,
+}
+
+impl SlashCommand {
+    /// Read the subcommand and its options; Err is a message for the user
+    pub fn parse(data: &CommandData) -> Result<Self, String> {
+        if data.name != SPIRAL_COMMAND {
+            return Err(format!("❓ Unknown command `/{}`", data.name));
+        }
+        let options = data.options();
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.first()
+        else {
+            return Err("❓ Missing subcommand: use `/spiral task`, `/spiral status`, `/spiral update`, `/spiral agents` or `/spiral tasks`".to_string());
+        };
+
+        match *name {
+            "task" => {
+                let agent = string_option(options, "agent").unwrap_or("dev");
+                let agent_type = AgentType::from_mention(agent)
+                    .ok_or_else(|| format!("❓ Unknown agent `{agent}`"))?;
+                let description = string_option(options, "description")
+                    .map(str::trim)
+                    .filter(|description| !description.is_empty())
+                    .ok_or("❌ Please describe the task.")?
+                    .to_string();
+                let priority = match string_option(options, "priority").unwrap_or("medium") {
+                    "low" => Priority::Low,
+                    "medium" => Priority::Medium,
+                    "high" => Priority::High,
+                    "critical" => Priority::Critical,
+                    other => return Err(format!("❓ Unknown priority `{other}`")),
+                };
+                Ok(Self::Task {
+                    agent_type,
+                    description,
+                    priority,
+                })
+            }
+            "status" => Ok(Self::Status {
+                task_id: string_option(options, "task_id")
+                    .map(str::trim)
+                    .filter(|task_id| !task_id.is_empty())
+                    .map(str::to_string),
+            }),
+            "update" => match string_option(options, "action").unwrap_or("status") {
+                "status" => Ok(Self::Update(UpdateAction::Status)),
+                "help" => Ok(Self::Update(UpdateAction::Help)),
+                "retry" => string_option(options, "codename")
+                    .map(str::trim)
+                    .filter(|codename| !codename.is_empty())
+                    .map(|codename| Self::Update(UpdateAction::Retry(codename.to_string())))
+                    .ok_or_else(|| {
+                        "❌ Please specify the codename of the update to retry.".to_string()
+                    }),
+                other => Err(format!("❓ Unknown update action `{other}`")),
+            },
+            "agents" => Ok(Self::Agents),
+            "tasks" => Ok(Self::Tasks),
+            other => Err(format!("❓ Unknown subcommand `/spiral {other}`")),
+        }
+    }
+
+    /// Lowest role allowed to run the command, the same as its `!spiral` form
+    pub fn required_role(&self) -> Role {
+        match self {
+            // Handing work to agents needs the same role as mentioning them
+            Self::Task { .. } => Role::Operator,
+            Self::Status { .. } => Role::Viewer,
+            Self::Update(_) => message_command_role("update"),
+            Self::Agents => message_command_role("agents"),
+            Self::Tasks => message_command_role("tasks"),
+        }
+    }
+
+    pub fn category(&self) -> CommandCategory {
+        match self {
+            Self::Update(_) => CommandCategory::Updates,
+            Self::Task { .. } | Self::Status { .. } | Self::Agents | Self::Tasks => {
+                CommandCategory::General
+            }
+        }
+    }
+
+    /// How the invocation appears in the audit log; task descriptions are left out
+    pub fn audit_target(&self) -> String {
+        match self {
+            Self::Task { agent_type, .. } => format!("/spiral task {agent_type:?}"),
+            Self::Status {
+                task_id: Some(task_id),
+            } => format!("/spiral status {task_id}"),
+            Self::Status { task_id: None } => "/spiral status".to_string(),
+            Self::Update(UpdateAction::Status) => "/spiral update".to_string(),
+            Self::Update(UpdateAction::Help) => "/spiral update help".to_string(),
+            Self::Update(UpdateAction::Retry(codename)) => {
+                format!("/spiral update retry {codename}")
+            }
+            Self::Agents => "/spiral agents".to_string(),
+            Self::Tasks => "/spiral tasks".to_string(),
+        }
+    }
+}
+
+fn message_command_role(name: &str) -> Role {
+    AVAILABLE_COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+        .map_or(Role::Admin, |command| command.required_role)
+}
+
+fn string_option<'a>(options: &[ResolvedOption<'a>], name: &str) -> Option<&'a str> {
+    options.iter().find_map(|option| match option.value {
+        ResolvedValue::String(value) if option.name == name => Some(value),
+        _ => None,
+    })
+}
+
+/// The `/spiral` command definition registered with Discord
+pub fn register() -> CreateCommand {
+    let task = CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "task",
+        "Hand a task to an agent",
+    )
+    .add_sub_option(
+        CreateCommandOption::new(CommandOptionType::String, "description", "What to do")
+            .required(true)
+            .max_length(MAX_MESSAGE_LENGTH as u16),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            "agent",
+            "Agent to run it (default SpiralDev)",
+        )
+        .add_string_choice("SpiralDev", "dev")
+        .add_string_choice("SpiralPM", "pm")
+        .add_string_choice("SpiralQA", "qa"),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            "priority",
+            "Queue priority (default medium)",
+        )
+        .add_string_choice("Low", "low")
+        .add_string_choice("Medium", "medium")
+        .add_string_choice("High", "high")
+        .add_string_choice("Critical", "critical"),
+    );
+
+    let status = CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "status",
+        "System status, or one task's status",
+    )
+    .add_sub_option(CreateCommandOption::new(
+        CommandOptionType::String,
+        "task_id",
+        "Task to look up",
+    ));
+
+    let update = CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "update",
+        "Self-update system",
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            "action",
+            "What to do (default status)",
+        )
+        .add_string_choice("Status", "status")
+        .add_string_choice("Help", "help")
+        .add_string_choice("Retry a failed update", "retry"),
+    )
+    .add_sub_option(CreateCommandOption::new(
+        CommandOptionType::String,
+        "codename",
+        "Update to retry",
+    ));
+
+    let agents = CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "agents",
+        "List the Claude validation and utility agents",
+    );
+
+    let tasks = CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "tasks",
+        "Your recent tasks with their status and duration",
+    );
+
+    CreateCommand::new(SPIRAL_COMMAND)
+        .description("Spiral Core agents and system")
+        .add_option(task)
+        .add_option(status)
+        .add_option(update)
+        .add_option(agents)
+        .add_option(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn command(subcommand: &str, options: serde_json::Value) -> CommandData {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": "spiral",
+            "type": 1,
+            "options": [{"name": subcommand, "type": 1, "options": options}],
+        }))
+        .unwrap()
+    }
+
+    fn string(name: &str, value: &str) -> serde_json::Value {
+        json!({"name": name, "type": 3, "value": value})
+    }
+
+    #[test]
+    fn test_parses_task_options() {
+        let parsed = SlashCommand::parse(&command(
+            "task",
+            json!([
+                string("description", "  add a health check  "),
+                string("agent", "qa"),
+                string("priority", "high"),
+            ]),
+        ));
+        assert_eq!(
+            parsed,
+            Ok(SlashCommand::Task {
+                agent_type: AgentType::QualityAssurance,
+                description: "add a health check".to_string(),
+                priority: Priority::High,
+            })
+        );
+
+        let defaults =
+            SlashCommand::parse(&command("task", json!([string("description", "fix it")])));
+        assert!(matches!(
+            defaults,
+            Ok(SlashCommand::Task {
+                agent_type: AgentType::SoftwareDeveloper,
+                priority: Priority::Medium,
+                ..
+            })
+        ));
+        assert!(
+            SlashCommand::parse(&command("task", json!([string("description", " ")]))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parses_update_actions_and_roles() {
+        let retry = SlashCommand::parse(&command(
+            "update",
+            json!([string("action", "retry"), string("codename", "brave-otter")]),
+        ))
+        .unwrap();
+        assert_eq!(
+            retry,
+            SlashCommand::Update(UpdateAction::Retry("brave-otter".to_string()))
+        );
+        assert_eq!(retry.required_role(), Role::Admin);
+        assert_eq!(retry.category(), CommandCategory::Updates);
+
+        assert!(
+            SlashCommand::parse(&command("update", json!([string("action", "retry")]))).is_err()
+        );
+        assert_eq!(
+            SlashCommand::parse(&command("update", json!([]))),
+            Ok(SlashCommand::Update(UpdateAction::Status))
+        );
+
+        let status = SlashCommand::parse(&command("status", json!([]))).unwrap();
+        assert_eq!(status, SlashCommand::Status { task_id: None });
+        assert_eq!(status.required_role(), Role::Viewer);
+        assert_eq!(SlashCommand::Agents.required_role(), Role::Viewer);
+    }
+}
@@ -0,0 +1,201 @@
+use super::CommandHandler;
+use crate::agents::orchestrator::AgentTaskRecord;
+use crate::constants::DISCORD_TASK_LIST_LIMIT;
+use crate::discord::spiral_constellation_bot::{AgentPersona, SpiralConstellationBot};
+use crate::discord::task_reply::format_duration;
+use crate::models::{Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use serenity::{model::channel::Message, prelude::Context};
+use tracing::info;
+
+const TASKS_PREFIX: &str = "!spiral tasks";
+const TASK_PREFIX: &str = "!spiral task";
+
+const NO_HISTORY: &str =
+    "📜 Task history is kept by the orchestrator, which this bot is running without.";
+
+pub struct TasksCommand {
+    // History lives in the orchestrator; nothing to keep here
+}
+
+impl Default for TasksCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TasksCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The caller's recent tasks, shared by `!spiral tasks` and `/spiral tasks`
+    pub async fn list_for(&self, user_id: u64, bot: &SpiralConstellationBot) -> String {
+        match bot
+            .user_task_history(user_id, DISCORD_TASK_LIST_LIMIT)
+            .await
+        {
+            Some(history) => format_task_list(&history, Utc::now()),
+            None => NO_HISTORY.to_string(),
+        }
+    }
+
+    /// One task's status, progress and result, with its timing
+    async fn detail(&self, task_id: &str, bot: &SpiralConstellationBot) -> String {
+        let report = bot.status_report(Some(task_id)).await;
+        match bot.task_record(task_id).await {
+            Some(record) => format!("{report}\n{}", format_timing(&record, Utc::now())),
+            None => report,
+        }
+    }
+}
+
+fn status_emoji(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "⏳",
+        TaskStatus::InProgress => "⚙️",
+        TaskStatus::Completed => "✅",
+        TaskStatus::Failed => "❌",
+        TaskStatus::Cancelled => "🚫",
+    }
+}
+
+fn since(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    format_duration((now - time).num_milliseconds().max(0) as u64)
+}
+
+/// How long the task waited, ran or took, and how often it was retried
+pub fn format_timing(record: &AgentTaskRecord, now: DateTime<Utc>) -> String {
+    let mut timing = match (record.started_at, record.duration_ms) {
+        (Some(_), Some(duration_ms)) => format!("⏱️ Took {}", format_duration(duration_ms as u64)),
+        (Some(started_at), None) => format!("⏱️ Running for {}", since(started_at, now)),
+        (None, _) => format!("⏱️ Queued for {}", since(record.created_at, now)),
+    };
+    if record.retry_count > 0 {
+        timing.push_str(&format!(" · 🔁 {} retries", record.retry_count));
+    }
+    if let Some(error) = &record.error {
+        let error: String = error.chars().take(200).collect();
+        timing.push_str(&format!("\n⚠️ {error}"));
+    }
+    timing
+}
+
+/// One line per task, newest first
+pub fn format_task_list(history: &[(Task, AgentTaskRecord)], now: DateTime<Utc>) -> String {
+    if history.is_empty() {
+        return "📜 You have no recent tasks. Mention an agent or use `/spiral task` to start one."
+            .to_string();
+    }
+    let mut list = "📜 **Your Recent Tasks**\n\n".to_string();
+    for (task, record) in history {
+        let persona = AgentPersona::for_agent_type(&task.agent_type);
+        let timing = match (record.started_at, record.duration_ms) {
+            (Some(_), Some(duration_ms)) => format!("took {}", format_duration(duration_ms as u64)),
+            (Some(started_at), None) => format!("running for {}", since(started_at, now)),
+            (None, _) => "queued".to_string(),
+        };
+        list.push_str(&format!(
+            "{} `{}` {} **{}** · {:?} · {timing} · {} ago\n",
+            status_emoji(&record.status),
+            record.task_id,
+            persona.emoji,
+            persona.name,
+            record.status,
+            since(record.created_at, now)
+        ));
+    }
+    list.push_str("\n*Use `!spiral task <id>` for details.*");
+    list
+}
+
+impl CommandHandler for TasksCommand {
+    async fn handle(
+        &self,
+        content: &str,
+        msg: &Message,
+        _ctx: &Context,
+        bot: &SpiralConstellationBot,
+    ) -> Option<String> {
+        let content_lower = content.trim().to_lowercase();
+        if content_lower.starts_with(TASKS_PREFIX) {
+            info!(
+                "[TasksCommand] Task list for user {} ({})",
+                msg.author.name,
+                msg.author.id.get()
+            );
+            return Some(self.list_for(msg.author.id.get(), bot).await);
+        }
+
+        let task_id = content
+            .trim()
+            .get(TASK_PREFIX.len()..)
+            .unwrap_or_default()
+            .trim();
+        if task_id.is_empty() {
+            return Some(
+                "❌ Usage: `!spiral task <id>`, or `!spiral tasks` for your recent tasks"
+                    .to_string(),
+            );
+        }
+        Some(self.detail(task_id, bot).await)
+    }
+
+    fn command_prefix(&self) -> &str {
+        TASKS_PREFIX
+    }
+
+    fn description(&self) -> &str {
+        "List your recent tasks, or show one task's status, progress and timing"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, Priority};
+    use chrono::Duration;
+
+    fn record(task: &Task, started: Option<i64>, duration_ms: Option<i64>) -> AgentTaskRecord {
+        AgentTaskRecord {
+            task_id: task.id.clone(),
+            status: task.status.clone(),
+            priority: task.priority.clone(),
+            created_at: task.created_at,
+            started_at: started.map(|secs| task.created_at + Duration::seconds(secs)),
+            finished_at: None,
+            duration_ms,
+            retry_count: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_task_list_shows_status_and_timing() {
+        let mut done = Task::new(AgentType::SoftwareDeveloper, "a".to_string(), Priority::Low);
+        done.status = TaskStatus::Completed;
+        let queued = Task::new(AgentType::QualityAssurance, "b".to_string(), Priority::Low);
+        let now = done.created_at + Duration::seconds(90);
+        let history = vec![
+            (done.clone(), record(&done, Some(1), Some(65_000))),
+            (queued.clone(), record(&queued, None, None)),
+        ];
+
+        let list = format_task_list(&history, now);
+        assert!(list.contains(&format!("✅ `{}` ", done.id)));
+        assert!(list.contains("took 1m 05s"));
+        assert!(list.contains(&format!("⏳ `{}` ", queued.id)));
+        assert!(list.contains("queued"));
+        assert!(format_task_list(&[], now).contains("no recent tasks"));
+    }
+
+    #[test]
+    fn test_timing_covers_running_tasks() {
+        let mut task = Task::new(AgentType::SoftwareDeveloper, "a".to_string(), Priority::Low);
+        task.status = TaskStatus::InProgress;
+        let mut running = record(&task, Some(10), None);
+        running.retry_count = 2;
+        let timing = format_timing(&running, task.created_at + Duration::seconds(40));
+        assert_eq!(timing, "⏱️ Running for 30.0s · 🔁 2 retries");
+    }
+}
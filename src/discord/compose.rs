@@ -0,0 +1,200 @@
+//! Requests written over several messages
+//!
+//! `!spiral compose` opens a draft for the user in that channel. Their next messages are
+//! collected into it until `!spiral done`, or until the draft is full, and then submitted
+//! as one task.
+
+use crate::constants::{COMPOSE_IDLE_MINUTES, MAX_COMPOSE_MESSAGES, MAX_COMPOSE_REQUEST_LENGTH};
+use crate::models::AgentType;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Characters of the composed request shown in the preview
+const PREVIEW_LENGTH: usize = 600;
+
+/// Messages collected for one request
+#[derive(Debug, Clone)]
+pub struct ComposeDraft {
+    /// Agent named when the draft was opened; None uses the usual agent detection
+    pub agent_type: Option<AgentType>,
+    pub combined_messages: Vec<String>,
+    /// Submitted once this many messages are collected
+    pub max_messages: usize,
+    last_activity: Instant,
+}
+
+impl ComposeDraft {
+    fn new(agent_type: Option<AgentType>, max_messages: usize) -> Self {
+        Self {
+            agent_type,
+            combined_messages: Vec::new(),
+            max_messages: max_messages.clamp(1, MAX_COMPOSE_MESSAGES),
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// The collected messages as one request, in the order they were sent
+    pub fn request(&self) -> String {
+        self.combined_messages.join("\n\n")
+    }
+
+    fn length(&self) -> usize {
+        self.combined_messages
+            .iter()
+            .map(|message| message.chars().count())
+            .sum()
+    }
+
+    fn is_expired(&self) -> bool {
+        self.last_activity.elapsed() > Duration::from_secs(COMPOSE_IDLE_MINUTES * 60)
+    }
+
+    /// What is about to be submitted, quoted and shortened
+    pub fn preview(&self) -> String {
+        let request = self.request();
+        let mut shown: String = request.chars().take(PREVIEW_LENGTH).collect();
+        if shown.len() < request.len() {
+            shown.push('…');
+        }
+        let quoted = shown
+            .lines()
+            .map(|line| format!("> {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "📝 **Composed request** ({} messages, {} characters)\n{quoted}",
+            self.combined_messages.len(),
+            self.length()
+        )
+    }
+}
+
+/// What happened to a message sent while composing
+#[derive(Debug)]
+pub enum Collected {
+    /// Added; the draft now holds `count` of at most `max` messages
+    Added { count: usize, max: usize },
+    /// Added, and the draft is full; it is closed and ready to submit
+    Complete(ComposeDraft),
+    /// Left out because the request would grow past its length limit
+    TooLong,
+}
+
+/// 📝 COMPOSE DRAFTS: Open drafts per channel and user
+/// 🏗️ ARCHITECTURE DECISION: In-memory map, expired drafts dropped when next looked at
+/// Why: A draft lives for minutes and is worthless after a restart
+/// Alternative: Collect replies in a thread (rejected: many channels are threads already)
+#[derive(Debug, Default)]
+pub struct ComposeDrafts {
+    drafts: Mutex<HashMap<(u64, u64), ComposeDraft>>,
+}
+
+impl ComposeDrafts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a draft, replacing one the user already had in the channel
+    pub fn start(
+        &self,
+        channel_id: u64,
+        user_id: u64,
+        agent_type: Option<AgentType>,
+        max_messages: usize,
+    ) -> ComposeDraft {
+        let draft = ComposeDraft::new(agent_type, max_messages);
+        self.drafts
+            .lock()
+            .unwrap()
+            .insert((channel_id, user_id), draft.clone());
+        draft
+    }
+
+    pub fn is_open(&self, channel_id: u64, user_id: u64) -> bool {
+        let mut drafts = self.drafts.lock().unwrap();
+        match drafts.get(&(channel_id, user_id)) {
+            Some(draft) if draft.is_expired() => {
+                drafts.remove(&(channel_id, user_id));
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Add a message to the user's draft; None when they have no open draft
+    pub fn collect(&self, channel_id: u64, user_id: u64, message: &str) -> Option<Collected> {
+        let mut drafts = self.drafts.lock().unwrap();
+        let key = (channel_id, user_id);
+        let draft = drafts.get_mut(&key).filter(|draft| !draft.is_expired())?;
+        if draft.length() + message.chars().count() > MAX_COMPOSE_REQUEST_LENGTH {
+            return Some(Collected::TooLong);
+        }
+        draft.combined_messages.push(message.to_string());
+        draft.last_activity = Instant::now();
+        if draft.combined_messages.len() >= draft.max_messages {
+            return drafts.remove(&key).map(Collected::Complete);
+        }
+        Some(Collected::Added {
+            count: draft.combined_messages.len(),
+            max: draft.max_messages,
+        })
+    }
+
+    /// Close the user's draft and hand it over for submission
+    pub fn finish(&self, channel_id: u64, user_id: u64) -> Option<ComposeDraft> {
+        self.drafts
+            .lock()
+            .unwrap()
+            .remove(&(channel_id, user_id))
+            .filter(|draft| !draft.is_expired())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draft_completes_after_its_message_count() {
+        let drafts = ComposeDrafts::new();
+        assert!(drafts.collect(1, 2, "ignored").is_none());
+
+        drafts.start(1, 2, Some(AgentType::QualityAssurance), 2);
+        assert!(drafts.is_open(1, 2));
+        assert!(!drafts.is_open(1, 3));
+        assert!(matches!(
+            drafts.collect(1, 2, "Review the parser"),
+            Some(Collected::Added { count: 1, max: 2 })
+        ));
+        let Some(Collected::Complete(draft)) = drafts.collect(1, 2, "and its error messages")
+        else {
+            panic!("a full draft completes");
+        };
+        assert_eq!(
+            draft.request(),
+            "Review the parser\n\nand its error messages"
+        );
+        assert_eq!(draft.agent_type, Some(AgentType::QualityAssurance));
+        assert!(draft.preview().contains("(2 messages, 39 characters)"));
+        assert!(!drafts.is_open(1, 2));
+    }
+
+    #[test]
+    fn test_draft_refuses_overlong_requests_and_finishes_early() {
+        let drafts = ComposeDrafts::new();
+        drafts.start(1, 2, None, 100);
+        let long = "x".repeat(MAX_COMPOSE_REQUEST_LENGTH);
+        assert!(matches!(
+            drafts.collect(1, 2, &long),
+            Some(Collected::Added { count: 1, max }) if max == MAX_COMPOSE_MESSAGES
+        ));
+        assert!(matches!(
+            drafts.collect(1, 2, "more"),
+            Some(Collected::TooLong)
+        ));
+        assert_eq!(drafts.finish(1, 2).unwrap().combined_messages.len(), 1);
+        assert!(drafts.finish(1, 2).is_none());
+    }
+}
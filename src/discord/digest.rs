@@ -0,0 +1,183 @@
+//! Notification digests for busy servers
+//!
+//! A guild that turns on digest mode with `!spiral config digest <minutes>` gets routine
+//! notifications, such as completed tasks in its results channel and successful scheduled
+//! runs, batched into one summary message per channel and interval. Failures, approvals
+//! and security alerts are still posted right away.
+
+use crate::models::Priority;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest digest message; Discord refuses more than 2000 characters
+const MAX_DIGEST_LENGTH: usize = 1900;
+
+/// Whether a completed task's notification may wait for the digest
+pub fn is_routine(succeeded: bool, priority: &Priority) -> bool {
+    succeeded && *priority < Priority::High
+}
+
+/// One notification waiting for its channel's digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestEntry {
+    /// Persona the notification is about, counted in the summary
+    pub agent: &'static str,
+    /// The notification as one line
+    pub line: String,
+}
+
+#[derive(Debug)]
+struct PendingDigest {
+    guild_id: u64,
+    since: Instant,
+    entries: Vec<DigestEntry>,
+}
+
+/// 📰 NOTIFICATION DIGESTS: Notifications waiting per channel
+/// 🏗️ ARCHITECTURE DECISION: In-memory batches flushed by one loop checking every minute
+/// Why: A batch is a few lines held for minutes; the loop reads each guild's interval
+/// when it checks, so a changed interval applies to batches already waiting
+/// Alternative: A timer per batch (rejected: a changed interval would not reach it)
+/// Trade-off: Notifications still waiting when the bot stops are lost
+#[derive(Debug, Default)]
+pub struct NotificationDigests {
+    pending: Mutex<HashMap<u64, PendingDigest>>,
+}
+
+impl NotificationDigests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a notification to the channel's next digest
+    pub fn push(&self, guild_id: u64, channel_id: u64, entry: DigestEntry) {
+        self.pending
+            .lock()
+            .unwrap()
+            .entry(channel_id)
+            .or_insert_with(|| PendingDigest {
+                guild_id,
+                since: Instant::now(),
+                entries: Vec::new(),
+            })
+            .entries
+            .push(entry);
+    }
+
+    /// Take the digests whose oldest notification has waited the guild's interval; a guild
+    /// that turned digests off (`interval` gives None) gets its waiting ones at once
+    pub fn take_due(
+        &self,
+        interval: impl Fn(u64) -> Option<Duration>,
+    ) -> Vec<(u64, Duration, Vec<DigestEntry>)> {
+        let mut pending = self.pending.lock().unwrap();
+        let due: Vec<u64> = pending
+            .iter()
+            .filter(|(_, digest)| {
+                interval(digest.guild_id).is_none_or(|interval| digest.since.elapsed() >= interval)
+            })
+            .map(|(channel_id, _)| *channel_id)
+            .collect();
+        due.into_iter()
+            .filter_map(|channel_id| {
+                let digest = pending.remove(&channel_id)?;
+                Some((channel_id, digest.since.elapsed(), digest.entries))
+            })
+            .collect()
+    }
+}
+
+/// The digest message: a count per agent, then one line per notification as far as they fit
+pub fn format_digest(entries: &[DigestEntry], waited: Duration) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for entry in entries {
+        match counts.iter_mut().find(|(agent, _)| *agent == entry.agent) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((entry.agent, 1)),
+        }
+    }
+    let summary = counts
+        .iter()
+        .map(|(agent, count)| format!("{agent} {count}"))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let minutes = (waited.as_secs() / 60).max(1);
+    let mut digest = format!(
+        "📰 **Digest** · {} notification{} in the last {minutes} min ({summary})\n",
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" }
+    );
+    for (shown, entry) in entries.iter().enumerate() {
+        let more = format!("…and {} more", entries.len() - shown);
+        if digest.chars().count() + entry.line.chars().count() + more.chars().count() + 2
+            > MAX_DIGEST_LENGTH
+        {
+            digest.push_str(&more);
+            return digest;
+        }
+        digest.push_str(&format!("\n{}", entry.line));
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(agent: &'static str, line: &str) -> DigestEntry {
+        DigestEntry {
+            agent,
+            line: line.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_digests_flush_after_their_guilds_interval() {
+        let digests = NotificationDigests::new();
+        digests.push(1, 10, entry("SpiralDev", "a"));
+        digests.push(1, 10, entry("SpiralQA", "b"));
+        digests.push(2, 20, entry("SpiralDev", "c"));
+
+        let waiting = |_| Some(Duration::from_secs(600));
+        assert!(digests.take_due(waiting).is_empty());
+
+        // Guild 1 turned digests off, so its batch goes out at once; guild 2's keeps waiting
+        let due = digests.take_due(|guild| (guild == 2).then(|| Duration::from_secs(600)));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, 10);
+        assert_eq!(due[0].2.len(), 2);
+        assert_eq!(digests.take_due(|_| None).len(), 1);
+        assert!(digests.take_due(|_| None).is_empty());
+
+        assert!(is_routine(true, &Priority::Medium));
+        assert!(!is_routine(true, &Priority::High));
+        assert!(!is_routine(false, &Priority::Low));
+    }
+
+    #[test]
+    fn test_format_digest_counts_agents_and_fits_one_message() {
+        let entries = vec![
+            entry("SpiralDev", "🚀 SpiralDev completed task `a`"),
+            entry("SpiralQA", "🔍 SpiralQA completed task `b`"),
+            entry("SpiralDev", "🚀 SpiralDev completed task `c`"),
+        ];
+        let digest = format_digest(&entries, Duration::from_secs(30 * 60));
+        assert!(digest.starts_with(
+            "📰 **Digest** · 3 notifications in the last 30 min (SpiralDev 2 · SpiralQA 1)\n"
+        ));
+        assert!(digest.ends_with("completed task `c`"));
+
+        let many: Vec<_> = (0..200)
+            .map(|i| {
+                entry(
+                    "SpiralDev",
+                    &format!("🚀 SpiralDev completed task `{i:08}`"),
+                )
+            })
+            .collect();
+        let digest = format_digest(&many, Duration::from_secs(60));
+        assert!(digest.chars().count() <= MAX_DIGEST_LENGTH);
+        assert!(digest.contains("more"));
+    }
+}
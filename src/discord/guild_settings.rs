@@ -0,0 +1,224 @@
+//! Per-guild bot settings
+//!
+//! DiscordConfig applies to every server the bot is in. A server's admins can override
+//! parts of it for their server with `!spiral config`; the overrides are kept in a JSON
+//! file so they survive restarts.
+
+use super::locale::Locale;
+use crate::auth::Role;
+use crate::models::AgentType;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+/// The prefix the command router matches; a guild prefix is rewritten to it
+pub const DEFAULT_COMMAND_PREFIX: &str = "!spiral";
+
+/// Overrides of the global Discord configuration for one guild
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildSettings {
+    /// Used instead of `!spiral` in this guild; `!spiral` keeps working
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_prefix: Option<String>,
+    /// Channels the bot answers in; empty allows every channel
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_channels: Vec<u64>,
+    /// Channels agent tasks may be started in; empty allows every allowed channel
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub task_channels: Vec<u64>,
+    /// Channel finished tasks are announced in, besides the answer to the requester
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results_channel: Option<u64>,
+    /// Channel security alerts are posted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ops_channel: Option<u64>,
+    /// Spiral roles given to members of these Discord roles, at most operator
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub role_grants: HashMap<u64, Role>,
+    /// Agent for messages that mention the bot without naming an agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_agent: Option<AgentType>,
+    /// Messages a user may send per minute; None keeps the global limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<usize>,
+    /// Language the bot replies in; None keeps English
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+    /// Minutes routine notifications are batched for; None posts each one right away
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_minutes: Option<u64>,
+}
+
+impl GuildSettings {
+    pub fn allows_channel(&self, channel_id: u64) -> bool {
+        self.allowed_channels.is_empty() || self.allowed_channels.contains(&channel_id)
+    }
+
+    pub fn allows_tasks_in(&self, channel_id: u64) -> bool {
+        self.task_channels.is_empty() || self.task_channels.contains(&channel_id)
+    }
+
+    /// Highest Spiral role the member's Discord roles are granted here
+    pub fn granted_role(&self, member_roles: &[u64]) -> Option<Role> {
+        member_roles
+            .iter()
+            .filter_map(|role_id| self.role_grants.get(role_id))
+            .max()
+            .copied()
+    }
+
+    /// A message starting with this guild's prefix, in the `!spiral` form the router matches
+    pub fn normalize_prefix<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        let Some(prefix) = &self.command_prefix else {
+            return Cow::Borrowed(content);
+        };
+        let trimmed = content.trim_start();
+        match trimmed.get(..prefix.len()) {
+            Some(head) if head.eq_ignore_ascii_case(prefix) => {
+                let rest = &trimmed[prefix.len()..];
+                // "!sp" must not turn "!spam" into a command
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    Cow::Owned(format!("{DEFAULT_COMMAND_PREFIX}{rest}"))
+                } else {
+                    Cow::Borrowed(content)
+                }
+            }
+            _ => Cow::Borrowed(content),
+        }
+    }
+}
+
+/// 🏘️ GUILD SETTINGS STORE: Each guild's overrides, read on every message
+/// 🏗️ ARCHITECTURE DECISION: In-memory map written through to a JSON file
+/// Why: Reads happen per message and must not touch the disk; writes happen only when an
+/// admin changes a setting
+/// Alternative: SQLite like agent memory (rejected: a handful of small records per guild
+/// needs no queries)
+/// Trade-off: The whole file is rewritten on each change, fine at this size
+#[derive(Debug, Default)]
+pub struct GuildSettingsStore {
+    settings: RwLock<HashMap<u64, GuildSettings>>,
+    /// None keeps settings in memory only
+    path: Option<PathBuf>,
+}
+
+impl GuildSettingsStore {
+    /// Open the store, restoring settings saved at `path`
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let settings = path.as_deref().map(Self::load).unwrap_or_default();
+        if !settings.is_empty() {
+            info!("Restored settings of {} guild(s)", settings.len());
+        }
+        Self {
+            settings: RwLock::new(settings),
+            path,
+        }
+    }
+
+    fn load(path: &Path) -> HashMap<u64, GuildSettings> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+            Err(e) => {
+                warn!("Failed to read guild settings {:?}: {}", path, e);
+                return HashMap::new();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring corrupt guild settings {:?}: {}", path, e);
+            HashMap::new()
+        })
+    }
+
+    /// The guild's settings; defaults when it has none
+    pub fn get(&self, guild_id: u64) -> GuildSettings {
+        self.settings
+            .read()
+            .unwrap()
+            .get(&guild_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Change the guild's settings and save them; returns the new settings
+    pub fn update(&self, guild_id: u64, change: impl FnOnce(&mut GuildSettings)) -> GuildSettings {
+        let mut all = self.settings.write().unwrap();
+        let mut settings = all.get(&guild_id).cloned().unwrap_or_default();
+        change(&mut settings);
+        if settings == GuildSettings::default() {
+            all.remove(&guild_id);
+        } else {
+            all.insert(guild_id, settings.clone());
+        }
+        self.persist(&all);
+        settings
+    }
+
+    fn persist(&self, all: &HashMap<u64, GuildSettings>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(all)
+            .map_err(std::io::Error::other)
+            .and_then(|serialized| std::fs::write(path, serialized));
+        if let Err(e) = result {
+            warn!("Failed to save guild settings to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_apply_prefix_channels_and_grants() {
+        let settings = GuildSettings {
+            command_prefix: Some("!sp".to_string()),
+            allowed_channels: vec![10],
+            role_grants: HashMap::from([(1, Role::Viewer), (2, Role::Operator)]),
+            ..GuildSettings::default()
+        };
+        assert_eq!(settings.normalize_prefix("!SP status"), "!spiral status");
+        assert_eq!(
+            settings.normalize_prefix("!spiral status"),
+            "!spiral status"
+        );
+        assert_eq!(settings.normalize_prefix("!spam"), "!spam");
+        assert!(settings.allows_channel(10));
+        assert!(!settings.allows_channel(11));
+        assert!(GuildSettings::default().allows_channel(11));
+        assert!(settings.allows_tasks_in(11));
+        let tasks_in_10 = GuildSettings {
+            task_channels: vec![10],
+            ..GuildSettings::default()
+        };
+        assert!(tasks_in_10.allows_tasks_in(10));
+        assert!(!tasks_in_10.allows_tasks_in(11));
+        assert_eq!(settings.granted_role(&[1, 2, 3]), Some(Role::Operator));
+        assert_eq!(settings.granted_role(&[3]), None);
+    }
+
+    #[test]
+    fn test_store_persists_changes_and_drops_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("guilds.json");
+        let store = GuildSettingsStore::open(Some(path.clone()));
+        store.update(7, |settings| {
+            settings.default_agent = Some(AgentType::QualityAssurance);
+        });
+
+        let reopened = GuildSettingsStore::open(Some(path.clone()));
+        assert_eq!(
+            reopened.get(7).default_agent,
+            Some(AgentType::QualityAssurance)
+        );
+        assert_eq!(reopened.get(8), GuildSettings::default());
+
+        reopened.update(7, |settings| settings.default_agent = None);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "{}");
+    }
+}
@@ -0,0 +1,240 @@
+//! Translations of the bot's replies
+//!
+//! Each locale is a JSON catalog in `locales/`, built into the binary. A directory set with
+//! DISCORD_LOCALE_DIR can override or add entries per locale (`<code>.json`); it is read at
+//! startup. Entries missing from a locale fall back to English. Guilds pick their locale
+//! with `!spiral config locale`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+/// Languages the bot answers in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Nl,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Nl, Locale::De];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Nl => "nl",
+            Locale::De => "de",
+        }
+    }
+
+    fn builtin_catalog(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../../locales/en.json"),
+            Locale::Nl => include_str!("../../locales/nl.json"),
+            Locale::De => include_str!("../../locales/de.json"),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let codes: Vec<&str> = Locale::ALL.iter().map(Locale::code).collect();
+                format!("Unknown locale '{s}', expected one of {}", codes.join(", "))
+            })
+    }
+}
+
+/// One catalog entry: a text, or alternatives picked at random such as greetings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Text(String),
+    Choices(Vec<String>),
+}
+
+type Catalog = HashMap<String, Entry>;
+
+/// 🌍 MESSAGE CATALOG: The bot's replies in every locale
+/// 🏗️ ARCHITECTURE DECISION: Flat JSON catalogs with `{name}` placeholders
+/// Why: Translators edit plain files without touching code, and the built-in catalogs keep
+/// the binary self-contained like the prompt templates
+/// Alternative: Fluent or gettext (rejected: a new dependency for a few dozen strings)
+/// Trade-off: No plural rules; texts are worded to avoid them
+#[derive(Debug, Default)]
+pub struct MessageCatalog {
+    catalogs: HashMap<Locale, Catalog>,
+}
+
+impl MessageCatalog {
+    /// The built-in catalogs with the files in `dir` laid over them
+    pub fn load(dir: Option<&Path>) -> Self {
+        let mut catalogs = HashMap::new();
+        for locale in Locale::ALL {
+            let mut catalog: Catalog = serde_json::from_str(locale.builtin_catalog())
+                .unwrap_or_else(|e| {
+                    warn!("Built-in {} message catalog is invalid: {}", locale, e);
+                    Catalog::new()
+                });
+            if let Some(dir) = dir {
+                catalog.extend(Self::read_overrides(&dir.join(format!("{locale}.json"))));
+            }
+            catalogs.insert(locale, catalog);
+        }
+        Self { catalogs }
+    }
+
+    fn read_overrides(path: &Path) -> Catalog {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Catalog::new(),
+            Err(e) => {
+                warn!("Failed to read message catalog {:?}: {}", path, e);
+                return Catalog::new();
+            }
+        };
+        match serde_json::from_str::<Catalog>(&content) {
+            Ok(catalog) => {
+                info!("Loaded {} message(s) from {:?}", catalog.len(), path);
+                catalog
+            }
+            Err(e) => {
+                warn!("Ignoring invalid message catalog {:?}: {}", path, e);
+                Catalog::new()
+            }
+        }
+    }
+
+    /// The entry in `locale`, else in English
+    fn entry(&self, locale: Locale, key: &str) -> Option<&Entry> {
+        [locale, Locale::En]
+            .iter()
+            .find_map(|locale| self.catalogs.get(locale)?.get(key))
+    }
+
+    /// The text for `key`; the key itself when no catalog has it, so a gap shows up
+    /// without breaking the reply
+    pub fn text(&self, locale: Locale, key: &str) -> String {
+        self.format(locale, key, &[])
+    }
+
+    /// The text for `key` with its `{name}` placeholders filled in
+    pub fn format(&self, locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+        let template = match self.entry(locale, key) {
+            Some(Entry::Text(text)) => text.as_str(),
+            Some(Entry::Choices(choices)) => choices.first().map(String::as_str).unwrap_or(key),
+            None => {
+                warn!("Message catalog has no entry '{}'", key);
+                key
+            }
+        };
+        fill(template, args)
+    }
+
+    /// A random choice among the entry's alternatives; None when no catalog has the key
+    pub fn choose(&self, locale: Locale, key: &str) -> Option<String> {
+        use rand::seq::SliceRandom;
+        match self.entry(locale, key)? {
+            Entry::Text(text) => Some(text.clone()),
+            Entry::Choices(choices) => choices.choose(&mut rand::thread_rng()).cloned(),
+        }
+    }
+}
+
+/// Replace `{name}` placeholders in one pass, so values containing braces stay as they are
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                filled.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_locale_translates_every_english_entry() {
+        let catalog = MessageCatalog::load(None);
+        let english = &catalog.catalogs[&Locale::En];
+        assert!(!english.is_empty());
+        for locale in [Locale::Nl, Locale::De] {
+            let translated = &catalog.catalogs[&locale];
+            for key in english.keys() {
+                assert!(translated.contains_key(key), "{locale} lacks '{key}'");
+            }
+            for key in translated.keys() {
+                assert!(
+                    english.contains_key(key) || key.starts_with("greetings."),
+                    "{locale} has '{key}', which English lacks"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_falls_back_and_fills_placeholders() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("nl.json"),
+            r#"{"tasks.working": "Bezig met {what}..."}"#,
+        )
+        .unwrap();
+        let mut catalog = MessageCatalog::load(Some(dir.path()));
+        catalog
+            .catalogs
+            .get_mut(&Locale::Nl)
+            .unwrap()
+            .remove("tasks.completed");
+
+        assert_eq!(
+            catalog.format(Locale::Nl, "tasks.working", &[("what", "{x}")]),
+            "Bezig met {x}..."
+        );
+        assert_eq!(
+            catalog.text(Locale::Nl, "tasks.completed"),
+            catalog.text(Locale::En, "tasks.completed")
+        );
+        assert_eq!(catalog.text(Locale::De, "no.such.key"), "no.such.key");
+        assert_eq!(fill("{a} and {b", &[("a", "1")]), "1 and {b");
+        assert_eq!("NL".parse::<Locale>(), Ok(Locale::Nl));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+}
@@ -1,11 +1,17 @@
 /// 🔐 DISCORD MESSAGE SECURITY
 /// Purpose: Centralized security validation for Discord message processing
 /// Coverage: Input sanitization, rate limiting, spam detection, permission validation
+use crate::constants::DISCORD_USER_MESSAGES_PER_MINUTE;
+use crate::rate_limit::{
+    tiers::{RateLimitTier, RateLimitTiers},
+    MemoryRateLimiterStore, RateLimiterStore, TokenBucket,
+};
 use crate::SpiralError;
 use serenity::model::channel::Message;
 use serenity::model::user::User;
-use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
 
 /// Discord message length limit for safety
 pub const MAX_MESSAGE_LENGTH: usize = 4000;
@@ -86,52 +92,167 @@ pub struct UserVerificationResult {
 }
 
 /// Rate limiter for message processing
+/// Each user, and optionally each channel, has a token bucket: a burst of messages after a
+/// quiet spell, then a steady rate. Buckets are kept here unless a shared store is set, in
+/// which case `check` takes from there so every bot instance sees the same messages
 pub struct MessageRateLimiter {
-    user_messages: HashMap<u64, Vec<Instant>>,
-    max_messages: usize,
-    time_window: Duration,
+    buckets: MemoryRateLimiterStore,
+    user_bucket: TokenBucket,
+    channel_bucket: Option<TokenBucket>,
+    store: Option<Arc<dyn RateLimiterStore>>,
+    /// Tiers granted to users; everyone is on the default tier without them
+    tiers: Option<Arc<RateLimitTiers>>,
 }
 
 impl MessageRateLimiter {
     pub fn new() -> Self {
         Self {
-            user_messages: HashMap::new(),
-            max_messages: 5,
-            time_window: Duration::from_secs(60),
+            buckets: MemoryRateLimiterStore::new(),
+            user_bucket: TokenBucket::per_minute(DISCORD_USER_MESSAGES_PER_MINUTE),
+            channel_bucket: None,
+            store: None,
+            tiers: None,
         }
     }
 
-    pub fn is_allowed(&mut self, user_id: u64, timestamp: Instant) -> bool {
-        let user_messages = self.user_messages.entry(user_id).or_default();
+    /// Limit each user to `user` and, when set, each channel to `channel`
+    pub fn with_buckets(mut self, user: TokenBucket, channel: Option<TokenBucket>) -> Self {
+        self.set_buckets(user, channel);
+        self
+    }
 
-        // Remove old messages outside the time window
-        user_messages.retain(|&msg_time| timestamp.duration_since(msg_time) < self.time_window);
+    /// Limit each user to `user` and each channel to `channel` from now on; tokens already
+    /// taken still count against the new buckets
+    pub fn set_buckets(&mut self, user: TokenBucket, channel: Option<TokenBucket>) {
+        self.user_bucket = user;
+        self.channel_bucket = channel;
+    }
 
-        if user_messages.len() >= self.max_messages {
-            return false;
-        }
+    /// Take from buckets in `store`, shared with other instances, instead of this limiter's
+    pub fn with_store(mut self, store: Arc<dyn RateLimiterStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Scale each user's bucket for the tier `tiers` grants them
+    pub fn with_tiers(mut self, tiers: Arc<RateLimitTiers>) -> Self {
+        self.tiers = Some(tiers);
+        self
+    }
+
+    pub fn tier(&self, user_id: u64) -> RateLimitTier {
+        self.tiers
+            .as_ref()
+            .map(|tiers| tiers.tier(&user_id.to_string()))
+            .unwrap_or_default()
+    }
 
-        user_messages.push(timestamp);
+    /// The user's bucket, or one of `max_messages` a minute as a guild's settings may ask
+    /// for, scaled for their tier; None when their tier is unlimited
+    fn user_bucket(&self, user_id: u64, max_messages: Option<usize>) -> Option<TokenBucket> {
+        let bucket = match max_messages {
+            Some(max) => TokenBucket::per_minute(u32::try_from(max).unwrap_or(u32::MAX)),
+            None => self.user_bucket,
+        };
+        self.tier(user_id).apply(bucket)
+    }
+
+    /// Take a token for a message from `user_id` in `channel_id`, from the user's bucket
+    /// (of `max_messages` a minute when given) and then the channel's
+    /// Users on the unlimited tier take from neither
+    /// With a shared store the buckets are kept there; while the store can't be reached
+    /// this limiter's own buckets decide, so an outage neither blocks nor unlimits users
+    pub async fn check(
+        &mut self,
+        user_id: u64,
+        channel_id: Option<u64>,
+        max_messages: Option<usize>,
+    ) -> bool {
+        let Some(user_bucket) = self.user_bucket(user_id, max_messages) else {
+            return true;
+        };
+        let mut limits = vec![(format!("discord:user:{user_id}"), user_bucket)];
+        if let (Some(channel_id), Some(bucket)) = (channel_id, self.channel_bucket) {
+            limits.push((format!("discord:channel:{channel_id}"), bucket));
+        }
+        for (key, bucket) in limits {
+            let allowed = match &self.store {
+                Some(store) => match store.hit(&key, bucket).await {
+                    Ok(decision) => decision.allowed,
+                    Err(e) => {
+                        warn!(
+                            "Rate limit store unavailable, limiting {} in process: {}",
+                            key, e
+                        );
+                        self.buckets.hit_at(&key, bucket, Instant::now()).allowed
+                    }
+                },
+                None => self.buckets.hit_at(&key, bucket, Instant::now()).allowed,
+            };
+            if !allowed {
+                return false;
+            }
+        }
         true
     }
 
+    pub fn is_allowed(&mut self, user_id: u64, timestamp: Instant) -> bool {
+        let Some(bucket) = self.user_bucket(user_id, None) else {
+            return true;
+        };
+        self.buckets
+            .hit_at(&format!("discord:user:{user_id}"), bucket, timestamp)
+            .allowed
+    }
+
+    /// Like `is_allowed`, with `max_messages` a minute instead of the default, as a
+    /// guild's settings may ask for
+    pub fn is_allowed_with_limit(
+        &mut self,
+        user_id: u64,
+        timestamp: Instant,
+        max_messages: usize,
+    ) -> bool {
+        let Some(bucket) = self.user_bucket(user_id, Some(max_messages)) else {
+            return true;
+        };
+        self.buckets
+            .hit_at(&format!("discord:user:{user_id}"), bucket, timestamp)
+            .allowed
+    }
+
     pub fn reset_user(&mut self, user_id: u64) {
-        self.user_messages.remove(&user_id);
+        self.buckets.forget(&format!("discord:user:{user_id}"));
     }
 
     pub fn get_remaining_messages(&self, user_id: u64) -> usize {
-        match self.user_messages.get(&user_id) {
-            Some(messages) => {
-                let now = Instant::now();
-                let recent_messages = messages
-                    .iter()
-                    .filter(|&&msg_time| now.duration_since(msg_time) < self.time_window)
-                    .count();
-                self.max_messages.saturating_sub(recent_messages)
-            }
-            None => self.max_messages,
+        match self.user_bucket(user_id, None) {
+            Some(bucket) => self.buckets.remaining_at(
+                &format!("discord:user:{user_id}"),
+                bucket,
+                Instant::now(),
+            ) as usize,
+            None => usize::MAX,
         }
     }
+
+    /// Messages `user_id` may send at once right now, from the shared store when there is
+    /// one, and their bucket's size; None when their tier is unlimited
+    pub async fn remaining(&self, user_id: u64) -> Option<(u32, TokenBucket)> {
+        let bucket = self.user_bucket(user_id, None)?;
+        let key = format!("discord:user:{user_id}");
+        let remaining = match &self.store {
+            Some(store) => store.remaining(&key, bucket).await.unwrap_or_else(|e| {
+                warn!(
+                    "Rate limit store unavailable, reading {} in process: {}",
+                    key, e
+                );
+                self.buckets.remaining_at(&key, bucket, Instant::now())
+            }),
+            None => self.buckets.remaining_at(&key, bucket, Instant::now()),
+        };
+        Some((remaining, bucket))
+    }
 }
 
 impl Default for MessageRateLimiter {
@@ -152,6 +273,41 @@ impl MessageSecurityValidator {
         }
     }
 
+    /// Limit each user to `user` and, when set, each channel to `channel`
+    pub fn with_rate_limits(mut self, user: TokenBucket, channel: Option<TokenBucket>) -> Self {
+        self.set_rate_limits(user, channel);
+        self
+    }
+
+    /// Replace the per-user and per-channel message limits, as on a configuration reload
+    pub fn set_rate_limits(&mut self, user: TokenBucket, channel: Option<TokenBucket>) {
+        self.rate_limiter.set_buckets(user, channel);
+    }
+
+    /// Take rate limit tokens from `store`, shared with other bot instances
+    pub fn with_rate_limiter_store(mut self, store: Arc<dyn RateLimiterStore>) -> Self {
+        self.rate_limiter = self.rate_limiter.with_store(store);
+        self
+    }
+
+    /// Scale each user's rate limit for the tier `tiers` grants them
+    pub fn with_rate_limit_tiers(mut self, tiers: Arc<RateLimitTiers>) -> Self {
+        self.rate_limiter = self.rate_limiter.with_tiers(tiers);
+        self
+    }
+
+    /// The user's tier, and the messages they may send at once with their bucket's size;
+    /// None for the unlimited tier
+    pub async fn rate_limit_status(
+        &self,
+        user_id: u64,
+    ) -> (RateLimitTier, Option<(u32, TokenBucket)>) {
+        (
+            self.rate_limiter.tier(user_id),
+            self.rate_limiter.remaining(user_id).await,
+        )
+    }
+
     /// Validate message content for security issues using const patterns
     pub fn validate_message_content(&self, content: &str) -> MessageValidationResult {
         let mut issues = Vec::new();
@@ -540,13 +696,25 @@ impl MessageSecurityValidator {
         true
     }
 
-    /// Comprehensive message validation
-    pub fn validate_message(
+    /// Rate limit, length, content and spam checks for text from a user, such as the
+    /// options of a slash command, which arrive without a message
+    pub async fn validate_text(
         &mut self,
-        message: &Message,
+        user_id: u64,
+        content: &str,
+    ) -> Result<MessageValidationResult, SpiralError> {
+        let allowed = self.rate_limiter.check(user_id, None, None).await;
+        self.validate_text_within_limit(allowed, content)
+    }
+
+    /// Length, content and spam checks of text whose rate limit check gave `allowed`
+    fn validate_text_within_limit(
+        &self,
+        allowed: bool,
+        content: &str,
     ) -> Result<MessageValidationResult, SpiralError> {
         // Check rate limiting first
-        if !self.check_rate_limit(message.author.id.get()) {
+        if !allowed {
             return Ok(MessageValidationResult {
                 is_valid: false,
                 risk_level: RiskLevel::High,
@@ -556,19 +724,19 @@ impl MessageSecurityValidator {
         }
 
         // Validate message length
-        let length_result = self.validate_message_length(&message.content);
+        let length_result = self.validate_message_length(content);
         if !length_result.is_valid {
             return Ok(length_result);
         }
 
         // Validate message content
-        let content_result = self.validate_message_content(&message.content);
+        let content_result = self.validate_message_content(content);
         if !content_result.is_valid {
             return Ok(content_result);
         }
 
         // Check for spam
-        if self.is_spam_message(&message.content) {
+        if self.is_spam_message(content) {
             return Ok(MessageValidationResult {
                 is_valid: false,
                 risk_level: RiskLevel::High,
@@ -577,6 +745,52 @@ impl MessageSecurityValidator {
             });
         }
 
+        Ok(MessageValidationResult {
+            is_valid: true,
+            risk_level: RiskLevel::Low,
+            issues: vec![],
+            sanitized_content: None,
+        })
+    }
+
+    /// Comprehensive message validation
+    /// The rate limit is counted in this process only, even with a shared store set
+    pub fn validate_message(
+        &mut self,
+        message: &Message,
+    ) -> Result<MessageValidationResult, SpiralError> {
+        let allowed = self.check_rate_limit(message.author.id.get());
+        self.validate_message_within_limit(allowed, message)
+    }
+
+    /// `validate_message` with the per-minute message limit of the guild it was posted in,
+    /// and the channel's limit, taken from the shared store when one is set
+    pub async fn validate_message_with_limit(
+        &mut self,
+        message: &Message,
+        rate_limit: Option<usize>,
+    ) -> Result<MessageValidationResult, SpiralError> {
+        let allowed = self
+            .rate_limiter
+            .check(
+                message.author.id.get(),
+                Some(message.channel_id.get()),
+                rate_limit,
+            )
+            .await;
+        self.validate_message_within_limit(allowed, message)
+    }
+
+    fn validate_message_within_limit(
+        &self,
+        allowed: bool,
+        message: &Message,
+    ) -> Result<MessageValidationResult, SpiralError> {
+        let text_result = self.validate_text_within_limit(allowed, &message.content)?;
+        if !text_result.is_valid {
+            return Ok(text_result);
+        }
+
         // Validate attachments
         for attachment in &message.attachments {
             let attachment_result = self.validate_attachment_name(&attachment.filename);
@@ -712,6 +926,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rate_limit_override() {
+        let mut limiter = MessageRateLimiter::new();
+        let now = Instant::now();
+        assert!(limiter.is_allowed_with_limit(1, now, 1));
+        assert!(!limiter.is_allowed_with_limit(1, now, 1));
+        // A guild allowing more messages lets the same user continue
+        assert!(limiter.is_allowed_with_limit(1, now, 10));
+    }
+
     #[test]
     fn test_command_validation() {
         let validator = MessageSecurityValidator::new();
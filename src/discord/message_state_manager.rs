@@ -1,3 +1,6 @@
+use super::paginator::PagedAnswer;
+use crate::constants::DISCORD_PAGE_IDLE_MINUTES;
+use serenity::all::{CreateActionRow, CreateEmbed};
 use serenity::model::id::{ChannelId, MessageId};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -53,6 +56,13 @@ impl PendingMessage {
     }
 }
 
+/// A paged answer and when it was last looked at
+#[derive(Debug, Clone)]
+struct PagedMessage {
+    answer: PagedAnswer,
+    last_turned: Instant,
+}
+
 /// Configuration for message state recovery
 #[derive(Debug, Clone)]
 pub struct MessageStateConfig {
@@ -64,6 +74,8 @@ pub struct MessageStateConfig {
     pub max_retries: u32,
     /// Cleanup interval for expired messages
     pub cleanup_interval: Duration,
+    /// Time without a page turn after which a paged answer's pages are dropped
+    pub page_timeout: Duration,
 }
 
 impl Default for MessageStateConfig {
@@ -73,6 +85,7 @@ impl Default for MessageStateConfig {
             retry_interval: Duration::from_secs(5),
             max_retries: 3,
             cleanup_interval: Duration::from_secs(60),
+            page_timeout: Duration::from_secs(DISCORD_PAGE_IDLE_MINUTES * 60),
         }
     }
 }
@@ -80,6 +93,7 @@ impl Default for MessageStateConfig {
 /// Manages message state and recovery for Discord bot
 pub struct MessageStateManager {
     pending_messages: Arc<RwLock<HashMap<MessageId, PendingMessage>>>,
+    paged_messages: Arc<RwLock<HashMap<MessageId, PagedMessage>>>,
     config: MessageStateConfig,
     recovery_stats: Arc<Mutex<RecoveryStats>>,
     // 🔧 RESOURCE LEAK FIX: Add task lifecycle management
@@ -98,6 +112,7 @@ impl MessageStateManager {
     pub fn new(config: MessageStateConfig) -> Self {
         Self {
             pending_messages: Arc::new(RwLock::new(HashMap::new())),
+            paged_messages: Arc::new(RwLock::new(HashMap::new())),
             config,
             recovery_stats: Arc::new(Mutex::new(RecoveryStats::default())),
             // 🔧 RESOURCE LEAK FIX: Initialize task management
@@ -187,8 +202,44 @@ impl MessageStateManager {
             .collect()
     }
 
+    /// 📖 PAGINATION: Keep the pages of an answer message so its buttons can turn them
+    pub async fn register_pages(&self, message_id: MessageId, answer: PagedAnswer) {
+        let page_count = answer.page_count();
+        let mut paged = self.paged_messages.write().await;
+        paged.insert(
+            message_id,
+            PagedMessage {
+                answer,
+                last_turned: Instant::now(),
+            },
+        );
+        debug!("Registered {} pages for message {}", page_count, message_id);
+    }
+
+    /// The embed and buttons showing `page` of the message's answer; None when the
+    /// message has no pages or they expired
+    pub async fn turn_page(
+        &self,
+        message_id: MessageId,
+        page: usize,
+    ) -> Option<(CreateEmbed, Vec<CreateActionRow>)> {
+        let mut paged = self.paged_messages.write().await;
+        let message = paged.get_mut(&message_id)?;
+        if message.last_turned.elapsed() > self.config.page_timeout {
+            paged.remove(&message_id);
+            return None;
+        }
+        message.last_turned = Instant::now();
+        Some((message.answer.embed(page), message.answer.buttons(page)))
+    }
+
     /// Clean up expired messages
     pub async fn cleanup_expired_messages(&self) {
+        self.paged_messages
+            .write()
+            .await
+            .retain(|_, message| message.last_turned.elapsed() <= self.config.page_timeout);
+
         let mut messages = self.pending_messages.write().await;
         let mut expired_count = 0;
 
@@ -213,9 +264,11 @@ impl MessageStateManager {
     pub async fn get_stats(&self) -> MessageRecoveryStats {
         let stats = self.recovery_stats.lock().await;
         let pending_count = self.pending_messages.read().await.len();
+        let paged_count = self.paged_messages.read().await.len();
 
         MessageRecoveryStats {
             pending_messages: pending_count as u64,
+            paged_messages: paged_count as u64,
             successful_recoveries: stats.successful_recoveries,
             failed_recoveries: stats.failed_recoveries,
             timed_out_messages: stats.timed_out_messages,
@@ -284,6 +337,7 @@ impl MessageStateManager {
 #[derive(Debug, Clone)]
 pub struct MessageRecoveryStats {
     pub pending_messages: u64,
+    pub paged_messages: u64,
     pub successful_recoveries: u64,
     pub failed_recoveries: u64,
     pub timed_out_messages: u64,
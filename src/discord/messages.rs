@@ -22,6 +22,8 @@ pub mod security {
     pub const MESSAGE_FLAGGED: &str = "🚫 Message flagged by security validation. Please ensure your message follows community guidelines.";
     pub const UNAUTHORIZED: &str =
         "🚫 This command requires authorization. Contact an administrator.";
+    pub const INSUFFICIENT_ROLE: &str =
+        "🚫 Your role does not allow this. Contact an administrator.";
     pub const RATE_LIMITED: &str = "⏸️ Rate limited (wait a moment)";
     pub const VALIDATION_FAILED: &str = "⚠️ Security validation failed. Message blocked.";
     pub const VALIDATION_ERROR: &str = "⚠️ Unable to process message securely. Please try again.";
@@ -50,8 +52,6 @@ pub mod success {
     pub const ROLES_CREATED: &str = "🌌 **SpiralConstellation Setup Complete!**";
     pub const ROLE_ASSIGNED: &str = "**Welcome to {}!**";
     pub const METRICS_RESET: &str = "✅ Security metrics have been reset.";
-    pub const RATE_LIMIT_RESET: &str =
-        "✅ Rate limit reset for <@{}>\nThey can now send messages again.";
 }
 
 /// Help and info messages
@@ -97,12 +97,6 @@ pub mod metrics {
     pub const YOUR_RATE_LIMIT: &str = "📊 **Your Rate Limit Status**";
 }
 
-/// Usage instructions
-pub mod usage {
-    pub const RATE_LIMIT_RESET_USAGE: &str =
-        "❌ Usage: `!spiral reset ratelimit @user` or `!spiral reset ratelimit <user_id>`";
-}
-
 /// Self-update system messages
 pub mod auto_core_update {
     pub const PROCESSING: &str = "🔄 Processing self-update request...";
@@ -111,7 +105,7 @@ pub mod auto_core_update {
     pub const RESTARTING: &str = "🔄 Restarting Spiral Core...";
     pub const SUCCESS: &str = "✅ Spiral Core Back online";
     pub const FAILURE: &str = "❌ Update failed:";
-    pub const UNAUTHORIZED: &str = "🔒 Self-updates require authorization...";
+    pub const UNAUTHORIZED: &str = "🔒 Self-updates require the admin role...";
     pub const QUEUE_BLOCKED: &str = "⏳ Self-update in progress. Your request has been queued.";
     pub const QUEUE_SUCCESS: &str = "✅ Self-update request queued successfully.";
     pub const INSUFFICIENT_INFO: &str =
@@ -216,11 +210,6 @@ impl MessageFormatter {
         )
     }
 
-    /// Format rate limit reset message
-    pub fn rate_limit_reset(user_id: u64) -> String {
-        success::RATE_LIMIT_RESET.replace("{}", &user_id.to_string())
-    }
-
     /// Format admin commands header
     pub fn admin_commands_header(has_access: bool) -> String {
         if has_access {
@@ -277,9 +266,6 @@ mod tests {
 
         let role = MessageFormatter::role_assigned("SpiralDev");
         assert!(role.contains("SpiralDev"));
-
-        let rate_limit = MessageFormatter::rate_limit_reset(123456789);
-        assert!(rate_limit.contains("123456789"));
     }
 
     #[test]
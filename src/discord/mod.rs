@@ -1,16 +1,29 @@
 pub mod agent_initializer;
 pub mod agent_registry;
+pub mod clarification;
 pub mod commands;
+pub mod compose;
+pub mod digest;
+pub mod guild_settings;
 pub mod intent_classifier;
+pub mod locale;
 pub mod lordgenome_quotes;
 pub mod message_security;
 pub mod message_state_manager;
 pub mod messages;
+pub mod paginator;
+pub mod presence;
 pub mod reaction_handler;
 pub mod secure_message_handler;
 pub mod self_update;
+pub mod setup_wizard;
 pub mod spiral_constellation_bot;
 pub mod startup;
+pub mod task_actions;
+pub mod task_reply;
+pub mod task_threads;
+pub mod user_prefs;
+pub mod user_sessions;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -24,5 +37,7 @@ pub use message_security::{
     UserVerificationResult,
 };
 pub use secure_message_handler::{MessageProcessingResult, SecureMessageHandler, SecurityMetrics};
-pub use spiral_constellation_bot::{SpiralConstellationBot, SpiralConstellationBotRunner};
+pub use spiral_constellation_bot::{
+    DiscordConfigReloader, SpiralConstellationBot, SpiralConstellationBotRunner,
+};
 pub use startup::{start_discord_bots, start_discord_with_orchestrator};
@@ -0,0 +1,197 @@
+//! Long task answers as pages
+//!
+//! An answer longer than one embed page is split into pages shown one at a time, with
+//! ⬅️/➡️ buttons to turn them. The MessageStateManager keeps the pages of each answer
+//! message until nobody has turned them for a while; the buttons are then removed.
+
+use crate::constants::{DISCORD_RESULT_PAGE_LENGTH, MAX_DISCORD_RESULT_PAGES};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter};
+
+/// Prefix of the custom IDs of the page buttons; the rest is the page they turn to
+const PAGE_BUTTON_PREFIX: &str = "spiral-page:";
+
+/// Custom ID of the disabled button showing the current page
+const PAGE_INDICATOR_ID: &str = "spiral-page:current";
+
+/// 📖 PAGED ANSWER: A result embed whose description is one of several pages
+#[derive(Debug, Clone)]
+pub struct PagedAnswer {
+    embed: CreateEmbed,
+    footer: String,
+    pages: Vec<String>,
+}
+
+impl PagedAnswer {
+    /// Split `answer` into pages of the result embed; None when it fits one page
+    pub fn new(embed: CreateEmbed, footer: String, answer: &str) -> Option<Self> {
+        let pages = split_pages(answer, DISCORD_RESULT_PAGE_LENGTH, MAX_DISCORD_RESULT_PAGES);
+        (pages.len() > 1).then_some(Self {
+            embed,
+            footer,
+            pages,
+        })
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// The embed showing `page`, counted from 0; the last page past the end
+    pub fn embed(&self, page: usize) -> CreateEmbed {
+        let page = page.min(self.pages.len() - 1);
+        self.embed
+            .clone()
+            .description(&self.pages[page])
+            .footer(CreateEmbedFooter::new(format!(
+                "{} · page {}/{}",
+                self.footer,
+                page + 1,
+                self.pages.len()
+            )))
+    }
+
+    /// The ⬅️ page / ➡️ row under the embed showing `page`
+    pub fn buttons(&self, page: usize) -> Vec<CreateActionRow> {
+        let last = self.pages.len() - 1;
+        let page = page.min(last);
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("{PAGE_BUTTON_PREFIX}{}", page.saturating_sub(1)))
+                .emoji('⬅')
+                .style(ButtonStyle::Secondary)
+                .disabled(page == 0),
+            CreateButton::new(PAGE_INDICATOR_ID)
+                .label(format!("{}/{}", page + 1, self.pages.len()))
+                .style(ButtonStyle::Secondary)
+                .disabled(true),
+            CreateButton::new(format!("{PAGE_BUTTON_PREFIX}{}", (page + 1).min(last)))
+                .emoji('➡')
+                .style(ButtonStyle::Secondary)
+                .disabled(page == last),
+        ])]
+    }
+}
+
+/// The page a page button turns to; None for other buttons
+pub fn page_from_custom_id(custom_id: &str) -> Option<usize> {
+    custom_id.strip_prefix(PAGE_BUTTON_PREFIX)?.parse().ok()
+}
+
+/// Split `text` into pages of at most about `max_chars` characters, at line breaks where
+/// possible. A code block cut by a page break is closed and reopened, so each page renders
+/// on its own. Text beyond `max_pages` pages is dropped with a note.
+pub fn split_pages(text: &str, max_chars: usize, max_pages: usize) -> Vec<String> {
+    // Room for the fence closing a code block at a page break
+    const FENCE_ROOM: usize = 4;
+    let piece_chars = (max_chars / 2).max(1);
+
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut page_chars = 0;
+    let mut open_fence: Option<String> = None;
+
+    for line in text.lines() {
+        // A line longer than a page is cut into pieces
+        let chars: Vec<char> = line.chars().collect();
+        let pieces: Vec<String> = if chars.is_empty() {
+            vec![String::new()]
+        } else {
+            chars
+                .chunks(piece_chars)
+                .map(|piece| piece.iter().collect())
+                .collect()
+        };
+
+        for piece in pieces {
+            let piece_len = piece.chars().count();
+            let is_fence = piece.trim_start().starts_with("```");
+            // A code block open after this piece may need closing at the page break
+            let room = if open_fence.is_some() != is_fence {
+                FENCE_ROOM
+            } else {
+                0
+            };
+            if page_chars > 0 && page_chars + 1 + piece_len + room > max_chars {
+                if open_fence.is_some() {
+                    page.push_str("\n```");
+                }
+                pages.push(std::mem::take(&mut page));
+                page_chars = 0;
+                if let Some(fence) = &open_fence {
+                    page.push_str(fence);
+                    page_chars = fence.chars().count();
+                }
+            }
+            if page_chars > 0 {
+                page.push('\n');
+                page_chars += 1;
+            }
+            page.push_str(&piece);
+            page_chars += piece_len;
+
+            if is_fence {
+                open_fence = match open_fence {
+                    Some(_) => None,
+                    None => Some(piece.trim().to_string()),
+                };
+            }
+        }
+    }
+    if page_chars > 0 || pages.is_empty() {
+        pages.push(page);
+    }
+
+    if pages.len() > max_pages {
+        pages.truncate(max_pages);
+        if let Some(last) = pages.last_mut() {
+            last.push_str(&format!("\n\n*(Output truncated after {max_pages} pages)*"));
+        }
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_pages_breaks_at_lines_and_reopens_code_blocks() {
+        assert_eq!(split_pages("short answer", 100, 5), vec!["short answer"]);
+        assert_eq!(split_pages("", 100, 5), vec![""]);
+
+        let text = "intro\n```rust\nlet a = 1;\nlet b = 2;\n```\noutro";
+        let pages = split_pages(text, 24, 10);
+        assert!(pages.len() > 1);
+        for page in &pages {
+            assert!(page.chars().count() <= 24, "{page:?} is too long");
+            assert_eq!(
+                page.matches("```").count() % 2,
+                0,
+                "{page:?} leaves a block open"
+            );
+        }
+        assert_eq!(pages[0], "intro\n```rust\n```");
+        assert!(pages.iter().any(|page| page.starts_with("```rust\nlet a")));
+
+        let long_line = "x".repeat(50);
+        let pages = split_pages(&long_line, 20, 10);
+        assert_eq!(pages.concat().len(), 50);
+
+        let pages = split_pages(&"line\n".repeat(100), 10, 3);
+        assert_eq!(pages.len(), 3);
+        assert!(pages[2].ends_with("*(Output truncated after 3 pages)*"));
+    }
+
+    #[test]
+    fn test_paged_answer_only_for_long_answers_and_buttons_turn_pages() {
+        let embed = CreateEmbed::new().title("answer");
+        assert!(PagedAnswer::new(embed.clone(), "footer".into(), "fits").is_none());
+
+        let answer = "a line of output\n".repeat(DISCORD_RESULT_PAGE_LENGTH / 8);
+        let paged = PagedAnswer::new(embed, "footer".into(), &answer).unwrap();
+        assert_eq!(paged.page_count(), 3);
+
+        assert_eq!(page_from_custom_id("spiral-page:2"), Some(2));
+        assert_eq!(page_from_custom_id(PAGE_INDICATOR_ID), None);
+        assert_eq!(page_from_custom_id("spiral-approval:approve"), None);
+    }
+}
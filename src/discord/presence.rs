@@ -0,0 +1,91 @@
+//! The bot's status as a glance at current work
+//!
+//! With `DISCORD_PRESENCE_STATUS` on, the bot's status names the task it is running, how
+//! long it has been at it and how many tasks wait, so operators need no command to see
+//! whether it is busy.
+
+use crate::constants::DISCORD_TASK_ID_DISPLAY_LENGTH;
+
+/// The status shown when the bot has nothing to do, and when presence updates are off
+pub const IDLE_ACTIVITY: &str = "!spiral commands for help";
+
+/// What the orchestrator is doing at one moment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkSnapshot {
+    /// Running tasks with the seconds since each started
+    pub running: Vec<(String, u64)>,
+    pub queued: usize,
+}
+
+/// 🟢 STATUS TEXT: The longest-running task and the queue length, or the help hint when idle
+pub fn activity_text(snapshot: &WorkSnapshot) -> String {
+    let longest = snapshot
+        .running
+        .iter()
+        .max_by_key(|(_, elapsed_secs)| *elapsed_secs);
+    let mut text = match longest {
+        Some((task_id, elapsed_secs)) => {
+            let short_id: String = task_id
+                .chars()
+                .take(DISCORD_TASK_ID_DISPLAY_LENGTH)
+                .collect();
+            let others = match snapshot.running.len() - 1 {
+                0 => String::new(),
+                more => format!(" +{more} more"),
+            };
+            format!(
+                "Working on task {short_id}{others} – {} elapsed",
+                format_elapsed(*elapsed_secs)
+            )
+        }
+        None if snapshot.queued > 0 => return format!("{} tasks queued", snapshot.queued),
+        None => return IDLE_ACTIVITY.to_string(),
+    };
+    if snapshot.queued > 0 {
+        text.push_str(&format!(" · {} queued", snapshot.queued));
+    }
+    text
+}
+
+/// Whole seconds, as the status is only refreshed every few of them
+fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_text_names_the_longest_running_task() {
+        assert_eq!(activity_text(&WorkSnapshot::default()), IDLE_ACTIVITY);
+        assert_eq!(
+            activity_text(&WorkSnapshot {
+                running: Vec::new(),
+                queued: 2
+            }),
+            "2 tasks queued"
+        );
+        assert_eq!(
+            activity_text(&WorkSnapshot {
+                running: vec![("abc123".to_string(), 45)],
+                queued: 0
+            }),
+            "Working on task abc123 – 45s elapsed"
+        );
+        assert_eq!(
+            activity_text(&WorkSnapshot {
+                running: vec![
+                    ("0123456789abcdef".to_string(), 30),
+                    ("fedcba9876543210".to_string(), 125),
+                ],
+                queued: 3
+            }),
+            "Working on task fedcba98 +1 more – 2m 05s elapsed · 3 queued"
+        );
+    }
+}
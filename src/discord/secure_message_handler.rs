@@ -48,6 +48,10 @@ pub struct SecurityMetrics {
     pub total_confidence: f64,
     pub classification_count: u64,
     pub low_confidence_count: u64, // confidence < 0.5
+    // Clarifying questions asked before unclear or risky tasks
+    pub clarifications_requested: u64,
+    pub clarifications_confirmed: u64,
+    pub clarifications_cancelled: u64,
 }
 
 /// Secure message handler with integrated security validation
@@ -382,6 +386,23 @@ impl SecureMessageHandler {
         }
     }
 
+    /// Count a clarifying question asked instead of starting a task
+    pub fn record_clarification_requested(&self) {
+        match self.metrics.lock() {
+            Ok(mut metrics) => metrics.clarifications_requested += 1,
+            Err(_) => error!("Failed to acquire metrics lock for clarification update"),
+        }
+    }
+
+    /// Count the author's answer to a clarifying question
+    pub fn record_clarification_answered(&self, confirmed: bool) {
+        match self.metrics.lock() {
+            Ok(mut metrics) if confirmed => metrics.clarifications_confirmed += 1,
+            Ok(mut metrics) => metrics.clarifications_cancelled += 1,
+            Err(_) => error!("Failed to acquire metrics lock for clarification update"),
+        }
+    }
+
     /// Get average confidence score
     pub fn get_average_confidence(&self) -> f64 {
         match self.metrics.lock() {
@@ -4,17 +4,92 @@
 //! review and approve/reject implementation plans before execution.
 
 use super::planner::{ApprovalStatus, ImplementationPlan};
-use crate::Result;
+use crate::{
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    constants::SELF_UPDATE_APPROVAL_TIMEOUT_SECS,
+    Result,
+};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
 use tracing::{info, warn};
 
+/// Prefix of the custom IDs of the buttons on a plan message
+const APPROVAL_BUTTON_PREFIX: &str = "spiral-approval:";
+
+/// The buttons on a plan message; the message itself identifies the plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalButton {
+    Approve,
+    Reject,
+    ShowPlan,
+}
+
+impl ApprovalButton {
+    pub fn custom_id(self) -> String {
+        let action = match self {
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+            Self::ShowPlan => "show-plan",
+        };
+        format!("{APPROVAL_BUTTON_PREFIX}{action}")
+    }
+
+    /// None for buttons that are not approval buttons
+    pub fn from_custom_id(custom_id: &str) -> Option<Self> {
+        match custom_id.strip_prefix(APPROVAL_BUTTON_PREFIX)? {
+            "approve" => Some(Self::Approve),
+            "reject" => Some(Self::Reject),
+            "show-plan" => Some(Self::ShowPlan),
+            _ => None,
+        }
+    }
+}
+
+/// The Approve / Reject / Show Plan row attached to a plan message
+pub fn approval_buttons() -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(ApprovalButton::Approve.custom_id())
+            .label("Approve")
+            .style(ButtonStyle::Success),
+        CreateButton::new(ApprovalButton::Reject.custom_id())
+            .label("Reject")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(ApprovalButton::ShowPlan.custom_id())
+            .label("Show Plan")
+            .style(ButtonStyle::Secondary),
+    ])]
+}
+
+/// Why a button press on a plan message was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalRefusal {
+    /// The plan was already decided on, or the message is not a plan awaiting approval
+    NotPending,
+    Expired,
+    /// Only the requester and admins may decide on a plan
+    NotAllowed,
+}
+
+impl fmt::Display for ApprovalRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotPending => "❓ This plan is no longer awaiting approval.",
+            Self::Expired => "⌛ This approval request has expired.",
+            Self::NotAllowed => "🚫 Only the requester or an admin can decide on this plan.",
+        })
+    }
+}
+
 /// Manages pending plan approvals
 pub struct ApprovalManager {
     /// Maps message IDs to pending plans awaiting approval
     pending_approvals: Arc<RwLock<HashMap<u64, PendingApproval>>>,
+    /// How long a plan waits for a decision before it is stale
+    expiry: Duration,
 }
 
 /// A plan waiting for user approval
@@ -50,8 +125,14 @@ pub enum ApprovalResult {
 impl ApprovalManager {
     /// Create a new approval manager
     pub fn new() -> Self {
+        Self::with_expiry(Duration::from_secs(SELF_UPDATE_APPROVAL_TIMEOUT_SECS))
+    }
+
+    /// An approval manager whose plans go stale after `expiry`
+    pub fn with_expiry(expiry: Duration) -> Self {
         Self {
             pending_approvals: Arc::new(RwLock::new(HashMap::new())),
+            expiry,
         }
     }
 
@@ -73,6 +154,8 @@ impl ApprovalManager {
             requested_at: std::time::Instant::now(),
         };
 
+        // Plans whose executor stopped waiting without removing them go here
+        self.cleanup_old_approvals().await;
         let mut approvals = self.pending_approvals.write().await;
         approvals.insert(plan_message_id, pending);
 
@@ -187,11 +270,67 @@ impl ApprovalManager {
             return None;
         };
 
-        // Update the plan status in the stored pending approval
+        self.apply_decision(plan_message_id, &request_id, user_id, channel_id, &result)
+            .await;
+        Some((request_id, result))
+    }
+
+    /// 🔘 BUTTON DECISION: Approve or reject the plan presented in `plan_message_id`
+    /// 🛡️ SECURITY AUDIT CHECKPOINT: Anyone in the channel can press a button, so the
+    /// presser must be the requester or an admin; refusals of others are for the caller to audit
+    pub async fn decide(
+        &self,
+        plan_message_id: u64,
+        user_id: u64,
+        is_admin: bool,
+        result: ApprovalResult,
+    ) -> std::result::Result<String, ApprovalRefusal> {
+        let pending = self.pending_for_message(plan_message_id).await?;
+        if pending.user_id != user_id && !is_admin {
+            return Err(ApprovalRefusal::NotAllowed);
+        }
+        self.apply_decision(
+            plan_message_id,
+            &pending.request_id,
+            user_id,
+            pending.channel_id,
+            &result,
+        )
+        .await;
+        Ok(pending.request_id)
+    }
+
+    /// The plan presented in `plan_message_id`, if it still awaits a decision
+    pub async fn pending_for_message(
+        &self,
+        plan_message_id: u64,
+    ) -> std::result::Result<PendingApproval, ApprovalRefusal> {
+        let approvals = self.pending_approvals.read().await;
+        let pending = approvals
+            .get(&plan_message_id)
+            .ok_or(ApprovalRefusal::NotPending)?;
+        if pending.plan.approval_status != ApprovalStatus::Pending {
+            return Err(ApprovalRefusal::NotPending);
+        }
+        if pending.requested_at.elapsed() > self.expiry {
+            return Err(ApprovalRefusal::Expired);
+        }
+        Ok(pending.clone())
+    }
+
+    /// Record a decision on the stored plan, where wait_for_approval picks it up
+    async fn apply_decision(
+        &self,
+        plan_message_id: u64,
+        request_id: &str,
+        user_id: u64,
+        channel_id: u64,
+        result: &ApprovalResult,
+    ) {
         {
             let mut approvals = self.pending_approvals.write().await;
             if let Some(pending) = approvals.get_mut(&plan_message_id) {
-                match &result {
+                match result {
                     ApprovalResult::Approved => {
                         pending.plan.approval_status = ApprovalStatus::Approved;
                     }
@@ -210,8 +349,24 @@ impl ApprovalManager {
             "[ApprovalManager] Processed approval response: {:?} for request {}",
             result, request_id
         );
-
-        Some((request_id, result))
+        let decision = match result {
+            ApprovalResult::Approved => "approved".to_string(),
+            ApprovalResult::Rejected(reason) => format!("rejected: {reason}"),
+            ApprovalResult::ModifyRequested(details) => {
+                format!("modification requested: {details}")
+            }
+            ApprovalResult::TimedOut => "timed out".to_string(),
+        };
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::SelfUpdateApproval,
+                AuditSource::Discord,
+                format!("self-update {request_id}"),
+            )
+            .with_actor(user_id.to_string())
+            .with_client(format!("channel {channel_id}"))
+            .with_details(decision),
+        );
     }
 
     /// Remove and return a pending approval
@@ -220,14 +375,15 @@ impl ApprovalManager {
         approvals.remove(&plan_message_id)
     }
 
-    /// Clean up old pending approvals (older than 1 hour)
+    /// Clean up pending approvals that went stale without a decision
+    /// A grace period past the expiry leaves a waiting executor time to collect its own
     pub async fn cleanup_old_approvals(&self) {
         let mut approvals = self.pending_approvals.write().await;
         let now = std::time::Instant::now();
 
         approvals.retain(|_, pending| {
             let age = now.duration_since(pending.requested_at);
-            if age > Duration::from_secs(3600) {
+            if age > self.expiry * 2 {
                 warn!(
                     "[ApprovalManager] Removing old approval request: {} (age: {:?})",
                     pending.request_id, age
@@ -270,10 +426,10 @@ impl Default for ApprovalManager {
 /// Format approval instructions for Discord
 pub fn format_approval_instructions() -> &'static str {
     "\n📋 **Plan Review Instructions**\n\
-    React with:\n\
-    • ✅ to **approve** and proceed with implementation\n\
-    • ❌ to **reject** and cancel the update\n\
-    • ✏️ to **request modifications**\n\
+    Use the buttons below:\n\
+    • **Approve** to proceed with the implementation\n\
+    • **Reject** to cancel the update\n\
+    • **Show Plan** to read the full plan\n\
     \n\
     Or reply with:\n\
     • **approve** to proceed with the implementation\n\
@@ -383,4 +539,86 @@ mod tests {
 
         assert!(matches!(result.0, ApprovalResult::TimedOut));
     }
+
+    #[tokio::test]
+    async fn test_button_decisions_need_requester_or_admin() {
+        let manager = ApprovalManager::new();
+        manager
+            .register_for_approval(
+                create_test_plan(),
+                "test-request-123".to_string(),
+                123456,
+                789012,
+                111222,
+            )
+            .await;
+
+        assert_eq!(
+            manager
+                .decide(111222, 999, false, ApprovalResult::Approved)
+                .await,
+            Err(ApprovalRefusal::NotAllowed)
+        );
+        assert_eq!(
+            manager
+                .decide(333444, 123456, false, ApprovalResult::Approved)
+                .await,
+            Err(ApprovalRefusal::NotPending)
+        );
+        assert_eq!(
+            manager
+                .decide(111222, 999, true, ApprovalResult::Approved)
+                .await,
+            Ok("test-request-123".to_string())
+        );
+        // A decided plan takes no second decision
+        assert_eq!(
+            manager
+                .decide(111222, 123456, false, ApprovalResult::Approved)
+                .await,
+            Err(ApprovalRefusal::NotPending)
+        );
+
+        let (result, _) = manager
+            .wait_for_approval(111222, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(matches!(result, ApprovalResult::Approved));
+    }
+
+    #[tokio::test]
+    async fn test_stale_approvals_expire() {
+        let manager = ApprovalManager::with_expiry(Duration::from_millis(10));
+        manager
+            .register_for_approval(
+                create_test_plan(),
+                "test-request-123".to_string(),
+                123456,
+                789012,
+                111222,
+            )
+            .await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert_eq!(
+            manager
+                .pending_for_message(111222)
+                .await
+                .map(|pending| pending.request_id),
+            Err(ApprovalRefusal::Expired)
+        );
+        assert_eq!(
+            manager
+                .decide(111222, 123456, false, ApprovalResult::Approved)
+                .await,
+            Err(ApprovalRefusal::Expired)
+        );
+
+        manager.cleanup_old_approvals().await;
+        assert!(!manager.has_pending_approval(123456, 789012).await);
+        assert_eq!(
+            ApprovalButton::from_custom_id(&ApprovalButton::ShowPlan.custom_id()),
+            Some(ApprovalButton::ShowPlan)
+        );
+    }
 }
@@ -4,14 +4,18 @@
 //! git operations, Claude Code integration, validation pipeline, and result reporting.
 
 use super::{
-    format_approval_instructions, format_plan_for_discord,
+    approval_buttons, format_approval_instructions, format_plan_summary_for_discord,
     pre_validation::PreImplementationValidator, ApprovalManager, ApprovalResult, GitOperations,
     ImplementationPlan, PreflightChecker, ProgressReporter, ScopeLimiter, SelfUpdateRequest,
     StatusTracker, StructuredLogger, SystemLock, UpdatePhase, UpdatePlanner, UpdateQueue,
     UpdateStatus, ValidationPipeline,
 };
-use crate::{claude_code::ClaudeCodeClient, error::SpiralError, Result};
-use serenity::{http::Http, model::id::ChannelId};
+use crate::{agents::AgentOrchestrator, claude_code::ClaudeCodeClient, error::SpiralError, Result};
+use serenity::{
+    all::{CreateMessage, EditMessage},
+    http::Http,
+    model::id::{ChannelId, MessageId},
+};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -47,6 +51,8 @@ pub struct UpdateExecutor {
     approval_manager: Arc<ApprovalManager>,
     /// System lock to prevent concurrent updates
     system_lock: Arc<SystemLock>,
+    /// Orchestrator to drain before restarting, if running in orchestrator mode
+    orchestrator: Option<Arc<AgentOrchestrator>>,
     /// Test mode flag for shorter timeouts
     test_mode: bool,
 }
@@ -76,10 +82,17 @@ impl UpdateExecutor {
             discord_http,
             approval_manager,
             system_lock,
+            orchestrator: None,
             test_mode: false,
         }
     }
 
+    /// Drain this orchestrator's in-flight tasks before the restart point
+    pub fn with_orchestrator(mut self, orchestrator: Arc<AgentOrchestrator>) -> Self {
+        self.orchestrator = Some(orchestrator);
+        self
+    }
+
     /// Create a new update executor in test mode (with shorter timeouts)
     pub fn new_test_mode(
         queue: Arc<UpdateQueue>,
@@ -94,6 +107,7 @@ impl UpdateExecutor {
             discord_http: None,
             approval_manager,
             system_lock,
+            orchestrator: None,
             test_mode: true,
         }
     }
@@ -197,12 +211,86 @@ impl UpdateExecutor {
             return result;
         }
 
+        // Stop task dispatch and let in-flight tasks finish before restarting
+        if let Err(result) = self.execute_drain_phase(&mut context, &snapshot_id).await {
+            return result;
+        }
+
         // System restart would happen here (not implemented yet)
         // TODO: Implement system restart
 
         // Run post-restart validation pipeline
-        self.execute_validation_phase(&mut context, snapshot_id)
-            .await
+        let result = self
+            .execute_validation_phase(&mut context, snapshot_id)
+            .await;
+
+        // Without a real restart the same orchestrator keeps running, so resume it
+        if let Some(ref orchestrator) = self.orchestrator {
+            orchestrator.resume().await;
+        }
+
+        result
+    }
+
+    /// Drain the orchestrator so no task is mid-execution when the system restarts
+    /// 🏗️ ARCHITECTURE DECISION: Explicit drain instead of relying on shutdown timeouts
+    /// Why: Shutdown only waits a fixed time for background loops, so a long task could
+    /// be cut off mid-edit while its workspace is being replaced
+    /// Alternative: Cancel in-flight tasks (rejected: loses user work that was nearly done)
+    async fn execute_drain_phase(
+        &self,
+        context: &mut UpdateContext,
+        snapshot_id: &Option<String>,
+    ) -> std::result::Result<(), UpdateResult> {
+        let Some(ref orchestrator) = self.orchestrator else {
+            return Ok(());
+        };
+
+        context
+            .progress_reporter
+            .set_status("Draining in-flight agent tasks before restart...".to_string())
+            .await;
+        self.update_discord_status(
+            &context.request,
+            "🚰 Waiting for running tasks to finish...",
+        )
+        .await;
+
+        let max_wait = if self.test_mode {
+            std::time::Duration::from_secs(5)
+        } else {
+            std::time::Duration::from_secs(crate::constants::DRAIN_TIMEOUT_SECS)
+        };
+
+        match orchestrator.drain(max_wait).await {
+            Ok(()) => {
+                info!("[UpdateExecutor] Orchestrator drained");
+                Ok(())
+            }
+            Err(e) => {
+                error!("[UpdateExecutor] Failed to drain orchestrator: {}", e);
+                orchestrator.resume().await;
+                context
+                    .progress_reporter
+                    .set_phase(UpdatePhase::Failed)
+                    .await;
+                context.progress_reporter.stop().await;
+
+                if let Some(ref id) = snapshot_id {
+                    self.rollback_changes(id).await;
+                    self.update_discord_status(
+                        &context.request,
+                        "❌ Running tasks did not finish in time - changes rolled back",
+                    )
+                    .await;
+                }
+
+                Err(self.create_failure_result(
+                    context.request.clone(),
+                    format!("Failed to drain orchestrator before restart: {e}"),
+                ))
+            }
+        }
     }
 
     /// Initialize the update context
@@ -410,14 +498,18 @@ impl UpdateExecutor {
         let approval_timeout = if self.test_mode {
             tokio::time::Duration::from_millis(100)
         } else {
-            tokio::time::Duration::from_secs(600)
+            tokio::time::Duration::from_secs(crate::constants::SELF_UPDATE_APPROVAL_TIMEOUT_SECS)
         };
         let (approval_result, _) = match self
             .approval_manager
             .wait_for_approval(plan_message_id, approval_timeout)
             .await
         {
-            Ok(result) => result,
+            Ok(result) => {
+                self.close_approval_message(&context.request, plan_message_id, &result.0)
+                    .await;
+                result
+            }
             Err(e) => {
                 error!("[UpdateExecutor] Failed to wait for approval: {}", e);
                 context
@@ -901,6 +993,7 @@ impl UpdateExecutor {
                 "Follow existing code patterns and conventions".to_string(),
             ],
             session_id: Some(format!("update-{}-execution", request.id)),
+            dry_run: false,
         };
 
         // Execute the update via Claude Code
@@ -1136,45 +1229,24 @@ Implement all tasks according to the plan."#,
     ) -> u64 {
         if let Some(ref http) = self.discord_http {
             let channel_id = ChannelId::new(request.channel_id);
-            let mut plan_message = format_plan_for_discord(plan);
-            plan_message.push_str("\n\n");
+            // 🏗️ ARCHITECTURE DECISION: Summary with buttons, full plan on request
+            // Why: A full plan easily passes Discord's 2000 character limit, and buttons
+            // carry the message ID back so the decision cannot land on another plan
+            // Alternative: Reactions (rejected: they cannot answer privately, so neither
+            // the full plan nor a refusal can be shown to just the person who asked)
+            let mut plan_message = format_plan_summary_for_discord(plan);
             plan_message.push_str(format_approval_instructions());
+            let message = CreateMessage::new()
+                .content(plan_message)
+                .components(approval_buttons());
 
-            match channel_id.say(http, &plan_message).await {
+            match channel_id.send_message(http, message).await {
                 Ok(msg) => {
                     info!(
                         "[UpdateExecutor] Sent plan for approval, message ID: {}",
                         msg.id
                     );
-
-                    // Add emoji reactions for approval
-                    let message_id = msg.id;
-
-                    // Add check mark emoji for approval
-                    if let Err(e) = msg
-                        .react(http, crate::discord::messages::emojis::CHECK)
-                        .await
-                    {
-                        warn!("[UpdateExecutor] Failed to add approval emoji: {}", e);
-                    }
-
-                    // Add cross mark emoji for rejection
-                    if let Err(e) = msg
-                        .react(http, crate::discord::messages::emojis::CROSS)
-                        .await
-                    {
-                        warn!("[UpdateExecutor] Failed to add rejection emoji: {}", e);
-                    }
-
-                    // Add pencil emoji for modification request
-                    if let Err(e) = msg
-                        .react(http, crate::discord::messages::emojis::PENCIL)
-                        .await
-                    {
-                        warn!("[UpdateExecutor] Failed to add modification emoji: {}", e);
-                    }
-
-                    return message_id.get();
+                    return msg.id.get();
                 }
                 Err(e) => {
                     warn!("[UpdateExecutor] Failed to send plan for approval: {}", e);
@@ -1185,6 +1257,34 @@ Implement all tasks according to the plan."#,
         0
     }
 
+    /// Take the buttons off a decided or expired plan and note the outcome on it
+    async fn close_approval_message(
+        &self,
+        request: &SelfUpdateRequest,
+        plan_message_id: u64,
+        result: &ApprovalResult,
+    ) {
+        let (Some(http), true) = (&self.discord_http, plan_message_id != 0) else {
+            return;
+        };
+        let outcome = match result {
+            ApprovalResult::Approved => "✅ **Approved**",
+            ApprovalResult::Rejected(_) => "❌ **Rejected**",
+            ApprovalResult::ModifyRequested(_) => "📝 **Modifications requested**",
+            ApprovalResult::TimedOut => "⌛ **Expired** without a decision",
+        };
+        let channel_id = ChannelId::new(request.channel_id);
+        let message_id = MessageId::new(plan_message_id);
+        let content = match channel_id.message(http, message_id).await {
+            Ok(message) => format!("{}\n\n{outcome}", message.content),
+            Err(_) => outcome.to_string(),
+        };
+        let edit = EditMessage::new().content(content).components(vec![]);
+        if let Err(e) = channel_id.edit_message(http, message_id, edit).await {
+            warn!("[UpdateExecutor] Failed to close approval message: {}", e);
+        }
+    }
+
     /// Determine if an error is retryable
     fn is_retryable_error(&self, error: &str) -> bool {
         // Network/transient errors are retryable
@@ -378,11 +378,7 @@ impl UpdateMessageTemplates {
 
     /// Format progress percentage with visual bar
     pub fn progress_bar(current: usize, total: usize, phase: UpdatePhase) -> String {
-        let percentage = if total > 0 {
-            (current * 100) / total
-        } else {
-            0
-        };
+        let percentage = (current * 100).checked_div(total).unwrap_or(0);
 
         let filled = (percentage / 5).min(20);
         let empty = 20 - filled;
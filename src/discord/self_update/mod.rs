@@ -74,7 +74,8 @@ mod validation_agents;
 pub mod claude_spawn_example;
 
 pub use approval::{
-    format_approval_instructions, ApprovalManager, ApprovalResult, PendingApproval,
+    approval_buttons, format_approval_instructions, ApprovalButton, ApprovalManager,
+    ApprovalRefusal, ApprovalResult, PendingApproval,
 };
 pub use executor::{UpdateExecutor, UpdateResult};
 pub use fixable_issues::{FixableIssue, FixableIssueTracker, IssueCategory};
@@ -86,8 +87,8 @@ pub use pipeline::{
     Phase2Attempt, Phase2Checks, PipelineContext, PipelineStatus, ValidationPipeline,
 };
 pub use planner::{
-    format_plan_for_discord, ApprovalStatus, ImplementationPlan, PlannedTask, ResourceRequirements,
-    RiskLevel as PlanRiskLevel, TaskCategory, UpdatePlanner,
+    format_plan_for_discord, format_plan_summary_for_discord, ApprovalStatus, ImplementationPlan,
+    PlannedTask, ResourceRequirements, RiskLevel as PlanRiskLevel, TaskCategory, UpdatePlanner,
 };
 pub use progress_reporter::{ProgressReporter, UpdatePhase, UpdateProgress};
 pub use queue::{UpdateQueue, UpdateQueueStatus};
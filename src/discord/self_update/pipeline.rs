@@ -526,6 +526,7 @@ impl FixHandler for ClaudeFixHandler {
                 "Preserve existing functionality".to_string(),
             ],
             session_id: Some(format!("phase2-{}-fix", check_name)),
+            dry_run: false,
         };
 
         // Execute Claude fix with timeout
@@ -1588,6 +1589,7 @@ impl ValidationPipeline {
                 existing_code: None,
                 requirements: vec![],
                 session_id: None,
+                dry_run: false,
             };
 
             // Execute with timeout
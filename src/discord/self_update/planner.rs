@@ -156,6 +156,7 @@ impl UpdatePlanner {
                 "Provide accurate time estimates".to_string(),
             ],
             session_id: Some(format!("planning-{}", request.id)),
+            dry_run: false,
         };
 
         // Execute the planning request
@@ -802,6 +803,29 @@ fn format_risk_level(risk: &RiskLevel) -> String {
     }
 }
 
+/// Short form of a plan for the approval message; the full plan is one button away
+pub fn format_plan_summary_for_discord(plan: &ImplementationPlan) -> String {
+    let mut output = format!("## 📋 Implementation Plan: {}\n\n", plan.plan_id);
+    if plan.requires_human_approval {
+        output.push_str("⚠️ **HUMAN APPROVAL REQUIRED** ⚠️\n");
+        if let Some(ref reason) = plan.approval_reason {
+            output.push_str(&format!("**Reason**: {reason}\n"));
+        }
+        output.push('\n');
+    }
+    output.push_str(&format!("**Summary**: {}\n", plan.summary));
+    output.push_str(&format!(
+        "**Risk Level**: {}\n",
+        format_risk_level(&plan.risk_level)
+    ));
+    output.push_str(&format!(
+        "**Scope**: {} tasks, {} identified risks\n",
+        plan.tasks.len(),
+        plan.identified_risks.len()
+    ));
+    output
+}
+
 /// Format a plan for Discord display
 pub fn format_plan_for_discord(plan: &ImplementationPlan) -> String {
     let mut output = String::new();
@@ -152,7 +152,7 @@ impl PreImplementationValidator {
         info!("[PreValidator] Running Phase 1: Engineering Review");
 
         // Run the 4 parts of Engineering Review - deep quality inspection
-        let checks = vec![
+        let checks = [
             self.check_code_standards(request, logger).await,
             self.check_testing_coverage(request, logger).await,
             self.check_security(request, logger).await,
@@ -292,6 +292,7 @@ impl PreImplementationValidator {
                     "Check for fake implementations".to_string(),
                 ],
                 session_id: Some(format!("validation-{}-code-standards", request.id)),
+                dry_run: false,
             };
 
             match claude_client.generate_code(code_request).await {
@@ -445,6 +446,7 @@ impl PreImplementationValidator {
                     "Test error boundaries and edge cases".to_string(),
                 ],
                 session_id: Some(format!("validation-{}-testing", request.id)),
+                dry_run: false,
             };
 
             match claude_client.generate_code(code_request).await {
@@ -565,6 +567,7 @@ impl PreImplementationValidator {
                     "Audit dependencies for known CVEs".to_string(),
                 ],
                 session_id: Some(format!("validation-{}-security", request.id)),
+                dry_run: false,
             };
 
             match claude_client.generate_code(code_request).await {
@@ -695,6 +698,7 @@ impl PreImplementationValidator {
                     "Validate system components work together".to_string(),
                 ],
                 session_id: Some(format!("validation-{}-integration", request.id)),
+                dry_run: false,
             };
 
             match claude_client.generate_code(code_request).await {
@@ -38,6 +38,15 @@ impl SelfUpdateTestHarness {
                 allowed_tools: vec!["write".to_string(), "read".to_string()],
                 workspace_cleanup_after_hours: 1,
                 max_workspace_size_mb: 100,
+                max_concurrent_invocations: 4,
+                daily_budget_usd: None,
+                response_cache: Default::default(),
+                circuit_breaker: Default::default(),
+                sandbox: Default::default(),
+                mcp_servers: Default::default(),
+                verify_cli_version: true,
+                workspace_templates: Default::default(),
+                transcripts: Default::default(),
             };
             Some(ClaudeCodeClient::new(config).await?)
         } else {
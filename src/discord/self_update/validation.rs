@@ -226,7 +226,7 @@ impl PreflightChecker {
         {
             // Similar implementation for Linux
             let df_output = Command::new("df")
-                .args(&["-BM", "."])
+                .args(["-BM", "."])
                 .output()
                 .map_err(|e| {
                     SpiralError::SystemError(format!("Failed to check disk space: {}", e))
@@ -0,0 +1,260 @@
+//! Guided first-run setup of a guild
+//!
+//! `!spiral setup` asks the admin who ran it a few questions in that channel: where Spiral
+//! answers, where tasks may start, where results and security alerts go and which server
+//! role operates it. The answers become the guild's settings, the same ones
+//! `!spiral config` changes one at a time.
+
+use crate::constants::SETUP_IDLE_MINUTES;
+use crate::discord::commands::guild_config::{parse_change, ConfigChange};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The questions of the wizard, in the order they are asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    Channels,
+    TaskChannels,
+    ResultsChannel,
+    OpsChannel,
+    OperatorRole,
+}
+
+impl SetupStep {
+    const ALL: [SetupStep; 5] = [
+        SetupStep::Channels,
+        SetupStep::TaskChannels,
+        SetupStep::ResultsChannel,
+        SetupStep::OpsChannel,
+        SetupStep::OperatorRole,
+    ];
+
+    fn number(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or(0) + 1
+    }
+
+    fn next(self) -> Option<SetupStep> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    pub fn question(self) -> String {
+        let question = match self {
+            SetupStep::Channels => {
+                "**Channels** · Which channels should Spiral answer in? Mention them, or reply `all`."
+            }
+            SetupStep::TaskChannels => {
+                "**Task channels** · Where may agent tasks be started? Mention channels, or reply `all` for every channel Spiral answers in."
+            }
+            SetupStep::ResultsChannel => {
+                "**Results** · Which channel should completed tasks be announced in? Mention one, or reply `none`."
+            }
+            SetupStep::OpsChannel => {
+                "**Security alerts** · Which channel should security alerts go to? Mention one, or reply `none`."
+            }
+            SetupStep::OperatorRole => {
+                "**Operators** · Members of which server role may start agent tasks? Mention it, or reply `none`."
+            }
+        };
+        format!(
+            "🧭 **{}/{}** {question}\n*`skip` keeps the current setting, `cancel` stops the setup.*",
+            self.number(),
+            Self::ALL.len()
+        )
+    }
+
+    /// The settings change an answer asks for; None keeps the setting as it is
+    fn change(self, answer: &str) -> Result<Option<ConfigChange>, String> {
+        let command = match self {
+            SetupStep::Channels => format!("channels {answer}"),
+            SetupStep::TaskChannels => format!("tasks {answer}"),
+            SetupStep::ResultsChannel => format!("results {answer}"),
+            SetupStep::OpsChannel => format!("ops {answer}"),
+            SetupStep::OperatorRole if answer.eq_ignore_ascii_case("none") => return Ok(None),
+            SetupStep::OperatorRole => format!("role {answer} operator"),
+        };
+        // `!spiral config` explains its whole syntax on errors; only the first line applies
+        parse_change(&command)
+            .map_err(|message| message.split("\n\n").next().unwrap_or_default().to_string())
+    }
+}
+
+/// Where a wizard is after an answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetupProgress {
+    /// Ask this, the next question or the same one again after a bad answer
+    Ask(String),
+    /// All questions are answered; save these changes to the guild's settings
+    Finished {
+        guild_id: u64,
+        changes: Vec<ConfigChange>,
+    },
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+struct SetupSession {
+    guild_id: u64,
+    step: SetupStep,
+    changes: Vec<ConfigChange>,
+    last_activity: Instant,
+}
+
+impl SetupSession {
+    fn is_expired(&self) -> bool {
+        self.last_activity.elapsed() > Duration::from_secs(SETUP_IDLE_MINUTES * 60)
+    }
+}
+
+/// 🧭 SETUP WIZARDS: Running `!spiral setup` wizards per channel and user
+/// 🏗️ ARCHITECTURE DECISION: In-memory map like the compose drafts, settings written once
+/// at the end
+/// Why: A half-answered setup must not leave the guild restricted to channels chosen in the
+/// first answer, and a wizard is worthless after a restart
+/// Alternative: Discord modals (rejected: they only exist for interactions and cannot
+/// take channel or role mentions)
+#[derive(Debug, Default)]
+pub struct SetupWizards {
+    sessions: Mutex<HashMap<(u64, u64), SetupSession>>,
+}
+
+impl SetupWizards {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a wizard, replacing one the user already ran in the channel; returns the first
+    /// question
+    pub fn start(&self, guild_id: u64, channel_id: u64, user_id: u64) -> String {
+        let step = SetupStep::Channels;
+        self.sessions.lock().unwrap().insert(
+            (channel_id, user_id),
+            SetupSession {
+                guild_id,
+                step,
+                changes: Vec::new(),
+                last_activity: Instant::now(),
+            },
+        );
+        step.question()
+    }
+
+    pub fn is_open(&self, channel_id: u64, user_id: u64) -> bool {
+        let mut sessions = self.sessions.lock().unwrap();
+        match sessions.get(&(channel_id, user_id)) {
+            Some(session) if session.is_expired() => {
+                sessions.remove(&(channel_id, user_id));
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Take the user's answer to the current question; None when they run no wizard here
+    pub fn answer(&self, channel_id: u64, user_id: u64, answer: &str) -> Option<SetupProgress> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let key = (channel_id, user_id);
+        let session = sessions
+            .get_mut(&key)
+            .filter(|session| !session.is_expired())?;
+        let answer = answer.trim();
+        if answer.eq_ignore_ascii_case("cancel") {
+            sessions.remove(&key);
+            return Some(SetupProgress::Cancelled);
+        }
+
+        session.last_activity = Instant::now();
+        if !answer.eq_ignore_ascii_case("skip") {
+            match session.step.change(answer) {
+                Ok(change) => session.changes.extend(change),
+                Err(message) => {
+                    return Some(SetupProgress::Ask(format!(
+                        "{message}\n\n{}",
+                        session.step.question()
+                    )))
+                }
+            }
+        }
+        match session.step.next() {
+            Some(step) => {
+                session.step = step;
+                Some(SetupProgress::Ask(step.question()))
+            }
+            None => sessions
+                .remove(&key)
+                .map(|session| SetupProgress::Finished {
+                    guild_id: session.guild_id,
+                    changes: session.changes,
+                }),
+        }
+    }
+
+    /// Stop the user's wizard without saving; false when they ran none here
+    pub fn cancel(&self, channel_id: u64, user_id: u64) -> bool {
+        self.sessions
+            .lock()
+            .unwrap()
+            .remove(&(channel_id, user_id))
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+
+    #[test]
+    fn test_wizard_collects_answers_into_settings_changes() {
+        let wizards = SetupWizards::new();
+        assert!(wizards.answer(1, 2, "all").is_none());
+
+        assert!(wizards.start(9, 1, 2).contains("1/5"));
+        assert!(wizards.is_open(1, 2));
+        assert!(!wizards.is_open(1, 3));
+
+        let Some(SetupProgress::Ask(question)) = wizards.answer(1, 2, "<#10> <#11>") else {
+            panic!("the wizard asks the next question");
+        };
+        assert!(question.contains("2/5"));
+        // A bad answer repeats the question without moving on
+        let Some(SetupProgress::Ask(retry)) = wizards.answer(1, 2, "<#abc>") else {
+            panic!("the wizard asks again");
+        };
+        assert!(retry.starts_with("❌") && retry.contains("2/5"));
+        wizards.answer(1, 2, "skip");
+        wizards.answer(1, 2, "none");
+        wizards.answer(1, 2, "<#12>");
+
+        assert_eq!(
+            wizards.answer(1, 2, "<@&5>"),
+            Some(SetupProgress::Finished {
+                guild_id: 9,
+                changes: vec![
+                    ConfigChange::Channels(vec![10, 11]),
+                    ConfigChange::ResultsChannel(None),
+                    ConfigChange::OpsChannel(Some(12)),
+                    ConfigChange::RoleGrant {
+                        role_id: 5,
+                        role: Some(Role::Operator)
+                    },
+                ],
+            })
+        );
+        assert!(!wizards.is_open(1, 2));
+    }
+
+    #[test]
+    fn test_wizard_cancels() {
+        let wizards = SetupWizards::new();
+        wizards.start(9, 1, 2);
+        assert_eq!(
+            wizards.answer(1, 2, "Cancel"),
+            Some(SetupProgress::Cancelled)
+        );
+        assert!(!wizards.cancel(1, 2));
+        wizards.start(9, 1, 2);
+        assert!(wizards.cancel(1, 2));
+    }
+}
@@ -1,46 +1,99 @@
 use crate::{
-    agents::{Agent, AgentOrchestrator, SoftwareDeveloperAgent},
-    claude_code::ClaudeCodeClient,
-    config::DiscordConfig,
+    agents::{
+        orchestrator::{
+            scheduler::SCHEDULE_ID_CONTEXT_KEY, AgentTaskRecord, ProgressReporter, ScheduledTask,
+            Submitter, TaskProgress, TaskProgressTracker, TaskSchedule,
+        },
+        Agent, AgentOrchestrator, OrchestratorHandle, ProjectManagerAgent, QualityAssuranceAgent,
+        SoftwareDeveloperAgent,
+    },
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    auth::rbac,
+    claude_code::{
+        sessions::{SESSION_CONTEXT_KEY, THREAD_CONTEXT_KEY},
+        ClaudeCodeClient, CostTracker,
+    },
+    config::{Config, DiscordConfig, Reloadable},
+    constants::{
+        DISCORD_DIGEST_CHECK_SECS, DISCORD_PRESENCE_MIN_INTERVAL_SECS,
+        DISCORD_TASK_ID_DISPLAY_LENGTH, DM_RATE_LIMIT_PER_MINUTE,
+    },
     discord::{
-        commands::CommandRouter,
+        clarification::{self, Clarifications, Interpretation, Resolution},
+        commands::{self, slash, CommandRouter},
+        compose::{Collected, ComposeDrafts},
+        digest::{self, DigestEntry, NotificationDigests},
+        guild_settings::{GuildSettings, GuildSettingsStore},
+        locale::{Locale, MessageCatalog},
         lordgenome_quotes::{DenialSeverity, LordgenomeQuoteGenerator},
         message_state_manager::{MessageStateConfig, MessageStateManager},
         messages::{self, emojis, risk_level_to_str},
+        paginator::{self, PagedAnswer},
+        presence::{self, WorkSnapshot},
         reaction_handler,
         self_update::{
-            ApprovalManager, FixableIssueTracker, GitOperations, PreflightChecker,
+            format_plan_for_discord, ApprovalButton, ApprovalManager, ApprovalRefusal,
+            ApprovalResult, FixableIssueTracker, GitOperations, PreflightChecker,
             SelfUpdateRequest, StatusTracker, SystemLock, UpdateExecutor, UpdateQueue,
             UpdateStatus, UpdateType, UpdateValidator,
         },
+        setup_wizard::SetupWizards,
+        task_actions::{self, TaskAction, TaskMessages},
+        task_reply::{self, TaskReply},
+        task_threads::{self, TaskThreads},
+        user_prefs::{UserPrefs, UserPrefsStore, Verbosity},
+        user_sessions::{self, UserSessions},
         IntentClassifier, IntentResponse, IntentType, MessageSecurityValidator, RiskLevel,
         SecureMessageHandler,
     },
-    models::{AgentType, Priority, Task},
+    models::{AgentType, Priority, Task, TaskStatus},
+    monitoring::{AvailabilityReport, SloReport, SystemMonitor},
+    prompts,
+    rate_limit::{
+        tiers::{RateLimitTier, RateLimitTiers},
+        RateLimiterStore, TokenBucket,
+    },
+    session::USER_SESSION_CONTEXT_KEY,
     Result, SpiralError,
 };
+use arc_swap::ArcSwap;
 use serde::{Deserialize, Serialize};
 use serenity::{
+    all::{CommandInteraction, ComponentInteraction, Interaction},
     async_trait,
+    builder::{
+        CreateInteractionResponse, CreateInteractionResponseFollowup,
+        CreateInteractionResponseMessage, CreateMessage, CreateThread,
+    },
+    http::Http,
     model::{
-        channel::{Message, Reaction},
+        channel::{AutoArchiveDuration, Channel, Message, Reaction},
         gateway::Ready,
         guild::Role,
-        id::GuildId,
+        id::{ChannelId, GuildId, RoleId, UserId},
         permissions::Permissions,
         user::{OnlineStatus, User},
     },
     prelude::*,
 };
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
+/// Task context key holding the Discord user who asked for the task
+pub const AUTHOR_CONTEXT_KEY: &str = "discord_author_id";
+/// Task context keys holding where the task was asked for
+pub const CHANNEL_CONTEXT_KEY: &str = "discord_channel_id";
+pub const GUILD_CONTEXT_KEY: &str = "discord_guild_id";
+
 /// Agent role name mappings for detection
 const AGENT_ROLE_MAPPINGS: &[(&str, AgentType)] = &[
     ("SpiralDev", AgentType::SoftwareDeveloper),
     ("SpiralPM", AgentType::ProjectManager),
+    ("SpiralQA", AgentType::QualityAssurance),
     // Only include implemented agents
 ];
 
@@ -48,6 +101,7 @@ const AGENT_ROLE_MAPPINGS: &[(&str, AgentType)] = &[
 const AGENT_MENTION_MAPPINGS: &[(&[&str], AgentType)] = &[
     (&["dev", "developer", "code"], AgentType::SoftwareDeveloper),
     (&["pm", "manager", "project"], AgentType::ProjectManager),
+    (&["qa", "quality", "tester"], AgentType::QualityAssurance),
     // 🏗️ ARCHITECTURE DECISION: Only map to implemented agents
     // Why: Avoid confusion with unavailable agents
     // Alternative: Keep all mappings (rejected: misleading to users)
@@ -130,13 +184,34 @@ pub enum UserIntent {
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
+/// 🔄 CONFIG RELOADER: Applies reloaded authorized users, roles and message limits to a
+/// running bot, which the Discord client owns by then
+pub struct DiscordConfigReloader {
+    discord_config: Arc<ArcSwap<DiscordConfig>>,
+    security_validator: Arc<tokio::sync::Mutex<MessageSecurityValidator>>,
+}
+
+#[async_trait]
+impl Reloadable for DiscordConfigReloader {
+    fn name(&self) -> &'static str {
+        "Discord bot"
+    }
+
+    async fn apply_config(&self, config: &Config) {
+        self.security_validator.lock().await.set_rate_limits(
+            config.discord.user_rate_limit,
+            config.discord.channel_rate_limit,
+        );
+        self.discord_config.store(Arc::new(config.discord.clone()));
+    }
+}
+
 /// 🌌 SPIRAL CONSTELLATION BOT: Single Discord bot with dynamic agent personas
 /// ARCHITECTURE DECISION: One bot, multiple personalities based on mention context
 /// Why: Simpler deployment, dynamic persona switching, maintains agent identity feel
 /// Alternative: Multiple Discord applications (rejected: deployment complexity)
 pub struct SpiralConstellationBot {
     // Direct mode (standalone Discord bot)
-    developer_agent: Option<Arc<SoftwareDeveloperAgent>>,
     claude_client: Option<Arc<ClaudeCodeClient>>,
     // 🏗️ ARCHITECTURE DECISION: Dynamic agent registry
     // Why: Look up agents by type without hardcoding
@@ -144,10 +219,13 @@ pub struct SpiralConstellationBot {
     agent_registry: Arc<std::sync::Mutex<HashMap<AgentType, Arc<dyn Agent>>>>,
     // Orchestrator mode (full system integration)
     orchestrator: Option<Arc<AgentOrchestrator>>,
+    // Metrics history behind the dashboard's availability figures, when monitoring runs
+    system_monitor: Option<Arc<SystemMonitor>>,
+    // Rate limit tiers granted to users, shared with the API
+    rate_limit_tiers: Arc<RateLimitTiers>,
     // Agent availability tracking
     active_agents: Arc<Mutex<HashSet<String>>>,
     // Common fields
-    #[allow(dead_code)]
     start_time: Instant,
     pub stats: Arc<tokio::sync::Mutex<BotStats>>,
     mention_regex: Regex,
@@ -166,8 +244,25 @@ pub struct SpiralConstellationBot {
     system_lock: Arc<SystemLock>,
     fixable_issues_tracker: Option<Arc<FixableIssueTracker>>,
     command_router: CommandRouter,
-    discord_config: DiscordConfig,
+    /// Swapped when access or message limits are reloaded
+    discord_config: Arc<ArcSwap<DiscordConfig>>,
     reaction_handler_manager: Arc<reaction_handler::ReactionHandlerManager>,
+    task_threads: TaskThreads,
+    task_messages: Arc<TaskMessages>,
+    compose_drafts: ComposeDrafts,
+    setup_wizards: SetupWizards,
+    clarifications: Clarifications,
+    digests: NotificationDigests,
+    /// Set once the loop posting scheduled task results has been started
+    schedule_delivery_started: AtomicBool,
+    /// Set once the loop keeping the bot's status in step with its work has been started
+    presence_updates_started: AtomicBool,
+    /// Set once the loop posting notification digests has been started
+    digest_delivery_started: AtomicBool,
+    guild_settings: GuildSettingsStore,
+    user_prefs: UserPrefsStore,
+    catalog: MessageCatalog,
+    user_sessions: UserSessions,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -311,6 +406,7 @@ impl AgentPersona {
         match agent_type {
             AgentType::SoftwareDeveloper => &AgentPersona::DEVELOPER,
             AgentType::ProjectManager => &AgentPersona::PROJECT_MANAGER,
+            AgentType::QualityAssurance => &AgentPersona::QUALITY_ASSURANCE,
             // Only implemented agents - no unused personas
         }
     }
@@ -341,31 +437,49 @@ impl SpiralConstellationBot {
         let message_state_manager =
             Arc::new(MessageStateManager::new(MessageStateConfig::default()));
 
+        // Start background cleanup task, which also expires paged answers
+        message_state_manager.clone().start_cleanup_task().await;
+
         // Initialize security components
-        let security_validator = Arc::new(tokio::sync::Mutex::new(MessageSecurityValidator::new()));
+        let security_validator = Arc::new(tokio::sync::Mutex::new(
+            MessageSecurityValidator::new().with_rate_limits(
+                discord_config.user_rate_limit,
+                discord_config.channel_rate_limit,
+            ),
+        ));
         let intent_classifier = Arc::new(IntentClassifier::new());
         let secure_message_handler = Arc::new(SecureMessageHandler::new());
 
         let active_agents = Arc::new(tokio::sync::Mutex::new(HashSet::new()));
 
-        // Register SpiralDev as active since we have a developer agent
+        // Register SpiralDev, SpiralPM and SpiralQA as active since we have their agents
         active_agents.lock().await.insert("SpiralDev".to_string());
+        active_agents.lock().await.insert("SpiralPM".to_string());
+        active_agents.lock().await.insert("SpiralQA".to_string());
 
         // 🏗️ ARCHITECTURE DECISION: Build agent registry
         // Why: Dynamic agent lookup by type
         // Alternative: Hardcoded if statements (rejected: tight coupling)
-        let dev_agent_arc = Arc::new(developer_agent);
         let mut agent_registry = HashMap::new();
         agent_registry.insert(
             AgentType::SoftwareDeveloper,
-            dev_agent_arc.clone() as Arc<dyn Agent>,
+            Arc::new(developer_agent) as Arc<dyn Agent>,
+        );
+        agent_registry.insert(
+            AgentType::ProjectManager,
+            Arc::new(ProjectManagerAgent::new(Some(claude_client.clone()))) as Arc<dyn Agent>,
+        );
+        agent_registry.insert(
+            AgentType::QualityAssurance,
+            Arc::new(QualityAssuranceAgent::new(Some(claude_client.clone()))) as Arc<dyn Agent>,
         );
 
         Ok(Self {
-            developer_agent: Some(dev_agent_arc.clone()),
             claude_client: Some(Arc::new(claude_client)),
             agent_registry: Arc::new(std::sync::Mutex::new(agent_registry)),
             orchestrator: None,
+            system_monitor: None,
+            rate_limit_tiers: Arc::new(RateLimitTiers::default()),
             start_time: Instant::now(),
             stats: Arc::new(tokio::sync::Mutex::new(BotStats::default())),
             mention_regex,
@@ -379,7 +493,36 @@ impl SpiralConstellationBot {
             system_lock: Arc::new(SystemLock::new()),
             fixable_issues_tracker: Some(Arc::new(FixableIssueTracker::new())),
             command_router: CommandRouter::new(),
-            discord_config,
+            guild_settings: GuildSettingsStore::open(
+                discord_config
+                    .guild_settings_path
+                    .as_ref()
+                    .map(PathBuf::from),
+            ),
+            user_prefs: UserPrefsStore::open(
+                discord_config.user_prefs_path.as_ref().map(PathBuf::from),
+            ),
+            catalog: MessageCatalog::load(
+                discord_config
+                    .locale_dir
+                    .as_deref()
+                    .map(std::path::Path::new),
+            ),
+            user_sessions: UserSessions::with_store(
+                discord_config.session_store.clone(),
+                discord_config.session_budget.clone(),
+                discord_config.session_policies.clone(),
+            ),
+            discord_config: Arc::new(ArcSwap::from_pointee(discord_config)),
+            task_threads: TaskThreads::new(),
+            task_messages: Arc::new(TaskMessages::new()),
+            compose_drafts: ComposeDrafts::new(),
+            setup_wizards: SetupWizards::new(),
+            clarifications: Clarifications::new(),
+            digests: NotificationDigests::new(),
+            schedule_delivery_started: AtomicBool::new(false),
+            presence_updates_started: AtomicBool::new(false),
+            digest_delivery_started: AtomicBool::new(false),
             reaction_handler_manager: Arc::new(reaction_handler::ReactionHandlerManager::new()),
         })
     }
@@ -405,7 +548,12 @@ impl SpiralConstellationBot {
         cleanup_manager.start_cleanup_task().await;
 
         // Initialize security components
-        let security_validator = Arc::new(tokio::sync::Mutex::new(MessageSecurityValidator::new()));
+        let security_validator = Arc::new(tokio::sync::Mutex::new(
+            MessageSecurityValidator::new().with_rate_limits(
+                discord_config.user_rate_limit,
+                discord_config.channel_rate_limit,
+            ),
+        ));
         let intent_classifier = Arc::new(IntentClassifier::new());
         let secure_message_handler = Arc::new(SecureMessageHandler::new());
 
@@ -417,11 +565,20 @@ impl SpiralConstellationBot {
             .await
             .insert("Orchestrator".to_string());
 
+        // 🗂️ The orchestrator archives a session's own workspace once the session ends
+        let user_sessions = UserSessions::with_store(
+            discord_config.session_store.clone(),
+            discord_config.session_budget.clone(),
+            discord_config.session_policies.clone(),
+        );
+        orchestrator.register_session_manager(user_sessions.manager());
+
         Ok(Self {
-            developer_agent: None,
             claude_client: None,
             agent_registry: Arc::new(std::sync::Mutex::new(HashMap::new())), // Orchestrator has its own agents
             orchestrator: Some(orchestrator),
+            system_monitor: None,
+            rate_limit_tiers: Arc::new(RateLimitTiers::default()),
             start_time: Instant::now(),
             stats: Arc::new(tokio::sync::Mutex::new(BotStats::default())),
             mention_regex,
@@ -435,14 +592,96 @@ impl SpiralConstellationBot {
             system_lock: Arc::new(SystemLock::new()),
             fixable_issues_tracker: Some(Arc::new(FixableIssueTracker::new())),
             command_router: CommandRouter::new(),
-            discord_config,
+            guild_settings: GuildSettingsStore::open(
+                discord_config
+                    .guild_settings_path
+                    .as_ref()
+                    .map(PathBuf::from),
+            ),
+            user_prefs: UserPrefsStore::open(
+                discord_config.user_prefs_path.as_ref().map(PathBuf::from),
+            ),
+            catalog: MessageCatalog::load(
+                discord_config
+                    .locale_dir
+                    .as_deref()
+                    .map(std::path::Path::new),
+            ),
+            user_sessions,
+            discord_config: Arc::new(ArcSwap::from_pointee(discord_config)),
+            task_threads: TaskThreads::new(),
+            task_messages: Arc::new(TaskMessages::new()),
+            compose_drafts: ComposeDrafts::new(),
+            setup_wizards: SetupWizards::new(),
+            clarifications: Clarifications::new(),
+            digests: NotificationDigests::new(),
+            schedule_delivery_started: AtomicBool::new(false),
+            presence_updates_started: AtomicBool::new(false),
+            digest_delivery_started: AtomicBool::new(false),
             reaction_handler_manager: Arc::new(reaction_handler::ReactionHandlerManager::new()),
         })
     }
 
+    /// Read availability for the admin dashboard from `monitor`'s metrics history
+    pub fn with_system_monitor(mut self, monitor: Arc<SystemMonitor>) -> Self {
+        self.system_monitor = Some(monitor);
+        self
+    }
+
+    /// Count message rate limits in `store`, shared with other bot instances, and scale
+    /// users' limits for the tiers in `tiers`
+    pub fn with_rate_limiting(
+        mut self,
+        store: Arc<dyn RateLimiterStore>,
+        tiers: Arc<RateLimitTiers>,
+    ) -> Self {
+        let config = self.discord_config.load();
+        self.security_validator = Arc::new(tokio::sync::Mutex::new(
+            MessageSecurityValidator::new()
+                .with_rate_limits(config.user_rate_limit, config.channel_rate_limit)
+                .with_rate_limiter_store(store)
+                .with_rate_limit_tiers(tiers.clone()),
+        ));
+        self.rate_limit_tiers = tiers;
+        self
+    }
+
+    pub fn rate_limit_tiers(&self) -> &Arc<RateLimitTiers> {
+        &self.rate_limit_tiers
+    }
+
+    /// What adopts reloaded access and message limits for this bot; take it once the
+    /// bot is fully built, since `with_rate_limiting` replaces the validator
+    pub fn config_reloader(&self) -> DiscordConfigReloader {
+        DiscordConfigReloader {
+            discord_config: Arc::clone(&self.discord_config),
+            security_validator: Arc::clone(&self.security_validator),
+        }
+    }
+
+    /// A user's rate limit tier and the messages they may send at once right now
+    pub async fn rate_limit_status(
+        &self,
+        user_id: u64,
+    ) -> (RateLimitTier, Option<(u32, TokenBucket)>) {
+        self.security_validator
+            .lock()
+            .await
+            .rate_limit_status(user_id)
+            .await
+    }
+
     /// 🎭 ROLE MANAGEMENT: Create agent persona roles in Discord server
+    /// Roles the guild already has are left alone, so running setup twice adds none
     pub async fn create_agent_roles(&self, ctx: &Context, guild_id: GuildId) -> Result<Vec<Role>> {
         let mut created_roles = Vec::new();
+        let existing_roles: Vec<String> = match guild_id.roles(&ctx.http).await {
+            Ok(roles) => roles.into_values().map(|role| role.name).collect(),
+            Err(e) => {
+                warn!("[SpiralConstellation] Failed to fetch roles: {}", e);
+                Vec::new()
+            }
+        };
 
         info!(
             "[SpiralConstellation] Creating agent persona roles in guild {}",
@@ -459,6 +698,9 @@ impl SpiralConstellationBot {
         ];
 
         for (persona, color) in personas {
+            if existing_roles.iter().any(|name| name == persona.name) {
+                continue;
+            }
             let edit_role = serenity::builder::EditRole::default()
                 .name(persona.name)
                 .colour(color)
@@ -596,7 +838,11 @@ impl SpiralConstellationBot {
             }
         }
 
-        // No fallback - only direct mentions are supported
+        // 🙋 PREFERENCE: Addressing Spiral without naming an agent reaches the author's
+        // preferred agent; other messages name their agent or get none
+        if self.mention_regex.is_match(content) || !msg.mention_roles.is_empty() {
+            return self.user_prefs.get(msg.author.id.get()).preferred_agent;
+        }
         None
     }
 
@@ -611,33 +857,23 @@ impl SpiralConstellationBot {
         let persona = AgentPersona::for_agent_type(&agent_type);
 
         // Enhance the description based on user intent
-        let enhanced_description = match intent {
-            UserIntent::StatusQuery => {
-                format!("INFORMATION QUERY: {content}. Please provide a clear, informative response about the current state of the workspace/project. Focus on listing, showing, or describing what exists rather than creating new code.")
-            }
-            UserIntent::TaskRequest => {
-                format!("DEVELOPMENT TASK: {content}. Please implement, create, or build the requested functionality following best practices.")
-            }
-            UserIntent::AgentSelection => {
-                format!("AGENT-SPECIFIC TASK: {content}. Please execute this task with the selected agent's specific expertise and capabilities.")
-            }
-            UserIntent::HelpRequest => {
-                format!("HELP REQUEST: {content}. Please provide helpful information about usage, capabilities, or guidance as requested.")
-            }
-            UserIntent::Greeting => {
-                format!("GREETING: {content}. Please respond appropriately to the user's greeting.")
-            }
-            UserIntent::Unknown => {
-                format!("GENERAL REQUEST: {content}. Please interpret and respond to this request appropriately.")
-            }
-        };
+        let enhanced_description = prompts::render(
+            Some(&agent_type),
+            "discord_task",
+            serde_json::json!({
+                "content": content,
+                "intent": format!("{intent:?}"),
+                "agent_type": format!("{agent_type:?}"),
+                "persona": persona.name,
+            }),
+        );
 
         let mut task = Task::new(agent_type, enhanced_description, Priority::Medium);
 
         // Add Discord context
         task = task
             .with_context(
-                "discord_channel_id".to_string(),
+                CHANNEL_CONTEXT_KEY.to_string(),
                 context.channel_id.to_string(),
             )
             .with_context(
@@ -645,7 +881,7 @@ impl SpiralConstellationBot {
                 context.message_id.to_string(),
             )
             .with_context(
-                "discord_author_id".to_string(),
+                AUTHOR_CONTEXT_KEY.to_string(),
                 context.author_id.to_string(),
             )
             .with_context("agent_persona".to_string(), persona.name.to_string())
@@ -656,28 +892,39 @@ impl SpiralConstellationBot {
             .with_context("user_intent".to_string(), format!("{intent:?}"));
 
         if let Some(guild_id) = context.guild_id {
-            task = task.with_context("discord_guild_id".to_string(), guild_id.to_string());
+            task = task.with_context(GUILD_CONTEXT_KEY.to_string(), guild_id.to_string());
+        }
+        if let Some(thread_id) = context.thread_id {
+            task = task.with_context(THREAD_CONTEXT_KEY.to_string(), thread_id.to_string());
+        }
+        if let Some(session_key) = context.session_key {
+            task = task.with_context(SESSION_CONTEXT_KEY.to_string(), session_key);
+        }
+        if let Some(user_session) = context.user_session {
+            task = task.with_context(
+                USER_SESSION_CONTEXT_KEY.to_string(),
+                user_session.to_string(),
+            );
         }
 
         task
     }
 
     /// 🎭 PERSONA RESPONSE: Format response in the agent's persona style
-    /// 🏗️ ARCHITECTURE DECISION: Delegate formatting to agents
-    /// Why: Each agent knows best how to format its output
+    /// 🏗️ ARCHITECTURE DECISION: Delegate formatting to agents, present it as an embed
+    /// Why: Each agent knows best how to format its output; the embed gives that output
+    /// 4096 characters, with files and timing as fields and produced files attached
     /// Alternative: Centralized formatting (rejected: violates SOLID)
-    fn format_persona_response(
+    /// The requester's verbosity shortens the answer or adds the run's details
+    async fn format_persona_response(
         &self,
         agent_type: &AgentType,
         result: &crate::models::TaskResult,
-    ) -> String {
+        verbosity: Verbosity,
+    ) -> TaskReply {
         let persona = AgentPersona::for_agent_type(agent_type);
-        let mut response = String::new();
+        let footer = format!("—{} @ SpiralConstellation", persona.name);
 
-        // Persona header
-        response.push_str(&format!("{} **{}**\n", persona.emoji, persona.name));
-
-        // Completion status
         match &result.result {
             crate::models::TaskExecutionResult::Success {
                 output,
@@ -693,33 +940,61 @@ impl SpiralConstellationBot {
                     } else {
                         "✅ Completed!"
                     };
-                response.push_str(&format!("{completion_message}\n\n"));
-
-                // 🏗️ ARCHITECTURE DECISION: Use agent registry for formatting
-                // Why: Dynamic agent lookup without hardcoding
-                // Alternative: Switch on agent type (rejected: tight coupling)
-                let formatted_output = {
-                    let registry = self.agent_registry.lock().unwrap();
-                    if let Some(agent) = registry.get(agent_type) {
-                        // Use the agent's own formatting
-                        agent.format_response(result)
-                    } else {
-                        // Fallback to generic formatting if agent not in registry
-                        self.generic_format_output(output, files_created, files_modified)
-                    }
-                };
 
-                response.push_str(&formatted_output);
-            }
-            crate::models::TaskExecutionResult::Failure { error, .. } => {
-                response.push_str(&format!("{} {error}", persona.error_style));
+                let files = task_reply::load_result_files(result).await;
+                let mut answer = self.agent_formatted_output(agent_type, result);
+                if verbosity == Verbosity::Brief {
+                    answer = task_reply::brief_answer(&answer);
+                }
+                let mut embed = task_reply::result_embed(
+                    &format!("{} {} · {completion_message}", persona.emoji, persona.name),
+                    &answer,
+                    &footer,
+                    result,
+                    &files,
+                );
+                if verbosity == Verbosity::Detailed {
+                    if let Some(details) = task_reply::result_details(result) {
+                        embed = embed.field("🔎 Details", details, false);
+                    }
+                }
+                // 📖 PAGINATION: An answer longer than one page is shown a page at a time
+                let pages = PagedAnswer::new(
+                    embed.clone(),
+                    task_reply::result_footer(&footer, result),
+                    &answer,
+                );
+                TaskReply {
+                    content: String::new(),
+                    embed: Some(pages.as_ref().map_or(embed, |pages| pages.embed(0))),
+                    attachments: files.into_attachments(),
+                    pages,
+                }
             }
+            crate::models::TaskExecutionResult::Failure { error, .. } => format!(
+                "{} **{}**\n{} {error}\n\n*{footer}*",
+                persona.emoji, persona.name, persona.error_style
+            )
+            .into(),
         }
+    }
 
-        // Persona-specific footer
-        response.push_str(&format!("\n\n*—{} @ SpiralConstellation*", persona.name));
-
-        response
+    /// 🏗️ ARCHITECTURE DECISION: Use agent registry for formatting
+    /// Why: Dynamic agent lookup without hardcoding
+    /// Alternative: Switch on agent type (rejected: tight coupling)
+    fn agent_formatted_output(
+        &self,
+        agent_type: &AgentType,
+        result: &crate::models::TaskResult,
+    ) -> String {
+        let registry = self.agent_registry.lock().unwrap();
+        match (registry.get(agent_type), &result.result) {
+            // Use the agent's own formatting
+            (Some(agent), _) => agent.format_response(result),
+            // Fallback when the agent is not in the registry; files are shown as embed fields
+            (None, crate::models::TaskExecutionResult::Success { output, .. }) => output.clone(),
+            (None, crate::models::TaskExecutionResult::Failure { error, .. }) => error.clone(),
+        }
     }
 
     /// 🧹 CLEAN MESSAGE: Remove mentions and extract clean content
@@ -728,38 +1003,8 @@ impl SpiralConstellationBot {
         cleaned.trim().to_string()
     }
 
-    /// 📋 GENERIC OUTPUT FORMATTING: Format output without agent-specific logic
-    fn generic_format_output(
-        &self,
-        output: &str,
-        files_created: &[String],
-        files_modified: &[String],
-    ) -> String {
-        let mut response = String::new();
-
-        // Add output with truncation if needed
-        if output.len() > MAX_OUTPUT_RESPONSE {
-            response.push_str(&output[..MAX_OUTPUT_RESPONSE]);
-            response.push_str("\n\n... (output truncated for Discord limits)");
-        } else {
-            response.push_str(output);
-        }
-
-        // Add file information if any
-        if !files_created.is_empty() || !files_modified.is_empty() {
-            response.push_str("\n\n**📁 Files:**\n");
-            if !files_created.is_empty() {
-                response.push_str(&format!("• Created: {} files\n", files_created.len()));
-            }
-            if !files_modified.is_empty() {
-                response.push_str(&format!("• Modified: {} files\n", files_modified.len()));
-            }
-        }
-
-        response
-    }
-
     /// 📋 CONCISE DEV SUMMARY: Extract key information for developer responses
+    #[allow(dead_code)]
     fn extract_dev_summary(
         &self,
         output: &str,
@@ -886,198 +1131,886 @@ impl SpiralConstellationBot {
         summary
     }
 
-    /// 💬 HELPFUL ERROR: Format user-friendly error messages with solutions
+    /// 💬 HELPFUL ERROR: Format user-friendly error messages with solutions, in the locale
     fn format_helpful_error_message(
         &self,
         error: &crate::SpiralError,
         persona: &AgentPersona,
+        locale: Locale,
     ) -> String {
         let error_str = error.to_string();
         let error_lower = error_str.to_lowercase();
-
-        // Check for timeout specifically
-        if error_lower.contains("timed out") || error_lower.contains("timeout") {
-            return format!(
-                "{} **{}**\n⏰ **System Timeout**\n\n\
-                I waited 30 seconds but couldn't connect to the Claude Code system.\n\n\
-                **🔧 This usually means:**\n\
-                • The full Spiral Core system isn't running\n\
-                • You're running just the Discord bot (`cargo run --bin discord-bot`)\n\
-                • Claude Code CLI is not available or responding\n\n\
-                **💡 To fix this:**\n\
-                • **Stop this bot** (Ctrl+C)\n\
-                • **Run the full system:** `cargo run`\n\
-                • **OR** add `CLAUDE_API_KEY` to your `.env` and restart\n\n\
-                **🤖 Current Mode:** Discord-only (no task execution)\n\n\
-                *—{} @ SpiralConstellation*",
-                persona.emoji, persona.name, persona.name
-            );
-        }
-
-        // Check for common system connectivity issues
-        if error_lower.contains("claude")
+        let key = if matches!(error, crate::SpiralError::RateLimit { .. }) {
+            // Limits such as the daily budget explain themselves; no troubleshooting applies
+            "errors.limit_reached"
+        } else if error_lower.contains("timed out") || error_lower.contains("timeout") {
+            "errors.timeout"
+        } else if error_lower.contains("claude")
             && (error_lower.contains("connection") || error_lower.contains("unavailable"))
         {
-            return format!(
-                "{} **{}**\n🔌 **Claude Code System Unavailable**\n\n\
-                I can't connect to the Claude Code system right now. This usually means:\n\n\
-                **🔧 Quick Fix:**\n\
-                • Make sure the full Spiral Core system is running: `cargo run`\n\
-                • Check that your `CLAUDE_API_KEY` is set in `.env`\n\
-                • Verify Claude Code CLI is installed and accessible\n\n\
-                **💡 Running Discord Bot Only?**\n\
-                The Discord bot (`cargo run --bin discord-bot`) needs the full Spiral Core system \
-                to actually execute tasks. Try running the complete system instead!\n\n\
-                *—{} @ SpiralConstellation (Discord-only mode)*",
-                persona.emoji, persona.name, persona.name
-            );
-        }
-
-        // Check for API key issues
-        if error_lower.contains("api")
+            // Common system connectivity issues
+            "errors.claude_unavailable"
+        } else if error_lower.contains("api")
             && (error_lower.contains("key")
                 || error_lower.contains("auth")
                 || error_lower.contains("unauthorized"))
         {
-            return format!(
-                "{} **{}**\n🔑 **API Authentication Issue**\n\n\
-                There's a problem with API authentication:\n\n\
-                **🔧 Check your `.env` file:**\n\
-                • `CLAUDE_API_KEY=your_key_here`\n\
-                • `API_KEY=your_spiral_api_key`\n\n\
-                **💡 Get Claude API Key:**\n\
-                Visit [Anthropic Console](https://console.anthropic.com) to get your API key\n\n\
-                *—{} @ SpiralConstellation*",
-                persona.emoji, persona.name, persona.name
-            );
-        }
-
-        // Check for workspace/permission issues
-        if error_lower.contains("permission")
+            "errors.api_key"
+        } else if error_lower.contains("permission")
             || error_lower.contains("workspace")
             || error_lower.contains("directory")
         {
-            return format!(
-                "{} **{}**\n📁 **Workspace Permission Issue**\n\n\
-                I'm having trouble accessing the workspace:\n\n\
-                **🔧 Try these fixes:**\n\
-                • Check file permissions in your project directory\n\
-                • Ensure Claude Code has write access\n\
-                • Run from your project root directory\n\n\
-                *—{} @ SpiralConstellation*",
-                persona.emoji, persona.name, persona.name
-            );
-        }
+            "errors.workspace"
+        } else {
+            // Generic error with helpful context
+            "errors.generic"
+        };
+        let message = match error {
+            crate::SpiralError::RateLimit { message } => message.as_str(),
+            _ => "",
+        };
 
-        // Generic error with helpful context
-        format!(
-            "{} **{}**\n{} Something went wrong!\n\n\
-            **🔍 Error Details:**\n```\n{}\n```\n\n\
-            **💡 Troubleshooting:**\n\
-            • Make sure Spiral Core is running: `cargo run`\n\
-            • Check your `.env` configuration\n\
-            • Try a simpler request first\n\n\
-            **🆘 Need Help?**\n\
-            Use `!spiral help` for commands or check the documentation\n\n\
-            *—{} @ SpiralConstellation*",
-            persona.emoji, persona.name, persona.error_style, error_str, persona.name
+        self.catalog.format(
+            locale,
+            key,
+            &[
+                ("emoji", persona.emoji),
+                ("name", persona.name),
+                ("error_style", persona.error_style),
+                ("message", message),
+                ("error", &error_str),
+            ],
         )
     }
 
-    /// 🔐 PERMISSION CHECK: Check if user is in authorized users list from config
-    pub fn is_authorized_user(&self, user_id: u64) -> bool {
-        self.discord_config.authorized_users.contains(&user_id)
-    }
+    /// ⏳ PROGRESS UPDATE: Edit text for a running task, from the agent's reported progress
+    fn format_progress_update(
+        persona: &AgentPersona,
+        action_description: &str,
+        request_label: &str,
+        request: &str,
+        progress: Option<&TaskProgress>,
+        queued: bool,
+        elapsed_secs: u64,
+    ) -> String {
+        let request_preview = if request.chars().count() > 100 {
+            format!("{}...", request.chars().take(100).collect::<String>())
+        } else {
+            request.to_string()
+        };
 
-    // Removed hardcoded agent checks - use is_agent_active() instead
+        let status_line = match progress {
+            Some(progress) => {
+                let detail = progress
+                    .message
+                    .as_deref()
+                    .map(|message| format!(" ({message})"))
+                    .unwrap_or_default();
+                format!(
+                    "{} {}% · {}{detail} ({elapsed_secs}s)",
+                    crate::discord::self_update::ProgressReporter::create_progress_bar(
+                        progress.percent
+                    ),
+                    progress.percent,
+                    progress.phase
+                )
+            }
+            None if queued => format!("⏳ Waiting in the queue... ({elapsed_secs}s)"),
+            None => format!("⚙️ Working on this... ({elapsed_secs}s)"),
+        };
 
-    /// 🏗️ ARCHITECTURE DECISION: Dynamic agent management
-    /// Why: Generic methods work with any agent type, no hardcoding
-    /// Alternative: Type-specific methods (rejected: doesn't scale)
-    /// Trade-off: Slightly more verbose but infinitely extensible
-    ///
-    /// Check if a specific agent is currently active
-    pub async fn is_agent_active(&self, agent_name: &str) -> bool {
-        self.active_agents.lock().await.contains(agent_name)
+        format!(
+            "{} **{}**\n{}\n\n{request_label} {}\n\n{}",
+            persona.emoji, persona.name, action_description, request_preview, status_line
+        )
     }
 
-    /// Register an agent as active (called when agent is initialized)
-    /// 🏗️ ARCHITECTURE DECISION: Verify Discord role exists when registering
-    /// Why: Ensure agents can be mentioned in Discord when they're active
-    /// Alternative: Create roles on demand (rejected: needs bot permissions)
-    pub async fn register_active_agent(&self, agent_name: String, ctx: Option<&Context>) {
-        info!(
-            "[SpiralConstellation] Registering active agent: {}",
-            agent_name
-        );
+    /// 🧵 TASK THREAD: Open a thread on the message that started a task, so its progress
+    /// and result stay together and later messages there continue it with the same agent
+    /// None when the thread could not be opened, e.g. without thread permissions; the task
+    /// then answers in the channel as before
+    async fn open_task_thread(
+        &self,
+        ctx: &Context,
+        msg: &Message,
+        agent_type: &AgentType,
+        request: &str,
+    ) -> Option<ChannelId> {
+        let persona = AgentPersona::for_agent_type(agent_type);
+        let builder = CreateThread::new(task_threads::thread_name(persona.name, request))
+            .auto_archive_duration(AutoArchiveDuration::OneDay);
+        match msg
+            .channel_id
+            .create_thread_from_message(&ctx.http, msg.id, builder)
+            .await
+        {
+            Ok(thread) => {
+                info!(
+                    "[SpiralConstellation] Opened thread {} for {} task",
+                    thread.id, persona.name
+                );
+                self.task_threads
+                    .register(thread.id.get(), agent_type.clone());
+                Some(thread.id)
+            }
+            Err(e) => {
+                warn!(
+                    "[SpiralConstellation] Could not open a task thread in channel {}: {}",
+                    msg.channel_id, e
+                );
+                None
+            }
+        }
+    }
 
-        // Check if Discord role exists for this agent (if context provided)
-        // Note: We can't check specific guilds without guild_id in config
-        // This would need to be done in the ready event or when handling messages
-        if let Some(_ctx) = ctx {
-            info!(
-                "[SpiralConstellation] Agent {} registered. Discord role check will be performed in ready event.",
-                agent_name
+    /// 📊 STATUS REPORT: One task's state when given its ID, otherwise the system's
+    pub(crate) async fn status_report(&self, task_id: Option<&str>) -> String {
+        let Some(orchestrator) = &self.orchestrator else {
+            let stats = self.stats.lock().await;
+            let note = if task_id.is_some() {
+                "\n\n*Task lookup needs the full system (`cargo run`); this bot runs agents directly.*"
+            } else {
+                ""
+            };
+            return format!(
+                "📊 **Spiral Status** (direct mode)\n\n\
+                ✅ Tasks completed: {}\n\
+                ❌ Tasks failed: {}\n\
+                ⏱️ Uptime: {}s{note}",
+                stats.dev_tasks_completed,
+                stats.total_tasks_failed,
+                self.start_time.elapsed().as_secs()
             );
-        }
+        };
 
-        self.active_agents.lock().await.insert(agent_name);
+        let Some(task_id) = task_id else {
+            let status = orchestrator.get_system_status().await;
+            let mut agents: Vec<_> = status.agents.iter().collect();
+            agents.sort_by_key(|(agent_type, _)| format!("{agent_type:?}"));
+            let mut report = format!(
+                "📊 **Spiral Status**\n\n⏳ Queued tasks: {}\n⏱️ Uptime: {:.0}s\n\n**Agents**\n",
+                status.queue_length, status.system_uptime
+            );
+            for (agent_type, agent) in agents {
+                let persona = AgentPersona::for_agent_type(agent_type);
+                report.push_str(&format!(
+                    "{} **{}**: {} · {} completed · {} failed\n",
+                    persona.emoji,
+                    persona.name,
+                    if agent.is_busy { "busy" } else { "idle" },
+                    agent.tasks_completed,
+                    agent.tasks_failed
+                ));
+            }
+            return report;
+        };
+
+        let Some(task) = orchestrator.get_task_status(task_id).await else {
+            return format!("❓ No task `{task_id}` found.");
+        };
+        let persona = AgentPersona::for_agent_type(&task.agent_type);
+        let mut report = format!(
+            "{} **{}**\n📋 Task `{}`: {:?} ({:?} priority)",
+            persona.emoji, persona.name, task.id, task.status, task.priority
+        );
+        // A status lookup answers in text; the full result went out with the task's embed
+        if let Some(result) = orchestrator.get_task_result(task_id).await {
+            let room = MAX_OUTPUT_RESPONSE.saturating_sub(report.chars().count() + 5);
+            let output = self.agent_formatted_output(&task.agent_type, &result);
+            report.push_str("\n\n");
+            report.extend(output.chars().take(room));
+            if output.chars().count() > room {
+                report.push('…');
+            }
+            return report;
+        }
+        if let Some(progress) = orchestrator.get_task_progress(task_id).await {
+            report.push_str(&format!(
+                "\n{} {}% · {}",
+                crate::discord::self_update::ProgressReporter::create_progress_bar(
+                    progress.percent
+                ),
+                progress.percent,
+                progress.phase
+            ));
+        }
+        report
     }
 
-    /// Unregister an agent (called when agent is stopped)
-    pub async fn unregister_agent(&self, agent_name: &str) {
-        info!("[SpiralConstellation] Unregistering agent: {}", agent_name);
-        self.active_agents.lock().await.remove(agent_name);
+    /// 📜 TASK HISTORY: The user's recent tasks, newest first; None without an orchestrator,
+    /// which is what keeps task history
+    pub async fn user_task_history(
+        &self,
+        user_id: u64,
+        limit: usize,
+    ) -> Option<Vec<(Task, AgentTaskRecord)>> {
+        let orchestrator = self.orchestrator.as_ref()?;
+        Some(
+            orchestrator
+                .get_task_history_by_context(AUTHOR_CONTEXT_KEY, &user_id.to_string(), limit)
+                .await,
+        )
     }
 
-    /// Get list of all currently active agents
-    pub async fn get_active_agents(&self) -> Vec<String> {
-        self.active_agents.lock().await.iter().cloned().collect()
+    /// 🎯 TASK SLOS: Execution time percentiles, success rates and missed objectives; None
+    /// without an orchestrator, which is what runs the tasks
+    pub fn task_slo_report(&self) -> Option<SloReport> {
+        Some(self.orchestrator.as_ref()?.slo_report())
     }
 
-    /// 🌌 LORDGENOME DESPAIR: Generate contextual despair quotes for unauthorized access
-    fn generate_lordgenome_quote(&self, username: &str, user_action: &str) -> String {
-        // Use our enhanced Lordgenome quote generator
-        let generator = LordgenomeQuoteGenerator::new();
+    /// 📈 AVAILABILITY: Time healthy, degraded, unhealthy and down over the reporting
+    /// windows; None without a system monitor or when its history can't be read
+    pub async fn availability_report(&self) -> Option<AvailabilityReport> {
+        match self.system_monitor.as_ref()?.availability().await {
+            Ok(report) => Some(report),
+            Err(e) => {
+                warn!("Failed to compute availability: {}", e);
+                None
+            }
+        }
+    }
 
-        // Detect the action type from the content
-        let action_type = LordgenomeQuoteGenerator::detect_action_type(user_action);
+    /// 📜 TASK HISTORY: Timing and outcome of one task
+    pub async fn task_record(&self, task_id: &str) -> Option<AgentTaskRecord> {
+        self.orchestrator.as_ref()?.get_task_record(task_id).await
+    }
 
-        // Determine severity based on the action
-        let severity = match action_type {
-            "security" => DenialSeverity::Apocalyptic,
-            "self_update" | "config" => DenialSeverity::Severe,
-            "command" | "role" => DenialSeverity::Moderate,
-            _ => DenialSeverity::Moderate,
+    /// 📅 SCHEDULED TASKS: Register a recurring task asked for by `msg`
+    /// The template is built like a task from a message, so each run carries the channel
+    /// its result is posted to and the requester it is attributed to
+    pub async fn schedule_from_message(
+        &self,
+        msg: &Message,
+        cron: &str,
+        agent_type: AgentType,
+        request: &str,
+    ) -> std::result::Result<ScheduledTask, String> {
+        let orchestrator = self.orchestrator.as_ref().ok_or(
+            "📅 Schedules are run by the orchestrator, which this bot is running without.",
+        )?;
+        if let Some(refusal) = self.task_channel_refusal(msg.guild_id, msg.channel_id) {
+            return Err(refusal);
+        }
+        let context = MessageContext {
+            author_id: msg.author.id.get(),
+            channel_id: msg.channel_id.get(),
+            message_id: msg.id.get(),
+            guild_id: msg.guild_id.map(|id| id.get()),
+            thread_id: None,
+            session_key: None,
+            user_session: None,
         };
-
-        // Generate a contextual quote
-        generator.generate_by_severity(username, action_type, severity)
+        let task =
+            self.create_task_with_persona(request, agent_type, context, UserIntent::TaskRequest);
+        orchestrator
+            .schedule_task(task, TaskSchedule::Cron(cron.to_string()))
+            .await
+            .map_err(|e| format!("❌ Could not schedule the task: {e}"))
     }
 
-    /// 📋 Setup reaction handlers for the bot
-    pub async fn setup_reaction_handlers(&self) {
-        let manager = &self.reaction_handler_manager;
-
-        // Register approval handlers for self-update
-        self.register_approval_handlers(manager).await;
-
-        // Register cleanup handlers
-        self.register_cleanup_handlers(manager).await;
-
-        // Register error recovery handlers
-        self.register_error_recovery_handlers(manager).await;
-
-        // Register dashboard refresh handler
-        // Dashboard refresh handler removed - not working properly
+    /// 📅 SCHEDULED TASKS: Schedules created from Discord, soonest first; None without an
+    /// orchestrator
+    pub async fn discord_schedules(&self) -> Option<Vec<ScheduledTask>> {
+        let orchestrator = self.orchestrator.as_ref()?;
+        let mut schedules = orchestrator.get_schedules().await;
+        schedules.retain(|schedule| schedule.template.context.contains_key(CHANNEL_CONTEXT_KEY));
+        Some(schedules)
+    }
 
-        info!(
-            "[SpiralConstellation] Reaction handlers registered: {:?}",
-            manager.registered_emojis().await
-        );
+    pub async fn remove_schedule(&self, schedule_id: &str) -> Option<ScheduledTask> {
+        self.orchestrator
+            .as_ref()?
+            .remove_schedule(schedule_id)
+            .await
     }
 
-    /// Register handlers for self-update approval
+    /// 📅 SCHEDULED RESULTS: Post each scheduled run's result to the channel it was
+    /// scheduled from
+    /// 🏗️ ARCHITECTURE DECISION: One result subscription for all schedules
+    /// Why: Nobody waits on a scheduled run the way a message waits on its task, and the
+    /// channel to post to is already in the task context
+    /// Alternative: A waiter per run (rejected: runs are submitted by the orchestrator, which
+    /// does not know about Discord)
+    /// Trade-off: Results broadcast while the bot is disconnected are not posted
+    async fn deliver_scheduled_results(self: Arc<Self>, http: Arc<Http>) {
+        let Some(orchestrator) = self.orchestrator.clone() else {
+            return;
+        };
+        let mut results = orchestrator.subscribe_results();
+        loop {
+            let result = match results.recv().await {
+                Ok(result) => result,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "[SpiralConstellation] Scheduled result delivery skipped {} results",
+                        skipped
+                    );
+                    continue;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+            };
+            let Some(task) = orchestrator.get_task_status(&result.task_id).await else {
+                continue;
+            };
+            let (Some(schedule_id), Some(channel_id)) = (
+                task.context.get(SCHEDULE_ID_CONTEXT_KEY),
+                task.context
+                    .get(CHANNEL_CONTEXT_KEY)
+                    .and_then(|channel| channel.parse::<u64>().ok()),
+            ) else {
+                continue;
+            };
+
+            // The scheduling user's verbosity applies to every run
+            let verbosity = task
+                .context
+                .get(AUTHOR_CONTEXT_KEY)
+                .and_then(|author| author.parse::<u64>().ok())
+                .map(|author| self.user_prefs.get(author).verbosity())
+                .unwrap_or_default();
+            let mut reply = self
+                .format_persona_response(&task.agent_type, &result, verbosity)
+                .await;
+            let short_id: String = schedule_id
+                .chars()
+                .take(DISCORD_TASK_ID_DISPLAY_LENGTH)
+                .collect();
+            // 📰 DIGEST: A routine run waits for the guild's digest; `!spiral task` shows it
+            let digest_guild = task
+                .context
+                .get(GUILD_CONTEXT_KEY)
+                .and_then(|guild| guild.parse::<u64>().ok())
+                .filter(|guild| self.guild_settings.get(*guild).digest_minutes.is_some());
+            let succeeded = matches!(
+                result.result,
+                crate::models::TaskExecutionResult::Success { .. }
+            );
+            if let Some(guild_id) =
+                digest_guild.filter(|_| digest::is_routine(succeeded, &task.priority))
+            {
+                let persona = AgentPersona::for_agent_type(&task.agent_type);
+                self.digests.push(
+                    guild_id,
+                    channel_id,
+                    DigestEntry {
+                        agent: persona.name,
+                        line: format!(
+                            "📅 Scheduled run `{short_id}` · {} {} completed task `{}` (`!spiral task {}`)",
+                            persona.emoji, persona.name, task.id, task.id
+                        ),
+                    },
+                );
+                continue;
+            }
+            reply.content = format!(
+                "📅 **Scheduled run** `{short_id}` · task `{}`\n{}",
+                task.id, reply.content
+            );
+            let pages = reply.pages.clone();
+            match ChannelId::new(channel_id)
+                .send_message(&*http, reply.message())
+                .await
+            {
+                Ok(message) => {
+                    self.task_messages.track(message.id.get(), &task.id);
+                    self.track_pages(pages.as_ref(), Some(&message)).await;
+                }
+                Err(e) => warn!(
+                    "[SpiralConstellation] Failed to post scheduled task {} result: {}",
+                    task.id, e
+                ),
+            }
+        }
+    }
+
+    /// 🟢 WORK SNAPSHOT: Running tasks and queue length for the bot's status; None without
+    /// an orchestrator
+    async fn work_snapshot(&self) -> Option<WorkSnapshot> {
+        let orchestrator = self.orchestrator.as_ref()?;
+        let now = chrono::Utc::now();
+        let running = orchestrator
+            .get_running_tasks()
+            .await
+            .into_iter()
+            .map(|record| {
+                let started_at = record.started_at.unwrap_or(record.created_at);
+                let elapsed_secs = (now - started_at).num_seconds().max(0) as u64;
+                (record.task_id, elapsed_secs)
+            })
+            .collect();
+        Some(WorkSnapshot {
+            running,
+            queued: orchestrator.get_queue_length().await,
+        })
+    }
+
+    /// 📰 DIGESTS: Post each channel's batched notifications once its guild's interval has
+    /// passed, for as long as the bot runs
+    async fn deliver_digests(self: Arc<Self>, http: Arc<Http>) {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(DISCORD_DIGEST_CHECK_SECS));
+        loop {
+            interval.tick().await;
+            let due = self.digests.take_due(|guild_id| {
+                self.guild_settings
+                    .get(guild_id)
+                    .digest_minutes
+                    .map(|minutes| std::time::Duration::from_secs(minutes * 60))
+            });
+            for (channel_id, waited, entries) in due {
+                if let Err(e) = ChannelId::new(channel_id)
+                    .say(&*http, digest::format_digest(&entries, waited))
+                    .await
+                {
+                    warn!(
+                        "[SpiralConstellation] Failed to post digest of {} notifications to channel {}: {}",
+                        entries.len(),
+                        channel_id,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// 🟢 PRESENCE: Keep the bot's status in step with its work for as long as it runs
+    /// The presence is only sent when its text changes, so an idle bot sends nothing
+    async fn update_presence(self: Arc<Self>, ctx: Context) {
+        let interval_secs = self
+            .discord_config
+            .load()
+            .presence_interval_secs
+            .max(DISCORD_PRESENCE_MIN_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        let mut shown = String::new();
+        loop {
+            interval.tick().await;
+            let Some(snapshot) = self.work_snapshot().await else {
+                return;
+            };
+            let text = presence::activity_text(&snapshot);
+            if text != shown {
+                debug!("[SpiralConstellation] Status: {}", text);
+                ctx.set_presence(
+                    Some(serenity::all::ActivityData::custom(text.clone())),
+                    OnlineStatus::Online,
+                );
+                shown = text;
+            }
+        }
+    }
+
+    /// 📖 PAGINATION: Keep the pages of an answer just sent, so its buttons can turn them
+    async fn track_pages(&self, pages: Option<&PagedAnswer>, answer: Option<&Message>) {
+        if let (Some(pages), Some(answer)) = (pages, answer) {
+            self.message_state_manager
+                .register_pages(answer.id, pages.clone())
+                .await;
+        }
+    }
+
+    /// 🔐 PERMISSION CHECK: The user's role from config, None if they may not use the bot
+    pub fn user_role(&self, user_id: u64) -> Option<rbac::Role> {
+        rbac::discord_user_role(&self.discord_config.load(), user_id)
+    }
+
+    /// 🔐 PERMISSION CHECK: Whether the user's role reaches `required`
+    pub fn has_role(&self, user_id: u64, required: rbac::Role) -> bool {
+        self.user_role(user_id)
+            .is_some_and(|role| role.permits(required))
+    }
+
+    /// 🔐 PERMISSION CHECK: The user's role in a guild, the higher of the configured role
+    /// and what the guild's settings grant the member's Discord roles
+    pub fn member_role(
+        &self,
+        user_id: u64,
+        guild_id: Option<GuildId>,
+        member_roles: &[RoleId],
+    ) -> Option<rbac::Role> {
+        let configured = self.user_role(user_id);
+        let Some(guild_id) = guild_id else {
+            return configured;
+        };
+        let member_roles: Vec<u64> = member_roles.iter().map(|role| role.get()).collect();
+        let granted = self
+            .guild_settings
+            .get(guild_id.get())
+            .granted_role(&member_roles);
+        configured.max(granted)
+    }
+
+    /// 🔐 PERMISSION CHECK: The role of a message's author where it was posted
+    pub fn message_role(&self, msg: &Message) -> Option<rbac::Role> {
+        let member_roles = msg
+            .member
+            .as_ref()
+            .map(|member| member.roles.as_slice())
+            .unwrap_or_default();
+        self.member_role(msg.author.id.get(), msg.guild_id, member_roles)
+    }
+
+    /// 🔐 PERMISSION CHECK: The role of whoever added a reaction, in the reaction's guild
+    pub fn reaction_role(&self, reaction: &Reaction, user_id: u64) -> Option<rbac::Role> {
+        let member_roles = reaction
+            .member
+            .as_ref()
+            .map(|member| member.roles.as_slice())
+            .unwrap_or_default();
+        self.member_role(user_id, reaction.guild_id, member_roles)
+    }
+
+    /// 🎟️ QUEUE ADMISSION: Admins may use the task queue's reserved slots at any priority
+    pub fn queue_submitter(role: Option<rbac::Role>) -> Submitter {
+        match role {
+            Some(role) if role.permits(rbac::Role::Admin) => Submitter::Privileged,
+            _ => Submitter::Standard,
+        }
+    }
+
+    /// 📝 COMPOSE: Requests being written over several messages
+    pub fn compose_drafts(&self) -> &ComposeDrafts {
+        &self.compose_drafts
+    }
+
+    /// 🧭 SETUP: Running `!spiral setup` wizards
+    pub fn setup_wizards(&self) -> &SetupWizards {
+        &self.setup_wizards
+    }
+
+    /// 🏘️ GUILD SETTINGS: Overrides `!spiral config` keeps per guild
+    pub fn guild_settings(&self) -> &GuildSettingsStore {
+        &self.guild_settings
+    }
+
+    /// 🙋 USER PREFS: Each user's own defaults, kept with `!spiral prefs`
+    pub fn user_prefs(&self) -> &UserPrefsStore {
+        &self.user_prefs
+    }
+
+    /// 🗂️ USER SESSIONS: Each user's session, extended by every task they start
+    pub fn user_sessions(&self) -> &UserSessions {
+        &self.user_sessions
+    }
+
+    /// 🌍 MESSAGE CATALOG: The bot's replies in each locale
+    pub fn catalog(&self) -> &MessageCatalog {
+        &self.catalog
+    }
+
+    /// The locale the user picked, else the one the guild picked; English otherwise
+    pub fn locale_for(&self, guild_id: Option<GuildId>, user_id: UserId) -> Locale {
+        Self::locale_of(
+            &self.user_prefs.get(user_id.get()),
+            &self.settings_for(guild_id),
+        )
+    }
+
+    fn locale_of(prefs: &UserPrefs, settings: &GuildSettings) -> Locale {
+        prefs.locale.or(settings.locale).unwrap_or_default()
+    }
+
+    /// 👋 GREETING: One of the persona's greetings in the locale, its English ones when the
+    /// catalog has none
+    fn greeting(&self, locale: Locale, persona: &AgentPersona) -> String {
+        self.catalog
+            .choose(locale, &format!("greetings.{}", persona.name))
+            .unwrap_or_else(|| persona.random_greeting().to_string())
+    }
+
+    /// The guild's settings; defaults outside a guild
+    fn settings_for(&self, guild_id: Option<GuildId>) -> GuildSettings {
+        guild_id
+            .map(|guild_id| self.guild_settings.get(guild_id.get()))
+            .unwrap_or_default()
+    }
+
+    /// 📣 RESULTS CHANNEL: Repeat a completed task's answer in the guild's results channel,
+    /// linking to where the requester got it; nothing when the answer is already there
+    #[allow(clippy::too_many_arguments)]
+    async fn announce_result(
+        &self,
+        ctx: &Context,
+        guild_id: Option<GuildId>,
+        task_id: &str,
+        agent_type: &AgentType,
+        priority: &Priority,
+        requester: &User,
+        reply: &TaskReply,
+        answer: Option<&Message>,
+    ) {
+        let settings = self.settings_for(guild_id);
+        let Some(results_channel) = settings.results_channel else {
+            return;
+        };
+        // Only completed tasks are answered with an embed; failures stay with the requester
+        let Some(embed) = &reply.embed else {
+            return;
+        };
+        if answer.is_some_and(|answer| answer.channel_id.get() == results_channel) {
+            return;
+        }
+        let persona = AgentPersona::for_agent_type(agent_type);
+        let mut content = format!(
+            "{} **{}** completed task `{task_id}` for {}",
+            persona.emoji, persona.name, requester.name
+        );
+        if let Some(answer) = answer {
+            // Interaction responses come without a guild ID, so it is passed explicitly
+            let link = answer.id.link(answer.channel_id, guild_id);
+            content.push_str(&format!(" · {link}"));
+        }
+        // 📰 DIGEST: Routine announcements wait for the guild's digest
+        if let Some(guild_id) = guild_id
+            .filter(|_| settings.digest_minutes.is_some() && digest::is_routine(true, priority))
+        {
+            self.digests.push(
+                guild_id.get(),
+                results_channel,
+                DigestEntry {
+                    agent: persona.name,
+                    line: content,
+                },
+            );
+            return;
+        }
+        let announcement = TaskReply {
+            content,
+            embed: Some(embed.clone()),
+            ..TaskReply::default()
+        };
+        if let Err(e) = ChannelId::new(results_channel)
+            .send_message(&ctx.http, announcement.message())
+            .await
+        {
+            warn!(
+                "[SpiralConstellation] Failed to announce task {} in results channel: {}",
+                task_id, e
+            );
+        }
+    }
+
+    /// 🚨 OPS CHANNEL: Post a security alert to the guild's ops channel, if it has one
+    /// Alerts name users rather than mention them, so they ping nobody
+    async fn alert_ops(&self, ctx: &Context, guild_id: Option<GuildId>, alert: String) {
+        let Some(ops_channel) = self.settings_for(guild_id).ops_channel else {
+            return;
+        };
+        let alert: String = alert.chars().take(MAX_OUTPUT_RESPONSE).collect();
+        if let Err(e) = ChannelId::new(ops_channel)
+            .say(&ctx.http, format!("🚨 **Security Alert**\n{alert}"))
+            .await
+        {
+            warn!(
+                "[SpiralConstellation] Failed to post security alert to ops channel: {}",
+                e
+            );
+        }
+    }
+
+    /// 🗂️ TASK CHANNELS: Where to start tasks instead, when the guild does not allow them
+    /// in `channel_id`
+    fn task_channel_refusal(
+        &self,
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+    ) -> Option<String> {
+        let settings = self.settings_for(guild_id);
+        if settings.allows_tasks_in(channel_id.get()) {
+            return None;
+        }
+        let channels = settings
+            .task_channels
+            .iter()
+            .map(|channel| format!("<#{channel}>"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(self.catalog.format(
+            settings.locale.unwrap_or_default(),
+            "task_channels.refused",
+            &[("channels", &channels)],
+        ))
+    }
+
+    /// 💸 COST TRACKER: Spend of the Claude Code client this bot runs tasks through
+    pub fn cost_tracker(&self) -> Option<Arc<CostTracker>> {
+        match &self.orchestrator {
+            Some(orchestrator) => orchestrator
+                .get_claude_client()
+                .ok()
+                .map(|client| client.costs().clone()),
+            None => self
+                .claude_client
+                .as_ref()
+                .map(|client| client.costs().clone()),
+        }
+    }
+
+    // Removed hardcoded agent checks - use is_agent_active() instead
+
+    /// Agent used for direct-mode execution of the given type, if one is registered
+    fn direct_agent(&self, agent_type: &AgentType) -> Option<Arc<dyn Agent>> {
+        self.agent_registry.lock().unwrap().get(agent_type).cloned()
+    }
+
+    /// 🏗️ ARCHITECTURE DECISION: Dynamic agent management
+    /// Why: Generic methods work with any agent type, no hardcoding
+    /// Alternative: Type-specific methods (rejected: doesn't scale)
+    /// Trade-off: Slightly more verbose but infinitely extensible
+    ///
+    /// Check if a specific agent is currently active
+    pub async fn is_agent_active(&self, agent_name: &str) -> bool {
+        self.active_agents.lock().await.contains(agent_name)
+    }
+
+    /// Register an agent as active (called when agent is initialized)
+    /// 🏗️ ARCHITECTURE DECISION: Verify Discord role exists when registering
+    /// Why: Ensure agents can be mentioned in Discord when they're active
+    /// Alternative: Create roles on demand (rejected: needs bot permissions)
+    pub async fn register_active_agent(&self, agent_name: String, ctx: Option<&Context>) {
+        info!(
+            "[SpiralConstellation] Registering active agent: {}",
+            agent_name
+        );
+
+        // Check if Discord role exists for this agent (if context provided)
+        // Note: We can't check specific guilds without guild_id in config
+        // This would need to be done in the ready event or when handling messages
+        if let Some(_ctx) = ctx {
+            info!(
+                "[SpiralConstellation] Agent {} registered. Discord role check will be performed in ready event.",
+                agent_name
+            );
+        }
+
+        self.active_agents.lock().await.insert(agent_name);
+    }
+
+    /// Unregister an agent (called when agent is stopped)
+    pub async fn unregister_agent(&self, agent_name: &str) {
+        info!("[SpiralConstellation] Unregistering agent: {}", agent_name);
+        self.active_agents.lock().await.remove(agent_name);
+    }
+
+    /// Get list of all currently active agents
+    pub async fn get_active_agents(&self) -> Vec<String> {
+        self.active_agents.lock().await.iter().cloned().collect()
+    }
+
+    /// 🌌 LORDGENOME DESPAIR: Generate contextual despair quotes for unauthorized access
+    fn generate_lordgenome_quote(&self, username: &str, user_action: &str) -> String {
+        // Use our enhanced Lordgenome quote generator
+        let generator = LordgenomeQuoteGenerator::new();
+
+        // Detect the action type from the content
+        let action_type = LordgenomeQuoteGenerator::detect_action_type(user_action);
+
+        // Determine severity based on the action
+        let severity = match action_type {
+            "security" => DenialSeverity::Apocalyptic,
+            "self_update" | "config" => DenialSeverity::Severe,
+            "command" | "role" => DenialSeverity::Moderate,
+            _ => DenialSeverity::Moderate,
+        };
+
+        // Generate a contextual quote
+        generator.generate_by_severity(username, action_type, severity)
+    }
+
+    /// 📋 Setup reaction handlers for the bot
+    pub async fn setup_reaction_handlers(&self) {
+        let manager = &self.reaction_handler_manager;
+
+        // Task actions go first: ❌ also rejects self-update plans, and the first handler
+        // whose condition holds takes the reaction
+        self.register_task_action_handlers(manager).await;
+
+        // Register approval handlers for self-update
+        self.register_approval_handlers(manager).await;
+
+        // Register cleanup handlers
+        self.register_cleanup_handlers(manager).await;
+
+        // Register error recovery handlers
+        self.register_error_recovery_handlers(manager).await;
+
+        // Register dashboard refresh handler
+        // Dashboard refresh handler removed - not working properly
+
+        info!(
+            "[SpiralConstellation] Reaction handlers registered: {:?}",
+            manager.registered_emojis().await
+        );
+    }
+
+    /// 🎛️ TASK ACTIONS: ❌, 🔁 and ⬆️ on a task's progress message cancel, retry or bump it
+    /// Only messages the bot tracks for a task qualify; ❌ and 🔁 need an operator, ⬆️ an
+    /// admin (see TaskAction::required_role)
+    async fn register_task_action_handlers(
+        &self,
+        manager: &reaction_handler::ReactionHandlerManager,
+    ) {
+        let Some(orchestrator) = self.orchestrator.clone() else {
+            return;
+        };
+        for (emoji, action) in TaskAction::emojis() {
+            let task_messages = self.task_messages.clone();
+            let tracked = self.task_messages.clone();
+            let orchestrator = orchestrator.clone();
+            manager
+                .register_conditional(
+                    emoji,
+                    action.description(),
+                    true,
+                    move |reaction, _user| tracked.task_for(reaction.message_id.get()).is_some(),
+                    move |ctx, reaction, user| {
+                        let task_messages = task_messages.clone();
+                        let orchestrator = orchestrator.clone();
+                        Box::pin(async move {
+                            let Some(task_id) = task_messages.task_for(reaction.message_id.get())
+                            else {
+                                return Ok(());
+                            };
+                            let outcome =
+                                task_actions::apply(&orchestrator, action, &task_id).await;
+                            info!(
+                                "[SpiralConstellation] {} by {} on task {}: {:?}",
+                                action.description(),
+                                user.id,
+                                task_id,
+                                outcome
+                            );
+                            audit::record(
+                                AuditEvent::new(
+                                    AuditEventKind::AdminAction,
+                                    AuditSource::Discord,
+                                    format!("reaction {}", action.description().to_lowercase()),
+                                )
+                                .with_actor(format!("{} ({})", user.name, user.id))
+                                .with_client(format!("channel {}", reaction.channel_id))
+                                .with_details(format!("task {task_id}: {outcome:?}")),
+                            );
+
+                            let message = reaction
+                                .message(&ctx.http)
+                                .await
+                                .map_err(|e| format!("Failed to get message: {e}"))?;
+                            let reply = message
+                                .reply(&ctx.http, outcome.message(&task_id))
+                                .await
+                                .map_err(|e| format!("Failed to send reply: {e}"))?;
+                            if let task_actions::ActionOutcome::Resubmitted { task_id } = &outcome {
+                                task_messages.track(reply.id.get(), task_id);
+                            }
+                            Ok(())
+                        })
+                    },
+                )
+                .await;
+        }
+    }
+
+    /// Register handlers for self-update approval
     async fn register_approval_handlers(&self, manager: &reaction_handler::ReactionHandlerManager) {
         let approval_manager = self.approval_manager.clone();
 
@@ -1370,6 +2303,35 @@ pub struct MessageContext {
     pub channel_id: u64,
     pub message_id: u64,
     pub guild_id: Option<u64>,
+    /// Set when the message was posted in a thread; tasks from one thread share a session
+    pub thread_id: Option<u64>,
+    /// Set for direct messages; tasks from one private session share a Claude conversation
+    pub session_key: Option<String>,
+    /// The user session the message was sent in, whose end cancels the task
+    pub user_session: Option<uuid::Uuid>,
+}
+
+impl MessageContext {
+    /// 🧵 THREAD LOOKUP: The thread the message was posted in, if any
+    /// Threads are channels, so the message's channel is the thread; looking it up costs an
+    /// API call, which is why only messages that become tasks pay it
+    pub async fn with_thread(mut self, ctx: &Context) -> Self {
+        let channel_id = ChannelId::new(self.channel_id);
+        self.thread_id = match channel_id.to_channel(ctx).await {
+            Ok(Channel::Guild(channel)) if channel.thread_metadata.is_some() => {
+                Some(channel.id.get())
+            }
+            Ok(_) => None,
+            Err(e) => {
+                debug!(
+                    "[SpiralConstellation] Could not look up channel {}: {}",
+                    channel_id, e
+                );
+                None
+            }
+        };
+        self
+    }
 }
 
 /// 🎮 CONSTELLATION BOT HANDLER: Discord event handler for the unified bot
@@ -1399,6 +2361,19 @@ impl EventHandler for ConstellationBotHandler {
         }
         debug!("[Event] Processing user message");
 
+        // 🏘️ GUILD SETTINGS: The server's own prefix, channels, grants and limits
+        let settings = self.bot.settings_for(msg.guild_id);
+        let prefs = self.bot.user_prefs.get(msg.author.id.get());
+        let locale = SpiralConstellationBot::locale_of(&prefs, &settings);
+        let catalog = self.bot.catalog();
+        // ✉️ DIRECT MESSAGES: Private sessions, with a tighter rate limit than servers
+        let is_dm = msg.guild_id.is_none();
+        let rate_limit = if is_dm {
+            Some(DM_RATE_LIMIT_PER_MINUTE)
+        } else {
+            settings.rate_limit_per_minute
+        };
+
         // 🛡️ SECURITY VALIDATION: Multi-layer security check before processing
         // ARCHITECTURE DECISION: Validate all messages through security pipeline first
         // Why: Prevents malicious content, spam, and injection attacks from reaching agents
@@ -1407,7 +2382,9 @@ impl EventHandler for ConstellationBotHandler {
         let validation_result = {
             let validator_result = {
                 let mut validator = self.bot.security_validator.lock().await;
-                validator.validate_message(&msg)
+                validator
+                    .validate_message_with_limit(&msg, rate_limit)
+                    .await
             };
 
             match validator_result {
@@ -1415,7 +2392,10 @@ impl EventHandler for ConstellationBotHandler {
                 Err(e) => {
                     warn!("[SpiralConstellation] Security validation error: {}", e);
                     if let Err(e) = msg
-                        .reply(&ctx.http, "⚠️ Security validation failed. Message blocked.")
+                        .reply(
+                            &ctx.http,
+                            catalog.text(locale, "security.validation_failed"),
+                        )
                         .await
                     {
                         warn!(
@@ -1469,10 +2449,50 @@ impl EventHandler for ConstellationBotHandler {
             );
 
             // Also log as a separate JSON line for easy parsing
-            tracing::info!(target: "security_events", "{}", security_event.to_json());
+            tracing::info!(target: crate::constants::SECURITY_EVENTS_TARGET, "{}", security_event.to_json());
+
+            let kind = if validation_result
+                .issues
+                .iter()
+                .any(|issue| issue.to_lowercase().contains("rate limit"))
+            {
+                AuditEventKind::RateLimited
+            } else {
+                AuditEventKind::BlockedMessage
+            };
+            let what = if kind == AuditEventKind::RateLimited {
+                "Rate limited message"
+            } else {
+                "Blocked message"
+            };
+            audit::record(
+                AuditEvent::new(kind, AuditSource::Discord, "message")
+                    .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                    .with_client(format!("channel {}", msg.channel_id))
+                    .with_details(validation_result.issues.join("; ")),
+            );
+            self.bot
+                .alert_ops(
+                    &ctx,
+                    msg.guild_id,
+                    format!(
+                        "{what} from {} ({}) in <#{}>: {}",
+                        msg.author.name,
+                        msg.author.id,
+                        msg.channel_id,
+                        validation_result.issues.join("; ")
+                    ),
+                )
+                .await;
 
-            if let Err(e) = msg.reply(&ctx.http, "🚫 Message flagged by security validation. Please ensure your message follows community guidelines.").await {
-                warn!("[SpiralConstellation] Failed to send security warning: {}", e);
+            if let Err(e) = msg
+                .reply(&ctx.http, catalog.text(locale, "security.flagged"))
+                .await
+            {
+                warn!(
+                    "[SpiralConstellation] Failed to send security warning: {}",
+                    e
+                );
             }
             return;
         }
@@ -1491,24 +2511,77 @@ impl EventHandler for ConstellationBotHandler {
                 msg.content.len(),
                 msg.author.id
             );
-            if let Err(e) = msg.reply(&ctx.http, "❌ Message too long for processing. Please keep requests under 4000 characters.").await {
+            let too_long = catalog.format(
+                locale,
+                "message.too_long",
+                &[("max", &MAX_MESSAGE_LENGTH.to_string())],
+            );
+            if let Err(e) = msg.reply(&ctx.http, too_long).await {
                 warn!("[SpiralConstellation] Failed to send length warning: {}", e);
             }
             return;
         }
 
+        // Commands with the guild's prefix are routed as `!spiral` commands
+        let command_content = settings.normalize_prefix(&msg.content);
+        let role = self.bot.message_role(&msg);
+        let is_operator = role.is_some_and(|role| role.permits(rbac::Role::Operator));
+
         // Check if message mentions any Spiral agent or contains role mentions
         let has_spiral_mention = self.bot.mention_regex.is_match(&msg.content);
         let has_role_mention = !msg.mention_roles.is_empty();
-        let has_spiral_command = msg.content.to_lowercase().contains("!spiral");
+        let has_spiral_command = command_content.to_lowercase().contains("!spiral");
+        // 📝 COMPOSE: A user writing a request over several messages need not mention anyone
+        let composing = self
+            .bot
+            .compose_drafts
+            .is_open(msg.channel_id.get(), msg.author.id.get());
+        // 🧭 SETUP: Answers to `!spiral setup` questions need no mention either
+        let in_setup = self
+            .bot
+            .setup_wizards
+            .is_open(msg.channel_id.get(), msg.author.id.get());
+
+        // Messages in a task thread continue the task without mentioning the agent; other
+        // people's chatter in the thread is not answered with a denial
+        let thread_agent = self
+            .bot
+            .task_threads
+            .agent_for(msg.channel_id.get())
+            .filter(|_| is_operator);
+
+        // Every direct message is addressed to the bot
+        if !has_spiral_mention
+            && !has_role_mention
+            && !has_spiral_command
+            && thread_agent.is_none()
+            && !is_dm
+            && !composing
+            && !in_setup
+        {
+            return;
+        }
 
-        if !has_spiral_mention && !has_role_mention && !has_spiral_command {
+        // 🏘️ ALLOWED CHANNELS: Elsewhere the bot stays silent, except in its own task threads
+        // and for `!spiral config`, so a guild cannot lock itself out of its settings
+        if !settings.allows_channel(msg.channel_id.get())
+            && thread_agent.is_none()
+            && !command_content
+                .trim_start()
+                .to_lowercase()
+                .starts_with(commands::guild_config::CONFIG_COMMAND)
+        {
+            debug!(
+                "[SpiralConstellation] Ignoring message in channel {} not allowed by guild settings",
+                msg.channel_id
+            );
             return;
         }
 
-        // 🔐 UNIVERSAL AUTHORIZATION: All spiral commands and mentions require authorization
+        // 🔐 UNIVERSAL AUTHORIZATION: All spiral commands and mentions require a role
         // Exception: Bot's own messages are allowed (to prevent self-blocking)
-        if !self.bot.is_authorized_user(msg.author.id.get()) {
+        // What each role may do is checked per command and per action further down
+        if role.is_none() {
             use crate::discord::lordgenome_quotes::LordgenomeQuoteGenerator;
             let generator = LordgenomeQuoteGenerator::new();
             let action_type = if has_spiral_command {
@@ -1517,6 +2590,26 @@ impl EventHandler for ConstellationBotHandler {
                 "general"
             };
             let denial_quote = generator.generate_denial(&msg.author.name, action_type);
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::AuthFailure,
+                    AuditSource::Discord,
+                    format!("spiral {action_type}"),
+                )
+                .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                .with_client(format!("channel {}", msg.channel_id))
+                .with_details("User is not authorized"),
+            );
+            self.bot
+                .alert_ops(
+                    &ctx,
+                    msg.guild_id,
+                    format!(
+                        "Unauthorized `spiral {action_type}` attempt by {} ({}) in <#{}>",
+                        msg.author.name, msg.author.id, msg.channel_id
+                    ),
+                )
+                .await;
 
             if let Err(e) = msg.reply(&ctx.http, &denial_quote).await {
                 warn!(
@@ -1537,11 +2630,110 @@ impl EventHandler for ConstellationBotHandler {
             channel_id: msg.channel_id.get(),
             message_id: msg.id.get(),
             guild_id: msg.guild_id.map(|id| id.get()),
+            thread_id: None,
+            session_key: None,
+            user_session: None,
+        };
+
+        // 🧭 SETUP: An answer to the wizard's question; commands still work meanwhile
+        if in_setup
+            && !command_content
+                .trim_start()
+                .to_lowercase()
+                .starts_with("!spiral")
+        {
+            if let Some(reply) = commands::setup::SetupCommand::answer(&msg, &self.bot) {
+                if let Err(e) = msg.reply(&ctx.http, reply).await {
+                    warn!("[SpiralConstellation] Failed to send setup reply: {}", e);
+                }
+            }
+            return;
+        }
+
+        // 📝 COMPOSE: Collect the message into the user's draft. `!spiral done`, or the
+        // draft filling up, submits the whole request in place of this message
+        let composed_draft = if composing {
+            let (channel_id, user_id) = (msg.channel_id.get(), msg.author.id.get());
+            let command = command_content.trim_start().to_lowercase();
+            if command.starts_with(commands::compose::DONE_COMMAND) {
+                self.bot.compose_drafts.finish(channel_id, user_id)
+            } else if command.starts_with("!spiral") {
+                // Other commands still work while composing
+                None
+            } else {
+                match self
+                    .bot
+                    .compose_drafts
+                    .collect(channel_id, user_id, &msg.content)
+                {
+                    Some(Collected::Complete(draft)) => Some(draft),
+                    Some(Collected::Added { count, max }) => {
+                        debug!(
+                            "[SpiralConstellation] Composed message {}/{} from {}",
+                            count, max, msg.author.id
+                        );
+                        if let Err(e) = msg.react(&ctx.http, '📝').await {
+                            warn!(
+                                "[SpiralConstellation] Failed to add compose reaction: {}",
+                                e
+                            );
+                        }
+                        return;
+                    }
+                    Some(Collected::TooLong) => {
+                        let response = format!(
+                            "📝 That would make the request too long, so it was left out. Send `{}` to submit what you have.",
+                            commands::compose::DONE_COMMAND
+                        );
+                        if let Err(e) = msg.reply(&ctx.http, response).await {
+                            warn!("[SpiralConstellation] Failed to send compose limit: {}", e);
+                        }
+                        return;
+                    }
+                    // The draft expired since it was checked
+                    None => return,
+                }
+            }
+        } else {
+            None
+        };
+        let is_composed = composed_draft.is_some();
+        let composed_agent = composed_draft
+            .as_ref()
+            .and_then(|draft| draft.agent_type.clone());
+        let composed_message = match composed_draft {
+            Some(draft) if draft.combined_messages.is_empty() => {
+                if let Err(e) = msg
+                    .reply(
+                        &ctx.http,
+                        "📝 Nothing was written, so there is nothing to submit.",
+                    )
+                    .await
+                {
+                    warn!("[SpiralConstellation] Failed to send compose reply: {}", e);
+                }
+                return;
+            }
+            Some(draft) => {
+                if let Err(e) = msg.reply(&ctx.http, draft.preview()).await {
+                    warn!(
+                        "[SpiralConstellation] Failed to send compose preview: {}",
+                        e
+                    );
+                }
+                // The request goes through the rest of the pipeline as if it were one message
+                let mut composed = msg.clone();
+                composed.content = draft.request();
+                Some(composed)
+            }
+            None => None,
         };
+        let msg: &Message = composed_message.as_ref().unwrap_or(&msg);
+        let has_spiral_command = has_spiral_command && !is_composed;
 
         // Check for Auto Core Update requests via direct bot mention
-        if self.is_auto_core_update_request(&msg).await {
-            self.handle_auto_core_update_request(&ctx, &msg).await;
+        if self.is_auto_core_update_request(msg).await {
+            self.handle_auto_core_update_request(&ctx, msg).await;
             return;
         }
 
@@ -1600,18 +2792,21 @@ impl EventHandler for ConstellationBotHandler {
             }
         }
 
-        // Handle special commands first
-        if let Some(command_response) = self
-            .bot
-            .command_router
-            .route_command(&msg.content, &msg, &ctx, &self.bot)
-            .await
-        {
+        // Handle special commands first; a composed request is no command
+        let command_response = if is_composed {
+            None
+        } else {
+            self.bot
+                .command_router
+                .route_command(&command_content, msg, &ctx, &self.bot)
+                .await
+        };
+        if let Some(command_response) = command_response {
             match msg.reply(&ctx.http, &command_response).await {
                 Ok(response_msg) => {
                     // If it's a blocked command message and user is authorized, add bug emoji
                     if command_response.contains(messages::patterns::COMMAND_BLOCKED_PATTERN)
-                        && self.bot.is_authorized_user(msg.author.id.get())
+                        && is_operator
                     {
                         if let Err(e) = response_msg.react(&ctx.http, emojis::BUG).await {
                             warn!("[SpiralConstellation] Failed to add bug reaction to blocked command: {}", e);
@@ -1646,15 +2841,98 @@ impl EventHandler for ConstellationBotHandler {
             return;
         }
 
-        // Detect which agent persona to use
-        let agent_type = match self
+        // 🎭 ROLE CHECK: Viewers can read status but not hand work to agents
+        if !is_operator {
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::AccessDenied,
+                    AuditSource::Discord,
+                    "agent request",
+                )
+                .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                .with_client(format!("channel {}", msg.channel_id))
+                .with_details("Requires operator role"),
+            );
+            let response = format!(
+                "{} (requires `{}`)",
+                messages::security::INSUFFICIENT_ROLE,
+                rbac::Role::Operator
+            );
+            if let Err(e) = msg.reply(&ctx.http, response).await {
+                warn!("[SpiralConstellation] Failed to send role denial: {}", e);
+            }
+            return;
+        }
+
+        // 🗂️ TASK CHANNELS: Follow-ups in a task thread continue where the task started
+        if thread_agent.is_none() {
+            if let Some(refusal) = self.bot.task_channel_refusal(msg.guild_id, msg.channel_id) {
+                if let Err(e) = msg.reply(&ctx.http, refusal).await {
+                    warn!(
+                        "[SpiralConstellation] Failed to send task channel refusal: {}",
+                        e
+                    );
+                }
+                return;
+            }
+        }
+
+        // 🗂️ USER SESSION: Every task message opens or extends the sender's session; a DM
+        // continues it privately, with its last agent
+        let user_session = match self
             .bot
-            .detect_agent_persona(&msg.content, &msg, &ctx)
+            .user_sessions
+            .session_for(msg.author.id.get())
             .await
+        {
+            Ok(session) => Some(session),
+            // A server task can run without a session; a DM is its session
+            Err(e) if !is_dm => {
+                warn!("[SpiralConstellation] Failed to open user session: {}", e);
+                None
+            }
+            Err(e) => {
+                warn!("[SpiralConstellation] Failed to open DM session: {}", e);
+                if let Err(e) = msg
+                    .reply(&ctx.http, catalog.text(locale, "dm.session_failed"))
+                    .await
+                {
+                    warn!("[SpiralConstellation] Failed to send session error: {}", e);
+                }
+                return;
+            }
+        };
+        let dm_agent = match &user_session {
+            Some(session) if is_dm && !has_spiral_command => Some(
+                session
+                    .agent()
+                    .or(prefs.preferred_agent.clone())
+                    .unwrap_or(AgentType::SoftwareDeveloper),
+            ),
+            _ => None,
+        };
+
+        // Detect which agent persona to use; one named when composing comes first
+        let detected_agent = match composed_agent {
+            Some(agent) => Some(agent),
+            None => self.bot.detect_agent_persona(&msg.content, msg, &ctx).await,
+        };
+        let agent_type = match detected_agent
+            .or(thread_agent)
+            // An unknown `!spiral` command is not a request for the guild's default agent
+            .or(settings
+                .default_agent
+                .clone()
+                .filter(|_| !has_spiral_command))
+            .or(dm_agent)
+            .or(is_composed.then_some(AgentType::SoftwareDeveloper))
         {
             Some(agent) => agent,
             None => {
-                if let Err(e) = msg.reply(&ctx.http, "❓ I'm not sure which agent you'd like to talk to. Try mentioning @SpiralDev, @SpiralPM, @SpiralQA, @SpiralKing, or use a role mention!").await {
+                if let Err(e) = msg
+                    .reply(&ctx.http, catalog.text(locale, "agent.unknown"))
+                    .await
+                {
                     warn!("[SpiralConstellation] Failed to send clarification: {}", e);
                 }
                 return;
@@ -1669,7 +2947,7 @@ impl EventHandler for ConstellationBotHandler {
                 "{} **{}**\n{}",
                 persona.emoji,
                 persona.name,
-                persona.random_greeting()
+                self.bot.greeting(locale, persona)
             );
             if let Err(e) = msg.reply(&ctx.http, response).await {
                 warn!("[SpiralConstellation] Failed to send greeting: {}", e);
@@ -1696,7 +2974,7 @@ impl EventHandler for ConstellationBotHandler {
         let secure_processing_result = match self
             .bot
             .secure_message_handler
-            .process_message_securely(&msg, &ctx)
+            .process_message_securely(msg, &ctx)
             .await
         {
             Ok(result) => result,
@@ -1708,7 +2986,7 @@ impl EventHandler for ConstellationBotHandler {
                 if let Err(e) = msg
                     .reply(
                         &ctx.http,
-                        "⚠️ Unable to process message securely. Please try again.",
+                        catalog.text(locale, "security.processing_failed"),
                     )
                     .await
                 {
@@ -1727,8 +3005,18 @@ impl EventHandler for ConstellationBotHandler {
                 "[SpiralConstellation] Message blocked by secure handler: {:?}",
                 secure_processing_result.validation_issues
             );
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::BlockedMessage,
+                    AuditSource::Discord,
+                    "message",
+                )
+                .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                .with_client(format!("channel {}", msg.channel_id))
+                .with_details(secure_processing_result.validation_issues.join("; ")),
+            );
             if let Err(e) = msg
-                .reply(&ctx.http, "🚫 Message blocked by security validation.")
+                .reply(&ctx.http, catalog.text(locale, "security.blocked"))
                 .await
             {
                 warn!("[SpiralConstellation] Failed to send block message: {}", e);
@@ -1754,6 +3042,29 @@ impl EventHandler for ConstellationBotHandler {
             intent_response.intent_type, intent_response.confidence, intent_response.risk_level
         );
 
+        // ✉️ DM INTENT CHECK: No moderators see a DM, so risky requests are refused there
+        if is_dm {
+            if let Some(reason) =
+                user_sessions::intent_refusal(secure_processing_result.intent.as_ref())
+            {
+                audit::record(
+                    AuditEvent::new(
+                        AuditEventKind::BlockedMessage,
+                        AuditSource::Discord,
+                        "direct message",
+                    )
+                    .with_actor(format!("{} ({})", msg.author.name, msg.author.id))
+                    .with_client(format!("DM channel {}", msg.channel_id))
+                    .with_details(reason),
+                );
+                let refusal = catalog.format(locale, "dm.refused", &[("reason", reason)]);
+                if let Err(e) = msg.reply(&ctx.http, refusal).await {
+                    warn!("[SpiralConstellation] Failed to send DM refusal: {}", e);
+                }
+                return;
+            }
+        }
+
         // Convert IntentType to UserIntent for compatibility
         let intent = match intent_response.intent_type {
             IntentType::Help => UserIntent::HelpRequest,
@@ -1775,6 +3086,37 @@ impl EventHandler for ConstellationBotHandler {
             intent
         };
 
+        // 🤔 CLARIFICATION: An unclear or risky request waits for its author to say what they
+        // meant; their answer brings the message back here with the chosen intent
+        let intent = match self.bot.clarifications.take_confirmed(msg.id.get()) {
+            Some(confirmed) => confirmed,
+            None if !is_composed && clarification::needs_clarification(intent_response) => {
+                let question = CreateMessage::new()
+                    .content(format!(
+                        "{} **{}**\n{}",
+                        persona.emoji,
+                        persona.name,
+                        clarification::question(intent_response)
+                    ))
+                    .components(clarification::buttons())
+                    .reference_message(msg);
+                match msg.channel_id.send_message(&ctx.http, question).await {
+                    Ok(asked) => {
+                        self.bot.clarifications.ask(asked.id.get(), msg.clone());
+                        self.bot
+                            .secure_message_handler
+                            .record_clarification_requested();
+                    }
+                    Err(e) => warn!(
+                        "[SpiralConstellation] Failed to ask clarifying question: {}",
+                        e
+                    ),
+                }
+                return;
+            }
+            None => intent,
+        };
+
         // Use sanitized content if available, otherwise use cleaned content
         let processed_message = secure_processing_result
             .sanitized_content
@@ -1786,29 +3128,69 @@ impl EventHandler for ConstellationBotHandler {
         );
 
         // Step 3: Respond with intended action
-        let action_description = match intent {
-            UserIntent::StatusQuery => "🔍 **Analyzing Workspace**\nI'll inspect your workspace to find and list existing projects.".to_string(),
-            UserIntent::TaskRequest => "🚀 **Development Task**\nI'll create/build the requested functionality.".to_string(),
-            UserIntent::AgentSelection => "🎯 **Agent-Specific Task**\nI'll handle this with the selected agent's expertise.".to_string(),
-            UserIntent::HelpRequest => "❓ **Help Request**\nI'll provide the information you need.".to_string(),
-            UserIntent::Greeting => "👋 **Greeting**\nNice to meet you!".to_string(),
-            UserIntent::Unknown => "🔄 **Processing Request**\nI'll handle your request appropriately.".to_string(),
-        };
+        let action_description = catalog.text(
+            locale,
+            match intent {
+                UserIntent::StatusQuery => "intent.status_query",
+                UserIntent::TaskRequest => "intent.task_request",
+                UserIntent::AgentSelection => "intent.agent_selection",
+                UserIntent::HelpRequest => "intent.help_request",
+                UserIntent::Greeting => "intent.greeting",
+                UserIntent::Unknown => "intent.unknown",
+            },
+        );
 
         let intent_response = format!(
-            "{} **{}**\n{}\n\n📝 **Request:** {}\n\n{}",
+            "{} **{}**\n{}\n\n{} {}\n\n{}",
             persona.emoji,
             persona.name,
             action_description,
+            catalog.text(locale, "tasks.request"),
             if processed_message.len() > 100 {
                 format!("{}...", &processed_message[..100])
             } else {
                 processed_message.clone()
             },
-            "⏳ Working on this now..."
+            catalog.text(locale, "tasks.working")
         );
 
-        let mut intent_msg = if let Ok(response) = msg.reply(&ctx.http, intent_response).await {
+        // 🧵 TASK THREAD: A task started in a channel gets its own thread, which keys its
+        // Claude session; one started inside a thread stays there
+        let mut context = context.with_thread(&ctx).await;
+        if let Some(session) = &user_session {
+            self.bot.user_sessions.set_agent(session, &agent_type).await;
+        }
+        let task_thread = if context.thread_id.is_none()
+            && context.guild_id.is_some()
+            && prefs
+                .task_threads
+                .unwrap_or(self.bot.discord_config.load().task_threads)
+        {
+            self.bot
+                .open_task_thread(&ctx, msg, &agent_type, &processed_message)
+                .await
+        } else {
+            None
+        };
+        if let Some(thread) = task_thread {
+            context.thread_id = Some(thread.get());
+        }
+        // 🗂️ SESSION WORKSPACE: The workspace the user chose wins; otherwise a task thread
+        // keeps its own conversation and other tasks continue the session's workspace
+        if let Some(session) = &user_session {
+            context.user_session = Some(session.id);
+            context.session_key = match session.workspace() {
+                Some(workspace) => Some(workspace.to_string()),
+                None => context
+                    .thread_id
+                    .is_none()
+                    .then(|| session.own_workspace_key()),
+            };
+        }
+
+        let mut intent_msg = if let Ok(response) =
+            Self::reply_to_task(&ctx, msg, task_thread, intent_response).await
+        {
             Some(response)
         } else {
             warn!("[SpiralConstellation] Failed to send intent response");
@@ -1838,7 +3220,7 @@ impl EventHandler for ConstellationBotHandler {
                     )
                     .await;
             } else {
-                let _ = msg.reply(&ctx.http, unavailable_response).await;
+                let _ = Self::reply_to_task(&ctx, msg, task_thread, unavailable_response).await;
             }
 
             // Remove eyes reaction since we can't process
@@ -1855,77 +3237,74 @@ impl EventHandler for ConstellationBotHandler {
             intent.clone(),
         );
         let task_id = task.id.clone();
+        let priority = task.priority.clone();
 
         info!(
             "[SpiralConstellation] Created {} task: {}",
             persona.name, task_id
         );
 
-        // Execute based on agent type - choose execution mode based on bot configuration
-        let result = match agent_type {
-            AgentType::SoftwareDeveloper => {
-                // Choose execution mode: direct agent or orchestrator
-                if let Some(orchestrator) = &self.bot.orchestrator {
-                    // 🎛️ ORCHESTRATOR MODE: Use full system with task queuing and management
-                    info!("[SpiralConstellation] Using orchestrator mode for task execution");
-                    let task_id = match orchestrator.submit_task(task).await {
-                        Ok(id) => id,
-                        Err(e) => {
+        // Execute with the orchestrator when available, otherwise with the agent directly
+        let result: TaskReply = {
+            // Choose execution mode: direct agent or orchestrator
+            if let Some(orchestrator) = &self.bot.orchestrator {
+                // 🎛️ ORCHESTRATOR MODE: Use full system with task queuing and management
+                info!("[SpiralConstellation] Using orchestrator mode for task execution");
+                // Subscribe before submitting so a fast result cannot be missed
+                let mut results = orchestrator.subscribe_results();
+                let submitter = SpiralConstellationBot::queue_submitter(self.bot.message_role(msg));
+                let task_id = match orchestrator.submit_task_as(task, submitter).await {
+                    Ok(id) => id,
+                    Err(e) => {
+                        warn!(
+                            "[SpiralConstellation] Failed to submit task to orchestrator: {}",
+                            e
+                        );
+                        let error_message =
+                            self.bot.format_helpful_error_message(&e, persona, locale);
+                        if let Err(reply_err) =
+                            Self::reply_to_task(&ctx, msg, task_thread, error_message).await
+                        {
                             warn!(
-                                "[SpiralConstellation] Failed to submit task to orchestrator: {}",
-                                e
+                                "[SpiralConstellation] Failed to send error message: {}",
+                                reply_err
                             );
-                            let error_message = self.bot.format_helpful_error_message(&e, persona);
-                            if let Err(reply_err) = msg.reply(&ctx.http, error_message).await {
-                                warn!(
-                                    "[SpiralConstellation] Failed to send error message: {}",
-                                    reply_err
-                                );
-                            }
-                            return;
                         }
-                    };
-
-                    // Wait for task completion with progress updates
-                    let timeout_duration = std::time::Duration::from_secs(120); // Increased timeout
-                    let progress_emojis = ["⏳", "⌛", "🤔", "⚙️", "🔄"];
-                    let mut emoji_index = 0;
-                    let start_time = std::time::Instant::now();
-
-                    let poll_future = async {
-                        let mut last_update = std::time::Instant::now();
-                        let max_attempts = 240; // 120 seconds at 500ms intervals
-                        let mut attempts = 0;
-
-                        loop {
-                            if let Some(result) = orchestrator.get_task_result(&task_id).await {
-                                return Ok(result);
-                            }
-
-                            attempts += 1;
-                            if attempts >= max_attempts {
-                                warn!("[SpiralConstellation] Task {} exceeded maximum polling attempts", task_id);
-                                return Err(SpiralError::Timeout {
-                                    message: format!("Task execution exceeded maximum polling time of {} seconds", timeout_duration.as_secs()),
-                                });
-                            }
+                        return;
+                    }
+                };
+                if let Some(progress_message) = &intent_msg {
+                    self.bot
+                        .task_messages
+                        .track(progress_message.id.get(), &task_id);
+                }
 
-                            // Update emoji every 15 seconds
-                            if last_update.elapsed() >= std::time::Duration::from_secs(15) {
-                                emoji_index = (emoji_index + 1) % progress_emojis.len();
-
-                                let progress_response = format!(
-                                    "{} **{}**\n{}\n\n📝 **Request:** {}\n\n{} Working on this... ({:.0}s)",
-                                    persona.emoji,
-                                    persona.name,
-                                    action_description,
-                                    if processed_message.len() > 100 {
-                                        format!("{}...", &processed_message[..100])
-                                    } else {
-                                        processed_message.clone()
-                                    },
-                                    progress_emojis[emoji_index],
-                                    start_time.elapsed().as_secs()
+                // Wait for task completion with progress updates
+                let timeout_duration = std::time::Duration::from_secs(120); // Increased timeout
+                let start_time = std::time::Instant::now();
+
+                let wait_future = async {
+                    let mut progress_interval =
+                        tokio::time::interval(std::time::Duration::from_secs(15));
+                    progress_interval.tick().await; // First tick completes immediately
+
+                    loop {
+                        let received = tokio::select! {
+                            received = results.recv() => received,
+                            _ = progress_interval.tick() => {
+                                let progress = orchestrator.get_task_progress(&task_id).await;
+                                let queued = orchestrator
+                                    .get_task_status(&task_id)
+                                    .await
+                                    .is_some_and(|task| task.status == TaskStatus::Pending);
+                                let progress_response = SpiralConstellationBot::format_progress_update(
+                                    persona,
+                                    &action_description,
+                                    &catalog.text(locale, "tasks.request"),
+                                    &processed_message,
+                                    progress.as_ref(),
+                                    queued,
+                                    start_time.elapsed().as_secs(),
                                 );
 
                                 if let Some(ref mut msg_ref) = intent_msg {
@@ -1938,241 +3317,285 @@ impl EventHandler for ConstellationBotHandler {
                                         .await;
                                 }
 
-                                last_update = std::time::Instant::now();
+                                continue;
                             }
+                        };
 
-                            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                        match received {
+                            Ok(result) if result.task_id == task_id => return result,
+                            Ok(_) => {}
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                                // Our result may have been among the skipped ones
+                                if let Some(result) = orchestrator.get_task_result(&task_id).await {
+                                    return result;
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                                // Orchestrator is gone, wait out the timeout instead of spinning
+                                std::future::pending::<()>().await;
+                            }
                         }
-                    };
+                    }
+                };
 
-                    match tokio::time::timeout(timeout_duration, poll_future).await {
-                        Ok(Ok(result)) => {
-                            info!(
-                                "[SpiralConstellation] {} task {} completed via orchestrator",
-                                persona.name, task_id
-                            );
+                match tokio::time::timeout(timeout_duration, wait_future).await {
+                    Ok(result) => {
+                        info!(
+                            "[SpiralConstellation] {} task {} completed via orchestrator",
+                            persona.name, task_id
+                        );
 
-                            // Update success stats
-                            {
-                                let mut stats = self.bot.stats.lock().await;
-                                stats.dev_tasks_completed += 1;
-                                stats.current_persona = None;
-                            }
+                        // Update success stats
+                        {
+                            let mut stats = self.bot.stats.lock().await;
+                            stats.dev_tasks_completed += 1;
+                            stats.current_persona = None;
+                        }
 
-                            self.bot.format_persona_response(&agent_type, &result)
+                        self.bot
+                            .format_persona_response(&agent_type, &result, prefs.verbosity())
+                            .await
+                    }
+                    Err(_timeout) => {
+                        warn!("[SpiralConstellation] {} task {} timed out via orchestrator after 2 minutes", persona.name, task_id);
+
+                        // Check one more time if task completed during timeout
+                        if let Some(result) = orchestrator.get_task_result(&task_id).await {
+                            info!("[SpiralConstellation] {} task {} completed just after timeout check", persona.name, task_id);
+                            self.bot
+                                .format_persona_response(&agent_type, &result, prefs.verbosity())
+                                .await
+                        } else {
+                            let timeout_error = crate::SpiralError::Agent {
+                            message: "Task is taking longer than expected - still processing in background".to_string(),
+                        };
+
+                            self.bot
+                                .format_helpful_error_message(&timeout_error, persona, locale)
+                                .into()
                         }
-                        Ok(Err(e)) => {
-                            warn!(
-                                "[SpiralConstellation] {} task {} failed via orchestrator: {}",
-                                persona.name, task_id, e
+                    }
+                }
+            } else if let Some(direct_agent) = self.bot.direct_agent(&agent_type) {
+                // 🎯 DIRECT MODE: Use standalone agent execution
+                info!("[SpiralConstellation] Using direct mode for task execution");
+                // 💸 BUDGET CHECK: The orchestrator refuses over-budget tasks on submit;
+                // without one the check happens here
+                if let Some(Err(e)) = self.bot.cost_tracker().map(|costs| costs.check_budget()) {
+                    let error_message = self.bot.format_helpful_error_message(&e, persona, locale);
+                    if let Err(reply_err) =
+                        Self::reply_to_task(&ctx, msg, task_thread, error_message).await
+                    {
+                        warn!(
+                            "[SpiralConstellation] Failed to send error message: {}",
+                            reply_err
+                        );
+                    }
+                    return;
+                }
+                // The agent reports into a local tracker that the edit loop below reads
+                let progress_tracker = TaskProgressTracker::new();
+                let handle = OrchestratorHandle::detached()
+                    .with_progress(ProgressReporter::new(progress_tracker.clone(), &task_id));
+                let execute_future = direct_agent.execute(task, handle);
+                let timeout_duration = std::time::Duration::from_secs(90); // Increased timeout for direct mode
+
+                // Create progress update task for direct mode
+                let progress_task = {
+                    let mut intent_msg_clone = intent_msg.clone();
+                    let ctx_clone = ctx.clone();
+                    let persona_clone = persona.clone();
+                    let action_desc_clone = action_description.clone();
+                    let request_label = catalog.text(locale, "tasks.request");
+                    let content_clone = processed_message.clone();
+                    let task_id_clone = task_id.clone();
+
+                    tokio::spawn(async move {
+                        let start_time = std::time::Instant::now();
+
+                        // Update every 15 seconds
+                        while start_time.elapsed() < std::time::Duration::from_secs(90) {
+                            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+
+                            let progress = progress_tracker.latest(&task_id_clone).await;
+                            let progress_response = SpiralConstellationBot::format_progress_update(
+                                &persona_clone,
+                                &action_desc_clone,
+                                &request_label,
+                                &content_clone,
+                                progress.as_ref(),
+                                false,
+                                start_time.elapsed().as_secs(),
                             );
 
-                            self.bot.format_helpful_error_message(&e, persona)
+                            if let Some(ref mut intent_message) = intent_msg_clone {
+                                let _ = intent_message
+                                    .edit(
+                                        &ctx_clone.http,
+                                        serenity::builder::EditMessage::new()
+                                            .content(progress_response),
+                                    )
+                                    .await;
+                            }
                         }
-                        Err(_timeout) => {
-                            warn!("[SpiralConstellation] {} task {} timed out via orchestrator after 2 minutes", persona.name, task_id);
+                    })
+                };
 
-                            // Check one more time if task completed during timeout
-                            if let Some(result) = orchestrator.get_task_result(&task_id).await {
-                                info!("[SpiralConstellation] {} task {} completed just after timeout check", persona.name, task_id);
-                                self.bot.format_persona_response(&agent_type, &result)
-                            } else {
-                                let timeout_error = crate::SpiralError::Agent {
-                                    message: "Task is taking longer than expected - still processing in background".to_string(),
-                                };
+                match tokio::time::timeout(timeout_duration, execute_future).await {
+                    Ok(execute_result) => {
+                        // Cancel progress updates
+                        progress_task.abort();
 
-                                self.bot
-                                    .format_helpful_error_message(&timeout_error, persona)
-                            }
-                        }
-                    }
-                } else if let Some(developer_agent) = &self.bot.developer_agent {
-                    // 🎯 DIRECT MODE: Use standalone agent execution
-                    info!("[SpiralConstellation] Using direct mode for task execution");
-                    let execute_future = developer_agent.execute(task);
-                    let timeout_duration = std::time::Duration::from_secs(90); // Increased timeout for direct mode
-
-                    // Create progress update task for direct mode
-                    let progress_task = {
-                        let mut intent_msg_clone = intent_msg.clone();
-                        let ctx_clone = ctx.clone();
-                        let persona_clone = persona.clone();
-                        let action_desc_clone = action_description.clone();
-                        let content_clone = processed_message.clone();
-
-                        tokio::spawn(async move {
-                            let progress_emojis = ["⏳", "⌛", "🤔", "⚙️", "🔄"];
-                            let mut emoji_index = 0;
-                            let start_time = std::time::Instant::now();
-
-                            // Update every 15 seconds
-                            while start_time.elapsed() < std::time::Duration::from_secs(90) {
-                                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
-                                emoji_index = (emoji_index + 1) % progress_emojis.len();
-
-                                let progress_response = format!(
-                                    "{} **{}**\n{}\n\n📝 **Request:** {}\n\n{} Working on this... ({:.0}s)",
-                                    persona_clone.emoji,
-                                    persona_clone.name,
-                                    action_desc_clone,
-                                    if content_clone.len() > 100 {
-                                        format!("{}...", &content_clone[..100])
-                                    } else {
-                                        content_clone.clone()
-                                    },
-                                    progress_emojis[emoji_index],
-                                    start_time.elapsed().as_secs()
+                        match execute_result {
+                            Ok(result) => {
+                                info!(
+                                    "[SpiralConstellation] {} task {} completed",
+                                    persona.name, task_id
                                 );
 
-                                if let Some(ref mut intent_message) = intent_msg_clone {
-                                    let _ = intent_message
-                                        .edit(
-                                            &ctx_clone.http,
-                                            serenity::builder::EditMessage::new()
-                                                .content(progress_response),
-                                        )
-                                        .await;
+                                // Update success stats
+                                {
+                                    let mut stats = self.bot.stats.lock().await;
+                                    stats.dev_tasks_completed += 1;
+                                    stats.current_persona = None;
                                 }
+
+                                self.bot
+                                    .format_persona_response(
+                                        &agent_type,
+                                        &result,
+                                        prefs.verbosity(),
+                                    )
+                                    .await
                             }
-                        })
-                    };
+                            Err(e) => {
+                                warn!(
+                                    "[SpiralConstellation] {} task {} failed: {}",
+                                    persona.name, task_id, e
+                                );
 
-                    match tokio::time::timeout(timeout_duration, execute_future).await {
-                        Ok(execute_result) => {
-                            // Cancel progress updates
-                            progress_task.abort();
-
-                            match execute_result {
-                                Ok(result) => {
-                                    info!(
-                                        "[SpiralConstellation] {} task {} completed",
-                                        persona.name, task_id
-                                    );
-
-                                    // Update success stats
-                                    {
-                                        let mut stats = self.bot.stats.lock().await;
-                                        stats.dev_tasks_completed += 1;
-                                        stats.current_persona = None;
-                                    }
-
-                                    self.bot.format_persona_response(&agent_type, &result)
-                                }
-                                Err(e) => {
-                                    warn!(
-                                        "[SpiralConstellation] {} task {} failed: {}",
-                                        persona.name, task_id, e
-                                    );
-
-                                    // Update failure stats
-                                    {
-                                        let mut stats = self.bot.stats.lock().await;
-                                        stats.total_tasks_failed += 1;
-                                        stats.current_persona = None;
-                                    }
-
-                                    // Provide helpful error messages based on error type
-
-                                    self.bot.format_helpful_error_message(&e, persona)
+                                // Update failure stats
+                                {
+                                    let mut stats = self.bot.stats.lock().await;
+                                    stats.total_tasks_failed += 1;
+                                    stats.current_persona = None;
                                 }
-                            }
-                        }
-                        Err(_timeout) => {
-                            // Cancel progress updates
-                            progress_task.abort();
 
-                            warn!(
-                                "[SpiralConstellation] {} task {} timed out after 90 seconds",
-                                persona.name, task_id
-                            );
+                                // Provide helpful error messages based on error type
 
-                            // Update failure stats
-                            {
-                                let mut stats = self.bot.stats.lock().await;
-                                stats.total_tasks_failed += 1;
-                                stats.current_persona = None;
+                                self.bot
+                                    .format_helpful_error_message(&e, persona, locale)
+                                    .into()
                             }
+                        }
+                    }
+                    Err(_timeout) => {
+                        // Cancel progress updates
+                        progress_task.abort();
 
-                            // Create a timeout error
-                            let timeout_error = crate::SpiralError::Agent {
-                                message: "Task execution timed out - Claude Code system may be unavailable".to_string(),
-                            };
+                        warn!(
+                            "[SpiralConstellation] {} task {} timed out after 90 seconds",
+                            persona.name, task_id
+                        );
 
-                            self.bot
-                                .format_helpful_error_message(&timeout_error, persona)
+                        // Update failure stats
+                        {
+                            let mut stats = self.bot.stats.lock().await;
+                            stats.total_tasks_failed += 1;
+                            stats.current_persona = None;
                         }
-                    }
-                } else {
-                    // Neither orchestrator nor direct agent available
-                    let config_error = crate::SpiralError::Agent {
-                        message: "Bot not properly configured - no execution method available"
-                            .to_string(),
-                    };
 
-                    self.bot
-                        .format_helpful_error_message(&config_error, persona)
+                        // Create a timeout error
+                        let timeout_error = crate::SpiralError::Agent {
+                            message:
+                                "Task execution timed out - Claude Code system may be unavailable"
+                                    .to_string(),
+                        };
+
+                        self.bot
+                            .format_helpful_error_message(&timeout_error, persona, locale)
+                            .into()
+                    }
                 }
-            }
-            _ => {
-                // For other agent types (not yet implemented)
-                format!(
-                    "{} **{}**\n🚧 I'm still learning how to handle {} requests. \
-                    My {} capabilities are under development!\n\n\
-                    💡 **What I'll be able to do:**\n{}\n\n\
-                    *—{} @ SpiralConstellation*",
-                    persona.emoji,
-                    persona.name,
-                    persona.name.to_lowercase(),
-                    persona.name.to_lowercase(),
-                    persona
-                        .personality_traits
-                        .iter()
-                        .map(|trait_name| format!("• {trait_name}"))
-                        .collect::<Vec<_>>()
-                        .join("\n"),
-                    persona.name
-                )
+            } else {
+                // Neither orchestrator nor direct agent available
+                let config_error = crate::SpiralError::Agent {
+                    message: "Bot not properly configured - no execution method available"
+                        .to_string(),
+                };
+
+                self.bot
+                    .format_helpful_error_message(&config_error, persona, locale)
+                    .into()
             }
         };
 
         // Step 5: Update the original intent message with the final result
-        if let Some(mut intent_message) = intent_msg {
+        let answer = if let Some(mut intent_message) = intent_msg {
             // Create final response with task summary
             let final_response = format!(
-                "{} **{}**\n{}\n\n📝 **Request:** {}\n\n✅ **Completed!**\n\n{}",
+                "{} **{}**\n{}\n\n{} {}\n\n{}\n\n{}",
                 persona.emoji,
                 persona.name,
                 action_description,
+                catalog.text(locale, "tasks.request"),
                 if processed_message.len() > 100 {
                     format!("{}...", &processed_message[..100])
                 } else {
                     processed_message.clone()
                 },
-                result
+                catalog.text(locale, "tasks.completed"),
+                result.content
             );
+            let final_reply = TaskReply {
+                content: final_response.trim_end().to_string(),
+                ..result.clone()
+            };
 
-            if let Err(e) = intent_message
-                .edit(
-                    &ctx.http,
-                    serenity::builder::EditMessage::new().content(final_response),
-                )
-                .await
-            {
-                warn!("[SpiralConstellation] Failed to edit intent message: {}", e);
-                // Fallback: send as new reply if edit fails
-                if let Err(e2) = msg.reply(&ctx.http, result).await {
-                    warn!(
-                        "[SpiralConstellation] Failed to send fallback result: {}",
-                        e2
-                    );
+            match intent_message.edit(&ctx.http, final_reply.edit()).await {
+                Ok(()) => Some(intent_message),
+                Err(e) => {
+                    warn!("[SpiralConstellation] Failed to edit intent message: {}", e);
+                    // Fallback: send as new reply if edit fails
+                    Self::reply_to_task(&ctx, msg, task_thread, result.clone())
+                        .await
+                        .inspect_err(|e2| {
+                            warn!(
+                                "[SpiralConstellation] Failed to send fallback result: {}",
+                                e2
+                            )
+                        })
+                        .ok()
                 }
             }
         } else {
             // Fallback: send as reply if we don't have the intent message
-            if let Err(e) = msg.reply(&ctx.http, result).await {
-                warn!("[SpiralConstellation] Failed to send result: {}", e);
-            }
+            Self::reply_to_task(&ctx, msg, task_thread, result.clone())
+                .await
+                .inspect_err(|e| warn!("[SpiralConstellation] Failed to send result: {}", e))
+                .ok()
+        };
+
+        // The answer may be a new message when the progress message could not be edited
+        if let (Some(_), Some(answer)) = (&self.bot.orchestrator, &answer) {
+            self.bot.task_messages.track(answer.id.get(), &task_id);
         }
+        self.bot
+            .track_pages(result.pages.as_ref(), answer.as_ref())
+            .await;
+
+        self.bot
+            .announce_result(
+                &ctx,
+                msg.guild_id,
+                &task_id,
+                &agent_type,
+                &priority,
+                &msg.author,
+                &result,
+                answer.as_ref(),
+            )
+            .await;
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
@@ -2217,13 +3640,61 @@ impl EventHandler for ConstellationBotHandler {
             }
         }
 
-        // Set bot activity status to show the commands
-        use serenity::all::ActivityData;
-        let activity = ActivityData::playing("!spiral commands for help");
-        let status = OnlineStatus::Online;
+        // 🏗️ ARCHITECTURE DECISION: Register /spiral per guild on every start
+        // Why: Guild commands reach clients at once, while global ones can take an hour;
+        // re-registering each start keeps the options in step with the running code
+        // Alternative: Global registration (rejected: stale options after every deploy)
+        // Trade-off: Guilds joined while running get the command on the next start
+        for guild in &ready.guilds {
+            match guild
+                .id
+                .set_commands(&ctx.http, vec![slash::register()])
+                .await
+            {
+                Ok(_) => info!("[Event] Registered /spiral in guild {}", guild.id),
+                Err(e) => warn!(
+                    "[Event] Failed to register /spiral in guild {}: {}",
+                    guild.id, e
+                ),
+            }
+        }
+
+        // Ready fires again after reconnects; one delivery loop is enough
+        if self.bot.orchestrator.is_some()
+            && !self
+                .bot
+                .schedule_delivery_started
+                .swap(true, Ordering::SeqCst)
+        {
+            tokio::spawn(self.bot.clone().deliver_scheduled_results(ctx.http.clone()));
+        }
+
+        if !self
+            .bot
+            .digest_delivery_started
+            .swap(true, Ordering::SeqCst)
+        {
+            tokio::spawn(self.bot.clone().deliver_digests(ctx.http.clone()));
+        }
+
+        // 🟢 PRESENCE: Current work as the status when configured, otherwise the help hint
+        if self.bot.discord_config.load().presence_status && self.bot.orchestrator.is_some() {
+            if !self
+                .bot
+                .presence_updates_started
+                .swap(true, Ordering::SeqCst)
+            {
+                tokio::spawn(self.bot.clone().update_presence(ctx.clone()));
+                info!("Bot status follows current work");
+            }
+        } else {
+            use serenity::all::ActivityData;
+            let activity = ActivityData::playing(presence::IDLE_ACTIVITY);
+            let status = OnlineStatus::Online;
 
-        ctx.set_presence(Some(activity), status);
-        info!("Bot status set: Playing '!spiral commands for help'");
+            ctx.set_presence(Some(activity), status);
+            info!("Bot status set: Playing '{}'", presence::IDLE_ACTIVITY);
+        }
 
         let stats = self.bot.stats.lock().await;
         info!(
@@ -2232,6 +3703,133 @@ impl EventHandler for ConstellationBotHandler {
         );
     }
 
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let command = match interaction {
+            Interaction::Command(command) => command,
+            Interaction::Component(component) => {
+                if let Some(button) = ApprovalButton::from_custom_id(&component.data.custom_id) {
+                    self.handle_approval_button(&ctx, &component, button).await;
+                } else if let Some(page) = paginator::page_from_custom_id(&component.data.custom_id)
+                {
+                    self.turn_page(&ctx, &component, page).await;
+                } else if let Some(choice) =
+                    Interpretation::from_custom_id(&component.data.custom_id)
+                {
+                    self.resolve_clarification(&ctx, &component, choice).await;
+                }
+                return;
+            }
+            _ => return,
+        };
+        if command.data.name != slash::SPIRAL_COMMAND {
+            return;
+        }
+        let user = &command.user;
+        debug!(
+            "[Event] /{} from {} in channel {}",
+            command.data.name, user.name, command.channel_id
+        );
+
+        // 🏘️ ALLOWED CHANNELS: Same as !spiral messages, but a slash command is answered
+        if !self
+            .bot
+            .settings_for(command.guild_id)
+            .allows_channel(command.channel_id.get())
+        {
+            Self::respond_ephemeral(
+                &ctx,
+                &command,
+                "🔇 This server's settings don't allow Spiral in this channel.".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        // 🔐 UNIVERSAL AUTHORIZATION: Same gate as !spiral messages
+        let member_roles = command
+            .member
+            .as_ref()
+            .map(|member| member.roles.as_slice())
+            .unwrap_or_default();
+        let role = self
+            .bot
+            .member_role(user.id.get(), command.guild_id, member_roles);
+        if role.is_none() {
+            let denial_quote =
+                LordgenomeQuoteGenerator::new().generate_denial(&user.name, "general");
+            audit::record(
+                AuditEvent::new(AuditEventKind::AuthFailure, AuditSource::Discord, "/spiral")
+                    .with_actor(format!("{} ({})", user.name, user.id))
+                    .with_client(format!("channel {}", command.channel_id))
+                    .with_details("User is not authorized"),
+            );
+            self.bot
+                .alert_ops(
+                    &ctx,
+                    command.guild_id,
+                    format!(
+                        "Unauthorized `/spiral` attempt by {} ({}) in <#{}>",
+                        user.name, user.id, command.channel_id
+                    ),
+                )
+                .await;
+            Self::respond_ephemeral(&ctx, &command, denial_quote).await;
+            return;
+        }
+
+        let parsed = match slash::SlashCommand::parse(&command.data) {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                Self::respond_ephemeral(&ctx, &command, message).await;
+                return;
+            }
+        };
+        if let Err(denial) = commands::authorize(
+            role,
+            user,
+            command.channel_id,
+            &parsed.audit_target(),
+            parsed.required_role(),
+            &parsed.category(),
+        ) {
+            Self::respond_ephemeral(&ctx, &command, denial).await;
+            return;
+        }
+
+        if let slash::SlashCommand::Task {
+            agent_type,
+            description,
+            priority,
+        } = parsed
+        {
+            if let Some(refusal) = self
+                .bot
+                .task_channel_refusal(command.guild_id, command.channel_id)
+            {
+                Self::respond_ephemeral(&ctx, &command, refusal).await;
+                return;
+            }
+            let submitter = SpiralConstellationBot::queue_submitter(role);
+            self.run_slash_task(&ctx, &command, agent_type, description, priority, submitter)
+                .await;
+            return;
+        }
+
+        let response = self
+            .bot
+            .command_router
+            .route_slash(&parsed, user, &self.bot)
+            .await
+            .unwrap_or_else(|| "❓ Nothing to report.".to_string());
+        let message = CreateInteractionResponseMessage::new().content(response);
+        if let Err(e) = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+            .await
+        {
+            warn!("[SpiralConstellation] Failed to answer /spiral: {}", e);
+        }
+    }
+
     async fn reaction_add(&self, ctx: Context, add_reaction: serenity::model::channel::Reaction) {
         // Don't handle reactions from bots
         if let Ok(user) = add_reaction.user(&ctx.http).await {
@@ -2239,8 +3837,19 @@ impl EventHandler for ConstellationBotHandler {
                 return;
             }
 
-            // Check if user is authorized
-            let is_authorized = self.bot.is_authorized_user(user.id.get());
+            // Reaction actions change state, so they need at least the operator role;
+            // task actions may need more, like the API routes they stand in for
+            let required = match &add_reaction.emoji {
+                serenity::model::channel::ReactionType::Unicode(name) => {
+                    TaskAction::from_emoji(name).map(|action| action.required_role())
+                }
+                _ => None,
+            }
+            .unwrap_or(rbac::Role::Operator);
+            let is_authorized = self
+                .bot
+                .reaction_role(&add_reaction, user.id.get())
+                .is_some_and(|role| role.permits(required));
 
             // Use the reaction handler manager
             let handled = self
@@ -2302,7 +3911,11 @@ impl EventHandler for ConstellationBotHandler {
             }
 
             // Check if user is authorized
-            if !self.bot.is_authorized_user(user.id.get()) {
+            let is_operator = self
+                .bot
+                .reaction_role(&add_reaction, user.id.get())
+                .is_some_and(|role| role.permits(rbac::Role::Operator));
+            if !is_operator {
                 return;
             }
 
@@ -2357,8 +3970,8 @@ impl EventHandler for ConstellationBotHandler {
                         user.id
                     );
 
-                    // CRITICAL SECURITY: Check authorization for auto-fix operations
-                    if !self.bot.is_authorized_user(user.id.get()) {
+                    // CRITICAL SECURITY: Auto-fix changes the codebase, so it is admin-only
+                    if !self.bot.has_role(user.id.get(), rbac::Role::Admin) {
                         warn!(
                             "[SpiralConstellation] Unauthorized auto-fix attempt by user {}",
                             user.id
@@ -2369,7 +3982,7 @@ impl EventHandler for ConstellationBotHandler {
                             **User:** <@{}>\n\
                             **Action:** Auto-fix operation\n\
                             **Status:** Unauthorized\n\n\
-                            You must be authorized to request auto-fix operations.",
+                            Auto-fix operations require the admin role.",
                             user.id
                         );
 
@@ -2404,7 +4017,7 @@ impl EventHandler for ConstellationBotHandler {
                         }
                     } else if emoji_unicode == emojis::HAMMER.to_string() {
                         // CRITICAL SECURITY: Check authorization for correction prompts
-                        if !self.bot.is_authorized_user(user.id.get()) {
+                        if !is_operator {
                             warn!("[SpiralConstellation] Unauthorized correction prompt attempt by user {}", user.id);
 
                             let unauthorized_msg = format!(
@@ -2412,7 +4025,7 @@ impl EventHandler for ConstellationBotHandler {
                                 **User:** <@{}>\n\
                                 **Action:** Message correction\n\
                                 **Status:** Unauthorized\n\n\
-                                You must be authorized to request message corrections.",
+                                Message corrections require the operator role.",
                                 user.id
                             );
 
@@ -2443,7 +4056,7 @@ impl EventHandler for ConstellationBotHandler {
                     );
 
                     // CRITICAL SECURITY: Re-check authorization for retry operations
-                    if !self.bot.is_authorized_user(user.id.get()) {
+                    if !self.bot.has_role(user.id.get(), rbac::Role::Admin) {
                         warn!(
                             "[SpiralConstellation] Unauthorized retry attempt by user {}",
                             user.id
@@ -2455,7 +4068,7 @@ impl EventHandler for ConstellationBotHandler {
                             **User:** <@{}>\n\
                             **Action:** Self-updating retry\n\
                             **Status:** Unauthorized\n\n\
-                            You must be authorized to retry Auto Core Update operations.",
+                            Retrying Auto Core Update operations requires the admin role.",
                             user.id
                         );
 
@@ -2474,19 +4087,455 @@ impl EventHandler for ConstellationBotHandler {
 }
 
 impl ConstellationBotHandler {
-    /// Check if message is an Auto Core Update request via direct bot mention
-    async fn is_auto_core_update_request(&self, msg: &Message) -> bool {
-        // Check for direct mention of the bot (exact user ID match)
-        let bot_user_id = match msg.guild_id {
-            Some(_) => {
-                // In a guild, we need to check if bot is mentioned directly
-                msg.mentions.iter().any(|user| user.bot)
-            }
+    /// Answer a task's message in the task's thread when it has one, otherwise as a reply
+    async fn reply_to_task(
+        ctx: &Context,
+        msg: &Message,
+        task_thread: Option<ChannelId>,
+        reply: impl Into<TaskReply>,
+    ) -> serenity::Result<Message> {
+        let message = reply.into().message();
+        match task_thread {
+            Some(thread) => thread.send_message(&ctx.http, message).await,
             None => {
-                // In DM, all messages are direct
-                true
+                msg.channel_id
+                    .send_message(&ctx.http, message.reference_message(msg))
+                    .await
+            }
+        }
+    }
+
+    /// Answer an interaction with a message only the invoking user sees
+    async fn respond_ephemeral(ctx: &Context, command: &CommandInteraction, content: String) {
+        let message = CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true);
+        if let Err(e) = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+            .await
+        {
+            warn!("[SpiralConstellation] Failed to answer /spiral: {}", e);
+        }
+    }
+
+    /// ✋ APPROVAL BUTTONS: Approve, reject or read the self-update plan a button sits on
+    /// 🛡️ SECURITY AUDIT CHECKPOINT: The presser needs a role, and deciding needs to be the
+    /// requester or an admin; refusals are audited and answered only to the presser
+    async fn handle_approval_button(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+        button: ApprovalButton,
+    ) {
+        let user = &component.user;
+        let plan_message_id = component.message.id.get();
+        let approval_manager = &self.bot.approval_manager;
+        let audit_denial = |details: &str| {
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::AccessDenied,
+                    AuditSource::Discord,
+                    format!("approval button on message {plan_message_id}"),
+                )
+                .with_actor(format!("{} ({})", user.name, user.id))
+                .with_client(format!("channel {}", component.channel_id))
+                .with_details(details.to_string()),
+            );
+        };
+
+        if self.bot.user_role(user.id.get()).is_none() {
+            audit_denial("User is not authorized");
+            let denial_quote =
+                LordgenomeQuoteGenerator::new().generate_denial(&user.name, "general");
+            Self::respond_to_button(ctx, component, denial_quote, true).await;
+            return;
+        }
+
+        let result = match button {
+            ApprovalButton::ShowPlan => {
+                let content = match approval_manager.pending_for_message(plan_message_id).await {
+                    Ok(pending) => {
+                        let plan = format_plan_for_discord(&pending.plan);
+                        if plan.chars().count() > MAX_OUTPUT_RESPONSE {
+                            let mut plan: String =
+                                plan.chars().take(MAX_OUTPUT_RESPONSE - 3).collect();
+                            plan.push_str("...");
+                            plan
+                        } else {
+                            plan
+                        }
+                    }
+                    Err(refusal) => refusal.to_string(),
+                };
+                Self::respond_to_button(ctx, component, content, true).await;
+                return;
+            }
+            ApprovalButton::Approve => ApprovalResult::Approved,
+            ApprovalButton::Reject => {
+                ApprovalResult::Rejected(format!("Rejected by {} via button", user.name))
+            }
+        };
+
+        let is_admin = self.bot.has_role(user.id.get(), rbac::Role::Admin);
+        match approval_manager
+            .decide(plan_message_id, user.id.get(), is_admin, result.clone())
+            .await
+        {
+            Ok(request_id) => {
+                info!(
+                    "[SpiralConstellation] Approval button {:?} from {} for request {}",
+                    button, user.id, request_id
+                );
+                let confirmation = match result {
+                    ApprovalResult::Approved => {
+                        format!(
+                            "✅ Plan approved by <@{}>! Proceeding with implementation...",
+                            user.id
+                        )
+                    }
+                    _ => format!("❌ Plan rejected by <@{}>. Update cancelled.", user.id),
+                };
+                Self::respond_to_button(ctx, component, confirmation, false).await;
+            }
+            Err(refusal) => {
+                if refusal == ApprovalRefusal::NotAllowed {
+                    audit_denial("Not the requester or an admin");
+                }
+                Self::respond_to_button(ctx, component, refusal.to_string(), true).await;
+            }
+        }
+    }
+
+    /// 📖 PAGE BUTTON: Show another page of a paged answer; once its pages have expired
+    /// the buttons are removed and the presser is told why
+    async fn turn_page(&self, ctx: &Context, component: &ComponentInteraction, page: usize) {
+        let turned = self
+            .bot
+            .message_state_manager
+            .turn_page(component.message.id, page)
+            .await;
+        let expired = turned.is_none();
+        let update = match turned {
+            Some((embed, buttons)) => CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(buttons),
+            None => CreateInteractionResponseMessage::new().components(Vec::new()),
+        };
+        if let Err(e) = component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(update))
+            .await
+        {
+            warn!("[SpiralConstellation] Failed to turn page: {}", e);
+            return;
+        }
+        if expired {
+            let notice = CreateInteractionResponseFollowup::new()
+                .content("⌛ These pages were open too long without a page turn and have expired.")
+                .ephemeral(true);
+            if let Err(e) = component.create_followup(&ctx.http, notice).await {
+                warn!(
+                    "[SpiralConstellation] Failed to send page expiry notice: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    /// 🤔 CLARIFICATION ANSWER: Run the request asked about with the chosen interpretation,
+    /// or drop it; the question loses its buttons either way
+    async fn resolve_clarification(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+        choice: Interpretation,
+    ) {
+        let resolution = self.bot.clarifications.resolve(
+            component.message.id.get(),
+            component.user.id.get(),
+            choice,
+        );
+        let (outcome, request) = match resolution {
+            Resolution::NotAuthor => {
+                Self::respond_to_button(
+                    ctx,
+                    component,
+                    "🤔 Only the person who made the request can answer this.".to_string(),
+                    true,
+                )
+                .await;
+                return;
+            }
+            Resolution::Expired => (
+                "⌛ This question has expired; mention the agent again to start over.".to_string(),
+                None,
+            ),
+            Resolution::Cancelled => {
+                self.bot
+                    .secure_message_handler
+                    .record_clarification_answered(false);
+                ("✖️ Cancelled; no task was started.".to_string(), None)
+            }
+            Resolution::Confirmed(request) => {
+                self.bot
+                    .secure_message_handler
+                    .record_clarification_answered(true);
+                (format!("✅ Going with: {}", choice.label()), Some(request))
+            }
+        };
+        info!(
+            "[SpiralConstellation] {} answered clarification {} with {:?}",
+            component.user.name, component.message.id, choice
+        );
+
+        let update = CreateInteractionResponseMessage::new()
+            .content(outcome)
+            .components(Vec::new());
+        if let Err(e) = component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(update))
+            .await
+        {
+            warn!(
+                "[SpiralConstellation] Failed to answer clarification: {}",
+                e
+            );
+        }
+        if let Some(request) = request {
+            self.message(ctx.clone(), *request).await;
+        }
+    }
+
+    async fn respond_to_button(
+        ctx: &Context,
+        component: &ComponentInteraction,
+        content: String,
+        ephemeral: bool,
+    ) {
+        let message = CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(ephemeral);
+        if let Err(e) = component
+            .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+            .await
+        {
+            warn!(
+                "[SpiralConstellation] Failed to answer approval button: {}",
+                e
+            );
+        }
+    }
+
+    /// ⚡ SLASH TASK: Run a `/spiral task` and answer with the agent's result
+    /// Discord wants an answer within 3 seconds, so the interaction is deferred and the
+    /// result edited in when the task finishes; past the wait the user is pointed at
+    /// `/spiral status`, as the task keeps running
+    async fn run_slash_task(
+        &self,
+        ctx: &Context,
+        command: &CommandInteraction,
+        agent_type: AgentType,
+        description: String,
+        priority: Priority,
+        submitter: Submitter,
+    ) {
+        let user_id = command.user.id.get();
+
+        // 🛡️ SECURITY VALIDATION: The description reaches an agent like a message would
+        let validation = {
+            let mut validator = self.bot.security_validator.lock().await;
+            validator.validate_text(user_id, &description).await
+        };
+        let issues = match validation {
+            Ok(result) if result.is_valid => None,
+            Ok(result) => Some(result.issues.join("; ")),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(issues) = issues {
+            audit::record(
+                AuditEvent::new(
+                    AuditEventKind::BlockedMessage,
+                    AuditSource::Discord,
+                    "/spiral task",
+                )
+                .with_actor(format!("{} ({})", command.user.name, command.user.id))
+                .with_client(format!("channel {}", command.channel_id))
+                .with_details(issues.clone()),
+            );
+            self.bot
+                .alert_ops(
+                    ctx,
+                    command.guild_id,
+                    format!(
+                        "Blocked `/spiral task` from {} ({}) in <#{}>: {issues}",
+                        command.user.name, command.user.id, command.channel_id
+                    ),
+                )
+                .await;
+            Self::respond_ephemeral(
+                ctx,
+                command,
+                "🚫 Task flagged by security validation. Please ensure your request follows community guidelines.".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        if let Err(e) = command.defer(&ctx.http).await {
+            warn!("[SpiralConstellation] Failed to defer /spiral task: {}", e);
+            return;
+        }
+        let edit = |reply: TaskReply| async move {
+            command
+                .edit_response(&ctx.http, reply.interaction_edit())
+                .await
+                .inspect_err(|e| {
+                    warn!(
+                        "[SpiralConstellation] Failed to edit /spiral task answer: {}",
+                        e
+                    )
+                })
+                .ok()
+        };
+
+        let persona = AgentPersona::for_agent_type(&agent_type);
+        let locale = self.bot.locale_for(command.guild_id, command.user.id);
+        let context = MessageContext {
+            author_id: user_id,
+            channel_id: command.channel_id.get(),
+            // Interactions have no message; the interaction ID identifies the request
+            message_id: command.id.get(),
+            guild_id: command.guild_id.map(|id| id.get()),
+            thread_id: None,
+            session_key: None,
+            user_session: None,
+        }
+        .with_thread(ctx)
+        .await;
+        let mut task = self.bot.create_task_with_persona(
+            &description,
+            agent_type.clone(),
+            context,
+            UserIntent::TaskRequest,
+        );
+        task.priority = priority;
+        let task_id = task.id.clone();
+        let priority = task.priority.clone();
+        info!(
+            "[SpiralConstellation] Created {} task {} from /spiral task",
+            persona.name, task_id
+        );
+
+        let outcome = if let Some(orchestrator) = &self.bot.orchestrator {
+            // Subscribe before submitting so a fast result cannot be missed
+            let mut results = orchestrator.subscribe_results();
+            if let Err(e) = orchestrator.submit_task_as(task, submitter).await {
+                edit(
+                    self.bot
+                        .format_helpful_error_message(&e, persona, locale)
+                        .into(),
+                )
+                .await;
+                return;
+            }
+            edit(
+                format!(
+                    "{} **{}**\n{}\n\n📋 Task `{}`",
+                    persona.emoji, persona.name, persona.working_message, task_id
+                )
+                .into(),
+            )
+            .await;
+
+            let wait = async {
+                loop {
+                    match results.recv().await {
+                        Ok(result) if result.task_id == task_id => return Some(result),
+                        Ok(_) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                            if let Some(result) = orchestrator.get_task_result(&task_id).await {
+                                return Some(result);
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            };
+            match tokio::time::timeout(std::time::Duration::from_secs(120), wait).await {
+                Ok(Some(result)) => Ok(result),
+                _ => match orchestrator.get_task_result(&task_id).await {
+                    Some(result) => Ok(result),
+                    None => {
+                        edit(format!(
+                            "{} **{}**\n⏳ Still working on task `{task_id}`. Check on it with `/spiral status task_id:{task_id}`.",
+                            persona.emoji, persona.name
+                        ).into())
+                        .await;
+                        return;
+                    }
+                },
+            }
+        } else if let Some(direct_agent) = self.bot.direct_agent(&agent_type) {
+            // 💸 BUDGET CHECK: Without an orchestrator nothing else refuses over-budget tasks
+            if let Some(Err(e)) = self.bot.cost_tracker().map(|costs| costs.check_budget()) {
+                Err(e)
+            } else {
+                let execute = direct_agent.execute(task, OrchestratorHandle::detached());
+                match tokio::time::timeout(std::time::Duration::from_secs(90), execute).await {
+                    Ok(result) => result,
+                    Err(_) => Err(crate::SpiralError::Agent {
+                        message: "Task execution timed out - Claude Code system may be unavailable"
+                            .to_string(),
+                    }),
+                }
+            }
+        } else {
+            Err(crate::SpiralError::Agent {
+                message: "Bot not properly configured - no execution method available".to_string(),
+            })
+        };
+
+        {
+            let mut stats = self.bot.stats.lock().await;
+            stats.current_persona = None;
+            match &outcome {
+                Ok(_) => stats.dev_tasks_completed += 1,
+                Err(_) => stats.total_tasks_failed += 1,
+            }
+        }
+        let response = match &outcome {
+            Ok(result) => {
+                let verbosity = self.bot.user_prefs.get(command.user.id.get()).verbosity();
+                self.bot
+                    .format_persona_response(&agent_type, result, verbosity)
+                    .await
             }
+            Err(e) => self
+                .bot
+                .format_helpful_error_message(e, persona, locale)
+                .into(),
         };
+        let answer = edit(response.clone()).await;
+        self.bot
+            .track_pages(response.pages.as_ref(), answer.as_ref())
+            .await;
+        self.bot
+            .announce_result(
+                ctx,
+                command.guild_id,
+                &task_id,
+                &agent_type,
+                &priority,
+                &command.user,
+                &response,
+                answer.as_ref(),
+            )
+            .await;
+    }
+
+    /// Check if message is an Auto Core Update request via direct bot mention
+    async fn is_auto_core_update_request(&self, msg: &Message) -> bool {
+        // Check for direct mention of the bot (exact user ID match)
+        // DMs are private task sessions, so there too an update must mention the bot
+        let bot_user_id = msg.mentions.iter().any(|user| user.bot);
 
         if !bot_user_id {
             return false;
@@ -2508,8 +4557,8 @@ impl ConstellationBotHandler {
     async fn handle_auto_core_update_request(&self, ctx: &Context, msg: &Message) {
         let user_id = msg.author.id.get();
 
-        // Check authorization
-        if !self.bot.is_authorized_user(user_id) {
+        // Self-updates rewrite the running system, so they are admin-only
+        if !self.bot.has_role(user_id, rbac::Role::Admin) {
             // Generate Lordgenome despair quote
             let action = self.extract_user_action(&msg.content);
             let username = &msg.author.name;
@@ -2856,7 +4905,10 @@ impl ConstellationBotHandler {
         debug_report.push_str("**Security Validation:**\n");
         let validation_result = {
             let mut validator = self.bot.security_validator.lock().await;
-            match validator.validate_message(original_msg) {
+            match validator
+                .validate_message_with_limit(original_msg, None)
+                .await
+            {
                 Ok(result) => result,
                 Err(e) => {
                     debug_report.push_str(&format!("• ❌ Validation error: {e}\n"));
@@ -2979,7 +5031,7 @@ impl ConstellationBotHandler {
                 .any(|i| i.contains("rate limit"))
             {
                 debug_report.push_str(
-                    "• User is rate limited - wait or use `!spiral reset ratelimit @user`\n",
+                    "• User is rate limited - wait, or grant a tier with `!spiral ratelimit tier @user trusted`\n",
                 );
             }
             if validation_result.issues.iter().any(|i| i.contains("spam")) {
@@ -3329,6 +5381,7 @@ impl ConstellationBotHandler {
                             "Minimize changes to existing code".to_string(),
                         ],
                         session_id: None,
+                        dry_run: false,
                     };
 
                     // Call Claude Code
@@ -3914,6 +5967,7 @@ impl SpiralConstellationBotRunner {
             let discord_http_clone = discord_http.clone();
             let approval_manager = bot_arc.approval_manager.clone();
             let system_lock = bot_arc.system_lock.clone();
+            let orchestrator = bot_arc.orchestrator.clone();
 
             tokio::spawn(async move {
                 info!("[UpdateExecutor] Starting background update processor...");
@@ -3926,6 +5980,9 @@ impl SpiralConstellationBotRunner {
                     approval_manager,
                     system_lock,
                 );
+                if let Some(orchestrator) = orchestrator {
+                    update_executor = update_executor.with_orchestrator(orchestrator);
+                }
 
                 // Run the queue processing loop
                 update_executor.process_queue().await;
@@ -5,7 +5,9 @@ use super::{
 use crate::{
     agents::{AgentOrchestrator, SoftwareDeveloperAgent},
     claude_code::ClaudeCodeClient,
-    config::Config,
+    config::{Config, ConfigHandle},
+    monitoring::SystemMonitor,
+    rate_limit::tiers::RateLimitTiers,
     Result, SpiralError,
 };
 use std::sync::Arc;
@@ -71,7 +73,9 @@ impl SpiralDiscordStartup {
         debug!("[Discord Startup] Creating developer agent...");
 
         // Create developer agent (currently the only implemented agent)
-        let developer_agent = SoftwareDeveloperAgent::new(self.claude_client.clone());
+        let backends = crate::llm::BackendSet::new(&self.config.llm, self.claude_client.clone())?;
+        let developer_agent = SoftwareDeveloperAgent::new(self.claude_client.clone())
+            .with_backend(backends.for_agent(&crate::models::AgentType::SoftwareDeveloper));
         debug!("[Discord Startup] Developer agent created successfully");
 
         // Create constellation bot with persona system
@@ -174,9 +178,15 @@ pub async fn start_discord_bots(config: Config, claude_client: ClaudeCodeClient)
 }
 
 /// 🎛️ ORCHESTRATOR INTEGRATION: Start Discord with full orchestration capabilities
+/// `system_monitor` gives the admin dashboard its availability figures; `rate_limit_tiers`
+/// is shared with the API so a tier granted through either applies to both; the bot
+/// adopts access and message limits reloaded through `config_handle`
 pub async fn start_discord_with_orchestrator(
     config: Config,
     orchestrator: Arc<AgentOrchestrator>,
+    system_monitor: Option<Arc<SystemMonitor>>,
+    rate_limit_tiers: Arc<RateLimitTiers>,
+    config_handle: Option<ConfigHandle>,
 ) -> Result<()> {
     info!("[Discord Startup] Starting Discord with orchestrator integration");
     debug!("[Discord Startup] Checking Discord token...");
@@ -220,6 +230,17 @@ pub async fn start_discord_with_orchestrator(
                 return Err(e);
             }
         };
+    let constellation_bot = match system_monitor {
+        Some(monitor) => constellation_bot.with_system_monitor(monitor),
+        None => constellation_bot,
+    }
+    .with_rate_limiting(
+        config
+            .rate_limit
+            .store
+            .open_or_memory("Discord message rate limits"),
+        rate_limit_tiers,
+    );
 
     // 🏗️ ARCHITECTURE DECISION: Dynamic agent listing from registry
     // Why: Single source of truth for available agents
@@ -227,13 +248,20 @@ pub async fn start_discord_with_orchestrator(
     info!("[Discord Startup] SpiralConstellation bot initialized with orchestrator");
     info!("[Discord Startup] Active agents will register themselves dynamically");
 
+    let reloading =
+        config_handle.map(|handle| handle.follow(Arc::new(constellation_bot.config_reloader())));
+
     // Create and run bot
     debug!("[Discord Startup] Creating bot runner...");
     let bot_runner = SpiralConstellationBotRunner::new(constellation_bot, config.discord.token);
     debug!("[Discord Startup] Bot runner created, starting bot...");
 
     info!("[Discord Startup] Attempting to connect to Discord API...");
-    match bot_runner.run().await {
+    let result = bot_runner.run().await;
+    if let Some(reloading) = reloading {
+        reloading.abort();
+    }
+    match result {
         Ok(()) => {
             info!("[Discord Startup] Discord bot shutdown gracefully");
             Ok(())
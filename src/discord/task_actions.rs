@@ -0,0 +1,244 @@
+//! Reactions that control the task behind a progress message
+//!
+//! Reacting to the bot's progress or result message with ❌ cancels the task, 🔁 submits
+//! a failed or cancelled task again, and ⬆️ moves a queued task up the queue. Cancel and
+//! retry need the operator role; bumping needs admin, like `POST /queue/{id}/promote`.
+
+use crate::agents::orchestrator::QueueMove;
+use crate::agents::AgentOrchestrator;
+use crate::auth::rbac::Role;
+use crate::constants::MAX_TRACKED_TASK_MESSAGES;
+use crate::models::TaskStatus;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+pub const CANCEL_EMOJI: &str = "❌";
+pub const RETRY_EMOJI: &str = "🔁";
+/// Discord sends ⬆️ with the emoji variation selector; the bare arrow is accepted too
+pub const ESCALATE_EMOJIS: [&str; 2] = ["⬆️", "⬆"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskAction {
+    Cancel,
+    Retry,
+    Escalate,
+}
+
+impl TaskAction {
+    pub fn from_emoji(emoji: &str) -> Option<Self> {
+        match emoji {
+            CANCEL_EMOJI => Some(Self::Cancel),
+            RETRY_EMOJI => Some(Self::Retry),
+            emoji if ESCALATE_EMOJIS.contains(&emoji) => Some(Self::Escalate),
+            _ => None,
+        }
+    }
+
+    /// Every emoji a handler is registered for, with its action
+    pub fn emojis() -> impl Iterator<Item = (&'static str, Self)> {
+        [(CANCEL_EMOJI, Self::Cancel), (RETRY_EMOJI, Self::Retry)]
+            .into_iter()
+            .chain(ESCALATE_EMOJIS.map(|emoji| (emoji, Self::Escalate)))
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Cancel => "Cancel task",
+            Self::Retry => "Retry failed task",
+            Self::Escalate => "Bump queued task",
+        }
+    }
+
+    /// 🔐 PERMISSION CHECK: The role the action needs, matching its API route
+    /// Bumping jumps everyone else's tasks, so it needs admin like queue promotion
+    pub fn required_role(&self) -> Role {
+        match self {
+            Self::Cancel | Self::Retry => Role::Operator,
+            Self::Escalate => Role::Admin,
+        }
+    }
+}
+
+/// What an action did, as replied to the reacting user
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionOutcome {
+    Cancelled {
+        was: TaskStatus,
+    },
+    Resubmitted {
+        task_id: String,
+    },
+    Promoted {
+        position: usize,
+    },
+    /// The task is in a state the action does not apply to; holds the reason
+    Refused(String),
+}
+
+impl ActionOutcome {
+    pub fn message(&self, task_id: &str) -> String {
+        match self {
+            Self::Cancelled { was } => format!("🚫 Task `{task_id}` cancelled (was {was:?})."),
+            Self::Resubmitted { task_id: retry_id } => format!(
+                "🔁 Task `{task_id}` resubmitted as `{retry_id}`. React here to control it, \
+                 or check on it with `!spiral task {retry_id}`."
+            ),
+            Self::Promoted { position } => {
+                format!("⬆️ Task `{task_id}` moved up to queue position {position}.")
+            }
+            Self::Refused(reason) => format!("⚠️ {reason}"),
+        }
+    }
+}
+
+/// Carry out `action` on `task_id`; states the action does not apply to are refused
+pub async fn apply(
+    orchestrator: &AgentOrchestrator,
+    action: TaskAction,
+    task_id: &str,
+) -> ActionOutcome {
+    let Some(task) = orchestrator.get_task_status(task_id).await else {
+        return ActionOutcome::Refused(format!("Task `{task_id}` is no longer known."));
+    };
+    let ended = matches!(
+        task.status,
+        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+    );
+    let result = match action {
+        TaskAction::Cancel if ended => {
+            return ActionOutcome::Refused(format!(
+                "Task `{task_id}` already ended ({:?}).",
+                task.status
+            ))
+        }
+        TaskAction::Cancel => orchestrator
+            .cancel_task(task_id)
+            .await
+            .map(|was| ActionOutcome::Cancelled { was }),
+        TaskAction::Retry => orchestrator
+            .resubmit_task(task_id)
+            .await
+            .map(|task_id| ActionOutcome::Resubmitted { task_id }),
+        TaskAction::Escalate if task.status != TaskStatus::Pending => {
+            return ActionOutcome::Refused(format!(
+                "Task `{task_id}` is not queued ({:?}); only queued tasks can be bumped.",
+                task.status
+            ))
+        }
+        TaskAction::Escalate => orchestrator
+            .move_queued_task(task_id, QueueMove::Promote)
+            .await
+            .map(|entry| ActionOutcome::Promoted {
+                position: entry.position,
+            }),
+    };
+    result.unwrap_or_else(|e| ActionOutcome::Refused(e.to_string()))
+}
+
+/// 🎛️ TASK MESSAGES: The task behind each progress message the bot posted
+/// 🏗️ ARCHITECTURE DECISION: In-memory map, oldest messages forgotten first, like the
+/// task threads
+/// Why: Reactions are a shortcut for recent tasks; every action is also reachable by task
+/// ID through the API
+/// Trade-off: After a restart, reactions on earlier progress messages do nothing
+#[derive(Debug, Default)]
+pub struct TaskMessages {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    tasks: HashMap<u64, String>,
+    order: VecDeque<u64>,
+}
+
+impl TaskMessages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn track(&self, message_id: u64, task_id: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner
+            .tasks
+            .insert(message_id, task_id.to_string())
+            .is_none()
+        {
+            inner.order.push_back(message_id);
+        }
+        while inner.order.len() > MAX_TRACKED_TASK_MESSAGES {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.tasks.remove(&oldest);
+            }
+        }
+    }
+
+    /// The task a reaction on `message_id` controls, if the bot posted it for one
+    pub fn task_for(&self, message_id: u64) -> Option<String> {
+        self.inner.lock().unwrap().tasks.get(&message_id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{AgentType, Priority, Task};
+
+    #[test]
+    fn test_emojis_map_to_actions_and_messages_to_tasks() {
+        assert_eq!(TaskAction::from_emoji("❌"), Some(TaskAction::Cancel));
+        assert_eq!(TaskAction::from_emoji("🔁"), Some(TaskAction::Retry));
+        assert_eq!(TaskAction::from_emoji("⬆️"), Some(TaskAction::Escalate));
+        assert_eq!(TaskAction::from_emoji("⬆"), Some(TaskAction::Escalate));
+        assert_eq!(TaskAction::from_emoji("🔄"), None);
+        assert_eq!(TaskAction::emojis().count(), 4);
+        assert_eq!(TaskAction::Retry.required_role(), Role::Operator);
+        assert_eq!(TaskAction::Escalate.required_role(), Role::Admin);
+
+        let messages = TaskMessages::new();
+        messages.track(1, "task-1");
+        assert_eq!(messages.task_for(1).as_deref(), Some("task-1"));
+        for message_id in 2..=(MAX_TRACKED_TASK_MESSAGES as u64 + 1) {
+            messages.track(message_id, "task-2");
+        }
+        assert_eq!(messages.task_for(1), None);
+    }
+
+    #[tokio::test]
+    async fn test_actions_follow_the_task_state() {
+        let orchestrator = AgentOrchestrator::new(Config::test_config()).await.unwrap();
+        let queued = |content: &str| {
+            Task::new(
+                AgentType::SoftwareDeveloper,
+                content.to_string(),
+                Priority::Low,
+            )
+        };
+        orchestrator.submit_task(queued("first")).await.unwrap();
+        let task_id = orchestrator.submit_task(queued("second")).await.unwrap();
+
+        assert!(matches!(
+            apply(&orchestrator, TaskAction::Retry, &task_id).await,
+            ActionOutcome::Refused(_)
+        ));
+        assert_eq!(
+            apply(&orchestrator, TaskAction::Escalate, &task_id).await,
+            ActionOutcome::Promoted { position: 1 }
+        );
+        assert_eq!(
+            apply(&orchestrator, TaskAction::Cancel, &task_id).await,
+            ActionOutcome::Cancelled {
+                was: TaskStatus::Pending
+            }
+        );
+        assert!(matches!(
+            apply(&orchestrator, TaskAction::Cancel, &task_id).await,
+            ActionOutcome::Refused(_)
+        ));
+        assert!(matches!(
+            apply(&orchestrator, TaskAction::Retry, &task_id).await,
+            ActionOutcome::Resubmitted { .. }
+        ));
+    }
+}
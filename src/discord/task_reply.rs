@@ -0,0 +1,475 @@
+//! Task results as Discord messages
+//!
+//! A finished task is answered with an embed (the agent's answer, the files it touched and
+//! how long it took) with the files it produced attached, instead of raw output cut off at
+//! the 2000 character message limit. Answers too long for one embed are paged.
+
+use crate::agents::{
+    quality_assurance::WORKSPACE_PATH_CONTEXT_KEY, task_utils::DURATION_MS_METADATA_KEY,
+};
+use crate::constants::{
+    DISCORD_BRIEF_ANSWER_LENGTH, DISCORD_EMBED_DESCRIPTION_LENGTH, DISCORD_EMBED_FIELD_LENGTH,
+    MAX_TASK_RESULT_ATTACHMENT_BYTES, MAX_TASK_RESULT_FILES,
+};
+use crate::discord::paginator::{self, PagedAnswer};
+use crate::models::{FileChangeKind, TaskExecutionResult, TaskResult};
+use flate2::{write::GzEncoder, Compression};
+use serenity::all::{
+    Colour, CreateAttachment, CreateEmbed, CreateEmbedFooter, CreateMessage,
+    EditInteractionResponse, EditMessage,
+};
+use std::path::{Component, Path};
+use std::{fs, io};
+use tracing::warn;
+
+/// A message answering a task: text, and for finished tasks an embed and attachments
+#[derive(Debug, Clone, Default)]
+pub struct TaskReply {
+    pub content: String,
+    pub embed: Option<CreateEmbed>,
+    pub attachments: Vec<CreateAttachment>,
+    /// Set when the answer spans several embed pages; the embed shows the first
+    pub pages: Option<PagedAnswer>,
+}
+
+impl From<String> for TaskReply {
+    fn from(content: String) -> Self {
+        Self {
+            content,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&str> for TaskReply {
+    fn from(content: &str) -> Self {
+        content.to_string().into()
+    }
+}
+
+impl TaskReply {
+    pub fn message(self) -> CreateMessage {
+        let mut message = CreateMessage::new().files(self.attachments);
+        if !self.content.is_empty() {
+            message = message.content(self.content);
+        }
+        if let Some(embed) = self.embed {
+            message = message.embed(embed);
+        }
+        if let Some(pages) = &self.pages {
+            message = message.components(pages.buttons(0));
+        }
+        message
+    }
+
+    pub fn edit(self) -> EditMessage {
+        let mut edit = EditMessage::new().content(self.content);
+        if let Some(embed) = self.embed {
+            edit = edit.embed(embed);
+        }
+        if let Some(pages) = &self.pages {
+            edit = edit.components(pages.buttons(0));
+        }
+        self.attachments
+            .into_iter()
+            .fold(edit, |edit, attachment| edit.new_attachment(attachment))
+    }
+
+    pub fn interaction_edit(self) -> EditInteractionResponse {
+        let mut edit = EditInteractionResponse::new().content(self.content);
+        if let Some(embed) = self.embed {
+            edit = edit.embed(embed);
+        }
+        if let Some(pages) = &self.pages {
+            edit = edit.components(pages.buttons(0));
+        }
+        self.attachments
+            .into_iter()
+            .fold(edit, |edit, attachment| edit.new_attachment(attachment))
+    }
+}
+
+/// Files attached to a task result
+#[derive(Debug, Default, PartialEq)]
+pub enum ResultFiles {
+    #[default]
+    None,
+    /// Each produced file, named after its workspace path
+    Files(Vec<(String, Vec<u8>)>),
+    /// Too many files to attach one by one, so all of them in one archive
+    Archive { name: String, bytes: Vec<u8> },
+    /// Over the size cap; the output stays in the workspace
+    TooLarge { bytes: u64 },
+}
+
+/// The result embed's footer: the persona's signature and the task
+pub fn result_footer(footer: &str, result: &TaskResult) -> String {
+    format!("{footer} · task {}", result.task_id)
+}
+
+/// 🖼️ RESULT EMBED: The agent's answer with the files touched and the time taken
+pub fn result_embed(
+    title: &str,
+    answer: &str,
+    footer: &str,
+    result: &TaskResult,
+    files: &ResultFiles,
+) -> CreateEmbed {
+    let succeeded = matches!(result.result, TaskExecutionResult::Success { .. });
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .description(truncate(answer, DISCORD_EMBED_DESCRIPTION_LENGTH))
+        .colour(if succeeded {
+            Colour::DARK_GREEN
+        } else {
+            Colour::RED
+        })
+        .footer(CreateEmbedFooter::new(result_footer(footer, result)));
+
+    let (created, modified) = changed_files(result);
+    if !created.is_empty() {
+        embed = embed.field("📄 Files created", file_list(&created), false);
+    }
+    if !modified.is_empty() {
+        embed = embed.field("✏️ Files modified", file_list(&modified), false);
+    }
+    if let Some(ms) = result
+        .metadata
+        .get(DURATION_MS_METADATA_KEY)
+        .and_then(|ms| ms.parse().ok())
+    {
+        embed = embed.field("⏱️ Duration", format_duration(ms), true);
+    }
+    match files {
+        ResultFiles::None => {}
+        ResultFiles::Files(files) => {
+            embed = embed.field("📎 Attached", format!("{} files", files.len()), true);
+        }
+        ResultFiles::Archive { name, .. } => {
+            embed = embed.field("📎 Attached", format!("Workspace as `{name}`"), true);
+        }
+        ResultFiles::TooLarge { bytes } => {
+            embed = embed.field(
+                "📎 Not attached",
+                format!(
+                    "{:.1} MiB of output is over the upload limit; it stays in the workspace",
+                    *bytes as f64 / (1024.0 * 1024.0)
+                ),
+                true,
+            );
+        }
+    }
+    embed
+}
+
+impl ResultFiles {
+    pub fn into_attachments(self) -> Vec<CreateAttachment> {
+        match self {
+            Self::Files(files) => files
+                .into_iter()
+                .map(|(name, bytes)| CreateAttachment::bytes(bytes, name))
+                .collect(),
+            Self::Archive { name, bytes } => vec![CreateAttachment::bytes(bytes, name)],
+            Self::None | Self::TooLarge { .. } => Vec::new(),
+        }
+    }
+}
+
+/// Read the files a task produced from its workspace, off the async runtime
+/// Failures are logged and leave the result without attachments
+pub async fn load_result_files(result: &TaskResult) -> ResultFiles {
+    let Some(workspace) = result.metadata.get(WORKSPACE_PATH_CONTEXT_KEY).cloned() else {
+        return ResultFiles::None;
+    };
+    let (created, modified) = changed_files(result);
+    let paths: Vec<String> = created.into_iter().chain(modified).collect();
+    let archive_name = format!("{}-workspace.tar.gz", result.task_id);
+
+    let gathered = tokio::task::spawn_blocking(move || {
+        gather_result_files(
+            Path::new(&workspace),
+            &paths,
+            &archive_name,
+            MAX_TASK_RESULT_FILES,
+            MAX_TASK_RESULT_ATTACHMENT_BYTES,
+        )
+    })
+    .await;
+    match gathered {
+        Ok(Ok(files)) => files,
+        Ok(Err(e)) => {
+            warn!("Failed to attach files of task {}: {}", result.task_id, e);
+            ResultFiles::None
+        }
+        Err(e) => {
+            warn!(
+                "Attachment reader for task {} failed: {}",
+                result.task_id, e
+            );
+            ResultFiles::None
+        }
+    }
+}
+
+/// 📎 ATTACHMENT CHOICE: The produced files one by one when there are few enough, else
+/// together as one archive, and nothing when either is over `max_bytes`
+/// 🛡️ SECURITY: Paths are workspace-relative; anything resolving outside the workspace,
+/// by `..` or through a symlink, is skipped
+pub fn gather_result_files(
+    workspace: &Path,
+    paths: &[String],
+    archive_name: &str,
+    max_files: usize,
+    max_bytes: u64,
+) -> io::Result<ResultFiles> {
+    let root = workspace.canonicalize()?;
+    let files: Vec<(&str, u64)> = paths
+        .iter()
+        .filter(|path| {
+            Path::new(path)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        })
+        .filter_map(|path| {
+            let full = root.join(path);
+            let metadata = fs::symlink_metadata(&full).ok()?;
+            let inside = full.canonicalize().ok()?.starts_with(&root);
+            (inside && metadata.is_file()).then_some((path.as_str(), metadata.len()))
+        })
+        .collect();
+    if files.is_empty() {
+        return Ok(ResultFiles::None);
+    }
+
+    let total_bytes: u64 = files.iter().map(|(_, bytes)| bytes).sum();
+    if files.len() <= max_files && total_bytes <= max_bytes {
+        let files = files
+            .into_iter()
+            .map(|(path, _)| Ok((path.replace('/', "_"), fs::read(root.join(path))?)))
+            .collect::<io::Result<_>>()?;
+        return Ok(ResultFiles::Files(files));
+    }
+
+    // ⚡ PERFORMANCE: Only the produced files, compressed into a buffer that refuses to
+    // grow past the cap; the workspace may hold build output and history worth gigabytes
+    let archive = || -> io::Result<Vec<u8>> {
+        let capped = CappedBuffer {
+            bytes: Vec::new(),
+            max_bytes,
+        };
+        let mut builder = tar::Builder::new(GzEncoder::new(capped, Compression::default()));
+        builder.follow_symlinks(false);
+        for (path, _) in &files {
+            builder.append_path_with_name(root.join(path), Path::new("workspace").join(path))?;
+        }
+        Ok(builder.into_inner()?.finish()?.bytes)
+    };
+    match archive() {
+        Ok(bytes) => Ok(ResultFiles::Archive {
+            name: archive_name.to_string(),
+            bytes,
+        }),
+        Err(e) if e.kind() == io::ErrorKind::FileTooLarge => {
+            Ok(ResultFiles::TooLarge { bytes: total_bytes })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// In-memory output that fails with FileTooLarge instead of growing past `max_bytes`
+struct CappedBuffer {
+    bytes: Vec<u8>,
+    max_bytes: u64,
+}
+
+impl io::Write for CappedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if (self.bytes.len() + buf.len()) as u64 > self.max_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::FileTooLarge,
+                format!("archive is over {} bytes", self.max_bytes),
+            ));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Workspace paths the task created and modified, from its recorded changes when it has them
+fn changed_files(result: &TaskResult) -> (Vec<String>, Vec<String>) {
+    if !result.changes.is_empty() {
+        let with_kind = |kind| {
+            result
+                .changes
+                .iter()
+                .filter(|change| change.kind == kind)
+                .map(|change| change.path.clone())
+                .collect()
+        };
+        return (
+            with_kind(FileChangeKind::Created),
+            with_kind(FileChangeKind::Modified),
+        );
+    }
+    match &result.result {
+        TaskExecutionResult::Success {
+            files_created,
+            files_modified,
+            ..
+        } => (files_created.clone(), files_modified.clone()),
+        TaskExecutionResult::Failure { .. } => (Vec::new(), Vec::new()),
+    }
+}
+
+/// One path per line, cut off with a count once the field is full
+fn file_list(paths: &[String]) -> String {
+    let mut list = String::new();
+    for (shown, path) in paths.iter().enumerate() {
+        let line = format!("`{path}`\n");
+        let more = format!("…and {} more", paths.len() - shown);
+        if list.chars().count() + line.chars().count() + more.chars().count()
+            > DISCORD_EMBED_FIELD_LENGTH
+        {
+            list.push_str(&more);
+            return list;
+        }
+        list.push_str(&line);
+    }
+    list
+}
+
+/// ✂️ BRIEF ANSWER: The start of an answer, for users who prefer brief ones, with a note
+/// when the rest is left out
+pub fn brief_answer(answer: &str) -> String {
+    let mut pages = paginator::split_pages(answer, DISCORD_BRIEF_ANSWER_LENGTH, usize::MAX);
+    if pages.len() <= 1 {
+        return answer.to_string();
+    }
+    let mut brief = pages.swap_remove(0);
+    brief.push_str("\n\n*…shortened; `!spiral prefs verbosity normal` shows whole answers*");
+    brief
+}
+
+/// 🔎 RESULT DETAILS: What the agent recorded about the run, for users who prefer detailed
+/// answers; None when it recorded nothing beyond the duration
+pub fn result_details(result: &TaskResult) -> Option<String> {
+    let mut details: Vec<_> = result
+        .metadata
+        .iter()
+        .filter(|(key, _)| key.as_str() != DURATION_MS_METADATA_KEY)
+        .map(|(key, value)| format!("**{key}:** {value}"))
+        .collect();
+    if details.is_empty() {
+        return None;
+    }
+    details.sort();
+    Some(truncate(&details.join("\n"), DISCORD_EMBED_FIELD_LENGTH))
+}
+
+pub(crate) fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        0..=59 => format!("{:.1}s", ms as f64 / 1000.0),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_durations_and_long_file_lists() {
+        assert_eq!(format_duration(4200), "4.2s");
+        assert_eq!(format_duration(125_000), "2m 05s");
+        assert_eq!(format_duration(3_720_000), "1h 02m");
+
+        let paths: Vec<String> = (0..200).map(|i| format!("src/module_{i}.rs")).collect();
+        let list = file_list(&paths);
+        assert!(list.chars().count() <= DISCORD_EMBED_FIELD_LENGTH);
+        assert!(list.starts_with("`src/module_0.rs`\n"));
+        assert!(list.contains("more"));
+        assert_eq!(
+            file_list(&paths[..2]),
+            "`src/module_0.rs`\n`src/module_1.rs`\n"
+        );
+    }
+
+    #[test]
+    fn test_brief_answer_keeps_the_start() {
+        assert_eq!(brief_answer("short\n"), "short\n");
+        let long = "line of an answer\n".repeat(100);
+        let brief = brief_answer(&long);
+        assert!(brief.starts_with("line of an answer\nline"));
+        assert!(brief.chars().count() < DISCORD_BRIEF_ANSWER_LENGTH + 100);
+        assert!(brief.ends_with("shows whole answers*"));
+    }
+
+    #[test]
+    fn test_attaches_files_or_workspace_archive_within_cap() {
+        let workspace = tempfile::tempdir().unwrap();
+        fs::create_dir_all(workspace.path().join("src")).unwrap();
+        fs::write(workspace.path().join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(workspace.path().join("README.md"), "# demo").unwrap();
+        let paths = vec![
+            "src/main.rs".to_string(),
+            "README.md".to_string(),
+            "../outside.txt".to_string(),
+            "missing.rs".to_string(),
+        ];
+
+        let files =
+            gather_result_files(workspace.path(), &paths, "t-workspace.tar.gz", 10, 1024).unwrap();
+        assert_eq!(
+            files,
+            ResultFiles::Files(vec![
+                ("src_main.rs".to_string(), b"fn main() {}".to_vec()),
+                ("README.md".to_string(), b"# demo".to_vec()),
+            ])
+        );
+
+        // More files than may be attached one by one: the produced files go as an archive,
+        // leaving out whatever else the workspace holds
+        fs::create_dir_all(workspace.path().join("target")).unwrap();
+        fs::write(workspace.path().join("target/app"), vec![7u8; 64 * 1024]).unwrap();
+        let archived =
+            gather_result_files(workspace.path(), &paths, "t-workspace.tar.gz", 1, 1024).unwrap();
+        let ResultFiles::Archive { name, bytes } = archived else {
+            panic!("Expected an archive, got {archived:?}");
+        };
+        assert_eq!(name, "t-workspace.tar.gz");
+        let mut entries: Vec<String> =
+            tar::Archive::new(flate2::read::GzDecoder::new(bytes.as_slice()))
+                .entries()
+                .unwrap()
+                .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+                .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec!["workspace/README.md", "workspace/src/main.rs"]
+        );
+
+        let too_large =
+            gather_result_files(workspace.path(), &paths, "t-workspace.tar.gz", 1, 10).unwrap();
+        assert!(matches!(too_large, ResultFiles::TooLarge { .. }));
+        assert_eq!(
+            gather_result_files(workspace.path(), &[], "t.tar.gz", 10, 1024).unwrap(),
+            ResultFiles::None
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Threads the bot opened for tasks
+//!
+//! A task started from a channel message gets its own thread; messages posted there later
+//! continue that task's conversation with the same agent, without mentioning it again.
+
+use crate::constants::{DISCORD_THREAD_NAME_LENGTH, MAX_TRACKED_TASK_THREADS};
+use crate::models::AgentType;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// 🧵 TASK THREADS: Which agent each bot-opened task thread belongs to
+/// 🏗️ ARCHITECTURE DECISION: In-memory map, oldest threads forgotten first
+/// Why: Follow-ups only need the agent; the conversation itself lives in the Claude
+/// session keyed by the thread ID, which survives restarts on disk
+/// Alternative: Persist the map (rejected: after a restart a mention in the thread
+/// resumes the same session anyway)
+/// Trade-off: After a restart, or once a thread is forgotten, follow-ups in it need a
+/// mention again
+#[derive(Debug, Default)]
+pub struct TaskThreads {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    agents: HashMap<u64, AgentType>,
+    order: VecDeque<u64>,
+}
+
+impl TaskThreads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, thread_id: u64, agent_type: AgentType) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.agents.insert(thread_id, agent_type).is_none() {
+            inner.order.push_back(thread_id);
+        }
+        while inner.order.len() > MAX_TRACKED_TASK_THREADS {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.agents.remove(&oldest);
+            }
+        }
+    }
+
+    /// The agent a message posted in `channel_id` continues, if it is a task thread
+    pub fn agent_for(&self, channel_id: u64) -> Option<AgentType> {
+        self.inner.lock().unwrap().agents.get(&channel_id).cloned()
+    }
+}
+
+/// Thread title for a task: the persona and the start of the request, within Discord's limit
+pub fn thread_name(persona_name: &str, request: &str) -> String {
+    let request = request.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = format!("{persona_name}: {request}");
+    if name.chars().count() <= DISCORD_THREAD_NAME_LENGTH {
+        return name;
+    }
+    let mut name: String = name.chars().take(DISCORD_THREAD_NAME_LENGTH - 3).collect();
+    name.push_str("...");
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_threads_continue_with_their_agent() {
+        let threads = TaskThreads::new();
+        threads.register(1, AgentType::ProjectManager);
+        assert_eq!(threads.agent_for(1), Some(AgentType::ProjectManager));
+        assert_eq!(threads.agent_for(2), None);
+
+        for thread_id in 2..=(MAX_TRACKED_TASK_THREADS as u64 + 1) {
+            threads.register(thread_id, AgentType::SoftwareDeveloper);
+        }
+        // The oldest thread is forgotten once the limit is passed
+        assert_eq!(threads.agent_for(1), None);
+        assert_eq!(
+            threads.agent_for(MAX_TRACKED_TASK_THREADS as u64 + 1),
+            Some(AgentType::SoftwareDeveloper)
+        );
+    }
+
+    #[test]
+    fn test_thread_name_fits_discord_limit() {
+        assert_eq!(
+            thread_name("SpiralDev", "add a\nhealth   check"),
+            "SpiralDev: add a health check"
+        );
+        let long = thread_name("SpiralDev", &"é".repeat(200));
+        assert_eq!(long.chars().count(), DISCORD_THREAD_NAME_LENGTH);
+        assert!(long.ends_with("..."));
+    }
+}
@@ -351,4 +351,18 @@ mod secure_message_handler_tests {
         let reset_metrics = handler.get_security_metrics();
         assert_eq!(reset_metrics.messages_processed, 0);
     }
+
+    #[test]
+    fn test_clarification_metrics() {
+        let handler = SecureMessageHandler::new();
+        handler.record_clarification_requested();
+        handler.record_clarification_requested();
+        handler.record_clarification_answered(true);
+        handler.record_clarification_answered(false);
+
+        let metrics = handler.get_security_metrics();
+        assert_eq!(metrics.clarifications_requested, 2);
+        assert_eq!(metrics.clarifications_confirmed, 1);
+        assert_eq!(metrics.clarifications_cancelled, 1);
+    }
 }
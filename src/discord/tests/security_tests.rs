@@ -279,7 +279,7 @@ mod integration_tests {
         std::env::set_current_dir(&temp_dir).unwrap();
 
         // Phase 1: Generation
-        let result = ensure_api_key_exists(None);
+        let result = ensure_api_key_exists(None, None);
         assert!(result.is_ok(), "Failed to generate key: {result:?}");
         let key1 = result.unwrap();
 
@@ -294,7 +294,7 @@ mod integration_tests {
         );
 
         // Phase 3: Loading
-        let result2 = ensure_api_key_exists(None);
+        let result2 = ensure_api_key_exists(None, None);
         assert!(result2.is_ok(), "Failed to load key: {result2:?}");
         let key2 = result2.unwrap();
 
@@ -303,7 +303,7 @@ mod integration_tests {
 
         // Phase 4: Environment override
         let env_key = generate_secure_api_key();
-        let result3 = ensure_api_key_exists(Some(&env_key));
+        let result3 = ensure_api_key_exists(Some(&env_key), None);
         assert!(result3.is_ok());
         assert_eq!(result3.unwrap(), env_key, "Environment key not respected");
     }
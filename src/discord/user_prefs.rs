@@ -0,0 +1,180 @@
+//! Per-user preferences
+//!
+//! Guild settings apply to everyone in a server. With `!spiral prefs` each user can set
+//! their own defaults on top: the agent their requests go to, how long answers are, the
+//! language of replies and whether tasks get a thread. They follow the user into every
+//! server and DM, and are kept in a JSON file so they survive restarts.
+
+use super::locale::Locale;
+use crate::models::AgentType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+/// How much of a task's answer is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    /// The start of the answer only
+    Brief,
+    #[default]
+    Normal,
+    /// The whole answer and the run's details
+    Detailed,
+}
+
+impl FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "brief" => Ok(Verbosity::Brief),
+            "normal" => Ok(Verbosity::Normal),
+            "detailed" => Ok(Verbosity::Detailed),
+            _ => Err(format!(
+                "Unknown verbosity `{value}`; use brief, normal or detailed."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verbosity::Brief => "brief",
+            Verbosity::Normal => "normal",
+            Verbosity::Detailed => "detailed",
+        })
+    }
+}
+
+/// One user's defaults; None keeps the server's or the bot's
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserPrefs {
+    /// Agent for the user's requests that name none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_agent: Option<AgentType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+    /// Language the bot replies to the user in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+    /// Whether the user's tasks get their own thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_threads: Option<bool>,
+}
+
+impl UserPrefs {
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity.unwrap_or_default()
+    }
+}
+
+/// 🙋 USER PREFS STORE: Each user's preferences, read on every message
+/// 🏗️ ARCHITECTURE DECISION: Same write-through JSON map as the guild settings store
+/// Why: Reads happen per message and must not touch the disk; users change them rarely
+/// Alternative: One store for guilds and users (rejected: the IDs share one number space
+/// only by accident, and the records differ)
+#[derive(Debug, Default)]
+pub struct UserPrefsStore {
+    prefs: RwLock<HashMap<u64, UserPrefs>>,
+    /// None keeps preferences in memory only
+    path: Option<PathBuf>,
+}
+
+impl UserPrefsStore {
+    /// Open the store, restoring preferences saved at `path`
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let prefs = path.as_deref().map(Self::load).unwrap_or_default();
+        if !prefs.is_empty() {
+            info!("Restored preferences of {} user(s)", prefs.len());
+        }
+        Self {
+            prefs: RwLock::new(prefs),
+            path,
+        }
+    }
+
+    fn load(path: &Path) -> HashMap<u64, UserPrefs> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+            Err(e) => {
+                warn!("Failed to read user preferences {:?}: {}", path, e);
+                return HashMap::new();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring corrupt user preferences {:?}: {}", path, e);
+            HashMap::new()
+        })
+    }
+
+    /// The user's preferences; defaults when they have none
+    pub fn get(&self, user_id: u64) -> UserPrefs {
+        self.prefs
+            .read()
+            .unwrap()
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Change the user's preferences and save them; returns the new preferences
+    pub fn update(&self, user_id: u64, change: impl FnOnce(&mut UserPrefs)) -> UserPrefs {
+        let mut all = self.prefs.write().unwrap();
+        let mut prefs = all.get(&user_id).cloned().unwrap_or_default();
+        change(&mut prefs);
+        if prefs == UserPrefs::default() {
+            all.remove(&user_id);
+        } else {
+            all.insert(user_id, prefs.clone());
+        }
+        self.persist(&all);
+        prefs
+    }
+
+    fn persist(&self, all: &HashMap<u64, UserPrefs>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(all)
+            .map_err(std::io::Error::other)
+            .and_then(|serialized| std::fs::write(path, serialized));
+        if let Err(e) = result {
+            warn!("Failed to save user preferences to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_persists_prefs_and_drops_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prefs.json");
+        let store = UserPrefsStore::open(Some(path.clone()));
+        store.update(7, |prefs| {
+            prefs.preferred_agent = Some(AgentType::ProjectManager);
+            prefs.verbosity = Some(Verbosity::Brief);
+            prefs.task_threads = Some(false);
+        });
+
+        let reopened = UserPrefsStore::open(Some(path.clone()));
+        let prefs = reopened.get(7);
+        assert_eq!(prefs.preferred_agent, Some(AgentType::ProjectManager));
+        assert_eq!(prefs.verbosity(), Verbosity::Brief);
+        assert_eq!(prefs.task_threads, Some(false));
+        assert_eq!(reopened.get(8).verbosity(), Verbosity::Normal);
+
+        reopened.update(7, |prefs| *prefs = UserPrefs::default());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "{}");
+        assert_eq!("DETAILED".parse(), Ok(Verbosity::Detailed));
+    }
+}
@@ -0,0 +1,254 @@
+//! Per-user sessions across every Discord interaction, and private DM sessions
+//!
+//! Each user's task messages, in servers and DMs alike, open or extend one session in the
+//! shared SessionManager. It remembers the agent they last worked with and the workspace
+//! they chose with `!spiral session workspace`, and both shape the tasks they start. Its
+//! recent requests and answers are given to later tasks until `!spiral session history
+//! clear` forgets them. Tasks
+//! outside a task thread otherwise share the session's own workspace, which the
+//! orchestrator archives once the session ends.
+//!
+//! A direct message to the bot continues the sender's session privately: tasks there share
+//! a Claude conversation and the last agent used, and results are answered in the DM.
+//! Without a guild there are no moderators, channel settings or role grants, so DMs get a
+//! tighter rate limit and a stricter intent check.
+
+use crate::constants::DM_SESSION_IDLE_HOURS;
+use crate::discord::{IntentResponse, IntentType, RiskLevel};
+use crate::models::AgentType;
+use crate::session::{
+    Session, SessionBudget, SessionConfig, SessionManager, SessionPolicies, SessionStoreBackend,
+    SharedSessionManager, AGENT_METADATA_KEY, WORKSPACE_METADATA_KEY,
+};
+use crate::Result;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// 🗂️ USER SESSIONS: One session per Discord user, through the shared SessionManager
+/// 🏗️ ARCHITECTURE DECISION: Sessions in the configured store, one active per user, with
+/// the current session IDs cached here
+/// Why: The Claude conversation itself survives restarts in the session workspace; with a
+/// SQLite or Redis store the session deciding whether a DM continues it does too
+/// Alternative: Key the conversation by user ID (rejected: it would never start afresh)
+/// Trade-off: Server messages keep a user's DM conversation alive as well; with the memory
+/// store a DM after a restart starts a new conversation
+pub struct UserSessions {
+    manager: SharedSessionManager,
+    sessions: Mutex<HashMap<u64, Uuid>>,
+}
+
+impl Default for UserSessions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserSessions {
+    /// Sessions kept in process memory only
+    pub fn new() -> Self {
+        Self::with_store(
+            SessionStoreBackend::Memory,
+            SessionBudget::default(),
+            SessionPolicies::default(),
+        )
+    }
+
+    /// Sessions kept in `store`, each starting with `budget` and bound by `policies`; a
+    /// store that can't be opened degrades to process memory
+    pub fn with_store(
+        store: SessionStoreBackend,
+        budget: SessionBudget,
+        policies: SessionPolicies,
+    ) -> Self {
+        let idle = chrono::Duration::hours(DM_SESSION_IDLE_HOURS);
+        let config = SessionConfig {
+            max_duration: idle,
+            max_concurrent: 1,
+            auto_extend: true,
+            // Each message pushes expiry to a full idle period from now
+            extend_duration: idle,
+            store,
+            budget,
+            policies,
+        };
+        Self {
+            manager: Arc::new(SessionManager::from_config_or_memory(
+                config,
+                "Discord user sessions",
+            )),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The manager behind the sessions, for the orchestrator to tell which have ended
+    pub fn manager(&self) -> SharedSessionManager {
+        self.manager.clone()
+    }
+
+    /// The user's active session, kept alive by this message; a new one once it has lapsed
+    pub async fn session_for(&self, user_id: u64) -> Result<Session> {
+        let mut sessions = self.sessions.lock().await;
+        if let Entry::Vacant(uncached) = sessions.entry(user_id) {
+            // The session may predate a restart or belong to another instance
+            if let Some(stored) = self.manager.active_session(&user_id.to_string()).await? {
+                uncached.insert(stored.id);
+            }
+        }
+        if let Some(current) = sessions.get(&user_id) {
+            match self.manager.validate_session(current).await {
+                Ok(session) => return Ok(session),
+                Err(e) => debug!("Session of user {} ended: {}", user_id, e),
+            }
+        }
+
+        if let Err(e) = self.manager.cleanup().await {
+            warn!("Failed to clean up expired user sessions: {}", e);
+        }
+        let session = self.manager.create_session(user_id.to_string()).await?;
+        info!("Started session {} for user {}", session.id, user_id);
+        sessions.insert(user_id, session.id);
+        Ok(session)
+    }
+
+    /// The user's active session without extending it, if they have one
+    pub async fn current(&self, user_id: u64) -> Result<Option<Session>> {
+        self.manager.active_session(&user_id.to_string()).await
+    }
+
+    /// Remember the agent the user's session worked with last
+    pub async fn set_agent(&self, session: &Session, agent_type: &AgentType) {
+        if let Err(e) = self
+            .manager
+            .set_metadata(
+                &session.id,
+                AGENT_METADATA_KEY,
+                Some(format!("{agent_type:?}")),
+            )
+            .await
+        {
+            warn!("Failed to save the agent of session {}: {}", session.id, e);
+        }
+    }
+
+    /// Choose the workspace the user's tasks share; None gives each task its own again
+    pub async fn set_workspace(&self, user_id: u64, workspace: Option<String>) -> Result<Session> {
+        let session = self.session_for(user_id).await?;
+        self.manager
+            .set_metadata(&session.id, WORKSPACE_METADATA_KEY, workspace)
+            .await
+    }
+
+    /// Forget the requests and answers of the user's session, returning how many turns it
+    /// held; None when they have no session
+    pub async fn clear_history(&self, user_id: u64) -> Result<Option<usize>> {
+        let Some(session) = self.current(user_id).await? else {
+            return Ok(None);
+        };
+        self.manager.clear_history(&session.id).await.map(Some)
+    }
+
+    /// End the user's session; their next message starts a fresh one. False when they had none
+    pub async fn end(&self, user_id: u64) -> Result<bool> {
+        let mut sessions = self.sessions.lock().await;
+        sessions.remove(&user_id);
+        let Some(session) = self.current(user_id).await? else {
+            return Ok(false);
+        };
+        self.manager.terminate_session(&session.id).await?;
+        info!("Ended session {} of user {}", session.id, user_id);
+        Ok(true)
+    }
+}
+
+/// 🛡️ SECURITY DECISION: Why a DM is refused as a task, if it is
+/// In a server, risky requests happen where moderators can see them; in a DM nobody does,
+/// so system and admin intents, high risk and unclassified messages are all refused
+pub fn intent_refusal(intent: Option<&IntentResponse>) -> Option<&'static str> {
+    let Some(intent) = intent else {
+        return Some("the request could not be classified");
+    };
+    match intent.intent_type {
+        IntentType::Malicious => Some("the request looks malicious"),
+        IntentType::SystemCommand | IntentType::AdminAction => {
+            Some("system and admin requests must be made in a server")
+        }
+        _ if intent.risk_level >= RiskLevel::High => Some("the request is high risk"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_session_continues_per_user() {
+        let sessions = UserSessions::new();
+        let first = sessions.session_for(1).await.unwrap();
+        let again = sessions.session_for(1).await.unwrap();
+        let other = sessions.session_for(2).await.unwrap();
+        assert_eq!(first.id, again.id);
+        assert_ne!(first.id, other.id);
+        assert_eq!(first.workspace_key(), first.own_workspace_key());
+
+        sessions.set_agent(&first, &AgentType::ProjectManager).await;
+        let session = sessions.session_for(1).await.unwrap();
+        assert_eq!(session.agent(), Some(AgentType::ProjectManager));
+        assert_eq!(sessions.session_for(2).await.unwrap().agent(), None);
+
+        let session = sessions
+            .set_workspace(1, Some("feature-x".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(session.workspace_key(), "feature-x");
+        assert!(sessions.end(1).await.unwrap());
+        assert!(!sessions.end(1).await.unwrap());
+        let fresh = sessions.session_for(1).await.unwrap();
+        assert_ne!(fresh.id, first.id);
+        assert_eq!(fresh.workspace(), None);
+    }
+
+    #[tokio::test]
+    async fn test_session_and_agent_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStoreBackend::Sqlite {
+            path: dir.path().join("sessions.db").display().to_string(),
+        };
+        let first = UserSessions::with_store(
+            store.clone(),
+            SessionBudget::default(),
+            SessionPolicies::default(),
+        );
+        let session = first.session_for(1).await.unwrap();
+        first
+            .set_agent(&session, &AgentType::QualityAssurance)
+            .await;
+
+        let restarted =
+            UserSessions::with_store(store, SessionBudget::default(), SessionPolicies::default());
+        let restored = restarted.session_for(1).await.unwrap();
+        assert_eq!(restored.id, session.id);
+        assert_eq!(restored.agent(), Some(AgentType::QualityAssurance));
+    }
+
+    #[test]
+    fn test_intent_refusal_is_stricter_than_servers() {
+        let intent = |intent_type, risk_level| IntentResponse {
+            intent_type,
+            confidence: 0.7,
+            parameters: HashMap::new(),
+            risk_level,
+        };
+        assert_eq!(
+            intent_refusal(Some(&intent(IntentType::CodeGeneration, RiskLevel::Medium))),
+            None
+        );
+        assert!(intent_refusal(Some(&intent(IntentType::AdminAction, RiskLevel::Low))).is_some());
+        assert!(intent_refusal(Some(&intent(IntentType::ChatResponse, RiskLevel::High))).is_some());
+        assert!(intent_refusal(None).is_some());
+    }
+}
@@ -0,0 +1,398 @@
+//! 🩺 DOCTOR: The startup validations as a report anyone can run (`spiral-core doctor`)
+//!
+//! Every check runs, even after one fails, so a single run lists everything to fix.
+//! Nothing is started and nothing is written, apart from a probe file in the workspace
+//! directory that is removed at once; in particular no API key file is generated.
+
+use crate::claude_code::{sandbox::Sandbox, ClaudeCodeClient, CliCompatibility};
+use crate::config::Config;
+use crate::constants::DOCTOR_CHECK_TIMEOUT_SECS;
+use crate::security;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but worth a look
+    Warn,
+    Fail,
+    /// Not applicable to this configuration
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Whether the server would start: no check failed
+    pub fn passed(&self) -> bool {
+        self.count(CheckStatus::Fail) == 0
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .checks
+            .iter()
+            .map(|check| check.name.len())
+            .max()
+            .unwrap_or(0);
+        for check in &self.checks {
+            writeln!(
+                f,
+                "{}  {:width$}  {}",
+                check.status, check.name, check.detail
+            )?;
+        }
+        write!(
+            f,
+            "\n{} passed, {} warning(s), {} failed, {} skipped",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip)
+        )
+    }
+}
+
+/// Check `loaded`, the configuration as the server would load it, and the environment it
+/// describes; `config_file` is the configuration file it was read from, if any
+pub async fn run(loaded: crate::Result<Config>, config_file: Option<&Path>) -> DoctorReport {
+    let source = config_file
+        .map(|path| format!("loaded with {}", path.display()))
+        .unwrap_or_else(|| "loaded from the environment".to_string());
+    let config = match loaded {
+        Ok(config) => config,
+        Err(e) => {
+            return DoctorReport {
+                checks: vec![Check::new(
+                    "Configuration",
+                    CheckStatus::Fail,
+                    e.to_string(),
+                )],
+            }
+        }
+    };
+
+    let (claude, discord, port) = tokio::join!(
+        check_claude_cli(&config),
+        check_discord_token(&config.discord.token),
+        check_port(&config.api.host, config.api.port),
+    );
+    DoctorReport {
+        checks: vec![
+            Check::new(
+                "Configuration",
+                CheckStatus::Pass,
+                match config.profile {
+                    Some(profile) => format!("{source}, {profile} profile"),
+                    None => format!("{source}, no profile"),
+                },
+            ),
+            claude,
+            check_workspace(&workspace_dir(&config)),
+            discord,
+            check_api_key(&config),
+            port,
+        ],
+    }
+}
+
+/// Where Claude Code workspaces are created: the configured directory, else
+/// ./claude-workspaces
+pub fn workspace_dir(config: &Config) -> PathBuf {
+    config
+        .claude_code
+        .working_directory
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            std::env::current_dir()
+                .unwrap_or_else(|_| PathBuf::from("."))
+                .join("claude-workspaces")
+        })
+}
+
+async fn check_claude_cli(config: &Config) -> Check {
+    const NAME: &str = "Claude Code CLI";
+    let sandbox = Sandbox::new(config.claude_code.sandbox.clone());
+    if let Err(e) = sandbox.verify().await {
+        return Check::new(NAME, CheckStatus::Fail, e.to_string());
+    }
+    let binary = match &config.claude_code.claude_binary_path {
+        Some(path) => path.clone(),
+        None if sandbox.is_enabled() => config.claude_code.sandbox.binary.clone(),
+        None => match ClaudeCodeClient::find_claude_binary().await {
+            Ok(path) => path,
+            Err(e) => return Check::new(NAME, CheckStatus::Fail, e.to_string()),
+        },
+    };
+    // Checked even when CLAUDE_VERIFY_CLI_VERSION turns the startup check off
+    match CliCompatibility::detect(sandbox.probe_command(&binary)).await {
+        Ok(cli) => {
+            let version = cli
+                .version()
+                .map(|version| version.to_string())
+                .unwrap_or_default();
+            let missing = cli.unavailable_features();
+            if missing.is_empty() {
+                Check::new(NAME, CheckStatus::Pass, format!("{binary} {version}"))
+            } else {
+                Check::new(
+                    NAME,
+                    CheckStatus::Warn,
+                    format!("{binary} {version} is too old for {missing:?}"),
+                )
+            }
+        }
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// Writable when it exists, else creatable under its nearest existing parent
+fn check_workspace(dir: &Path) -> Check {
+    const NAME: &str = "Workspace";
+    let existing = dir.ancestors().find(|ancestor| ancestor.exists());
+    let Some(existing) = existing else {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} has no existing parent", dir.display()),
+        );
+    };
+    let probe = existing.join(format!(".spiral-doctor-{}", std::process::id()));
+    let written = std::fs::write(&probe, b"").and_then(|()| std::fs::remove_file(&probe));
+    match (written, existing == dir) {
+        (Ok(()), true) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} is writable", dir.display()),
+        ),
+        (Ok(()), false) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} will be created", dir.display()),
+        ),
+        (Err(e), _) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", existing.display()),
+        ),
+    }
+}
+
+/// Ask Discord for the bot's gateway, which only a valid bot token is given
+async fn check_discord_token(token: &str) -> Check {
+    const NAME: &str = "Discord token";
+    if token.is_empty() {
+        return Check::new(
+            NAME,
+            CheckStatus::Skip,
+            "not set; Discord integration is off",
+        );
+    }
+    if token == "your-discord-token" {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            "still the .env.example placeholder",
+        );
+    }
+    let http = serenity::http::Http::new(token);
+    match tokio::time::timeout(
+        Duration::from_secs(DOCTOR_CHECK_TIMEOUT_SECS),
+        http.get_bot_gateway(),
+    )
+    .await
+    {
+        Ok(Ok(gateway)) => {
+            let limit = gateway.session_start_limit;
+            let status = if limit.remaining == 0 {
+                CheckStatus::Warn
+            } else {
+                CheckStatus::Pass
+            };
+            Check::new(
+                NAME,
+                status,
+                format!(
+                    "accepted by the gateway; {} of {} session starts left",
+                    limit.remaining, limit.total
+                ),
+            )
+        }
+        Ok(Err(serenity::Error::Http(serenity::http::HttpError::UnsuccessfulRequest(
+            response,
+        )))) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "rejected by Discord ({}): {}",
+                response.status_code, response.error.message
+            ),
+        ),
+        Ok(Err(e)) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("could not reach Discord: {e}"),
+        ),
+        Err(_) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("Discord did not answer within {DOCTOR_CHECK_TIMEOUT_SECS}s"),
+        ),
+    }
+}
+
+fn check_api_key(config: &Config) -> Check {
+    const NAME: &str = "API key";
+    if let Some(key) = config
+        .api
+        .api_key
+        .as_deref()
+        .filter(|key| !key.trim().is_empty())
+    {
+        return api_key_strength(key, "API_KEY");
+    }
+    match security::read_api_key_file(config.api.key_file_passphrase.as_deref()) {
+        Ok(Some(key)) => api_key_strength(&key, security::API_KEY_FILE),
+        Ok(None) => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "not set; a key is generated into {} on first start",
+                security::API_KEY_FILE
+            ),
+        ),
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// Long enough and not built from a handful of characters; a generated key always passes
+fn api_key_strength(key: &str, source: &str) -> Check {
+    const NAME: &str = "API key";
+    let distinct = key.chars().collect::<std::collections::HashSet<_>>().len();
+    if key.len() < 32 {
+        Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "{source} has {} characters; at least 32 are required",
+                key.len()
+            ),
+        )
+    } else if distinct < 10 {
+        Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{source} is made of only {distinct} different characters"),
+        )
+    } else if key.len() < security::API_KEY_LENGTH {
+        Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "{source} has {} characters; generated keys have {}",
+                key.len(),
+                security::API_KEY_LENGTH
+            ),
+        )
+    } else {
+        Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{source}, {} characters", key.len()),
+        )
+    }
+}
+
+async fn check_port(host: &str, port: u16) -> Check {
+    const NAME: &str = "API port";
+    match tokio::net::TcpListener::bind((host, port)).await {
+        Ok(_) => Check::new(NAME, CheckStatus::Pass, format!("{host}:{port} is free")),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("cannot listen on {host}:{port}: {e}"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_report_lists_each_failure() {
+        let taken = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let dir = tempfile::tempdir().unwrap();
+
+        let report = DoctorReport {
+            checks: vec![
+                check_workspace(&dir.path().join("workspaces")),
+                api_key_strength(&security::generate_secure_api_key(), "API_KEY"),
+                api_key_strength(&"ab".repeat(20), "API_KEY"),
+                check_port("127.0.0.1", port).await,
+                check_discord_token("").await,
+            ],
+        };
+        let statuses: Vec<CheckStatus> = report.checks.iter().map(|check| check.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                CheckStatus::Pass,
+                CheckStatus::Pass,
+                CheckStatus::Fail,
+                CheckStatus::Fail,
+                CheckStatus::Skip
+            ]
+        );
+        assert!(!report.passed());
+        assert!(report
+            .to_string()
+            .ends_with("2 passed, 0 warning(s), 2 failed, 1 skipped"));
+    }
+}
@@ -49,6 +49,9 @@ pub enum SpiralError {
     #[error("System resource error: {message}")]
     SystemResource { message: String },
 
+    #[error("Workspace quota exceeded: {0}")]
+    WorkspaceQuotaExceeded(crate::claude_code::quota::QuotaBreach),
+
     #[error("Git error: {message}")]
     Git { message: String },
 
@@ -28,6 +28,8 @@
 pub mod agents;
 /// HTTP API server and endpoints
 pub mod api;
+/// Append-only audit log of security-relevant actions
+pub mod audit;
 /// Authentication and authorization
 pub mod auth;
 /// Claude Code client integration
@@ -38,14 +40,24 @@ pub mod config;
 pub mod constants;
 /// Discord bot integration
 pub mod discord;
+/// Pre-flight checks of the configuration and environment (`spiral-core doctor`)
+pub mod doctor;
 /// Error types and handling
 pub mod error;
+/// Pluggable code generation backends (Claude Code, OpenAI, Ollama)
+pub mod llm;
+/// Log output and runtime log levels
+pub mod logging;
 /// Core data models
 pub mod models;
 /// System monitoring and metrics
 pub mod monitoring;
+/// Prompt templates per agent type, editable without recompiling
+pub mod prompts;
 /// Rate limiting functionality
 pub mod rate_limit;
+/// Minimal Redis client shared by the session and rate limit stores
+pub mod redis;
 /// Security utilities and API key management
 pub mod security;
 /// Session management for agents and users
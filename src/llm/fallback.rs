@@ -0,0 +1,179 @@
+use super::{BackendKind, CodeGenerationBackend};
+use crate::{
+    claude_code::{CodeGenerationRequest, CodeGenerationResult, GenerationEvent},
+    Result,
+};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::sync::Arc;
+use tracing::warn;
+
+/// 🔀 FALLBACK ROUTING: Primary backend first, the fallbacks in order while it is down
+/// 🏗️ ARCHITECTURE DECISION: Fall back on unavailability, not on every error
+/// Why: An open circuit breaker means the primary is known to be failing; an ordinary
+/// error (bad request, validation) would fail the same way elsewhere, and quietly
+/// re-running it on another model would hide it
+/// Alternative: Retry every failure on the next backend (rejected: doubles the cost of
+/// every genuine failure)
+#[derive(Debug)]
+pub struct FallbackBackend {
+    primary: Arc<dyn CodeGenerationBackend>,
+    fallbacks: Vec<Arc<dyn CodeGenerationBackend>>,
+}
+
+impl FallbackBackend {
+    pub fn new(
+        primary: Arc<dyn CodeGenerationBackend>,
+        fallbacks: Vec<Arc<dyn CodeGenerationBackend>>,
+    ) -> Self {
+        Self { primary, fallbacks }
+    }
+
+    /// The first available backend; the primary if none is, so its own error surfaces
+    async fn select(&self) -> &Arc<dyn CodeGenerationBackend> {
+        if self.primary.is_available().await {
+            return &self.primary;
+        }
+        for fallback in &self.fallbacks {
+            if fallback.is_available().await {
+                warn!(
+                    "{} backend is unavailable, falling back to {}",
+                    self.primary.kind(),
+                    fallback.kind()
+                );
+                return fallback;
+            }
+        }
+        &self.primary
+    }
+}
+
+#[async_trait]
+impl CodeGenerationBackend for FallbackBackend {
+    fn kind(&self) -> BackendKind {
+        self.primary.kind()
+    }
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult> {
+        let backend = self.select().await;
+        match backend.generate_code(request.clone()).await {
+            // This failure may be the one that opened the circuit; retry elsewhere if so
+            Err(e) if Arc::ptr_eq(backend, &self.primary) && !backend.is_available().await => {
+                let fallback = self.select().await;
+                if Arc::ptr_eq(fallback, &self.primary) {
+                    return Err(e);
+                }
+                fallback.generate_code(request).await
+            }
+            outcome => outcome,
+        }
+    }
+
+    async fn generate_code_stream(
+        &self,
+        request: CodeGenerationRequest,
+    ) -> Result<BoxStream<'static, GenerationEvent>> {
+        self.select().await.generate_code_stream(request).await
+    }
+
+    async fn is_available(&self) -> bool {
+        if self.primary.is_available().await {
+            return true;
+        }
+        for fallback in &self.fallbacks {
+            if fallback.is_available().await {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpiralError;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct StubBackend {
+        kind: BackendKind,
+        available: AtomicBool,
+        calls: AtomicUsize,
+        /// Going unavailable after a failed call, like a tripping circuit breaker
+        trips: bool,
+    }
+
+    impl StubBackend {
+        fn new(kind: BackendKind, available: bool, trips: bool) -> Arc<Self> {
+            Arc::new(Self {
+                kind,
+                available: AtomicBool::new(available),
+                calls: AtomicUsize::new(0),
+                trips,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CodeGenerationBackend for StubBackend {
+        fn kind(&self) -> BackendKind {
+            self.kind
+        }
+
+        async fn generate_code(
+            &self,
+            request: CodeGenerationRequest,
+        ) -> Result<CodeGenerationResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.trips {
+                self.available.store(false, Ordering::SeqCst);
+                return Err(SpiralError::Agent {
+                    message: "service failed".to_string(),
+                });
+            }
+            Ok(super::super::text_result(self.kind.to_string(), &request))
+        }
+
+        async fn is_available(&self) -> bool {
+            self.available.load(Ordering::SeqCst)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_only_while_primary_is_unavailable() {
+        let primary = StubBackend::new(BackendKind::Claude, true, false);
+        let ollama = StubBackend::new(BackendKind::Ollama, true, false);
+        let backend = FallbackBackend::new(primary.clone(), vec![ollama.clone()]);
+        let request = super::super::tests::request();
+
+        let result = backend.generate_code(request.clone()).await.unwrap();
+        assert_eq!(result.explanation, "claude");
+
+        primary.available.store(false, Ordering::SeqCst);
+        let result = backend.generate_code(request).await.unwrap();
+        assert_eq!(result.explanation, "ollama");
+        assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_that_opens_the_circuit_is_retried_on_a_fallback() {
+        let primary = StubBackend::new(BackendKind::Claude, true, true);
+        let openai = StubBackend::new(BackendKind::OpenAi, true, false);
+        let backend = FallbackBackend::new(primary.clone(), vec![openai.clone()]);
+
+        let result = backend
+            .generate_code(super::super::tests::request())
+            .await
+            .unwrap();
+        assert_eq!(result.explanation, "openai");
+
+        // Nothing available: the primary's own error comes back
+        openai.available.store(false, Ordering::SeqCst);
+        assert!(!backend.is_available().await);
+        assert!(backend
+            .generate_code(super::super::tests::request())
+            .await
+            .is_err());
+    }
+}
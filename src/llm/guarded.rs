@@ -0,0 +1,114 @@
+use super::{BackendKind, CodeGenerationBackend};
+use crate::{
+    claude_code::{
+        circuit_breaker::{CircuitBreaker, FailureClass},
+        CodeGenerationRequest, CodeGenerationResult,
+    },
+    Result, SpiralError,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+/// 🔌 GUARDED BACKEND: An HTTP backend behind its own circuit breaker
+/// 🏗️ ARCHITECTURE DECISION: A wrapper rather than breaker code in each backend
+/// Why: OpenAI and Ollama fail the same ways (timeouts, rejected keys, server errors),
+/// and the wrapper gives any future backend the same protection for free
+/// Alternative: Share Claude Code's breaker (rejected: an Ollama outage would stop
+/// Claude Code, and a fallback would never look available while Claude is down)
+#[derive(Debug)]
+pub struct GuardedBackend {
+    inner: Arc<dyn CodeGenerationBackend>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl GuardedBackend {
+    pub fn new(inner: Arc<dyn CodeGenerationBackend>, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { inner, breaker }
+    }
+}
+
+#[async_trait]
+impl CodeGenerationBackend for GuardedBackend {
+    fn kind(&self) -> BackendKind {
+        self.inner.kind()
+    }
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult> {
+        if !self.breaker.should_allow_request().await {
+            warn!(
+                "Circuit breaker is open - {} backend is unavailable",
+                self.kind()
+            );
+            return Err(SpiralError::Agent {
+                message: format!(
+                    "The {} backend is temporarily unavailable due to repeated failures",
+                    self.kind()
+                ),
+            });
+        }
+        let outcome = self.inner.generate_code(request).await;
+        match &outcome {
+            Ok(_) => self.breaker.record_success().await,
+            Err(e) => {
+                if let Some(class) = FailureClass::classify(e) {
+                    self.breaker.record_classified_failure(class).await;
+                }
+            }
+        }
+        outcome
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.breaker.is_rejecting().await && self.inner.is_available().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude_code::circuit_breaker::{CircuitBreakerConfig, FailureAction};
+    use crate::config::OllamaConfig;
+    use crate::llm::OllamaBackend;
+
+    #[tokio::test]
+    async fn test_rejected_key_opens_only_this_backends_circuit() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": "unauthorized"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let ollama = OllamaBackend::new(OllamaConfig {
+            base_url: server.url(),
+            model: "qwen2.5-coder".to_string(),
+            circuit_breaker: Default::default(),
+        })
+        .unwrap();
+        let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+            on_auth_error: FailureAction::Trip,
+            ..Default::default()
+        }));
+        let backend = GuardedBackend::new(Arc::new(ollama), breaker.clone());
+
+        assert!(backend.is_available().await);
+        assert!(backend
+            .generate_code(super::super::tests::request())
+            .await
+            .is_err());
+        assert!(!backend.is_available().await);
+
+        // While open, requests are refused without reaching the server
+        assert!(backend
+            .generate_code(super::super::tests::request())
+            .await
+            .is_err());
+        mock.assert_async().await;
+
+        breaker.reset().await;
+        assert!(backend.is_available().await);
+    }
+}
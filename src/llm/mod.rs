@@ -0,0 +1,306 @@
+//! Code generation backends
+//!
+//! Claude Code stays the primary engine; OpenAI-compatible APIs and local Ollama models
+//! can serve chosen agents, or stand in while Claude Code's circuit breaker is open.
+
+use crate::{
+    claude_code::{ClaudeCodeClient, CodeGenerationRequest, CodeGenerationResult, GenerationEvent},
+    config::LlmConfig,
+    models::AgentType,
+    prompts, Result,
+};
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, sync::Arc};
+
+mod fallback;
+mod guarded;
+mod ollama;
+mod openai;
+
+pub use fallback::FallbackBackend;
+pub use guarded::GuardedBackend;
+pub use ollama::OllamaBackend;
+pub use openai::OpenAiBackend;
+
+/// The backends code generation can be routed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    Claude,
+    OpenAi,
+    Ollama,
+}
+
+impl BackendKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::OpenAi => "openai",
+            Self::Ollama => "ollama",
+        }
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "claude" => Ok(Self::Claude),
+            "openai" => Ok(Self::OpenAi),
+            "ollama" => Ok(Self::Ollama),
+            other => Err(format!(
+                "Unknown backend '{other}' (expected claude, openai or ollama)"
+            )),
+        }
+    }
+}
+
+/// 🏗️ ARCHITECTURE DECISION: Agents generate through this trait, not a concrete client
+/// Why: Lets an agent run on a local model, and keeps agents working while Claude Code
+/// is down, without each agent knowing which engine answered
+/// Alternative: An enum of clients (rejected: every new backend would touch every match)
+/// Trade-off: Only Claude Code has tools and a workspace; the HTTP backends return text,
+/// so their results carry no files and an empty workspace_path
+#[async_trait]
+pub trait CodeGenerationBackend: Send + Sync + fmt::Debug {
+    fn kind(&self) -> BackendKind;
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult>;
+
+    /// Incremental events ending in Completed, Failed or QuotaExceeded; backends without streaming send
+    /// just the final event
+    async fn generate_code_stream(
+        &self,
+        request: CodeGenerationRequest,
+    ) -> Result<BoxStream<'static, GenerationEvent>> {
+        let event = match self.generate_code(request).await {
+            Ok(result) => GenerationEvent::Completed(result),
+            Err(e) => GenerationEvent::failure(e),
+        };
+        Ok(stream::once(async move { event }).boxed())
+    }
+
+    /// Whether the backend would accept a request right now
+    async fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl CodeGenerationBackend for ClaudeCodeClient {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Claude
+    }
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult> {
+        ClaudeCodeClient::generate_code(self, request).await
+    }
+
+    async fn generate_code_stream(
+        &self,
+        request: CodeGenerationRequest,
+    ) -> Result<BoxStream<'static, GenerationEvent>> {
+        Ok(ClaudeCodeClient::generate_code_stream(self, request)
+            .await?
+            .boxed())
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.circuit_open().await
+    }
+}
+
+/// Every configured backend, handing out the right one per agent type
+#[derive(Debug, Clone)]
+pub struct BackendSet {
+    config: LlmConfig,
+    claude: Arc<dyn CodeGenerationBackend>,
+    openai: Option<Arc<dyn CodeGenerationBackend>>,
+    ollama: Option<Arc<dyn CodeGenerationBackend>>,
+}
+
+impl BackendSet {
+    pub fn new(config: &LlmConfig, claude: ClaudeCodeClient) -> Result<Self> {
+        // Each backend trips on its own; the registry lets both backend sets share them
+        let guarded = |kind: BackendKind,
+                       backend: Arc<dyn CodeGenerationBackend>,
+                       policy: &crate::config::CircuitBreakerPolicy| {
+            let breaker = claude
+                .circuit_breakers()
+                .get_or_create(kind.as_str(), policy.into());
+            Arc::new(GuardedBackend::new(backend, breaker)) as Arc<dyn CodeGenerationBackend>
+        };
+        let openai = match &config.openai {
+            Some(openai) => Some(guarded(
+                BackendKind::OpenAi,
+                Arc::new(OpenAiBackend::new(openai.clone())?),
+                &openai.circuit_breaker,
+            )),
+            None => None,
+        };
+        let ollama = match &config.ollama {
+            Some(ollama) => Some(guarded(
+                BackendKind::Ollama,
+                Arc::new(OllamaBackend::new(ollama.clone())?),
+                &ollama.circuit_breaker,
+            )),
+            None => None,
+        };
+        Ok(Self {
+            config: config.clone(),
+            claude: Arc::new(claude),
+            openai,
+            ollama,
+        })
+    }
+
+    fn get(&self, kind: BackendKind) -> Option<Arc<dyn CodeGenerationBackend>> {
+        match kind {
+            BackendKind::Claude => Some(self.claude.clone()),
+            BackendKind::OpenAi => self.openai.clone(),
+            BackendKind::Ollama => self.ollama.clone(),
+        }
+    }
+
+    /// The agent's configured backend (Claude Code unless overridden), wrapped so the
+    /// fallback backends take over while it is unavailable
+    pub fn for_agent(&self, agent_type: &AgentType) -> Arc<dyn CodeGenerationBackend> {
+        let primary_kind = self
+            .config
+            .agent_backends
+            .get(agent_type)
+            .copied()
+            .unwrap_or(BackendKind::Claude);
+        let primary = self
+            .get(primary_kind)
+            .unwrap_or_else(|| self.claude.clone());
+        let fallbacks: Vec<_> = self
+            .config
+            .fallback
+            .iter()
+            .filter(|kind| **kind != primary_kind)
+            .filter_map(|kind| self.get(*kind))
+            .collect();
+
+        if fallbacks.is_empty() {
+            primary
+        } else {
+            Arc::new(FallbackBackend::new(primary, fallbacks))
+        }
+    }
+}
+
+/// Prompt for backends that answer in plain text and cannot run tools
+fn chat_prompt(request: &CodeGenerationRequest) -> String {
+    prompts::render_request("chat_generation", request)
+}
+
+const CHAT_SYSTEM_PROMPT: &str =
+    "You are a Software Developer Agent in the Spiral Core orchestration system.";
+
+/// Turn a plain-text answer into a result; the first fenced block is the code
+fn text_result(text: String, request: &CodeGenerationRequest) -> CodeGenerationResult {
+    let code = extract_code_block(&text, &request.language).unwrap_or_else(|| text.clone());
+    CodeGenerationResult {
+        code,
+        language: request.language.clone(),
+        explanation: text,
+        files_to_create: Vec::new(),
+        files_to_modify: Vec::new(),
+        session_id: request.session_id.clone(),
+        workspace_path: String::new(),
+        changes: Vec::new(),
+        dry_run: request.dry_run,
+    }
+}
+
+fn extract_code_block(text: &str, language: &str) -> Option<String> {
+    [format!("```{language}\n"), "```\n".to_string()]
+        .iter()
+        .find_map(|fence| {
+            let start = text.find(fence.as_str())? + fence.len();
+            let end = text[start..].find("\n```")?;
+            Some(text[start..start + end].to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, OllamaConfig};
+
+    pub(super) fn request() -> CodeGenerationRequest {
+        CodeGenerationRequest {
+            language: "rust".to_string(),
+            description: "Write an add function".to_string(),
+            context: Default::default(),
+            existing_code: None,
+            requirements: vec!["Keep it small".to_string()],
+            session_id: Some("s-1".to_string()),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_text_result_extracts_the_code_block() {
+        let text = "Here it is:\n```rust\nfn add(a: i32, b: i32) -> i32 { a + b }\n```\nDone.";
+        let result = text_result(text.to_string(), &request());
+        assert_eq!(result.code, "fn add(a: i32, b: i32) -> i32 { a + b }");
+        assert_eq!(result.explanation, text);
+        assert_eq!(result.session_id.as_deref(), Some("s-1"));
+        assert!(result.workspace_path.is_empty());
+
+        let plain = text_result("no fences".to_string(), &request());
+        assert_eq!(plain.code, "no fences");
+    }
+
+    #[tokio::test]
+    async fn test_agents_get_their_configured_backend() {
+        let mut config = Config::test_config();
+        config.llm.ollama = Some(OllamaConfig {
+            base_url: "http://127.0.0.1:11434".to_string(),
+            model: "qwen2.5-coder".to_string(),
+            circuit_breaker: Default::default(),
+        });
+        config
+            .llm
+            .agent_backends
+            .insert(AgentType::ProjectManager, BackendKind::Ollama);
+        let claude = ClaudeCodeClient::new(config.claude_code.clone())
+            .await
+            .unwrap();
+        let backends = BackendSet::new(&config.llm, claude.clone()).unwrap();
+
+        assert_eq!(
+            backends.for_agent(&AgentType::ProjectManager).kind(),
+            BackendKind::Ollama
+        );
+        assert_eq!(
+            backends.for_agent(&AgentType::SoftwareDeveloper).kind(),
+            BackendKind::Claude
+        );
+
+        // With a fallback the developer gets a wrapper that still reports Claude first
+        config.llm.fallback = vec![BackendKind::Ollama];
+        let backends = BackendSet::new(&config.llm, claude).unwrap();
+        let developer = backends.for_agent(&AgentType::SoftwareDeveloper);
+        assert_eq!(developer.kind(), BackendKind::Claude);
+        assert!(format!("{developer:?}").contains("FallbackBackend"));
+    }
+
+    #[test]
+    fn test_backend_kind_parsing() {
+        assert_eq!("OpenAI".parse::<BackendKind>(), Ok(BackendKind::OpenAi));
+        assert_eq!(" ollama ".parse::<BackendKind>(), Ok(BackendKind::Ollama));
+        assert!("gemini".parse::<BackendKind>().is_err());
+    }
+}
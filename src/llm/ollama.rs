@@ -0,0 +1,125 @@
+use super::{chat_prompt, text_result, BackendKind, CodeGenerationBackend, CHAT_SYSTEM_PROMPT};
+use crate::{
+    claude_code::{validate_generation_request, CodeGenerationRequest, CodeGenerationResult},
+    config::OllamaConfig,
+    constants::LLM_HTTP_TIMEOUT_SECS,
+    validation::TaskContentValidator,
+    Result, SpiralError,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::info;
+
+/// A model served by a local (or LAN) Ollama instance
+/// Nothing leaves the machine, which also makes it the fallback that works offline
+#[derive(Debug)]
+pub struct OllamaBackend {
+    config: OllamaConfig,
+    http: reqwest::Client,
+    validator: TaskContentValidator,
+}
+
+impl OllamaBackend {
+    pub fn new(config: OllamaConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(LLM_HTTP_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| SpiralError::Internal(e.into()))?;
+        Ok(Self {
+            config,
+            http,
+            validator: TaskContentValidator::new()?,
+        })
+    }
+}
+
+#[async_trait]
+impl CodeGenerationBackend for OllamaBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Ollama
+    }
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult> {
+        validate_generation_request(&self.validator, &request)?;
+        info!(
+            "Generating {} code with Ollama model {}",
+            request.language, self.config.model
+        );
+
+        let url = format!("{}/api/chat", self.config.base_url.trim_end_matches('/'));
+        let response = self
+            .http
+            .post(&url)
+            .json(&json!({
+                "model": self.config.model,
+                "stream": false,
+                "messages": [
+                    {"role": "system", "content": CHAT_SYSTEM_PROMPT},
+                    {"role": "user", "content": chat_prompt(&request)},
+                ],
+            }))
+            .send()
+            .await
+            .map_err(|e| SpiralError::Agent {
+                message: format!("Ollama request failed: {e}"),
+            })?;
+
+        let status = response.status();
+        let body: Value = response.json().await.map_err(|e| SpiralError::Agent {
+            message: format!("Failed to parse Ollama response: {e}"),
+        })?;
+        if !status.is_success() {
+            let reason = body["error"].as_str().unwrap_or("no details");
+            return Err(SpiralError::Agent {
+                message: format!("Ollama returned {status}: {reason}"),
+            });
+        }
+
+        let text = body["message"]["content"]
+            .as_str()
+            .ok_or_else(|| SpiralError::Agent {
+                message: "Ollama response has no message content".to_string(),
+            })?;
+        Ok(text_result(text.to_string(), &request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[tokio::test]
+    async fn test_generates_from_chat_endpoint() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .match_body(Matcher::PartialJson(
+                json!({"model": "qwen2.5-coder", "stream": false}),
+            ))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"message": {"role": "assistant", "content": "```\nfn add() {}\n```"},
+                    "done": true})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let backend = OllamaBackend::new(OllamaConfig {
+            base_url: format!("{}/", server.url()),
+            model: "qwen2.5-coder".to_string(),
+            circuit_breaker: Default::default(),
+        })
+        .unwrap();
+        let result = backend
+            .generate_code(super::super::tests::request())
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.code, "fn add() {}");
+        assert_eq!(backend.kind(), BackendKind::Ollama);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{chat_prompt, text_result, BackendKind, CodeGenerationBackend, CHAT_SYSTEM_PROMPT};
+use crate::{
+    claude_code::{validate_generation_request, CodeGenerationRequest, CodeGenerationResult},
+    config::OpenAiConfig,
+    constants::LLM_HTTP_TIMEOUT_SECS,
+    validation::TaskContentValidator,
+    Result, SpiralError,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::{fmt, time::Duration};
+use tracing::info;
+
+/// Chat completions against the OpenAI API, or any server speaking the same protocol
+pub struct OpenAiBackend {
+    config: OpenAiConfig,
+    http: reqwest::Client,
+    validator: TaskContentValidator,
+}
+
+// Hand-written so the API key never reaches a log line
+impl fmt::Debug for OpenAiBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAiBackend")
+            .field("base_url", &self.config.base_url)
+            .field("model", &self.config.model)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OpenAiBackend {
+    pub fn new(config: OpenAiConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(LLM_HTTP_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| SpiralError::Internal(e.into()))?;
+        Ok(Self {
+            config,
+            http,
+            validator: TaskContentValidator::new()?,
+        })
+    }
+}
+
+#[async_trait]
+impl CodeGenerationBackend for OpenAiBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::OpenAi
+    }
+
+    async fn generate_code(&self, request: CodeGenerationRequest) -> Result<CodeGenerationResult> {
+        validate_generation_request(&self.validator, &request)?;
+        info!(
+            "Generating {} code with OpenAI model {}",
+            request.language, self.config.model
+        );
+
+        let url = format!(
+            "{}/chat/completions",
+            self.config.base_url.trim_end_matches('/')
+        );
+        let response = self
+            .http
+            .post(&url)
+            .bearer_auth(&self.config.api_key)
+            .json(&json!({
+                "model": self.config.model,
+                "messages": [
+                    {"role": "system", "content": CHAT_SYSTEM_PROMPT},
+                    {"role": "user", "content": chat_prompt(&request)},
+                ],
+            }))
+            .send()
+            .await
+            .map_err(|e| SpiralError::Agent {
+                message: format!("OpenAI request failed: {e}"),
+            })?;
+
+        let status = response.status();
+        let body: Value = response.json().await.map_err(|e| SpiralError::Agent {
+            message: format!("Failed to parse OpenAI response: {e}"),
+        })?;
+        if !status.is_success() {
+            let reason = body["error"]["message"].as_str().unwrap_or("no details");
+            return Err(SpiralError::Agent {
+                message: format!("OpenAI returned {status}: {reason}"),
+            });
+        }
+
+        let text = body["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| SpiralError::Agent {
+                message: "OpenAI response has no message content".to_string(),
+            })?;
+        Ok(text_result(text.to_string(), &request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn backend(base_url: String) -> OpenAiBackend {
+        OpenAiBackend::new(OpenAiConfig {
+            api_key: "sk-test".to_string(),
+            base_url,
+            model: "gpt-4o".to_string(),
+            circuit_breaker: Default::default(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generates_from_chat_completions() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer sk-test")
+            .match_body(Matcher::PartialJson(json!({"model": "gpt-4o"})))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"choices": [{"message": {"role": "assistant",
+                    "content": "```rust\nfn add() {}\n```\nAdds."}}]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let result = backend(format!("{}/v1", server.url()))
+            .generate_code(super::super::tests::request())
+            .await
+            .unwrap();
+        mock.assert_async().await;
+        assert_eq!(result.code, "fn add() {}");
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_are_reported() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/chat/completions")
+            .with_status(429)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": {"message": "Rate limit reached"}}"#)
+            .create_async()
+            .await;
+
+        let error = backend(server.url())
+            .generate_code(super::super::tests::request())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Rate limit reached"));
+        assert!(!format!("{:?}", backend(server.url())).contains("sk-test"));
+    }
+}
@@ -0,0 +1,183 @@
+//! Log files that rotate by day or size
+//!
+//! Files are named `<prefix>.<YYYY-MM-DD>.log`, so the date a file was written is in its
+//! name and retention needs no metadata. With size rotation a full file is renamed to
+//! `<prefix>.<YYYY-MM-DD>.<n>.log` and a fresh one started.
+
+use crate::config::LogRotation;
+use chrono::{NaiveDate, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const LOG_FILE_EXTENSION: &str = "log";
+
+/// 📁 LOG FILE: Appends log lines, starting a new file each day and, with size rotation,
+/// whenever the current one reaches its limit
+/// 🏗️ ARCHITECTURE DECISION: Own writer behind tracing-appender's non-blocking worker
+/// Why: tracing-appender rotates by time only; doing both here keeps one naming scheme for
+/// rotation and retention, while writes still happen off the logging thread
+/// Alternative: RollingFileAppender for daily plus a second writer for size (rejected: two
+/// naming schemes for one retention sweep)
+pub struct RotatingFile {
+    dir: PathBuf,
+    prefix: String,
+    rotation: LogRotation,
+    max_bytes: u64,
+    current: Option<OpenFile>,
+}
+
+struct OpenFile {
+    file: File,
+    date: NaiveDate,
+    bytes: u64,
+}
+
+impl RotatingFile {
+    pub fn new(
+        dir: impl AsRef<Path>,
+        prefix: &str,
+        rotation: LogRotation,
+        max_bytes: u64,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            rotation,
+            max_bytes: max_bytes.max(1),
+            current: None,
+        })
+    }
+
+    fn path_for(&self, date: NaiveDate) -> PathBuf {
+        self.dir
+            .join(format!("{}.{date}.{LOG_FILE_EXTENSION}", self.prefix))
+    }
+
+    /// The file to write to on `today`, rotating first when its day or size is up
+    fn file_for(&mut self, today: NaiveDate) -> io::Result<&mut OpenFile> {
+        let full =
+            |open: &OpenFile| self.rotation == LogRotation::Size && open.bytes >= self.max_bytes;
+        let rotate = match &self.current {
+            None => true,
+            Some(open) => open.date != today || full(open),
+        };
+        if rotate {
+            self.current = None;
+            let path = self.path_for(today);
+            let existing = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if self.rotation == LogRotation::Size && existing >= self.max_bytes {
+                fs::rename(&path, self.next_numbered(today))?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            let bytes = file.metadata()?.len();
+            self.current = Some(OpenFile {
+                file,
+                date: today,
+                bytes,
+            });
+        }
+        Ok(self.current.as_mut().expect("log file opened above"))
+    }
+
+    /// The first unused `<prefix>.<date>.<n>.log` name for a full file
+    fn next_numbered(&self, date: NaiveDate) -> PathBuf {
+        (1..)
+            .map(|n| {
+                self.dir
+                    .join(format!("{}.{date}.{n}.{LOG_FILE_EXTENSION}", self.prefix))
+            })
+            .find(|path| !path.exists())
+            .expect("an unused file number")
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let open = self.file_for(Utc::now().date_naive())?;
+        open.file.write_all(buf)?;
+        open.bytes += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.current {
+            Some(open) => open.file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Delete log files of `prefixes` in `dir` dated more than `retention_days` before `today`
+/// Returns how many were removed; 0 days keeps everything
+pub fn remove_expired(
+    dir: &Path,
+    prefixes: &[&str],
+    retention_days: u32,
+    today: NaiveDate,
+) -> usize {
+    if retention_days == 0 {
+        return 0;
+    }
+    let cutoff = today - chrono::Duration::days(i64::from(retention_days));
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(date) = prefixes.iter().find_map(|prefix| file_date(&name, prefix)) else {
+            continue;
+        };
+        if date >= cutoff {
+            continue;
+        }
+        match fs::remove_file(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(e) => warn!("Failed to remove expired log file {}: {}", name, e),
+        }
+    }
+    removed
+}
+
+/// The date in `<prefix>.<date>[.<n>].log`, if `name` is one of `prefix`'s files
+fn file_date(name: &str, prefix: &str) -> Option<NaiveDate> {
+    let rest = name
+        .strip_prefix(prefix)?
+        .strip_prefix('.')?
+        .strip_suffix(&format!(".{LOG_FILE_EXTENSION}"))?;
+    let date = rest.split('.').next()?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_rotation_numbers_full_files_and_retention_removes_old_days() {
+        let dir = tempfile::tempdir().unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let mut log = RotatingFile::new(dir.path(), "spiral-core", LogRotation::Size, 10).unwrap();
+        for line in ["first line\n", "second line\n", "third\n"] {
+            let open = log.file_for(today).unwrap();
+            open.file.write_all(line.as_bytes()).unwrap();
+            open.bytes += line.len() as u64;
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("spiral-core.2026-10-17.1.log"), "first line\n");
+        assert_eq!(read("spiral-core.2026-10-17.2.log"), "second line\n");
+        assert_eq!(read("spiral-core.2026-10-17.log"), "third\n");
+
+        fs::write(dir.path().join("spiral-core.2026-09-01.log"), "old").unwrap();
+        fs::write(dir.path().join("security-events.2026-09-01.3.log"), "old").unwrap();
+        fs::write(dir.path().join("unrelated.2026-09-01.log"), "kept").unwrap();
+        let removed = remove_expired(dir.path(), &["spiral-core", "security-events"], 14, today);
+        assert_eq!(removed, 2);
+        assert!(dir.path().join("unrelated.2026-09-01.log").exists());
+        assert!(dir.path().join("spiral-core.2026-10-17.1.log").exists());
+    }
+}
@@ -0,0 +1,235 @@
+//! Log output and runtime log levels
+//!
+//! Logging is set up once at startup from the `logging` config: text or JSON lines, and
+//! an EnvFilter of per-module levels such as `info,spiral_core::discord=debug`. The
+//! filter sits behind a reload layer, so an admin can change it from the API or Discord
+//! while the process keeps running. With a log directory configured, lines are also
+//! written to rotating files, and security events to a file of their own.
+
+pub mod file;
+
+pub use file::RotatingFile;
+
+use crate::config::{LogFileConfig, LogFormat, LoggingConfig};
+use crate::constants::{LOG_FILE_PREFIX, SECURITY_EVENTS_TARGET, SECURITY_LOG_FILE_PREFIX};
+use crate::{Result, SpiralError};
+use std::sync::OnceLock;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{
+    filter::{filter_fn, Targets},
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    reload,
+    util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
+};
+
+/// Swaps the filter of the installed subscriber; set once by init
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// The filter logging started with, which reset_filter goes back to
+static CONFIGURED_FILTER: OnceLock<String> = OnceLock::new();
+
+/// Where log files are written and how long they are kept; set by init with file logging
+static LOG_FILES: OnceLock<LogFileConfig> = OnceLock::new();
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Keeps the log file writers running; dropping it flushes what they still hold
+#[must_use = "dropping the guard stops file logging"]
+pub struct LogGuard {
+    _workers: Vec<WorkerGuard>,
+}
+
+/// Parse log filter directives, e.g. `warn,spiral_core=debug`
+pub fn parse_filter(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::try_new(directives.trim())
+        .map_err(|e| SpiralError::Validation(format!("Invalid log filter '{directives}': {e}")))
+}
+
+/// 📊 LOGGING SETUP: Install the global subscriber described by `config`
+/// 🏗️ ARCHITECTURE DECISION: Reload only the filter, not the output format
+/// Why: Levels are what an operator chasing a problem needs to change; the format is
+/// decided by whatever collects the logs and does not change while it runs
+/// Alternative: RUST_LOG and a restart (rejected: a restart loses the state being debugged)
+/// Hold the returned guard until exit, or file logging stops
+pub fn init(config: &LoggingConfig) -> Result<LogGuard> {
+    let (layers, handle, guard) = layers(config)?;
+    tracing_subscriber::registry()
+        .with(layers)
+        .try_init()
+        .map_err(|e| SpiralError::SystemError(format!("Failed to initialize logging: {e}")))?;
+    let _ = FILTER.set(handle);
+    let _ = CONFIGURED_FILTER.set(config.level.trim().to_string());
+    if let Some(files) = &config.file {
+        let _ = LOG_FILES.set(files.clone());
+        apply_retention();
+    }
+    Ok(guard)
+}
+
+/// The output layers of `config`, the handle to their shared filter, and the file writers
+/// 🏗️ ARCHITECTURE DECISION: security_events get a file of their own, outside the
+/// adjustable filter
+/// Why: Turning levels down while debugging something else must not drop security
+/// records, and reviewers want them without the rest of the noise
+/// Trade-off: They still go to stdout under the adjustable filter, as before
+fn layers(
+    config: &LoggingConfig,
+) -> Result<(
+    Vec<BoxedLayer>,
+    reload::Handle<EnvFilter, Registry>,
+    LogGuard,
+)> {
+    let (filter, handle) = reload::Layer::new(parse_filter(&config.level)?);
+    let mut filtered = vec![output(config.format, std::io::stdout, true)];
+    let mut layers = Vec::new();
+    let mut workers = Vec::new();
+
+    if let Some(files) = &config.file {
+        let open = |prefix: &str| {
+            RotatingFile::new(
+                &files.dir,
+                prefix,
+                files.rotation,
+                files.max_file_mb.saturating_mul(1024 * 1024),
+            )
+            .map_err(|e| {
+                SpiralError::ConfigurationError(format!(
+                    "Cannot write log files to {}: {e}",
+                    files.dir
+                ))
+            })
+        };
+        let (writer, worker) = tracing_appender::non_blocking(open(LOG_FILE_PREFIX)?);
+        workers.push(worker);
+        filtered.push(
+            output(config.format, writer, false)
+                .with_filter(filter_fn(|meta| meta.target() != SECURITY_EVENTS_TARGET))
+                .boxed(),
+        );
+
+        let (writer, worker) = tracing_appender::non_blocking(open(SECURITY_LOG_FILE_PREFIX)?);
+        workers.push(worker);
+        layers.push(
+            output(config.format, writer, false)
+                .with_filter(Targets::new().with_target(SECURITY_EVENTS_TARGET, LevelFilter::TRACE))
+                .boxed(),
+        );
+    }
+
+    layers.insert(0, filtered.with_filter(filter).boxed());
+    Ok((layers, handle, LogGuard { _workers: workers }))
+}
+
+/// Lines in `format` written to `writer`; colours only suit terminals
+fn output<W>(format: LogFormat, writer: W, ansi: bool) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).with_ansi(ansi).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// 🧹 LOG RETENTION: Delete log files past their retention; run by the cleanup loop
+/// Returns how many files were removed; nothing without file logging
+pub fn apply_retention() -> usize {
+    let Some(files) = LOG_FILES.get() else {
+        return 0;
+    };
+    let removed = file::remove_expired(
+        std::path::Path::new(&files.dir),
+        &[LOG_FILE_PREFIX, SECURITY_LOG_FILE_PREFIX],
+        files.retention_days,
+        chrono::Utc::now().date_naive(),
+    );
+    if removed > 0 {
+        tracing::info!("Removed {} log file(s) past retention", removed);
+    }
+    removed
+}
+
+/// The filter in effect, or None when logging was not set up by init
+pub fn current_filter() -> Option<String> {
+    FILTER.get()?.with_current(|filter| filter.to_string()).ok()
+}
+
+/// 🔧 RUNTIME LOG LEVEL: Replace the filter without restarting; returns the new filter
+pub fn set_filter(directives: &str) -> Result<String> {
+    let filter = parse_filter(directives)?;
+    let handle = FILTER.get().ok_or_else(|| {
+        SpiralError::SystemError("Log levels can't be changed: logging was not set up".to_string())
+    })?;
+    let applied = filter.to_string();
+    handle
+        .reload(filter)
+        .map_err(|e| SpiralError::SystemError(format!("Failed to change log filter: {e}")))?;
+    tracing::info!("Log filter changed to '{}'", applied);
+    Ok(applied)
+}
+
+/// Go back to the filter from the configuration
+pub fn reset_filter() -> Result<String> {
+    let configured = CONFIGURED_FILTER.get().ok_or_else(|| {
+        SpiralError::SystemError("Log levels can't be changed: logging was not set up".to_string())
+    })?;
+    set_filter(configured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_are_validated_before_being_applied() {
+        // Directives come back normalized, most specific first
+        assert_eq!(
+            parse_filter(" info,spiral_core::discord=debug ")
+                .unwrap()
+                .to_string(),
+            "spiral_core::discord=debug,info"
+        );
+        assert!(matches!(
+            parse_filter("spiral_core=loud"),
+            Err(SpiralError::Validation(_))
+        ));
+        assert!(set_filter("spiral_core=[").is_err());
+    }
+
+    #[test]
+    fn test_security_events_get_their_own_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LoggingConfig {
+            level: "warn".to_string(),
+            file: Some(LogFileConfig {
+                dir: dir.path().to_string_lossy().into_owned(),
+                ..LogFileConfig::default()
+            }),
+            ..LoggingConfig::default()
+        };
+        let (layers, _handle, guard) = layers(&config).unwrap();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layers), || {
+            tracing::warn!("disk nearly full");
+            tracing::info!("below the filter");
+            tracing::info!(target: SECURITY_EVENTS_TARGET, "blocked message");
+        });
+        drop(guard);
+
+        let read = |prefix: &str| {
+            let today = chrono::Utc::now().date_naive();
+            std::fs::read_to_string(dir.path().join(format!("{prefix}.{today}.log"))).unwrap()
+        };
+        let main = read(LOG_FILE_PREFIX);
+        assert!(main.contains("disk nearly full"));
+        assert!(!main.contains("below the filter"));
+        assert!(!main.contains("blocked message"));
+        assert!(read(SECURITY_LOG_FILE_PREFIX).contains("blocked message"));
+    }
+}
@@ -1,14 +1,45 @@
+use clap::Parser;
 use spiral_core::discord::startup::start_discord_with_orchestrator;
 use spiral_core::{
     agents::AgentOrchestrator,
     api::ApiServer,
-    config::Config,
-    monitoring::{MonitoringConfig, SystemMonitor},
+    audit,
+    config::{self, Config, ConfigArgs, ConfigHandle, LoggingConfig},
+    constants::SESSION_CLEANUP_INTERVAL_SECS,
+    doctor, logging,
+    monitoring::{
+        AlertEngine, CrashReporter, MetricsHistoryStore, MonitoringConfig, SystemMonitor,
+    },
+    prompts,
+    rate_limit::tiers::RateLimitTiers,
     security,
+    session::SessionJanitor,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
-use tracing::{debug, error, info, warn, Level};
+use tracing::{debug, error, info, warn};
+
+/// Spiral Core agent orchestration server
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+    /// Print the resolved configuration as JSON, credentials redacted, and exit
+    #[arg(long)]
+    print_config: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Check the configuration, Claude Code CLI, workspace, Discord token, API key and API
+    /// port, print a pass/fail report, and exit; fails if any check fails
+    Doctor,
+}
 
 /// 🚀 SPIRAL CORE MAIN ENTRY POINT
 /// DECISION: Graceful startup/shutdown with proper resource management
@@ -16,18 +47,41 @@ use tracing::{debug, error, info, warn, Level};
 /// Alternative: Simple crash on exit (rejected: loses in-flight work)
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // 📊 STARTUP PHASE 1: Initialize logging
-    tracing_subscriber::fmt()
-        .with_max_level(Level::DEBUG)
-        .init();
+    let cli = Cli::parse();
+
+    // 📊 STARTUP PHASE 1: Load configuration, which says how to log. Flags win over the
+    // environment, which wins over the configuration file. .env is read first so it may
+    // name the file in SPIRAL_CONFIG
+    let _ = dotenvy::dotenv();
+    config::layers::install(cli.config.layers()?);
+    let loaded = Config::load();
+
+    if cli.print_config {
+        println!("{}", serde_json::to_string_pretty(&loaded?.redacted())?);
+        return Ok(());
+    }
+
+    if let Some(Command::Doctor) = cli.command {
+        let file = config::layers::installed_file();
+        let report = doctor::run(loaded, file.as_deref()).await;
+        println!("{report}");
+        // The report already says what failed; only the exit status is left to set
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+
+    // 📊 STARTUP PHASE 2: Initialize logging, with defaults if the configuration failed so
+    // the failure itself is logged. The guard flushes the log files when main returns
+    let logging_config = loaded
+        .as_ref()
+        .map(|config| config.logging.clone())
+        .unwrap_or_else(|_| LoggingConfig::default());
+    let _log_guard = logging::init(&logging_config)?;
 
     info!("Starting Spiral Core Agent Orchestration System");
     info!("Version: {}", env!("CARGO_PKG_VERSION"));
     info!("PID: {}", std::process::id());
 
-    // 📊 STARTUP PHASE 2: Load and validate configuration
-    info!("Loading configuration...");
-    let config = match Config::load() {
+    let config = match loaded {
         Ok(cfg) => {
             info!("Configuration loaded successfully");
             cfg
@@ -38,6 +92,22 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    // 💥 STARTUP PHASE 2.4: Report panics from here on, and supervise the subsystems
+    let crash_reporter = CrashReporter::new(&config.crash);
+    crash_reporter.install_panic_hook();
+
+    // 📜 STARTUP PHASE 2.5: Open the audit log before anything can generate events
+    if let Err(e) = audit::init(&config.audit) {
+        error!("Failed to open audit log: {}", e);
+        return Err(anyhow::Error::from(e));
+    }
+
+    // 📝 STARTUP PHASE 2.6: Load prompt templates so broken ones stop startup
+    if let Err(e) = prompts::init(&config.prompts) {
+        error!("Failed to load prompt templates: {}", e);
+        return Err(anyhow::Error::from(e));
+    }
+
     // 📊 STARTUP PHASE 3: Perform startup validations
     perform_startup_validations(&config).await?;
 
@@ -54,7 +124,74 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    // 🤖 STARTUP PHASE 4.5: Initialize Discord integration (optional)
+    // 🧹 STARTUP PHASE 4.55: Remove expired sessions of every front end in the background
+    let session_janitor = Arc::new(SessionJanitor::new(
+        orchestrator.session_registry(),
+        Duration::from_secs(SESSION_CLEANUP_INTERVAL_SECS),
+    ));
+    if let Err(e) = session_janitor.start().await {
+        error!("Failed to start session janitor: {}", e);
+        return Err(anyhow::Error::from(e));
+    }
+
+    // 🔧 STARTUP PHASE 4.6: Initialize system monitoring
+    info!("Initializing system monitoring...");
+    let mut system_monitor = SystemMonitor::new(MonitoringConfig::from(&config.monitoring));
+    system_monitor.register_session_janitor(session_janitor.clone());
+    system_monitor.register_orchestrator(orchestrator.clone());
+    system_monitor.set_crash_reporter(crash_reporter.clone());
+    if let Ok(client) = orchestrator.get_claude_client() {
+        system_monitor.register_claude_client(Arc::new(client.clone()));
+    }
+
+    // 🚨 STARTUP PHASE 4.65: Alert on the collected metrics
+    let alerts = match AlertEngine::from_config(
+        &config.alerting,
+        &config.discord.token,
+        config.api.api_key.clone(),
+    ) {
+        Ok(engine) => engine,
+        Err(e) => {
+            error!("Failed to configure alerting: {}", e);
+            return Err(anyhow::Error::from(e));
+        }
+    };
+    info!("Alerting on {} rule(s)", alerts.rules().len());
+    system_monitor.set_alert_engine(Arc::new(alerts));
+
+    // 📉 STARTUP PHASE 4.7: Keep metrics history across restarts
+    if let Some(path) = &config.monitoring.history_path {
+        match MetricsHistoryStore::open(path) {
+            Ok(store) => system_monitor.set_history_store(store),
+            Err(e) => {
+                error!("Failed to open metrics history: {}", e);
+                return Err(anyhow::Error::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = system_monitor.start_monitoring().await {
+        error!("Failed to start system monitoring: {}", e);
+        return Err(anyhow::Error::from(e));
+    }
+    info!("System monitoring initialized successfully");
+
+    let system_monitor = Arc::new(system_monitor);
+
+    // 🎟️ STARTUP PHASE 4.75: Rate limit tiers, one store for the API and Discord
+    let rate_limit_tiers = Arc::new(RateLimitTiers::open(
+        config.rate_limit.tiers_path.as_ref().map(PathBuf::from),
+    ));
+
+    // 🔄 STARTUP PHASE 4.78: Apply edits to the configuration file without a restart
+    let config_handle = ConfigHandle::new(config.clone());
+    config_handle.follow(system_monitor.clone());
+    if let Some(path) = config::layers::installed_file() {
+        tokio::spawn(config::reload::watch_file(config_handle.clone(), path));
+    }
+
+    // 🤖 STARTUP PHASE 4.8: Initialize Discord integration (optional), once monitoring runs
+    // so the admin dashboard can report availability
     let discord_handle = if !config.discord.token.is_empty() {
         info!("[Main] Discord token detected, preparing Discord integration...");
         debug!(
@@ -62,19 +199,38 @@ async fn main() -> anyhow::Result<()> {
             config.discord.token.len()
         );
 
-        let config_clone = config.clone();
+        let handle_clone = config_handle.clone();
         let orchestrator_clone = orchestrator.clone();
+        let monitor_clone = system_monitor.clone();
+        let tiers_clone = rate_limit_tiers.clone();
 
         info!("[Main] Spawning Discord integration task...");
-        Some(tokio::spawn(async move {
-            info!("[Main] Discord integration task started");
-            match start_discord_with_orchestrator(config_clone, orchestrator_clone).await {
-                Ok(()) => {
-                    info!("[Main] Discord integration completed successfully");
-                }
-                Err(e) => {
-                    error!("[Main] Discord integration failed: {}", e);
-                    error!("[Main] Discord error details: {:?}", e);
+        // A panic restarts the integration, with the configuration in effect by then; an
+        // error it returns ends it as before
+        Some(crash_reporter.supervise("discord", move || {
+            let config_clone = (*handle_clone.current()).clone();
+            let handle_clone = handle_clone.clone();
+            let orchestrator_clone = orchestrator_clone.clone();
+            let monitor_clone = monitor_clone.clone();
+            let tiers_clone = tiers_clone.clone();
+            async move {
+                info!("[Main] Discord integration task started");
+                match start_discord_with_orchestrator(
+                    config_clone,
+                    orchestrator_clone,
+                    Some(monitor_clone),
+                    tiers_clone,
+                    Some(handle_clone),
+                )
+                .await
+                {
+                    Ok(()) => {
+                        info!("[Main] Discord integration completed successfully");
+                    }
+                    Err(e) => {
+                        error!("[Main] Discord integration failed: {}", e);
+                        error!("[Main] Discord error details: {:?}", e);
+                    }
                 }
             }
         }))
@@ -83,24 +239,14 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    // 🔧 STARTUP PHASE 4.6: Initialize system monitoring
-    info!("Initializing system monitoring...");
-    let system_monitor = SystemMonitor::new(MonitoringConfig::default());
-
-    // Register components for monitoring (Claude client will be registered by the orchestrator)
-    if let Err(e) = system_monitor.start_monitoring().await {
-        error!("Failed to start system monitoring: {}", e);
-        return Err(anyhow::Error::from(e));
-    }
-    info!("System monitoring initialized successfully");
-
-    let system_monitor = Arc::new(system_monitor);
-
     info!("Initializing API server...");
     let api_server = match ApiServer::new(config.clone(), orchestrator.clone()) {
         Ok(server) => {
             info!("API server initialized successfully");
-            server.with_system_monitor(system_monitor.clone())
+            server
+                .with_system_monitor(system_monitor.clone())
+                .with_rate_limit_tiers(rate_limit_tiers.clone())
+                .with_config_handle(config_handle.clone())
         }
         Err(e) => {
             error!("Failed to initialize API server: {}", e);
@@ -130,7 +276,7 @@ async fn main() -> anyhow::Result<()> {
         _result = async {
             if let Some(handle) = discord_handle {
                 handle.await.unwrap_or_else(|e| {
-                    error!("Discord task panicked: {}", e);
+                    error!("Discord supervisor failed: {}", e);
                 });
             } else {
                 // If no Discord integration, just wait forever
@@ -145,6 +291,7 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // 📊 SHUTDOWN PHASE: Clean up resources
+    session_janitor.shutdown().await;
     perform_graceful_shutdown(orchestrator).await;
 
     info!("Spiral Core shutdown complete");
@@ -167,7 +314,10 @@ async fn perform_startup_validations(config: &Config) -> anyhow::Result<()> {
     // 🔐 SECURE API KEY VALIDATION: Ensure cryptographically secure API key exists
     // CRITICAL: Use config key if exists, otherwise generate secure file-based key
     // SECURITY: Authentication is always enabled
-    match security::ensure_api_key_exists(config.api.api_key.as_deref()) {
+    match security::ensure_api_key_exists(
+        config.api.api_key.as_deref(),
+        config.api.key_file_passphrase.as_deref(),
+    ) {
         Ok(api_key) => {
             info!(
                 "API key validation successful (length: {} chars)",
@@ -182,16 +332,7 @@ async fn perform_startup_validations(config: &Config) -> anyhow::Result<()> {
     }
 
     // Check workspace directory permissions
-    let workspace_dir = config
-        .claude_code
-        .working_directory
-        .as_ref()
-        .map(std::path::PathBuf::from)
-        .unwrap_or_else(|| {
-            std::env::current_dir()
-                .unwrap_or_else(|_| std::path::PathBuf::from("."))
-                .join("claude-workspaces")
-        });
+    let workspace_dir = doctor::workspace_dir(config);
 
     if workspace_dir.exists() {
         match tokio::fs::metadata(&workspace_dir).await {
@@ -267,16 +408,24 @@ async fn setup_shutdown_handler() {
 async fn perform_graceful_shutdown(orchestrator: Arc<AgentOrchestrator>) {
     info!("Beginning graceful shutdown sequence...");
 
-    // Give in-flight requests time to complete
-    info!("Waiting for in-flight requests to complete (max 30s)...");
-    tokio::time::timeout(
-        std::time::Duration::from_secs(30),
-        wait_for_tasks_completion(&orchestrator),
-    )
-    .await
-    .unwrap_or_else(|_| {
-        warn!("Timeout waiting for tasks to complete, forcing shutdown");
-    });
+    // Give in-flight tasks time to finish, then save the rest for the next start
+    info!(
+        "Waiting for in-flight tasks to complete (max {}s)...",
+        spiral_core::constants::SHUTDOWN_GRACE_SECS
+    );
+    match orchestrator
+        .checkpoint_and_stop(std::time::Duration::from_secs(
+            spiral_core::constants::SHUTDOWN_GRACE_SECS,
+        ))
+        .await
+    {
+        Ok(0) => info!("All tasks completed"),
+        Ok(count) => info!(
+            "Checkpointed {} unfinished task(s) for resume on restart",
+            count
+        ),
+        Err(e) => warn!("Failed to checkpoint unfinished tasks: {}", e),
+    }
 
     // Clean up Claude Code workspaces
     if let Ok(claude_client) = orchestrator.get_claude_client() {
@@ -290,35 +439,3 @@ async fn perform_graceful_shutdown(orchestrator: Arc<AgentOrchestrator>) {
     info!("Flushing logs...");
     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 }
-
-/// Wait for all active tasks to complete
-async fn wait_for_tasks_completion(orchestrator: &AgentOrchestrator) {
-    let mut check_interval = tokio::time::interval(std::time::Duration::from_secs(1));
-    let start_time = std::time::Instant::now();
-
-    loop {
-        check_interval.tick().await;
-
-        let status = orchestrator.get_system_status().await;
-        let active_tasks: usize = status.agents.values().filter(|s| s.is_busy).count();
-
-        if active_tasks == 0 {
-            info!("All tasks completed");
-            break;
-        }
-
-        let elapsed = start_time.elapsed();
-        info!(
-            "Waiting for {} active tasks to complete ({}s elapsed)",
-            active_tasks,
-            elapsed.as_secs()
-        );
-
-        if elapsed > std::time::Duration::from_secs(25) {
-            warn!(
-                "Approaching shutdown timeout with {} tasks still active",
-                active_tasks
-            );
-        }
-    }
-}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, str::FromStr};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Represents a task to be processed by an agent
@@ -17,6 +18,12 @@ pub struct Task {
     pub status: TaskStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Number of times the orchestrator has re-queued this task after a transient failure
+    #[serde(default)]
+    pub retry_count: u32,
+    /// Per-task retry budget; falls back to the orchestrator's retry policy when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
 }
 
 /// Types of specialized agents available in the system
@@ -26,10 +33,11 @@ pub struct Task {
 /// 🏗️ ARCHITECTURE DECISION: Only implemented agent types
 /// Why: Remove unused complexity, follow YAGNI principle
 /// Alternative: Keep all planned types (rejected: premature abstraction)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 pub enum AgentType {
     SoftwareDeveloper,
     ProjectManager,
+    QualityAssurance,
     // Future agents can be added when actually implemented
 }
 
@@ -37,7 +45,7 @@ pub enum AgentType {
 ///
 /// Higher priority tasks are processed before lower priority ones
 /// when multiple tasks are queued.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd, ToSchema)]
 pub enum Priority {
     Low,
     Medium,
@@ -46,7 +54,7 @@ pub enum Priority {
 }
 
 /// Current status of a task in the processing pipeline
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub enum TaskStatus {
     Pending,
     InProgress,
@@ -66,6 +74,34 @@ pub struct TaskResult {
     pub result: TaskExecutionResult,
     pub metadata: HashMap<String, String>,
     pub completed_at: chrono::DateTime<chrono::Utc>,
+    /// What the task did to the files in its workspace
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FileChange>,
+}
+
+/// How a file in the workspace changed during a task
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// One file a task created, modified or deleted, with what changed in it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct FileChange {
+    /// Path relative to the task's workspace
+    pub path: String,
+    pub kind: FileChangeKind,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    /// Full content of a created file, unified diff otherwise; omitted for binary and
+    /// very large files and once the result's change budget is spent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The content was cut at the size cap or left out
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +123,34 @@ pub struct AgentCapability {
     pub description: String,
     pub supported_languages: Vec<String>,
     pub required_tools: Vec<String>,
+    /// Kinds of work the agent is suited for, matched against task content and skills
+    #[serde(default)]
+    pub task_categories: Vec<String>,
+    /// Most complex task the agent should be routed
+    #[serde(default)]
+    pub max_complexity: TaskComplexity,
+}
+
+/// Task complexity scale shared by analysis output and agent capabilities
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskComplexity {
+    Low,
+    Medium,
+    #[default]
+    High,
+    Critical,
+}
+
+impl TaskComplexity {
+    /// Parse the complexity label produced by task analysis, defaulting to Medium
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "low" => Self::Low,
+            "high" => Self::High,
+            "critical" => Self::Critical,
+            _ => Self::Medium,
+        }
+    }
 }
 
 /// Represents a message from Discord that needs processing
@@ -114,6 +178,8 @@ impl Task {
             status: TaskStatus::Pending,
             created_at: now,
             updated_at: now,
+            retry_count: 0,
+            max_retries: None,
         }
     }
 
@@ -121,6 +187,68 @@ impl Task {
         self.context.insert(key, value);
         self
     }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+}
+
+/// A group of tasks submitted together
+///
+/// Batches are admitted all or nothing: either every task fits in the
+/// queue or none of them are enqueued. Member tasks carry the batch ID in
+/// their context under `batch_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskBatch {
+    pub id: String,
+    pub task_ids: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TaskBatch {
+    /// Creates a new batch for the given task IDs with a unique batch ID
+    pub fn new(task_ids: Vec<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            task_ids,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Aggregate status counts for the tasks in a batch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TaskBatchStatus {
+    pub total: usize,
+    pub pending: usize,
+    pub in_progress: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+}
+
+impl TaskBatchStatus {
+    /// Tally the given task statuses
+    pub fn from_statuses<'a>(statuses: impl IntoIterator<Item = &'a TaskStatus>) -> Self {
+        let mut counts = Self::default();
+        for status in statuses {
+            counts.total += 1;
+            match status {
+                TaskStatus::Pending => counts.pending += 1,
+                TaskStatus::InProgress => counts.in_progress += 1,
+                TaskStatus::Completed => counts.completed += 1,
+                TaskStatus::Failed => counts.failed += 1,
+                TaskStatus::Cancelled => counts.cancelled += 1,
+            }
+        }
+        counts
+    }
+
+    /// True once no task in the batch is waiting or running
+    pub fn is_finished(&self) -> bool {
+        self.pending == 0 && self.in_progress == 0
+    }
 }
 
 impl FromStr for AgentType {
@@ -130,6 +258,7 @@ impl FromStr for AgentType {
         match s {
             "SoftwareDeveloper" => Ok(AgentType::SoftwareDeveloper),
             "ProjectManager" => Ok(AgentType::ProjectManager),
+            "QualityAssurance" => Ok(AgentType::QualityAssurance),
             // Only implemented agents
             _ => Err(format!("Unknown agent type: {s}")),
         }
@@ -141,6 +270,7 @@ impl AgentType {
         match mention.to_lowercase().as_str() {
             "dev" | "developer" | "code" => Some(AgentType::SoftwareDeveloper),
             "pm" | "manager" | "project" => Some(AgentType::ProjectManager),
+            "qa" | "quality" | "tester" => Some(AgentType::QualityAssurance),
             // Only implemented agents
             _ => None,
         }
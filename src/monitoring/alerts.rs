@@ -0,0 +1,645 @@
+//! Alerting on system metrics
+//!
+//! Each collection tick the monitor hands its metrics to the AlertEngine, which checks
+//! them against a list of rules such as "a circuit breaker has been open for 5 minutes" or
+//! "the task queue is 80% full". A rule that holds for its whole grace period fires once,
+//! and once it stops holding a resolve notice follows. Both go to every configured
+//! notifier: a Discord channel, a signed webhook, and email over SMTP.
+
+use super::{HealthStatus, SystemMetrics};
+use crate::agents::orchestrator::webhook::{
+    sign_payload, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
+use crate::claude_code::circuit_breaker::CircuitState;
+use crate::{Result, SpiralError};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// How urgently an alert needs attention
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    #[default]
+    Warning,
+    Critical,
+}
+
+/// A host resource a rule can watch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resource {
+    Memory,
+    Cpu,
+    Disk,
+}
+
+/// What a rule watches for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Any circuit breaker is open
+    CircuitOpen,
+    /// The task queue is at least `percent` full
+    QueueFull { percent: f64 },
+    /// A resource's usage is at or above `percent`
+    Resource { resource: Resource, percent: f64 },
+    /// Overall health is `status` or worse
+    Health { status: HealthStatus },
+}
+
+impl AlertCondition {
+    /// What is wrong, if the condition holds for these metrics
+    pub fn check(&self, metrics: &SystemMetrics) -> Option<String> {
+        match self {
+            Self::CircuitOpen => {
+                let mut open: Vec<&str> = metrics
+                    .circuit_breakers
+                    .iter()
+                    .filter(|(_, breaker)| breaker.state == CircuitState::Open)
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                open.sort_unstable();
+                (!open.is_empty()).then(|| format!("Circuit breaker open: {}", open.join(", ")))
+            }
+            Self::QueueFull { percent } => {
+                let capacity = metrics
+                    .orchestrator
+                    .as_ref()
+                    .map(|orchestrator| orchestrator.queue_capacity)
+                    .filter(|capacity| *capacity > 0)?;
+                let used = metrics.queue_size as f64 / capacity as f64 * 100.0;
+                (used >= *percent).then(|| {
+                    format!(
+                        "Task queue {used:.0}% full ({} of {capacity} tasks)",
+                        metrics.queue_size
+                    )
+                })
+            }
+            Self::Resource { resource, percent } => {
+                let usage = match resource {
+                    Resource::Memory => &metrics.memory_usage,
+                    Resource::Cpu => &metrics.cpu_usage,
+                    Resource::Disk => &metrics.disk_usage,
+                };
+                (usage.current >= *percent)
+                    .then(|| format!("{resource:?} usage at {:.1}%", usage.current))
+            }
+            Self::Health { status } => (metrics.health_status as u8 >= *status as u8)
+                .then(|| format!("System health is {:?}", metrics.health_status)),
+        }
+    }
+}
+
+/// A named condition, how long it must hold before firing, and how bad that is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Identifies the rule in notifications; unique within the rule list
+    pub name: String,
+    pub condition: AlertCondition,
+    /// Seconds the condition must hold before the rule fires; 0 fires on first sight
+    #[serde(default)]
+    pub for_secs: u64,
+    #[serde(default)]
+    pub severity: AlertSeverity,
+}
+
+/// Rules used when ALERT_RULES_FILE names none
+pub fn default_rules() -> Vec<AlertRule> {
+    vec![
+        AlertRule {
+            name: "circuit_breaker_open".to_string(),
+            condition: AlertCondition::CircuitOpen,
+            for_secs: crate::constants::ALERT_CIRCUIT_OPEN_SECS,
+            severity: AlertSeverity::Warning,
+        },
+        AlertRule {
+            name: "task_queue_filling".to_string(),
+            condition: AlertCondition::QueueFull {
+                percent: crate::constants::ALERT_QUEUE_FULL_PERCENT,
+            },
+            for_secs: 0,
+            severity: AlertSeverity::Warning,
+        },
+        AlertRule {
+            name: "disk_critical".to_string(),
+            condition: AlertCondition::Resource {
+                resource: Resource::Disk,
+                percent: super::MonitoringConfig::default().disk_critical_threshold,
+            },
+            for_secs: 0,
+            severity: AlertSeverity::Critical,
+        },
+        AlertRule {
+            name: "memory_critical".to_string(),
+            condition: AlertCondition::Resource {
+                resource: Resource::Memory,
+                percent: super::MonitoringConfig::default().memory_critical_threshold,
+            },
+            for_secs: 120,
+            severity: AlertSeverity::Critical,
+        },
+    ]
+}
+
+/// Whether an alert has just fired or just cleared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// One notification: a rule firing, or a fired rule clearing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub rule: String,
+    pub severity: AlertSeverity,
+    pub state: AlertState,
+    /// What was wrong when the rule fired
+    pub detail: String,
+    /// When the condition was first seen, before the grace period
+    pub started_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Alert {
+    /// One-line summary for chat and email subjects
+    pub fn headline(&self) -> String {
+        match self.state {
+            AlertState::Firing => format!("[{:?}] {} firing", self.severity, self.rule),
+            AlertState::Resolved => format!("[{:?}] {} resolved", self.severity, self.rule),
+        }
+    }
+
+    /// Headline plus detail, for chat and email bodies
+    pub fn describe(&self) -> String {
+        match self.resolved_at {
+            Some(resolved_at) => {
+                let minutes = (resolved_at - self.started_at).num_minutes();
+                format!(
+                    "{}\n{} (cleared after {minutes} min)",
+                    self.headline(),
+                    self.detail
+                )
+            }
+            None => format!(
+                "{}\n{} (since {})",
+                self.headline(),
+                self.detail,
+                self.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+            ),
+        }
+    }
+}
+
+/// 📣 ALERT NOTIFIER: One place alerts are delivered to
+#[async_trait]
+pub trait AlertNotifier: Send + Sync {
+    /// Names the channel in logs
+    fn name(&self) -> &str;
+
+    async fn notify(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Where a rule stands between ticks
+struct RuleState {
+    /// When the condition was first seen holding, in this episode
+    since: DateTime<Utc>,
+    /// The alert sent when the rule fired, if it has
+    firing: Option<Alert>,
+}
+
+/// 🚨 ALERT ENGINE: Turn metric snapshots into deduplicated alerts
+/// 🏗️ ARCHITECTURE DECISION: Rules are evaluated on the monitor's own snapshots, keeping
+/// only per-rule episode state
+/// Why: Every figure a rule needs is already collected each tick, and keeping the start
+/// of each episode is enough for grace periods, deduplication and resolve notices
+/// Alternative: Export to Prometheus and alert with Alertmanager (rejected: most
+/// deployments are a single VPS without a metrics stack)
+/// Trade-off: Rule state is in memory, so an alert firing across a restart is sent again
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    notifiers: Vec<Arc<dyn AlertNotifier>>,
+    states: Mutex<HashMap<String, RuleState>>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            notifiers: Vec::new(),
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn AlertNotifier>) -> Self {
+        self.notifiers.push(notifier);
+        self
+    }
+
+    /// An engine with the configured rules and a notifier for each configured channel
+    /// The Discord channel needs `discord_token`; webhook bodies are signed with
+    /// `signing_key` the way task callbacks are
+    pub fn from_config(
+        config: &crate::config::AlertingConfig,
+        discord_token: &str,
+        signing_key: Option<String>,
+    ) -> Result<Self> {
+        let mut engine = Self::new(config.rules.clone());
+        if let Some(channel) = config.discord_channel_id {
+            if discord_token.is_empty() {
+                warn!("ALERT_DISCORD_CHANNEL_ID is set without a Discord token; skipping it");
+            } else {
+                engine = engine
+                    .with_notifier(Arc::new(DiscordAlertNotifier::new(discord_token, channel)));
+            }
+        }
+        if let Some(url) = &config.webhook_url {
+            engine = engine.with_notifier(Arc::new(WebhookAlertNotifier::new(
+                url.clone(),
+                signing_key,
+            )));
+        }
+        if let Some(smtp) = &config.smtp {
+            engine = engine.with_notifier(Arc::new(EmailAlertNotifier::new(smtp)?));
+        }
+        Ok(engine)
+    }
+
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Alerts currently firing
+    pub fn active(&self) -> Vec<Alert> {
+        let states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        states
+            .values()
+            .filter_map(|state| state.firing.clone())
+            .collect()
+    }
+
+    /// Alerts that fired or resolved with these metrics; a rule still firing yields nothing
+    pub fn evaluate(&self, metrics: &SystemMetrics, now: DateTime<Utc>) -> Vec<Alert> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        let mut changes = Vec::new();
+        for rule in &self.rules {
+            match rule.condition.check(metrics) {
+                Some(detail) => {
+                    let state = states.entry(rule.name.clone()).or_insert(RuleState {
+                        since: now,
+                        firing: None,
+                    });
+                    let grace = Duration::seconds(rule.for_secs as i64);
+                    if state.firing.is_none() && now - state.since >= grace {
+                        let alert = Alert {
+                            rule: rule.name.clone(),
+                            severity: rule.severity,
+                            state: AlertState::Firing,
+                            detail,
+                            started_at: state.since,
+                            resolved_at: None,
+                        };
+                        state.firing = Some(alert.clone());
+                        changes.push(alert);
+                    }
+                }
+                None => {
+                    if let Some(alert) = states.remove(&rule.name).and_then(|s| s.firing) {
+                        changes.push(Alert {
+                            state: AlertState::Resolved,
+                            resolved_at: Some(now),
+                            ..alert
+                        });
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    /// Send each alert to every notifier, logging the ones that fail
+    pub async fn dispatch(&self, alerts: &[Alert]) {
+        let timeout = std::time::Duration::from_secs(crate::constants::ALERT_NOTIFY_TIMEOUT_SECS);
+        for alert in alerts {
+            info!("🚨 {}", alert.describe().replace('\n', ": "));
+            for notifier in &self.notifiers {
+                match tokio::time::timeout(timeout, notifier.notify(alert)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => warn!(
+                        "Failed to send alert {} via {}: {}",
+                        alert.rule,
+                        notifier.name(),
+                        e
+                    ),
+                    Err(_) => warn!(
+                        "Sending alert {} via {} timed out",
+                        alert.rule,
+                        notifier.name()
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// 💬 DISCORD ALERTS: Post alerts to one ops channel over the Discord HTTP API
+/// Uses the bot token without a gateway connection, so it works whether or not the bot
+/// runs in this process
+pub struct DiscordAlertNotifier {
+    http: Arc<serenity::http::Http>,
+    channel: serenity::model::id::ChannelId,
+}
+
+impl DiscordAlertNotifier {
+    pub fn new(token: &str, channel_id: u64) -> Self {
+        Self {
+            http: Arc::new(serenity::http::Http::new(token)),
+            channel: serenity::model::id::ChannelId::new(channel_id),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertNotifier for DiscordAlertNotifier {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let icon = match (alert.state, alert.severity) {
+            (AlertState::Resolved, _) => "✅",
+            (AlertState::Firing, AlertSeverity::Warning) => "⚠️",
+            (AlertState::Firing, AlertSeverity::Critical) => "🚨",
+        };
+        let (headline, detail) = alert
+            .describe()
+            .split_once('\n')
+            .map(|(headline, detail)| (headline.to_string(), detail.to_string()))
+            .unwrap_or_default();
+        self.channel
+            .say(&self.http, format!("{icon} **{headline}**\n{detail}"))
+            .await
+            .map(|_| ())
+            .map_err(|e| SpiralError::Discord(Box::new(e)))
+    }
+}
+
+/// 🔔 WEBHOOK ALERTS: POST each alert as JSON, signed like task callbacks
+/// The event header is `alert.firing` or `alert.resolved`
+pub struct WebhookAlertNotifier {
+    client: reqwest::Client,
+    url: String,
+    signing_key: Option<String>,
+}
+
+impl WebhookAlertNotifier {
+    pub fn new(url: String, signing_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            signing_key,
+        }
+    }
+}
+
+#[async_trait]
+impl AlertNotifier for WebhookAlertNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let body = serde_json::to_vec(alert)?;
+        let event = match alert.state {
+            AlertState::Firing => "alert.firing",
+            AlertState::Resolved => "alert.resolved",
+        };
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_EVENT_HEADER, event);
+        if let Some(key) = &self.signing_key {
+            let timestamp = Utc::now().timestamp();
+            request = request
+                .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+                .header(
+                    WEBHOOK_SIGNATURE_HEADER,
+                    sign_payload(key, timestamp, &body),
+                );
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| SpiralError::SystemError(format!("Alert webhook unreachable: {e}")))?;
+        if !response.status().is_success() {
+            return Err(SpiralError::SystemError(format!(
+                "Alert webhook answered {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// ✉️ EMAIL ALERTS: Mail each alert to the configured recipients over SMTP
+pub struct EmailAlertNotifier {
+    transport: lettre::AsyncSmtpTransport<lettre::Tokio1Executor>,
+    from: lettre::message::Mailbox,
+    to: Vec<lettre::message::Mailbox>,
+}
+
+impl EmailAlertNotifier {
+    pub fn new(config: &crate::config::SmtpConfig) -> Result<Self> {
+        use crate::config::SmtpTls;
+        use lettre::{AsyncSmtpTransport, Tokio1Executor};
+
+        let invalid = |what: &str, e: &dyn std::fmt::Display| {
+            SpiralError::ConfigurationError(format!("{what}: {e}"))
+        };
+        let builder = match config.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| invalid("ALERT_SMTP_HOST", &e))?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| invalid("ALERT_SMTP_HOST", &e))?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        };
+        let mut builder = builder
+            .port(config.port)
+            .timeout(Some(std::time::Duration::from_secs(
+                crate::constants::ALERT_NOTIFY_TIMEOUT_SECS,
+            )));
+        if let Some(username) = &config.username {
+            builder =
+                builder.credentials(lettre::transport::smtp::authentication::Credentials::new(
+                    username.clone(),
+                    config.password.clone().unwrap_or_default(),
+                ));
+        }
+
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| invalid("ALERT_EMAIL_FROM", &e))?;
+        let to = config
+            .to
+            .iter()
+            .map(|address| address.parse().map_err(|e| invalid("ALERT_EMAIL_TO", &e)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+}
+
+#[async_trait]
+impl AlertNotifier for EmailAlertNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        use lettre::AsyncTransport;
+
+        let mut message = lettre::Message::builder()
+            .from(self.from.clone())
+            .subject(format!("spiral-core: {}", alert.headline()));
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = message
+            .body(alert.describe())
+            .map_err(|e| SpiralError::SystemError(format!("Could not build alert email: {e}")))?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| SpiralError::SystemError(format!("SMTP server rejected the alert: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude_code::circuit_breaker::CircuitBreakerMetrics;
+    use crate::monitoring::{MonitoringConfig, OrchestratorMetrics, SystemMonitor};
+
+    async fn metrics() -> SystemMetrics {
+        SystemMonitor::new(MonitoringConfig::default())
+            .get_current_metrics()
+            .await
+    }
+
+    fn breaker(state: CircuitState) -> CircuitBreakerMetrics {
+        CircuitBreakerMetrics {
+            state,
+            failure_count: 0,
+            success_count: 0,
+            total_requests: 0,
+            total_failures: 0,
+            last_state_change_seconds: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rule_fires_after_grace_once_and_resolves() {
+        let engine = AlertEngine::new(default_rules());
+        let start = Utc::now();
+        let mut open = metrics().await;
+        open.circuit_breakers
+            .insert("claude_code".to_string(), breaker(CircuitState::Open));
+
+        assert!(engine.evaluate(&open, start).is_empty());
+        let fired = engine.evaluate(&open, start + Duration::minutes(5));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].rule, "circuit_breaker_open");
+        assert_eq!(fired[0].state, AlertState::Firing);
+        assert_eq!(fired[0].detail, "Circuit breaker open: claude_code");
+        // Still open: no repeat notification
+        assert!(engine
+            .evaluate(&open, start + Duration::minutes(10))
+            .is_empty());
+        assert_eq!(engine.active().len(), 1);
+
+        let resolved = engine.evaluate(&metrics().await, start + Duration::minutes(12));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].state, AlertState::Resolved);
+        assert_eq!(resolved[0].started_at, start);
+        assert!(engine.active().is_empty());
+
+        // Closing before the grace period ends never fires
+        engine.evaluate(&open, start + Duration::minutes(20));
+        assert!(engine
+            .evaluate(&metrics().await, start + Duration::minutes(22))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queue_and_resource_conditions_fire_immediately() {
+        let engine = AlertEngine::new(default_rules());
+        let mut busy = metrics().await;
+        busy.queue_size = 850;
+        busy.orchestrator = Some(OrchestratorMetrics {
+            queue_capacity: 1000,
+            ..OrchestratorMetrics::default()
+        });
+        busy.disk_usage.current = 97.0;
+
+        let mut fired: Vec<(String, AlertSeverity)> = engine
+            .evaluate(&busy, Utc::now())
+            .into_iter()
+            .map(|alert| (alert.rule, alert.severity))
+            .collect();
+        fired.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            fired,
+            vec![
+                ("disk_critical".to_string(), AlertSeverity::Critical),
+                ("task_queue_filling".to_string(), AlertSeverity::Warning),
+            ]
+        );
+        assert_eq!(
+            AlertCondition::QueueFull { percent: 80.0 }.check(&busy),
+            Some("Task queue 85% full (850 of 1000 tasks)".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notifier_posts_signed_alert() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/alerts")
+            .match_header(WEBHOOK_EVENT_HEADER, "alert.firing")
+            .match_header(
+                WEBHOOK_SIGNATURE_HEADER,
+                mockito::Matcher::Regex("^sha256=".into()),
+            )
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"rule":"disk_critical","state":"firing"}"#.to_string(),
+            ))
+            .with_status(204)
+            .create_async()
+            .await;
+        let notifier =
+            WebhookAlertNotifier::new(format!("{}/alerts", server.url()), Some("key".into()));
+        let alert = Alert {
+            rule: "disk_critical".to_string(),
+            severity: AlertSeverity::Critical,
+            state: AlertState::Firing,
+            detail: "Disk usage at 97.0%".to_string(),
+            started_at: Utc::now(),
+            resolved_at: None,
+        };
+
+        notifier.notify(&alert).await.unwrap();
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,134 @@
+//! Availability over the last day, week and month
+//!
+//! Each metrics snapshot's health is taken to hold until the next snapshot, so the
+//! history gives the time spent healthy, degraded and unhealthy. A gap between snapshots
+//! much longer than the collection interval means nothing was collecting, i.e. the
+//! service was down, and counts against availability.
+
+use super::{HealthStatus, SystemMetrics};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Time spent in each health state over one window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AvailabilityWindow {
+    /// "24h", "7d" or "30d"
+    pub window: String,
+    pub window_secs: u64,
+    /// Seconds since the first snapshot in the window; earlier time is not counted
+    pub monitored_secs: u64,
+    pub healthy_secs: u64,
+    pub degraded_secs: u64,
+    /// Unhealthy or critical
+    pub unhealthy_secs: u64,
+    /// Gaps in the history, when the service was not running
+    pub down_secs: u64,
+    /// Share of the monitored time spent healthy or degraded; None without snapshots
+    pub availability_percent: Option<f64>,
+    /// Share of the monitored time spent healthy; None without snapshots
+    pub healthy_percent: Option<f64>,
+}
+
+/// Availability over each reporting window, shortest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AvailabilityReport {
+    /// Unix seconds the windows end at
+    pub generated_at: u64,
+    pub windows: Vec<AvailabilityWindow>,
+}
+
+/// 📈 AVAILABILITY: Time in each health state between `since` and `until`
+/// `samples` are oldest first; one covers at most `max_gap` seconds and the rest of a
+/// longer gap counts as down
+/// 🏗️ ARCHITECTURE DECISION: Derive availability from the stored snapshots
+/// Why: The health of every collection is already persisted, so the figures cover
+/// restarts and need no second record of state changes
+/// Alternative: Log each health transition (rejected: a crash between transitions loses
+/// the downtime, which the gap in snapshots shows)
+/// Trade-off: Five-minute rollups keep their worst health, so week and month windows
+/// count a briefly degraded window as degraded throughout
+pub fn summarize(
+    window: &str,
+    samples: &[SystemMetrics],
+    since: u64,
+    until: u64,
+    max_gap: u64,
+) -> AvailabilityWindow {
+    let mut summary = AvailabilityWindow {
+        window: window.to_string(),
+        window_secs: until.saturating_sub(since),
+        ..AvailabilityWindow::default()
+    };
+    let samples: Vec<&SystemMetrics> = samples
+        .iter()
+        .filter(|sample| sample.timestamp <= until)
+        .collect();
+
+    for (i, sample) in samples.iter().enumerate() {
+        let start = sample.timestamp.max(since);
+        let end = samples
+            .get(i + 1)
+            .map_or(until, |next| next.timestamp)
+            .min(until);
+        if end <= start {
+            continue;
+        }
+        let covered = (end - start).min(max_gap);
+        match sample.health_status {
+            HealthStatus::Healthy => summary.healthy_secs += covered,
+            HealthStatus::Degraded => summary.degraded_secs += covered,
+            HealthStatus::Unhealthy | HealthStatus::Critical => summary.unhealthy_secs += covered,
+        }
+        summary.down_secs += end - start - covered;
+    }
+
+    if let Some(first) = samples.iter().find(|sample| sample.timestamp < until) {
+        summary.monitored_secs = until - first.timestamp.max(since);
+    }
+    if summary.monitored_secs > 0 {
+        let percent = |secs: u64| secs as f64 / summary.monitored_secs as f64 * 100.0;
+        summary.availability_percent = Some(percent(summary.healthy_secs + summary.degraded_secs));
+        summary.healthy_percent = Some(percent(summary.healthy_secs));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::{MonitoringConfig, SystemMonitor};
+
+    #[tokio::test]
+    async fn test_states_are_timed_until_the_next_sample_and_long_gaps_count_as_down() {
+        let template = SystemMonitor::new(MonitoringConfig::default())
+            .get_current_metrics()
+            .await;
+        let sample = |timestamp: u64, health_status: HealthStatus| SystemMetrics {
+            timestamp,
+            health_status,
+            ..template.clone()
+        };
+        let samples = vec![
+            sample(900, HealthStatus::Unhealthy),
+            sample(1000, HealthStatus::Healthy),
+            sample(1030, HealthStatus::Healthy),
+            sample(1060, HealthStatus::Degraded),
+            sample(1090, HealthStatus::Critical),
+            // Down from 1180 until the restart at 1400
+            sample(1400, HealthStatus::Healthy),
+        ];
+
+        let summary = summarize("24h", &samples, 1000, 1430, 90);
+        assert_eq!(summary.window_secs, 430);
+        assert_eq!(summary.monitored_secs, 430);
+        assert_eq!(summary.healthy_secs, 90);
+        assert_eq!(summary.degraded_secs, 30);
+        assert_eq!(summary.unhealthy_secs, 90);
+        assert_eq!(summary.down_secs, 220);
+        let availability = summary.availability_percent.unwrap();
+        assert!((availability - 120.0 / 430.0 * 100.0).abs() < 1e-9);
+
+        let empty = summarize("7d", &[], 1000, 1430, 90);
+        assert_eq!(empty.availability_percent, None);
+    }
+}
@@ -0,0 +1,405 @@
+//! Resource usage collection for the system monitor
+//!
+//! The monitor asks a MetricsCollector for memory, CPU and disk usage on every tick. On
+//! Linux they are read from /proc and the filesystem itself, with no child processes;
+//! other platforms get what they can report, and tests substitute their own collector.
+//!
+//! Besides the host, the collector can report on spiral-core itself: its memory, CPU and
+//! open file descriptors, and the Claude CLI processes it has started, so a leak in the
+//! service shows up even on a busy host.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Resource usage as percentages; None where the platform can't tell
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceSample {
+    pub memory_percent: Option<f64>,
+    pub cpu_percent: Option<f64>,
+    pub disk_percent: Option<f64>,
+}
+
+/// 🔬 PROCESS METRICS: What spiral-core itself and its Claude CLI processes use
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessMetrics {
+    /// Resident memory of this process
+    pub rss_mb: f64,
+    /// Share of the host's CPU time this process used since the previous sample
+    pub cpu_percent: Option<f64>,
+    pub open_fds: usize,
+    /// Claude CLI processes this process started, sandbox wrappers included
+    pub claude_processes: usize,
+    /// Resident memory of those processes and every process they started
+    pub claude_rss_mb: f64,
+}
+
+/// 📏 METRICS COLLECTOR: Where the monitor's resource figures come from
+/// 🏗️ ARCHITECTURE DECISION: A trait with one implementation per platform
+/// Why: Reading /proc is cheap and can't hang, unlike spawning vm_stat, top and df on
+/// every tick, and tests can feed the monitor exact figures
+/// Alternative: The sysinfo crate (rejected: a large dependency for three numbers)
+/// Trade-off: Platforms without an implementation report no memory or CPU usage
+pub trait MetricsCollector: Send + Sync {
+    /// Usage right now; CPU usage is measured since the previous sample
+    fn sample(&self) -> ResourceSample;
+
+    /// This process's own usage, counting child processes whose command line names
+    /// `claude_binary` as Claude processes; None where the platform can't tell
+    fn process(&self, _claude_binary: &str) -> Option<ProcessMetrics> {
+        None
+    }
+}
+
+/// The collector for this platform, measuring the disk holding the working directory
+pub fn platform_collector() -> Arc<dyn MetricsCollector> {
+    #[cfg(target_os = "linux")]
+    {
+        Arc::new(ProcCollector::new())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Arc::new(DiskOnlyCollector {
+            disk_path: PathBuf::from("."),
+        })
+    }
+}
+
+/// CPU time counters from /proc/stat, in clock ticks
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+/// 🐧 PROC COLLECTOR: Memory and CPU from /proc, disk from statvfs
+pub struct ProcCollector {
+    proc_root: PathBuf,
+    disk_path: PathBuf,
+    /// Counters of the previous sample, which CPU usage is measured against
+    last_cpu: Mutex<Option<CpuTimes>>,
+    /// This process's CPU ticks and the host's total at the previous process sample
+    last_process_cpu: Mutex<Option<(u64, u64)>>,
+}
+
+impl Default for ProcCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcCollector {
+    pub fn new() -> Self {
+        Self::with_paths("/proc", ".")
+    }
+
+    /// Read `meminfo` and `stat` under `proc_root` and measure the disk holding `disk_path`
+    pub fn with_paths(proc_root: impl Into<PathBuf>, disk_path: impl Into<PathBuf>) -> Self {
+        let collector = Self {
+            proc_root: proc_root.into(),
+            disk_path: disk_path.into(),
+            last_cpu: Mutex::new(None),
+            last_process_cpu: Mutex::new(None),
+        };
+        // Prime the counters so the first tick already reports CPU usage
+        *collector.last_cpu.lock().unwrap_or_else(|e| e.into_inner()) = collector.read_cpu_times();
+        collector
+    }
+
+    fn read(&self, file: &str) -> Option<String> {
+        std::fs::read_to_string(self.proc_root.join(file))
+            .map_err(|e| debug!("Failed to read {} from /proc: {}", file, e))
+            .ok()
+    }
+
+    fn read_cpu_times(&self) -> Option<CpuTimes> {
+        self.read("stat").as_deref().and_then(parse_cpu_times)
+    }
+
+    /// Resident memory of a process in MB, 0 for kernel threads and vanished processes
+    fn rss_mb(&self, pid: &str) -> f64 {
+        self.read(&format!("{pid}/status"))
+            .as_deref()
+            .and_then(|status| status_kb(status, "VmRSS:"))
+            .map_or(0.0, |kb| kb as f64 / 1024.0)
+    }
+
+    /// Parent of every process, from each `<pid>/stat`
+    fn parents(&self) -> HashMap<String, String> {
+        let Ok(entries) = std::fs::read_dir(&self.proc_root) else {
+            return HashMap::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let pid = entry.file_name().into_string().ok()?;
+                if !pid.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+                Some((pid, parse_stat(&stat)?.parent))
+            })
+            .collect()
+    }
+
+    /// Whether a process was started as `claude_binary`, directly or through a wrapper
+    fn runs(&self, pid: &str, claude_binary: &str) -> bool {
+        let wanted = Path::new(claude_binary).file_name();
+        self.read(&format!("{pid}/cmdline")).is_some_and(|cmdline| {
+            cmdline
+                .split('\0')
+                .any(|arg| !arg.is_empty() && Path::new(arg).file_name() == wanted)
+        })
+    }
+}
+
+impl MetricsCollector for ProcCollector {
+    fn sample(&self) -> ResourceSample {
+        let current = self.read_cpu_times();
+        let previous = std::mem::replace(
+            &mut *self.last_cpu.lock().unwrap_or_else(|e| e.into_inner()),
+            current,
+        );
+        let cpu_percent = match (previous, current) {
+            (Some(previous), Some(current)) if current.total > previous.total => {
+                let busy = current.busy.saturating_sub(previous.busy);
+                Some(busy as f64 / (current.total - previous.total) as f64 * 100.0)
+            }
+            _ => None,
+        };
+
+        ResourceSample {
+            memory_percent: self.read("meminfo").as_deref().and_then(parse_meminfo),
+            cpu_percent,
+            disk_percent: disk_percent(&self.disk_path),
+        }
+    }
+
+    fn process(&self, claude_binary: &str) -> Option<ProcessMetrics> {
+        let own = parse_stat(&self.read("self/stat")?)?;
+
+        let host_ticks = self.read_cpu_times().map(|times| times.total);
+        let cpu_percent = host_ticks.and_then(|host| {
+            let previous = self
+                .last_process_cpu
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .replace((own.cpu_ticks, host));
+            let (own_before, host_before) = previous?;
+            (host > host_before).then(|| {
+                own.cpu_ticks.saturating_sub(own_before) as f64 / (host - host_before) as f64
+                    * 100.0
+            })
+        });
+
+        let open_fds = std::fs::read_dir(self.proc_root.join("self/fd"))
+            .map(|fds| fds.count())
+            .unwrap_or(0);
+
+        // Claude processes are our children running the binary; count the memory of
+        // everything under them, since the CLI runs node and tools of its own
+        let parents = self.parents();
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (pid, parent) in &parents {
+            children
+                .entry(parent.as_str())
+                .or_default()
+                .push(pid.as_str());
+        }
+        let claude: Vec<&str> = children
+            .get(own.pid.as_str())
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|pid| self.runs(pid, claude_binary))
+            .collect();
+        let mut claude_rss_mb = 0.0;
+        let mut pending = claude.clone();
+        while let Some(pid) = pending.pop() {
+            claude_rss_mb += self.rss_mb(pid);
+            pending.extend(children.get(pid).into_iter().flatten().copied());
+        }
+
+        Some(ProcessMetrics {
+            rss_mb: self.rss_mb("self"),
+            cpu_percent,
+            open_fds,
+            claude_processes: claude.len(),
+            claude_rss_mb,
+        })
+    }
+}
+
+/// Disk usage alone, for platforms without /proc
+#[cfg(not(target_os = "linux"))]
+struct DiskOnlyCollector {
+    disk_path: PathBuf,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl MetricsCollector for DiskOnlyCollector {
+    fn sample(&self) -> ResourceSample {
+        ResourceSample {
+            disk_percent: disk_percent(&self.disk_path),
+            ..ResourceSample::default()
+        }
+    }
+}
+
+/// Share of memory in use, from the MemTotal and MemAvailable lines of /proc/meminfo
+fn parse_meminfo(meminfo: &str) -> Option<f64> {
+    let total = status_kb(meminfo, "MemTotal:").filter(|total| *total > 0)?;
+    let available = status_kb(meminfo, "MemAvailable:")?;
+    Some(total.saturating_sub(available) as f64 / total as f64 * 100.0)
+}
+
+/// A value in kB from a `/proc/<pid>/status` line such as `VmRSS:   1234 kB`
+fn status_kb(status: &str, name: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(name))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// The fields of `/proc/<pid>/stat` the collector needs
+struct ProcStat {
+    pid: String,
+    parent: String,
+    /// User plus system time, in clock ticks
+    cpu_ticks: u64,
+}
+
+/// Parse `/proc/<pid>/stat`; the command name may hold spaces and parentheses, so fields
+/// are counted from its closing parenthesis
+fn parse_stat(stat: &str) -> Option<ProcStat> {
+    let (pid, rest) = stat.split_once(' ')?;
+    let fields: Vec<&str> = rest.rsplit_once(')')?.1.split_whitespace().collect();
+    // state ppid ... utime is the 12th field after the name, stime the 13th
+    let ticks = |index: usize| fields.get(index)?.parse::<u64>().ok();
+    Some(ProcStat {
+        pid: pid.to_string(),
+        parent: fields.get(1)?.to_string(),
+        cpu_ticks: ticks(11)? + ticks(12)?,
+    })
+}
+
+/// Busy and total time from the aggregate `cpu` line of /proc/stat; idle and iowait are
+/// idle, everything else is busy
+fn parse_cpu_times(stat: &str) -> Option<CpuTimes> {
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let times: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .map_while(|field| field.parse().ok())
+        .collect();
+    if times.len() < 4 {
+        return None;
+    }
+    // user nice system idle iowait irq softirq steal; guest time is already in user
+    let total: u64 = times.iter().take(8).sum();
+    let idle = times[3] + times.get(4).copied().unwrap_or(0);
+    Some(CpuTimes {
+        busy: total - idle,
+        total,
+    })
+}
+
+/// Share of the filesystem holding `path` in use, as df reports it
+#[cfg(unix)]
+fn disk_percent(path: &Path) -> Option<f64> {
+    let stats = rustix::fs::statvfs(path)
+        .map_err(|e| debug!("Failed to stat filesystem of {}: {}", path.display(), e))
+        .ok()?;
+    let used = stats.f_blocks.saturating_sub(stats.f_bfree);
+    // Blocks reserved for root count as neither used nor available
+    let usable = used + stats.f_bavail;
+    (usable > 0).then(|| used as f64 / usable as f64 * 100.0)
+}
+
+#[cfg(not(unix))]
+fn disk_percent(_path: &Path) -> Option<f64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc_collector_reads_memory_cpu_and_disk() {
+        let proc_root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            proc_root.path().join("meminfo"),
+            "MemTotal:       1000 kB\nMemFree:         100 kB\nMemAvailable:    250 kB\n",
+        )
+        .unwrap();
+        let stat =
+            |busy: u64, idle: u64| format!("cpu  {busy} 0 0 {idle} 0 0 0 0 0 0\ncpu0 1 0 0 1\n");
+        std::fs::write(proc_root.path().join("stat"), stat(100, 900)).unwrap();
+
+        let collector = ProcCollector::with_paths(proc_root.path(), proc_root.path());
+        std::fs::write(proc_root.path().join("stat"), stat(130, 970)).unwrap();
+        let sample = collector.sample();
+
+        assert_eq!(sample.memory_percent, Some(75.0));
+        // 30 of the 100 ticks since priming were busy
+        assert_eq!(sample.cpu_percent, Some(30.0));
+        let disk = sample.disk_percent.unwrap();
+        assert!((0.0..=100.0).contains(&disk));
+
+        // Counters that didn't move give no CPU figure rather than a made-up one
+        assert_eq!(collector.sample().cpu_percent, None);
+        assert_eq!(parse_meminfo("MemFree: 5 kB"), None);
+    }
+
+    #[test]
+    fn test_process_metrics_count_own_usage_and_claude_children() {
+        let proc_root = tempfile::tempdir().unwrap();
+        let process = |dir: &str, pid: u32, parent: u32, ticks: u64, rss_kb: u64, cmdline: &str| {
+            let dir = proc_root.path().join(dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("stat"),
+                format!("{pid} (my (odd) name) S {parent} 0 0 0 0 0 0 0 0 0 {ticks} 0 20 0"),
+            )
+            .unwrap();
+            std::fs::write(
+                dir.join("status"),
+                format!("Name:\tx\nVmRSS:\t{rss_kb} kB\n"),
+            )
+            .unwrap();
+            std::fs::write(dir.join("cmdline"), cmdline.replace(' ', "\0")).unwrap();
+        };
+        let host = |total: u64| format!("cpu  {total} 0 0 0 0 0 0 0 0 0\n");
+        std::fs::write(proc_root.path().join("stat"), host(1000)).unwrap();
+        process("self", 100, 1, 50, 2048, "spiral-core");
+        process("100", 100, 1, 50, 2048, "spiral-core");
+        process("200", 200, 100, 0, 1024, "node /opt/bin/claude -p");
+        process("300", 300, 200, 0, 512, "cargo test");
+        process("400", 400, 100, 0, 4096, "git status");
+        process("500", 500, 1, 0, 4096, "claude -p");
+        let fds = proc_root.path().join("self/fd");
+        std::fs::create_dir_all(&fds).unwrap();
+        for fd in 0..3 {
+            std::fs::write(fds.join(fd.to_string()), "").unwrap();
+        }
+
+        let collector = ProcCollector::with_paths(proc_root.path(), proc_root.path());
+        let first = collector.process("/usr/local/bin/claude").unwrap();
+        assert_eq!(first.cpu_percent, None);
+        assert_eq!(first.rss_mb, 2.0);
+        assert_eq!(first.open_fds, 3);
+        // Only our own claude child counts, with the tool it started
+        assert_eq!(first.claude_processes, 1);
+        assert_eq!(first.claude_rss_mb, 1.5);
+
+        std::fs::write(proc_root.path().join("stat"), host(1200)).unwrap();
+        process("self", 100, 1, 70, 2048, "spiral-core");
+        let second = collector.process("claude").unwrap();
+        // 20 of the host's 200 ticks since the first sample were ours
+        assert_eq!(second.cpu_percent, Some(10.0));
+    }
+}
@@ -0,0 +1,363 @@
+//! Panic capture and post-mortem reports
+//!
+//! A panic hook writes every panic, with its backtrace and the subsystem it happened in,
+//! to a JSON report in the crash directory. Long-running subsystems such as the Discord
+//! handler and the monitor loop run under `supervise`, which restarts them after a panic
+//! until they crash too often, and then leaves them stopped. The monitor sends each crash
+//! to the alert channels and holds health down while crashes are recent.
+
+use super::alerts::{Alert, AlertSeverity, AlertState};
+use crate::config::CrashConfig;
+use crate::constants::{
+    CRASH_HEALTH_WINDOW_SECS, CRASH_RECENT_REPORTS, CRASH_RESTART_BACKOFF_SECS,
+    CRASH_RESTART_WINDOW_SECS,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::{BTreeSet, VecDeque};
+use std::future::Future;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+tokio::task_local! {
+    /// The supervised subsystem the running task belongs to
+    static SUBSYSTEM: &'static str;
+}
+
+/// What was captured about one panic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: uuid::Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Supervised subsystem that panicked; None for panics outside one
+    pub subsystem: Option<String>,
+    pub thread: Option<String>,
+    /// Tokio task that panicked, when it happened in one
+    pub task_id: Option<String>,
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Where the report was written; None when it was only logged
+    pub path: Option<String>,
+}
+
+impl CrashReport {
+    /// The notification sent to the alert channels
+    pub fn to_alert(&self) -> Alert {
+        let subsystem = self.subsystem.as_deref().unwrap_or("a background task");
+        let mut detail = format!(
+            "Panic in {subsystem}: {} at {}",
+            self.message,
+            self.location.as_deref().unwrap_or("unknown location")
+        );
+        if let Some(path) = &self.path {
+            detail.push_str(&format!(" (report: {path})"));
+        }
+        Alert {
+            rule: "panic".to_string(),
+            severity: AlertSeverity::Critical,
+            state: AlertState::Firing,
+            detail,
+            started_at: self.timestamp,
+            resolved_at: None,
+        }
+    }
+}
+
+/// Panics seen by the crash reporter, for SystemMetrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrashMetrics {
+    /// Panics since startup
+    pub total: u64,
+    /// Panics within the last CRASH_HEALTH_WINDOW_SECS
+    pub recent: usize,
+    /// Supervised subsystems that crashed and were not restarted
+    pub failed_subsystems: Vec<String>,
+}
+
+struct CrashState {
+    total: u64,
+    recent: VecDeque<(Instant, CrashReport)>,
+    failed: BTreeSet<String>,
+}
+
+struct Inner {
+    report_dir: Option<PathBuf>,
+    restart: bool,
+    max_restarts: u32,
+    backoff: Duration,
+    state: Mutex<CrashState>,
+    events: broadcast::Sender<CrashReport>,
+}
+
+/// 💥 CRASH REPORTER: Record panics and keep supervised subsystems running
+/// 🏗️ ARCHITECTURE DECISION: Capture in the panic hook, restart in a supervisor that
+/// awaits the subsystem's JoinHandle
+/// Why: Only the hook sees the backtrace and location, while only the awaiting task can
+/// tell a panic from a normal exit and start the subsystem again
+/// Alternative: catch_unwind around each subsystem (rejected: needs UnwindSafe futures and
+/// still has no backtrace)
+/// Trade-off: Tasks a subsystem spawns itself are not supervised; their panics are
+/// reported but only restart the subsystem if they bring it down
+#[derive(Clone)]
+pub struct CrashReporter {
+    inner: Arc<Inner>,
+}
+
+impl CrashReporter {
+    pub fn new(config: &CrashConfig) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            inner: Arc::new(Inner {
+                report_dir: config.report_dir.as_ref().map(PathBuf::from),
+                restart: config.restart,
+                max_restarts: config.max_restarts,
+                backoff: Duration::from_secs(CRASH_RESTART_BACKOFF_SECS),
+                state: Mutex::new(CrashState {
+                    total: 0,
+                    recent: VecDeque::new(),
+                    failed: BTreeSet::new(),
+                }),
+                events,
+            }),
+        }
+    }
+
+    /// 🪝 PANIC HOOK: Report every panic in the process, then run the previous hook
+    pub fn install_panic_hook(&self) {
+        let reporter = self.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            reporter.record(capture(info));
+            previous(info);
+        }));
+    }
+
+    /// Write `report` to the crash directory, keep it for metrics and announce it
+    pub fn record(&self, mut report: CrashReport) {
+        if let Some(dir) = &self.inner.report_dir {
+            match write_report(dir, &report) {
+                Ok(path) => report.path = Some(path.to_string_lossy().into_owned()),
+                Err(e) => warn!("Failed to write crash report to {:?}: {}", dir, e),
+            }
+        }
+        error!(
+            "💥 Panic in {}: {} at {}{}",
+            report.subsystem.as_deref().unwrap_or("unsupervised task"),
+            report.message,
+            report.location.as_deref().unwrap_or("unknown location"),
+            report
+                .path
+                .as_deref()
+                .map(|path| format!(" (report: {path})"))
+                .unwrap_or_default()
+        );
+
+        let mut state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.total += 1;
+        state.recent.push_back((Instant::now(), report.clone()));
+        while state.recent.len() > CRASH_RECENT_REPORTS {
+            state.recent.pop_front();
+        }
+        drop(state);
+        // Nobody listening only means no monitor is running
+        let _ = self.inner.events.send(report);
+    }
+
+    /// Receive each crash as it is recorded
+    pub fn subscribe(&self) -> broadcast::Receiver<CrashReport> {
+        self.inner.events.subscribe()
+    }
+
+    /// The most recent crash reports, newest first
+    pub fn recent_reports(&self) -> Vec<CrashReport> {
+        let state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .recent
+            .iter()
+            .rev()
+            .map(|(_, report)| report.clone())
+            .collect()
+    }
+
+    pub fn metrics(&self) -> CrashMetrics {
+        let window = Duration::from_secs(CRASH_HEALTH_WINDOW_SECS);
+        let state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+        CrashMetrics {
+            total: state.total,
+            recent: state
+                .recent
+                .iter()
+                .filter(|(at, _)| at.elapsed() <= window)
+                .count(),
+            failed_subsystems: state.failed.iter().cloned().collect(),
+        }
+    }
+
+    /// 🔁 SUPERVISE: Run the future `start` makes as `subsystem`, starting it again after
+    /// a panic while restarts are enabled and it has not used up its restarts this hour
+    /// The returned handle finishes when the subsystem returns or is left stopped
+    pub fn supervise<F, Fut>(&self, subsystem: &'static str, start: F) -> JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let reporter = self.clone();
+        tokio::spawn(async move {
+            let window = Duration::from_secs(CRASH_RESTART_WINDOW_SECS);
+            let mut restarts: VecDeque<Instant> = VecDeque::new();
+            loop {
+                match tokio::spawn(SUBSYSTEM.scope(subsystem, start())).await {
+                    Ok(()) => return,
+                    Err(e) if e.is_cancelled() => return,
+                    Err(_) => {}
+                }
+
+                restarts.retain(|at| at.elapsed() < window);
+                let inner = &reporter.inner;
+                if !inner.restart || restarts.len() >= inner.max_restarts as usize {
+                    error!("{} crashed and will not be restarted", subsystem);
+                    inner
+                        .state
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .failed
+                        .insert(subsystem.to_string());
+                    return;
+                }
+                restarts.push_back(Instant::now());
+                warn!(
+                    "Restarting {} after a crash ({} of {} restarts this hour)",
+                    subsystem,
+                    restarts.len(),
+                    inner.max_restarts
+                );
+                tokio::time::sleep(inner.backoff).await;
+            }
+        })
+    }
+}
+
+/// The report of the panic `info` describes, taken on the panicking thread
+fn capture(info: &PanicHookInfo<'_>) -> CrashReport {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string());
+    CrashReport {
+        id: uuid::Uuid::new_v4(),
+        timestamp: Utc::now(),
+        subsystem: SUBSYSTEM.try_with(|name| name.to_string()).ok(),
+        thread: std::thread::current().name().map(str::to_string),
+        task_id: tokio::task::try_id().map(|id| id.to_string()),
+        message,
+        location: info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: Backtrace::force_capture().to_string(),
+        path: None,
+    }
+}
+
+/// Write `report` as `crash-<time>-<id>.json` in `dir`
+fn write_report(dir: &std::path::Path, report: &CrashReport) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "crash-{}-{}.json",
+        report.timestamp.format("%Y%m%dT%H%M%SZ"),
+        report.id.simple()
+    ));
+    let json = serde_json::to_string_pretty(report).map_err(std::io::Error::other)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn reporter(max_restarts: u32) -> CrashReporter {
+        let mut reporter = CrashReporter::new(&CrashConfig {
+            report_dir: None,
+            restart: true,
+            max_restarts,
+        });
+        Arc::get_mut(&mut reporter.inner).unwrap().backoff = Duration::ZERO;
+        reporter
+    }
+
+    #[tokio::test]
+    async fn test_supervise_restarts_until_restarts_run_out() {
+        // Panics twice, then runs to completion
+        let recovering = reporter(5);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        recovering
+            .supervise("flaky", move || {
+                let counter = Arc::clone(&counter);
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                        panic!("flaky start");
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(recovering.metrics().failed_subsystems.is_empty());
+
+        // Always panics: started once, restarted once, then left stopped
+        let giving_up = reporter(1);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        giving_up
+            .supervise("broken", move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { panic!("always broken") }
+            })
+            .await
+            .unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(giving_up.metrics().failed_subsystems, vec!["broken"]);
+    }
+
+    #[test]
+    fn test_record_writes_the_report_and_counts_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let reporter = CrashReporter::new(&CrashConfig {
+            report_dir: Some(dir.path().to_string_lossy().into_owned()),
+            ..CrashConfig::default()
+        });
+        let mut events = reporter.subscribe();
+        reporter.record(CrashReport {
+            id: uuid::Uuid::new_v4(),
+            timestamp: Utc::now(),
+            subsystem: Some("discord".to_string()),
+            thread: None,
+            task_id: None,
+            message: "index out of bounds".to_string(),
+            location: Some("src/discord/mod.rs:10:5".to_string()),
+            backtrace: String::new(),
+            path: None,
+        });
+
+        let report = events.try_recv().unwrap();
+        let written: CrashReport =
+            serde_json::from_str(&std::fs::read_to_string(report.path.as_ref().unwrap()).unwrap())
+                .unwrap();
+        assert_eq!(written.message, "index out of bounds");
+        assert!(report.to_alert().detail.contains("Panic in discord"));
+        let metrics = reporter.metrics();
+        assert_eq!((metrics.total, metrics.recent), (1, 1));
+    }
+}
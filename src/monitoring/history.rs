@@ -0,0 +1,374 @@
+//! Metrics history persisted to a SQLite database file
+//!
+//! Every collection is kept as raw JSON for a day. Each completed five-minute window is
+//! also rolled up into one snapshot that is kept for a month, so week-long trends
+//! survive restarts without keeping every 30-second sample.
+
+use super::{HealthStatus, ResourceMetrics, SystemMetrics};
+use crate::constants::{
+    METRICS_RAW_RETENTION_SECS, METRICS_ROLLUP_RETENTION_SECS, METRICS_ROLLUP_SECS,
+};
+use crate::{Result, SpiralError};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS metrics_raw (
+        timestamp INTEGER PRIMARY KEY,
+        snapshot TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS metrics_rollup (
+        bucket INTEGER PRIMARY KEY,
+        snapshot TEXT NOT NULL
+    );
+";
+
+/// How finely history is returned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum HistoryResolution {
+    /// Every collected snapshot; only the last day is kept
+    #[serde(rename = "raw")]
+    Raw,
+    /// One snapshot per five minutes, kept for 30 days
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    /// Raw when the whole range is still kept raw, five-minute otherwise
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+impl HistoryResolution {
+    /// Raw or FiveMinutes: what Auto means for a range starting at `since`
+    pub fn resolve(self, since: u64, now: u64) -> Self {
+        match self {
+            Self::Auto if since >= now.saturating_sub(METRICS_RAW_RETENTION_SECS) => Self::Raw,
+            Self::Auto => Self::FiveMinutes,
+            resolution => resolution,
+        }
+    }
+}
+
+/// Metrics snapshots in a SQLite file, surviving restarts
+/// 🏗️ ARCHITECTURE DECISION: Raw and rolled-up tables in one file, rolled up as samples
+/// are written
+/// Why: Same shape as the session and agent memory databases; rolling up on write keeps
+/// queries to a range scan and needs no second background task
+/// Alternative: An append-only file per day (rejected: range queries and retention would
+/// mean scanning and rewriting files)
+/// Trade-off: A rollup is written when the next window's first sample arrives, so the
+/// newest window is computed from raw samples at query time
+#[derive(Clone)]
+pub struct MetricsHistoryStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl MetricsHistoryStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let connection = Connection::open(path).map_err(|e| {
+            SpiralError::SystemError(format!(
+                "Failed to open metrics history at {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::with_connection(connection)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        let connection = Connection::open_in_memory().map_err(storage_error)?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    async fn with_db<T, F>(&self, operation: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().map_err(|_| {
+                SpiralError::SystemError("Metrics history lock poisoned".to_string())
+            })?;
+            operation(&connection).map_err(storage_error)
+        })
+        .await
+        .map_err(|e| SpiralError::SystemError(format!("Metrics history task failed: {e}")))?
+    }
+
+    /// Store one collection, roll up the windows it completes and drop expired history
+    pub async fn record(&self, metrics: &SystemMetrics) -> Result<()> {
+        let timestamp = metrics.timestamp;
+        let json = serde_json::to_string(metrics)?;
+        self.with_db(move |db| {
+            let tx = db.unchecked_transaction()?;
+            tx.execute(
+                "INSERT OR REPLACE INTO metrics_raw (timestamp, snapshot) VALUES (?1, ?2)",
+                params![timestamp as i64, json],
+            )?;
+
+            let pending_from = next_rollup_bucket(&tx)?;
+            let window_start = bucket_start(timestamp);
+            let completed = snapshots(
+                &tx,
+                "SELECT snapshot FROM metrics_raw WHERE timestamp >= ?1 AND timestamp < ?2
+                 ORDER BY timestamp",
+                pending_from,
+                window_start,
+            )?;
+            for rollup in downsample(&completed) {
+                let json = serde_json::to_string(&rollup)
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO metrics_rollup (bucket, snapshot) VALUES (?1, ?2)",
+                    params![rollup.timestamp as i64, json],
+                )?;
+            }
+
+            tx.execute(
+                "DELETE FROM metrics_raw WHERE timestamp < ?1",
+                params![timestamp.saturating_sub(METRICS_RAW_RETENTION_SECS) as i64],
+            )?;
+            tx.execute(
+                "DELETE FROM metrics_rollup WHERE bucket < ?1",
+                params![timestamp.saturating_sub(METRICS_ROLLUP_RETENTION_SECS) as i64],
+            )?;
+            tx.commit()
+        })
+        .await
+    }
+
+    /// Snapshots from `since` to `until` (Unix seconds, inclusive), oldest first
+    /// `resolution` must already be resolved; Auto is treated as Raw
+    pub async fn query(
+        &self,
+        since: u64,
+        until: u64,
+        resolution: HistoryResolution,
+    ) -> Result<Vec<SystemMetrics>> {
+        if resolution != HistoryResolution::FiveMinutes {
+            let raw = self
+                .with_db(move |db| {
+                    snapshots(
+                        db,
+                        "SELECT snapshot FROM metrics_raw WHERE timestamp >= ?1 AND timestamp <= ?2
+                         ORDER BY timestamp",
+                        since,
+                        until,
+                    )
+                })
+                .await?;
+            return Ok(raw);
+        }
+
+        let (mut rollups, pending) = self
+            .with_db(move |db| {
+                let rollups = snapshots(
+                    db,
+                    "SELECT snapshot FROM metrics_rollup WHERE bucket >= ?1 AND bucket <= ?2
+                     ORDER BY bucket",
+                    bucket_start(since),
+                    until,
+                )?;
+                // Samples of windows not rolled up yet, i.e. the one still being collected
+                let pending_from = next_rollup_bucket(db)?.max(bucket_start(since));
+                let pending = snapshots(
+                    db,
+                    "SELECT snapshot FROM metrics_raw WHERE timestamp >= ?1 AND timestamp <= ?2
+                     ORDER BY timestamp",
+                    pending_from,
+                    until,
+                )?;
+                Ok((rollups, pending))
+            })
+            .await?;
+        rollups.extend(downsample(&pending));
+        Ok(rollups)
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> SpiralError {
+    SpiralError::SystemError(format!("Metrics history storage error: {e}"))
+}
+
+/// Start of the rollup window `timestamp` falls in
+fn bucket_start(timestamp: u64) -> u64 {
+    timestamp - timestamp % METRICS_ROLLUP_SECS
+}
+
+/// The first window without a rollup: the one after the newest rollup, or 0 with none
+fn next_rollup_bucket(db: &Connection) -> rusqlite::Result<u64> {
+    let newest: Option<i64> =
+        db.query_row("SELECT MAX(bucket) FROM metrics_rollup", [], |row| {
+            row.get(0)
+        })?;
+    Ok(newest.map_or(0, |bucket| bucket as u64 + METRICS_ROLLUP_SECS))
+}
+
+/// Snapshots selected by `sql` with two Unix-second bounds
+/// Rows written by a newer build that no longer parse are skipped rather than failing
+fn snapshots(
+    db: &Connection,
+    sql: &str,
+    from: u64,
+    to: u64,
+) -> rusqlite::Result<Vec<SystemMetrics>> {
+    let mut statement = db.prepare(sql)?;
+    let rows = statement.query_map(params![from as i64, to as i64], |row| {
+        row.get::<_, String>(0)
+    })?;
+    let mut snapshots = Vec::new();
+    for json in rows {
+        match serde_json::from_str(&json?) {
+            Ok(snapshot) => snapshots.push(snapshot),
+            Err(e) => warn!("Skipping unreadable metrics snapshot: {}", e),
+        }
+    }
+    Ok(snapshots)
+}
+
+/// 📉 DOWNSAMPLING: One snapshot per rollup window of `samples`, oldest first
+/// Resource usage becomes the window's mean with its highest peak, health the worst seen,
+/// and everything else, e.g. the request counters, is taken from the window's last sample
+/// `samples` must be ordered by timestamp
+pub fn downsample(samples: &[SystemMetrics]) -> Vec<SystemMetrics> {
+    samples
+        .chunk_by(|a, b| bucket_start(a.timestamp) == bucket_start(b.timestamp))
+        .map(|window| {
+            let count = window.len() as f64;
+            let last = &window[window.len() - 1];
+            SystemMetrics {
+                timestamp: bucket_start(last.timestamp),
+                health_status: worst(window.iter().map(|m| m.health_status)),
+                memory_usage: rollup_resource(window.iter().map(|m| &m.memory_usage)),
+                cpu_usage: rollup_resource(window.iter().map(|m| &m.cpu_usage)),
+                disk_usage: rollup_resource(window.iter().map(|m| &m.disk_usage)),
+                average_response_time: window.iter().map(|m| m.average_response_time).sum::<f64>()
+                    / count,
+                ..last.clone()
+            }
+        })
+        .collect()
+}
+
+fn worst(statuses: impl Iterator<Item = HealthStatus>) -> HealthStatus {
+    statuses
+        .max_by_key(|status| *status as u8)
+        .unwrap_or(HealthStatus::Healthy)
+}
+
+fn rollup_resource<'a>(
+    samples: impl Iterator<Item = &'a ResourceMetrics> + Clone,
+) -> ResourceMetrics {
+    let count = samples.clone().count().max(1) as f64;
+    let mean = samples.clone().map(|m| m.current).sum::<f64>() / count;
+    let last = samples.clone().last().cloned().unwrap_or_default();
+    ResourceMetrics {
+        current: mean,
+        peak: samples.clone().map(|m| m.peak).fold(0.0, f64::max),
+        average: mean,
+        status: worst(samples.map(|m| m.status)),
+        ..last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::{MonitoringConfig, SystemMonitor};
+
+    async fn snapshot(timestamp: u64, cpu: f64, health: HealthStatus) -> SystemMetrics {
+        let mut metrics = SystemMonitor::new(MonitoringConfig::default())
+            .get_current_metrics()
+            .await;
+        metrics.timestamp = timestamp;
+        metrics.health_status = health;
+        metrics.cpu_usage.current = cpu;
+        metrics.cpu_usage.peak = cpu;
+        metrics
+    }
+
+    #[tokio::test]
+    async fn test_completed_windows_are_rolled_up_and_expired_history_dropped() {
+        let store = MetricsHistoryStore::open_in_memory().unwrap();
+        let start = 1_700_000_100 - 1_700_000_100 % METRICS_ROLLUP_SECS;
+        store
+            .record(&snapshot(start, 10.0, HealthStatus::Healthy).await)
+            .await
+            .unwrap();
+        store
+            .record(&snapshot(start + 30, 30.0, HealthStatus::Degraded).await)
+            .await
+            .unwrap();
+        store
+            .record(&snapshot(start + 300, 50.0, HealthStatus::Healthy).await)
+            .await
+            .unwrap();
+
+        let raw = store
+            .query(start, start + 300, HistoryResolution::Raw)
+            .await
+            .unwrap();
+        assert_eq!(raw.len(), 3);
+
+        // The first window is stored rolled up, the second comes from its raw sample
+        let rolled = store
+            .query(start, start + 300, HistoryResolution::FiveMinutes)
+            .await
+            .unwrap();
+        assert_eq!(rolled.len(), 2);
+        assert_eq!(rolled[0].timestamp, start);
+        assert_eq!(rolled[0].cpu_usage.current, 20.0);
+        assert_eq!(rolled[0].cpu_usage.peak, 30.0);
+        assert_eq!(rolled[0].health_status, HealthStatus::Degraded);
+        assert_eq!(rolled[1].cpu_usage.current, 50.0);
+
+        // A day later the raw samples are gone but the rollups remain
+        let later = start + METRICS_RAW_RETENTION_SECS + 600;
+        store
+            .record(&snapshot(later, 5.0, HealthStatus::Healthy).await)
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .query(start, start + 300, HistoryResolution::Raw)
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+        let rolled = store
+            .query(start, later, HistoryResolution::FiveMinutes)
+            .await
+            .unwrap();
+        assert_eq!(rolled.len(), 3);
+        assert_eq!(rolled[1].timestamp, start + 300);
+    }
+
+    #[test]
+    fn test_auto_resolution_is_raw_only_within_raw_retention() {
+        let now = 2_000_000_000;
+        assert_eq!(
+            HistoryResolution::Auto.resolve(now - 3600, now),
+            HistoryResolution::Raw
+        );
+        assert_eq!(
+            HistoryResolution::Auto.resolve(now - METRICS_RAW_RETENTION_SECS - 1, now),
+            HistoryResolution::FiveMinutes
+        );
+        assert_eq!(
+            HistoryResolution::FiveMinutes.resolve(now, now),
+            HistoryResolution::FiveMinutes
+        );
+    }
+}
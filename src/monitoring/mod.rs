@@ -2,14 +2,35 @@
 /// CRITICAL: Centralized monitoring for circuit breakers, resources, and system health
 /// Why: Provides visibility into system performance and enables proactive issue detection
 /// Alternative: Individual monitoring per component (rejected: lack of unified view)
+pub mod alerts;
+pub mod availability;
+pub mod collector;
+pub mod crash;
+pub mod history;
+pub mod requests;
+pub mod slo;
+
+pub use alerts::{Alert, AlertEngine, AlertNotifier, AlertRule};
+pub use availability::{AvailabilityReport, AvailabilityWindow};
+pub use collector::{MetricsCollector, ProcCollector, ProcessMetrics, ResourceSample};
+pub use crash::{CrashMetrics, CrashReport, CrashReporter};
+pub use history::{HistoryResolution, MetricsHistoryStore};
+pub use requests::{RequestMetrics, RequestTotals};
+pub use slo::{SloReport, SloTarget, TaskOutcomes};
+
+use crate::agents::orchestrator::DispatchState;
+use crate::agents::AgentOrchestrator;
 use crate::claude_code::circuit_breaker::{CircuitBreakerMetrics, CircuitState};
-use crate::claude_code::ClaudeCodeClient;
+use crate::claude_code::{ClaudeCodeClient, InvocationPoolMetrics, ResponseCacheMetrics};
+use crate::models::AgentType;
+use crate::session::{SessionEvent, SessionJanitor, SessionJanitorMetrics, SharedSessionManager};
 use crate::SpiralError;
+use arc_swap::ArcSwap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
@@ -32,23 +53,162 @@ pub struct SystemMetrics {
     // Circuit breaker metrics
     pub circuit_breakers: HashMap<String, CircuitBreakerMetrics>,
 
+    // Claude Code response cache, when a client is registered
+    #[serde(default)]
+    pub response_cache: Option<ResponseCacheMetrics>,
+
+    // Running and queued Claude Code processes, when a client is registered
+    #[serde(default)]
+    pub claude_invocations: Option<InvocationPoolMetrics>,
+
+    // Expired session cleanup, when a session janitor is registered
+    #[serde(default)]
+    pub session_cleanup: Option<SessionJanitorMetrics>,
+
+    // Agents and task throughput, when an orchestrator is registered
+    #[serde(default)]
+    pub orchestrator: Option<OrchestratorMetrics>,
+
     // Resource metrics
     pub memory_usage: ResourceMetrics,
     pub cpu_usage: ResourceMetrics,
     pub disk_usage: ResourceMetrics,
 
+    // This process and its Claude CLI processes, where the platform reports them
+    #[serde(default)]
+    pub process: Option<ProcessMetrics>,
+
+    // Panics and subsystems left stopped, when a crash reporter is registered
+    #[serde(default)]
+    pub crashes: Option<CrashMetrics>,
+
     // Application metrics
+    /// API requests answered since startup
     pub total_requests: u64,
+    /// API requests answered with a 5xx status since startup
     pub failed_requests: u64,
+    /// Mean milliseconds to answer the API requests since the previous collection
     pub average_response_time: f64,
     pub active_connections: u32,
+    /// Sessions opened since startup that have not yet expired or ended
+    #[serde(default)]
+    pub active_sessions: usize,
 
-    // Queue metrics (from Auto Core Update system)
+    // Task queue metrics, when an orchestrator is registered
+    /// Tasks waiting for an agent
     pub queue_size: usize,
+    /// Submissions refused because the queue was full, since startup
     pub queue_rejected_count: u64,
+    /// Whether queued tasks are being dispatched, i.e. not paused or draining
     pub queue_processing: bool,
 }
 
+impl SystemMetrics {
+    /// Gauges of this snapshot in the Prometheus text exposition format
+    pub fn render_prometheus(&self, out: &mut String) {
+        use std::fmt::Write as _;
+
+        let mut gauge = |name: &str, help: &str, value: f64| {
+            let _ = writeln!(out, "# HELP spiral_core_{name} {help}");
+            let _ = writeln!(out, "# TYPE spiral_core_{name} gauge");
+            let _ = writeln!(out, "spiral_core_{name} {value}");
+        };
+        gauge(
+            "uptime_seconds",
+            "Seconds since monitoring started",
+            self.uptime_seconds,
+        );
+        gauge(
+            "health_status",
+            "0 healthy, 1 degraded, 2 unhealthy, 3 critical",
+            self.health_status as u8 as f64,
+        );
+        gauge(
+            "memory_usage_percent",
+            "Host memory in use",
+            self.memory_usage.current,
+        );
+        gauge(
+            "cpu_usage_percent",
+            "Host CPU in use",
+            self.cpu_usage.current,
+        );
+        gauge("disk_usage_percent", "Disk in use", self.disk_usage.current);
+        gauge(
+            "active_sessions",
+            "Live API sessions",
+            self.active_sessions as f64,
+        );
+        if let Some(orchestrator) = &self.orchestrator {
+            gauge(
+                "task_queue_length",
+                "Tasks waiting for an agent",
+                self.queue_size as f64,
+            );
+            gauge(
+                "task_queue_rejected",
+                "Submissions refused because the queue was full",
+                self.queue_rejected_count as f64,
+            );
+            gauge(
+                "agents_busy",
+                "Agent types running at least one task",
+                orchestrator.agents.values().filter(|a| a.busy).count() as f64,
+            );
+            gauge(
+                "tasks_completed",
+                "Tasks completed since startup",
+                orchestrator.tasks_completed as f64,
+            );
+            gauge(
+                "tasks_failed",
+                "Tasks failed since startup",
+                orchestrator.tasks_failed as f64,
+            );
+        }
+        if let Some(crashes) = &self.crashes {
+            gauge("panics", "Panics since startup", crashes.total as f64);
+        }
+        if let Some(process) = &self.process {
+            gauge(
+                "process_resident_memory_mb",
+                "Resident memory of spiral-core",
+                process.rss_mb,
+            );
+            gauge(
+                "process_open_fds",
+                "Open file descriptors of spiral-core",
+                process.open_fds as f64,
+            );
+        }
+    }
+}
+
+/// Task flow through the orchestrator; queue length itself is in SystemMetrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrchestratorMetrics {
+    /// Most tasks the queue admits
+    pub queue_capacity: usize,
+    pub agents: HashMap<AgentType, AgentActivity>,
+    pub tasks_completed: u64,
+    pub tasks_failed: u64,
+    /// Tasks completed or failed per minute since the previous collection
+    pub tasks_per_minute: f64,
+    /// Execution time percentiles and success rates, and the objectives missed
+    #[serde(default)]
+    pub slo: SloReport,
+}
+
+/// What one agent type is doing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentActivity {
+    pub busy: bool,
+    /// Tasks of this type executing across all workers
+    pub active_tasks: u32,
+    pub tasks_completed: u64,
+    pub tasks_failed: u64,
+}
+
 /// Resource usage metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceMetrics {
@@ -88,17 +248,46 @@ impl Default for MonitoringConfig {
     }
 }
 
+impl From<&crate::config::MonitoringSettings> for MonitoringConfig {
+    fn from(settings: &crate::config::MonitoringSettings) -> Self {
+        Self {
+            collection_interval: Duration::from_secs(settings.interval_secs),
+            metrics_retention_count: settings.retention_count,
+            cpu_warning_threshold: settings.cpu.warning_percent,
+            cpu_critical_threshold: settings.cpu.critical_percent,
+            memory_warning_threshold: settings.memory.warning_percent,
+            memory_critical_threshold: settings.memory.critical_percent,
+            disk_warning_threshold: settings.disk.warning_percent,
+            disk_critical_threshold: settings.disk.critical_percent,
+        }
+    }
+}
+
 /// Centralized system monitoring
 pub struct SystemMonitor {
-    config: MonitoringConfig,
+    /// Swapped when thresholds are reloaded; the loops read it on every collection
+    config: Arc<ArcSwap<MonitoringConfig>>,
     start_time: Instant,
 
     // Metrics storage
     metrics_history: Arc<RwLock<Vec<SystemMetrics>>>,
     current_metrics: Arc<RwLock<SystemMetrics>>,
+    /// Latest overall health, notifying subscribers only when it changes
+    health: watch::Sender<HealthStatus>,
 
     // Components to monitor
     claude_client: Option<Arc<ClaudeCodeClient>>,
+    session_janitor: Option<Arc<SessionJanitor>>,
+    orchestrator: Option<Arc<AgentOrchestrator>>,
+    alerts: Option<Arc<AlertEngine>>,
+    history_store: Option<MetricsHistoryStore>,
+    crashes: Option<CrashReporter>,
+    request_metrics: RequestMetrics,
+    /// Live sessions of the registered session managers, kept current by their events
+    active_sessions: Arc<std::sync::Mutex<HashSet<uuid::Uuid>>>,
+    session_listeners: std::sync::Mutex<Vec<JoinHandle<()>>>,
+    /// Source of memory, CPU and disk usage
+    collector: Arc<dyn MetricsCollector>,
 
     // Task management for monitoring loops
     monitor_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
@@ -115,24 +304,41 @@ impl SystemMonitor {
             uptime_seconds: 0.0,
             health_status: HealthStatus::Healthy,
             circuit_breakers: HashMap::new(),
+            response_cache: None,
+            claude_invocations: None,
+            session_cleanup: None,
+            orchestrator: None,
             memory_usage: ResourceMetrics::default(),
             cpu_usage: ResourceMetrics::default(),
             disk_usage: ResourceMetrics::default(),
+            process: None,
+            crashes: None,
             total_requests: 0,
             failed_requests: 0,
             average_response_time: 0.0,
             active_connections: 0,
+            active_sessions: 0,
             queue_size: 0,
             queue_rejected_count: 0,
             queue_processing: false,
         };
 
         Self {
-            config,
+            config: Arc::new(ArcSwap::from_pointee(config)),
             start_time: Instant::now(),
             metrics_history: Arc::new(RwLock::new(Vec::new())),
             current_metrics: Arc::new(RwLock::new(initial_metrics)),
+            health: watch::Sender::new(HealthStatus::Healthy),
             claude_client: None,
+            session_janitor: None,
+            orchestrator: None,
+            alerts: None,
+            history_store: None,
+            crashes: None,
+            request_metrics: RequestMetrics::default(),
+            active_sessions: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            session_listeners: std::sync::Mutex::new(Vec::new()),
+            collector: collector::platform_collector(),
             monitor_handle: Arc::new(Mutex::new(None)),
             shutdown_signal_sender: Arc::new(Mutex::new(None)),
         }
@@ -143,38 +349,110 @@ impl SystemMonitor {
         self.claude_client = Some(client);
     }
 
+    /// Register the session janitor for monitoring
+    pub fn register_session_janitor(&mut self, janitor: Arc<SessionJanitor>) {
+        self.session_janitor = Some(janitor);
+    }
+
+    /// Register the orchestrator whose queue, agents and throughput are monitored
+    pub fn register_orchestrator(&mut self, orchestrator: Arc<AgentOrchestrator>) {
+        self.orchestrator = Some(orchestrator);
+    }
+
+    /// Check each collection's metrics against `engine`'s rules and send its alerts
+    /// Set before start_monitoring, which hands the engine to the monitoring task
+    pub fn set_alert_engine(&mut self, engine: Arc<AlertEngine>) {
+        self.alerts = Some(engine);
+    }
+
+    /// Also write each collection to `store`, which keeps history across restarts
+    /// Set before start_monitoring, which hands the store to the monitoring task
+    pub fn set_history_store(&mut self, store: MetricsHistoryStore) {
+        self.history_store = Some(store);
+    }
+
+    /// Report crashes to the alert channels, count them into health, and restart the
+    /// monitoring loop if it panics
+    /// Set before start_monitoring, which hands the reporter to the monitoring task
+    pub fn set_crash_reporter(&mut self, reporter: CrashReporter) {
+        self.crashes = Some(reporter);
+    }
+
+    /// The registry the API records its requests in, read into each collection
+    pub fn request_metrics(&self) -> RequestMetrics {
+        self.request_metrics.clone()
+    }
+
+    /// Alerts currently firing; empty without an alert engine
+    pub fn active_alerts(&self) -> Vec<Alert> {
+        self.alerts
+            .as_ref()
+            .map(|engine| engine.active())
+            .unwrap_or_default()
+    }
+
+    /// Take resource usage from `collector` instead of this platform's, e.g. in tests
+    /// Set before start_monitoring, which hands the collector to the monitoring task
+    pub fn set_metrics_collector(&mut self, collector: Arc<dyn MetricsCollector>) {
+        self.collector = collector;
+    }
+
+    /// 📣 SESSION COUNT: Count the live sessions of `sessions` from its lifecycle events
+    /// Sessions opened before registering are not seen, so register at startup
+    pub fn register_session_manager(&self, sessions: SharedSessionManager) {
+        let mut events = sessions.subscribe();
+        let active_sessions = Arc::clone(&self.active_sessions);
+        let listener = tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Session count missed {} session event(s)", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let mut active = active_sessions.lock().unwrap_or_else(|e| e.into_inner());
+                match event {
+                    SessionEvent::Created(session) => active.insert(session.id),
+                    SessionEvent::Expired(session) | SessionEvent::Terminated(session) => {
+                        active.remove(&session.id)
+                    }
+                };
+            }
+        });
+        self.session_listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(listener);
+    }
+
     /// Start monitoring background tasks
     /// 🔧 MONITORING IMPLEMENTATION: Background task with graceful shutdown
     pub async fn start_monitoring(&self) -> Result<(), SpiralError> {
         info!(
             "Starting system monitoring with {}s intervals",
-            self.config.collection_interval.as_secs()
+            self.config.load().collection_interval.as_secs()
         );
 
-        let (shutdown_signal_sender, mut shutdown_signal_receiver) = mpsc::channel::<()>(1);
+        let (shutdown_signal_sender, shutdown_signal_receiver) = mpsc::channel::<()>(1);
         {
             let mut sender_guard = self.shutdown_signal_sender.lock().await;
             *sender_guard = Some(shutdown_signal_sender);
         }
 
         let monitor_clone = Arc::new(self.clone_for_monitoring());
-        let handle = tokio::spawn(async move {
-            let mut interval = tokio::time::interval(monitor_clone.config.collection_interval);
-
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = monitor_clone.collect_metrics().await {
-                            error!("Failed to collect metrics: {}", e);
-                        }
-                    }
-                    _ = shutdown_signal_receiver.recv() => {
-                        info!("System monitoring shutting down gracefully");
-                        break;
-                    }
-                }
-            }
-        });
+        // Shared so a restarted loop still hears shutdown; a panicking loop drops its guard
+        let shutdown_signal_receiver = Arc::new(Mutex::new(shutdown_signal_receiver));
+        let monitoring_loop = move || {
+            let monitor_clone = Arc::clone(&monitor_clone);
+            let shutdown_signal_receiver = Arc::clone(&shutdown_signal_receiver);
+            async move { monitor_clone.run(&shutdown_signal_receiver).await }
+        };
+        let handle = match &self.crashes {
+            Some(reporter) => reporter.supervise("monitor", monitoring_loop),
+            None => tokio::spawn(monitoring_loop()),
+        };
 
         {
             let mut handle_guard = self.monitor_handle.lock().await;
@@ -200,6 +478,14 @@ impl SystemMonitor {
                 warn!("Error waiting for monitoring task to complete: {}", e);
             }
         }
+        for listener in self
+            .session_listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain(..)
+        {
+            listener.abort();
+        }
 
         info!("System monitoring shutdown complete");
     }
@@ -214,20 +500,109 @@ impl SystemMonitor {
         self.metrics_history.read().await.clone()
     }
 
+    /// Snapshots from `since` to `until` (Unix seconds, inclusive), oldest first
+    /// Without a history store only the in-memory samples can be returned
+    pub async fn query_history(
+        &self,
+        since: u64,
+        until: u64,
+        resolution: HistoryResolution,
+    ) -> Result<Vec<SystemMetrics>, SpiralError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let resolution = resolution.resolve(since, now);
+        if let Some(store) = &self.history_store {
+            return store.query(since, until, resolution).await;
+        }
+
+        let recent: Vec<SystemMetrics> = self
+            .metrics_history
+            .read()
+            .await
+            .iter()
+            .filter(|m| (since..=until).contains(&m.timestamp))
+            .cloned()
+            .collect();
+        Ok(match resolution {
+            HistoryResolution::FiveMinutes => history::downsample(&recent),
+            _ => recent,
+        })
+    }
+
+    /// 📈 AVAILABILITY: Time healthy, degraded, unhealthy and down over each of
+    /// AVAILABILITY_WINDOWS, from the metrics history
+    /// Without a history store only the in-memory samples are there to go on
+    pub async fn availability(&self) -> Result<AvailabilityReport, SpiralError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut windows = Vec::new();
+        for (label, secs) in crate::constants::AVAILABILITY_WINDOWS {
+            let since = now.saturating_sub(*secs);
+            let resolution = HistoryResolution::Auto.resolve(since, now);
+            let interval = match resolution {
+                HistoryResolution::FiveMinutes => crate::constants::METRICS_ROLLUP_SECS,
+                _ => self.config.load().collection_interval.as_secs().max(1),
+            };
+            let samples = self.query_history(since, now, resolution).await?;
+            windows.push(availability::summarize(
+                label,
+                &samples,
+                since,
+                now,
+                interval * crate::constants::AVAILABILITY_MAX_GAP_INTERVALS,
+            ));
+        }
+        Ok(AvailabilityReport {
+            generated_at: now,
+            windows,
+        })
+    }
+
     /// Get overall system health status
     pub async fn get_health_status(&self) -> HealthStatus {
         let metrics = self.current_metrics.read().await;
         metrics.health_status
     }
 
+    /// Receive health transitions, e.g. Healthy → Degraded, as metrics are collected
+    pub fn subscribe_health(&self) -> watch::Receiver<HealthStatus> {
+        self.health.subscribe()
+    }
+
+    /// Use the warning and critical thresholds in `settings` from the next collection on;
+    /// the interval and retention are fixed when monitoring starts
+    pub fn set_thresholds(&self, settings: &crate::config::MonitoringSettings) {
+        let current = self.config.load();
+        self.config.store(Arc::new(MonitoringConfig {
+            collection_interval: current.collection_interval,
+            metrics_retention_count: current.metrics_retention_count,
+            ..MonitoringConfig::from(settings)
+        }));
+    }
+
     // Helper method to create a cloneable version for async tasks
     fn clone_for_monitoring(&self) -> SystemMonitorInternal {
         SystemMonitorInternal {
-            config: self.config.clone(),
+            config: Arc::clone(&self.config),
             start_time: self.start_time,
             metrics_history: Arc::clone(&self.metrics_history),
             current_metrics: Arc::clone(&self.current_metrics),
+            health: self.health.clone(),
             claude_client: self.claude_client.clone(),
+            session_janitor: self.session_janitor.clone(),
+            orchestrator: self.orchestrator.clone(),
+            last_tasks_finished: Arc::new(std::sync::Mutex::new(None)),
+            alerts: self.alerts.clone(),
+            history_store: self.history_store.clone(),
+            crashes: self.crashes.clone(),
+            request_metrics: self.request_metrics.clone(),
+            last_request_totals: Arc::new(std::sync::Mutex::new(self.request_metrics.totals())),
+            active_sessions: Arc::clone(&self.active_sessions),
+            collector: Arc::clone(&self.collector),
             peak_memory: Arc::new(RwLock::new(0.0)),
             peak_cpu: Arc::new(RwLock::new(0.0)),
             peak_disk: Arc::new(RwLock::new(0.0)),
@@ -235,14 +610,38 @@ impl SystemMonitor {
     }
 }
 
+#[async_trait::async_trait]
+impl crate::config::Reloadable for SystemMonitor {
+    fn name(&self) -> &'static str {
+        "system monitor"
+    }
+
+    async fn apply_config(&self, config: &crate::config::Config) {
+        self.set_thresholds(&config.monitoring);
+    }
+}
+
 /// Internal struct for monitoring tasks (avoids Clone issues with JoinHandle)
 #[derive(Clone)]
 struct SystemMonitorInternal {
-    config: MonitoringConfig,
+    config: Arc<ArcSwap<MonitoringConfig>>,
     start_time: Instant,
     metrics_history: Arc<RwLock<Vec<SystemMetrics>>>,
     current_metrics: Arc<RwLock<SystemMetrics>>,
+    health: watch::Sender<HealthStatus>,
     claude_client: Option<Arc<ClaudeCodeClient>>,
+    session_janitor: Option<Arc<SessionJanitor>>,
+    orchestrator: Option<Arc<AgentOrchestrator>>,
+    alerts: Option<Arc<AlertEngine>>,
+    history_store: Option<MetricsHistoryStore>,
+    crashes: Option<CrashReporter>,
+    request_metrics: RequestMetrics,
+    active_sessions: Arc<std::sync::Mutex<HashSet<uuid::Uuid>>>,
+    collector: Arc<dyn MetricsCollector>,
+    /// Request totals at the previous collection, for the interval's average latency
+    last_request_totals: Arc<std::sync::Mutex<RequestTotals>>,
+    /// Tasks finished by the previous collection and when, for the interval's throughput
+    last_tasks_finished: Arc<std::sync::Mutex<Option<(u64, Instant)>>>,
     // 🔧 REAL MONITORING: Track peak values across monitoring sessions
     peak_memory: Arc<RwLock<f64>>,
     peak_cpu: Arc<RwLock<f64>>,
@@ -250,9 +649,59 @@ struct SystemMonitorInternal {
 }
 
 impl SystemMonitorInternal {
+    /// Collect metrics every interval and report crashes as they happen, until shutdown
+    async fn run(&self, shutdown: &Mutex<mpsc::Receiver<()>>) {
+        let mut shutdown = shutdown.lock().await;
+        let mut interval = tokio::time::interval(self.config.load().collection_interval);
+        let mut crashes = self.crashes.as_ref().map(CrashReporter::subscribe);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(e) = self.collect_metrics().await {
+                        error!("Failed to collect metrics: {}", e);
+                    }
+                }
+                crash = async { crashes.as_mut()?.recv().await.ok() }, if crashes.is_some() => {
+                    // 💥 A crash is announced at once and degrades health without
+                    // waiting for the next tick
+                    if let Some(report) = crash {
+                        if let Some(engine) = &self.alerts {
+                            engine.dispatch(&[report.to_alert()]).await;
+                        }
+                    }
+                    if let Err(e) = self.collect_metrics().await {
+                        error!("Failed to collect metrics: {}", e);
+                    }
+                }
+                _ = shutdown.recv() => {
+                    info!("System monitoring shutting down gracefully");
+                    break;
+                }
+            }
+        }
+    }
+
     /// Collect all system metrics
     async fn collect_metrics(&self) -> Result<(), SpiralError> {
         debug!("Collecting system metrics");
+        // Usage the platform can't report counts as none, which keeps it healthy
+        let sample = self.collector.sample();
+        let requests = self.request_metrics.totals();
+        let previous = std::mem::replace(
+            &mut *self
+                .last_request_totals
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+            requests,
+        );
+        let interval_requests = requests.requests.saturating_sub(previous.requests);
+        let average_response_time = if interval_requests == 0 {
+            0.0
+        } else {
+            (requests.latency_sum_secs - previous.latency_sum_secs) / interval_requests as f64
+                * 1000.0
+        };
 
         let mut metrics = SystemMetrics {
             timestamp: std::time::SystemTime::now()
@@ -262,13 +711,34 @@ impl SystemMonitorInternal {
             uptime_seconds: self.start_time.elapsed().as_secs_f64(),
             health_status: HealthStatus::Healthy,
             circuit_breakers: HashMap::new(),
-            memory_usage: self.collect_memory_metrics().await,
-            cpu_usage: self.collect_cpu_metrics().await,
-            disk_usage: self.collect_disk_metrics().await,
-            total_requests: 0,
-            failed_requests: 0,
-            average_response_time: 0.0,
+            response_cache: None,
+            claude_invocations: None,
+            session_cleanup: None,
+            orchestrator: None,
+            memory_usage: self
+                .collect_memory_metrics(sample.memory_percent.unwrap_or_default())
+                .await,
+            cpu_usage: self
+                .collect_cpu_metrics(sample.cpu_percent.unwrap_or_default())
+                .await,
+            disk_usage: self
+                .collect_disk_metrics(sample.disk_percent.unwrap_or_default())
+                .await,
+            process: self.collector.process(
+                self.claude_client
+                    .as_ref()
+                    .map_or("claude", |client| client.claude_binary()),
+            ),
+            crashes: self.crashes.as_ref().map(CrashReporter::metrics),
+            total_requests: requests.requests,
+            failed_requests: requests.server_errors,
+            average_response_time,
             active_connections: 0,
+            active_sessions: self
+                .active_sessions
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .len(),
             queue_size: 0,
             queue_rejected_count: 0,
             queue_processing: false,
@@ -276,14 +746,32 @@ impl SystemMonitorInternal {
 
         // Collect circuit breaker metrics
         if let Some(client) = &self.claude_client {
-            let cb_metrics = client.get_circuit_breaker_metrics().await;
-            metrics
-                .circuit_breakers
-                .insert("claude_code".to_string(), cb_metrics);
+            metrics.circuit_breakers = client.circuit_breakers().metrics().await;
+            metrics.response_cache = Some(client.response_cache_metrics());
+            metrics.claude_invocations = Some(client.invocation_pool_metrics());
+        }
+        metrics.session_cleanup = self
+            .session_janitor
+            .as_ref()
+            .map(|janitor| janitor.metrics());
+        if let Some(orchestrator) = &self.orchestrator {
+            self.collect_orchestrator_metrics(orchestrator, &mut metrics)
+                .await;
         }
 
         // Determine overall health status
         metrics.health_status = self.calculate_health_status(&metrics);
+        self.health.send_if_modified(|health| {
+            let changed = *health != metrics.health_status;
+            *health = metrics.health_status;
+            changed
+        });
+
+        let alert_changes = self
+            .alerts
+            .as_ref()
+            .map(|engine| engine.evaluate(&metrics, chrono::Utc::now()))
+            .unwrap_or_default();
 
         // Update current metrics
         {
@@ -291,21 +779,86 @@ impl SystemMonitorInternal {
             *current = metrics.clone();
         }
 
+        // A history file that can't be written loses trend data, not the collection
+        if let Some(store) = &self.history_store {
+            if let Err(e) = store.record(&metrics).await {
+                warn!("Failed to persist metrics history: {}", e);
+            }
+        }
+
         // Add to history and maintain retention limit
         {
             let mut history = self.metrics_history.write().await;
             history.push(metrics);
 
             // Maintain retention count
-            while history.len() > self.config.metrics_retention_count {
+            while history.len() > self.config.load().metrics_retention_count {
                 history.remove(0);
             }
         }
 
+        // Sent after the metrics are stored, so slow notifiers don't hold back readers
+        if let Some(engine) = &self.alerts {
+            engine.dispatch(&alert_changes).await;
+        }
+
         debug!("System metrics collected successfully");
         Ok(())
     }
 
+    /// Queue, agent and throughput figures of the registered orchestrator
+    async fn collect_orchestrator_metrics(
+        &self,
+        orchestrator: &AgentOrchestrator,
+        metrics: &mut SystemMetrics,
+    ) {
+        let status = orchestrator.get_system_status().await;
+        metrics.queue_size = status.queue_length;
+        metrics.queue_rejected_count = orchestrator.queue_rejected_count();
+        metrics.queue_processing =
+            orchestrator.get_dispatch_state().await == DispatchState::Running;
+
+        let agents: HashMap<AgentType, AgentActivity> = orchestrator
+            .get_all_agent_statuses()
+            .await
+            .into_iter()
+            .map(|(agent_type, status)| {
+                let activity = AgentActivity {
+                    busy: status.is_busy,
+                    active_tasks: status.active_tasks,
+                    tasks_completed: status.tasks_completed,
+                    tasks_failed: status.tasks_failed,
+                };
+                (agent_type, activity)
+            })
+            .collect();
+        let tasks_completed = agents.values().map(|a| a.tasks_completed).sum();
+        let tasks_failed = agents.values().map(|a| a.tasks_failed).sum();
+
+        let finished: u64 = tasks_completed + tasks_failed;
+        let now = Instant::now();
+        let previous = self
+            .last_tasks_finished
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace((finished, now));
+        let tasks_per_minute = match previous {
+            Some((before, at)) if now > at => {
+                finished.saturating_sub(before) as f64 / (now - at).as_secs_f64() * 60.0
+            }
+            _ => 0.0,
+        };
+
+        metrics.orchestrator = Some(OrchestratorMetrics {
+            queue_capacity: crate::constants::MAX_QUEUE_SIZE,
+            agents,
+            tasks_completed,
+            tasks_failed,
+            tasks_per_minute,
+            slo: orchestrator.slo_report(),
+        });
+    }
+
     /// Calculate overall system health based on all metrics
     fn calculate_health_status(&self, metrics: &SystemMetrics) -> HealthStatus {
         let mut max_status = HealthStatus::Healthy;
@@ -338,14 +891,36 @@ impl SystemMonitorInternal {
             }
         }
 
+        // 🎯 Missed task objectives: work still completes, just slower or less reliably
+        if let Some(orchestrator) = &metrics.orchestrator {
+            for violation in &orchestrator.slo.violations {
+                warn!(
+                    "{:?} missing its {:?} objective: {:.2} against a target of {:.2}",
+                    violation.agent_type, violation.objective, violation.actual, violation.target
+                );
+                max_status = std::cmp::max(max_status as u8, HealthStatus::Degraded as u8).into();
+            }
+        }
+
+        // 💥 Crashes: a recent panic degrades health, a subsystem left stopped is unhealthy
+        if let Some(crashes) = &metrics.crashes {
+            if crashes.recent > 0 {
+                max_status = std::cmp::max(max_status as u8, HealthStatus::Degraded as u8).into();
+            }
+            if !crashes.failed_subsystems.is_empty() {
+                warn!(
+                    "Subsystems stopped after crashing: {}",
+                    crashes.failed_subsystems.join(", ")
+                );
+                max_status = std::cmp::max(max_status as u8, HealthStatus::Unhealthy as u8).into();
+            }
+        }
+
         max_status
     }
 
     /// Collect memory usage metrics
-    async fn collect_memory_metrics(&self) -> ResourceMetrics {
-        // 🔧 REAL MONITORING: Platform-specific memory collection
-        let (current, _) = self.get_memory_usage();
-
+    async fn collect_memory_metrics(&self, current: f64) -> ResourceMetrics {
         // Update peak value if current is higher
         let peak = {
             let mut peak_guard = self.peak_memory.write().await;
@@ -366,10 +941,11 @@ impl SystemMonitorInternal {
             }
         };
 
-        // Determine health status based on thresholds
-        let status = if current >= self.config.memory_critical_threshold {
+        // Determine health status based on thresholds, as configured now
+        let config = self.config.load();
+        let status = if current >= config.memory_critical_threshold {
             HealthStatus::Critical
-        } else if current >= self.config.memory_warning_threshold {
+        } else if current >= config.memory_warning_threshold {
             HealthStatus::Degraded
         } else {
             HealthStatus::Healthy
@@ -379,17 +955,14 @@ impl SystemMonitorInternal {
             current,
             peak,
             average,
-            threshold_warning: self.config.memory_warning_threshold,
-            threshold_critical: self.config.memory_critical_threshold,
+            threshold_warning: config.memory_warning_threshold,
+            threshold_critical: config.memory_critical_threshold,
             status,
         }
     }
 
     /// Collect CPU usage metrics  
-    async fn collect_cpu_metrics(&self) -> ResourceMetrics {
-        // 🔧 REAL MONITORING: Platform-specific CPU collection
-        let (current, _) = self.get_cpu_usage();
-
+    async fn collect_cpu_metrics(&self, current: f64) -> ResourceMetrics {
         // Update peak value if current is higher
         let peak = {
             let mut peak_guard = self.peak_cpu.write().await;
@@ -410,10 +983,11 @@ impl SystemMonitorInternal {
             }
         };
 
-        // Determine health status based on thresholds
-        let status = if current >= self.config.cpu_critical_threshold {
+        // Determine health status based on thresholds, as configured now
+        let config = self.config.load();
+        let status = if current >= config.cpu_critical_threshold {
             HealthStatus::Critical
-        } else if current >= self.config.cpu_warning_threshold {
+        } else if current >= config.cpu_warning_threshold {
             HealthStatus::Degraded
         } else {
             HealthStatus::Healthy
@@ -423,17 +997,14 @@ impl SystemMonitorInternal {
             current,
             peak,
             average,
-            threshold_warning: self.config.cpu_warning_threshold,
-            threshold_critical: self.config.cpu_critical_threshold,
+            threshold_warning: config.cpu_warning_threshold,
+            threshold_critical: config.cpu_critical_threshold,
             status,
         }
     }
 
     /// Collect disk usage metrics
-    async fn collect_disk_metrics(&self) -> ResourceMetrics {
-        // 🔧 REAL MONITORING: Platform-specific disk collection
-        let (current, _) = self.get_disk_usage();
-
+    async fn collect_disk_metrics(&self, current: f64) -> ResourceMetrics {
         // Update peak value if current is higher
         let peak = {
             let mut peak_guard = self.peak_disk.write().await;
@@ -454,10 +1025,11 @@ impl SystemMonitorInternal {
             }
         };
 
-        // Determine health status based on thresholds
-        let status = if current >= self.config.disk_critical_threshold {
+        // Determine health status based on thresholds, as configured now
+        let config = self.config.load();
+        let status = if current >= config.disk_critical_threshold {
             HealthStatus::Critical
-        } else if current >= self.config.disk_warning_threshold {
+        } else if current >= config.disk_warning_threshold {
             HealthStatus::Degraded
         } else {
             HealthStatus::Healthy
@@ -467,8 +1039,8 @@ impl SystemMonitorInternal {
             current,
             peak,
             average,
-            threshold_warning: self.config.disk_warning_threshold,
-            threshold_critical: self.config.disk_critical_threshold,
+            threshold_warning: config.disk_warning_threshold,
+            threshold_critical: config.disk_critical_threshold,
             status,
         }
     }
@@ -499,202 +1071,46 @@ impl From<u8> for HealthStatus {
     }
 }
 
-impl SystemMonitorInternal {
-    /// 🔧 REAL MONITORING: Platform-specific memory usage collection
-    /// Returns (current_percentage, peak_percentage)
-    fn get_memory_usage(&self) -> (f64, f64) {
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
-
-            // Use vm_stat on macOS to get memory statistics
-            if let Ok(output) = Command::new("vm_stat").output() {
-                if let Ok(stats) = String::from_utf8(output.stdout) {
-                    let mut page_size = 4096; // Default page size
-                    let mut pages_free = 0u64;
-                    let pages_total; // Will be assigned from sysctl
-
-                    for line in stats.lines() {
-                        if line.contains("page size of") {
-                            if let Some(size_str) = line.split_whitespace().nth(7) {
-                                page_size = size_str.parse().unwrap_or(4096);
-                            }
-                        } else if line.starts_with("Pages free:") {
-                            if let Some(value) = line.split_whitespace().nth(2) {
-                                pages_free = value.trim_end_matches('.').parse().unwrap_or(0);
-                            }
-                        }
-                    }
-
-                    // Get total memory using sysctl
-                    if let Ok(output) = Command::new("sysctl").args(["-n", "hw.memsize"]).output() {
-                        if let Ok(total_str) = String::from_utf8(output.stdout) {
-                            if let Ok(total_bytes) = total_str.trim().parse::<u64>() {
-                                pages_total = total_bytes / page_size as u64;
-                                let pages_used = pages_total.saturating_sub(pages_free);
-                                let usage_percent =
-                                    (pages_used as f64 / pages_total as f64) * 100.0;
-
-                                // For peak, we'd need to track this over time
-                                // For now, return current + 10% as a conservative estimate
-                                return (usage_percent, (usage_percent + 10.0).min(100.0));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs;
-
-            // Read /proc/meminfo on Linux
-            if let Ok(meminfo) = fs::read_to_string("/proc/meminfo") {
-                let mut mem_total = 0u64;
-                let mut mem_available = 0u64;
-
-                for line in meminfo.lines() {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 2 {
-                        match parts[0] {
-                            "MemTotal:" => mem_total = parts[1].parse().unwrap_or(0),
-                            "MemAvailable:" => mem_available = parts[1].parse().unwrap_or(0),
-                            _ => {}
-                        }
-                    }
-                }
-
-                if mem_total > 0 {
-                    let mem_used = mem_total.saturating_sub(mem_available);
-                    let usage_percent = (mem_used as f64 / mem_total as f64) * 100.0;
-
-                    // For peak, we'd need to track this over time
-                    return (usage_percent, (usage_percent + 10.0).min(100.0));
-                }
-            }
-        }
-
-        // Fallback for unsupported platforms
-        (45.0, 60.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_configured_thresholds_must_put_warning_below_critical() {
+        let mut settings = crate::config::MonitoringSettings::default();
+        settings.validate().unwrap();
+        settings.disk.warning_percent = 60.0;
+        settings.interval_secs = 10;
+        let config = MonitoringConfig::from(&settings);
+        assert_eq!(config.disk_warning_threshold, 60.0);
+        assert_eq!(config.collection_interval, Duration::from_secs(10));
+
+        settings.cpu.warning_percent = settings.cpu.critical_percent;
+        assert!(settings.validate().is_err());
+        settings.cpu.warning_percent = 50.0;
+        settings.memory.critical_percent = 120.0;
+        assert!(settings.validate().is_err());
     }
 
-    /// 🔧 REAL MONITORING: Platform-specific CPU usage collection
-    /// Returns (current_percentage, peak_percentage)
-    fn get_cpu_usage(&self) -> (f64, f64) {
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
-
-            // Use top command to get CPU usage
-            if let Ok(output) = Command::new("top").args(["-l", "1", "-n", "0"]).output() {
-                if let Ok(stats) = String::from_utf8(output.stdout) {
-                    for line in stats.lines() {
-                        if line.contains("CPU usage:") {
-                            // Parse line like "CPU usage: 12.5% user, 25.0% sys, 62.5% idle"
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            let mut user_percent = 0.0;
-                            let mut sys_percent = 0.0;
-
-                            for (i, part) in parts.iter().enumerate() {
-                                if part.ends_with('%') {
-                                    if let Ok(value) = part.trim_end_matches('%').parse::<f64>() {
-                                        if i > 0 {
-                                            match parts[i - 1] {
-                                                "usage:" => user_percent = value,
-                                                "sys," => sys_percent = value,
-                                                _ => {}
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            let total_usage = user_percent + sys_percent;
-                            return (total_usage, (total_usage + 15.0).min(100.0));
-                        }
-                    }
-                }
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs;
-            use std::thread;
-            use std::time::Duration;
-
-            // Read CPU stats twice with a small delay to calculate usage
-            if let Ok(stat1) = fs::read_to_string("/proc/stat") {
-                thread::sleep(Duration::from_millis(100));
-
-                if let Ok(stat2) = fs::read_to_string("/proc/stat") {
-                    let parse_cpu_line = |line: &str| -> Option<(u64, u64)> {
-                        let parts: Vec<&str> = line.split_whitespace().collect();
-                        if parts.len() >= 5 && parts[0] == "cpu" {
-                            let user = parts[1].parse::<u64>().unwrap_or(0);
-                            let nice = parts[2].parse::<u64>().unwrap_or(0);
-                            let system = parts[3].parse::<u64>().unwrap_or(0);
-                            let idle = parts[4].parse::<u64>().unwrap_or(0);
-
-                            let busy = user + nice + system;
-                            let total = busy + idle;
-                            Some((busy, total))
-                        } else {
-                            None
-                        }
-                    };
-
-                    if let (Some((busy1, total1)), Some((busy2, total2))) = (
-                        stat1.lines().next().and_then(parse_cpu_line),
-                        stat2.lines().next().and_then(parse_cpu_line),
-                    ) {
-                        let busy_delta = busy2.saturating_sub(busy1);
-                        let total_delta = total2.saturating_sub(total1);
-
-                        if total_delta > 0 {
-                            let usage_percent = (busy_delta as f64 / total_delta as f64) * 100.0;
-                            return (usage_percent, (usage_percent + 15.0).min(100.0));
-                        }
-                    }
-                }
-            }
-        }
-
-        // Fallback for unsupported platforms
-        (25.0, 45.0)
-    }
-
-    /// 🔧 REAL MONITORING: Platform-specific disk usage collection
-    /// Returns (current_percentage, peak_percentage)
-    fn get_disk_usage(&self) -> (f64, f64) {
-        use std::process::Command;
-
-        // Use df command (available on both macOS and Linux)
-        if let Ok(output) = Command::new("df")
-            .args(["-k", "/"]) // Root filesystem in KB
-            .output()
-        {
-            if let Ok(stats) = String::from_utf8(output.stdout) {
-                // Skip header line and parse the data line
-                if let Some(data_line) = stats.lines().nth(1) {
-                    let parts: Vec<&str> = data_line.split_whitespace().collect();
-                    // Format: Filesystem 1K-blocks Used Available Use% Mounted
-                    if parts.len() >= 5 {
-                        // The Use% field might be at different positions depending on filesystem name
-                        for part in &parts {
-                            if part.ends_with('%') {
-                                if let Ok(usage) = part.trim_end_matches('%').parse::<f64>() {
-                                    return (usage, (usage + 5.0).min(100.0));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Fallback
-        (35.0, 40.0)
+    #[tokio::test]
+    async fn test_registered_orchestrator_feeds_queue_and_agent_metrics() {
+        let orchestrator = Arc::new(AgentOrchestrator::new(Config::test_config()).await.unwrap());
+        let mut monitor = SystemMonitor::new(MonitoringConfig::default());
+        monitor.register_orchestrator(orchestrator.clone());
+        let internal = monitor.clone_for_monitoring();
+
+        internal.collect_metrics().await.unwrap();
+        let metrics = monitor.get_current_metrics().await;
+        assert!(metrics.queue_processing);
+        assert_eq!(metrics.queue_size, 0);
+        let activity = metrics.orchestrator.unwrap();
+        assert_eq!(activity.queue_capacity, crate::constants::MAX_QUEUE_SIZE);
+        assert!(activity.agents.contains_key(&AgentType::SoftwareDeveloper));
+        assert!(activity.agents.values().all(|agent| !agent.busy));
+
+        orchestrator.pause().await;
+        internal.collect_metrics().await.unwrap();
+        assert!(!monitor.get_current_metrics().await.queue_processing);
     }
 }
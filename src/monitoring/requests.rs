@@ -0,0 +1,169 @@
+//! HTTP request metrics
+//!
+//! The API records every request here: its route, status and how long it took. The
+//! system monitor reads the totals into SystemMetrics each tick, and `/metrics` renders
+//! the per-route counters and latency histograms for Prometheus.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Upper bounds in seconds of the latency histogram buckets, besides +Inf
+pub const LATENCY_BUCKETS_SECS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Route label of requests no route matched, so probes of random paths add no series
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Counters of one method and route
+#[derive(Debug, Clone, Default)]
+struct RouteStats {
+    by_status: BTreeMap<u16, u64>,
+    /// Requests at or under each bound of LATENCY_BUCKETS_SECS, not cumulative
+    buckets: [u64; LATENCY_BUCKETS_SECS.len()],
+    count: u64,
+    latency_sum_secs: f64,
+}
+
+/// Totals across all routes, as the system monitor reads them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestTotals {
+    pub requests: u64,
+    /// Requests answered with a 5xx status
+    pub server_errors: u64,
+    pub latency_sum_secs: f64,
+}
+
+/// 📈 REQUEST METRICS: Per-route request counts, statuses and latencies
+/// 🏗️ ARCHITECTURE DECISION: One registry shared by the API middleware, the system
+/// monitor and the Prometheus endpoint, keyed by route template
+/// Why: Route templates keep the label set bounded however many task IDs are requested,
+/// and one registry means /metrics and /system/metrics never disagree
+/// Alternative: The prometheus crate (rejected: a dependency for a few counters and one
+/// text format)
+/// Trade-off: Counters reset on restart, which Prometheus's rate() already handles
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetrics {
+    routes: Arc<Mutex<BTreeMap<(String, String), RouteStats>>>,
+}
+
+impl RequestMetrics {
+    /// Count one answered request
+    pub fn record(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let mut routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = routes
+            .entry((method.to_string(), route.to_string()))
+            .or_default();
+        *stats.by_status.entry(status).or_default() += 1;
+        if let Some(bucket) = LATENCY_BUCKETS_SECS.iter().position(|bound| secs <= *bound) {
+            stats.buckets[bucket] += 1;
+        }
+        stats.count += 1;
+        stats.latency_sum_secs += secs;
+    }
+
+    pub fn totals(&self) -> RequestTotals {
+        let routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+        let mut totals = RequestTotals::default();
+        for stats in routes.values() {
+            totals.requests += stats.count;
+            totals.latency_sum_secs += stats.latency_sum_secs;
+            totals.server_errors += stats
+                .by_status
+                .range(500..600)
+                .map(|(_, count)| count)
+                .sum::<u64>();
+        }
+        totals
+    }
+
+    /// Counters and histograms in the Prometheus text exposition format
+    pub fn render_prometheus(&self, out: &mut String) {
+        let routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+
+        out.push_str(
+            "# HELP spiral_core_requests_total API requests answered, by route and status\n",
+        );
+        out.push_str("# TYPE spiral_core_requests_total counter\n");
+        for ((method, route), stats) in routes.iter() {
+            for (status, count) in &stats.by_status {
+                let _ = writeln!(
+                    out,
+                    "spiral_core_requests_total{{method=\"{method}\",route=\"{}\",status=\"{status}\"}} {count}",
+                    escape_label(route)
+                );
+            }
+        }
+
+        out.push_str("# HELP spiral_core_request_duration_seconds Time to answer API requests\n");
+        out.push_str("# TYPE spiral_core_request_duration_seconds histogram\n");
+        for ((method, route), stats) in routes.iter() {
+            let labels = format!("method=\"{method}\",route=\"{}\"", escape_label(route));
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS_SECS.iter().zip(stats.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "spiral_core_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "spiral_core_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                stats.count
+            );
+            let _ = writeln!(
+                out,
+                "spiral_core_request_duration_seconds_sum{{{labels}}} {}",
+                stats.latency_sum_secs
+            );
+            let _ = writeln!(
+                out,
+                "spiral_core_request_duration_seconds_count{{{labels}}} {}",
+                stats.count
+            );
+        }
+    }
+}
+
+/// A label value with the characters the text format reserves escaped
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_counted_by_route_status_and_latency() {
+        let metrics = RequestMetrics::default();
+        metrics.record("GET", "/tasks/{task_id}", 200, Duration::from_millis(3));
+        metrics.record("GET", "/tasks/{task_id}", 200, Duration::from_millis(40));
+        metrics.record("POST", "/tasks", 503, Duration::from_secs(20));
+
+        let totals = metrics.totals();
+        assert_eq!(totals.requests, 3);
+        assert_eq!(totals.server_errors, 1);
+        assert!((totals.latency_sum_secs - 20.043).abs() < 1e-9);
+
+        let mut text = String::new();
+        metrics.render_prometheus(&mut text);
+        assert!(text.contains(
+            "spiral_core_requests_total{method=\"GET\",route=\"/tasks/{task_id}\",status=\"200\"} 2"
+        ));
+        // Cumulative buckets: one request by 5ms, both by 50ms
+        assert!(text.contains("route=\"/tasks/{task_id}\",le=\"0.005\"} 1"));
+        assert!(text.contains("route=\"/tasks/{task_id}\",le=\"0.05\"} 2"));
+        // Slower than every bound only shows in +Inf
+        assert!(text.contains("route=\"/tasks\",le=\"10\"} 0"));
+        assert!(text.contains("route=\"/tasks\",le=\"+Inf\"} 1"));
+    }
+}
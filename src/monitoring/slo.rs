@@ -0,0 +1,302 @@
+//! Task latency and success-rate objectives
+//!
+//! The orchestrator records how long each finished task ran and whether it succeeded.
+//! Over a rolling window this gives each agent type its p50/p95/p99 execution time and
+//! success rate, which are checked against the configured targets. A missed target
+//! degrades system health until the window recovers.
+
+use crate::models::AgentType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+/// A measure of an agent type's tasks an objective is set on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SloObjective {
+    /// Median execution time, in seconds; at most the target
+    P50Secs,
+    /// 95th percentile execution time, in seconds; at most the target
+    P95Secs,
+    /// 99th percentile execution time, in seconds; at most the target
+    P99Secs,
+    /// Fraction of tasks that succeeded, 0.0 to 1.0; at least the target
+    SuccessRate,
+}
+
+impl SloObjective {
+    fn measure(self, summary: &TaskLatencySummary) -> f64 {
+        match self {
+            Self::P50Secs => summary.p50_secs,
+            Self::P95Secs => summary.p95_secs,
+            Self::P99Secs => summary.p99_secs,
+            Self::SuccessRate => summary.success_rate,
+        }
+    }
+
+    fn is_met(self, actual: f64, target: f64) -> bool {
+        match self {
+            Self::SuccessRate => actual >= target,
+            _ => actual <= target,
+        }
+    }
+}
+
+/// One service level objective, e.g. "SoftwareDeveloper p95 at most 600s"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SloTarget {
+    /// The agent type held to it; None holds every agent type to it
+    #[serde(default)]
+    pub agent_type: Option<AgentType>,
+    pub objective: SloObjective,
+    pub target: f64,
+}
+
+/// The targets used when none are configured
+pub fn default_targets() -> Vec<SloTarget> {
+    vec![
+        SloTarget {
+            agent_type: None,
+            objective: SloObjective::P95Secs,
+            target: crate::constants::SLO_DEFAULT_P95_SECS,
+        },
+        SloTarget {
+            agent_type: None,
+            objective: SloObjective::SuccessRate,
+            target: crate::constants::SLO_DEFAULT_SUCCESS_RATE,
+        },
+    ]
+}
+
+/// One agent type's finished tasks within the window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskLatencySummary {
+    pub tasks: usize,
+    pub succeeded: usize,
+    /// Fraction that succeeded; 1.0 with no tasks
+    pub success_rate: f64,
+    pub p50_secs: f64,
+    pub p95_secs: f64,
+    pub p99_secs: f64,
+}
+
+/// A target an agent type is currently missing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SloViolation {
+    pub agent_type: AgentType,
+    pub objective: SloObjective,
+    pub target: f64,
+    pub actual: f64,
+}
+
+/// Task latency and success per agent type, and the objectives currently missed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SloReport {
+    pub window_secs: u64,
+    pub agents: HashMap<AgentType, TaskLatencySummary>,
+    pub violations: Vec<SloViolation>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Outcome {
+    finished: Instant,
+    secs: f64,
+    success: bool,
+}
+
+/// 🎯 TASK OUTCOMES: Recent execution times and results of each agent type's tasks
+/// 🏗️ ARCHITECTURE DECISION: Keep the window's samples and sort them when a report is
+/// asked for, rather than bucketed histograms
+/// Why: Percentiles are exact, and a report is built once per monitoring tick or
+/// dashboard request over at most a few thousand samples
+/// Alternative: Fixed latency buckets like the request metrics (rejected: task times range
+/// from seconds to an hour, so any fixed buckets make p99 a guess)
+/// Trade-off: Samples are capped per agent type, so at very high throughput the window is
+/// effectively shorter
+#[derive(Debug, Clone)]
+pub struct TaskOutcomes {
+    window: Duration,
+    targets: Arc<Vec<SloTarget>>,
+    outcomes: Arc<Mutex<HashMap<AgentType, VecDeque<Outcome>>>>,
+}
+
+impl TaskOutcomes {
+    pub fn new(window: Duration, targets: Vec<SloTarget>) -> Self {
+        Self {
+            window,
+            targets: Arc::new(targets),
+            outcomes: Arc::default(),
+        }
+    }
+
+    /// Count one finished task; retried attempts count once, when the task finishes
+    pub fn record(&self, agent_type: &AgentType, elapsed: Duration, success: bool) {
+        self.record_at(agent_type, elapsed, success, Instant::now());
+    }
+
+    fn record_at(&self, agent_type: &AgentType, elapsed: Duration, success: bool, at: Instant) {
+        let mut outcomes = self.outcomes.lock().unwrap_or_else(|e| e.into_inner());
+        let samples = outcomes.entry(agent_type.clone()).or_default();
+        samples.push_back(Outcome {
+            finished: at,
+            secs: elapsed.as_secs_f64(),
+            success,
+        });
+        while samples.len() > crate::constants::SLO_MAX_SAMPLES_PER_AGENT {
+            samples.pop_front();
+        }
+    }
+
+    /// Each agent type's summary over the window and the targets missed
+    /// An agent type with fewer than SLO_MIN_SAMPLES tasks in the window is summarized but
+    /// not judged, so one slow task after a quiet hour does not degrade health
+    pub fn report(&self) -> SloReport {
+        self.report_at(Instant::now())
+    }
+
+    fn report_at(&self, now: Instant) -> SloReport {
+        let mut outcomes = self.outcomes.lock().unwrap_or_else(|e| e.into_inner());
+        let mut report = SloReport {
+            window_secs: self.window.as_secs(),
+            ..SloReport::default()
+        };
+        for (agent_type, samples) in outcomes.iter_mut() {
+            while samples
+                .front()
+                .is_some_and(|outcome| now.duration_since(outcome.finished) > self.window)
+            {
+                samples.pop_front();
+            }
+            let summary = summarize(samples);
+
+            if summary.tasks >= crate::constants::SLO_MIN_SAMPLES {
+                for target in self.targets.iter().filter(|target| {
+                    target
+                        .agent_type
+                        .as_ref()
+                        .is_none_or(|only| only == agent_type)
+                }) {
+                    let actual = target.objective.measure(&summary);
+                    if !target.objective.is_met(actual, target.target) {
+                        report.violations.push(SloViolation {
+                            agent_type: agent_type.clone(),
+                            objective: target.objective,
+                            target: target.target,
+                            actual,
+                        });
+                    }
+                }
+            }
+            report.agents.insert(agent_type.clone(), summary);
+        }
+        report
+    }
+}
+
+fn summarize(samples: &VecDeque<Outcome>) -> TaskLatencySummary {
+    let mut secs: Vec<f64> = samples.iter().map(|outcome| outcome.secs).collect();
+    secs.sort_by(f64::total_cmp);
+    let succeeded = samples.iter().filter(|outcome| outcome.success).count();
+    TaskLatencySummary {
+        tasks: samples.len(),
+        succeeded,
+        success_rate: if samples.is_empty() {
+            1.0
+        } else {
+            succeeded as f64 / samples.len() as f64
+        },
+        p50_secs: percentile(&secs, 0.50),
+        p95_secs: percentile(&secs, 0.95),
+        p99_secs: percentile(&secs, 0.99),
+    }
+}
+
+/// Nearest-rank percentile of ascending `sorted`; 0 when empty
+fn percentile(sorted: &[f64], quantile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_and_violations_over_the_window() {
+        let outcomes = TaskOutcomes::new(
+            Duration::from_secs(3600),
+            vec![
+                SloTarget {
+                    agent_type: None,
+                    objective: SloObjective::P95Secs,
+                    target: 60.0,
+                },
+                SloTarget {
+                    agent_type: Some(AgentType::QualityAssurance),
+                    objective: SloObjective::SuccessRate,
+                    target: 0.9,
+                },
+            ],
+        );
+        let start = Instant::now();
+        // An hour-old slow failure that has left the window by the report
+        outcomes.record_at(
+            &AgentType::SoftwareDeveloper,
+            Duration::from_secs(900),
+            false,
+            start,
+        );
+        let now = start + Duration::from_secs(3700);
+        for secs in 1..=100 {
+            outcomes.record_at(
+                &AgentType::SoftwareDeveloper,
+                Duration::from_secs(secs),
+                secs != 100,
+                now,
+            );
+        }
+        // QA is fast but fails too often; PM has too few tasks to judge
+        for i in 0..10 {
+            outcomes.record_at(
+                &AgentType::QualityAssurance,
+                Duration::from_secs(5),
+                i < 8,
+                now,
+            );
+        }
+        outcomes.record_at(
+            &AgentType::ProjectManager,
+            Duration::from_secs(999),
+            false,
+            now,
+        );
+
+        let report = outcomes.report_at(now);
+        let developer = &report.agents[&AgentType::SoftwareDeveloper];
+        assert_eq!(developer.tasks, 100);
+        assert_eq!(developer.success_rate, 0.99);
+        assert_eq!(developer.p50_secs, 50.0);
+        assert_eq!(developer.p95_secs, 95.0);
+        assert_eq!(developer.p99_secs, 99.0);
+
+        let mut missed: Vec<(AgentType, SloObjective)> = report
+            .violations
+            .iter()
+            .map(|violation| (violation.agent_type.clone(), violation.objective))
+            .collect();
+        missed.sort_by_key(|(agent_type, _)| format!("{agent_type:?}"));
+        assert_eq!(
+            missed,
+            vec![
+                (AgentType::QualityAssurance, SloObjective::SuccessRate),
+                (AgentType::SoftwareDeveloper, SloObjective::P95Secs),
+            ]
+        );
+        assert_eq!(report.agents[&AgentType::ProjectManager].tasks, 1);
+    }
+}
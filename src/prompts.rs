@@ -0,0 +1,422 @@
+//! Prompt templates per agent type
+//!
+//! Prompts are minijinja templates. The defaults in `prompts/` are built in; a templates
+//! directory can override any of them for every agent (`<name>.j2`) or for one agent
+//! type (`<AgentType>/<name>.j2`), and edits there are picked up without a restart.
+
+use crate::claude_code::CodeGenerationRequest;
+use crate::config::PromptConfig;
+use crate::models::AgentType;
+use crate::{Result, SpiralError};
+use minijinja::{Environment, Value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, warn};
+
+/// Prompts shipped with the binary, by template name
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("generation", include_str!("../prompts/generation.j2")),
+    (
+        "chat_generation",
+        include_str!("../prompts/chat_generation.j2"),
+    ),
+    ("discord_task", include_str!("../prompts/discord_task.j2")),
+];
+
+const TEMPLATE_EXTENSION: &str = "j2";
+
+/// Block tags take their own lines without leaving blank ones behind
+fn builtin_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    for (name, source) in BUILTIN_TEMPLATES {
+        if let Err(e) = env.add_template(name, source) {
+            warn!("Built-in prompt template '{}' is invalid: {}", name, e);
+        }
+    }
+    env
+}
+
+/// A template file as last seen, to notice edits
+#[derive(Debug, Clone, PartialEq)]
+struct TemplateFile {
+    name: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// The `.j2` files of a templates directory: shared ones at the top, per-agent ones in
+/// a directory named after the agent type
+fn scan(dir: &Path) -> std::io::Result<Vec<TemplateFile>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(dir.to_path_buf(), None::<String>)];
+    while let Some((current, agent)) = pending.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            let metadata = entry.metadata()?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if metadata.is_dir() {
+                if agent.is_none() && name.parse::<AgentType>().is_ok() {
+                    pending.push((path, Some(name)));
+                } else {
+                    warn!(
+                        "Ignoring {:?}: not an agent type's template directory",
+                        path
+                    );
+                }
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some(TEMPLATE_EXTENSION) {
+                continue;
+            }
+            let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy()) else {
+                continue;
+            };
+            let name = match &agent {
+                Some(agent) => format!("{agent}/{stem}"),
+                None => stem.into_owned(),
+            };
+            files.push(TemplateFile {
+                name,
+                path,
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+            });
+        }
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// The built-in templates with a directory's files laid over them
+fn build(files: &[TemplateFile]) -> Result<Environment<'static>> {
+    let mut env = builtin_environment();
+    for file in files {
+        let invalid = |e: &dyn std::fmt::Display| {
+            SpiralError::ConfigurationError(format!(
+                "Prompt template {:?} is invalid: {e}",
+                file.path
+            ))
+        };
+        let source = std::fs::read_to_string(&file.path).map_err(|e| invalid(&e))?;
+        env.add_template_owned(file.name.clone(), source)
+            .map_err(|e| invalid(&e))?;
+    }
+    Ok(env)
+}
+
+#[derive(Debug)]
+struct Loaded {
+    env: Environment<'static>,
+    files: Vec<TemplateFile>,
+    checked: Instant,
+}
+
+/// 📝 PROMPT TEMPLATES: The prompts agents send, editable without recompiling
+/// 🏗️ ARCHITECTURE DECISION: Poll the directory on render instead of watching it
+/// Why: Renders happen once per task, so checking file times at most every few seconds
+/// costs nothing and needs no watcher thread or platform-specific events
+/// Alternative: Reload on SIGHUP (rejected: needs shell access to the host)
+/// Trade-off: An edit takes effect up to one reload interval later; a template that no
+/// longer parses is reported and the previous version kept
+#[derive(Debug)]
+pub struct PromptTemplates {
+    dir: Option<PathBuf>,
+    reload_interval: Duration,
+    builtin: Environment<'static>,
+    loaded: RwLock<Loaded>,
+}
+
+impl PromptTemplates {
+    /// Only the templates shipped with the binary
+    pub fn builtin() -> Self {
+        Self {
+            dir: None,
+            reload_interval: Duration::ZERO,
+            builtin: builtin_environment(),
+            loaded: RwLock::new(Loaded {
+                env: builtin_environment(),
+                files: Vec::new(),
+                checked: Instant::now(),
+            }),
+        }
+    }
+
+    /// Templates from a directory over the built-in ones; a zero interval never reloads
+    /// Fails on an unreadable directory or a template that does not parse, so mistakes
+    /// surface at startup
+    pub fn load(dir: impl Into<PathBuf>, reload_interval: Duration) -> Result<Self> {
+        let dir = dir.into();
+        let files = scan(&dir).map_err(|e| {
+            SpiralError::ConfigurationError(format!(
+                "Cannot read prompt templates from {dir:?}: {e}"
+            ))
+        })?;
+        let env = build(&files)?;
+        info!("Loaded {} prompt template(s) from {:?}", files.len(), dir);
+        Ok(Self {
+            dir: Some(dir),
+            reload_interval,
+            builtin: builtin_environment(),
+            loaded: RwLock::new(Loaded {
+                env,
+                files,
+                checked: Instant::now(),
+            }),
+        })
+    }
+
+    fn loaded(&self) -> RwLockReadGuard<'_, Loaded> {
+        self.loaded.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Render `name` for an agent type: its own template if the directory has one,
+    /// else the shared one; a template that fails to render falls back to the built-in
+    pub fn render(&self, agent: Option<&AgentType>, name: &str, vars: impl Serialize) -> String {
+        self.reload_if_changed();
+        let vars = Value::from_serialize(vars);
+
+        let candidates = agent
+            .map(|agent| format!("{agent:?}/{name}"))
+            .into_iter()
+            .chain([name.to_string()]);
+        {
+            let loaded = self.loaded();
+            for candidate in candidates {
+                let Ok(template) = loaded.env.get_template(&candidate) else {
+                    continue;
+                };
+                match template.render(&vars) {
+                    Ok(prompt) => return prompt,
+                    Err(e) => {
+                        warn!(
+                            "Prompt template '{}' failed to render, using the built-in one: {}",
+                            candidate, e
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.builtin
+            .get_template(name)
+            .and_then(|template| template.render(&vars))
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Built-in prompt template '{}' failed to render: {}",
+                    name, e
+                );
+                String::new()
+            })
+    }
+
+    /// ♻️ HOT RELOAD: Rebuild once the directory's files changed since the last check
+    fn reload_if_changed(&self) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        if self.reload_interval.is_zero() || self.loaded().checked.elapsed() < self.reload_interval
+        {
+            return;
+        }
+        let mut loaded = self.loaded.write().unwrap_or_else(|e| e.into_inner());
+        // Another render may have reloaded while this one waited for the lock
+        if loaded.checked.elapsed() < self.reload_interval {
+            return;
+        }
+        loaded.checked = Instant::now();
+
+        let files = match scan(dir) {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("Cannot rescan prompt templates in {:?}: {}", dir, e);
+                return;
+            }
+        };
+        if files == loaded.files {
+            return;
+        }
+        match build(&files) {
+            Ok(env) => {
+                info!("Reloaded {} prompt template(s) from {:?}", files.len(), dir);
+                loaded.env = env;
+            }
+            Err(e) => warn!("Keeping the previous prompt templates: {}", e),
+        }
+        // Remembered either way, so a broken file is reported once, not on every render
+        loaded.files = files;
+    }
+}
+
+static PROMPTS: OnceLock<PromptTemplates> = OnceLock::new();
+
+/// Load the process-wide templates; without a directory the built-in ones are used
+pub fn init(config: &PromptConfig) -> Result<()> {
+    let Some(dir) = &config.templates_dir else {
+        return Ok(());
+    };
+    let templates = PromptTemplates::load(dir, Duration::from_secs(config.reload_interval_secs))?;
+    if PROMPTS.set(templates).is_err() {
+        warn!("Prompt templates already initialized");
+    }
+    Ok(())
+}
+
+/// The process-wide templates; the built-in ones until `init` loads a directory
+pub fn global() -> &'static PromptTemplates {
+    PROMPTS.get_or_init(PromptTemplates::builtin)
+}
+
+/// Render a prompt with the process-wide templates
+pub fn render(agent: Option<&AgentType>, name: &str, vars: impl Serialize) -> String {
+    global().render(agent, name, vars)
+}
+
+/// What code generation templates can use; `context` is the task context, so
+/// `{{ context.workspace_path }}` and the like work without code changes
+#[derive(Debug, Serialize)]
+struct RequestVars<'a> {
+    language: &'a str,
+    task: &'a str,
+    agent_type: Option<&'a str>,
+    /// Sorted, so the same task renders the same prompt
+    context: BTreeMap<&'a str, &'a str>,
+    requirements: &'a [String],
+    existing_code: Option<&'a str>,
+    /// Plan the changes instead of making them
+    dry_run: bool,
+}
+
+/// Render a code generation prompt for the agent type named in the request's context
+pub fn render_request(name: &str, request: &CodeGenerationRequest) -> String {
+    let agent_type = request.context.get("agent_type").map(String::as_str);
+    let vars = RequestVars {
+        language: &request.language,
+        task: &request.description,
+        agent_type,
+        context: request
+            .context
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect(),
+        requirements: &request.requirements,
+        existing_code: request.existing_code.as_deref(),
+        dry_run: request.dry_run,
+    };
+    let agent = agent_type.and_then(|agent| agent.parse::<AgentType>().ok());
+    render(agent.as_ref(), name, vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(path: &Path, source: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+
+    #[test]
+    fn test_builtin_discord_task_keeps_intent_prefixes() {
+        let templates = PromptTemplates::builtin();
+        let prompt = templates.render(
+            None,
+            "discord_task",
+            json!({ "intent": "StatusQuery", "content": "what's in src?" }),
+        );
+        assert_eq!(
+            prompt,
+            "INFORMATION QUERY: what's in src?. Please provide a clear, informative \
+             response about the current state of the workspace/project. Focus on listing, \
+             showing, or describing what exists rather than creating new code."
+        );
+        let greeting = templates.render(
+            None,
+            "discord_task",
+            json!({ "intent": "Greeting", "content": "hi" }),
+        );
+        assert_eq!(
+            greeting,
+            "GREETING: hi. Please respond appropriately to the user's greeting."
+        );
+    }
+
+    #[test]
+    fn test_builtin_generation_lists_context_and_requirements() {
+        let prompt = PromptTemplates::builtin().render(
+            None,
+            "generation",
+            json!({
+                "language": "rust",
+                "task": "Parse CSV",
+                "context": { "agent_type": "SoftwareDeveloper" },
+                "requirements": ["Handle quoting"],
+                "existing_code": null,
+            }),
+        );
+        assert!(prompt.contains("Generate high-quality rust code"));
+        assert!(prompt.contains(
+            "Task: Parse CSV\n\nContext:\n- agent_type: SoftwareDeveloper\n\n\
+             Requirements:\n- Handle quoting\n\nIMPORTANT:"
+        ));
+        assert!(!prompt.contains("Existing code to modify"));
+        assert!(prompt.ends_with("Provide the complete implementation with explanations."));
+    }
+
+    #[test]
+    fn test_dry_run_asks_for_a_plan_instead_of_code() {
+        let vars = json!({ "language": "rust", "task": "Parse CSV", "dry_run": true });
+        let templates = PromptTemplates::builtin();
+
+        let prompt = templates.render(None, "generation", &vars);
+        assert!(prompt.contains("DRY RUN: Do not create, modify or delete any files"));
+        assert!(!prompt.contains("IMPORTANT: After implementing the code"));
+        let chat = templates.render(None, "chat_generation", &vars);
+        assert!(chat.contains("DRY RUN: Do not write the implementation"));
+    }
+
+    #[test]
+    fn test_directory_overrides_per_agent_and_reloads_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        write(&dir.path().join("generation.j2"), "shared: {{ task }}");
+        write(
+            &dir.path().join("QualityAssurance/generation.j2"),
+            "qa: {{ task }} in {{ context.workspace }}",
+        );
+        let templates = PromptTemplates::load(dir.path(), Duration::from_millis(1)).unwrap();
+        let vars = json!({ "task": "check", "context": { "workspace": "ws-1" } });
+
+        let qa = templates.render(Some(&AgentType::QualityAssurance), "generation", &vars);
+        assert_eq!(qa, "qa: check in ws-1");
+        let developer = templates.render(Some(&AgentType::SoftwareDeveloper), "generation", &vars);
+        assert_eq!(developer, "shared: check");
+
+        // An edit is picked up; a broken edit keeps the last good version
+        write(
+            &dir.path().join("generation.j2"),
+            "edited shared: {{ task }}!",
+        );
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(
+            templates.render(None, "generation", &vars),
+            "edited shared: check!"
+        );
+        write(&dir.path().join("generation.j2"), "broken {% if %}");
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(
+            templates.render(None, "generation", &vars),
+            "edited shared: check!"
+        );
+
+        // A bad template fails loading outright
+        assert!(PromptTemplates::load(dir.path(), Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Task submission limits that follow system health
+//!
+//! While the system monitor reports the system degraded, or worse, or Claude's circuit
+//! breaker is open, task submissions get a fraction of their configured limits. Limits
+//! return to normal once health recovers. Admins may pin a level instead.
+
+use crate::{
+    claude_code::circuit_breaker::CircuitBreaker,
+    constants::{
+        ADAPTIVE_CRITICAL_PERCENT, ADAPTIVE_DEGRADED_PERCENT, ADAPTIVE_RATE_LIMIT_CHECK_SECS,
+    },
+    monitoring::{HealthStatus, SystemMonitor},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::TokenBucket;
+
+/// How far the task submission limits are cut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdaptiveLevel {
+    /// The configured limits
+    Normal,
+    /// ADAPTIVE_DEGRADED_PERCENT of them
+    Reduced,
+    /// ADAPTIVE_CRITICAL_PERCENT of them
+    Minimal,
+}
+
+impl AdaptiveLevel {
+    pub fn percent(self) -> u32 {
+        match self {
+            Self::Normal => 100,
+            Self::Reduced => ADAPTIVE_DEGRADED_PERCENT,
+            Self::Minimal => ADAPTIVE_CRITICAL_PERCENT,
+        }
+    }
+}
+
+/// Whether the level follows health, or the one an admin pinned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdaptiveMode {
+    /// Follow health and the circuit breaker
+    #[default]
+    Auto,
+    Normal,
+    Reduced,
+    Minimal,
+}
+
+impl AdaptiveMode {
+    fn pinned(self) -> Option<AdaptiveLevel> {
+        match self {
+            Self::Auto => None,
+            Self::Normal => Some(AdaptiveLevel::Normal),
+            Self::Reduced => Some(AdaptiveLevel::Reduced),
+            Self::Minimal => Some(AdaptiveLevel::Minimal),
+        }
+    }
+}
+
+/// What the level is decided from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthSignals {
+    /// None without a system monitor
+    pub health: Option<HealthStatus>,
+    pub circuit_open: bool,
+}
+
+impl HealthSignals {
+    fn level(self) -> (AdaptiveLevel, Option<String>) {
+        if self.circuit_open {
+            return (
+                AdaptiveLevel::Minimal,
+                Some("Claude circuit breaker is open".to_string()),
+            );
+        }
+        match self.health {
+            Some(status @ (HealthStatus::Unhealthy | HealthStatus::Critical)) => (
+                AdaptiveLevel::Minimal,
+                Some(format!("System health is {status:?}")),
+            ),
+            Some(HealthStatus::Degraded) => (
+                AdaptiveLevel::Reduced,
+                Some("System health is Degraded".to_string()),
+            ),
+            Some(HealthStatus::Healthy) | None => (AdaptiveLevel::Normal, None),
+        }
+    }
+}
+
+/// The adaptive limits as shown in /system/status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AdaptiveRateLimitStatus {
+    pub mode: AdaptiveMode,
+    /// The level in effect
+    pub level: AdaptiveLevel,
+    /// Percent of the configured task submission limits allowed
+    pub percent: u32,
+    /// The level health calls for, which applies while mode is auto
+    pub observed_level: AdaptiveLevel,
+    /// Why health calls for a lower level, if it does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Unix seconds when the level in effect last changed
+    pub since: u64,
+}
+
+#[derive(Debug)]
+struct State {
+    mode: AdaptiveMode,
+    observed: AdaptiveLevel,
+    reason: Option<String>,
+    since: u64,
+}
+
+impl State {
+    fn level(&self) -> AdaptiveLevel {
+        self.mode.pinned().unwrap_or(self.observed)
+    }
+}
+
+/// 🩺 ADAPTIVE RATE LIMITS: The level task submissions are limited at
+/// 🏗️ ARCHITECTURE DECISION: Scale the matched policy's bucket, not swap policies
+/// Why: Per-key and per-network policies keep their relative sizes; a client allowed
+/// 100/min still gets five times a default client's share while degraded
+/// Alternative: Separate degraded policies in RATE_LIMIT_POLICIES (rejected: every
+/// policy would need a twin, and forgetting one leaves that client unthrottled)
+/// Trade-off: Buckets are shared with the store, so a client who used a full bucket
+/// before the cut waits for the refill to fall under the smaller one
+#[derive(Debug)]
+pub struct AdaptiveRateLimits {
+    state: RwLock<State>,
+}
+
+impl Default for AdaptiveRateLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveRateLimits {
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(State {
+                mode: AdaptiveMode::Auto,
+                observed: AdaptiveLevel::Normal,
+                reason: None,
+                since: unix_now(),
+            }),
+        }
+    }
+
+    pub fn status(&self) -> AdaptiveRateLimitStatus {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        let level = state.level();
+        AdaptiveRateLimitStatus {
+            mode: state.mode,
+            level,
+            percent: level.percent(),
+            observed_level: state.observed,
+            reason: state.reason.clone(),
+            since: state.since,
+        }
+    }
+
+    pub fn level(&self) -> AdaptiveLevel {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).level()
+    }
+
+    /// Record what health calls for; returns the new level when the one in effect changed
+    pub fn observe(&self, signals: HealthSignals) -> Option<AdaptiveLevel> {
+        let (observed, reason) = signals.level();
+        self.update(|state| {
+            state.observed = observed;
+            state.reason = reason;
+        })
+    }
+
+    /// Follow health again, or pin a level; returns the new level when it changed
+    pub fn set_mode(&self, mode: AdaptiveMode) -> Option<AdaptiveLevel> {
+        self.update(|state| state.mode = mode)
+    }
+
+    fn update(&self, change: impl FnOnce(&mut State)) -> Option<AdaptiveLevel> {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let before = state.level();
+        change(&mut state);
+        let after = state.level();
+        (after != before).then(|| {
+            state.since = unix_now();
+            after
+        })
+    }
+
+    /// `bucket` cut to the level in effect; never below one request
+    pub fn scale(&self, bucket: TokenBucket) -> TokenBucket {
+        let percent = self.level().percent();
+        let cut = |n: u32| (u64::from(n) * u64::from(percent) / 100).max(1) as u32;
+        TokenBucket {
+            burst: cut(bucket.burst),
+            refill_per_minute: cut(bucket.refill_per_minute),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Check health and Claude's circuit breaker every ADAPTIVE_RATE_LIMIT_CHECK_SECS for as
+/// long as the server runs, moving `limits` with them
+pub async fn follow_health(
+    limits: Arc<AdaptiveRateLimits>,
+    monitor: Option<Arc<SystemMonitor>>,
+    breaker: Option<Arc<CircuitBreaker>>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(ADAPTIVE_RATE_LIMIT_CHECK_SECS));
+    loop {
+        interval.tick().await;
+        let health = match &monitor {
+            Some(monitor) => Some(monitor.get_health_status().await),
+            None => None,
+        };
+        let circuit_open = match &breaker {
+            Some(breaker) => breaker.is_rejecting().await,
+            None => false,
+        };
+        let signals = HealthSignals {
+            health,
+            circuit_open,
+        };
+        match limits.observe(signals) {
+            Some(AdaptiveLevel::Normal) => {
+                info!("Task submission rate limits restored: system recovered")
+            }
+            Some(level) => warn!(
+                "Task submission rate limits cut to {}%: {}",
+                level.percent(),
+                limits.status().reason.unwrap_or_default()
+            ),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_tighten_with_health_and_admins_can_pin_a_level() {
+        let limits = AdaptiveRateLimits::new();
+        let bucket = TokenBucket {
+            burst: 10,
+            refill_per_minute: 10,
+        };
+        let signals = |health, circuit_open| HealthSignals {
+            health: Some(health),
+            circuit_open,
+        };
+
+        assert_eq!(limits.observe(signals(HealthStatus::Healthy, false)), None);
+        assert_eq!(limits.scale(bucket), bucket);
+
+        assert_eq!(
+            limits.observe(signals(HealthStatus::Degraded, false)),
+            Some(AdaptiveLevel::Reduced)
+        );
+        assert_eq!(limits.scale(bucket).refill_per_minute, 5);
+        assert_eq!(
+            limits.observe(signals(HealthStatus::Healthy, true)),
+            Some(AdaptiveLevel::Minimal)
+        );
+        assert_eq!(limits.scale(bucket).burst, 2);
+        assert_eq!(
+            limits.status().reason.as_deref(),
+            Some("Claude circuit breaker is open")
+        );
+
+        // A pinned level holds until the mode goes back to auto
+        assert_eq!(
+            limits.set_mode(AdaptiveMode::Normal),
+            Some(AdaptiveLevel::Normal)
+        );
+        assert_eq!(limits.observe(signals(HealthStatus::Critical, false)), None);
+        assert_eq!(limits.status().observed_level, AdaptiveLevel::Minimal);
+        assert_eq!(
+            limits.set_mode(AdaptiveMode::Auto),
+            Some(AdaptiveLevel::Minimal)
+        );
+        assert_eq!(
+            limits.observe(signals(HealthStatus::Healthy, false)),
+            Some(AdaptiveLevel::Normal)
+        );
+
+        // Small limits keep at least one request
+        assert_eq!(
+            limits.set_mode(AdaptiveMode::Minimal),
+            Some(AdaptiveLevel::Minimal)
+        );
+        assert_eq!(limits.scale(TokenBucket::per_minute(1)).burst, 1);
+    }
+}
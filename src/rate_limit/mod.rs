@@ -0,0 +1,323 @@
+pub mod adaptive;
+pub mod policy;
+pub mod store;
+pub mod tiers;
+
+pub use store::{
+    MemoryRateLimiterStore, RateDecision, RateLimiterBackend, RateLimiterStore,
+    RedisRateLimiterStore, TokenBucket,
+};
+
+use crate::{
+    api::ErrorResponse,
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    auth::ApiKeyIdentity,
+    constants::API_AUTH_FAILURES_PER_MINUTE,
+};
+use adaptive::AdaptiveRateLimits;
+use arc_swap::ArcSwap;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use policy::{Caller, RateLimitGroup, RateLimitPolicy};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Instant,
+};
+use tiers::RateLimitTiers;
+use tracing::warn;
+
+// SECURITY: Rate limiting configuration
+pub const REQUESTS_PER_MINUTE: u32 = 60; // Allow 60 requests per minute per IP
+pub const TASK_REQUESTS_PER_MINUTE: u32 = 10; // More restrictive for task creation
+pub const READ_REQUESTS_PER_MINUTE: u32 = 120; // Status reads are cheap and polled
+
+/// Requests a client may make in the current window, on every limited response
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+/// Requests left in the current window after this one
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+
+/// 🚦 API RATE LIMITER: The configured policies and the store their counts are kept in
+/// 🏗️ ARCHITECTURE DECISION: Resolve a policy per request from route group and client
+/// Why: One global quota let a single busy client lock every other client out, and a
+/// status poller used up the quota task submissions needed
+/// Trade-off: Requests are limited after authentication so they can be counted per key;
+/// failed authentications are counted per address in front of auth instead, so key
+/// guessing is bounded without the address limits reaching clients with valid keys
+#[derive(Clone)]
+pub struct ApiRateLimiter {
+    store: Arc<dyn RateLimiterStore>,
+    /// Counts used while the store can't be reached
+    fallback: Arc<MemoryRateLimiterStore>,
+    /// Swapped when the configuration is reloaded
+    policies: Arc<ArcSwap<Vec<RateLimitPolicy>>>,
+    /// Cuts task submission limits while the system is unhealthy
+    adaptive: Arc<AdaptiveRateLimits>,
+    /// Tiers granted to API keys
+    tiers: Arc<RateLimitTiers>,
+}
+
+impl ApiRateLimiter {
+    pub fn new(store: Arc<dyn RateLimiterStore>, policies: Vec<RateLimitPolicy>) -> Self {
+        Self {
+            store,
+            fallback: Arc::new(MemoryRateLimiterStore::new()),
+            policies: Arc::new(ArcSwap::from_pointee(policies)),
+            adaptive: Arc::new(AdaptiveRateLimits::new()),
+            tiers: Arc::new(RateLimitTiers::default()),
+        }
+    }
+
+    /// Apply the tiers in `tiers`, shared with the Discord bot
+    pub fn with_tiers(mut self, tiers: Arc<RateLimitTiers>) -> Self {
+        self.tiers = tiers;
+        self
+    }
+
+    /// Limit requests by `policies` from now on; counts already taken are kept
+    pub fn set_policies(&self, policies: Vec<RateLimitPolicy>) {
+        self.policies.store(Arc::new(policies));
+    }
+
+    pub fn tiers(&self) -> &Arc<RateLimitTiers> {
+        &self.tiers
+    }
+
+    pub fn adaptive(&self) -> &Arc<AdaptiveRateLimits> {
+        &self.adaptive
+    }
+
+    /// Take a token for a request from `caller` in `group` from the policy's bucket
+    /// The bucket is scaled for the caller's API key tier, and task submission buckets are
+    /// cut to the adaptive level in effect
+    /// Returns the sustained limit applied with the decision; None when no policy limits
+    /// the group or the caller's tier is unlimited
+    pub async fn check(
+        &self,
+        group: RateLimitGroup,
+        caller: &Caller,
+    ) -> Option<(u32, RateDecision)> {
+        let policies = self.policies.load_full();
+        let policy = policy::policy_for(&policies, group, caller)?;
+        let key = format!("api:{}:{}", group.as_str(), caller.counter());
+        let tier = caller
+            .api_key
+            .as_ref()
+            .map(|key| self.tiers.tier(&format!("api-key:{key}")))
+            .unwrap_or_default();
+        let bucket = tier.apply(policy.bucket())?;
+        let bucket = match group {
+            RateLimitGroup::TaskSubmission => self.adaptive.scale(bucket),
+            _ => bucket,
+        };
+        let decision = match self.store.hit(&key, bucket).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                // A store outage must neither stop every request nor lift the limits
+                warn!("Rate limit store unavailable, limiting in process: {}", e);
+                self.fallback.hit_at(&key, bucket, Instant::now())
+            }
+        };
+        Some((bucket.refill_per_minute, decision))
+    }
+
+    /// Failed authentications `ip` may still make before it is refused outright
+    pub async fn auth_failures_left(&self, ip: IpAddr) -> u32 {
+        let key = auth_failure_key(ip);
+        let bucket = auth_failure_bucket();
+        match self.store.remaining(&key, bucket).await {
+            Ok(remaining) => remaining,
+            Err(e) => {
+                warn!("Rate limit store unavailable, limiting in process: {}", e);
+                self.fallback.remaining_at(&key, bucket, Instant::now())
+            }
+        }
+    }
+
+    /// Count a request from `ip` that auth refused
+    pub async fn record_auth_failure(&self, ip: IpAddr) {
+        let key = auth_failure_key(ip);
+        let bucket = auth_failure_bucket();
+        if let Err(e) = self.store.hit(&key, bucket).await {
+            warn!("Rate limit store unavailable, limiting in process: {}", e);
+            self.fallback.hit_at(&key, bucket, Instant::now());
+        }
+    }
+}
+
+fn auth_failure_key(ip: IpAddr) -> String {
+    format!("api:auth_failures:ip:{ip}")
+}
+
+fn auth_failure_bucket() -> TokenBucket {
+    TokenBucket::per_minute(API_AUTH_FAILURES_PER_MINUTE)
+}
+
+#[async_trait::async_trait]
+impl crate::config::Reloadable for ApiRateLimiter {
+    fn name(&self) -> &'static str {
+        "API rate limiter"
+    }
+
+    async fn apply_config(&self, config: &crate::config::Config) {
+        self.set_policies(config.api.rate_limits.clone());
+    }
+}
+
+// SECURITY: General rate limiting middleware
+pub async fn rate_limit_middleware(
+    State(limiter): State<ApiRateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // 🛡️ SECURITY DECISION: Per-client limits for each route group
+    // Why: Prevents abuse while one client's traffic leaves others their own quota
+    // AUDIT CHECKPOINT: Critical DoS protection - verify rate limits are enforced
+
+    let path = request.uri().path().to_string();
+    let method = request.method().clone();
+    let client_ip = addr.ip();
+    let identity = request.extensions().get::<ApiKeyIdentity>().cloned();
+    let caller = Caller::new(identity.as_ref(), client_ip);
+
+    // 🎯 ENDPOINT-SPECIFIC RATE LIMITING: Different limits for different operations
+    // Why: Task creation is more resource-intensive than status checks
+    // Versioned and legacy paths share a quota, so switching prefixes gains nothing
+    let group = RateLimitGroup::of(&method, &path);
+    let Some((limit, decision)) = limiter.check(group, &caller).await else {
+        return next.run(request).await;
+    };
+
+    // 🛡️ RATE LIMIT ENFORCEMENT: Check quota before processing request
+    // Why: Prevents resource exhaustion from abusive clients
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        // 🚨 RATE LIMIT EXCEEDED: Log security event and reject request
+        // AUDIT CHECKPOINT: Ensure all rate limit violations are logged
+        warn!(
+            "Rate limit exceeded for {} {} ({}) from {} - request denied",
+            method,
+            path,
+            group.as_str(),
+            caller.counter()
+        );
+        let mut event = AuditEvent::new(
+            AuditEventKind::RateLimited,
+            AuditSource::Api,
+            format!("{method} {path}"),
+        )
+        .with_client(client_ip.to_string());
+        if let Some(identity) = &identity {
+            event = event.with_actor(identity.name.clone());
+        }
+        audit::record(event);
+
+        let retry_after = decision.retry_after_secs();
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error: "Rate limit exceeded".to_string(),
+                details: Some(format!(
+                    "{limit} {} requests per minute; retry in {retry_after} seconds",
+                    group.as_str()
+                )),
+            }),
+        )
+            .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        response
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(
+        HeaderName::from_static(RATE_LIMIT_LIMIT_HEADER),
+        HeaderValue::from(limit),
+    );
+    headers.insert(
+        HeaderName::from_static(RATE_LIMIT_REMAINING_HEADER),
+        HeaderValue::from(decision.remaining),
+    );
+    response
+}
+
+/// 🔑 FAILED AUTHENTICATION LIMIT: Runs in front of auth, refusing an address that has
+/// used up its failed authentications and counting each request auth refuses
+/// AUDIT CHECKPOINT: The only limit key guessing meets; the per-key limits run after auth
+pub async fn auth_failure_limit_middleware(
+    State(limiter): State<ApiRateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client_ip = addr.ip();
+    if limiter.auth_failures_left(client_ip).await == 0 {
+        warn!(
+            "Too many failed authentications from {} - request denied",
+            client_ip
+        );
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::RateLimited,
+                AuditSource::Api,
+                format!("{} {}", request.method(), request.uri().path()),
+            )
+            .with_client(client_ip.to_string()),
+        );
+        // A failure's token refills in this many seconds, rounded up
+        let retry_after = 60u32.div_ceil(API_AUTH_FAILURES_PER_MINUTE).max(1);
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error: "Too many failed authentications".to_string(),
+                details: Some(format!(
+                    "{API_AUTH_FAILURES_PER_MINUTE} failed authentications per minute; retry in {retry_after} seconds"
+                )),
+            }),
+        )
+            .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        limiter.record_auth_failure(client_ip).await;
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_failed_authentications_are_limited_per_address() {
+        let limiter = ApiRateLimiter::new(
+            Arc::new(MemoryRateLimiterStore::new()),
+            policy::default_policies(),
+        );
+        let guesser: IpAddr = "203.0.113.9".parse().unwrap();
+        for _ in 0..API_AUTH_FAILURES_PER_MINUTE {
+            assert!(limiter.auth_failures_left(guesser).await > 0);
+            limiter.record_auth_failure(guesser).await;
+        }
+        assert_eq!(limiter.auth_failures_left(guesser).await, 0);
+
+        let other: IpAddr = "203.0.113.10".parse().unwrap();
+        assert_eq!(
+            limiter.auth_failures_left(other).await,
+            API_AUTH_FAILURES_PER_MINUTE
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Request limits per route group and client
+//!
+//! Every API request falls in one route group. A policy sets a group's limit for every
+//! client, or for one API key or network. Limits are counted per client: by API key when
+//! the request carried one, by address otherwise.
+
+use crate::{api::unversioned_path, auth::ApiKeyIdentity};
+use axum::http::Method;
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::{fmt, net::IpAddr, str::FromStr};
+
+use super::{TokenBucket, READ_REQUESTS_PER_MINUTE, REQUESTS_PER_MINUTE, TASK_REQUESTS_PER_MINUTE};
+
+/// Routes that share a request limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitGroup {
+    /// POST requests under /tasks, each of which queues work for an agent
+    TaskSubmission,
+    /// Everything under /admin
+    Admin,
+    /// Other GET and HEAD requests: status, metrics and listings
+    Reads,
+    /// Other requests that change something
+    Writes,
+}
+
+impl RateLimitGroup {
+    /// The group a request belongs to; versioned and legacy paths share a group
+    pub fn of(method: &Method, path: &str) -> Self {
+        let path = unversioned_path(path);
+        if path == "/admin" || path.starts_with("/admin/") {
+            Self::Admin
+        } else if method == Method::POST && path.starts_with("/tasks") {
+            Self::TaskSubmission
+        } else if method == Method::GET || method == Method::HEAD {
+            Self::Reads
+        } else {
+            Self::Writes
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TaskSubmission => "task_submission",
+            Self::Admin => "admin",
+            Self::Reads => "reads",
+            Self::Writes => "writes",
+        }
+    }
+}
+
+impl FromStr for RateLimitGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "task_submission" | "tasks" => Ok(Self::TaskSubmission),
+            "admin" => Ok(Self::Admin),
+            "reads" => Ok(Self::Reads),
+            "writes" => Ok(Self::Writes),
+            other => Err(format!(
+                "Unknown route group '{other}' (expected task_submission, admin, reads or writes)"
+            )),
+        }
+    }
+}
+
+/// The clients a policy is for: one API key (`api-key:<key_id>`, `api-key:master` for the
+/// master key) or every address in a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RateLimitClient {
+    ApiKey(String),
+    Network(IpNet),
+}
+
+impl FromStr for RateLimitClient {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(key) = s.strip_prefix("api-key:") {
+            return match key.trim() {
+                "" => Err("api-key: needs a key ID".to_string()),
+                key => Ok(Self::ApiKey(key.to_string())),
+            };
+        }
+        s.parse::<IpNet>()
+            .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+            .map(Self::Network)
+            .map_err(|_| format!("'{s}' is neither api-key:<key_id> nor an address or network"))
+    }
+}
+
+impl fmt::Display for RateLimitClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ApiKey(key) => write!(f, "api-key:{key}"),
+            Self::Network(network) => write!(f, "{network}"),
+        }
+    }
+}
+
+impl TryFrom<String> for RateLimitClient {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RateLimitClient> for String {
+    fn from(client: RateLimitClient) -> Self {
+        client.to_string()
+    }
+}
+
+/// One limit, e.g. "task submission 10/min" or "reads 1000/min for api-key:ci"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitPolicy {
+    pub group: RateLimitGroup,
+    /// The clients it is for; None is every client without a policy of its own
+    #[serde(default)]
+    pub client: Option<RateLimitClient>,
+    /// Sustained rate, at which a client's bucket refills
+    pub requests_per_minute: u32,
+    /// Requests a client may make at once after a quiet spell; None is a minute's worth
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitPolicy {
+    pub fn bucket(&self) -> TokenBucket {
+        TokenBucket {
+            burst: self.burst.unwrap_or(self.requests_per_minute),
+            refill_per_minute: self.requests_per_minute,
+        }
+    }
+}
+
+/// The limits used when none are configured: one per group, for every client
+pub fn default_policies() -> Vec<RateLimitPolicy> {
+    [
+        (RateLimitGroup::TaskSubmission, TASK_REQUESTS_PER_MINUTE),
+        (RateLimitGroup::Admin, REQUESTS_PER_MINUTE),
+        (RateLimitGroup::Reads, READ_REQUESTS_PER_MINUTE),
+        (RateLimitGroup::Writes, REQUESTS_PER_MINUTE),
+    ]
+    .into_iter()
+    .map(|(group, requests_per_minute)| RateLimitPolicy {
+        group,
+        client: None,
+        requests_per_minute,
+        burst: None,
+    })
+    .collect()
+}
+
+/// Who made a request, as the rate limits see them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caller {
+    /// Key ID of the API key the request authenticated with; "master" for the master key
+    pub api_key: Option<String>,
+    pub ip: IpAddr,
+}
+
+impl Caller {
+    /// The caller of a request from `ip` that authenticated as `identity`
+    pub fn new(identity: Option<&ApiKeyIdentity>, ip: IpAddr) -> Self {
+        Self {
+            api_key: identity
+                .map(|identity| identity.key_id.as_deref().unwrap_or("master").to_string()),
+            ip,
+        }
+    }
+
+    /// The name the caller's requests are counted under
+    pub fn counter(&self) -> String {
+        match &self.api_key {
+            Some(key) => format!("api-key:{key}"),
+            None => format!("ip:{}", self.ip),
+        }
+    }
+}
+
+/// The policy limiting `caller` in `group`: one naming its API key, else the narrowest
+/// network holding its address, else the group's policy for every client
+/// None when no policy covers the group, leaving it unlimited
+pub fn policy_for<'a>(
+    policies: &'a [RateLimitPolicy],
+    group: RateLimitGroup,
+    caller: &Caller,
+) -> Option<&'a RateLimitPolicy> {
+    policies
+        .iter()
+        .filter(|policy| policy.group == group)
+        .filter_map(|policy| {
+            let rank = match &policy.client {
+                None => 0,
+                Some(RateLimitClient::Network(network)) if network.contains(&caller.ip) => {
+                    1 + u16::from(network.prefix_len())
+                }
+                Some(RateLimitClient::ApiKey(key)) if caller.api_key.as_ref() == Some(key) => {
+                    u16::MAX
+                }
+                Some(_) => return None,
+            };
+            Some((rank, policy))
+        })
+        .max_by_key(|(rank, _)| *rank)
+        .map(|(_, policy)| policy)
+}
+
+/// Policies from `group[@client]=REQUESTS_PER_MINUTE[/BURST]` entries separated by
+/// commas, e.g. `reads=200/50,task_submission@api-key:ci=100,reads@10.0.0.0/8=1000`
+pub fn parse_policies(raw: &str) -> Result<Vec<RateLimitPolicy>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (target, limits) = entry.rsplit_once('=').ok_or_else(|| {
+                format!(
+                    "entries look like group[@client]=REQUESTS_PER_MINUTE[/BURST], got '{entry}'"
+                )
+            })?;
+            let (group, client) = match target.split_once('@') {
+                Some((group, client)) => (group, Some(client.parse()?)),
+                None => (target, None),
+            };
+            let positive = |raw: &str| {
+                raw.trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|value| *value > 0)
+                    .ok_or_else(|| format!("'{raw}' in '{entry}' is not a positive number"))
+            };
+            let (rate, burst) = match limits.split_once('/') {
+                Some((rate, burst)) => (rate, Some(positive(burst)?)),
+                None => (limits, None),
+            };
+            Ok(RateLimitPolicy {
+                group: group.parse()?,
+                client,
+                requests_per_minute: positive(rate)?,
+                burst,
+            })
+        })
+        .collect()
+}
+
+/// `overrides` laid over `policies`; one for the same group and client replaces it
+pub fn merge_policies(
+    mut policies: Vec<RateLimitPolicy>,
+    overrides: Vec<RateLimitPolicy>,
+) -> Vec<RateLimitPolicy> {
+    for policy in overrides {
+        match policies
+            .iter_mut()
+            .find(|existing| existing.group == policy.group && existing.client == policy.client)
+        {
+            Some(existing) => *existing = policy,
+            None => policies.push(policy),
+        }
+    }
+    policies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_policies_beat_networks_which_beat_the_default() {
+        let policies = merge_policies(
+            default_policies(),
+            parse_policies(
+                "reads=200, reads@10.0.0.0/8=1000, reads@10.1.0.0/16=500, reads@api-key:ci=5000",
+            )
+            .unwrap(),
+        );
+        let limit = |api_key: Option<&str>, ip: &str| {
+            let caller = Caller {
+                api_key: api_key.map(str::to_string),
+                ip: ip.parse().unwrap(),
+            };
+            policy_for(&policies, RateLimitGroup::Reads, &caller).map(|p| p.requests_per_minute)
+        };
+        assert_eq!(limit(None, "192.0.2.1"), Some(200));
+        assert_eq!(limit(None, "10.2.0.1"), Some(1000));
+        assert_eq!(limit(Some("master"), "10.1.0.1"), Some(500));
+        assert_eq!(limit(Some("ci"), "10.1.0.1"), Some(5000));
+        assert_eq!(policies.len(), 7);
+        assert_eq!(
+            parse_policies("writes=30/5").unwrap()[0].bucket(),
+            TokenBucket {
+                burst: 5,
+                refill_per_minute: 30
+            }
+        );
+        assert!(policy_for(
+            &[],
+            RateLimitGroup::Reads,
+            &Caller {
+                api_key: None,
+                ip: "192.0.2.1".parse().unwrap(),
+            }
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_requests_fall_in_groups_and_bad_entries_are_refused() {
+        assert_eq!(
+            RateLimitGroup::of(&Method::POST, "/v1/tasks/batch"),
+            RateLimitGroup::TaskSubmission
+        );
+        assert_eq!(
+            RateLimitGroup::of(&Method::DELETE, "/admin/keys/abc"),
+            RateLimitGroup::Admin
+        );
+        assert_eq!(
+            RateLimitGroup::of(&Method::GET, "/tasks/abc"),
+            RateLimitGroup::Reads
+        );
+        assert_eq!(
+            RateLimitGroup::of(&Method::POST, "/system/pause"),
+            RateLimitGroup::Writes
+        );
+
+        assert!(parse_policies("reads").is_err());
+        assert!(parse_policies("reads=0").is_err());
+        assert!(parse_policies("reads=10/0").is_err());
+        assert!(parse_policies("everything=10").is_err());
+        assert!(parse_policies("reads@api-key:=10").is_err());
+        assert!(parse_policies("reads@not-an-address=10").is_err());
+    }
+}
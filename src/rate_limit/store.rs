@@ -0,0 +1,389 @@
+//! Token buckets kept in process memory or in Redis
+//!
+//! A bucket holds up to `burst` tokens and refills at a steady rate; each request takes
+//! one. A client may send a burst after a quiet spell, then only as fast as the bucket
+//! refills. Stores keep how many tokens each key has used, so a key checked against a
+//! larger bucket, such as a guild with a higher limit, keeps what it already used.
+
+use crate::{
+    redis::{redis_error, RedisClient, Reply},
+    Result,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// 🪣 TOKEN BUCKET: Up to `burst` requests at once, then `refill_per_minute` sustained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBucket {
+    pub burst: u32,
+    pub refill_per_minute: u32,
+}
+
+impl TokenBucket {
+    /// `per_minute` requests a minute, all of which may come at once
+    pub fn per_minute(per_minute: u32) -> Self {
+        Self {
+            burst: per_minute,
+            refill_per_minute: per_minute,
+        }
+    }
+
+    fn capacity(self) -> f64 {
+        f64::from(self.burst.max(1))
+    }
+
+    fn refill_per_sec(self) -> f64 {
+        f64::from(self.refill_per_minute.max(1)) / 60.0
+    }
+}
+
+/// Whether a request was let through, and what is left of its limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateDecision {
+    pub allowed: bool,
+    /// Requests that could be made right now, after this one
+    pub remaining: u32,
+    /// How long until a denied request would be allowed; zero when it was allowed
+    pub retry_after: Duration,
+}
+
+impl RateDecision {
+    /// Whole seconds to wait, rounded up so a client retrying on time is let through
+    pub fn retry_after_secs(&self) -> u64 {
+        (self.retry_after.as_millis().div_ceil(1000) as u64).max(1)
+    }
+}
+
+/// Tokens left of `capacity` once `used` are taken, rounded down
+fn tokens_left(capacity: f64, used: f64) -> u32 {
+    (capacity - used).max(0.0).floor() as u32
+}
+
+/// 🚦 RATE LIMITER STORE: Where the token buckets behind the rate limits are kept
+/// 🏗️ ARCHITECTURE DECISION: One keyed store shared by the API middleware and the
+/// Discord message limiter, chosen by configuration like the session store
+/// Why: Counts kept in process let every instance grant the full quota, so two instances
+/// double it; a store all instances point at keeps one bucket per key
+/// Alternative: Each limiter talks to Redis itself (rejected: two copies of the bucket
+/// logic that could disagree)
+/// Trade-off: A request waits on a round trip to the store when it is Redis
+#[async_trait]
+pub trait RateLimiterStore: Send + Sync {
+    /// Take a token from `key`'s bucket if it has one
+    async fn hit(&self, key: &str, bucket: TokenBucket) -> Result<RateDecision>;
+
+    /// Tokens `key`'s bucket holds right now, without taking one
+    async fn remaining(&self, key: &str, bucket: TokenBucket) -> Result<u32>;
+
+    /// Refill `key`'s bucket
+    async fn reset(&self, key: &str) -> Result<()>;
+}
+
+/// Tokens one key has used, as of `at`
+#[derive(Debug, Clone, Copy)]
+struct Usage {
+    used: f64,
+    at: Instant,
+    /// When the bucket will be full again, after which the entry can be dropped
+    full_at: Instant,
+}
+
+impl Usage {
+    fn used_at(&self, bucket: TokenBucket, now: Instant) -> f64 {
+        let refilled =
+            now.saturating_duration_since(self.at).as_secs_f64() * bucket.refill_per_sec();
+        (self.used - refilled).max(0.0)
+    }
+}
+
+/// Buckets in process memory; what a single instance needs
+#[derive(Default)]
+pub struct MemoryRateLimiterStore {
+    usage: Mutex<HashMap<String, Usage>>,
+}
+
+impl MemoryRateLimiterStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a token from `key`'s bucket as of `now`
+    pub fn hit_at(&self, key: &str, bucket: TokenBucket, now: Instant) -> RateDecision {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        // Keys of clients that went quiet would otherwise be kept forever
+        if usage.len() >= crate::constants::RATE_LIMIT_MEMORY_MAX_KEYS {
+            usage.retain(|_, entry| entry.full_at > now);
+        }
+        let used = usage
+            .get(key)
+            .map_or(0.0, |entry| entry.used_at(bucket, now));
+        let capacity = bucket.capacity();
+        let rate = bucket.refill_per_sec();
+        // A little slack so a token refilled over many small steps still counts as whole
+        let allowed = used + 1.0 <= capacity + 1e-9;
+        let used = if allowed { used + 1.0 } else { used };
+        usage.insert(
+            key.to_string(),
+            Usage {
+                used,
+                at: now,
+                full_at: now + Duration::from_secs_f64(used / rate),
+            },
+        );
+        RateDecision {
+            allowed,
+            remaining: tokens_left(capacity, used),
+            retry_after: if allowed {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64((used + 1.0 - capacity) / rate)
+            },
+        }
+    }
+
+    /// Tokens `key`'s bucket holds as of `now`
+    pub fn remaining_at(&self, key: &str, bucket: TokenBucket, now: Instant) -> u32 {
+        let usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let used = usage
+            .get(key)
+            .map_or(0.0, |entry| entry.used_at(bucket, now));
+        tokens_left(bucket.capacity(), used)
+    }
+
+    /// Refill `key`'s bucket
+    pub fn forget(&self, key: &str) {
+        self.usage
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+#[async_trait]
+impl RateLimiterStore for MemoryRateLimiterStore {
+    async fn hit(&self, key: &str, bucket: TokenBucket) -> Result<RateDecision> {
+        Ok(self.hit_at(key, bucket, Instant::now()))
+    }
+
+    async fn remaining(&self, key: &str, bucket: TokenBucket) -> Result<u32> {
+        Ok(self.remaining_at(key, bucket, Instant::now()))
+    }
+
+    async fn reset(&self, key: &str) -> Result<()> {
+        self.forget(key);
+        Ok(())
+    }
+}
+
+const REDIS_KEY_PREFIX: &str = "spiral:ratelimit:";
+
+/// Refill the bucket for the time since it was last used, then take a token if it has one
+/// Arguments: now in milliseconds, burst, refill per minute
+/// Replies `{allowed, remaining, retry_after_ms}`
+const REDIS_HIT_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local rate = tonumber(ARGV[3]) / 60000
+local state = redis.call('HMGET', KEYS[1], 'used', 'at')
+local used = tonumber(state[1]) or 0
+local at = tonumber(state[2]) or now
+used = math.max(0, used - math.max(0, now - at) * rate)
+local allowed = 0
+local retry = 0
+if used + 1 <= burst + 1e-9 then
+  used = used + 1
+  allowed = 1
+else
+  retry = math.ceil((used + 1 - burst) / rate)
+end
+redis.call('HSET', KEYS[1], 'used', tostring(used), 'at', tostring(now))
+redis.call('PEXPIRE', KEYS[1], math.ceil(used / rate) + 1000)
+return {allowed, math.floor(math.max(0, burst - used)), retry}
+"#;
+
+/// Buckets in Redis, shared by every instance pointing at the server
+/// Each key is a hash of the tokens used and when, updated by one script so that
+/// instances taking from the same bucket at once cannot both take its last token
+/// Trade-off: Times come from each instance's clock, so instances whose clocks disagree
+/// by a second refill a bucket by a second's worth more or less
+pub struct RedisRateLimiterStore {
+    client: RedisClient,
+}
+
+impl RedisRateLimiterStore {
+    /// A store for the server at `url`; it connects on first use
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            client: RedisClient::new(url)?,
+        })
+    }
+}
+
+fn redis_key(key: &str) -> String {
+    format!("{REDIS_KEY_PREFIX}{key}")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[async_trait]
+impl RateLimiterStore for RedisRateLimiterStore {
+    async fn hit(&self, key: &str, bucket: TokenBucket) -> Result<RateDecision> {
+        let key = redis_key(key);
+        let now = now_millis().to_string();
+        let burst = bucket.burst.max(1).to_string();
+        let refill = bucket.refill_per_minute.max(1).to_string();
+        let reply = self
+            .client
+            .command(&[
+                b"EVAL",
+                REDIS_HIT_SCRIPT.as_bytes(),
+                b"1",
+                key.as_bytes(),
+                now.as_bytes(),
+                burst.as_bytes(),
+                refill.as_bytes(),
+            ])
+            .await?;
+        match reply {
+            Reply::Array(items) => match items.as_slice() {
+                [Reply::Integer(allowed), Reply::Integer(remaining), Reply::Integer(retry_ms)] => {
+                    Ok(RateDecision {
+                        allowed: *allowed == 1,
+                        remaining: u32::try_from(*remaining).unwrap_or(0),
+                        retry_after: Duration::from_millis(u64::try_from(*retry_ms).unwrap_or(0)),
+                    })
+                }
+                _ => Err(redis_error(format!(
+                    "unexpected rate limit reply {items:?}"
+                ))),
+            },
+            other => Err(redis_error(format!(
+                "unexpected rate limit reply {other:?}"
+            ))),
+        }
+    }
+
+    async fn remaining(&self, key: &str, bucket: TokenBucket) -> Result<u32> {
+        let key = redis_key(key);
+        let state = self
+            .client
+            .command(&[b"HMGET", key.as_bytes(), b"used", b"at"])
+            .await?
+            .into_strings()
+            .unwrap_or_default();
+        let number = |i: usize| state.get(i).and_then(|value| value.parse::<f64>().ok());
+        let used = match (number(0), number(1)) {
+            (Some(used), Some(at)) => {
+                let elapsed_secs = (now_millis() as f64 - at).max(0.0) / 1000.0;
+                (used - elapsed_secs * bucket.refill_per_sec()).max(0.0)
+            }
+            _ => 0.0,
+        };
+        Ok(tokens_left(bucket.capacity(), used))
+    }
+
+    async fn reset(&self, key: &str) -> Result<()> {
+        self.client
+            .command(&[b"DEL", redis_key(key).as_bytes()])
+            .await?;
+        Ok(())
+    }
+}
+
+/// 🚦 RATE LIMITER STORE CHOICE: Which RateLimiterStore keeps the buckets
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum RateLimiterBackend {
+    /// Process memory; each instance counts on its own
+    #[default]
+    Memory,
+    /// A Redis server, `redis://[:password@]host[:port][/db]`; buckets are shared by every
+    /// instance using it
+    Redis { url: String },
+}
+
+impl RateLimiterBackend {
+    /// Open the chosen store; Redis connects on first use
+    pub fn open(&self) -> Result<Arc<dyn RateLimiterStore>> {
+        Ok(match self {
+            RateLimiterBackend::Memory => Arc::new(MemoryRateLimiterStore::new()),
+            RateLimiterBackend::Redis { url } => Arc::new(RedisRateLimiterStore::new(url)?),
+        })
+    }
+
+    /// Like open, but a store that can't be opened degrades to process memory rather
+    /// than blocking startup; `purpose` names the limits in the log
+    pub fn open_or_memory(&self, purpose: &str) -> Arc<dyn RateLimiterStore> {
+        match self.open() {
+            Ok(store) => {
+                tracing::info!("Counting {} in the {} store", purpose, self.kind());
+                store
+            }
+            Err(e) => {
+                warn!("{}, counting {} in process only", e, purpose);
+                Arc::new(MemoryRateLimiterStore::new())
+            }
+        }
+    }
+
+    /// Short name for logs; a Redis URL may hold a password, so it is left out
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RateLimiterBackend::Memory => "memory",
+            RateLimiterBackend::Redis { .. } => "redis",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_a_burst_then_the_refill_rate() {
+        let store = MemoryRateLimiterStore::new();
+        // Three at once, then one every 10 seconds
+        let bucket = TokenBucket {
+            burst: 3,
+            refill_per_minute: 6,
+        };
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        for expected_remaining in [2, 1, 0] {
+            let decision = store.hit_at("a", bucket, start);
+            assert_eq!(
+                (decision.allowed, decision.remaining),
+                (true, expected_remaining)
+            );
+        }
+        let denied = store.hit_at("a", bucket, at(4));
+        assert!(!denied.allowed);
+        assert!((denied.retry_after.as_secs_f64() - 6.0).abs() < 1e-3);
+        // Other keys have their own bucket
+        assert!(store.hit_at("b", bucket, start).allowed);
+
+        assert!(store.hit_at("a", bucket, at(10)).allowed);
+        assert!(!store.hit_at("a", bucket, at(15)).allowed);
+        // A larger bucket keeps what was used but lets the key continue
+        assert!(
+            store
+                .hit_at("a", TokenBucket::per_minute(10), at(15))
+                .allowed
+        );
+        // A minute of quiet fills the bucket again, and no further
+        assert_eq!(store.remaining_at("a", bucket, at(120)), 3);
+
+        store.forget("a");
+        assert_eq!(store.remaining_at("a", bucket, at(15)), 3);
+    }
+}
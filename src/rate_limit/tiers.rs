@@ -0,0 +1,274 @@
+//! Rate limit tiers granted to particular users and API keys
+//!
+//! Everyone is on the default tier. Admins may grant a Discord user or an API key the
+//! trusted tier, with RATE_LIMIT_TRUSTED_MULTIPLIER times the limits, or the unlimited
+//! tier, which no rate limit applies to. Grants are kept in a JSON file so they survive
+//! restarts, and every change is audited.
+
+use crate::{
+    audit::{self, AuditEvent, AuditEventKind, AuditSource},
+    constants::RATE_LIMIT_TRUSTED_MULTIPLIER,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::RwLock,
+};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::TokenBucket;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitTier {
+    /// The configured limits
+    #[default]
+    Default,
+    /// RATE_LIMIT_TRUSTED_MULTIPLIER times the configured limits
+    Trusted,
+    /// No rate limits
+    Unlimited,
+}
+
+impl RateLimitTier {
+    /// `bucket` for this tier; None when the tier is not limited
+    pub fn apply(self, bucket: TokenBucket) -> Option<TokenBucket> {
+        match self {
+            Self::Default => Some(bucket),
+            Self::Trusted => Some(TokenBucket {
+                burst: bucket.burst.saturating_mul(RATE_LIMIT_TRUSTED_MULTIPLIER),
+                refill_per_minute: bucket
+                    .refill_per_minute
+                    .saturating_mul(RATE_LIMIT_TRUSTED_MULTIPLIER),
+            }),
+            Self::Unlimited => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Trusted => "trusted",
+            Self::Unlimited => "unlimited",
+        }
+    }
+}
+
+impl fmt::Display for RateLimitTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RateLimitTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "trusted" => Ok(Self::Trusted),
+            "unlimited" => Ok(Self::Unlimited),
+            other => Err(format!(
+                "Unknown tier '{other}' (expected default, trusted or unlimited)"
+            )),
+        }
+    }
+}
+
+/// Who a tier may be granted to: a Discord user ID, or `api-key:<key_id>`
+/// (`api-key:master` for the master key); returns the subject as stored
+pub fn parse_subject(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    if let Some(key) = raw.strip_prefix("api-key:") {
+        return match key.trim() {
+            "" => Err("api-key: needs a key ID".to_string()),
+            key => Ok(format!("api-key:{key}")),
+        };
+    }
+    raw.parse::<u64>()
+        .map(|user_id| user_id.to_string())
+        .map_err(|_| format!("'{raw}' is neither a Discord user ID nor api-key:<key_id>"))
+}
+
+/// A tier above the default, and who granted it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TierGrant {
+    pub tier: RateLimitTier,
+    /// API key name or Discord user
+    pub granted_by: String,
+    pub granted_at: DateTime<Utc>,
+}
+
+/// 🎟️ RATE LIMIT TIERS: Grants by subject, read on every limited request and message
+/// 🏗️ ARCHITECTURE DECISION: In-memory map written through to a JSON file, one store
+/// shared by the API and the Discord bot
+/// Why: Lookups happen per request and must not touch the disk; a grant made through
+/// either must apply to both at once
+/// Alternative: Tiers as RATE_LIMIT_POLICIES entries (rejected: policies are per route
+/// group and need a restart to change)
+/// Trade-off: The whole file is rewritten on each change, fine at this size
+#[derive(Debug, Default)]
+pub struct RateLimitTiers {
+    grants: RwLock<HashMap<String, TierGrant>>,
+    /// None keeps grants in memory only
+    path: Option<PathBuf>,
+}
+
+impl RateLimitTiers {
+    /// Open the store, restoring grants saved at `path`
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let grants = path.as_deref().map(Self::load).unwrap_or_default();
+        if !grants.is_empty() {
+            info!("Restored {} rate limit tier grant(s)", grants.len());
+        }
+        Self {
+            grants: RwLock::new(grants),
+            path,
+        }
+    }
+
+    fn load(path: &Path) -> HashMap<String, TierGrant> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+            Err(e) => {
+                warn!("Failed to read rate limit tiers {:?}: {}", path, e);
+                return HashMap::new();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring corrupt rate limit tiers {:?}: {}", path, e);
+            HashMap::new()
+        })
+    }
+
+    /// The subject's tier; default without a grant
+    pub fn tier(&self, subject: &str) -> RateLimitTier {
+        self.grants
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(subject)
+            .map(|grant| grant.tier)
+            .unwrap_or_default()
+    }
+
+    /// Every grant, by subject
+    pub fn grants(&self) -> Vec<(String, TierGrant)> {
+        let mut grants: Vec<_> = self
+            .grants
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(subject, grant)| (subject.clone(), grant.clone()))
+            .collect();
+        grants.sort_by(|a, b| a.0.cmp(&b.0));
+        grants
+    }
+
+    /// Put `subject` on `tier`, saving and auditing the change; the default tier removes
+    /// the grant. Returns the subject's previous tier
+    pub fn set(
+        &self,
+        subject: &str,
+        tier: RateLimitTier,
+        source: AuditSource,
+        granted_by: &str,
+    ) -> RateLimitTier {
+        let mut grants = self.grants.write().unwrap_or_else(|e| e.into_inner());
+        let previous = grants
+            .get(subject)
+            .map(|grant| grant.tier)
+            .unwrap_or_default();
+        match tier {
+            RateLimitTier::Default => {
+                grants.remove(subject);
+            }
+            tier => {
+                grants.insert(
+                    subject.to_string(),
+                    TierGrant {
+                        tier,
+                        granted_by: granted_by.to_string(),
+                        granted_at: Utc::now(),
+                    },
+                );
+            }
+        }
+        self.persist(&grants);
+        drop(grants);
+
+        info!(
+            "Rate limit tier of {} changed from {} to {} by {}",
+            subject, previous, tier, granted_by
+        );
+        audit::record(
+            AuditEvent::new(
+                AuditEventKind::AdminAction,
+                source,
+                format!("rate limit tier {subject}"),
+            )
+            .with_actor(granted_by)
+            .with_details(format!("{previous} -> {tier}")),
+        );
+        previous
+    }
+
+    fn persist(&self, grants: &HashMap<String, TierGrant>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(grants)
+            .map_err(std::io::Error::other)
+            .and_then(|serialized| std::fs::write(path, serialized));
+        if let Err(e) = result {
+            warn!("Failed to save rate limit tiers to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiers_persist_and_scale_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tiers.json");
+        let tiers = RateLimitTiers::open(Some(path.clone()));
+        let ci = parse_subject("api-key:ci").unwrap();
+        assert_eq!(
+            tiers.set(&ci, RateLimitTier::Trusted, AuditSource::Api, "ops"),
+            RateLimitTier::Default
+        );
+        tiers.set(
+            "42",
+            RateLimitTier::Unlimited,
+            AuditSource::Discord,
+            "admin",
+        );
+
+        let reopened = RateLimitTiers::open(Some(path.clone()));
+        assert_eq!(reopened.tier("api-key:ci"), RateLimitTier::Trusted);
+        assert_eq!(reopened.tier("43"), RateLimitTier::Default);
+        assert_eq!(reopened.grants()[0].1.granted_by, "admin");
+
+        // Back to default drops the grant
+        reopened.set("42", RateLimitTier::Default, AuditSource::Discord, "admin");
+        assert_eq!(RateLimitTiers::open(Some(path)).grants().len(), 1);
+
+        let bucket = TokenBucket::per_minute(10);
+        assert_eq!(RateLimitTier::Default.apply(bucket), Some(bucket));
+        assert_eq!(
+            RateLimitTier::Trusted.apply(bucket),
+            Some(TokenBucket::per_minute(10 * RATE_LIMIT_TRUSTED_MULTIPLIER))
+        );
+        assert_eq!(RateLimitTier::Unlimited.apply(bucket), None);
+        assert!(parse_subject("someone").is_err());
+        assert!(parse_subject("api-key:").is_err());
+    }
+}
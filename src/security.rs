@@ -32,34 +32,76 @@ pub fn generate_secure_api_key() -> String {
         .collect()
 }
 
+/// 🔒 ENCRYPTED KEY FILE MARKER: Prefix of an encrypted .spiral-api-key
+/// Format: `spiral-encrypted-v1:<pbkdf2 iterations>:<salt hex>:<nonce hex>:<ciphertext hex>`
+/// Why: A plaintext key never contains ':', so old files are recognised unambiguously
+pub const ENCRYPTED_KEY_FILE_PREFIX: &str = "spiral-encrypted-v1";
+
+/// 🔒 PASSPHRASE STRETCHING: PBKDF2-HMAC-SHA256 rounds for new encrypted key files
+/// DECISION: OWASP's 2023 recommendation for PBKDF2-HMAC-SHA256
+/// Why: The passphrase is human-chosen; stretching makes offline guessing expensive
+/// Trade-off: ~0.5s once at startup; stored per file so it can be raised later
+pub const KEY_FILE_PBKDF2_ITERATIONS: u32 = 600_000;
+
 /// 💾 API KEY PERSISTENCE: Secure file storage with proper permissions
 /// CRITICAL: Restricts file permissions to owner-only (600)
 /// Why: Prevents other users/processes from reading the API key
 /// Alternative: World-readable (rejected: security risk), no persistence (rejected: not practical)
-pub fn save_api_key_to_file(api_key: &str) -> Result<(), SpiralError> {
-    info!("Saving API key to secure file: {}", API_KEY_FILE);
+/// With a passphrase the key is encrypted, so a copied file alone does not leak it
+pub fn save_api_key_to_file(api_key: &str, passphrase: Option<&str>) -> Result<(), SpiralError> {
+    save_api_key_at(Path::new(API_KEY_FILE), api_key, passphrase)
+}
 
-    // Write the API key to file
-    fs::write(API_KEY_FILE, api_key).map_err(|e| {
+fn save_api_key_at(
+    path: &Path,
+    api_key: &str,
+    passphrase: Option<&str>,
+) -> Result<(), SpiralError> {
+    info!("Saving API key to secure file: {}", path.display());
+
+    let content = match passphrase {
+        Some(passphrase) => encrypt_api_key(api_key, passphrase, KEY_FILE_PBKDF2_ITERATIONS)?,
+        None => api_key.to_string(),
+    };
+
+    // 🛡️ SECURITY DECISION: Create the file with owner-only permissions
+    // Why: Writing first and restricting afterwards leaves a window where others can read it
+    let temp_path = path.with_extension("partial");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600); // Owner read/write only
+    }
+    let write_result = options.open(&temp_path).and_then(|mut file| {
+        use std::io::Write;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    write_result.map_err(|e| {
         SpiralError::ConfigurationError(format!("Failed to write API key file: {e}"))
     })?;
-
-    // Set restrictive permissions (owner read/write only)
+    // A file left over from an earlier crash may have kept broader permissions
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(API_KEY_FILE)
-            .map_err(|e| {
-                SpiralError::ConfigurationError(format!("Failed to get file metadata: {e}"))
-            })?
-            .permissions();
-        perms.set_mode(0o600); // Owner read/write only
-        fs::set_permissions(API_KEY_FILE, perms).map_err(|e| {
+        fs::set_permissions(&temp_path, fs::Permissions::from_mode(0o600)).map_err(|e| {
             SpiralError::ConfigurationError(format!("Failed to set file permissions: {e}"))
         })?;
     }
+    fs::rename(&temp_path, path).map_err(|e| {
+        SpiralError::ConfigurationError(format!("Failed to write API key file: {e}"))
+    })?;
 
-    info!("API key saved successfully with secure permissions");
+    info!(
+        "API key saved successfully with secure permissions{}",
+        if passphrase.is_some() {
+            " (encrypted)"
+        } else {
+            ""
+        }
+    );
     Ok(())
 }
 
@@ -67,16 +109,58 @@ pub fn save_api_key_to_file(api_key: &str) -> Result<(), SpiralError> {
 /// DECISION: Silent failure if file doesn't exist (None return)
 /// Why: Allows generation flow to work smoothly
 /// Alternative: Error on missing file (rejected: breaks generation flow)
-pub fn load_api_key_from_file() -> Result<Option<String>, SpiralError> {
-    if !Path::new(API_KEY_FILE).exists() {
+/// A plaintext file is encrypted in place once a passphrase is configured
+pub fn load_api_key_from_file(passphrase: Option<&str>) -> Result<Option<String>, SpiralError> {
+    load_api_key_at(Path::new(API_KEY_FILE), passphrase)
+}
+
+fn load_api_key_at(path: &Path, passphrase: Option<&str>) -> Result<Option<String>, SpiralError> {
+    let Some((api_key, encrypted)) = read_api_key_at(path, passphrase)? else {
+        return Ok(None);
+    };
+
+    // 🔄 MIGRATION: Existing plaintext files are encrypted the first time a passphrase is set
+    if !encrypted && passphrase.is_some() {
+        info!("Encrypting existing plaintext API key file");
+        save_api_key_at(path, &api_key, passphrase)?;
+    }
+
+    info!("API key loaded successfully from file");
+    Ok(Some(api_key))
+}
+
+/// 🔍 API KEY INSPECTION: The key in the file, checked like at startup but never rewritten
+/// Why: `spiral-core doctor` reports on the file without migrating it
+pub fn read_api_key_file(passphrase: Option<&str>) -> Result<Option<String>, SpiralError> {
+    Ok(read_api_key_at(Path::new(API_KEY_FILE), passphrase)?.map(|(api_key, _)| api_key))
+}
+
+/// The validated key in `path` and whether the file was encrypted
+fn read_api_key_at(
+    path: &Path,
+    passphrase: Option<&str>,
+) -> Result<Option<(String, bool)>, SpiralError> {
+    if !path.exists() {
         return Ok(None);
     }
 
-    let api_key = fs::read_to_string(API_KEY_FILE).map_err(|e| {
+    check_key_file_permissions(path)?;
+
+    let content = fs::read_to_string(path).map_err(|e| {
         SpiralError::ConfigurationError(format!("Failed to read API key file: {e}"))
     })?;
-
-    let api_key = api_key.trim().to_string();
+    let content = content.trim();
+
+    let encrypted = content.starts_with(ENCRYPTED_KEY_FILE_PREFIX);
+    let api_key = match (encrypted, passphrase) {
+        (true, Some(passphrase)) => decrypt_api_key(content, passphrase)?,
+        (true, None) => {
+            return Err(SpiralError::ConfigurationError(
+                "API key file is encrypted; set API_KEY_FILE_PASSPHRASE".to_string(),
+            ))
+        }
+        (false, _) => content.to_string(),
+    };
 
     // Validate the loaded key
     if api_key.len() != API_KEY_LENGTH {
@@ -97,15 +181,139 @@ pub fn load_api_key_from_file() -> Result<Option<String>, SpiralError> {
         ));
     }
 
-    info!("API key loaded successfully from file");
-    Ok(Some(api_key))
+    Ok(Some((api_key, encrypted)))
+}
+
+/// 🛡️ PERMISSION CHECK: Refuse a key file other users can read or write
+/// Why: Like ssh with private keys; a key others could read must be assumed leaked, and
+/// one others could write could be swapped for a key an attacker knows
+/// Fix: `chmod 600 .spiral-api-key`, or delete it to have a new key generated
+fn check_key_file_permissions(path: &Path) -> Result<(), SpiralError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path)
+            .map_err(|e| {
+                SpiralError::ConfigurationError(format!("Failed to get file metadata: {e}"))
+            })?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            return Err(SpiralError::ConfigurationError(format!(
+                "API key file {} has permissions {:o}; it must only be accessible by its owner (chmod 600)",
+                path.display(),
+                mode & 0o777
+            )));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// 🔒 KEY FILE ENCRYPTION: AES-256-GCM under a PBKDF2-stretched passphrase
+/// Why: Authenticated encryption, so a wrong passphrase or edited file is detected rather
+/// than yielding a garbage key
+/// Alternative: OS keychain (not used: unavailable on headless servers, where the
+/// passphrase can come from the secret provider instead)
+fn encrypt_api_key(
+    api_key: &str,
+    passphrase: &str,
+    iterations: u32,
+) -> Result<String, SpiralError> {
+    use ring::{
+        aead,
+        rand::{SecureRandom, SystemRandom},
+    };
+
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| {
+            SpiralError::ConfigurationError("No secure randomness available".to_string())
+        })?;
+
+    let key = derive_key_file_key(passphrase, &salt, iterations)?;
+    let mut ciphertext = api_key.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce),
+        aead::Aad::empty(),
+        &mut ciphertext,
+    )
+    .map_err(|_| SpiralError::ConfigurationError("Failed to encrypt API key".to_string()))?;
+
+    Ok(format!(
+        "{ENCRYPTED_KEY_FILE_PREFIX}:{iterations}:{}:{}:{}",
+        hex::encode(salt),
+        hex::encode(nonce),
+        hex::encode(ciphertext)
+    ))
+}
+
+fn decrypt_api_key(content: &str, passphrase: &str) -> Result<String, SpiralError> {
+    use ring::aead;
+
+    let invalid = || SpiralError::ConfigurationError("Invalid encrypted API key file".to_string());
+    let parts: Vec<&str> = content.split(':').collect();
+    let [_, iterations, salt, nonce, ciphertext] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    let iterations: u32 = iterations.parse().map_err(|_| invalid())?;
+    let salt = hex::decode(salt).map_err(|_| invalid())?;
+    let nonce: [u8; aead::NONCE_LEN] = hex::decode(nonce)
+        .ok()
+        .and_then(|nonce| nonce.try_into().ok())
+        .ok_or_else(invalid)?;
+    let mut ciphertext = hex::decode(ciphertext).map_err(|_| invalid())?;
+
+    let key = derive_key_file_key(passphrase, &salt, iterations)?;
+    let plaintext = key
+        .open_in_place(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::empty(),
+            &mut ciphertext,
+        )
+        .map_err(|_| {
+            SpiralError::ConfigurationError(
+                "Failed to decrypt API key file: wrong API_KEY_FILE_PASSPHRASE?".to_string(),
+            )
+        })?;
+    String::from_utf8(plaintext.to_vec()).map_err(|_| invalid())
+}
+
+fn derive_key_file_key(
+    passphrase: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> Result<ring::aead::LessSafeKey, SpiralError> {
+    use ring::{aead, pbkdf2};
+
+    let iterations = std::num::NonZeroU32::new(iterations).ok_or_else(|| {
+        SpiralError::ConfigurationError("Invalid encrypted API key file".to_string())
+    })?;
+    let mut key_bytes = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key_bytes,
+    );
+    let key = aead::UnboundKey::new(&aead::AES_256_GCM, &key_bytes)
+        .map_err(|_| SpiralError::ConfigurationError("Failed to derive key".to_string()))?;
+    Ok(aead::LessSafeKey::new(key))
 }
 
 /// 🔄 API KEY INITIALIZATION: Validate existing or generate new secure key
 /// DECISION: Use config key if exists, otherwise generate secure file-based key
 /// Why: Respect environment configuration while ensuring secure fallback
 /// Alternative: Always generate (rejected: ignores user config)
-pub fn ensure_api_key_exists(existing_api_key: Option<&str>) -> Result<String, SpiralError> {
+pub fn ensure_api_key_exists(
+    existing_api_key: Option<&str>,
+    passphrase: Option<&str>,
+) -> Result<String, SpiralError> {
     info!("Ensuring secure API key exists...");
 
     // If config already has an API key (from env var), validate and use it
@@ -117,7 +325,7 @@ pub fn ensure_api_key_exists(existing_api_key: Option<&str>) -> Result<String, S
     }
 
     // No config key, try to load existing file-based key
-    match load_api_key_from_file()? {
+    match load_api_key_from_file(passphrase)? {
         Some(existing_key) => {
             info!("Using existing API key from file");
             Ok(existing_key)
@@ -125,7 +333,7 @@ pub fn ensure_api_key_exists(existing_api_key: Option<&str>) -> Result<String, S
         None => {
             info!("No API key found, generating new secure key...");
             let new_key = generate_secure_api_key();
-            save_api_key_to_file(&new_key)?;
+            save_api_key_to_file(&new_key, passphrase)?;
             info!("New API key generated and saved securely");
             Ok(new_key)
         }
@@ -231,6 +439,50 @@ mod tests {
         }
     }
 
+    /// 🔒 ENCRYPTION TEST: Plaintext files migrate, and a wrong passphrase is rejected
+    #[test]
+    fn test_encrypted_key_file_round_trip() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+        let path = temp_dir.path().join("api-key");
+        let key = generate_secure_api_key();
+
+        // Plaintext file from before encryption existed
+        save_api_key_at(&path, &key, None).unwrap();
+        assert_eq!(load_api_key_at(&path, None).unwrap(), Some(key.clone()));
+
+        // Configuring a passphrase encrypts it in place
+        assert_eq!(
+            load_api_key_at(&path, Some("correct horse")).unwrap(),
+            Some(key.clone())
+        );
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with(ENCRYPTED_KEY_FILE_PREFIX));
+        assert!(!content.contains(&key));
+
+        assert_eq!(
+            load_api_key_at(&path, Some("correct horse")).unwrap(),
+            Some(key)
+        );
+        assert!(load_api_key_at(&path, Some("wrong")).is_err());
+        assert!(load_api_key_at(&path, None).is_err());
+    }
+
+    /// 🛡️ PERMISSION TEST: Key files others can access are refused
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+        let path = temp_dir.path().join("api-key");
+        save_api_key_at(&path, &generate_secure_api_key(), None).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(load_api_key_at(&path, None).is_err());
+    }
+
     /// ⚡ PERFORMANCE TEST: Ensure key generation is fast
     #[test]
     fn test_key_generation_performance() {
@@ -2,14 +2,85 @@
 //!
 //! This module extends the base session management with agent-specific tracking.
 //! It follows SOLID principles by extending rather than duplicating functionality.
-
-use super::{Session as BaseSession, SessionConfig as BaseSessionConfig, SessionManager};
+//!
+//! A multi-agent collaboration, such as a workflow run in a session, gives each agent's
+//! step a child session of the session it started in; `collaboration_tree` walks the
+//! resulting tree.
+
+use super::{
+    Session as BaseSession, SessionConfig as BaseSessionConfig, SessionManager, SessionStore,
+    AGENT_METADATA_KEY,
+};
 use crate::error::Result;
 use crate::models::AgentType;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// 🌳 COLLABORATION TREE: A session and, recursively, the sessions spawned from it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CollaborationTree {
+    pub session_id: String,
+    /// Active, Suspended, Expired or Terminated
+    pub state: String,
+    /// The agent working in the session, once one has
+    pub agent_type: Option<AgentType>,
+    pub created_at: DateTime<Utc>,
+    /// Child sessions still in the store, oldest first
+    #[schema(no_recursion)]
+    pub children: Vec<CollaborationTree>,
+}
+
+/// The collaboration tree under `root`, or None if the session doesn't exist
+/// Children already removed from the store are left out
+pub async fn collaboration_tree<S: SessionStore>(
+    manager: &SessionManager<S>,
+    root: &Uuid,
+) -> Result<Option<CollaborationTree>> {
+    let Some(root) = manager.get_session(root).await? else {
+        return Ok(None);
+    };
+
+    // Fetch breadth-first, then assemble bottom-up; `seen` guards against a cycle
+    let mut sessions = vec![root];
+    let mut seen = HashSet::from([sessions[0].id]);
+    let mut next = 0;
+    while next < sessions.len() {
+        let children = sessions[next].children.clone();
+        next += 1;
+        for child_id in children {
+            if !seen.insert(child_id) {
+                continue;
+            }
+            if let Some(child) = manager.get_session(&child_id).await? {
+                sessions.push(child);
+            }
+        }
+    }
+
+    let mut built: HashMap<Uuid, CollaborationTree> = HashMap::new();
+    for session in sessions.iter().rev() {
+        let children = session
+            .children
+            .iter()
+            .filter_map(|id| built.remove(id))
+            .collect();
+        built.insert(
+            session.id,
+            CollaborationTree {
+                session_id: session.id.to_string(),
+                state: format!("{:?}", session.state),
+                agent_type: session.agent(),
+                created_at: session.created_at,
+                children,
+            },
+        );
+    }
+    Ok(built.remove(&sessions[0].id))
+}
+
 /// Agent-specific session that extends the base session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentSession {
@@ -63,6 +134,60 @@ impl<S: super::SessionStore> AgentSessionManager<S> {
         })
     }
 
+    /// 🌳 Spawn a child of `parent_id` for an agent's part of a collaboration
+    pub async fn create_child_agent_session(
+        &self,
+        parent_id: &Uuid,
+        agent_type: AgentType,
+    ) -> Result<AgentSession> {
+        let child = self.base_manager.create_child_session(parent_id).await?;
+        let child = self
+            .base_manager
+            .set_metadata(
+                &child.id,
+                AGENT_METADATA_KEY,
+                Some(format!("{agent_type:?}")),
+            )
+            .await?;
+
+        let mut assignments = self.agent_assignments.write().await;
+        assignments.insert(child.id, agent_type.clone());
+
+        Ok(AgentSession {
+            base: child,
+            agent_type,
+            agent_metadata: HashMap::new(),
+        })
+    }
+
+    /// The collaboration tree under a session
+    pub async fn collaboration_tree(&self, root: &Uuid) -> Result<Option<CollaborationTree>> {
+        collaboration_tree(&self.base_manager, root).await
+    }
+
+    /// End a session and its whole collaboration, forgetting their agent assignments
+    pub async fn terminate_collaboration(&self, root: &Uuid) -> Result<()> {
+        let ended: Vec<Uuid> = match self.collaboration_tree(root).await? {
+            Some(tree) => {
+                let mut ids = Vec::new();
+                let mut pending = vec![&tree];
+                while let Some(node) = pending.pop() {
+                    ids.extend(Uuid::parse_str(&node.session_id));
+                    pending.extend(&node.children);
+                }
+                ids
+            }
+            None => Vec::new(),
+        };
+        self.base_manager.terminate_session(root).await?;
+
+        let mut assignments = self.agent_assignments.write().await;
+        for id in ended {
+            assignments.remove(&id);
+        }
+        Ok(())
+    }
+
     /// Get agent assignment for a session
     pub async fn get_agent_for_session(&self, session_id: &Uuid) -> Option<AgentType> {
         let assignments = self.agent_assignments.read().await;
@@ -105,6 +230,9 @@ mod tests {
             max_concurrent: 10,
             auto_extend: true,
             extend_duration: Duration::minutes(30),
+            store: Default::default(),
+            budget: Default::default(),
+            policies: Default::default(),
         };
         AgentSessionManager::new(store, config)
     }
@@ -196,4 +324,52 @@ mod tests {
         assert_eq!(stats.get(&AgentType::SoftwareDeveloper), Some(&2));
         assert_eq!(stats.get(&AgentType::ProjectManager), Some(&1));
     }
+
+    #[tokio::test]
+    async fn test_collaboration_tree_and_cascading_termination() {
+        let manager = create_test_manager().await;
+        let root = manager
+            .create_agent_session("user1".to_string(), AgentType::ProjectManager)
+            .await
+            .unwrap();
+        let developer = manager
+            .create_child_agent_session(&root.base.id, AgentType::SoftwareDeveloper)
+            .await
+            .unwrap();
+        let reviewer = manager
+            .create_child_agent_session(&developer.base.id, AgentType::QualityAssurance)
+            .await
+            .unwrap();
+        assert_eq!(developer.base.parent_id, Some(root.base.id));
+        // Children don't count as the user's session
+        let active = manager.base_manager.active_session("user1").await.unwrap();
+        assert_eq!(active.map(|s| s.id), Some(root.base.id));
+
+        let tree = manager
+            .collaboration_tree(&root.base.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(
+            tree.children[0].agent_type,
+            Some(AgentType::SoftwareDeveloper)
+        );
+        assert_eq!(
+            tree.children[0].children[0].session_id,
+            reviewer.base.id.to_string()
+        );
+
+        manager
+            .terminate_collaboration(&root.base.id)
+            .await
+            .unwrap();
+        let tree = manager
+            .collaboration_tree(&root.base.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.children[0].children[0].state, "Terminated");
+        assert_eq!(manager.get_agent_for_session(&reviewer.base.id).await, None);
+    }
 }
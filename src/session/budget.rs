@@ -0,0 +1,128 @@
+//! Per-session resource budgets
+//!
+//! A session may be given limits on how many tasks it runs, what its Claude Code calls
+//! cost and how large its workspace grows. Each task attributed to the session is checked
+//! against them before it is queued, so a long-lived session can't quietly use up the
+//! system; tasks already running finish.
+
+use crate::{Result, SpiralError};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Start of the RateLimit message refusing a task over its session's budget, telling it
+/// apart from the daily budget's
+pub const SESSION_BUDGET_EXHAUSTED: &str = "Session budget used up";
+
+/// Limits on what one session may use; None is unlimited
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionBudget {
+    #[serde(default)]
+    pub max_tasks: Option<u64>,
+    /// Claude Code spend of the session's finished tasks, in US dollars
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    /// Size of the session's workspace, measured when a task is submitted
+    #[serde(default)]
+    pub max_workspace_mb: Option<u64>,
+}
+
+/// What a session has used so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionUsage {
+    pub tasks: u64,
+    pub cost_usd: f64,
+    /// Workspace size when the last task was submitted
+    pub workspace_mb: u64,
+}
+
+/// What is left of a session's budget; None where the budget sets no limit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RemainingBudget {
+    pub tasks: Option<u64>,
+    pub cost_usd: Option<f64>,
+    pub workspace_mb: Option<u64>,
+}
+
+impl SessionBudget {
+    pub fn remaining(&self, usage: &SessionUsage) -> RemainingBudget {
+        RemainingBudget {
+            tasks: self.max_tasks.map(|max| max.saturating_sub(usage.tasks)),
+            cost_usd: self.max_cost_usd.map(|max| (max - usage.cost_usd).max(0.0)),
+            workspace_mb: self
+                .max_workspace_mb
+                .map(|max| max.saturating_sub(usage.workspace_mb)),
+        }
+    }
+
+    /// 💸 SESSION BUDGET CHECK: Refuse another task once any limit is reached
+    pub fn check(&self, usage: &SessionUsage) -> Result<()> {
+        let exhausted = |what: String| {
+            Err(SpiralError::RateLimit {
+                message: format!("{SESSION_BUDGET_EXHAUSTED}: {what}; open a new session"),
+            })
+        };
+        if let Some(max) = self.max_tasks.filter(|max| usage.tasks >= *max) {
+            return exhausted(format!("{} of {max} tasks run", usage.tasks));
+        }
+        if let Some(max) = self.max_cost_usd.filter(|max| usage.cost_usd >= *max) {
+            return exhausted(format!("${:.2} of ${max:.2} spent", usage.cost_usd));
+        }
+        if let Some(max) = self
+            .max_workspace_mb
+            .filter(|max| usage.workspace_mb >= *max)
+        {
+            return exhausted(format!(
+                "workspace is {} MB of {max} MB",
+                usage.workspace_mb
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{InMemorySessionStore, SessionConfig, SessionManager};
+
+    #[tokio::test]
+    async fn test_tasks_are_refused_once_a_limit_is_reached() {
+        let budget = SessionBudget {
+            max_tasks: Some(2),
+            max_cost_usd: Some(1.0),
+            max_workspace_mb: Some(100),
+        };
+        let config = SessionConfig {
+            budget: budget.clone(),
+            ..SessionConfig::default()
+        };
+        let manager = SessionManager::new(InMemorySessionStore::new(), config);
+        let session = manager.create_session("user1".to_string()).await.unwrap();
+        assert_eq!(session.budget, budget);
+
+        let charged = manager.charge_task(&session.id, 10).await.unwrap();
+        assert_eq!(
+            charged.remaining_budget(),
+            RemainingBudget {
+                tasks: Some(1),
+                cost_usd: Some(1.0),
+                workspace_mb: Some(90),
+            }
+        );
+        // A large workspace refuses the task without counting it
+        assert!(matches!(
+            manager.charge_task(&session.id, 100).await,
+            Err(SpiralError::RateLimit { .. })
+        ));
+
+        manager.record_cost(&session.id, 1.25).await.unwrap();
+        let Err(SpiralError::RateLimit { message }) = manager.charge_task(&session.id, 10).await
+        else {
+            panic!("Spend over the budget should refuse the task");
+        };
+        assert!(message.starts_with(SESSION_BUDGET_EXHAUSTED));
+        let session = manager.validate_session(&session.id).await.unwrap();
+        assert_eq!(session.usage.tasks, 1);
+        assert_eq!(session.remaining_budget().cost_usd, Some(0.0));
+    }
+}
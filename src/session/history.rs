@@ -0,0 +1,193 @@
+//! Conversation history of a session
+//!
+//! Each finished task in a session adds the user's request and a summary of the agent's
+//! answer to a short transcript kept with the session. Later tasks in the session get its
+//! recent part in their context, so a follow-up such as "now add tests for that" knows
+//! what "that" was. Secrets are redacted before anything is stored, and the user can clear
+//! the transcript at any time.
+
+use crate::claude_code::transcripts::Redactor;
+use crate::constants::{SESSION_HISTORY_ENTRY_CHARS, SESSION_HISTORY_MAX_BYTES};
+use crate::models::{AgentType, Task};
+use crate::validation::TaskContentValidator;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Context key carrying the rendered history into the task
+pub const SESSION_HISTORY_CONTEXT_KEY: &str = "session_history";
+
+/// Who said a history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryRole {
+    User,
+    Agent,
+}
+
+/// One turn of the session's conversation, already redacted and shortened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub at: DateTime<Utc>,
+    pub role: HistoryRole,
+    /// The agent that answered, on agent entries
+    #[serde(default)]
+    pub agent_type: Option<AgentType>,
+    pub text: String,
+}
+
+/// 💬 SESSION HISTORY: Rolling transcript of a session, oldest first
+/// 🏗️ ARCHITECTURE DECISION: Kept inside the session record, bounded by total size
+/// Why: It lives and ends with the session in whichever store holds it, with no second
+/// store to keep consistent or clean up
+/// Alternative: Agent memory task summaries (rejected: those outlive the session and are
+/// per agent, while a follow-up may go to a different agent)
+/// Trade-off: Every store update rewrites the transcript, so it is kept small
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SessionHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl SessionHistory {
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Add a turn, redacted and cut to the entry length, dropping the oldest turns once
+    /// the transcript outgrows its size limit
+    pub fn push(
+        &mut self,
+        role: HistoryRole,
+        agent_type: Option<AgentType>,
+        text: &str,
+        redactor: &Redactor,
+    ) {
+        let text = redactor
+            .redact(text.trim())
+            .chars()
+            .take(SESSION_HISTORY_ENTRY_CHARS)
+            .collect();
+        self.entries.push(HistoryEntry {
+            at: Utc::now(),
+            role,
+            agent_type,
+            text,
+        });
+
+        let mut size: usize = self.entries.iter().map(|entry| entry.text.len()).sum();
+        while size > SESSION_HISTORY_MAX_BYTES && self.entries.len() > 1 {
+            size -= self.entries.remove(0).text.len();
+        }
+    }
+
+    /// The most recent turns that fit in `max_bytes`, oldest first, for the task context
+    /// Turns the Claude request validator would reject are skipped, so one odd request
+    /// cannot block every later task in the session
+    pub fn render_for_context(&self, max_bytes: usize) -> Option<String> {
+        let validator = TaskContentValidator::default();
+        let header = "Earlier in this session:";
+        let mut size = header.len();
+        let mut lines = Vec::new();
+        for entry in self.entries.iter().rev() {
+            let speaker = match (&entry.role, &entry.agent_type) {
+                (HistoryRole::User, _) => "User".to_string(),
+                (HistoryRole::Agent, Some(agent)) => format!("{agent:?}"),
+                (HistoryRole::Agent, None) => "Agent".to_string(),
+            };
+            let line = format!("\n- {speaker}: {}", entry.text);
+            if size + line.len() > max_bytes {
+                break;
+            }
+            if validator
+                .validate_and_sanitize_context_value(&line)
+                .is_err()
+            {
+                continue;
+            }
+            size += line.len();
+            lines.push(line);
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(format!("{header}{}", lines.concat()))
+    }
+}
+
+/// A finished task's answer, as kept in the history: its first non-empty line
+pub fn summarize_outcome(output: &str) -> &str {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+}
+
+/// The task's session history as a Claude request context entry, for agents that build
+/// their request context by hand instead of copying the task's
+pub fn history_context(task: &Task) -> Option<(String, String)> {
+    task.context
+        .get(SESSION_HISTORY_CONTEXT_KEY)
+        .map(|rendered| (SESSION_HISTORY_CONTEXT_KEY.to_string(), rendered.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_is_redacted_bounded_and_rendered_newest_last() {
+        let redactor = Redactor::new(["hunter2-password".to_string()]);
+        let mut history = SessionHistory::default();
+        history.push(
+            HistoryRole::User,
+            None,
+            "Log in with hunter2-password and add a login form",
+            &redactor,
+        );
+        history.push(
+            HistoryRole::Agent,
+            Some(AgentType::SoftwareDeveloper),
+            "Added LoginForm in src/login.rs",
+            &redactor,
+        );
+        assert!(!history.entries()[0].text.contains("hunter2"));
+
+        let rendered = history.render_for_context(1000).unwrap();
+        assert!(rendered.starts_with("Earlier in this session:"));
+        assert!(
+            rendered.find("add a login form").unwrap()
+                < rendered.find("SoftwareDeveloper: Added LoginForm").unwrap()
+        );
+        // Only the newest turn fits a small budget
+        let rendered = history.render_for_context(90).unwrap();
+        assert!(!rendered.contains("login form"));
+        assert!(rendered.contains("LoginForm"));
+
+        let long = "x".repeat(SESSION_HISTORY_ENTRY_CHARS * 2);
+        for _ in 0..(SESSION_HISTORY_MAX_BYTES / SESSION_HISTORY_ENTRY_CHARS + 5) {
+            history.push(HistoryRole::User, None, &long, &redactor);
+        }
+        let size: usize = history.entries().iter().map(|e| e.text.len()).sum();
+        assert!(size <= SESSION_HISTORY_MAX_BYTES);
+        assert_eq!(history.entries()[0].text.len(), SESSION_HISTORY_ENTRY_CHARS);
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.render_for_context(1000), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! 🧹 SESSION JANITOR: Periodic removal of expired sessions
+//!
+//! Front ends only clean up their store when a user opens a session, so a quiet front end
+//! would keep expired sessions forever and never announce their expiry. The janitor runs
+//! `SessionManager::cleanup` for every registered manager on an interval.
+
+use super::SessionRegistry;
+use crate::SpiralError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// What the janitor has done since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionJanitorMetrics {
+    pub runs: u64,
+    /// Runs in which a session store failed; its sessions wait for the next run
+    pub failed_runs: u64,
+    pub sessions_removed: u64,
+    /// Unix seconds of the last run, if any
+    pub last_run_at: Option<u64>,
+}
+
+/// 🧹 SESSION JANITOR: Supervised background cleanup of every registered session store
+/// 🏗️ ARCHITECTURE DECISION: One loop over the shared SessionRegistry
+/// Why: Front ends register with the registry as they start, Discord possibly after the
+/// janitor, so each run reads the managers anew rather than a list fixed at startup
+/// Alternative: A cleanup loop per manager (rejected: one shutdown path per front end)
+pub struct SessionJanitor {
+    registry: SessionRegistry,
+    interval: Duration,
+    metrics: Arc<std::sync::Mutex<SessionJanitorMetrics>>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+    shutdown_signal_sender: Mutex<Option<mpsc::Sender<()>>>,
+}
+
+impl SessionJanitor {
+    pub fn new(registry: SessionRegistry, interval: Duration) -> Self {
+        Self {
+            registry,
+            interval,
+            metrics: Arc::new(std::sync::Mutex::new(SessionJanitorMetrics::default())),
+            handle: Mutex::new(None),
+            shutdown_signal_sender: Mutex::new(None),
+        }
+    }
+
+    /// Start the cleanup loop; the first run happens one interval from now
+    pub async fn start(&self) -> Result<(), SpiralError> {
+        let mut handle_guard = self.handle.lock().await;
+        if handle_guard.is_some() {
+            return Err(SpiralError::Validation(
+                "Session janitor is already running".to_string(),
+            ));
+        }
+        info!(
+            "Starting session janitor with {}s intervals",
+            self.interval.as_secs()
+        );
+
+        let (shutdown_signal_sender, mut shutdown_signal_receiver) = mpsc::channel::<()>(1);
+        *self.shutdown_signal_sender.lock().await = Some(shutdown_signal_sender);
+
+        let registry = self.registry.clone();
+        let metrics = Arc::clone(&self.metrics);
+        let period = self.interval;
+        *handle_guard = Some(tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        run(&registry, &metrics).await;
+                    }
+                    _ = shutdown_signal_receiver.recv() => {
+                        info!("Session janitor shutting down gracefully");
+                        break;
+                    }
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    /// Remove expired sessions from every registered store now; returns how many
+    pub async fn run_once(&self) -> usize {
+        run(&self.registry, &self.metrics).await
+    }
+
+    pub fn metrics(&self) -> SessionJanitorMetrics {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Stop the loop, letting a run in progress finish
+    pub async fn shutdown(&self) {
+        if let Some(sender) = self.shutdown_signal_sender.lock().await.take() {
+            let _ = sender.send(()).await;
+        }
+        if let Some(handle) = self.handle.lock().await.take() {
+            if let Err(e) = handle.await {
+                warn!("Error waiting for session janitor to complete: {}", e);
+            }
+        }
+    }
+}
+
+async fn run(
+    registry: &SessionRegistry,
+    metrics: &std::sync::Mutex<SessionJanitorMetrics>,
+) -> usize {
+    let mut removed = 0;
+    let mut failed = false;
+    for manager in registry.managers() {
+        match manager.cleanup().await {
+            Ok(count) => removed += count,
+            Err(e) => {
+                warn!("Session janitor failed to clean up a session store: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    if removed > 0 {
+        info!("Session janitor removed {} expired session(s)", removed);
+    } else {
+        debug!("Session janitor found no expired sessions");
+    }
+    let mut metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+    metrics.runs += 1;
+    metrics.failed_runs += u64::from(failed);
+    metrics.sessions_removed += removed as u64;
+    metrics.last_run_at = Some(chrono::Utc::now().timestamp().max(0) as u64);
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{SessionConfig, SessionManager};
+
+    #[tokio::test]
+    async fn test_janitor_removes_expired_sessions_of_late_registered_managers() {
+        let registry = SessionRegistry::new();
+        let janitor = SessionJanitor::new(registry.clone(), Duration::from_millis(50));
+        janitor.start().await.unwrap();
+        assert!(janitor.start().await.is_err());
+
+        // Registered after the janitor started, as the Discord bot is
+        let manager = Arc::new(SessionManager::from_config_or_memory(
+            SessionConfig {
+                max_duration: chrono::Duration::milliseconds(10),
+                auto_extend: false,
+                ..SessionConfig::default()
+            },
+            "test sessions",
+        ));
+        registry.register(manager.clone());
+        let session = manager.create_session("user1".to_string()).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while janitor.metrics().sessions_removed == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Janitor never removed the expired session");
+        assert!(manager.get_session(&session.id).await.unwrap().is_none());
+
+        janitor.shutdown().await;
+        let runs = janitor.metrics().runs;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(janitor.metrics().runs, runs);
+        assert_eq!(janitor.run_once().await, 0);
+    }
+}
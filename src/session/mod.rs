@@ -37,17 +37,51 @@
 //! # Submodules
 //!
 //! - `agent_sessions` - Agent-specific session management extensions
+//! - `budget` - Limits on the tasks, spend and workspace of one session
+//! - `history` - The session's recent requests and answers, given to its later tasks
+//! - `janitor` - Background removal of expired sessions
+//! - `policy` - Idle timeouts, absolute lifetimes and remember-me sessions
+//! - `sqlite` - Sessions in a SQLite file, surviving restarts
+//! - `redis` - Sessions in Redis, shared between instances
 
 pub mod agent_sessions;
+pub mod budget;
+pub mod history;
+pub mod janitor;
+pub mod policy;
+pub mod redis;
+pub mod sqlite;
+
+pub use self::budget::{RemainingBudget, SessionBudget, SessionUsage, SESSION_BUDGET_EXHAUSTED};
+pub use self::history::{HistoryEntry, HistoryRole, SessionHistory, SESSION_HISTORY_CONTEXT_KEY};
+pub use self::janitor::{SessionJanitor, SessionJanitorMetrics};
+pub use self::policy::{SessionKind, SessionPolicies, SessionPolicy};
+pub use self::redis::RedisSessionStore;
+pub use self::sqlite::SqliteSessionStore;
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
+use crate::claude_code::sessions::{
+    is_valid_shared_session_key, MANAGED_SESSION_KEY_PREFIX, SESSION_CONTEXT_KEY,
+};
+use crate::claude_code::transcripts::Redactor;
+use crate::constants::SESSION_EVENT_CAPACITY;
 use crate::error::{Result, SpiralError};
+use crate::models::{AgentType, Task};
+
+/// Session metadata key naming the workspace the session's tasks share
+pub const WORKSPACE_METADATA_KEY: &str = "workspace";
+
+/// Session metadata key holding the agent the session last worked with
+pub const AGENT_METADATA_KEY: &str = "agent";
+
+/// Task context key naming the session a task was created in
+pub const USER_SESSION_CONTEXT_KEY: &str = "user_session_id";
 
 /// Session configuration with sensible defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +94,16 @@ pub struct SessionConfig {
     pub auto_extend: bool,
     /// Duration to extend session on activity
     pub extend_duration: Duration,
+    /// Where sessions are kept; configs that predate the choice keep them in memory
+    #[serde(default)]
+    pub store: SessionStoreBackend,
+    /// Budget each new session starts with; unlimited by default
+    #[serde(default)]
+    pub budget: SessionBudget,
+    /// Idle timeouts and lifetimes, by user and session kind; an idle timeout takes the
+    /// place of extend_duration and max_duration for the sessions it applies to
+    #[serde(default)]
+    pub policies: SessionPolicies,
 }
 
 impl Default for SessionConfig {
@@ -69,6 +113,47 @@ impl Default for SessionConfig {
             max_concurrent: 5,
             auto_extend: true,
             extend_duration: Duration::hours(1),
+            store: SessionStoreBackend::default(),
+            budget: SessionBudget::default(),
+            policies: SessionPolicies::default(),
+        }
+    }
+}
+
+/// 🗄️ SESSION STORE CHOICE: Which SessionStore implementation keeps sessions
+/// 🏗️ ARCHITECTURE DECISION: Part of SessionConfig, opened by SessionManager::from_config
+/// Why: Callers pick a backend by configuration instead of naming a store type, so the
+/// same code runs on one instance with a file or on several sharing Redis
+/// Alternative: A generic parameter chosen at compile time (rejected: deployments differ)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SessionStoreBackend {
+    /// Process memory; sessions end with the process
+    #[default]
+    Memory,
+    /// A SQLite database file; sessions survive restarts of this instance
+    Sqlite { path: String },
+    /// A Redis server, `redis://[:password@]host[:port][/db]`; sessions are shared by
+    /// every instance using it
+    Redis { url: String },
+}
+
+impl SessionStoreBackend {
+    /// Open the chosen store; Redis connects on first use
+    pub fn open(&self) -> Result<Arc<dyn SessionStore>> {
+        Ok(match self {
+            SessionStoreBackend::Memory => Arc::new(InMemorySessionStore::new()),
+            SessionStoreBackend::Sqlite { path } => Arc::new(SqliteSessionStore::open(path)?),
+            SessionStoreBackend::Redis { url } => Arc::new(RedisSessionStore::new(url)?),
+        })
+    }
+
+    /// Short name for logs; a Redis URL may hold a password, so it is left out
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SessionStoreBackend::Memory => "memory",
+            SessionStoreBackend::Sqlite { .. } => "sqlite",
+            SessionStoreBackend::Redis { .. } => "redis",
         }
     }
 }
@@ -89,6 +174,97 @@ pub struct Session {
     /// Session metadata for extensibility
     pub metadata: HashMap<String, String>,
     pub state: SessionState,
+    /// Limits on the session's use; sessions stored before budgets existed are unlimited
+    #[serde(default)]
+    pub budget: SessionBudget,
+    #[serde(default)]
+    pub usage: SessionUsage,
+    /// The session that spawned this one, for a step of a multi-agent collaboration
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Sessions spawned from this one, oldest first
+    #[serde(default)]
+    pub children: Vec<Uuid>,
+    /// Recent requests and answers; a collaboration keeps its history on the root session
+    #[serde(default)]
+    pub history: SessionHistory,
+    /// Which of the configured policies bounds the session
+    #[serde(default)]
+    pub kind: SessionKind,
+}
+
+impl Session {
+    /// What the session may still use before its tasks are refused
+    pub fn remaining_budget(&self) -> RemainingBudget {
+        self.budget.remaining(&self.usage)
+    }
+
+    /// The workspace the session's tasks share, if one was chosen
+    /// Another session's own workspace can't be chosen, so its tasks stay out of it
+    pub fn workspace(&self) -> Option<&str> {
+        self.metadata
+            .get(WORKSPACE_METADATA_KEY)
+            .map(String::as_str)
+            .filter(|workspace| {
+                is_valid_shared_session_key(workspace)
+                    && !workspace.starts_with(MANAGED_SESSION_KEY_PREFIX)
+            })
+    }
+
+    /// The agent the session last worked with; requests naming no agent go to it
+    pub fn agent(&self) -> Option<AgentType> {
+        self.metadata
+            .get(AGENT_METADATA_KEY)
+            .and_then(|agent| agent.parse().ok())
+    }
+
+    /// The Claude session key of the session's own workspace, which is archived once the
+    /// session ends
+    pub fn own_workspace_key(&self) -> String {
+        format!("{MANAGED_SESSION_KEY_PREFIX}{}", self.id)
+    }
+
+    /// The Claude session key the session's tasks share: the chosen workspace, or its own
+    pub fn workspace_key(&self) -> String {
+        self.workspace()
+            .map_or_else(|| self.own_workspace_key(), str::to_string)
+    }
+
+    /// 🗂️ SESSION CONTEXT: A task created in the session is tagged with it and works in the
+    /// session's workspace, unless its request named a Claude session of its own
+    pub fn apply_to_task(&self, task: Task) -> Task {
+        let task = task.with_context(USER_SESSION_CONTEXT_KEY.to_string(), self.id.to_string());
+        if task.context.contains_key(SESSION_CONTEXT_KEY) {
+            return task;
+        }
+        task.with_context(SESSION_CONTEXT_KEY.to_string(), self.workspace_key())
+    }
+}
+
+/// The session whose own workspace a Claude session key names, if it names one
+pub fn session_of_workspace_key(key: &str) -> Option<Uuid> {
+    key.strip_prefix(MANAGED_SESSION_KEY_PREFIX)
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// A change in a session's lifecycle, broadcast to the subscribers of its manager
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    /// The session was opened
+    Created(Session),
+    /// The session outlived its expiry, noticed on use or by cleanup
+    Expired(Session),
+    /// The session was ended on purpose
+    Terminated(Session),
+}
+
+impl SessionEvent {
+    /// The session the event is about, as it was when the event happened
+    pub fn session(&self) -> &Session {
+        match self {
+            Self::Created(session) | Self::Expired(session) | Self::Terminated(session) => session,
+        }
+    }
 }
 
 /// Session lifecycle states
@@ -122,8 +298,36 @@ pub trait SessionStore: Send + Sync {
     /// List all sessions for a user
     async fn list_by_user(&self, user_id: &str) -> Result<Vec<Session>>;
 
-    /// Clean up expired sessions
-    async fn cleanup_expired(&self) -> Result<usize>;
+    /// Remove expired sessions, returning the sessions removed
+    async fn cleanup_expired(&self) -> Result<Vec<Session>>;
+}
+
+/// A shared store is a store too, so a SessionManager can hold whichever one was configured
+#[async_trait::async_trait]
+impl<T: SessionStore + ?Sized> SessionStore for Arc<T> {
+    async fn create(&self, session: Session) -> Result<()> {
+        (**self).create(session).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Session>> {
+        (**self).get(id).await
+    }
+
+    async fn update(&self, session: Session) -> Result<()> {
+        (**self).update(session).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        (**self).delete(id).await
+    }
+
+    async fn list_by_user(&self, user_id: &str) -> Result<Vec<Session>> {
+        (**self).list_by_user(user_id).await
+    }
+
+    async fn cleanup_expired(&self) -> Result<Vec<Session>> {
+        (**self).cleanup_expired().await
+    }
 }
 
 /// In-memory session store implementation
@@ -201,7 +405,7 @@ impl SessionStore for InMemorySessionStore {
         Ok(user_sessions)
     }
 
-    async fn cleanup_expired(&self) -> Result<usize> {
+    async fn cleanup_expired(&self) -> Result<Vec<Session>> {
         let mut sessions = self.sessions.write().await;
         let now = Utc::now();
 
@@ -211,13 +415,42 @@ impl SessionStore for InMemorySessionStore {
             .map(|(id, _)| *id)
             .collect();
 
-        let count = expired_ids.len();
+        Ok(expired_ids
+            .iter()
+            .filter_map(|id| sessions.remove(id))
+            .collect())
+    }
+}
 
-        for id in expired_ids {
-            sessions.remove(&id);
-        }
+/// A session manager over the store the configuration chose, shared by its users
+pub type SharedSessionManager = Arc<SessionManager<Arc<dyn SessionStore>>>;
 
-        Ok(count)
+/// 🗂️ SESSION REGISTRY: The session managers of every front end that opens sessions
+/// Subsystems that look after sessions, such as workspace archiving and the janitor, share
+/// one registry, so a front end registers its manager once
+#[derive(Clone, Default)]
+pub struct SessionRegistry {
+    managers: Arc<std::sync::RwLock<Vec<SharedSessionManager>>>,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, manager: SharedSessionManager) {
+        self.managers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(manager);
+    }
+
+    /// The managers registered so far
+    pub fn managers(&self) -> Vec<SharedSessionManager> {
+        self.managers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
     }
 }
 
@@ -225,21 +458,177 @@ impl SessionStore for InMemorySessionStore {
 pub struct SessionManager<S: SessionStore> {
     store: S,
     config: SessionConfig,
+    events: broadcast::Sender<SessionEvent>,
+    /// Scrubs credentials from history before it is stored
+    redactor: Redactor,
+}
+
+impl SessionManager<Arc<dyn SessionStore>> {
+    /// Create a session manager over the store `config.store` chooses
+    pub fn from_config(config: SessionConfig) -> Result<Self> {
+        let store = config.store.open()?;
+        Ok(Self::new(store, config))
+    }
+
+    /// Like from_config, but a store that can't be opened degrades to process memory
+    /// rather than blocking startup; `purpose` names the sessions in the log
+    pub fn from_config_or_memory(config: SessionConfig, purpose: &str) -> Self {
+        match Self::from_config(config.clone()) {
+            Ok(manager) => {
+                tracing::info!("Keeping {} in the {} store", purpose, config.store.kind());
+                manager
+            }
+            Err(e) => {
+                tracing::warn!("{}, keeping {} in process only", e, purpose);
+                let store: Arc<dyn SessionStore> = Arc::new(InMemorySessionStore::new());
+                Self::new(
+                    store,
+                    SessionConfig {
+                        store: SessionStoreBackend::Memory,
+                        ..config
+                    },
+                )
+            }
+        }
+    }
 }
 
 impl<S: SessionStore> SessionManager<S> {
     /// Create a new session manager
     pub fn new(store: S, config: SessionConfig) -> Self {
-        Self { store, config }
+        let (events, _) = broadcast::channel(SESSION_EVENT_CAPACITY);
+        Self {
+            store,
+            config,
+            events,
+            redactor: Redactor::from_env(),
+        }
+    }
+
+    /// 📣 SESSION EVENTS: Lifecycle events from now on
+    /// 🏗️ ARCHITECTURE DECISION: A broadcast channel rather than registered callbacks
+    /// Why: Listeners such as the orchestrator and the monitor run on their own tasks, so
+    /// a slow one never holds up the request that opened or ended the session
+    /// Alternative: Callback list (rejected: callbacks would run inside session calls)
+    /// Trade-off: Only events after subscribing arrive, and a listener that falls more than
+    /// SESSION_EVENT_CAPACITY behind skips the oldest
+    pub fn subscribe(&self) -> broadcast::Receiver<SessionEvent> {
+        self.events.subscribe()
+    }
+
+    fn emit(&self, event: SessionEvent) {
+        // No subscribers is not an error; nobody is listening yet
+        let _ = self.events.send(event);
+    }
+
+    /// The user's unexpired active session used most recently, if any; sessions spawned
+    /// for a collaboration belong to their parent rather than to the user
+    pub async fn active_session(&self, user_id: &str) -> Result<Option<Session>> {
+        let now = Utc::now();
+        Ok(self
+            .store
+            .list_by_user(user_id)
+            .await?
+            .into_iter()
+            .filter(|s| {
+                s.state == SessionState::Active && s.expires_at >= now && s.parent_id.is_none()
+            })
+            .max_by_key(|s| s.last_activity))
+    }
+
+    /// Whether a session may still be used: active or suspended, and not expired
+    pub async fn is_live(&self, id: &Uuid) -> Result<bool> {
+        let now = Utc::now();
+        Ok(self.store.get(id).await?.is_some_and(|s| {
+            matches!(s.state, SessionState::Active | SessionState::Suspended) && s.expires_at >= now
+        }))
+    }
+
+    /// A session by ID, whatever its state; unlike validate_session it does not extend it
+    pub async fn get_session(&self, id: &Uuid) -> Result<Option<Session>> {
+        self.store.get(id).await
+    }
+
+    /// Set one metadata entry of a session; None removes it
+    pub async fn set_metadata(
+        &self,
+        id: &Uuid,
+        key: &str,
+        value: Option<String>,
+    ) -> Result<Session> {
+        let mut session = self
+            .store
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+
+        match value {
+            Some(value) => session.metadata.insert(key.to_string(), value),
+            None => session.metadata.remove(key),
+        };
+        self.store.update(session.clone()).await?;
+        Ok(session)
+    }
+
+    /// The expiry of a session in use at `now`
+    /// A policy with an idle timeout sets it alone, capped by the lifetime; otherwise the
+    /// session slides by extend_duration up to max_duration ahead, capped by the lifetime
+    fn expiry_at(&self, session: &Session, now: DateTime<Utc>, opening: bool) -> DateTime<Utc> {
+        let policy = self
+            .config
+            .policies
+            .for_session(&session.user_id, session.kind);
+        let sliding = match (policy.idle_timeout, opening) {
+            (Some(_), _) => None,
+            (None, true) => Some(now + self.config.max_duration),
+            (None, false) => Some(
+                (session.expires_at + self.config.extend_duration)
+                    .min(now + self.config.max_duration),
+            ),
+        };
+        let bound = policy.deadline(session.created_at, now);
+        match (sliding, bound) {
+            (Some(sliding), Some(bound)) => sliding.min(bound),
+            (sliding, bound) => sliding.or(bound).unwrap_or(session.expires_at),
+        }
+    }
+
+    /// ⏳ SESSION EXPIRY: Why the session has ended by `now`, if it has
+    /// The policy is checked as well as the stored expiry, so a policy tightened since the
+    /// session was last used applies at once
+    fn expiry_reason(&self, session: &Session, now: DateTime<Utc>) -> Option<String> {
+        let policy = self
+            .config
+            .policies
+            .for_session(&session.user_id, session.kind);
+        let deadline = policy
+            .deadline(session.created_at, session.last_activity)
+            .map_or(session.expires_at, |bound| bound.min(session.expires_at));
+        (deadline < now).then(|| {
+            format!(
+                "Session expired: it {}",
+                policy.expiry_reason(session.created_at, now)
+            )
+        })
     }
 
     /// Create a new session for a user
     pub async fn create_session(&self, user_id: String) -> Result<Session> {
-        // Check concurrent session limit
+        self.create_session_of_kind(user_id, SessionKind::Standard)
+            .await
+    }
+
+    /// Create a new session of a kind, such as a long-lived remember-me session
+    pub async fn create_session_of_kind(
+        &self,
+        user_id: String,
+        kind: SessionKind,
+    ) -> Result<Session> {
+        // Check concurrent session limit; a collaboration's sessions count as its root
         let existing = self.store.list_by_user(&user_id).await?;
         let active_count = existing
             .iter()
-            .filter(|s| s.state == SessionState::Active)
+            .filter(|s| s.state == SessionState::Active && s.parent_id.is_none())
             .count();
 
         if active_count >= self.config.max_concurrent {
@@ -250,7 +639,7 @@ impl<S: SessionStore> SessionManager<S> {
         }
 
         let now = Utc::now();
-        let session = Session {
+        let mut session = Session {
             id: Uuid::new_v4(),
             user_id,
             created_at: now,
@@ -258,12 +647,60 @@ impl<S: SessionStore> SessionManager<S> {
             expires_at: now + self.config.max_duration,
             metadata: HashMap::new(),
             state: SessionState::Active,
+            budget: self.config.budget.clone(),
+            usage: SessionUsage::default(),
+            parent_id: None,
+            children: Vec::new(),
+            history: SessionHistory::default(),
+            kind,
         };
+        session.expires_at = self.expiry_at(&session, now, true);
 
         self.store.create(session.clone()).await?;
+        self.emit(SessionEvent::Created(session.clone()));
         Ok(session)
     }
 
+    /// 🌳 CHILD SESSION: Spawn a session for one step of a collaboration the parent runs
+    /// The child belongs to the parent's user, ends with the parent at the latest, and
+    /// doesn't count against the user's concurrent sessions
+    pub async fn create_child_session(&self, parent_id: &Uuid) -> Result<Session> {
+        let mut parent = self
+            .store
+            .get(parent_id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Parent session not found".to_string()))?;
+        if parent.state != SessionState::Active || parent.expires_at < Utc::now() {
+            return Err(SpiralError::Validation(format!(
+                "Parent session is not active: {:?}",
+                parent.state
+            )));
+        }
+
+        let now = Utc::now();
+        let child = Session {
+            id: Uuid::new_v4(),
+            user_id: parent.user_id.clone(),
+            created_at: now,
+            last_activity: now,
+            expires_at: parent.expires_at,
+            metadata: HashMap::new(),
+            state: SessionState::Active,
+            budget: self.config.budget.clone(),
+            usage: SessionUsage::default(),
+            parent_id: Some(parent.id),
+            children: Vec::new(),
+            history: SessionHistory::default(),
+            kind: parent.kind,
+        };
+
+        self.store.create(child.clone()).await?;
+        parent.children.push(child.id);
+        self.store.update(parent).await?;
+        self.emit(SessionEvent::Created(child.clone()));
+        Ok(child)
+    }
+
     /// Validate and optionally extend a session
     pub async fn validate_session(&self, id: &Uuid) -> Result<Session> {
         let session = self
@@ -272,12 +709,17 @@ impl<S: SessionStore> SessionManager<S> {
             .await?
             .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
 
-        // Check if session is expired
-        if session.expires_at < Utc::now() {
+        // Check if session is expired, by its expiry or its policy
+        let now = Utc::now();
+        if let Some(reason) = self.expiry_reason(&session, now) {
             let mut expired = session.clone();
             expired.state = SessionState::Expired;
-            self.store.update(expired).await?;
-            return Err(SpiralError::Validation("Session expired".to_string()));
+            expired.expires_at = expired.expires_at.min(now);
+            self.store.update(expired.clone()).await?;
+            if session.state != SessionState::Expired {
+                self.emit(SessionEvent::Expired(expired));
+            }
+            return Err(SpiralError::Validation(reason));
         }
 
         // Check if session is active
@@ -288,17 +730,12 @@ impl<S: SessionStore> SessionManager<S> {
             )));
         }
 
-        // Auto-extend if enabled
+        // Auto-extend if enabled: from the current expiry by extend_duration, capped at
+        // max_duration from now, or a full idle timeout from now; never past the lifetime
         if self.config.auto_extend {
             let mut updated = session.clone();
-            let now = Utc::now();
             updated.last_activity = now;
-            // Extend from the current expiry, not from now
-            // This ensures the session always gets longer, never shorter
-            let new_expiry = updated.expires_at + self.config.extend_duration;
-            // But cap it at max_duration from now
-            let max_expiry = now + self.config.max_duration;
-            updated.expires_at = new_expiry.min(max_expiry);
+            updated.expires_at = self.expiry_at(&session, now, false);
             self.store.update(updated.clone()).await?;
             return Ok(updated);
         }
@@ -306,16 +743,34 @@ impl<S: SessionStore> SessionManager<S> {
         Ok(session)
     }
 
-    /// Terminate a session
+    /// Terminate a session and every session spawned from it
     pub async fn terminate_session(&self, id: &Uuid) -> Result<()> {
-        let mut session = self
+        let session = self
             .store
             .get(id)
             .await?
             .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
 
-        session.state = SessionState::Terminated;
-        self.store.update(session).await
+        let mut pending = vec![session];
+        let mut seen = std::collections::HashSet::new();
+        while let Some(mut session) = pending.pop() {
+            if !seen.insert(session.id) {
+                continue;
+            }
+            for child_id in &session.children {
+                // Children removed by cleanup have nothing left to end
+                if let Some(child) = self.store.get(child_id).await? {
+                    pending.push(child);
+                }
+            }
+            if session.state == SessionState::Terminated && session.id != *id {
+                continue;
+            }
+            session.state = SessionState::Terminated;
+            self.store.update(session.clone()).await?;
+            self.emit(SessionEvent::Terminated(session));
+        }
+        Ok(())
     }
 
     /// Suspend a session
@@ -350,11 +805,12 @@ impl<S: SessionStore> SessionManager<S> {
             ));
         }
 
-        // Check if still valid
-        if session.expires_at < Utc::now() {
+        // Check if still valid; time suspended counts as idle
+        if let Some(reason) = self.expiry_reason(&session, Utc::now()) {
             session.state = SessionState::Expired;
-            self.store.update(session).await?;
-            return Err(SpiralError::Validation("Session expired".to_string()));
+            self.store.update(session.clone()).await?;
+            self.emit(SessionEvent::Expired(session));
+            return Err(SpiralError::Validation(reason));
         }
 
         session.state = SessionState::Active;
@@ -363,9 +819,116 @@ impl<S: SessionStore> SessionManager<S> {
         Ok(session)
     }
 
-    /// Clean up expired sessions
+    /// 💸 SESSION BUDGET: Count a task against the session, given its workspace's current
+    /// size; fails with RateLimit, charging nothing, once any limit is reached
+    pub async fn charge_task(&self, id: &Uuid, workspace_mb: u64) -> Result<Session> {
+        let mut session = self
+            .store
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+
+        session.usage.workspace_mb = workspace_mb;
+        session.budget.check(&session.usage)?;
+        session.usage.tasks += 1;
+        self.store.update(session.clone()).await?;
+        Ok(session)
+    }
+
+    /// Give back a task charged by `charge_task` that was then not submitted
+    pub async fn refund_task(&self, id: &Uuid) -> Result<()> {
+        let mut session = self
+            .store
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+
+        session.usage.tasks = session.usage.tasks.saturating_sub(1);
+        self.store.update(session).await
+    }
+
+    /// Add a finished task's Claude Code spend to the session
+    pub async fn record_cost(&self, id: &Uuid, cost_usd: f64) -> Result<()> {
+        let mut session = self
+            .store
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+
+        session.usage.cost_usd += cost_usd;
+        self.store.update(session).await
+    }
+
+    /// The session a collaboration's history is kept on: the root above `id`
+    async fn history_root(&self, id: &Uuid) -> Result<Session> {
+        let mut session = self
+            .store
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+        let mut seen = std::collections::HashSet::from([session.id]);
+        while let Some(parent_id) = session.parent_id.filter(|parent| seen.insert(*parent)) {
+            match self.store.get(&parent_id).await? {
+                Some(parent) => session = parent,
+                None => break,
+            }
+        }
+        Ok(session)
+    }
+
+    /// 💬 SESSION HISTORY: Add a finished task's request and answer to its session's
+    /// history, redacting secrets; an empty answer records the request alone
+    pub async fn record_exchange(
+        &self,
+        id: &Uuid,
+        request: &str,
+        agent_type: &AgentType,
+        answer: &str,
+    ) -> Result<()> {
+        let mut session = self.history_root(id).await?;
+        session
+            .history
+            .push(HistoryRole::User, None, request, &self.redactor);
+        if !answer.trim().is_empty() {
+            session.history.push(
+                HistoryRole::Agent,
+                Some(agent_type.clone()),
+                answer,
+                &self.redactor,
+            );
+        }
+        self.store.update(session).await
+    }
+
+    /// The history later tasks of a session are given
+    pub async fn history(&self, id: &Uuid) -> Result<SessionHistory> {
+        Ok(self.history_root(id).await?.history)
+    }
+
+    /// Forget a session's history, returning how many turns it held
+    pub async fn clear_history(&self, id: &Uuid) -> Result<usize> {
+        let mut session = self.history_root(id).await?;
+        let cleared = session.history.len();
+        session.history.clear();
+        self.store.update(session).await?;
+        Ok(cleared)
+    }
+
+    /// Clean up expired sessions, announcing those that expired unnoticed
     pub async fn cleanup(&self) -> Result<usize> {
-        self.store.cleanup_expired().await
+        let removed = self.store.cleanup_expired().await?;
+        let count = removed.len();
+        for mut session in removed {
+            // Sessions already marked Expired or Terminated were announced back then
+            if matches!(
+                session.state,
+                SessionState::Active | SessionState::Suspended
+            ) {
+                session.state = SessionState::Expired;
+                self.emit(SessionEvent::Expired(session));
+            }
+        }
+        Ok(count)
     }
 }
 
@@ -381,6 +944,9 @@ mod tests {
             max_concurrent: 2,
             auto_extend: true,
             extend_duration: Duration::minutes(30),
+            store: SessionStoreBackend::Memory,
+            budget: SessionBudget::default(),
+            policies: SessionPolicies::default(),
         };
         SessionManager::new(store, config)
     }
@@ -469,6 +1035,9 @@ mod tests {
             max_concurrent: 10,
             auto_extend: false,
             extend_duration: Duration::minutes(30),
+            store: SessionStoreBackend::Memory,
+            budget: SessionBudget::default(),
+            policies: SessionPolicies::default(),
         };
         let manager = SessionManager::new(store, config);
 
@@ -493,6 +1062,161 @@ mod tests {
         assert_eq!(remaining.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_session_workspace_outlives_the_session_only_while_live() {
+        let manager = create_test_manager().await;
+        let session = manager.create_session("user1".to_string()).await.unwrap();
+
+        // Tasks share the session's own workspace, which maps back to the session
+        let task = session.apply_to_task(Task::new(
+            AgentType::SoftwareDeveloper,
+            "Add a test".to_string(),
+            crate::models::Priority::Medium,
+        ));
+        let key = task.context.get(SESSION_CONTEXT_KEY).unwrap();
+        assert_eq!(key, &session.own_workspace_key());
+        assert_eq!(session_of_workspace_key(key), Some(session.id));
+        assert_eq!(session_of_workspace_key("feature-x"), None);
+
+        let named = manager
+            .set_metadata(
+                &session.id,
+                WORKSPACE_METADATA_KEY,
+                Some("feature-x".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(named.workspace_key(), "feature-x");
+
+        assert!(manager.is_live(&session.id).await.unwrap());
+        manager.terminate_session(&session.id).await.unwrap();
+        assert!(!manager.is_live(&session.id).await.unwrap());
+        assert!(!manager.is_live(&Uuid::new_v4()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_lifetime_and_remember_me_expiry() {
+        let tick = tokio::time::Duration::from_millis(200);
+        let mut policies = SessionPolicies {
+            default: SessionPolicy {
+                idle_timeout: Some(Duration::milliseconds(300)),
+                max_lifetime: None,
+            },
+            remember_me: SessionPolicy {
+                idle_timeout: Some(Duration::hours(1)),
+                max_lifetime: Some(Duration::hours(2)),
+            },
+            users: HashMap::new(),
+        };
+        policies.users.insert(
+            "busy".to_string(),
+            SessionPolicy {
+                idle_timeout: None,
+                max_lifetime: Some(Duration::milliseconds(500)),
+            },
+        );
+        let config = SessionConfig {
+            max_concurrent: 10,
+            policies,
+            ..SessionConfig::default()
+        };
+        let manager = SessionManager::new(InMemorySessionStore::new(), config);
+
+        let idle = manager.create_session("idle".to_string()).await.unwrap();
+        let active = manager.create_session("active".to_string()).await.unwrap();
+        let busy = manager.create_session("busy".to_string()).await.unwrap();
+        let remembered = manager
+            .create_session_of_kind("idle".to_string(), SessionKind::RememberMe)
+            .await
+            .unwrap();
+        assert_eq!(
+            remembered.expires_at - remembered.created_at,
+            Duration::hours(1)
+        );
+
+        // Each use slides the idle window, but not past the lifetime
+        for _ in 0..2 {
+            tokio::time::sleep(tick).await;
+            manager.validate_session(&active.id).await.unwrap();
+            manager.validate_session(&busy.id).await.unwrap();
+        }
+        tokio::time::sleep(tick).await;
+
+        let idle_error = manager.validate_session(&idle.id).await.unwrap_err();
+        assert!(idle_error.to_string().contains("idle"), "{idle_error}");
+        let busy_error = manager.validate_session(&busy.id).await.unwrap_err();
+        assert!(
+            busy_error.to_string().contains("maximum lifetime"),
+            "{busy_error}"
+        );
+        assert!(manager.validate_session(&remembered.id).await.is_ok());
+        let active = manager.validate_session(&active.id).await.unwrap();
+        assert!(active.expires_at <= Utc::now() + Duration::milliseconds(300));
+    }
+
+    #[tokio::test]
+    async fn test_collaboration_shares_its_root_history() {
+        let manager = SessionManager::new(InMemorySessionStore::new(), SessionConfig::default());
+        let root = manager.create_session("user1".to_string()).await.unwrap();
+        let step = manager.create_child_session(&root.id).await.unwrap();
+
+        manager
+            .record_exchange(
+                &step.id,
+                "Add a login form",
+                &AgentType::SoftwareDeveloper,
+                "Added LoginForm",
+            )
+            .await
+            .unwrap();
+        let history = manager.history(&root.id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.entries()[0].role, HistoryRole::User);
+        assert_eq!(manager.history(&step.id).await.unwrap(), history);
+
+        assert_eq!(manager.clear_history(&root.id).await.unwrap(), 2);
+        assert!(manager.history(&step.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_reach_subscribers() {
+        let store = InMemorySessionStore::new();
+        let config = SessionConfig {
+            max_duration: Duration::milliseconds(100),
+            auto_extend: false,
+            store: SessionStoreBackend::Memory,
+            ..SessionConfig::default()
+        };
+        let manager = SessionManager::new(store, config);
+        let mut events = manager.subscribe();
+
+        let ended = manager.create_session("user1".to_string()).await.unwrap();
+        let lapsed = manager.create_session("user1".to_string()).await.unwrap();
+        manager.terminate_session(&ended.id).await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        assert_eq!(manager.cleanup().await.unwrap(), 2);
+
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            let kind = match &event {
+                SessionEvent::Created(_) => "created",
+                SessionEvent::Expired(_) => "expired",
+                SessionEvent::Terminated(_) => "terminated",
+            };
+            seen.push((kind, event.session().id));
+        }
+        // The terminated session was already announced, so cleanup only reports the other
+        assert_eq!(
+            seen,
+            vec![
+                ("created", ended.id),
+                ("created", lapsed.id),
+                ("terminated", ended.id),
+                ("expired", lapsed.id),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_session_not_found() {
         let manager = create_test_manager().await;
@@ -0,0 +1,145 @@
+//! Session expiry policies
+//!
+//! Besides the expiry it pushes back on each use, a session may be bound by an idle
+//! timeout, ending it once it goes unused for that long, and an absolute lifetime, ending
+//! it that long after it was opened however busy it is. A user can be given policies of
+//! their own, and a "remember me" session follows a separate, longer-lived policy.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How long a session may go unused and how long it may live; None sets no bound
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionPolicy {
+    /// Ends the session this long after its last use; each use starts the wait over
+    #[serde(default)]
+    pub idle_timeout: Option<Duration>,
+    /// Ends the session this long after it was opened, however often it is used
+    #[serde(default)]
+    pub max_lifetime: Option<Duration>,
+}
+
+impl SessionPolicy {
+    /// The expiry the policy allows for a session opened at `created_at` and last used
+    /// at `last_activity`, if it bounds it at all
+    pub fn deadline(
+        &self,
+        created_at: DateTime<Utc>,
+        last_activity: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let idle = self.idle_timeout.map(|idle| last_activity + idle);
+        let lifetime = self.max_lifetime.map(|lifetime| created_at + lifetime);
+        match (idle, lifetime) {
+            (Some(idle), Some(lifetime)) => Some(idle.min(lifetime)),
+            (idle, lifetime) => idle.or(lifetime),
+        }
+    }
+
+    /// Why a session past its expiry ended, as told to the user
+    pub fn expiry_reason(&self, created_at: DateTime<Utc>, now: DateTime<Utc>) -> &'static str {
+        match self.max_lifetime {
+            Some(lifetime) if created_at + lifetime <= now => "reached its maximum lifetime",
+            _ if self.idle_timeout.is_some() => "was idle too long",
+            _ => "was not used in time",
+        }
+    }
+}
+
+/// The kind of session a client asked for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    #[default]
+    Standard,
+    /// A long-lived session for a client that asked to be remembered
+    RememberMe,
+}
+
+/// ⏳ SESSION POLICIES: Which policy bounds each session
+/// 🏗️ ARCHITECTURE DECISION: Policies resolved from the config at every validation
+/// rather than copied into each session
+/// Why: Tightening a policy then applies to sessions already open, which is what an
+/// operator reacting to an incident wants
+/// Alternative: Store the policy on the session (rejected: old sessions would keep the
+/// policy they were opened under until they expire)
+/// Trade-off: Loosening a policy does not revive sessions it already ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPolicies {
+    /// Policy of standard sessions of users without one of their own
+    #[serde(default)]
+    pub default: SessionPolicy,
+    /// Policy of every remember-me session
+    #[serde(default = "default_remember_me")]
+    pub remember_me: SessionPolicy,
+    /// Policies of particular users' standard sessions, by session user ID
+    #[serde(default)]
+    pub users: HashMap<String, SessionPolicy>,
+}
+
+fn default_remember_me() -> SessionPolicy {
+    SessionPolicy {
+        idle_timeout: Some(Duration::days(
+            crate::constants::SESSION_REMEMBER_ME_IDLE_DAYS,
+        )),
+        max_lifetime: Some(Duration::days(
+            crate::constants::SESSION_REMEMBER_ME_LIFETIME_DAYS,
+        )),
+    }
+}
+
+impl Default for SessionPolicies {
+    fn default() -> Self {
+        Self {
+            default: SessionPolicy::default(),
+            remember_me: default_remember_me(),
+            users: HashMap::new(),
+        }
+    }
+}
+
+impl SessionPolicies {
+    /// The policy a session of `user_id` of this kind is held to
+    pub fn for_session(&self, user_id: &str, kind: SessionKind) -> &SessionPolicy {
+        match kind {
+            SessionKind::RememberMe => &self.remember_me,
+            SessionKind::Standard => self.users.get(user_id).unwrap_or(&self.default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_is_the_earlier_bound() {
+        let opened = Utc::now();
+        let policy = SessionPolicy {
+            idle_timeout: Some(Duration::minutes(30)),
+            max_lifetime: Some(Duration::hours(2)),
+        };
+        assert_eq!(
+            policy.deadline(opened, opened),
+            Some(opened + Duration::minutes(30))
+        );
+        let late_use = opened + Duration::minutes(110);
+        assert_eq!(
+            policy.deadline(opened, late_use),
+            Some(opened + Duration::hours(2))
+        );
+        assert_eq!(SessionPolicy::default().deadline(opened, late_use), None);
+
+        let mut policies = SessionPolicies::default();
+        policies.users.insert("ops".to_string(), policy.clone());
+        assert_eq!(policies.for_session("ops", SessionKind::Standard), &policy);
+        assert_eq!(
+            policies.for_session("dev", SessionKind::Standard),
+            &SessionPolicy::default()
+        );
+        assert!(policies
+            .for_session("ops", SessionKind::RememberMe)
+            .max_lifetime
+            .is_some());
+    }
+}
@@ -0,0 +1,173 @@
+//! Sessions in Redis, shared by every instance pointing at the same server
+//!
+//! The store talks to Redis through the crate's own minimal client, and needs only a
+//! handful of commands. Keys:
+//! - `spiral:session:<id>` holds the session's JSON and expires a grace period after the
+//!   session does
+//! - `spiral:user-sessions:<user>` is the set of a user's session IDs
+//! - `spiral:sessions` is the set of all session IDs, walked by `cleanup_expired`
+
+use super::{Session, SessionState, SessionStore};
+use crate::constants::SESSION_REDIS_EXPIRY_GRACE_SECS;
+use crate::redis::{RedisClient, Reply};
+use crate::{Result, SpiralError};
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::warn;
+use uuid::Uuid;
+
+const SESSION_KEY_PREFIX: &str = "spiral:session:";
+const USER_KEY_PREFIX: &str = "spiral:user-sessions:";
+const ALL_SESSIONS_KEY: &str = "spiral:sessions";
+/// Sessions in Redis, shared between instances
+/// Trade-off: Sets are updated by separate commands and outlive keys Redis expires, so
+/// they can hold IDs of sessions that are gone; reads and cleanup drop those
+pub struct RedisSessionStore {
+    client: RedisClient,
+}
+
+impl RedisSessionStore {
+    /// A store for the server at `url`; it connects on first use, and again after a failure
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            client: RedisClient::new(url)?,
+        })
+    }
+
+    async fn command(&self, args: &[&[u8]]) -> Result<Reply> {
+        self.client.command(args).await
+    }
+
+    /// Write the session, expiring a grace period after it does; `condition` is NX or XX.
+    /// Returns false when the condition kept it from being written
+    async fn put(&self, session: &Session, condition: &[u8]) -> Result<bool> {
+        let json = serde_json::to_vec(session)?;
+        let ttl = ((session.expires_at - Utc::now()).num_seconds().max(0)
+            + SESSION_REDIS_EXPIRY_GRACE_SECS)
+            .max(1)
+            .to_string();
+        let key = session_key(&session.id);
+        let reply = self
+            .command(&[
+                b"SET",
+                key.as_bytes(),
+                &json,
+                condition,
+                b"EX",
+                ttl.as_bytes(),
+            ])
+            .await?;
+        if reply == Reply::Bulk(None) {
+            return Ok(false);
+        }
+
+        let id = session.id.to_string();
+        let user_key = user_key(&session.user_id);
+        self.command(&[b"SADD", user_key.as_bytes(), id.as_bytes()])
+            .await?;
+        self.command(&[b"SADD", ALL_SESSIONS_KEY.as_bytes(), id.as_bytes()])
+            .await?;
+        Ok(true)
+    }
+
+    async fn remove(&self, session: &Session) -> Result<()> {
+        let id = session.id.to_string();
+        let user_key = user_key(&session.user_id);
+        self.command(&[b"DEL", session_key(&session.id).as_bytes()])
+            .await?;
+        self.command(&[b"SREM", user_key.as_bytes(), id.as_bytes()])
+            .await?;
+        self.command(&[b"SREM", ALL_SESSIONS_KEY.as_bytes(), id.as_bytes()])
+            .await?;
+        Ok(())
+    }
+
+    /// Sessions by the IDs in the set at `set_key`; IDs whose session is gone are dropped
+    /// from the set
+    async fn sessions_in(&self, set_key: &str) -> Result<Vec<Session>> {
+        let ids = self
+            .command(&[b"SMEMBERS", set_key.as_bytes()])
+            .await?
+            .into_strings()?;
+        let mut sessions = Vec::with_capacity(ids.len());
+        for id in ids {
+            let session = match Uuid::parse_str(&id) {
+                Ok(uuid) => self.get(&uuid).await?,
+                Err(_) => None,
+            };
+            match session {
+                Some(session) => sessions.push(session),
+                None => {
+                    self.command(&[b"SREM", set_key.as_bytes(), id.as_bytes()])
+                        .await?;
+                }
+            }
+        }
+        Ok(sessions)
+    }
+}
+
+fn session_key(id: &Uuid) -> String {
+    format!("{SESSION_KEY_PREFIX}{id}")
+}
+
+fn user_key(user_id: &str) -> String {
+    format!("{USER_KEY_PREFIX}{user_id}")
+}
+
+#[async_trait]
+impl SessionStore for RedisSessionStore {
+    async fn create(&self, session: Session) -> Result<()> {
+        if !self.put(&session, b"NX").await? {
+            return Err(SpiralError::Validation(
+                "Session already exists".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Session>> {
+        let key = session_key(id);
+        let Some(json) = self.command(&[b"GET", key.as_bytes()]).await?.into_bulk()? else {
+            return Ok(None);
+        };
+        match serde_json::from_slice(&json) {
+            Ok(session) => Ok(Some(session)),
+            Err(e) => {
+                warn!("Skipping unreadable session {}: {}", id, e);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn update(&self, session: Session) -> Result<()> {
+        if !self.put(&session, b"XX").await? {
+            return Err(SpiralError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let session = self
+            .get(id)
+            .await?
+            .ok_or_else(|| SpiralError::NotFound("Session not found".to_string()))?;
+        self.remove(&session).await
+    }
+
+    async fn list_by_user(&self, user_id: &str) -> Result<Vec<Session>> {
+        self.sessions_in(&user_key(user_id)).await
+    }
+
+    async fn cleanup_expired(&self) -> Result<Vec<Session>> {
+        let now = Utc::now();
+        let mut removed = Vec::new();
+        for session in self.sessions_in(ALL_SESSIONS_KEY).await? {
+            if session.expires_at < now || session.state == SessionState::Expired {
+                self.remove(&session).await?;
+                removed.push(session);
+            }
+        }
+        Ok(removed)
+    }
+}
@@ -0,0 +1,279 @@
+//! Sessions persisted to a SQLite database file
+//!
+//! Each session is kept as its JSON, next to the columns the store filters on, so a
+//! field added to Session needs no schema change.
+
+use super::{Session, SessionState, SessionStore};
+use crate::{Result, SpiralError};
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sessions (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        state TEXT NOT NULL,
+        expires_at_us INTEGER NOT NULL,
+        session TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS sessions_user ON sessions (user_id);
+";
+
+/// Sessions in a SQLite file, surviving restarts
+/// 🏗️ ARCHITECTURE DECISION: One connection behind a std Mutex, used from spawn_blocking
+/// Why: Same shape as the agent memory database; session reads are one row per message
+/// Alternative: A connection pool (rejected: one writer per file either way)
+#[derive(Clone)]
+pub struct SqliteSessionStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteSessionStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let connection = Connection::open(path).map_err(|e| {
+            SpiralError::SystemError(format!(
+                "Failed to open session store at {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::with_connection(connection)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        let connection = Connection::open_in_memory().map_err(storage_error)?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    async fn with_db<T, F>(&self, operation: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = connection
+                .lock()
+                .map_err(|_| SpiralError::SystemError("Session store lock poisoned".to_string()))?;
+            operation(&connection).map_err(storage_error)
+        })
+        .await
+        .map_err(|e| SpiralError::SystemError(format!("Session store task failed: {e}")))?
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> SpiralError {
+    SpiralError::SystemError(format!("Session storage error: {e}"))
+}
+
+/// The row values of a session: id, user, state, expiry and the session's JSON
+fn columns(session: &Session) -> Result<(String, String, String, i64, String)> {
+    Ok((
+        session.id.to_string(),
+        session.user_id.clone(),
+        format!("{:?}", session.state),
+        session.expires_at.timestamp_micros(),
+        serde_json::to_string(session)?,
+    ))
+}
+
+/// Rows written by a newer build that no longer parse are skipped rather than failing
+fn session_from_row(id: &str, json: &str) -> Option<Session> {
+    let session = serde_json::from_str(json);
+    if let Err(e) = &session {
+        warn!("Skipping unreadable session {}: {}", id, e);
+    }
+    session.ok()
+}
+
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    async fn create(&self, session: Session) -> Result<()> {
+        let (id, user_id, state, expires_at_us, json) = columns(&session)?;
+        let inserted = self
+            .with_db(move |db| {
+                db.execute(
+                    "INSERT OR IGNORE INTO sessions (id, user_id, state, expires_at_us, session)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![id, user_id, state, expires_at_us, json],
+                )
+            })
+            .await?;
+
+        if inserted == 0 {
+            return Err(SpiralError::Validation(
+                "Session already exists".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Session>> {
+        let id = id.to_string();
+        let json = self
+            .with_db({
+                let id = id.clone();
+                move |db| {
+                    let mut statement = db.prepare("SELECT session FROM sessions WHERE id = ?1")?;
+                    let mut rows = statement.query_map(params![id], |row| row.get(0))?;
+                    rows.next().transpose()
+                }
+            })
+            .await?;
+        Ok(json.and_then(|json: String| session_from_row(&id, &json)))
+    }
+
+    async fn update(&self, session: Session) -> Result<()> {
+        let (id, user_id, state, expires_at_us, json) = columns(&session)?;
+        let updated = self
+            .with_db(move |db| {
+                db.execute(
+                    "UPDATE sessions
+                     SET user_id = ?2, state = ?3, expires_at_us = ?4, session = ?5
+                     WHERE id = ?1",
+                    params![id, user_id, state, expires_at_us, json],
+                )
+            })
+            .await?;
+
+        if updated == 0 {
+            return Err(SpiralError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let id = id.to_string();
+        let deleted = self
+            .with_db(move |db| db.execute("DELETE FROM sessions WHERE id = ?1", params![id]))
+            .await?;
+
+        if deleted == 0 {
+            return Err(SpiralError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn list_by_user(&self, user_id: &str) -> Result<Vec<Session>> {
+        let user_id = user_id.to_string();
+        let rows = self
+            .with_db(move |db| {
+                let mut statement =
+                    db.prepare("SELECT id, session FROM sessions WHERE user_id = ?1")?;
+                let rows = statement
+                    .query_map(params![user_id], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|(id, json)| session_from_row(id, json))
+            .collect())
+    }
+
+    async fn cleanup_expired(&self) -> Result<Vec<Session>> {
+        let now_us = Utc::now().timestamp_micros();
+        let expired = format!("{:?}", SessionState::Expired);
+        let rows = self
+            .with_db(move |db| {
+                let rows = db
+                    .prepare(
+                        "SELECT id, session FROM sessions WHERE expires_at_us < ?1 OR state = ?2",
+                    )?
+                    .query_map(params![now_us, expired], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                db.execute(
+                    "DELETE FROM sessions WHERE expires_at_us < ?1 OR state = ?2",
+                    params![now_us, expired],
+                )?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|(id, json)| session_from_row(id, json))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{SessionConfig, SessionManager};
+
+    #[tokio::test]
+    async fn test_sessions_survive_reopening_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.db");
+        let config = SessionConfig {
+            max_concurrent: 1,
+            ..SessionConfig::default()
+        };
+
+        let session = {
+            let manager =
+                SessionManager::new(SqliteSessionStore::open(&path).unwrap(), config.clone());
+            let session = manager.create_session("user1".to_string()).await.unwrap();
+            manager
+                .set_metadata(&session.id, "agent", Some("developer".to_string()))
+                .await
+                .unwrap();
+            session
+        };
+
+        let manager = SessionManager::new(SqliteSessionStore::open(&path).unwrap(), config);
+        let restored = manager.active_session("user1").await.unwrap().unwrap();
+        assert_eq!(restored.id, session.id);
+        assert_eq!(restored.metadata.get("agent").unwrap(), "developer");
+        // Still counts against the limit after the restart
+        assert!(manager.create_session("user1".to_string()).await.is_err());
+        assert!(manager.active_session("user2").await.unwrap().is_none());
+
+        manager.terminate_session(&session.id).await.unwrap();
+        assert!(manager.active_session("user1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_store_matches_in_memory_semantics() {
+        let store = SqliteSessionStore::open_in_memory().unwrap();
+        let manager = SessionManager::new(store.clone(), SessionConfig::default());
+        let session = manager.create_session("user1".to_string()).await.unwrap();
+
+        assert!(matches!(
+            store.create(session.clone()).await,
+            Err(SpiralError::Validation(_))
+        ));
+        let mut expired = session.clone();
+        expired.state = SessionState::Expired;
+        store.update(expired).await.unwrap();
+        assert_eq!(store.cleanup_expired().await.unwrap().len(), 1);
+        assert!(store.get(&session.id).await.unwrap().is_none());
+        assert!(matches!(
+            store.delete(&session.id).await,
+            Err(SpiralError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.update(session).await,
+            Err(SpiralError::NotFound(_))
+        ));
+    }
+}
@@ -95,8 +95,11 @@ mod orchestrator_lifecycle {
             match orchestrator.submit_task(task).await {
                 Ok(_) => continue,
                 Err(SpiralError::Agent { message }) if message.contains("queue is full") => {
-                    // Expected error when queue is full
-                    assert!(orchestrator.get_queue_length().await >= 1000);
+                    // Expected once Low priority tasks reach the unreserved part of the queue
+                    let unreserved = crate::constants::MAX_QUEUE_SIZE
+                        * (100 - crate::constants::QUEUE_RESERVED_PERCENT as usize)
+                        / 100;
+                    assert!(orchestrator.get_queue_length().await >= unreserved);
                     break;
                 }
                 Err(e) => panic!("Unexpected error: {e}"),
@@ -110,6 +113,974 @@ mod orchestrator_lifecycle {
         orchestrator.shutdown().await;
     }
 
+    /// Happy path: Paused orchestrator holds queued tasks until resumed
+    #[tokio::test]
+    async fn test_orchestrator_pause_resume_drain_lifecycle() {
+        use crate::agents::orchestrator::DispatchState;
+
+        let config = Config::test_config();
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(config)
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+
+        // Phase 1: Paused dispatch keeps submitted work queued
+        orchestrator.pause().await;
+        assert_eq!(
+            orchestrator.get_dispatch_state().await,
+            DispatchState::Paused
+        );
+
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Held while paused".to_string(),
+            Priority::Medium,
+        );
+        let task_id = orchestrator.submit_task(task).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(orchestrator.get_queue_length().await, 1);
+
+        // Phase 2: Draining with nothing in flight completes immediately and stays paused
+        orchestrator
+            .drain(Duration::from_secs(1))
+            .await
+            .expect("Drain should finish with no in-flight tasks");
+        assert_eq!(
+            orchestrator.get_dispatch_state().await,
+            DispatchState::Paused
+        );
+        assert_eq!(orchestrator.get_queue_length().await, 1);
+
+        // Phase 3: Resume dispatches the held task
+        orchestrator.resume().await;
+        assert_eq!(
+            orchestrator.get_dispatch_state().await,
+            DispatchState::Running
+        );
+
+        let result = timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(task) = orchestrator.get_task_status(&task_id).await {
+                    if task.status != TaskStatus::Pending {
+                        break;
+                    }
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+        assert!(result.is_ok(), "Task was not dispatched after resume");
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Happy path: Every subscriber receives the final result of a task
+    #[tokio::test]
+    async fn test_orchestrator_result_subscription_lifecycle() {
+        use crate::models::TaskExecutionResult;
+
+        let config = Config::test_config();
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(config)
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+
+        // Subscribers may attach before the orchestrator is running
+        let mut first = orchestrator.subscribe_results();
+        let mut second = orchestrator.subscribe_results();
+
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Broadcast my result".to_string(),
+            Priority::Medium,
+        );
+        let task_id = orchestrator.submit_task(task).await.unwrap();
+
+        // The mock Claude binary cannot be spawned, so the result is a failure
+        for receiver in [&mut first, &mut second] {
+            let result = timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .expect("Result was not broadcast in time")
+                .expect("Result channel closed");
+            assert_eq!(result.task_id, task_id);
+            assert!(matches!(result.result, TaskExecutionResult::Failure { .. }));
+        }
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Error path: Batches are admitted all or nothing against queue capacity
+    #[tokio::test]
+    async fn test_orchestrator_batch_lifecycle() {
+        use crate::constants::{MAX_BATCH_SIZE, MAX_QUEUE_SIZE};
+
+        let config = Config::test_config();
+        let orchestrator = AgentOrchestrator::new(config)
+            .await
+            .expect("Failed to create orchestrator");
+        let make_tasks = |count: usize| -> Vec<Task> {
+            (0..count)
+                .map(|i| {
+                    Task::new(
+                        AgentType::SoftwareDeveloper,
+                        format!("Batch task {i}"),
+                        Priority::Low,
+                    )
+                })
+                .collect()
+        };
+
+        // Phase 1: A batch is queued as a unit and reports aggregate status
+        let batch = orchestrator.submit_batch(make_tasks(3)).await.unwrap();
+        assert_eq!(batch.task_ids.len(), 3);
+        assert_eq!(orchestrator.get_queue_length().await, 3);
+
+        let status = orchestrator.get_batch_status(&batch.id).await.unwrap();
+        assert_eq!(status.total, 3);
+        assert_eq!(status.pending, 3);
+        assert!(!status.is_finished());
+
+        let member = orchestrator
+            .get_task_status(&batch.task_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(member.context.get("batch_id"), Some(&batch.id));
+
+        // Phase 2: Fill the unreserved part of the queue so the next batch no longer fits
+        let unreserved =
+            MAX_QUEUE_SIZE * (100 - crate::constants::QUEUE_RESERVED_PERCENT as usize) / 100;
+        while orchestrator.get_queue_length().await < unreserved - 2 {
+            orchestrator
+                .submit_task(make_tasks(1).remove(0))
+                .await
+                .unwrap();
+        }
+        let result = orchestrator.submit_batch(make_tasks(3)).await;
+        assert!(matches!(result, Err(SpiralError::QueueFull)));
+        assert_eq!(orchestrator.get_queue_length().await, unreserved - 2);
+
+        // Phase 3: Empty and oversized batches are rejected outright
+        assert!(matches!(
+            orchestrator.submit_batch(Vec::new()).await,
+            Err(SpiralError::Validation(_))
+        ));
+        assert!(matches!(
+            orchestrator
+                .submit_batch(make_tasks(MAX_BATCH_SIZE + 1))
+                .await,
+            Err(SpiralError::Validation(_))
+        ));
+        assert!(orchestrator.get_batch_status("missing").await.is_none());
+    }
+
+    /// Error path: A failing task leaves a complete event trail
+    #[tokio::test]
+    async fn test_orchestrator_event_log_lifecycle() {
+        use crate::agents::orchestrator::TaskEventKind;
+
+        let config = Config::test_config();
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(config)
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+        let mut results = orchestrator.subscribe_results();
+
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Trace my lifecycle".to_string(),
+            Priority::Medium,
+        );
+        let task_id = orchestrator.submit_task(task).await.unwrap();
+
+        // The mock Claude binary cannot be spawned, so the task fails
+        timeout(Duration::from_secs(5), results.recv())
+            .await
+            .expect("Task did not finish in time")
+            .expect("Result channel closed");
+
+        let kinds: Vec<TaskEventKind> = orchestrator
+            .get_task_events(&task_id)
+            .await
+            .expect("Task should have events")
+            .into_iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TaskEventKind::Submitted,
+                TaskEventKind::Dequeued,
+                TaskEventKind::Started,
+                TaskEventKind::Failed,
+            ]
+        );
+        assert!(orchestrator.get_task_events("unknown").await.is_none());
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Routing: A task naming the wrong agent is routed by its content
+    #[tokio::test]
+    async fn test_orchestrator_capability_routing_by_content() {
+        let config = Config::test_config();
+        let orchestrator = AgentOrchestrator::new(config)
+            .await
+            .expect("Failed to create orchestrator");
+
+        let mut task = Task::new(
+            AgentType::ProjectManager,
+            "Implement a rust parser".to_string(),
+            Priority::Medium,
+        );
+        assert_eq!(
+            orchestrator.route_task_by_content(&mut task).await.unwrap(),
+            AgentType::SoftwareDeveloper
+        );
+        assert_eq!(task.agent_type, AgentType::SoftwareDeveloper);
+        assert!(orchestrator.submit_task(task).await.is_ok());
+    }
+
+    /// Error path: A workflow stops at its first failed step
+    #[tokio::test]
+    async fn test_orchestrator_workflow_lifecycle() {
+        use crate::agents::orchestrator::{WorkflowRunStatus, WorkflowStepStatus};
+
+        let mut config = Config::test_config();
+        config.orchestrator.workflows.insert(
+            "double".to_string(),
+            vec![AgentType::SoftwareDeveloper, AgentType::SoftwareDeveloper],
+        );
+        config.orchestrator.workflows.insert(
+            "planned".to_string(),
+            vec![AgentType::ProjectManager, AgentType::SoftwareDeveloper],
+        );
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(config)
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+
+        let request = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Build and review".to_string(),
+            Priority::Medium,
+        );
+
+        let wait_for_finish = |run_id: String| {
+            let orchestrator = orchestrator.clone();
+            async move {
+                timeout(Duration::from_secs(5), async {
+                    loop {
+                        let run = orchestrator.get_workflow_run(&run_id).await.unwrap();
+                        if run.is_finished() {
+                            return run;
+                        }
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                })
+                .await
+                .expect("Workflow did not finish in time")
+            }
+        };
+
+        // Phase 1: Unknown workflows are rejected up front
+        assert!(matches!(
+            orchestrator.run_workflow("missing", request.clone()).await,
+            Err(SpiralError::NotFound(_))
+        ));
+
+        // Phase 2: The mock Claude binary fails the first step, so the second never runs
+        let run = orchestrator
+            .run_workflow("double", request.clone())
+            .await
+            .unwrap();
+        assert_eq!(run.status, WorkflowRunStatus::Running);
+
+        let finished = wait_for_finish(run.id).await;
+        assert_eq!(finished.status, WorkflowRunStatus::Failed);
+        assert_eq!(finished.steps[0].status, WorkflowStepStatus::Failed);
+        assert!(finished.steps[0].task_id.is_some());
+        assert_eq!(finished.steps[1].status, WorkflowStepStatus::Pending);
+        assert!(finished.error.is_some());
+
+        // Phase 3: The project manager plans without Claude, then the developer step fails
+        let run = orchestrator.run_workflow("planned", request).await.unwrap();
+        let finished = wait_for_finish(run.id).await;
+        assert_eq!(finished.status, WorkflowRunStatus::Failed);
+        assert_eq!(finished.steps[0].status, WorkflowStepStatus::Completed);
+        assert_eq!(finished.steps[1].status, WorkflowStepStatus::Failed);
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Error path: Delegation chains are capped by fan-out and depth limits
+    #[tokio::test]
+    async fn test_orchestrator_delegation_limits() {
+        use crate::agents::orchestrator::delegation::{
+            DELEGATED_BY_CONTEXT_KEY, DELEGATION_DEPTH_CONTEXT_KEY,
+        };
+        use crate::agents::orchestrator::{OrchestratorHandle, TaskEventKind};
+        use crate::constants::{MAX_DELEGATIONS_PER_TASK, MAX_DELEGATION_DEPTH};
+
+        // Not running, so delegated tasks stay queued for inspection
+        let orchestrator = AgentOrchestrator::new(Config::test_config())
+            .await
+            .expect("Failed to create orchestrator");
+        let follow_up = || {
+            Task::new(
+                AgentType::QualityAssurance,
+                "Write tests for the parser module".to_string(),
+                Priority::Medium,
+            )
+        };
+
+        // Phase 1: A task may delegate up to the fan-out limit
+        let parent = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Build a parser".to_string(),
+            Priority::Medium,
+        );
+        let handle = OrchestratorHandle::for_task(orchestrator.clone(), &parent).await;
+        let mut children = Vec::new();
+        for _ in 0..MAX_DELEGATIONS_PER_TASK {
+            children.push(
+                handle
+                    .delegate(follow_up())
+                    .await
+                    .expect("Delegation failed"),
+            );
+        }
+        assert!(!handle.can_delegate());
+        assert!(matches!(
+            handle.delegate(follow_up()).await,
+            Err(SpiralError::Validation(_))
+        ));
+
+        // Phase 2: Children record their lineage and the parent records each delegation
+        let child = orchestrator
+            .get_task_status(&children[0])
+            .await
+            .expect("Delegated task should be queued");
+        assert_eq!(child.agent_type, AgentType::QualityAssurance);
+        assert_eq!(
+            child.context.get(DELEGATED_BY_CONTEXT_KEY),
+            Some(&parent.id)
+        );
+        assert_eq!(
+            child
+                .context
+                .get(DELEGATION_DEPTH_CONTEXT_KEY)
+                .map(String::as_str),
+            Some("1")
+        );
+        let delegated = orchestrator
+            .get_task_events(&parent.id)
+            .await
+            .expect("Parent should have events")
+            .iter()
+            .filter(|event| event.kind == TaskEventKind::Delegated)
+            .count();
+        assert_eq!(delegated, MAX_DELEGATIONS_PER_TASK);
+
+        // Phase 3: A retry gets a fresh handle but not a fresh budget
+        let retry_handle = OrchestratorHandle::for_task(orchestrator.clone(), &parent).await;
+        assert!(!retry_handle.can_delegate());
+
+        // Phase 4: Tasks at the depth limit cannot delegate at all
+        let deep = follow_up().with_context(
+            DELEGATION_DEPTH_CONTEXT_KEY.to_string(),
+            MAX_DELEGATION_DEPTH.to_string(),
+        );
+        let deep_handle = OrchestratorHandle::for_task(orchestrator.clone(), &deep).await;
+        assert!(matches!(
+            deep_handle.delegate(follow_up()).await,
+            Err(SpiralError::Validation(_))
+        ));
+    }
+
+    /// Happy path: Successful tasks are remembered alongside taught conventions
+    #[tokio::test]
+    async fn test_orchestrator_agent_memory_lifecycle() {
+        use crate::agents::memory::{MemoryEntry, MemoryKind, MemoryScope};
+        use crate::models::TaskExecutionResult;
+
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(Config::test_config())
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+        let mut results = orchestrator.subscribe_results();
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+
+        // Phase 1: Teach the project manager a convention for one user
+        let user = MemoryScope::User("42".to_string());
+        orchestrator
+            .remember(MemoryEntry::new(
+                AgentType::ProjectManager,
+                user.clone(),
+                MemoryKind::Convention,
+                "Report in bullet points".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        // Phase 2: The heuristic fallback completes without Claude
+        let task = Task::new(
+            AgentType::ProjectManager,
+            "Summarize the status of the release".to_string(),
+            Priority::Medium,
+        )
+        .with_context("user_id".to_string(), "42".to_string());
+        orchestrator.submit_task(task).await.unwrap();
+        let result = timeout(Duration::from_secs(10), results.recv())
+            .await
+            .expect("Task did not finish in time")
+            .expect("Result channel closed");
+        assert!(matches!(result.result, TaskExecutionResult::Success { .. }));
+
+        // Phase 3: The task summary joins the convention in the user's scope only
+        let memories = orchestrator
+            .recall_memories(&AgentType::ProjectManager, &[user], 10)
+            .await
+            .unwrap();
+        let kinds: Vec<MemoryKind> = memories.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, vec![MemoryKind::TaskSummary, MemoryKind::Convention]);
+        assert!(memories[0]
+            .content
+            .contains("Summarize the status of the release"));
+
+        let other_user = MemoryScope::User("7".to_string());
+        assert!(orchestrator
+            .recall_memories(&AgentType::ProjectManager, &[other_user], 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Happy path: Agent progress is tracked while running and marked complete at the end
+    #[tokio::test]
+    async fn test_orchestrator_progress_lifecycle() {
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(Config::test_config())
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+        let mut results = orchestrator.subscribe_results();
+
+        // Phase 1: Nothing is reported before the task runs
+        let task = Task::new(
+            AgentType::ProjectManager,
+            "Prioritize the open bugs".to_string(),
+            Priority::Medium,
+        );
+        let task_id = orchestrator.submit_task(task).await.unwrap();
+        assert!(orchestrator.get_task_progress(&task_id).await.is_none());
+
+        // Phase 2: Completion overrides the agent's last reported phase
+        let orchestrator_clone = orchestrator.clone();
+        tokio::spawn(async move { orchestrator_clone.run().await });
+        timeout(Duration::from_secs(10), results.recv())
+            .await
+            .expect("Task did not finish in time")
+            .expect("Result channel closed");
+
+        let progress = orchestrator
+            .get_task_progress(&task_id)
+            .await
+            .expect("Completed task should have progress");
+        assert_eq!(progress.percent, 100);
+        assert_eq!(progress.phase, "completed");
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Happy path: The queue lists tasks in dispatch order and operators can reorder them
+    #[tokio::test]
+    async fn test_orchestrator_queue_reordering() {
+        use crate::agents::orchestrator::{QueueMove, TaskEventKind};
+
+        // Not running, so every task stays queued
+        let orchestrator = AgentOrchestrator::new(Config::test_config())
+            .await
+            .expect("Failed to create orchestrator");
+        let submit = |content: &str, priority| {
+            let orchestrator = orchestrator.clone();
+            let task = Task::new(AgentType::SoftwareDeveloper, content.to_string(), priority);
+            async move { orchestrator.submit_task(task).await.unwrap() }
+        };
+        let first = submit("first", Priority::Medium).await;
+        let second = submit("second", Priority::Medium).await;
+        let urgent = submit("urgent", Priority::High).await;
+
+        // Phase 1: Higher priority first, then submission order
+        let order = |queue: Vec<crate::agents::orchestrator::QueuedTask>| {
+            queue
+                .into_iter()
+                .map(|entry| entry.task_id)
+                .collect::<Vec<_>>()
+        };
+        let queue = orchestrator.get_queue().await;
+        assert_eq!(queue[0].position, 1);
+        assert_eq!(
+            order(queue),
+            vec![urgent.clone(), first.clone(), second.clone()]
+        );
+
+        // Phase 2: Promoting within a priority band swaps neighbours
+        let moved = orchestrator
+            .move_queued_task(&second, QueueMove::Promote)
+            .await
+            .unwrap();
+        assert_eq!(moved.position, 2);
+        assert_eq!(moved.priority, Priority::Medium);
+
+        // Phase 3: Promoting past a higher priority task adopts its priority
+        let moved = orchestrator
+            .move_queued_task(&second, QueueMove::Promote)
+            .await
+            .unwrap();
+        assert_eq!(moved.position, 1);
+        assert_eq!(moved.priority, Priority::High);
+        assert_eq!(
+            order(orchestrator.get_queue().await),
+            vec![second.clone(), urgent.clone(), first.clone()]
+        );
+        let stored = orchestrator.get_task_status(&second).await.unwrap();
+        assert_eq!(stored.priority, Priority::High);
+        let reorders = orchestrator
+            .get_task_events(&second)
+            .await
+            .unwrap()
+            .iter()
+            .filter(|event| event.kind == TaskEventKind::Reordered)
+            .count();
+        assert_eq!(reorders, 2);
+
+        // Phase 4: New submissions respect the manual order
+        let later = submit("later", Priority::High).await;
+        assert_eq!(
+            order(orchestrator.get_queue().await),
+            vec![second, urgent, later, first]
+        );
+
+        // Phase 5: Unknown tasks cannot be moved
+        assert!(matches!(
+            orchestrator
+                .move_queued_task("missing", QueueMove::Demote)
+                .await,
+            Err(SpiralError::NotFound(_))
+        ));
+    }
+
+    /// Pressure: Concurrent submissions to a full queue neither overfill it nor share a victim
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_orchestrator_queue_cap_holds_under_concurrent_submission() {
+        use crate::agents::orchestrator::Submitter;
+        use crate::constants::{MAX_QUEUE_SIZE, QUEUE_RESERVED_PERCENT};
+
+        let mut config = Config::test_config();
+        config.orchestrator.queue_evict_low_priority = true;
+        // Not running, so every task stays queued
+        let orchestrator = AgentOrchestrator::new(config)
+            .await
+            .expect("Failed to create orchestrator");
+        let submit_concurrently = |count: usize, priority: Priority| {
+            let handles: Vec<_> = (0..count)
+                .map(|i| {
+                    let orchestrator = orchestrator.clone();
+                    let task = Task::new(
+                        AgentType::SoftwareDeveloper,
+                        format!("Task {i}"),
+                        priority.clone(),
+                    );
+                    tokio::spawn(async move { orchestrator.submit_task(task).await })
+                })
+                .collect();
+            async move {
+                let mut admitted = Vec::new();
+                for handle in handles {
+                    if let Ok(task_id) = handle.await.unwrap() {
+                        admitted.push(task_id);
+                    }
+                }
+                admitted
+            }
+        };
+
+        // Phase 1: Low tasks racing for the last unreserved slots get only those slots
+        let unreserved = MAX_QUEUE_SIZE * (100 - QUEUE_RESERVED_PERCENT as usize) / 100;
+        for i in 0..unreserved - 5 {
+            let task = Task::new(
+                AgentType::SoftwareDeveloper,
+                format!("Fill {i}"),
+                Priority::Low,
+            );
+            orchestrator.submit_task(task).await.unwrap();
+        }
+        assert_eq!(submit_concurrently(50, Priority::Low).await.len(), 5);
+        assert_eq!(orchestrator.get_queue_length().await, unreserved);
+
+        // Phase 2: Fill the reserved slots too
+        while orchestrator.get_queue_length().await < MAX_QUEUE_SIZE {
+            let task = Task::new(
+                AgentType::SoftwareDeveloper,
+                "Admin".to_string(),
+                Priority::Low,
+            );
+            orchestrator
+                .submit_task_as(task, Submitter::Privileged)
+                .await
+                .unwrap();
+        }
+
+        // Phase 3: Urgent tasks racing into the full queue each evict a different Low task
+        let admitted = submit_concurrently(50, Priority::Critical).await;
+        assert_eq!(admitted.len(), 50);
+        assert_eq!(orchestrator.get_queue_length().await, MAX_QUEUE_SIZE);
+        let queue = orchestrator.get_queue().await;
+        let urgent = queue
+            .iter()
+            .filter(|entry| entry.priority == Priority::Critical)
+            .count();
+        assert_eq!(urgent, 50);
+        for task_id in &admitted {
+            assert!(queue.iter().any(|entry| &entry.task_id == task_id));
+        }
+    }
+
+    /// Lifecycle: Unfinished tasks are checkpointed on shutdown and resumed on the next start
+    #[tokio::test]
+    async fn test_orchestrator_checkpoint_and_resume() {
+        use crate::agents::orchestrator::{AgentCheckpoint, OrchestratorHandle};
+
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let checkpoint_path = dir.path().join("checkpoints.json");
+        let mut config = Config::test_config();
+        config.orchestrator.checkpoint_store_path =
+            Some(checkpoint_path.to_string_lossy().to_string());
+
+        // Phase 1: Queued tasks and agent state are saved at shutdown
+        let orchestrator = AgentOrchestrator::new(config.clone())
+            .await
+            .expect("Failed to create orchestrator");
+        let task = Task::new(
+            AgentType::SoftwareDeveloper,
+            "Build a parser".to_string(),
+            Priority::High,
+        );
+        let task_id = orchestrator.submit_task(task.clone()).await.unwrap();
+        let other_id = orchestrator
+            .submit_task(Task::new(
+                AgentType::SoftwareDeveloper,
+                "Write docs".to_string(),
+                Priority::Low,
+            ))
+            .await
+            .unwrap();
+        OrchestratorHandle::for_task(orchestrator.clone(), &task)
+            .await
+            .checkpoint(AgentCheckpoint {
+                workspace_path: None,
+                session_id: Some("session-1".to_string()),
+                notes: vec!["generating rust code".to_string()],
+            })
+            .await;
+
+        let saved = orchestrator
+            .checkpoint_and_stop(Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert_eq!(saved, 2);
+        assert!(checkpoint_path.exists());
+
+        // Phase 2: A new orchestrator re-queues them under the same IDs with the saved state
+        let restarted = AgentOrchestrator::new(config.clone())
+            .await
+            .expect("Failed to create orchestrator");
+        let queued: Vec<String> = restarted
+            .get_queue()
+            .await
+            .into_iter()
+            .map(|entry| entry.task_id)
+            .collect();
+        assert_eq!(queued, vec![task_id.clone(), other_id]);
+
+        let resumed = restarted.get_task_status(&task_id).await.unwrap();
+        let state = AgentCheckpoint::from_context(&resumed).expect("Task should be resumed");
+        assert_eq!(state.session_id.as_deref(), Some("session-1"));
+        assert_eq!(state.notes, vec!["generating rust code".to_string()]);
+
+        // Phase 3: Checkpoints are resumed once
+        assert!(!checkpoint_path.exists());
+        let again = AgentOrchestrator::new(config)
+            .await
+            .expect("Failed to create orchestrator");
+        assert!(again.get_queue().await.is_empty());
+    }
+
+    /// Happy path: An agent's history lists its tasks with outcomes and durations
+    #[tokio::test]
+    async fn test_orchestrator_agent_task_history() {
+        let orchestrator = Arc::new(
+            AgentOrchestrator::new(Config::test_config())
+                .await
+                .expect("Failed to create orchestrator"),
+        );
+        let mut results = orchestrator.subscribe_results();
+        let planned = orchestrator
+            .submit_task(Task::new(
+                AgentType::ProjectManager,
+                "Plan the release".to_string(),
+                Priority::High,
+            ))
+            .await
+            .unwrap();
+
+        // Phase 1: Run the task to completion
+        let runner = orchestrator.clone();
+        tokio::spawn(async move { runner.run().await });
+        timeout(Duration::from_secs(30), async {
+            while results.recv().await.unwrap().task_id != planned {}
+        })
+        .await
+        .expect("Task should complete");
+
+        // Phase 2: The finished task carries its outcome and timing
+        let history = orchestrator
+            .get_agent_task_history(&AgentType::ProjectManager, None, 10)
+            .await;
+        assert_eq!(history.len(), 1);
+        let record = &history[0];
+        assert_eq!(record.task_id, planned);
+        assert_eq!(record.status, TaskStatus::Completed);
+        assert!(record.started_at.is_some() && record.finished_at.is_some());
+        assert!(record.duration_ms.is_some());
+        assert!(record.error.is_none());
+
+        // Phase 3: Filters and limits, newest first
+        let later = orchestrator
+            .submit_task(Task::new(
+                AgentType::ProjectManager,
+                "Plan the next release".to_string(),
+                Priority::Low,
+            ))
+            .await
+            .unwrap();
+        let newest = orchestrator
+            .get_agent_task_history(&AgentType::ProjectManager, None, 1)
+            .await;
+        assert_eq!(newest[0].task_id, later);
+        assert!(orchestrator
+            .get_agent_task_history(&AgentType::ProjectManager, Some(&TaskStatus::Failed), 10)
+            .await
+            .is_empty());
+        assert!(orchestrator
+            .get_agent_task_history(&AgentType::QualityAssurance, None, 10)
+            .await
+            .is_empty());
+
+        // Phase 4: History of one requester, from the task context
+        let requested = orchestrator
+            .submit_task(
+                Task::new(
+                    AgentType::QualityAssurance,
+                    "Review the release notes".to_string(),
+                    Priority::Low,
+                )
+                .with_context("discord_author_id".to_string(), "7".to_string()),
+            )
+            .await
+            .unwrap();
+        let mine = orchestrator
+            .get_task_history_by_context("discord_author_id", "7", 10)
+            .await;
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].1.task_id, requested);
+        assert!(orchestrator
+            .get_task_history_by_context("discord_author_id", "8", 10)
+            .await
+            .is_empty());
+
+        orchestrator.shutdown().await;
+    }
+
+    /// Lifecycle: An ended task can be submitted again as a new task
+    #[tokio::test]
+    async fn test_orchestrator_resubmits_ended_task() {
+        let orchestrator = AgentOrchestrator::new(Config::test_config())
+            .await
+            .expect("Failed to create orchestrator");
+        let original = orchestrator
+            .submit_task(
+                Task::new(
+                    AgentType::SoftwareDeveloper,
+                    "Fix the flaky test".to_string(),
+                    Priority::High,
+                )
+                .with_context("discord_author_id".to_string(), "7".to_string()),
+            )
+            .await
+            .unwrap();
+
+        // Phase 1: A queued task has not ended, so there is nothing to retry
+        assert!(orchestrator.resubmit_task(&original).await.is_err());
+
+        // Phase 2: Once cancelled it is resubmitted under a new ID with the same request
+        orchestrator.cancel_task(&original).await.unwrap();
+        let retry = orchestrator.resubmit_task(&original).await.unwrap();
+        assert_ne!(retry, original);
+        let task = orchestrator.get_task_status(&retry).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert_eq!(task.content, "Fix the flaky test");
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.context["retry_of"], original);
+        assert_eq!(task.context["discord_author_id"], "7");
+        assert_eq!(
+            orchestrator
+                .get_task_status(&original)
+                .await
+                .unwrap()
+                .status,
+            TaskStatus::Cancelled
+        );
+    }
+
+    /// Lifecycle: Terminating a session cancels the tasks still waiting in it
+    #[tokio::test]
+    async fn test_terminated_session_cancels_its_tasks() {
+        use crate::session::{SessionConfig, SessionManager};
+
+        let orchestrator = AgentOrchestrator::new(Config::test_config())
+            .await
+            .expect("Failed to create orchestrator");
+        let sessions = Arc::new(SessionManager::from_config_or_memory(
+            SessionConfig::default(),
+            "test sessions",
+        ));
+        orchestrator.register_session_manager(sessions.clone());
+
+        let session = sessions.create_session("user1".to_string()).await.unwrap();
+        let task = |content: &str| {
+            Task::new(
+                AgentType::SoftwareDeveloper,
+                content.to_string(),
+                Priority::Medium,
+            )
+        };
+        let in_session = orchestrator
+            .submit_task(session.apply_to_task(task("Add a health check")))
+            .await
+            .unwrap();
+        let outside = orchestrator
+            .submit_task(task("Fix the flaky test"))
+            .await
+            .unwrap();
+
+        sessions.terminate_session(&session.id).await.unwrap();
+        let status = |id: String| {
+            let orchestrator = orchestrator.clone();
+            async move { orchestrator.get_task_status(&id).await.unwrap().status }
+        };
+        timeout(Duration::from_secs(5), async {
+            while status(in_session.clone()).await != TaskStatus::Cancelled {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Session task was not cancelled");
+        assert_eq!(status(outside).await, TaskStatus::Pending);
+        orchestrator.shutdown().await;
+    }
+
+    /// Lifecycle: A batch counts every task against its session's budget, and a batch that
+    /// is refused gives the budget back
+    #[tokio::test]
+    async fn test_batch_is_charged_to_its_session() {
+        use crate::constants::{MAX_QUEUE_SIZE, QUEUE_RESERVED_PERCENT};
+        use crate::session::{SessionBudget, SessionConfig, SessionManager};
+
+        let orchestrator = AgentOrchestrator::new(Config::test_config())
+            .await
+            .expect("Failed to create orchestrator");
+        let config = SessionConfig {
+            budget: SessionBudget {
+                max_tasks: Some(3),
+                ..SessionBudget::default()
+            },
+            ..SessionConfig::default()
+        };
+        let sessions = Arc::new(SessionManager::from_config_or_memory(
+            config,
+            "test sessions",
+        ));
+        orchestrator.register_session_manager(sessions.clone());
+
+        let session = sessions.create_session("user1".to_string()).await.unwrap();
+        let batch = |count: usize| {
+            (0..count)
+                .map(|i| {
+                    session.apply_to_task(Task::new(
+                        AgentType::SoftwareDeveloper,
+                        format!("Batch task {i}"),
+                        Priority::Low,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+        let used = || async {
+            sessions
+                .validate_session(&session.id)
+                .await
+                .unwrap()
+                .usage
+                .tasks
+        };
+
+        orchestrator.submit_batch(batch(2)).await.unwrap();
+        assert_eq!(used().await, 2);
+
+        // Over the budget: refused whole, with nothing charged
+        assert!(matches!(
+            orchestrator.submit_batch(batch(2)).await,
+            Err(SpiralError::RateLimit { .. })
+        ));
+        assert_eq!(used().await, 2);
+
+        // Refused by a full queue: the charge is refunded
+        let unreserved = MAX_QUEUE_SIZE * (100 - QUEUE_RESERVED_PERCENT as usize) / 100;
+        for i in 2..unreserved {
+            let task = Task::new(
+                AgentType::SoftwareDeveloper,
+                format!("Fill {i}"),
+                Priority::Low,
+            );
+            orchestrator.submit_task(task).await.unwrap();
+        }
+        assert!(matches!(
+            orchestrator.submit_batch(batch(1)).await,
+            Err(SpiralError::QueueFull)
+        ));
+        assert_eq!(used().await, 2);
+    }
+
     /// Error path: Orchestrator handles task failures gracefully
     #[tokio::test]
     async fn test_orchestrator_agent_failure_recovery() {
@@ -323,6 +1294,36 @@ mod api_server_lifecycle {
 
         assert_eq!(response.status(), 400);
 
+        // Dry-run analysis validates content like submission does
+        let response = client
+            .post(format!(
+                "http://{}:{}/v1/analyze",
+                config.api.host, config.api.port
+            ))
+            .header("X-API-Key", "test-key-must-be-at-least-32-characters-long")
+            .json(&serde_json::json!({
+                "agent_type": "SoftwareDeveloper",
+                "content": "<script>alert('xss')</script>"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 400);
+
+        // Analysing a stored task needs the task to exist
+        let response = client
+            .post(format!(
+                "http://{}:{}/v1/tasks/missing-task/analyze",
+                config.api.host, config.api.port
+            ))
+            .header("X-API-Key", "test-key-must-be-at-least-32-characters-long")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 404);
+
         server_handle.abort();
         orchestrator.shutdown().await;
     }
@@ -411,6 +1412,7 @@ mod circuit_breaker_lifecycle {
             timeout_duration: Duration::from_millis(100),
             success_threshold: 1,
             failure_window: Duration::from_secs(60),
+            ..Default::default()
         });
 
         // Phase 1: Closed state (normal operation)
@@ -454,6 +1456,7 @@ mod circuit_breaker_lifecycle {
             timeout_duration: Duration::from_millis(50),
             success_threshold: 2,
             failure_window: Duration::from_secs(60),
+            ..Default::default()
         });
 
         // Simulate cascading failures
@@ -265,6 +265,7 @@ mod concurrency_intersection_pressure_tests {
             timeout_duration: Duration::from_millis(50),
             success_threshold: 2,
             failure_window: Duration::from_secs(1),
+            ..Default::default()
         })));
 
         // 🎯 PRECISION TARGET: Concurrent threads causing state transitions